@@ -6,14 +6,82 @@
 //! this module generates a donation plan that distributes the budget across
 //! projects according to the chosen allocation strategy.
 
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail};
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 
-use crate::project::UpstreamProject;
+use crate::config::{BudgetConfig, Cadence};
+use crate::discover::{InstallReason, InstalledPackage};
+use crate::enrich::{EnrichmentMap, deps_dev};
+use crate::project::{FundingChannel, FundingTier, UpstreamProject, canonicalize_url};
+
+/// Smallest donation worth making through a platform without a known
+/// minimum: fees take a large part of smaller ones, so small shares are
+/// saved up over several months instead.
+pub const MIN_DONATION: f64 = 5.0;
+
+/// Smallest donation by platform, where it differs from [`MIN_DONATION`]:
+/// platforms charging little or no fixed fee accept smaller ones. Set with
+/// `minimums` in the `[budget]` config section.
+const PLATFORM_MINIMUMS: &[(&str, f64)] = &[
+    ("GitHub Sponsors", 1.0),
+    ("Liberapay", 2.0),
+    ("Ko-fi", 3.0),
+    ("Buy Me a Coffee", 3.0),
+    ("Patreon", 3.0),
+];
+
+/// Longest a share is saved up before being donated.
+const MAX_MONTHS_BETWEEN_DONATIONS: u32 = 12;
+
+/// Share of the inverse-popularity strategy left to popular or company
+/// developed projects.
+const POPULAR_WEIGHT: f64 = 0.1;
+
+/// Companies whose projects the inverse-popularity strategy treats as
+/// backed, matched at the start of the organization name (lowercase).
+const CORPORATE_DEVELOPERS: &[&str] = &[
+    "adobe",
+    "amazon",
+    "apple",
+    "canonical",
+    "facebook",
+    "google",
+    "ibm",
+    "intel",
+    "jetbrains",
+    "meta",
+    "microsoft",
+    "mozilla corporation",
+    "nvidia",
+    "oracle",
+    "red hat",
+    "suse",
+    "valve",
+];
+
+/// Criticality the criticality strategy assumes at least, so that projects
+/// nothing is known to depend on still get a little.
+const MIN_CRITICALITY: f64 = 0.1;
+
+/// Weight of an explicitly installed package in the weighted strategy,
+/// where other packages weigh 1: the user chose it, not a dependency
+/// resolver.
+const EXPLICIT_INSTALL_WEIGHT: f64 = 2.0;
 
 /// A complete donation plan for a budget period.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DonationPlan {
+    /// Currency code of the allocation amounts
+    pub currency: String,
+
     pub allocations: Vec<Allocation>,
+
+    /// Problems with the pinned projects, e.g. fixed amounts over budget
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 /// A single allocation in a donation plan.
@@ -59,3 +127,1092 @@ pub struct DonationRecord {
     /// Free-form notes
     pub notes: Option<String>,
 }
+
+/// A budget of `amount` `currency` per `cadence` period, checking that the
+/// amount is positive and the currency an ISO 4217 code such as `EUR`.
+pub fn new_budget(amount: f64, currency: &str, cadence: Cadence) -> Result<BudgetConfig> {
+    check_amount(amount)?;
+    let currency = currency.trim().to_ascii_uppercase();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        bail!("The currency must be a three-letter code such as USD or EUR, got {currency:?}");
+    }
+    Ok(BudgetConfig {
+        amount: Some(amount),
+        currency,
+        cadence,
+        ..Default::default()
+    })
+}
+
+/// Check that `amount`, of a budget or a pinned donation, is positive.
+pub fn check_amount(amount: f64) -> Result<()> {
+    if !amount.is_finite() || amount <= 0.0 {
+        bail!("The amount must be a positive number, got {amount}");
+    }
+    Ok(())
+}
+
+/// The last day of the budget period containing `today`: the end of the
+/// month or of the year.
+pub fn period_end(cadence: Cadence, today: NaiveDate) -> NaiveDate {
+    let (year, month) = match cadence {
+        Cadence::Monthly if today.month() == 12 => (today.year() + 1, 1),
+        Cadence::Monthly => (today.year(), today.month() + 1),
+        Cadence::Yearly => (today.year() + 1, 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(today)
+}
+
+/// Days left in the budget period containing `today`, counting today.
+pub fn days_left(cadence: Cadence, today: NaiveDate) -> i64 {
+    (period_end(cadence, today) - today).num_days() + 1
+}
+
+/// Projects pinned with `syld budget pin`, with their fixed amount per
+/// budget period if any, and projects excluded with `syld budget exclude`,
+/// by canonical URL.
+#[derive(Debug, Default)]
+pub struct PlanOverrides {
+    pub pins: HashMap<String, Option<f64>>,
+    pub exclusions: HashSet<String>,
+}
+
+impl PlanOverrides {
+    /// Whether `project`, at canonical `url`, is pinned, and at which fixed
+    /// amount if any.
+    fn pin(&self, url: &str, project: &UpstreamProject) -> Option<Option<f64>> {
+        urls(url, project).find_map(|u| self.pins.get(&u).copied())
+    }
+
+    fn excludes(&self, url: &str, project: &UpstreamProject) -> bool {
+        urls(url, project).any(|u| self.exclusions.contains(&u))
+    }
+}
+
+/// The canonical URLs a project may be pinned or excluded by: its package
+/// URL, repository, or homepage.
+fn urls<'a>(url: &'a str, project: &'a UpstreamProject) -> impl Iterator<Item = String> + 'a {
+    std::iter::once(url.to_string()).chain(
+        [&project.repo_url, &project.homepage]
+            .into_iter()
+            .flatten()
+            .map(|u| canonicalize_url(u)),
+    )
+}
+
+/// Split `budget` equally between the `projects` (keyed by canonical URL)
+/// that have a funding channel, alphabetically.
+///
+/// Returns `None` when the budget has no amount.
+pub fn plan_equal(
+    budget: &BudgetConfig,
+    projects: &EnrichmentMap,
+    overrides: &PlanOverrides,
+) -> Option<DonationPlan> {
+    plan_by_weight(budget, projects, overrides, |_, _| {
+        Some((1.0, "equal share of the budget".to_string()))
+    })
+}
+
+/// Split `budget` between the `projects` (keyed by canonical URL) that have
+/// a funding channel, in proportion to the installed `packages` of each,
+/// explicitly installed ones counting double. Most used projects first.
+///
+/// Returns `None` when the budget has no amount.
+pub fn plan_weighted(
+    budget: &BudgetConfig,
+    packages: &[InstalledPackage],
+    projects: &EnrichmentMap,
+    overrides: &PlanOverrides,
+) -> Option<DonationPlan> {
+    // Installed and explicitly installed packages per canonical URL.
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    for pkg in packages {
+        if let Some(url) = &pkg.url {
+            let count = counts.entry(canonicalize_url(url)).or_default();
+            count.0 += 1;
+            if pkg.install_reason == InstallReason::Explicit {
+                count.1 += 1;
+            }
+        }
+    }
+    plan_by_weight(budget, projects, overrides, |url, _| {
+        let &(installed, explicit) = counts.get(url)?;
+        let weight = (installed - explicit) as f64 + explicit as f64 * EXPLICIT_INSTALL_WEIGHT;
+        Some((weight, usage_reason(installed, explicit)))
+    })
+}
+
+/// Split `budget` between the `projects` (keyed by canonical URL) that have
+/// a funding channel, giving more to those with fewer stars. Projects with
+/// at least `star_cutoff` stars in the budget config, or developed by a
+/// company, get a tenth of what their stars alone would give them. Smallest
+/// projects first.
+///
+/// Returns `None` when the budget has no amount.
+pub fn plan_inverse_popularity(
+    budget: &BudgetConfig,
+    projects: &EnrichmentMap,
+    overrides: &PlanOverrides,
+) -> Option<DonationPlan> {
+    plan_by_weight(budget, projects, overrides, |_, project| {
+        // Unknown star counts weigh as a project with a thousand stars.
+        let stars = project.stars.unwrap_or(1000);
+        let mut weight = 1.0 / (1.0 + (stars as f64).log10().max(0.0));
+        let corporate = corporate_developer(project);
+        let reason = if let Some(company) = corporate {
+            format!("developed by {company}")
+        } else if stars >= budget.star_cutoff {
+            format!("popular ({stars} stars)")
+        } else if project.stars.is_some() {
+            format!("small project ({stars} stars)")
+        } else {
+            "unknown popularity".to_string()
+        };
+        if corporate.is_some() || stars >= budget.star_cutoff {
+            weight *= POPULAR_WEIGHT;
+        }
+        Some((weight, reason))
+    })
+}
+
+/// Split `budget` between the `projects` (keyed by canonical URL) that have
+/// a funding channel in proportion to their criticality, or that of their
+/// dependent count where only that is known, so that infrastructure much of
+/// the ecosystem relies on gets the most. Projects of unknown or little
+/// criticality get the share of a [`MIN_CRITICALITY`] one.
+///
+/// Returns `None` when the budget has no amount.
+pub fn plan_criticality(
+    budget: &BudgetConfig,
+    projects: &EnrichmentMap,
+    overrides: &PlanOverrides,
+) -> Option<DonationPlan> {
+    plan_by_weight(budget, projects, overrides, |_, project| {
+        let score = project
+            .criticality
+            .or_else(|| project.dependents.map(deps_dev::criticality));
+        let reason = match (score, project.dependents) {
+            (Some(score), Some(dependents)) => {
+                format!("criticality {score:.2} ({dependents} dependents)")
+            }
+            (Some(score), None) => format!("criticality {score:.2}"),
+            (None, _) => "criticality unknown".to_string(),
+        };
+        Some((score.unwrap_or(0.0).max(MIN_CRITICALITY), reason))
+    })
+}
+
+/// Split `budget` between the `projects` (keyed by canonical URL) that have
+/// a funding channel and published funding goals, in proportion to the
+/// share of their most distant goal still missing. Projects that reached
+/// their goals, or whose progress towards them is unknown, are left out.
+///
+/// Returns `None` when the budget has no amount.
+pub fn plan_funding_gap(
+    budget: &BudgetConfig,
+    projects: &EnrichmentMap,
+    overrides: &PlanOverrides,
+) -> Option<DonationPlan> {
+    plan_by_weight(budget, projects, overrides, |_, project| {
+        let (status, missing) = project
+            .funding_status
+            .iter()
+            .filter_map(|s| Some((s, s.missing_share()?)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        let reason = format!(
+            "{:.0}% of its {} goal missing",
+            missing * 100.0,
+            status.platform
+        );
+        Some((missing, reason))
+    })
+}
+
+/// The company developing `project`, if its organization is a known one.
+fn corporate_developer(project: &UpstreamProject) -> Option<&str> {
+    let organization = project.organization.as_deref()?;
+    let lowercase = organization.to_lowercase();
+    CORPORATE_DEVELOPERS
+        .iter()
+        .any(|company| {
+            lowercase == *company
+                || lowercase
+                    .strip_prefix(company)
+                    .is_some_and(|rest| rest.starts_with([' ', ',']))
+        })
+        .then_some(organization)
+}
+
+/// Split `budget` between the `projects` that have a funding channel, in
+/// proportion to the weight `weigh` gives each with its reason, or leaving
+/// it out for `None`. The largest shares come first, then projects
+/// alphabetically.
+///
+/// Excluded projects are left out. Pinned projects come before the others,
+/// whether they have a funding channel or not, and even when not among
+/// `projects`: those with a fixed amount get it, and the rest of the budget
+/// is split; those without get at least the largest share. The plan warns
+/// when fixed amounts leave nothing to split.
+fn plan_by_weight(
+    budget: &BudgetConfig,
+    projects: &EnrichmentMap,
+    overrides: &PlanOverrides,
+    weigh: impl Fn(&str, &UpstreamProject) -> Option<(f64, String)>,
+) -> Option<DonationPlan> {
+    let amount = budget.amount?;
+    let mut warnings = Vec::new();
+
+    // Pinned projects the user does not use are only known by their URL.
+    let mut unused: Vec<(String, UpstreamProject)> = overrides
+        .pins
+        .keys()
+        .filter(|pin| {
+            !projects
+                .iter()
+                .any(|(url, project)| urls(url, project).any(|u| u == **pin))
+        })
+        .map(|pin| {
+            let project = UpstreamProject {
+                name: pin.clone(),
+                homepage: Some(format!("https://{pin}")),
+                ..Default::default()
+            };
+            (pin.clone(), project)
+        })
+        .collect();
+    unused.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut fixed: Vec<(&UpstreamProject, f64)> = Vec::new();
+    let mut pinned: Vec<(&UpstreamProject, f64)> = Vec::new();
+    let mut weighted: Vec<(&UpstreamProject, f64, String)> = Vec::new();
+    for (url, project) in projects.iter().chain(unused.iter().map(|(u, p)| (u, p))) {
+        if overrides.excludes(url, project) {
+            continue;
+        }
+        let weight = weigh(url, project);
+        match overrides.pin(url, project) {
+            Some(Some(amount)) => fixed.push((project, amount)),
+            Some(None) => pinned.push((project, weight.map_or(0.0, |(w, _)| w))),
+            None => {
+                if let Some((weight, reason)) = weight
+                    && weight > 0.0
+                    && !project.funding.is_empty()
+                {
+                    weighted.push((project, weight, reason));
+                }
+            }
+        }
+    }
+
+    let largest = weighted
+        .iter()
+        .map(|(_, weight, _)| *weight)
+        .reduce(f64::max)
+        .unwrap_or(1.0);
+    let by_name = |a: &UpstreamProject, b: &UpstreamProject| {
+        a.name.to_lowercase().cmp(&b.name.to_lowercase())
+    };
+    fixed.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| by_name(a.0, b.0)));
+    let mut shared: Vec<(&UpstreamProject, f64, Inclusion)> = pinned
+        .into_iter()
+        .map(|(project, weight)| (project, weight.max(largest), Inclusion::Pinned))
+        .collect();
+    shared.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| by_name(a.0, b.0)));
+    weighted.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| by_name(a.0, b.0)));
+    shared.extend(
+        weighted
+            .into_iter()
+            .map(|(project, weight, reason)| (project, weight, Inclusion::Weighted(reason))),
+    );
+
+    // Fixed amounts too small to ever reach the minimum donation are left
+    // out rather than sent below it.
+    fixed.retain(|(project, amount)| {
+        let reachable = months_to_reach(budget, project, per_month(*amount, budget.cadence));
+        if reachable.is_none() {
+            warnings.push(format!(
+                "{} is pinned at {amount:.2} {currency} {cadence}, which does not reach its \
+                 {minimum:.2} {currency} minimum donation within a year",
+                project.name,
+                currency = budget.currency,
+                cadence = cadence_label(budget.cadence),
+                minimum = project_minimum(budget, project),
+            ));
+        }
+        reachable.is_some()
+    });
+
+    let fixed_total: f64 = fixed.iter().map(|(_, amount)| amount).sum();
+    if fixed_total > amount {
+        warnings.push(format!(
+            "Pinned amounts add up to {fixed_total:.2} {currency} {cadence}, over the \
+             {amount:.2} {currency} budget",
+            currency = budget.currency,
+            cadence = cadence_label(budget.cadence),
+        ));
+    }
+    let remaining = per_month(amount - fixed_total, budget.cadence);
+    if remaining <= 0.0 && !shared.is_empty() {
+        warnings.push(format!(
+            "Pinned amounts take the whole budget, leaving nothing for {} other project(s)",
+            shared.len()
+        ));
+        shared.clear();
+    }
+    // Leave out the smallest shares that would not reach the minimum donation
+    // of their project within a year, giving them to the others. Pinned
+    // projects are only left out once no other share can be, with a warning.
+    loop {
+        let total: f64 = shared.iter().map(|(_, weight, _)| weight).sum();
+        let unreachable = shared
+            .iter()
+            .enumerate()
+            .filter(|(_, (project, weight, _))| {
+                months_to_reach(budget, project, remaining * weight / total).is_none()
+            })
+            .min_by(|a, b| {
+                let pinned = |share: &(&UpstreamProject, f64, Inclusion)| {
+                    matches!(share.2, Inclusion::Pinned)
+                };
+                pinned(a.1)
+                    .cmp(&pinned(b.1))
+                    .then_with(|| a.1.1.total_cmp(&b.1.1))
+            })
+            .map(|(i, _)| i);
+        let Some(i) = unreachable else {
+            break;
+        };
+        let (project, weight, inclusion) = shared.remove(i);
+        if let Inclusion::Pinned = inclusion {
+            warnings.push(format!(
+                "{} is pinned, but its share of {share:.2} {currency} per year does not reach \
+                 its {minimum:.2} {currency} minimum donation",
+                project.name,
+                share = cents(remaining * weight / total * 12.0),
+                currency = budget.currency,
+                minimum = project_minimum(budget, project),
+            ));
+        }
+    }
+
+    let total: f64 = shared.iter().map(|(_, weight, _)| weight).sum();
+    let allocations =
+        fixed
+            .into_iter()
+            .map(|(project, amount)| {
+                let share = per_month(amount, budget.cadence);
+                (project, share, Inclusion::Pinned)
+            })
+            .chain(shared.into_iter().map(|(project, weight, inclusion)| {
+                (project, remaining * weight / total, inclusion)
+            }))
+            .filter_map(|(project, share, inclusion)| {
+                allocate(budget, project, share, inclusion.reason())
+            })
+            .collect();
+    Some(DonationPlan {
+        currency: budget.currency.clone(),
+        allocations,
+        warnings,
+    })
+}
+
+/// Why a project gets a share of a donation plan.
+enum Inclusion {
+    /// Pinned with `syld budget pin`
+    Pinned,
+
+    /// Given a share by the strategy, for this reason
+    Weighted(String),
+}
+
+impl Inclusion {
+    fn reason(self) -> String {
+        match self {
+            Inclusion::Pinned => "pinned".to_string(),
+            Inclusion::Weighted(reason) => reason,
+        }
+    }
+}
+
+/// Why the weighted strategy gives a project its share, e.g. "3 installed
+/// packages, 1 explicitly".
+fn usage_reason(installed: usize, explicit: usize) -> String {
+    let packages = match installed {
+        1 => "1 installed package".to_string(),
+        n => format!("{n} installed packages"),
+    };
+    match (installed, explicit) {
+        (_, 0) => packages,
+        (1, 1) => "1 explicitly installed package".to_string(),
+        (_, e) => format!("{packages}, {e} explicitly"),
+    }
+}
+
+/// How often a `cadence` budget is given, e.g. "per month".
+pub fn cadence_label(cadence: Cadence) -> &'static str {
+    match cadence {
+        Cadence::Monthly => "per month",
+        Cadence::Yearly => "per year",
+    }
+}
+
+/// An `amount` per `cadence` period, spread over a month.
+fn per_month(amount: f64, cadence: Cadence) -> f64 {
+    match cadence {
+        Cadence::Monthly => amount,
+        Cadence::Yearly => amount / 12.0,
+    }
+}
+
+/// The allocation of `monthly_share` to `project`, donated through the
+/// funding channel with the smallest minimum donation, and saved up for as
+/// many months as it takes to reach it. `None` if a year is not enough.
+fn allocate(
+    budget: &BudgetConfig,
+    project: &UpstreamProject,
+    monthly_share: f64,
+    reason: String,
+) -> Option<Allocation> {
+    let every_n_months = months_to_reach(budget, project, monthly_share)?;
+    Some(Allocation {
+        project: project.clone(),
+        amount: cents(monthly_share * every_n_months as f64),
+        every_n_months,
+        via: cheapest_channel(budget, project).map(|c| c.url.clone()),
+        reason: Some(reason),
+    })
+}
+
+/// How many months `monthly_share` must be saved up to reach the minimum
+/// donation of `project`, or `None` if a year is not enough.
+fn months_to_reach(
+    budget: &BudgetConfig,
+    project: &UpstreamProject,
+    monthly_share: f64,
+) -> Option<u32> {
+    let minimum = project_minimum(budget, project);
+    (1..=MAX_MONTHS_BETWEEN_DONATIONS).find(|&n| cents(monthly_share * n as f64) >= minimum)
+}
+
+/// The smallest donation worth making to `project`: the minimum of its
+/// cheapest channel, or [`MIN_DONATION`] without any.
+fn project_minimum(budget: &BudgetConfig, project: &UpstreamProject) -> f64 {
+    cheapest_channel(budget, project)
+        .map_or(MIN_DONATION, |c| minimum_donation(budget, &c.platform))
+}
+
+/// The funding channel of `project` accepting the smallest donations, the
+/// first of them on a tie.
+fn cheapest_channel<'a>(
+    budget: &BudgetConfig,
+    project: &'a UpstreamProject,
+) -> Option<&'a FundingChannel> {
+    project.funding.iter().reduce(|cheapest, channel| {
+        if minimum_donation(budget, &channel.platform)
+            < minimum_donation(budget, &cheapest.platform)
+        {
+            channel
+        } else {
+            cheapest
+        }
+    })
+}
+
+/// The smallest donation worth making through `platform`: as configured, or
+/// else its default.
+pub fn minimum_donation(budget: &BudgetConfig, platform: &str) -> f64 {
+    budget.minimums.get(platform).copied().unwrap_or_else(|| {
+        PLATFORM_MINIMUMS
+            .iter()
+            .find(|(name, _)| *name == platform)
+            .map_or(MIN_DONATION, |(_, minimum)| *minimum)
+    })
+}
+
+/// `amount` rounded to cents.
+fn cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Suggest the sponsorship tier of `channel` that best matches an
+/// allocation: the most expensive tier not above `amount`, monthly when
+/// donating every month and one-time otherwise.
+///
+/// Returns `None` when the channel lists no tiers, or none is affordable.
+pub fn suggest_tier(
+    channel: &FundingChannel,
+    amount: f64,
+    every_n_months: u32,
+) -> Option<&FundingTier> {
+    let one_time = every_n_months != 1;
+    channel
+        .page
+        .as_ref()?
+        .tiers
+        .iter()
+        .filter(|tier| tier.one_time == one_time && tier.amount as f64 <= amount)
+        .max_by_key(|tier| tier.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::FundingPage;
+
+    fn tier(amount: u64, one_time: bool) -> FundingTier {
+        FundingTier {
+            name: format!("${amount}"),
+            amount,
+            one_time,
+        }
+    }
+
+    #[test]
+    fn suggests_closest_affordable_tier() {
+        let channel = FundingChannel {
+            platform: "GitHub Sponsors".to_string(),
+            url: "https://github.com/sponsors/octocat".to_string(),
+            page: Some(FundingPage {
+                title: None,
+                goal: None,
+                tiers: vec![
+                    tier(5, false),
+                    tier(25, false),
+                    tier(10, true),
+                    tier(50, true),
+                ],
+            }),
+        };
+
+        assert_eq!(suggest_tier(&channel, 20.0, 1), Some(&tier(5, false)));
+        assert_eq!(suggest_tier(&channel, 25.0, 1), Some(&tier(25, false)));
+        assert_eq!(suggest_tier(&channel, 30.0, 6), Some(&tier(10, true)));
+        assert_eq!(suggest_tier(&channel, 3.0, 1), None);
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn validates_budgets() {
+        let budget = new_budget(20.0, " eur", Cadence::Yearly).unwrap();
+        assert_eq!(budget.amount, Some(20.0));
+        assert_eq!(budget.currency, "EUR");
+
+        assert!(new_budget(0.0, "USD", Cadence::Monthly).is_err());
+        assert!(new_budget(-5.0, "USD", Cadence::Monthly).is_err());
+        assert!(new_budget(f64::NAN, "USD", Cadence::Monthly).is_err());
+        assert!(new_budget(5.0, "dollars", Cadence::Monthly).is_err());
+        assert!(new_budget(5.0, "U$D", Cadence::Monthly).is_err());
+    }
+
+    #[test]
+    fn time_left_in_period() {
+        assert_eq!(
+            period_end(Cadence::Monthly, date(2026, 2, 10)),
+            date(2026, 2, 28)
+        );
+        assert_eq!(
+            period_end(Cadence::Monthly, date(2026, 12, 31)),
+            date(2026, 12, 31)
+        );
+        assert_eq!(
+            period_end(Cadence::Yearly, date(2026, 10, 16)),
+            date(2026, 12, 31)
+        );
+        assert_eq!(days_left(Cadence::Monthly, date(2026, 10, 16)), 16);
+        assert_eq!(days_left(Cadence::Monthly, date(2026, 10, 31)), 1);
+        assert_eq!(days_left(Cadence::Yearly, date(2026, 12, 1)), 31);
+    }
+
+    fn project(name: &str, funding: &[&str]) -> UpstreamProject {
+        UpstreamProject {
+            name: name.to_string(),
+            repo_url: Some(format!("https://github.com/{name}/{name}")),
+            funding: funding
+                .iter()
+                .map(|u| FundingChannel::from_url(u))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// `projects` keyed by the canonical URL of their repository.
+    fn keyed(projects: Vec<UpstreamProject>) -> EnrichmentMap {
+        projects
+            .into_iter()
+            .map(|p| (canonicalize_url(p.repo_url.as_deref().unwrap()), p))
+            .collect()
+    }
+
+    fn budget(amount: f64, cadence: Cadence) -> BudgetConfig {
+        new_budget(amount, "EUR", cadence).unwrap()
+    }
+
+    #[test]
+    fn equal_plan_splits_between_funded_projects() {
+        let projects = keyed(vec![
+            project("vim", &["https://github.com/sponsors/vim"]),
+            project("curl", &["https://opencollective.com/curl"]),
+            project("unfunded", &[]),
+        ]);
+
+        let plan = plan_equal(
+            &budget(20.0, Cadence::Monthly),
+            &projects,
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.currency, "EUR");
+        let names: Vec<&str> = plan
+            .allocations
+            .iter()
+            .map(|a| a.project.name.as_str())
+            .collect();
+        assert_eq!(names, ["curl", "vim"]);
+        assert_eq!(plan.allocations[0].amount, 10.0);
+        assert_eq!(plan.allocations[0].every_n_months, 1);
+        assert_eq!(
+            plan.allocations[0].via.as_deref(),
+            Some("https://opencollective.com/curl")
+        );
+    }
+
+    #[test]
+    fn platform_minimums() {
+        let mut configured = budget(24.0, Cadence::Yearly);
+        assert_eq!(minimum_donation(&configured, "GitHub Sponsors"), 1.0);
+        assert_eq!(minimum_donation(&configured, "Custom"), MIN_DONATION);
+        configured
+            .minimums
+            .insert("GitHub Sponsors".to_string(), 4.0);
+        assert_eq!(minimum_donation(&configured, "GitHub Sponsors"), 4.0);
+
+        // Donate through the channel with the smallest minimum.
+        let projects = keyed(vec![project(
+            "curl",
+            &[
+                "https://opencollective.com/curl",
+                "https://liberapay.com/curl",
+            ],
+        )]);
+        let plan = plan_equal(&configured, &projects, &PlanOverrides::default()).unwrap();
+        assert_eq!(
+            plan.allocations[0].via.as_deref(),
+            Some("https://liberapay.com/curl")
+        );
+        assert_eq!(plan.allocations[0].every_n_months, 1);
+        assert_eq!(plan.allocations[0].amount, 2.0);
+
+        // 0.40 a month each: unpinned projects that cannot reach their
+        // minimum within a year are left out, pinned ones are kept.
+        let projects = keyed(vec![
+            project("curl", &["https://opencollective.com/curl"]),
+            project("vim", &["https://github.com/sponsors/vim"]),
+            project("zlib", &["https://opencollective.com/zlib"]),
+        ]);
+        let overrides = PlanOverrides {
+            pins: HashMap::from([("github.com/zlib/zlib".to_string(), None)]),
+            exclusions: HashSet::new(),
+        };
+        let plan = plan_equal(&budget(14.4, Cadence::Yearly), &projects, &overrides).unwrap();
+        let allocations: Vec<(&str, f64, u32)> = plan
+            .allocations
+            .iter()
+            .map(|a| (a.project.name.as_str(), a.amount, a.every_n_months))
+            .collect();
+        assert_eq!(allocations, [("zlib", 5.4, 9), ("vim", 1.2, 2)]);
+    }
+
+    #[test]
+    fn small_shares_are_saved_up() {
+        let projects = keyed(vec![
+            project("curl", &["https://opencollective.com/curl"]),
+            project("vim", &["https://github.com/sponsors/vim"]),
+        ]);
+
+        // 1.50 a month each: donate 6.00 every four months.
+        let plan = plan_equal(
+            &budget(36.0, Cadence::Yearly),
+            &projects,
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.allocations[0].every_n_months, 4);
+        assert_eq!(plan.allocations[0].amount, 6.0);
+
+        // Never more than a year apart: curl would get 1.00 a year, below
+        // the Open Collective minimum, so vim gets it all.
+        let plan = plan_equal(
+            &budget(2.0, Cadence::Yearly),
+            &projects,
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.allocations.len(), 1);
+        assert_eq!(plan.allocations[0].project.name, "vim");
+        assert_eq!(plan.allocations[0].every_n_months, 6);
+        assert_eq!(plan.allocations[0].amount, 1.0);
+    }
+
+    #[test]
+    fn unreachable_pins_are_reported() {
+        let projects = keyed(vec![
+            project("curl", &["https://opencollective.com/curl"]),
+            project("vim", &["https://github.com/sponsors/vim"]),
+        ]);
+
+        // 4.80 a year never reaches the Open Collective minimum.
+        let fixed = PlanOverrides {
+            pins: HashMap::from([("github.com/curl/curl".to_string(), Some(0.4))]),
+            exclusions: HashSet::new(),
+        };
+        let plan = plan_equal(&budget(10.0, Cadence::Monthly), &projects, &fixed).unwrap();
+        assert_eq!(plan.allocations.len(), 1);
+        assert_eq!(plan.allocations[0].project.name, "vim");
+        assert_eq!(plan.allocations[0].amount, 10.0);
+        assert_eq!(
+            plan.warnings,
+            [
+                "curl is pinned at 0.40 EUR per month, which does not reach its 5.00 EUR \
+              minimum donation within a year"
+            ]
+        );
+
+        // Neither does a 1.00 share a year.
+        let shared = PlanOverrides {
+            pins: HashMap::from([("github.com/curl/curl".to_string(), None)]),
+            exclusions: HashSet::new(),
+        };
+        let plan = plan_equal(&budget(2.0, Cadence::Yearly), &projects, &shared).unwrap();
+        let allocations: Vec<(&str, f64, u32)> = plan
+            .allocations
+            .iter()
+            .map(|a| (a.project.name.as_str(), a.amount, a.every_n_months))
+            .collect();
+        assert_eq!(allocations, [("vim", 1.0, 6)]);
+        assert_eq!(
+            plan.warnings,
+            [
+                "curl is pinned, but its share of 1.00 EUR per year does not reach its 5.00 EUR \
+              minimum donation"
+            ]
+        );
+    }
+
+    #[test]
+    fn pinned_and_excluded_projects() {
+        let projects = keyed(vec![
+            project("curl", &["https://opencollective.com/curl"]),
+            project("vim", &["https://github.com/sponsors/vim"]),
+            project("zlib", &["https://github.com/sponsors/madler"]),
+            project("unfunded", &[]),
+        ]);
+        let overrides = PlanOverrides {
+            pins: HashMap::from([
+                (canonicalize_url("https://github.com/curl/curl"), Some(60.0)),
+                (
+                    canonicalize_url("https://github.com/unfunded/unfunded"),
+                    None,
+                ),
+            ]),
+            exclusions: HashSet::from([canonicalize_url("https://github.com/zlib/zlib")]),
+        };
+
+        // 60.00 a year for curl, the 120.00 left split between vim and the
+        // pinned project without an amount.
+        let plan = plan_equal(&budget(180.0, Cadence::Yearly), &projects, &overrides).unwrap();
+        let shares: Vec<(&str, f64, u32, Option<&str>)> = plan
+            .allocations
+            .iter()
+            .map(|a| {
+                (
+                    a.project.name.as_str(),
+                    a.amount,
+                    a.every_n_months,
+                    a.reason.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            shares,
+            [
+                ("curl", 5.0, 1, Some("pinned")),
+                ("unfunded", 5.0, 1, Some("pinned")),
+                ("vim", 5.0, 1, Some("equal share of the budget")),
+            ]
+        );
+        assert_eq!(plan.allocations[1].via, None);
+        assert!(plan.warnings.is_empty());
+
+        // Pins taking the whole budget leave nothing for the others.
+        let plan = plan_equal(&budget(50.0, Cadence::Yearly), &projects, &overrides).unwrap();
+        assert_eq!(plan.allocations.len(), 1);
+        assert_eq!(plan.allocations[0].project.name, "curl");
+        assert_eq!(
+            plan.warnings,
+            [
+                "Pinned amounts add up to 60.00 EUR per year, over the 50.00 EUR budget",
+                "Pinned amounts take the whole budget, leaving nothing for 2 other project(s)",
+            ]
+        );
+    }
+
+    #[test]
+    fn pinned_projects_not_in_use() {
+        let projects = keyed(vec![project("vim", &["https://github.com/sponsors/vim"])]);
+        let overrides = PlanOverrides {
+            pins: HashMap::from([("curl.se".to_string(), Some(5.0))]),
+            exclusions: HashSet::new(),
+        };
+
+        let plan = plan_equal(&budget(10.0, Cadence::Monthly), &projects, &overrides).unwrap();
+        let shares: Vec<(&str, f64)> = plan
+            .allocations
+            .iter()
+            .map(|a| (a.project.name.as_str(), a.amount))
+            .collect();
+        assert_eq!(shares, [("curl.se", 5.0), ("vim", 5.0)]);
+        assert_eq!(
+            plan.allocations[0].project.homepage.as_deref(),
+            Some("https://curl.se")
+        );
+    }
+
+    #[test]
+    fn no_plan_without_amount() {
+        let projects = keyed(vec![project("curl", &["https://opencollective.com/curl"])]);
+        assert!(
+            plan_equal(
+                &BudgetConfig::default(),
+                &projects,
+                &PlanOverrides::default()
+            )
+            .is_none()
+        );
+        let plan = plan_equal(
+            &budget(10.0, Cadence::Monthly),
+            &EnrichmentMap::new(),
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        assert!(plan.allocations.is_empty());
+    }
+
+    fn package(name: &str, url: &str, install_reason: InstallReason) -> InstalledPackage {
+        InstalledPackage {
+            name: name.to_string(),
+            version: "1.0".to_string(),
+            description: None,
+            url: Some(url.to_string()),
+            source: crate::discover::PackageSource::Pacman,
+            licenses: vec![],
+            funding: vec![],
+            install_reason,
+            maintainer: None,
+        }
+    }
+
+    #[test]
+    fn weighted_plan_favours_most_used_projects() {
+        let packages = vec![
+            package("curl", "https://curl.se", InstallReason::Explicit),
+            package("libcurl", "https://curl.se/", InstallReason::Dependency),
+            package("vim", "https://www.vim.org", InstallReason::Dependency),
+            package("zlib", "https://zlib.net", InstallReason::Explicit),
+        ];
+        let projects = EnrichmentMap::from([
+            (
+                canonicalize_url("https://curl.se"),
+                project("curl", &["https://opencollective.com/curl"]),
+            ),
+            (
+                canonicalize_url("https://www.vim.org"),
+                project("vim", &["https://github.com/sponsors/vim"]),
+            ),
+            (canonicalize_url("https://zlib.net"), project("zlib", &[])),
+        ]);
+
+        // curl weighs 3 (one explicit, one dependency), vim 1.
+        let plan = plan_weighted(
+            &budget(40.0, Cadence::Monthly),
+            &packages,
+            &projects,
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.allocations.len(), 2);
+        assert_eq!(plan.allocations[0].project.name, "curl");
+        assert_eq!(plan.allocations[0].amount, 30.0);
+        assert_eq!(
+            plan.allocations[0].reason.as_deref(),
+            Some("2 installed packages, 1 explicitly")
+        );
+        assert_eq!(plan.allocations[1].project.name, "vim");
+        assert_eq!(plan.allocations[1].amount, 10.0);
+        assert_eq!(
+            plan.allocations[1].reason.as_deref(),
+            Some("1 installed package")
+        );
+    }
+
+    fn starred(name: &str, stars: Option<u64>, organization: Option<&str>) -> UpstreamProject {
+        UpstreamProject {
+            stars,
+            organization: organization.map(str::to_string),
+            ..project(name, &[&format!("https://opencollective.com/{name}")])
+        }
+    }
+
+    #[test]
+    fn inverse_popularity_favours_small_projects() {
+        let projects = keyed(vec![
+            starred("tiny", Some(10), None),
+            starred("mid", Some(1000), None),
+            starred("huge", Some(100_000), None),
+            starred("chrome", Some(100), Some("Google")),
+        ]);
+        let mut budget = budget(100.0, Cadence::Monthly);
+        budget.star_cutoff = 10_000;
+
+        let plan = plan_inverse_popularity(&budget, &projects, &PlanOverrides::default()).unwrap();
+        let names: Vec<&str> = plan
+            .allocations
+            .iter()
+            .map(|a| a.project.name.as_str())
+            .collect();
+        assert_eq!(names, ["tiny", "mid", "chrome", "huge"]);
+        assert_eq!(
+            plan.allocations[0].reason.as_deref(),
+            Some("small project (10 stars)")
+        );
+        assert_eq!(
+            plan.allocations[2].reason.as_deref(),
+            Some("developed by Google")
+        );
+        assert_eq!(
+            plan.allocations[3].reason.as_deref(),
+            Some("popular (100000 stars)")
+        );
+        // tiny weighs 1/2, mid 1/4, chrome 1/30, huge 1/60.
+        assert_eq!(plan.allocations[0].amount, 62.5);
+        // 2.08 a month for huge, saved up for three months.
+        assert_eq!(plan.allocations[3].every_n_months, 3);
+        assert_eq!(plan.allocations[3].amount, 6.25);
+    }
+
+    #[test]
+    fn criticality_favours_infrastructure() {
+        let critical = |name: &str, criticality, dependents| UpstreamProject {
+            criticality,
+            dependents,
+            ..project(name, &[&format!("https://opencollective.com/{name}")])
+        };
+        let projects = keyed(vec![
+            critical("app", None, None),
+            critical("zlib", Some(0.9), Some(200_000)),
+            critical("leaf", Some(0.0), Some(0)),
+            critical("libfoo", None, Some(5000)),
+        ]);
+
+        let plan = plan_criticality(
+            &budget(120.0, Cadence::Monthly),
+            &projects,
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        let names: Vec<&str> = plan
+            .allocations
+            .iter()
+            .map(|a| a.project.name.as_str())
+            .collect();
+        assert_eq!(names, ["zlib", "libfoo", "app", "leaf"]);
+        assert_eq!(
+            plan.allocations[0].reason.as_deref(),
+            Some("criticality 0.90 (200000 dependents)")
+        );
+        assert_eq!(
+            plan.allocations[2].reason.as_deref(),
+            Some("criticality unknown")
+        );
+        // Unknown and zero criticality get the same minimal share.
+        assert_eq!(plan.allocations[2].amount, plan.allocations[3].amount);
+        assert!(plan.allocations[0].amount > plan.allocations[1].amount);
+    }
+
+    #[test]
+    fn funding_gap_skips_funded_projects() {
+        use crate::project::{FundingGoal, FundingStatus, GoalKind};
+
+        let raising = |name: &str, yearly_income: Option<f64>| UpstreamProject {
+            funding_status: vec![FundingStatus {
+                platform: "Liberapay".to_string(),
+                currency: "EUR".to_string(),
+                balance: None,
+                yearly_income,
+                goals: vec![FundingGoal {
+                    title: None,
+                    amount: 10000.0,
+                    kind: GoalKind::YearlyIncome,
+                }],
+            }],
+            ..project(name, &[&format!("https://liberapay.com/{name}")])
+        };
+        let projects = keyed(vec![
+            raising("funded", Some(12000.0)),
+            raising("halfway", Some(5000.0)),
+            raising("starting", Some(0.0)),
+            raising("unknown", None),
+            project("nogoal", &["https://liberapay.com/nogoal"]),
+        ]);
+
+        let plan = plan_funding_gap(
+            &budget(30.0, Cadence::Monthly),
+            &projects,
+            &PlanOverrides::default(),
+        )
+        .unwrap();
+        let names: Vec<&str> = plan
+            .allocations
+            .iter()
+            .map(|a| a.project.name.as_str())
+            .collect();
+        assert_eq!(names, ["starting", "halfway"]);
+        assert_eq!(plan.allocations[0].amount, 20.0);
+        assert_eq!(plan.allocations[1].amount, 10.0);
+        assert_eq!(
+            plan.allocations[1].reason.as_deref(),
+            Some("50% of its Liberapay goal missing")
+        );
+    }
+
+    #[test]
+    fn corporate_developers() {
+        let developer = |organization| {
+            corporate_developer(&starred("x", None, Some(organization))).map(str::to_string)
+        };
+        assert_eq!(developer("Microsoft").as_deref(), Some("Microsoft"));
+        assert_eq!(developer("Red Hat, Inc.").as_deref(), Some("Red Hat, Inc."));
+        assert_eq!(developer("Intelligent Systems"), None);
+        assert_eq!(developer("KDE"), None);
+        assert_eq!(corporate_developer(&starred("x", None, None)), None);
+    }
+
+    #[test]
+    fn usage_reasons() {
+        assert_eq!(usage_reason(1, 1), "1 explicitly installed package");
+        assert_eq!(usage_reason(4, 0), "4 installed packages");
+        assert_eq!(usage_reason(4, 2), "4 installed packages, 2 explicitly");
+    }
+
+    #[test]
+    fn no_tiers_without_page() {
+        let channel = FundingChannel::from_url("https://github.com/sponsors/octocat");
+        assert_eq!(suggest_tier(&channel, 100.0, 1), None);
+    }
+}
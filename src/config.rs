@@ -1,11 +1,12 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Config {
@@ -16,9 +17,254 @@ pub struct Config {
     /// Whether to enable network-based enrichment by default
     #[serde(default)]
     pub enrich: bool,
+
+    /// Never access the network: enrichment serves cached data and runs
+    /// offline backends only, and network lookups during discovery and
+    /// contribution discovery are skipped. Also set by `--offline`.
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Opt-in discovery sources
+    #[serde(default)]
+    pub discover: DiscoverConfig,
+
+    /// GitHub API access
+    #[serde(default)]
+    pub github: GitHubConfig,
+
+    /// Mastodon account used by `syld share --mastodon`
+    #[serde(default)]
+    pub mastodon: MastodonConfig,
+
+    /// Which enrichment backends run, and in what order
+    #[serde(default)]
+    pub enrichment: EnrichmentConfig,
+
+    /// Issue labels suggested to experienced contributors, per forge
+    #[serde(default)]
+    pub issue_labels: IssueLabelsConfig,
+
+    /// Languages the user can translate into
+    #[serde(default)]
+    pub translation: TranslationConfig,
+
+    /// How `syld digest` delivers new contribution opportunities
+    #[serde(default)]
+    pub digest: DigestConfig,
+
+    /// Skills and interests that contribution opportunities must match, and
+    /// whether to look up past contributions
+    #[serde(default)]
+    pub contribute: ContributeConfig,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DiscoverConfig {
+    /// Development directories scanned for project lockfiles. Empty (the
+    /// default) disables lockfile discovery. A leading `~` is expanded to the
+    /// home directory.
+    #[serde(default)]
+    pub project_dirs: Vec<PathBuf>,
+
+    /// Report the images of pods and the Helm charts running in the current
+    /// kubeconfig context. Since it talks to the cluster, it is skipped when
+    /// offline.
+    #[serde(default)]
+    pub kubernetes: bool,
+
+    /// Scan the packages installed inside distrobox/toolbox containers.
+    /// Entering a container starts it if it is stopped.
+    #[serde(default)]
+    pub container_packages: bool,
+
+    /// Report F-Droid apps installed on Android devices connected via adb.
+    /// Downloads the F-Droid index to resolve their source repositories, so
+    /// it is skipped when offline.
+    #[serde(default)]
+    pub android: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GitHubConfig {
+    /// Personal access token for the GitHub API. Falls back to the
+    /// `GITHUB_TOKEN` environment variable; without either, requests are
+    /// anonymous and subject to a much lower rate limit.
+    #[serde(serialize_with = "redacted")]
+    pub token: Option<String>,
+}
+
+/// Serialize a secret as `"<redacted>"`, so that the output of `syld config
+/// show` can be shared in bug reports. Only the default config, which holds
+/// no secrets, is ever written back to disk.
+fn redacted<S: Serializer>(secret: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    match secret {
+        Some(_) => serializer.serialize_some("<redacted>"),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MastodonConfig {
+    /// Instance to post to, e.g. `mastodon.social`.
+    pub instance: Option<String>,
+
+    /// Access token with the `write:statuses` scope. Falls back to the
+    /// `MASTODON_TOKEN` environment variable.
+    #[serde(serialize_with = "redacted")]
+    pub token: Option<String>,
+}
+
+/// Enrichment backends are named as in the "Enrichment backends" line that
+/// `syld report --enrich` prints, e.g. `github` or `wikidata`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EnrichmentConfig {
+    /// Backends to run. Empty (the default) runs every available backend.
+    #[serde(default)]
+    pub enabled: Vec<String>,
+
+    /// Backends never to run, e.g. `["github"]` on a metered connection.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+
+    /// Backends whose data wins when sources disagree, most trusted first.
+    /// They run before the others, which keep their default order.
+    #[serde(default)]
+    pub priority: Vec<String>,
+}
+
+impl EnrichmentConfig {
+    /// Whether the backend named `name` may run.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        (self.enabled.is_empty() || self.enabled.iter().any(|b| b == name))
+            && !self.disabled.iter().any(|b| b == name)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TranslationConfig {
+    /// Language codes as used by Weblate, e.g. `["de", "pt_BR"]`. Empty (the
+    /// default) uses the language of the `LANG` environment variable, unless
+    /// it is English.
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+/// Narrows contribution opportunities down to what the user can and wants to
+/// work on. Empty lists (the default) match everything.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ContributeConfig {
+    /// Programming languages the user writes, e.g. `["Python", "Rust"]`,
+    /// matched against the main language of the repository of issues.
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Topics the user is interested in, e.g. `["graphics", "networking"]`,
+    /// matched against the topics of the repository.
+    #[serde(default)]
+    pub topics: Vec<String>,
+
+    /// Leave out opportunities harder than this.
+    pub max_difficulty: Option<Difficulty>,
+
+    /// Repository of the user's distribution on Repology, e.g. `debian_12`,
+    /// for suggesting packaging work. Detected from `/etc/os-release` when
+    /// unset.
+    pub distro_repo: Option<String>,
+
+    /// Search GitHub for the issues and pull requests opened by the owner of
+    /// the GitHub token, to count the dependencies they contributed to. Off
+    /// by default, since the search is tied to the user's identity.
+    #[serde(default)]
+    pub past_contributions: bool,
+}
+
+/// How much work and experience a contribution takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct DigestConfig {
+    /// Address to email digests to, through the local `sendmail`. Unset (the
+    /// default) sends no email.
+    pub email: Option<String>,
+
+    /// Show digests as desktop notifications, through `notify-send`.
+    #[serde(default = "default_true")]
+    pub desktop: bool,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            email: None,
+            desktop: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Labels of the issues that the `help_wanted_issues` contribution backend
+/// suggests: issues the maintainers want help with, bugs, and issues they
+/// flagged as a priority. An issue carrying any of the labels of its forge is
+/// suggested; labels are matched case-insensitively.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueLabelsConfig {
+    /// Labels on GitHub repositories.
+    #[serde(default = "default_github_labels")]
+    pub github: Vec<String>,
+
+    /// Labels on GitLab projects, including scoped labels such as
+    /// `priority::high`.
+    #[serde(default = "default_gitlab_labels")]
+    pub gitlab: Vec<String>,
+
+    /// Labels on Gitea and Forgejo repositories, including scoped labels
+    /// such as `Priority/High`.
+    #[serde(default = "default_gitea_labels")]
+    pub gitea: Vec<String>,
+}
+
+impl Default for IssueLabelsConfig {
+    fn default() -> Self {
+        Self {
+            github: default_github_labels(),
+            gitlab: default_gitlab_labels(),
+            gitea: default_gitea_labels(),
+        }
+    }
+}
+
+fn default_github_labels() -> Vec<String> {
+    labels(&["help wanted", "bug", "priority: high", "priority: critical"])
+}
+
+fn default_gitlab_labels() -> Vec<String> {
+    labels(&["help wanted", "bug", "priority::high", "priority::critical"])
+}
+
+/// Gitea's "Advanced" label set scopes kinds and priorities.
+fn default_gitea_labels() -> Vec<String> {
+    labels(&[
+        "help wanted",
+        "Kind/Bug",
+        "Priority/High",
+        "Priority/Critical",
+    ])
+}
+
+fn labels(names: &[&str]) -> Vec<String> {
+    names.iter().map(|l| l.to_string()).collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BudgetConfig {
     /// Monthly budget amount (in user's currency)
     pub amount: Option<f64>,
@@ -30,6 +276,16 @@ pub struct BudgetConfig {
     /// Budget cadence
     #[serde(default)]
     pub cadence: Cadence,
+
+    /// Star count from which the inverse-popularity strategy considers a
+    /// project popular enough to need little of the budget
+    #[serde(default = "default_star_cutoff")]
+    pub star_cutoff: u64,
+
+    /// Smallest donation worth making, by funding platform (e.g.
+    /// "Open Collective"), overriding the defaults
+    #[serde(default)]
+    pub minimums: BTreeMap<String, f64>,
 }
 
 impl Default for BudgetConfig {
@@ -38,11 +294,13 @@ impl Default for BudgetConfig {
             amount: None,
             currency: default_currency(),
             cadence: Cadence::default(),
+            star_cutoff: default_star_cutoff(),
+            minimums: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Cadence {
     #[default]
@@ -54,6 +312,10 @@ fn default_currency() -> String {
     "USD".to_string()
 }
 
+fn default_star_cutoff() -> u64 {
+    10_000
+}
+
 impl Config {
     /// Load configuration from XDG config directory.
     /// Returns default config if the file doesn't exist yet.
@@ -102,17 +364,101 @@ mod tests {
     fn parse_full_config() {
         let toml = r#"
 enrich = true
+offline = true
 
 [budget]
 amount = 10.0
 currency = "EUR"
 cadence = "yearly"
+star_cutoff = 5000
+
+[budget.minimums]
+"Open Collective" = 10.0
 "#;
         let config: Config = toml::from_str(toml).unwrap();
         assert!(config.enrich);
+        assert!(config.offline);
         assert_eq!(config.budget.amount, Some(10.0));
         assert_eq!(config.budget.currency, "EUR");
         assert!(matches!(config.budget.cadence, Cadence::Yearly));
+        assert_eq!(config.budget.star_cutoff, 5000);
+        assert_eq!(config.budget.minimums["Open Collective"], 10.0);
+    }
+
+    #[test]
+    fn parse_discover_config() {
+        let toml = r#"
+[discover]
+project_dirs = ["~/src", "/work"]
+kubernetes = true
+container_packages = true
+android = true
+
+[github]
+token = "ghp_example"
+
+[mastodon]
+instance = "mastodon.social"
+
+[enrichment]
+disabled = ["github"]
+priority = ["distro", "wikidata"]
+
+[issue_labels]
+github = ["help wanted", "P1"]
+
+[translation]
+languages = ["de", "pt_BR"]
+
+[digest]
+email = "me@example.com"
+
+[contribute]
+languages = ["Python"]
+max_difficulty = "medium"
+"#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.discover.project_dirs,
+            vec![PathBuf::from("~/src"), PathBuf::from("/work")]
+        );
+        assert!(config.discover.kubernetes);
+        assert!(config.discover.container_packages);
+        assert!(config.discover.android);
+        assert_eq!(config.github.token.as_deref(), Some("ghp_example"));
+        assert_eq!(config.mastodon.instance.as_deref(), Some("mastodon.social"));
+        assert_eq!(config.mastodon.token, None);
+        assert_eq!(config.enrichment.disabled, vec!["github"]);
+        assert_eq!(config.enrichment.priority, vec!["distro", "wikidata"]);
+        assert_eq!(config.issue_labels.github, vec!["help wanted", "P1"]);
+        // Forges left out keep their default labels.
+        assert!(config.issue_labels.gitlab.contains(&"bug".to_string()));
+        assert_eq!(config.translation.languages, vec!["de", "pt_BR"]);
+        assert_eq!(config.digest.email.as_deref(), Some("me@example.com"));
+        assert!(config.digest.desktop);
+        assert_eq!(config.contribute.languages, vec!["Python"]);
+        assert!(config.contribute.topics.is_empty());
+        assert_eq!(config.contribute.max_difficulty, Some(Difficulty::Medium));
+    }
+
+    #[test]
+    fn enrichment_backend_selection() {
+        let all = EnrichmentConfig::default();
+        assert!(all.is_enabled("github"));
+
+        let without_github = EnrichmentConfig {
+            disabled: vec!["github".to_string()],
+            ..Default::default()
+        };
+        assert!(!without_github.is_enabled("github"));
+        assert!(without_github.is_enabled("wikidata"));
+
+        let offline = EnrichmentConfig {
+            enabled: vec!["distro".to_string(), "appstream".to_string()],
+            ..Default::default()
+        };
+        assert!(offline.is_enabled("distro"));
+        assert!(!offline.is_enabled("github"));
     }
 
     #[test]
@@ -122,6 +468,15 @@ cadence = "yearly"
         assert_eq!(config.budget.amount, None);
         assert_eq!(config.budget.currency, "USD");
         assert!(matches!(config.budget.cadence, Cadence::Monthly));
+        assert!(config.discover.project_dirs.is_empty());
+        assert!(!config.discover.kubernetes);
+        assert!(!config.discover.container_packages);
+        assert!(!config.discover.android);
+        assert_eq!(config.github.token, None);
+        assert!(config.enrichment.enabled.is_empty());
+        assert!(config.enrichment.disabled.is_empty());
+        assert!(config.enrichment.priority.is_empty());
+        assert_eq!(config.issue_labels.gitea, default_gitea_labels());
     }
 
     #[test]
@@ -135,6 +490,7 @@ amount = 5.0
         assert_eq!(config.budget.amount, Some(5.0));
         assert_eq!(config.budget.currency, "USD");
         assert!(matches!(config.budget.cadence, Cadence::Monthly));
+        assert_eq!(config.budget.star_cutoff, 10_000);
     }
 
     #[test]
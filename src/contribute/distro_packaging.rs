@@ -0,0 +1,238 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Distribution packaging contribution backend.
+//!
+//! Distributions rely on volunteers to package projects and keep the
+//! packages up to date. Using Repology, this backend finds the projects that
+//! other distributions package but the user's does not, and those whose
+//! package there is outdated. The distribution is detected from
+//! `/etc/os-release`, or set with `distro_repo` in the `[contribute]` config
+//! section, as named on Repology (e.g. `debian_12`).
+
+use std::fs;
+
+use anyhow::Result;
+
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::config::Config;
+use crate::enrich::repology::{self, RepologyPackage};
+use crate::project::UpstreamProject;
+
+/// Backend that suggests packaging projects for the user's distribution.
+pub struct DistroPackagingBackend {
+    /// Repology repository of the user's distribution, if known.
+    pub repo: Option<String>,
+}
+
+impl DistroPackagingBackend {
+    pub fn from_config(config: &Config) -> Self {
+        let repo = config.contribute.distro_repo.clone().or_else(|| {
+            fs::read_to_string("/etc/os-release")
+                .ok()
+                .and_then(|content| repo_from_os_release(&content))
+        });
+        Self { repo }
+    }
+}
+
+impl ContributionBackend for DistroPackagingBackend {
+    fn name(&self) -> &str {
+        "distro_packaging"
+    }
+
+    fn is_available(&self) -> bool {
+        self.repo.is_some()
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some(repo) = &self.repo else {
+            return Ok(Vec::new());
+        };
+        let name = project.name.to_lowercase();
+        let packages = repology::fetch_project(&name)?;
+        Ok(packaging_opportunity(&name, repo, &packages)
+            .into_iter()
+            .collect())
+    }
+}
+
+/// A suggestion to package the project `name` in `repo`, or to update its
+/// package there, from the project's Repology packages.
+fn packaging_opportunity(
+    name: &str,
+    repo: &str,
+    packages: &[RepologyPackage],
+) -> Option<ContributionOpportunity> {
+    // Repology project names do not always match package names.
+    if !packages.iter().any(|p| p.is_named(name)) {
+        return None;
+    }
+    let newest = packages
+        .iter()
+        .find(|p| p.status.as_deref() == Some("newest"))?;
+    let url = format!("https://repology.org/project/{name}/versions");
+
+    let ours: Vec<&RepologyPackage> = packages.iter().filter(|p| p.repo == repo).collect();
+    if ours.is_empty() {
+        return Some(ContributionOpportunity {
+            kind: ContributionKind::Packaging,
+            title: format!("Package {name} for {repo}"),
+            description: Some(format!(
+                "Packaged elsewhere at version {}, but not in {repo}",
+                newest.version
+            )),
+            url,
+        });
+    }
+
+    // Outdated packages usually have a newer one next to them when the
+    // repository ships several branches; only suggest an update if none is
+    // current.
+    let current = ours
+        .iter()
+        .any(|p| matches!(p.status.as_deref(), Some("newest" | "devel" | "unique")));
+    if current {
+        return None;
+    }
+    let outdated = ours
+        .iter()
+        .find(|p| p.status.as_deref() == Some("outdated"))?;
+    Some(ContributionOpportunity {
+        kind: ContributionKind::Packaging,
+        title: format!("Update {name} in {repo}"),
+        description: Some(format!(
+            "{repo} has {}, the newest version is {}",
+            outdated.version, newest.version
+        )),
+        url,
+    })
+}
+
+/// The Repology repository of the distribution described by the contents of
+/// `/etc/os-release`, for the distributions whose naming is known.
+fn repo_from_os_release(content: &str) -> Option<String> {
+    let field = |key: &str| {
+        content.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix('=')?;
+            Some(value.trim_matches('"').to_string())
+        })
+    };
+    let id = field("ID")?;
+    let version = field("VERSION_ID");
+    // Repology names versioned repositories with underscores, and Alpine's by
+    // major and minor version only.
+    let versioned = |v: &str| v.replace('.', "_");
+    match (id.as_str(), version.as_deref()) {
+        ("arch" | "gentoo", _) => Some(id.clone()),
+        ("opensuse-tumbleweed", _) => Some("opensuse_tumbleweed".to_string()),
+        ("debian" | "ubuntu" | "fedora", Some(v)) => Some(format!("{id}_{}", versioned(v))),
+        ("alpine", Some(v)) => {
+            let major_minor: Vec<&str> = v.split('.').take(2).collect();
+            Some(format!("alpine_{}", major_minor.join("_")))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packages(json: &str) -> Vec<RepologyPackage> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn repos_from_os_release() {
+        let repo = |content: &str| repo_from_os_release(content);
+        assert_eq!(
+            repo("NAME=\"Arch Linux\"\nID=arch\n").as_deref(),
+            Some("arch")
+        );
+        assert_eq!(
+            repo("ID=debian\nVERSION_ID=\"12\"\n").as_deref(),
+            Some("debian_12")
+        );
+        assert_eq!(
+            repo("ID=ubuntu\nID_LIKE=debian\nVERSION_ID=\"24.04\"\n").as_deref(),
+            Some("ubuntu_24_04")
+        );
+        assert_eq!(
+            repo("ID=alpine\nVERSION_ID=3.20.1\n").as_deref(),
+            Some("alpine_3_20")
+        );
+        assert_eq!(
+            repo("ID=\"opensuse-tumbleweed\"\n").as_deref(),
+            Some("opensuse_tumbleweed")
+        );
+        assert_eq!(repo("ID=linuxfromscratch\n"), None);
+    }
+
+    #[test]
+    fn configured_repo_wins() {
+        let mut config = Config::default();
+        config.contribute.distro_repo = Some("nix_unstable".to_string());
+        let backend = DistroPackagingBackend::from_config(&config);
+        assert_eq!(backend.repo.as_deref(), Some("nix_unstable"));
+        assert!(backend.is_available());
+    }
+
+    #[test]
+    fn suggests_packaging_missing_projects() {
+        let found = packaging_opportunity(
+            "zstd",
+            "debian_12",
+            &packages(
+                r#"[
+                    {"repo": "arch", "binname": "zstd", "version": "1.5.6", "status": "newest"},
+                    {"repo": "fedora_40", "binname": "zstd", "version": "1.5.6", "status": "newest"}
+                ]"#,
+            ),
+        )
+        .unwrap();
+        assert_eq!(found.kind, ContributionKind::Packaging);
+        assert_eq!(found.title, "Package zstd for debian_12");
+        assert_eq!(found.url, "https://repology.org/project/zstd/versions");
+    }
+
+    #[test]
+    fn suggests_updating_outdated_packages() {
+        let found = packaging_opportunity(
+            "zstd",
+            "debian_12",
+            &packages(
+                r#"[
+                    {"repo": "arch", "binname": "zstd", "version": "1.5.6", "status": "newest"},
+                    {"repo": "debian_12", "binname": "zstd", "version": "1.5.4", "status": "outdated"}
+                ]"#,
+            ),
+        )
+        .unwrap();
+        assert_eq!(found.title, "Update zstd in debian_12");
+        assert_eq!(
+            found.description.as_deref(),
+            Some("debian_12 has 1.5.4, the newest version is 1.5.6")
+        );
+    }
+
+    #[test]
+    fn nothing_for_current_or_unknown_packages() {
+        let current = packages(
+            r#"[
+                {"repo": "arch", "binname": "zstd", "version": "1.5.6", "status": "newest"},
+                {"repo": "debian_12", "binname": "zstd", "version": "1.5.4", "status": "legacy"},
+                {"repo": "debian_12", "binname": "zstd", "version": "1.5.6", "status": "newest"}
+            ]"#,
+        );
+        assert!(packaging_opportunity("zstd", "debian_12", &current).is_none());
+
+        let other_project = packages(
+            r#"[{"repo": "arch", "binname": "python-zstd", "version": "1.5.6", "status": "newest"}]"#,
+        );
+        assert!(packaging_opportunity("zstd", "debian_12", &other_project).is_none());
+        assert!(packaging_opportunity("zstd", "debian_12", &[]).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Documentation contribution backend.
+//!
+//! Surfaces documentation issues of projects on GitHub, GitLab, and Gitea or
+//! Forgejo, and projects that have no documentation at all: no documentation
+//! site was found during enrichment and their repository has the wiki
+//! turned off.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::forge_issues::Forge;
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::enrich::net::{self, RequestExt};
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+
+/// Labels of documentation issues on GitHub, as in its default label set.
+const GITHUB_LABELS: &[&str] = &["documentation"];
+
+/// Labels of documentation issues on GitLab.
+const GITLAB_LABELS: &[&str] = &["documentation", "docs", "type::documentation"];
+
+/// Labels of documentation issues on Gitea, including the "Advanced" label
+/// set.
+const GITEA_LABELS: &[&str] = &["documentation", "Kind/Documentation"];
+
+/// Backend that discovers documentation work.
+pub struct DocumentationBackend {
+    pub client: GitHubClient,
+}
+
+/// The wiki settings of `GET /repos/{owner}/{repo}` on GitHub and Gitea, and
+/// of `GET /api/v4/projects/:id` on GitLab.
+#[derive(Debug, Deserialize)]
+struct RepoWiki {
+    has_wiki: Option<bool>,
+    wiki_access_level: Option<String>,
+}
+
+impl RepoWiki {
+    fn enabled(&self) -> bool {
+        match &self.wiki_access_level {
+            Some(level) => level != "disabled",
+            None => self.has_wiki.unwrap_or(true),
+        }
+    }
+}
+
+impl ContributionBackend for DocumentationBackend {
+    fn name(&self) -> &str {
+        "documentation"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some(repo_url) = project.repo_url.as_deref() else {
+            return Ok(Vec::new());
+        };
+        let Some(forge) = Forge::from_url(repo_url) else {
+            return Ok(Vec::new());
+        };
+
+        let labels = match forge {
+            Forge::GitHub(_) => GITHUB_LABELS,
+            Forge::GitLab(..) => GITLAB_LABELS,
+            Forge::Gitea(..) => GITEA_LABELS,
+        };
+        let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+        let mut opportunities: Vec<ContributionOpportunity> = forge
+            .issues(&self.client, &labels)?
+            .into_iter()
+            .map(|issue| ContributionOpportunity {
+                kind: ContributionKind::Documentation,
+                title: issue.title,
+                description: None,
+                url: issue.html_url,
+            })
+            .collect();
+
+        if project.documentation_url.is_none()
+            && let Some(wiki) = self.repo_wiki(&forge)?
+            && !wiki.enabled()
+        {
+            opportunities.push(missing_docs_opportunity(&project.name, repo_url));
+        }
+
+        Ok(opportunities)
+    }
+}
+
+impl DocumentationBackend {
+    /// The wiki settings of the repository, `None` when it is gone.
+    fn repo_wiki(&self, forge: &Forge) -> Result<Option<RepoWiki>> {
+        let url = match forge {
+            Forge::GitHub(owner_repo) => {
+                return self.client.get_json(&format!("repos/{owner_repo}"));
+            }
+            Forge::GitLab(host, path) => format!(
+                "https://{host}/api/v4/projects/{}",
+                path.replace('/', "%2F")
+            ),
+            Forge::Gitea(host, owner_repo) => format!("https://{host}/api/v1/repos/{owner_repo}"),
+        };
+        let response = net::client()
+            .get(&url)
+            .send_paced()
+            .with_context(|| format!("Failed to request {url}"))?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let wiki = response
+            .json()
+            .with_context(|| format!("Failed to parse {url}"))?;
+        Ok(Some(wiki))
+    }
+}
+
+fn missing_docs_opportunity(name: &str, repo_url: &str) -> ContributionOpportunity {
+    ContributionOpportunity {
+        kind: ContributionKind::Documentation,
+        title: format!("Write documentation for {name}"),
+        description: Some("No documentation site, and the wiki is turned off".to_string()),
+        url: repo_url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wiki_settings_per_forge() {
+        let github: RepoWiki =
+            serde_json::from_str(r#"{"full_name": "owner/repo", "has_wiki": false}"#).unwrap();
+        assert!(!github.enabled());
+
+        let gitlab: RepoWiki = serde_json::from_str(
+            r#"{"path_with_namespace": "group/project", "wiki_enabled": true, "wiki_access_level": "disabled"}"#,
+        )
+        .unwrap();
+        assert!(!gitlab.enabled());
+
+        let gitea: RepoWiki = serde_json::from_str(r#"{"has_wiki": true}"#).unwrap();
+        assert!(gitea.enabled());
+
+        // Unknown settings do not count as missing documentation.
+        let unknown: RepoWiki = serde_json::from_str("{}").unwrap();
+        assert!(unknown.enabled());
+    }
+
+    #[test]
+    fn missing_docs_links_repository() {
+        let opportunity = missing_docs_opportunity("tiny", "https://github.com/owner/tiny");
+        assert_eq!(opportunity.kind, ContributionKind::Documentation);
+        assert_eq!(opportunity.title, "Write documentation for tiny");
+        assert_eq!(opportunity.url, "https://github.com/owner/tiny");
+    }
+}
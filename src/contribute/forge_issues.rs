@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Labeled issue and repository lookup shared by the contribution backends
+//! that work across GitHub, GitLab, and Gitea or Forgejo.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use super::gitea_issues::gitea_repo;
+use super::github_good_first_issues::extract_github_owner_repo;
+use crate::enrich::gitlab::gitlab_project;
+use crate::enrich::net::{self, RequestExt};
+use crate::github::GitHubClient;
+
+/// Maximum number of issues suggested per project.
+const MAX_ISSUES: usize = 10;
+
+/// A repository on a forge whose issues can be listed.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Forge {
+    /// `owner/repo` on GitHub.
+    GitHub(String),
+    /// Host and project path of a GitLab instance.
+    GitLab(String, String),
+    /// Host and `owner/repo` of a Gitea or Forgejo instance.
+    Gitea(String, String),
+}
+
+/// An issue as returned by the GitHub, GitLab, and Gitea issue lists.
+#[derive(Debug, Deserialize)]
+pub(super) struct ForgeIssue {
+    pub title: String,
+    #[serde(alias = "web_url")]
+    pub html_url: String,
+    #[serde(default)]
+    labels: Vec<ForgeLabel>,
+    /// Present when a GitHub "issue" is actually a pull request.
+    pull_request: Option<serde_json::Value>,
+}
+
+/// GitLab lists label names; GitHub and Gitea list label objects.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ForgeLabel {
+    Name(String),
+    Object { name: String },
+}
+
+/// The main programming language and the topics of a repository.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct RepoProfile {
+    pub language: Option<String>,
+    pub topics: Vec<String>,
+}
+
+/// The part of a repository as returned by the GitHub, GitLab, and Gitea
+/// APIs used for its profile. Only GitHub names the main language.
+#[derive(Debug, Deserialize)]
+struct RepoInfo {
+    language: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+}
+
+impl ForgeIssue {
+    /// The names of the issue's labels.
+    pub fn label_names(&self) -> Vec<&str> {
+        self.labels
+            .iter()
+            .map(|l| match l {
+                ForgeLabel::Name(name) | ForgeLabel::Object { name } => name.as_str(),
+            })
+            .collect()
+    }
+}
+
+impl Forge {
+    /// The forge repository of `repo_url`, if it is on a supported forge.
+    pub fn from_url(repo_url: &str) -> Option<Self> {
+        if let Some(owner_repo) = extract_github_owner_repo(repo_url) {
+            Some(Forge::GitHub(owner_repo))
+        } else if let Some((host, path)) = gitlab_project(repo_url) {
+            Some(Forge::GitLab(host, path))
+        } else {
+            gitea_repo(repo_url).map(|(host, owner_repo)| Forge::Gitea(host, owner_repo))
+        }
+    }
+
+    /// The main language and topics of the repository, `None` when it is
+    /// gone.
+    pub fn profile(&self, client: &GitHubClient) -> Result<Option<RepoProfile>> {
+        let (repo_url, languages_url) = match self {
+            Forge::GitHub(owner_repo) => {
+                let info: Option<RepoInfo> = client.get_json(&format!("repos/{owner_repo}"))?;
+                return Ok(info.map(|i| RepoProfile {
+                    language: i.language,
+                    topics: i.topics,
+                }));
+            }
+            Forge::GitLab(host, path) => {
+                let url = format!(
+                    "https://{host}/api/v4/projects/{}",
+                    path.replace('/', "%2F")
+                );
+                (url.clone(), format!("{url}/languages"))
+            }
+            Forge::Gitea(host, owner_repo) => {
+                let url = format!("https://{host}/api/v1/repos/{owner_repo}");
+                (url.clone(), format!("{url}/languages"))
+            }
+        };
+        let Some(info) = get_json::<RepoInfo>(&repo_url)? else {
+            return Ok(None);
+        };
+        let languages: HashMap<String, f64> = get_json(&languages_url)?.unwrap_or_default();
+        Ok(Some(RepoProfile {
+            language: main_language(languages),
+            topics: info.topics,
+        }))
+    }
+
+    /// Open issues carrying any of `labels`, at most [`MAX_ISSUES`], without
+    /// pull requests. Label names are matched case-insensitively, and
+    /// repositories that are gone or have issues disabled have none.
+    pub fn issues(&self, client: &GitHubClient, labels: &[String]) -> Result<Vec<ForgeIssue>> {
+        let issues = match self {
+            Forge::GitHub(owner_repo) => github_issues(client, owner_repo, labels)?,
+            Forge::GitLab(host, path) => gitlab_issues(host, path, labels)?,
+            Forge::Gitea(host, owner_repo) => gitea_issues(host, owner_repo, labels)?,
+        };
+        // Gitea returns every issue when none of the queried labels exist.
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .filter(|issue| {
+                issue
+                    .label_names()
+                    .iter()
+                    .any(|l| labels.iter().any(|w| w.eq_ignore_ascii_case(l)))
+            })
+            .take(MAX_ISSUES)
+            .collect())
+    }
+}
+
+/// GitHub lists issues carrying all of the given labels, so each label needs
+/// its own query.
+fn github_issues(
+    client: &GitHubClient,
+    owner_repo: &str,
+    labels: &[String],
+) -> Result<Vec<ForgeIssue>> {
+    let mut issues = Vec::new();
+    for label in labels {
+        if issues.len() >= MAX_ISSUES {
+            break;
+        }
+        let Some(found) = client.get_json(&format!(
+            "repos/{owner_repo}/issues?labels={}&state=open&per_page=10",
+            encode_label(label)
+        ))?
+        else {
+            break;
+        };
+        add_new(&mut issues, found);
+    }
+    Ok(issues)
+}
+
+/// Like GitHub, GitLab lists issues carrying all of the given labels.
+fn gitlab_issues(host: &str, path: &str, labels: &[String]) -> Result<Vec<ForgeIssue>> {
+    let url = format!(
+        "https://{host}/api/v4/projects/{}/issues",
+        path.replace('/', "%2F")
+    );
+    let mut issues = Vec::new();
+    for label in labels {
+        if issues.len() >= MAX_ISSUES {
+            break;
+        }
+        let response = net::client()
+            .get(&url)
+            .query(&[
+                ("labels", label.as_str()),
+                ("state", "opened"),
+                ("per_page", "10"),
+            ])
+            .send_paced()
+            .context("Failed to query GitLab API")?;
+        if !response.status().is_success() {
+            break;
+        }
+        let found: Vec<ForgeIssue> = response
+            .json()
+            .context("Failed to parse GitLab API response")?;
+        add_new(&mut issues, found);
+    }
+    Ok(issues)
+}
+
+/// Gitea lists issues carrying any of the given labels in one query.
+fn gitea_issues(host: &str, owner_repo: &str, labels: &[String]) -> Result<Vec<ForgeIssue>> {
+    let response = net::client()
+        .get(format!("https://{host}/api/v1/repos/{owner_repo}/issues"))
+        .query(&[
+            ("labels", labels.join(",").as_str()),
+            ("state", "open"),
+            ("type", "issues"),
+            ("limit", "10"),
+        ])
+        .send_paced()
+        .context("Failed to query Gitea API")?;
+    if !response.status().is_success() {
+        return Ok(Vec::new());
+    }
+    response
+        .json()
+        .context("Failed to parse Gitea API response")
+}
+
+/// GET `url` and decode the JSON response, `None` when it is not found.
+fn get_json<T: DeserializeOwned>(url: &str) -> Result<Option<T>> {
+    let response = net::client()
+        .get(url)
+        .send_paced()
+        .with_context(|| format!("Failed to request {url}"))?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+    let value = response
+        .json()
+        .with_context(|| format!("Failed to parse {url}"))?;
+    Ok(Some(value))
+}
+
+/// The language with the largest share, from GitLab's percentages or
+/// Gitea's byte counts.
+fn main_language(languages: HashMap<String, f64>) -> Option<String> {
+    languages
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(language, _)| language)
+}
+
+/// Percent-encode a label for a GitHub query string.
+fn encode_label(label: &str) -> String {
+    label
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Append the issues of `found` not already in `issues`.
+fn add_new(issues: &mut Vec<ForgeIssue>, found: Vec<ForgeIssue>) {
+    for issue in found {
+        if !issues.iter().any(|i| i.html_url == issue.html_url) {
+            issues.push(issue);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forges_from_urls() {
+        assert_eq!(
+            Forge::from_url("https://github.com/owner/repo"),
+            Some(Forge::GitHub("owner/repo".to_string()))
+        );
+        assert_eq!(
+            Forge::from_url("https://gitlab.gnome.org/GNOME/gtk"),
+            Some(Forge::GitLab(
+                "gitlab.gnome.org".to_string(),
+                "GNOME/gtk".to_string()
+            ))
+        );
+        assert_eq!(
+            Forge::from_url("https://codeberg.org/owner/repo"),
+            Some(Forge::Gitea(
+                "codeberg.org".to_string(),
+                "owner/repo".to_string()
+            ))
+        );
+        assert_eq!(Forge::from_url("https://git.sr.ht/~owner/repo"), None);
+    }
+
+    #[test]
+    fn parse_label_names_and_objects() {
+        let json = r#"[
+            {
+                "title": "Memory leak in parser",
+                "web_url": "https://gitlab.com/group/project/-/issues/5",
+                "labels": ["priority::high", "type::bug"]
+            },
+            {
+                "title": "Support Windows paths",
+                "html_url": "https://github.com/owner/repo/issues/1",
+                "labels": [{"name": "help wanted"}]
+            }
+        ]"#;
+        let issues: Vec<ForgeIssue> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            issues[0].html_url,
+            "https://gitlab.com/group/project/-/issues/5"
+        );
+        assert_eq!(issues[0].label_names(), ["priority::high", "type::bug"]);
+        assert_eq!(issues[1].label_names(), ["help wanted"]);
+    }
+
+    #[test]
+    fn main_language_by_share() {
+        let gitlab: HashMap<String, f64> =
+            serde_json::from_str(r#"{"C": 80.5, "Python": 19.5}"#).unwrap();
+        assert_eq!(main_language(gitlab).as_deref(), Some("C"));
+
+        let gitea: HashMap<String, f64> =
+            serde_json::from_str(r#"{"Go": 1200, "JavaScript": 56000}"#).unwrap();
+        assert_eq!(main_language(gitea).as_deref(), Some("JavaScript"));
+
+        assert_eq!(main_language(HashMap::new()), None);
+    }
+
+    #[test]
+    fn encodes_labels_for_github() {
+        assert_eq!(encode_label("help wanted"), "help%20wanted");
+        assert_eq!(encode_label("priority: high"), "priority%3A%20high");
+    }
+}
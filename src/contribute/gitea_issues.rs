@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Gitea issues contribution backend.
+//!
+//! Discovers beginner-friendly and help-wanted issues from repositories on
+//! Gitea-API forges: Codeberg, gitea.com, and self-hosted Gitea or Forgejo
+//! instances whose host name says so (e.g. `gitea.example.org`).
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::enrich::net::{self, RequestExt};
+use crate::project::UpstreamProject;
+
+/// Forges known to run Gitea or Forgejo.
+const KNOWN_HOSTS: &[&str] = &["codeberg.org", "gitea.com"];
+
+/// Labels marking beginner-friendly issues, lowercase.
+const GOOD_FIRST_LABELS: &[&str] = &[
+    "good first issue",
+    "good-first-issue",
+    "beginner",
+    "newcomer",
+];
+
+/// Labels marking issues the maintainers want help with, lowercase.
+const HELP_WANTED_LABELS: &[&str] = &["help wanted", "help-wanted"];
+
+/// Backend that discovers labeled issues from Gitea and Forgejo repos.
+pub struct GiteaIssuesBackend;
+
+/// A single issue from `GET /api/v1/repos/{owner}/{repo}/issues`.
+#[derive(Debug, Deserialize)]
+struct GtIssue {
+    title: String,
+    html_url: String,
+    #[serde(default)]
+    labels: Vec<GtLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtLabel {
+    name: String,
+}
+
+impl ContributionBackend for GiteaIssuesBackend {
+    fn name(&self) -> &str {
+        "gitea_issues"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some((host, owner_repo)) = project.repo_url.as_deref().and_then(gitea_repo) else {
+            return Ok(Vec::new());
+        };
+
+        let labels = GOOD_FIRST_LABELS
+            .iter()
+            .chain(HELP_WANTED_LABELS)
+            .copied()
+            .collect::<Vec<_>>()
+            .join(",");
+        let response = net::client()
+            .get(format!("https://{host}/api/v1/repos/{owner_repo}/issues"))
+            .query(&[
+                ("labels", labels.as_str()),
+                ("state", "open"),
+                ("type", "issues"),
+                ("limit", "10"),
+            ])
+            .send_paced()
+            .context("Failed to query Gitea API")?;
+        // Repos that are gone or have issues disabled are not an error.
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let issues: Vec<GtIssue> = response
+            .json()
+            .context("Failed to parse Gitea API response")?;
+        Ok(issues_to_opportunities(issues))
+    }
+}
+
+/// Split a Gitea repository URL into host and `owner/repo`.
+pub(crate) fn gitea_repo(url: &str) -> Option<(String, String)> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_lowercase();
+    if !KNOWN_HOSTS.contains(&host.as_str())
+        && !host.starts_with("gitea.")
+        && !host.starts_with("forgejo.")
+    {
+        return None;
+    }
+
+    let mut parts = path.split(['?', '#']).next()?.split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    Some((host, format!("{owner}/{repo}")))
+}
+
+/// The kind of contribution an issue's labels ask for. Labels are matched
+/// case-insensitively and without their scope, so `Kind/Good First Issue`
+/// counts too.
+fn issue_kind(labels: &[GtLabel]) -> Option<ContributionKind> {
+    let names: Vec<String> = labels
+        .iter()
+        .map(|l| {
+            let name = l.name.rsplit('/').next().unwrap_or(&l.name);
+            name.trim().to_lowercase()
+        })
+        .collect();
+    let has = |wanted: &[&str]| names.iter().any(|n| wanted.contains(&n.as_str()));
+    if has(GOOD_FIRST_LABELS) {
+        Some(ContributionKind::GoodFirstIssue)
+    } else if has(HELP_WANTED_LABELS) {
+        Some(ContributionKind::HelpWanted)
+    } else {
+        None
+    }
+}
+
+/// Convert API issues into contribution opportunities. Older Gitea versions
+/// ignore unknown label names in the query and return every issue, so the
+/// labels are checked again here.
+fn issues_to_opportunities(issues: Vec<GtIssue>) -> Vec<ContributionOpportunity> {
+    issues
+        .into_iter()
+        .filter_map(|issue| {
+            let kind = issue_kind(&issue.labels)?;
+            let labels: Vec<&str> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+            Some(ContributionOpportunity {
+                kind,
+                title: issue.title,
+                description: Some(labels.join(", ")),
+                url: issue.html_url,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gitea_repo_urls() {
+        assert_eq!(
+            gitea_repo("https://codeberg.org/forgejo/forgejo.git"),
+            Some(("codeberg.org".to_string(), "forgejo/forgejo".to_string()))
+        );
+        assert_eq!(
+            gitea_repo("https://gitea.wildfiregames.com/0ad/0ad/issues"),
+            Some(("gitea.wildfiregames.com".to_string(), "0ad/0ad".to_string()))
+        );
+        assert_eq!(gitea_repo("https://github.com/owner/repo"), None);
+        assert_eq!(gitea_repo("https://codeberg.org/owner"), None);
+    }
+
+    #[test]
+    fn parse_gt_issue_json() {
+        let json = r#"[
+            {
+                "number": 7,
+                "title": "Add dark theme",
+                "html_url": "https://codeberg.org/owner/repo/issues/7",
+                "labels": [{"id": 1, "name": "Kind/Good First Issue"}]
+            },
+            {
+                "number": 8,
+                "title": "Port to Wayland",
+                "html_url": "https://codeberg.org/owner/repo/issues/8",
+                "labels": [{"id": 2, "name": "help wanted"}, {"id": 3, "name": "enhancement"}]
+            },
+            {
+                "number": 9,
+                "title": "Crash on start",
+                "html_url": "https://codeberg.org/owner/repo/issues/9",
+                "labels": [{"id": 4, "name": "bug"}]
+            }
+        ]"#;
+        let issues: Vec<GtIssue> = serde_json::from_str(json).unwrap();
+        let opportunities = issues_to_opportunities(issues);
+
+        assert_eq!(opportunities.len(), 2);
+        assert_eq!(opportunities[0].kind, ContributionKind::GoodFirstIssue);
+        assert_eq!(opportunities[0].title, "Add dark theme");
+        assert_eq!(
+            opportunities[0].url,
+            "https://codeberg.org/owner/repo/issues/7"
+        );
+        assert_eq!(opportunities[1].kind, ContributionKind::HelpWanted);
+        assert_eq!(
+            opportunities[1].description.as_deref(),
+            Some("help wanted, enhancement")
+        );
+    }
+}
@@ -3,27 +3,29 @@
 //! GitHub good-first-issues contribution backend.
 //!
 //! Discovers beginner-friendly issues from GitHub repositories that the user
-//! depends on. Uses the `gh` CLI to query the GitHub API, which handles
-//! authentication transparently.
+//! depends on, using the GitHub REST API.
 
-use std::process::Command;
-
-use anyhow::{Context, Result};
+use anyhow::Result;
 use serde::Deserialize;
 
 use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::github::GitHubClient;
 use crate::project::UpstreamProject;
 
 /// Backend that discovers "good first issue" labeled issues from GitHub repos.
-pub struct GitHubGoodFirstIssuesBackend;
+pub struct GitHubGoodFirstIssuesBackend {
+    pub client: GitHubClient,
+}
 
-/// A single issue from the `gh` CLI JSON output.
+/// A single issue from `GET /repos/{owner}/{repo}/issues`.
 #[derive(Debug, Deserialize)]
 struct GhIssue {
     title: String,
-    url: String,
+    html_url: String,
     #[serde(default)]
     labels: Vec<GhLabel>,
+    /// Present when the "issue" is actually a pull request.
+    pull_request: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,12 +39,7 @@ impl ContributionBackend for GitHubGoodFirstIssuesBackend {
     }
 
     fn is_available(&self) -> bool {
-        // Check that gh CLI is installed and authenticated.
-        Command::new("gh")
-            .args(["auth", "status"])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        true
     }
 
     fn find_opportunities(
@@ -59,67 +56,44 @@ impl ContributionBackend for GitHubGoodFirstIssuesBackend {
             None => return Ok(Vec::new()),
         };
 
-        let output = Command::new("gh")
-            .args([
-                "issue",
-                "list",
-                "--repo",
-                &owner_repo,
-                "--label",
-                "good first issue",
-                "--state",
-                "open",
-                "--limit",
-                "10",
-                "--json",
-                "title,url,labels",
-            ])
-            .output()
-            .context("Failed to run gh issue list")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            // Some repos may have issues disabled or be inaccessible — not fatal.
-            if stderr.contains("Could not resolve")
-                || stderr.contains("not found")
-                || stderr.contains("403")
-            {
-                return Ok(Vec::new());
-            }
-            anyhow::bail!("gh issue list failed for {owner_repo}: {stderr}");
-        }
+        // Repos that are gone or have issues disabled are not an error.
+        let issues: Vec<GhIssue> = self
+            .client
+            .get_json(&format!(
+                "repos/{owner_repo}/issues?labels=good%20first%20issue&state=open&per_page=10"
+            ))?
+            .unwrap_or_default();
 
-        let stdout =
-            String::from_utf8(output.stdout).context("gh issue list output is not valid UTF-8")?;
-
-        let issues: Vec<GhIssue> =
-            serde_json::from_str(&stdout).context("Failed to parse gh issue list JSON")?;
-
-        let opportunities = issues
-            .into_iter()
-            .map(|issue| ContributionOpportunity {
-                kind: ContributionKind::GoodFirstIssue,
-                title: issue.title,
-                description: if issue.labels.is_empty() {
-                    None
-                } else {
-                    Some(
-                        issue
-                            .labels
-                            .iter()
-                            .map(|l| l.name.as_str())
-                            .collect::<Vec<_>>()
-                            .join(", "),
-                    )
-                },
-                url: issue.url,
-            })
-            .collect();
-
-        Ok(opportunities)
+        Ok(issues_to_opportunities(issues))
     }
 }
 
+/// Convert API issues into contribution opportunities, skipping pull
+/// requests (which the issues endpoint also returns).
+fn issues_to_opportunities(issues: Vec<GhIssue>) -> Vec<ContributionOpportunity> {
+    issues
+        .into_iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .map(|issue| ContributionOpportunity {
+            kind: ContributionKind::GoodFirstIssue,
+            title: issue.title,
+            description: if issue.labels.is_empty() {
+                None
+            } else {
+                Some(
+                    issue
+                        .labels
+                        .iter()
+                        .map(|l| l.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                )
+            },
+            url: issue.html_url,
+        })
+        .collect()
+}
+
 /// Extract `owner/repo` from a GitHub URL.
 ///
 /// Accepts HTTPS, SSH, and `git://` URL formats:
@@ -269,19 +243,13 @@ mod tests {
 
     #[test]
     fn find_opportunities_skips_non_github_projects() {
-        let backend = GitHubGoodFirstIssuesBackend;
+        let backend = GitHubGoodFirstIssuesBackend {
+            client: GitHubClient::new(None).unwrap(),
+        };
         let project = UpstreamProject {
             name: "test".to_string(),
             repo_url: Some("https://gitlab.com/owner/repo".to_string()),
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let result = backend.find_opportunities(&project).unwrap();
@@ -290,19 +258,12 @@ mod tests {
 
     #[test]
     fn find_opportunities_skips_projects_without_repo_url() {
-        let backend = GitHubGoodFirstIssuesBackend;
+        let backend = GitHubGoodFirstIssuesBackend {
+            client: GitHubClient::new(None).unwrap(),
+        };
         let project = UpstreamProject {
             name: "test".to_string(),
-            repo_url: None,
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let result = backend.find_opportunities(&project).unwrap();
@@ -314,7 +275,7 @@ mod tests {
         let json = r#"[
             {
                 "title": "Fix typo in README",
-                "url": "https://github.com/example/repo/issues/1",
+                "html_url": "https://github.com/example/repo/issues/1",
                 "labels": [
                     {"name": "good first issue"},
                     {"name": "documentation"}
@@ -322,7 +283,7 @@ mod tests {
             },
             {
                 "title": "Add missing test",
-                "url": "https://github.com/example/repo/issues/2",
+                "html_url": "https://github.com/example/repo/issues/2",
                 "labels": [
                     {"name": "good first issue"}
                 ]
@@ -347,11 +308,40 @@ mod tests {
     fn parse_gh_issue_json_no_labels() {
         let json = r#"[{
             "title": "Test issue",
-            "url": "https://github.com/example/repo/issues/3"
+            "html_url": "https://github.com/example/repo/issues/3"
         }]"#;
 
         let issues: Vec<GhIssue> = serde_json::from_str(json).unwrap();
         assert_eq!(issues.len(), 1);
         assert!(issues[0].labels.is_empty());
     }
+
+    #[test]
+    fn issues_skip_pull_requests() {
+        let json = r#"[
+            {
+                "title": "Improve error message",
+                "html_url": "https://github.com/owner/repo/issues/1",
+                "labels": [{"name": "good first issue"}, {"name": "docs"}]
+            },
+            {
+                "title": "Fix typo",
+                "html_url": "https://github.com/owner/repo/pull/2",
+                "labels": [{"name": "good first issue"}],
+                "pull_request": {"url": "https://api.github.com/repos/owner/repo/pulls/2"}
+            }
+        ]"#;
+        let issues: Vec<GhIssue> = serde_json::from_str(json).unwrap();
+        let opportunities = issues_to_opportunities(issues);
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].title, "Improve error message");
+        assert_eq!(
+            opportunities[0].url,
+            "https://github.com/owner/repo/issues/1"
+        );
+        assert_eq!(
+            opportunities[0].description.as_deref(),
+            Some("good first issue, docs")
+        );
+    }
 }
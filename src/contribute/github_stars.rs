@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! GitHub stars contribution backend.
+//!
+//! Suggests starring the GitHub repositories of the projects the user depends
+//! on that they have not starred yet. Stars are a cheap way to tell
+//! maintainers their work is used, and `syld star` gives them in batch.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use super::github_good_first_issues::extract_github_owner_repo;
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+use crate::storage::Storage;
+
+/// Backend that suggests starring unstarred GitHub repositories.
+pub struct GitHubStarsBackend {
+    pub client: GitHubClient,
+
+    /// Repositories (`owner/repo`, lowercase) already starred through
+    /// `syld star`, skipped without asking GitHub.
+    pub starred: HashSet<String>,
+}
+
+impl ContributionBackend for GitHubStarsBackend {
+    fn name(&self) -> &str {
+        "github_stars"
+    }
+
+    /// Whether a repository is starred is only known for an authenticated
+    /// user.
+    fn is_available(&self) -> bool {
+        self.client.has_token()
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        Ok(self
+            .unstarred_repo(project)?
+            .map(|r| star_opportunity(&r))
+            .into_iter()
+            .collect())
+    }
+}
+
+impl GitHubStarsBackend {
+    /// The unstarred GitHub repositories (`owner/repo`) of `projects`, each
+    /// once, in order. Projects that cannot be checked are reported and
+    /// skipped.
+    pub fn unstarred_repos<'a>(
+        &self,
+        projects: impl IntoIterator<Item = &'a UpstreamProject>,
+    ) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut repos = Vec::new();
+        for project in projects {
+            match self.unstarred_repo(project) {
+                Ok(Some(repo)) if seen.insert(repo.to_lowercase()) => repos.push(repo),
+                Ok(_) => {}
+                Err(e) => eprintln!("Warning: could not check stars of {}: {e}", project.name),
+            }
+        }
+        repos
+    }
+
+    /// Star `owner_repo` and record it in `storage`, so that it is not
+    /// suggested again.
+    pub fn star(&self, storage: &Storage, owner_repo: &str) -> Result<()> {
+        self.client.star(owner_repo)?;
+        storage.record_starred(owner_repo)
+    }
+
+    fn unstarred_repo(&self, project: &UpstreamProject) -> Result<Option<String>> {
+        let Some(owner_repo) = project
+            .repo_url
+            .as_deref()
+            .and_then(extract_github_owner_repo)
+        else {
+            return Ok(None);
+        };
+        if self.starred.contains(&owner_repo.to_lowercase())
+            || self.client.is_starred(&owner_repo)?
+        {
+            return Ok(None);
+        }
+        Ok(Some(owner_repo))
+    }
+}
+
+fn star_opportunity(owner_repo: &str) -> ContributionOpportunity {
+    ContributionOpportunity {
+        kind: ContributionKind::Star,
+        title: format!("Star {owner_repo} on GitHub"),
+        description: None,
+        url: format!("https://github.com/{owner_repo}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(repo_url: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: "ripgrep".to_string(),
+            repo_url: Some(repo_url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unavailable_without_token() {
+        let backend = GitHubStarsBackend {
+            client: GitHubClient::new(None).unwrap(),
+            starred: HashSet::new(),
+        };
+        assert!(!backend.is_available());
+    }
+
+    #[test]
+    fn skips_non_github_and_recorded_repos() {
+        let backend = GitHubStarsBackend {
+            client: GitHubClient::new(None).unwrap(),
+            starred: HashSet::from(["burntsushi/ripgrep".to_string()]),
+        };
+        let gitlab = project("https://gitlab.com/inkscape/inkscape");
+        assert!(backend.find_opportunities(&gitlab).unwrap().is_empty());
+        let recorded = project("https://github.com/BurntSushi/ripgrep");
+        assert!(backend.find_opportunities(&recorded).unwrap().is_empty());
+    }
+
+    #[test]
+    fn star_opportunity_links_repo() {
+        let opportunity = star_opportunity("BurntSushi/ripgrep");
+        assert_eq!(opportunity.kind, ContributionKind::Star);
+        assert_eq!(opportunity.url, "https://github.com/BurntSushi/ripgrep");
+    }
+}
@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! GitLab good-first-issues contribution backend.
+//!
+//! Discovers beginner-friendly issues from the GitLab-hosted repositories
+//! that the user depends on, on gitlab.com and the community instances known
+//! to the GitLab enrichment backend, using the GitLab REST API.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::enrich::gitlab::gitlab_project;
+use crate::enrich::net::{self, RequestExt};
+use crate::project::UpstreamProject;
+
+/// Labels marking beginner-friendly issues, queried in order. GNOME projects
+/// use "1. Newcomers".
+const LABELS: &[&str] = &["good first issue", "newcomer", "Newcomers", "1. Newcomers"];
+
+/// Maximum number of issues suggested per project.
+const MAX_ISSUES: usize = 10;
+
+/// Backend that discovers beginner-friendly issues from GitLab projects.
+pub struct GitLabGoodFirstIssuesBackend;
+
+/// A single issue from `GET /api/v4/projects/:id/issues`.
+#[derive(Debug, Deserialize)]
+struct GlIssue {
+    title: String,
+    web_url: String,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+impl ContributionBackend for GitLabGoodFirstIssuesBackend {
+    fn name(&self) -> &str {
+        "gitlab_good_first_issues"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some((host, path)) = project.repo_url.as_deref().and_then(gitlab_project) else {
+            return Ok(Vec::new());
+        };
+
+        // The API matches issues carrying all of the given labels, so each
+        // label needs its own query.
+        let url = format!(
+            "https://{host}/api/v4/projects/{}/issues",
+            path.replace('/', "%2F")
+        );
+        let mut issues: Vec<GlIssue> = Vec::new();
+        for label in LABELS {
+            if issues.len() >= MAX_ISSUES {
+                break;
+            }
+            let response = net::client()
+                .get(&url)
+                .query(&[("labels", *label), ("state", "opened"), ("per_page", "10")])
+                .send_paced()
+                .context("Failed to query GitLab API")?;
+            // Projects that are gone or have issues disabled are not an error.
+            if !response.status().is_success() {
+                return Ok(issues_to_opportunities(issues));
+            }
+            let found: Vec<GlIssue> = response
+                .json()
+                .context("Failed to parse GitLab API response")?;
+            for issue in found {
+                if !issues.iter().any(|i| i.web_url == issue.web_url) {
+                    issues.push(issue);
+                }
+            }
+        }
+        issues.truncate(MAX_ISSUES);
+
+        Ok(issues_to_opportunities(issues))
+    }
+}
+
+/// Convert API issues into contribution opportunities.
+fn issues_to_opportunities(issues: Vec<GlIssue>) -> Vec<ContributionOpportunity> {
+    issues
+        .into_iter()
+        .map(|issue| ContributionOpportunity {
+            kind: ContributionKind::GoodFirstIssue,
+            title: issue.title,
+            description: (!issue.labels.is_empty()).then(|| issue.labels.join(", ")),
+            url: issue.web_url,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_opportunities_skips_non_gitlab_projects() {
+        let project = UpstreamProject {
+            name: "test".to_string(),
+            repo_url: Some("https://github.com/owner/repo".to_string()),
+            ..Default::default()
+        };
+
+        let result = GitLabGoodFirstIssuesBackend
+            .find_opportunities(&project)
+            .unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn parse_gl_issue_json() {
+        let json = r#"[
+            {
+                "iid": 42,
+                "title": "Port dialog to GTK 4",
+                "web_url": "https://gitlab.gnome.org/GNOME/gnome-calculator/-/issues/42",
+                "labels": ["1. Newcomers", "4. Enhancement"]
+            },
+            {
+                "iid": 43,
+                "title": "Fix tooltip typo",
+                "web_url": "https://gitlab.gnome.org/GNOME/gnome-calculator/-/issues/43"
+            }
+        ]"#;
+        let issues: Vec<GlIssue> = serde_json::from_str(json).unwrap();
+        let opportunities = issues_to_opportunities(issues);
+
+        assert_eq!(opportunities.len(), 2);
+        assert_eq!(opportunities[0].kind, ContributionKind::GoodFirstIssue);
+        assert_eq!(opportunities[0].title, "Port dialog to GTK 4");
+        assert_eq!(
+            opportunities[0].description.as_deref(),
+            Some("1. Newcomers, 4. Enhancement")
+        );
+        assert_eq!(
+            opportunities[0].url,
+            "https://gitlab.gnome.org/GNOME/gnome-calculator/-/issues/42"
+        );
+        assert_eq!(opportunities[1].description, None);
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Hacktoberfest contribution backend.
+//!
+//! Hacktoberfest is a yearly event in October encouraging contributions to
+//! open source. Participating repositories carry the `hacktoberfest` topic
+//! and label the issues they want help with `hacktoberfest`. This backend
+//! is not among [`active_backends`](super::active_backends): it replaces
+//! them with `syld contribute --hacktoberfest`, which also prints a
+//! [`plan`] spreading the issues over the weeks of October.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use super::forge_issues::{Forge, ForgeIssue};
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity, ProjectOpportunity};
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+
+/// Topic of participating repositories, and label of their issues.
+pub const HACKTOBERFEST: &str = "hacktoberfest";
+
+/// Days of October starting each week of the plan; the last week runs to
+/// the end of the month.
+const WEEK_STARTS: [u32; 4] = [1, 8, 15, 22];
+
+/// Backend that discovers issues of repositories taking part in
+/// Hacktoberfest.
+pub struct HacktoberfestBackend {
+    pub client: GitHubClient,
+}
+
+impl ContributionBackend for HacktoberfestBackend {
+    fn name(&self) -> &str {
+        "hacktoberfest"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some(forge) = project.repo_url.as_deref().and_then(Forge::from_url) else {
+            return Ok(Vec::new());
+        };
+        let participates = forge.profile(&self.client)?.is_some_and(|p| {
+            p.topics
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(HACKTOBERFEST))
+        });
+        if !participates {
+            return Ok(Vec::new());
+        }
+
+        Ok(forge
+            .issues(&self.client, &[HACKTOBERFEST.to_string()])?
+            .into_iter()
+            .map(issue_to_opportunity)
+            .collect())
+    }
+}
+
+/// Issues also labeled as good first issues keep that kind; the others are
+/// [`ContributionKind::HelpWanted`].
+fn issue_to_opportunity(issue: ForgeIssue) -> ContributionOpportunity {
+    let labels = issue.label_names();
+    let kind = if labels
+        .iter()
+        .any(|l| l.eq_ignore_ascii_case("good first issue"))
+    {
+        ContributionKind::GoodFirstIssue
+    } else {
+        ContributionKind::HelpWanted
+    };
+    let description = Some(labels.join(", "));
+    ContributionOpportunity {
+        kind,
+        title: issue.title,
+        description,
+        url: issue.html_url,
+    }
+}
+
+/// A week of the participation plan and the issue to work on during it.
+#[derive(Debug)]
+pub struct PlanWeek<'a> {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub opportunity: &'a ProjectOpportunity,
+}
+
+/// A participation plan for October of `year`: one issue per week, easiest
+/// first, taken from different projects where possible.
+pub fn plan(opportunities: &[ProjectOpportunity], year: i32) -> Vec<PlanWeek<'_>> {
+    let mut picks: Vec<&ProjectOpportunity> = Vec::new();
+    let mut sorted: Vec<&ProjectOpportunity> = opportunities.iter().collect();
+    sorted.sort_by_key(|f| super::skills::difficulty(&f.opportunity));
+    // A first pass takes one issue per project, a second fills the weeks left.
+    for one_per_project in [true, false] {
+        for found in &sorted {
+            if picks.len() == WEEK_STARTS.len() {
+                break;
+            }
+            let taken = picks.iter().any(|p| std::ptr::eq(*p, *found));
+            let project_taken = picks.iter().any(|p| p.project == found.project);
+            if taken || (one_per_project && project_taken) {
+                continue;
+            }
+            picks.push(found);
+        }
+    }
+
+    picks
+        .into_iter()
+        .zip(WEEK_STARTS)
+        .filter_map(|(opportunity, start)| {
+            let end = if start == WEEK_STARTS[WEEK_STARTS.len() - 1] {
+                31
+            } else {
+                start + 6
+            };
+            Some(PlanWeek {
+                start: NaiveDate::from_ymd_opt(year, 10, start)?,
+                end: NaiveDate::from_ymd_opt(year, 10, end)?,
+                opportunity,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contribute::OpportunityState;
+
+    fn found(project: &str, kind: ContributionKind, number: u32) -> ProjectOpportunity {
+        ProjectOpportunity {
+            project: project.to_string(),
+            opportunity: ContributionOpportunity {
+                kind,
+                title: format!("Issue {number}"),
+                description: None,
+                url: format!("https://github.com/{project}/{project}/issues/{number}"),
+            },
+            state: OpportunityState::New,
+        }
+    }
+
+    #[test]
+    fn kinds_by_label() {
+        let json = r#"[
+            {
+                "title": "Add a dark theme",
+                "html_url": "https://github.com/owner/repo/issues/1",
+                "labels": [{"name": "hacktoberfest"}, {"name": "good first issue"}]
+            },
+            {
+                "title": "Speed up parsing",
+                "html_url": "https://github.com/owner/repo/issues/2",
+                "labels": [{"name": "Hacktoberfest"}]
+            }
+        ]"#;
+        let issues: Vec<ForgeIssue> = serde_json::from_str(json).unwrap();
+        let opportunities: Vec<_> = issues.into_iter().map(issue_to_opportunity).collect();
+        assert_eq!(opportunities[0].kind, ContributionKind::GoodFirstIssue);
+        assert_eq!(opportunities[1].kind, ContributionKind::HelpWanted);
+    }
+
+    #[test]
+    fn plan_spreads_issues_over_october() {
+        let opportunities = vec![
+            found("curl", ContributionKind::HelpWanted, 1),
+            found("curl", ContributionKind::GoodFirstIssue, 2),
+            found("curl", ContributionKind::GoodFirstIssue, 3),
+            found("gimp", ContributionKind::HelpWanted, 4),
+            found("vim", ContributionKind::GoodFirstIssue, 5),
+        ];
+
+        let weeks = plan(&opportunities, 2026);
+        let picked: Vec<&str> = weeks
+            .iter()
+            .map(|w| w.opportunity.opportunity.title.as_str())
+            .collect();
+        // Easy issues of different projects first.
+        assert_eq!(picked, ["Issue 2", "Issue 5", "Issue 4", "Issue 3"]);
+        assert_eq!(
+            weeks[0].start,
+            NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()
+        );
+        assert_eq!(weeks[0].end, NaiveDate::from_ymd_opt(2026, 10, 7).unwrap());
+        assert_eq!(weeks[3].end, NaiveDate::from_ymd_opt(2026, 10, 31).unwrap());
+    }
+
+    #[test]
+    fn short_plan_with_few_issues() {
+        let opportunities = vec![found("curl", ContributionKind::HelpWanted, 1)];
+        assert_eq!(plan(&opportunities, 2026).len(), 1);
+        assert!(plan(&[], 2026).is_empty());
+    }
+}
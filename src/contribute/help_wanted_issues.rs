@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Help-wanted issues contribution backend.
+//!
+//! For experienced contributors looking for higher-impact work than good
+//! first issues: surfaces issues labeled as wanting help, bugs, and issues
+//! the maintainers flagged as a priority, on GitHub, GitLab, and Gitea or
+//! Forgejo. The labels looked for are configured per forge in
+//! [`IssueLabelsConfig`].
+
+use anyhow::Result;
+
+use super::forge_issues::{Forge, ForgeIssue};
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::config::IssueLabelsConfig;
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+
+/// Backend that discovers help-wanted, bug, and priority issues.
+pub struct HelpWantedIssuesBackend {
+    pub client: GitHubClient,
+    pub labels: IssueLabelsConfig,
+}
+
+impl ContributionBackend for HelpWantedIssuesBackend {
+    fn name(&self) -> &str {
+        "help_wanted_issues"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some(forge) = project.repo_url.as_deref().and_then(Forge::from_url) else {
+            return Ok(Vec::new());
+        };
+        let labels = match forge {
+            Forge::GitHub(_) => &self.labels.github,
+            Forge::GitLab(..) => &self.labels.gitlab,
+            Forge::Gitea(..) => &self.labels.gitea,
+        };
+
+        Ok(forge
+            .issues(&self.client, labels)?
+            .into_iter()
+            .map(issue_to_opportunity)
+            .collect())
+    }
+}
+
+/// Issues labeled as wanting help are [`ContributionKind::HelpWanted`];
+/// bugs and priority issues are [`ContributionKind::PriorityIssue`].
+fn issue_to_opportunity(issue: ForgeIssue) -> ContributionOpportunity {
+    let labels = issue.label_names();
+    let kind = if labels.iter().any(|l| is_help_wanted(l)) {
+        ContributionKind::HelpWanted
+    } else {
+        ContributionKind::PriorityIssue
+    };
+    let description = Some(labels.join(", "));
+    ContributionOpportunity {
+        kind,
+        title: issue.title,
+        description,
+        url: issue.html_url,
+    }
+}
+
+fn is_help_wanted(label: &str) -> bool {
+    let label = label.to_lowercase().replace(['-', '_'], " ");
+    label.contains("help wanted")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_by_label() {
+        let json = r#"[
+            {
+                "title": "Support Windows paths",
+                "html_url": "https://github.com/owner/repo/issues/1",
+                "labels": [{"name": "help wanted"}, {"name": "enhancement"}]
+            },
+            {
+                "title": "Crash on empty input",
+                "html_url": "https://github.com/owner/repo/issues/2",
+                "labels": [{"name": "Bug"}]
+            },
+            {
+                "title": "Memory leak in parser",
+                "web_url": "https://gitlab.com/group/project/-/issues/5",
+                "labels": ["priority::high", "Help-Wanted"]
+            }
+        ]"#;
+        let issues: Vec<ForgeIssue> = serde_json::from_str(json).unwrap();
+        let opportunities: Vec<_> = issues.into_iter().map(issue_to_opportunity).collect();
+
+        assert_eq!(opportunities[0].kind, ContributionKind::HelpWanted);
+        assert_eq!(
+            opportunities[0].description.as_deref(),
+            Some("help wanted, enhancement")
+        );
+        assert_eq!(opportunities[1].kind, ContributionKind::PriorityIssue);
+        assert_eq!(
+            opportunities[1].url,
+            "https://github.com/owner/repo/issues/2"
+        );
+        assert_eq!(opportunities[2].kind, ContributionKind::HelpWanted);
+    }
+
+    #[test]
+    fn skips_projects_on_other_hosts() {
+        let backend = HelpWantedIssuesBackend {
+            client: GitHubClient::new(None).unwrap(),
+            labels: IssueLabelsConfig::default(),
+        };
+        let project = UpstreamProject {
+            name: "test".to_string(),
+            repo_url: Some("https://git.sr.ht/~owner/repo".to_string()),
+            ..Default::default()
+        };
+        assert!(backend.find_opportunities(&project).unwrap().is_empty());
+    }
+}
@@ -64,7 +64,8 @@
 //!
 //! # Example
 //!
-//! A minimal backend that suggests starring GitHub repositories:
+//! A minimal backend that suggests starring GitHub repositories (the real
+//! [`github_stars::GitHubStarsBackend`] also skips those already starred):
 //!
 //! ```rust,ignore
 //! use anyhow::Result;
@@ -79,12 +80,8 @@
 //!     }
 //!
 //!     fn is_available(&self) -> bool {
-//!         // Check that the `gh` CLI is authenticated (cheap subprocess call).
-//!         std::process::Command::new("gh")
-//!             .args(["auth", "status"])
-//!             .output()
-//!             .map(|o| o.status.success())
-//!             .unwrap_or(false)
+//!         // Starring requires an authenticated user (cheap env lookup).
+//!         std::env::var_os("GITHUB_TOKEN").is_some()
 //!     }
 //!
 //!     fn find_opportunities(
@@ -109,12 +106,30 @@
 //! See the parent issue <https://github.com/bombfork/syld/issues/26> for
 //! the full design context.
 
+pub mod distro_packaging;
+pub mod documentation;
+mod forge_issues;
+pub mod gitea_issues;
 pub mod github_good_first_issues;
+pub mod github_stars;
+pub mod gitlab_good_first_issues;
+pub mod hacktoberfest;
+pub mod help_wanted_issues;
+pub mod past_contributions;
+pub mod polar_funded_issues;
+pub mod skills;
+pub mod spread_the_word;
+pub mod translation;
+
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
+use indicatif::{ProgressBar, ProgressStyle};
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
+use crate::github::GitHubClient;
 use crate::project::UpstreamProject;
 
 /// The kind of non-monetary contribution a user can make.
@@ -124,6 +139,12 @@ pub enum ContributionKind {
     Star,
     /// Work on a beginner-friendly issue.
     GoodFirstIssue,
+    /// Work on an issue the maintainers asked for help with.
+    HelpWanted,
+    /// Work on a bug or an issue the maintainers flagged as a priority.
+    PriorityIssue,
+    /// Work on an issue that backers have pledged money towards.
+    FundedIssue,
     /// Report a bug through the project's issue tracker.
     BugReport,
     /// Help translate the project into other languages.
@@ -132,6 +153,10 @@ pub enum ContributionKind {
     Documentation,
     /// Share the project on social media or a blog.
     SpreadTheWord,
+    /// Package the project for a distribution, or update its package.
+    Packaging,
+    /// Host a mirror of the project's downloads.
+    Mirroring,
 }
 
 impl std::fmt::Display for ContributionKind {
@@ -139,14 +164,59 @@ impl std::fmt::Display for ContributionKind {
         match self {
             ContributionKind::Star => write!(f, "star"),
             ContributionKind::GoodFirstIssue => write!(f, "good first issue"),
+            ContributionKind::HelpWanted => write!(f, "help wanted"),
+            ContributionKind::PriorityIssue => write!(f, "priority issue"),
+            ContributionKind::FundedIssue => write!(f, "funded issue"),
             ContributionKind::BugReport => write!(f, "bug report"),
             ContributionKind::Translation => write!(f, "translation"),
             ContributionKind::Documentation => write!(f, "documentation"),
             ContributionKind::SpreadTheWord => write!(f, "spread the word"),
+            ContributionKind::Packaging => write!(f, "packaging"),
+            ContributionKind::Mirroring => write!(f, "mirroring"),
         }
     }
 }
 
+impl ContributionKind {
+    /// Every kind, in display order.
+    pub const ALL: [ContributionKind; 11] = [
+        ContributionKind::Star,
+        ContributionKind::GoodFirstIssue,
+        ContributionKind::HelpWanted,
+        ContributionKind::PriorityIssue,
+        ContributionKind::FundedIssue,
+        ContributionKind::BugReport,
+        ContributionKind::Translation,
+        ContributionKind::Documentation,
+        ContributionKind::SpreadTheWord,
+        ContributionKind::Packaging,
+        ContributionKind::Mirroring,
+    ];
+}
+
+/// Parses the [`Display`](std::fmt::Display) form, with hyphens allowed for
+/// spaces (`good-first-issue`), as taken by `syld contribute --kind`.
+impl std::str::FromStr for ContributionKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let wanted = s.trim().to_lowercase().replace(['-', '_'], " ");
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.to_string() == wanted)
+            .ok_or_else(|| {
+                let kinds: Vec<String> = Self::ALL
+                    .iter()
+                    .map(|k| k.to_string().replace(' ', "-"))
+                    .collect();
+                anyhow::anyhow!(
+                    "unknown contribution kind '{s}' (expected one of: {})",
+                    kinds.join(", ")
+                )
+            })
+    }
+}
+
 /// A concrete opportunity to contribute to an upstream project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContributionOpportunity {
@@ -188,6 +258,12 @@ pub trait ContributionBackend {
     /// API token or a CLI tool.
     fn is_available(&self) -> bool;
 
+    /// Whether this backend works from local data alone. Only these run in
+    /// offline mode.
+    fn is_offline(&self) -> bool {
+        false
+    }
+
     /// Discovers contribution opportunities for the given upstream project.
     ///
     /// Backends should inspect the project's metadata (repo URL, bug tracker,
@@ -208,7 +284,8 @@ pub trait ContributionBackend {
 ///
 /// Every known backend is instantiated and then filtered through
 /// [`ContributionBackend::is_available()`]. Only backends that can operate
-/// are returned.
+/// are returned, and in offline mode only
+/// [offline](ContributionBackend::is_offline) ones.
 ///
 /// # Registering a new backend
 ///
@@ -216,21 +293,220 @@ pub trait ContributionBackend {
 /// `Box::new(YourBackend)` entry to the `candidates` vector below. The new
 /// backend will automatically be included whenever its
 /// [`is_available()`](ContributionBackend::is_available) check passes.
-pub fn active_backends(_config: &Config) -> Vec<Box<dyn ContributionBackend>> {
-    let candidates: Vec<Box<dyn ContributionBackend>> = vec![Box::new(
-        github_good_first_issues::GitHubGoodFirstIssuesBackend,
-    )];
+pub fn active_backends(config: &Config) -> Vec<Box<dyn ContributionBackend>> {
+    let mut candidates: Vec<Box<dyn ContributionBackend>> = Vec::new();
+    match GitHubClient::from_config(config) {
+        _ if config.offline => {}
+        Ok(client) => candidates.push(Box::new(
+            github_good_first_issues::GitHubGoodFirstIssuesBackend { client },
+        )),
+        Err(e) => eprintln!("Warning: GitHub contributions unavailable: {e}"),
+    }
+    if !config.offline
+        && let Ok(client) = GitHubClient::from_config(config)
+    {
+        candidates.push(Box::new(github_stars::GitHubStarsBackend {
+            client,
+            starred: HashSet::new(),
+        }));
+    }
+    if !config.offline
+        && let Ok(client) = GitHubClient::from_config(config)
+    {
+        candidates.push(Box::new(help_wanted_issues::HelpWantedIssuesBackend {
+            client,
+            labels: config.issue_labels.clone(),
+        }));
+    }
+    if !config.offline
+        && let Ok(client) = GitHubClient::from_config(config)
+    {
+        candidates.push(Box::new(documentation::DocumentationBackend { client }));
+    }
+    candidates.push(Box::new(
+        gitlab_good_first_issues::GitLabGoodFirstIssuesBackend,
+    ));
+    candidates.push(Box::new(gitea_issues::GiteaIssuesBackend));
+    candidates.push(Box::new(polar_funded_issues::PolarFundedIssuesBackend));
+    candidates.push(Box::new(translation::TranslationBackend::from_config(
+        config,
+    )));
+    candidates.push(Box::new(spread_the_word::SpreadTheWordBackend));
+    candidates.push(Box::new(
+        distro_packaging::DistroPackagingBackend::from_config(config),
+    ));
 
     candidates
         .into_iter()
-        .filter(|b| b.is_available())
+        .filter(|b| (b.is_offline() || !config.offline) && b.is_available())
         .collect()
 }
 
+/// What the user did about an opportunity, recorded by URL with
+/// `syld contribute done` and `syld contribute dismiss`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpportunityState {
+    /// Not acted upon yet.
+    #[default]
+    New,
+    /// Not interesting to the user.
+    Dismissed,
+    /// Acted upon: the issue was worked on, the translation done, etc.
+    Done,
+}
+
+impl OpportunityState {
+    /// The name stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OpportunityState::New => "new",
+            OpportunityState::Dismissed => "dismissed",
+            OpportunityState::Done => "done",
+        }
+    }
+
+    /// Parse a name stored by [`as_str`](Self::as_str).
+    pub fn from_db(s: &str) -> Option<Self> {
+        match s {
+            "new" => Some(OpportunityState::New),
+            "dismissed" => Some(OpportunityState::Dismissed),
+            "done" => Some(OpportunityState::Done),
+            _ => None,
+        }
+    }
+}
+
+/// Which opportunities [`find_all_opportunities`] keeps. Done opportunities
+/// are always left out.
+#[derive(Default)]
+pub struct OpportunityFilter {
+    /// Only keep opportunities of this kind.
+    pub kind: Option<ContributionKind>,
+
+    /// Recorded states by opportunity URL.
+    pub states: HashMap<String, OpportunityState>,
+
+    /// Also leave out dismissed opportunities.
+    pub hide_dismissed: bool,
+
+    /// Only keep opportunities matching the user's skills.
+    pub skills: skills::SkillFilter,
+}
+
+impl OpportunityFilter {
+    fn state(&self, url: &str) -> OpportunityState {
+        self.states.get(url).copied().unwrap_or_default()
+    }
+
+    fn keeps(&self, opportunity: &ContributionOpportunity) -> bool {
+        let wanted_kind = self.kind.as_ref().is_none_or(|k| *k == opportunity.kind);
+        let wanted_state = match self.state(&opportunity.url) {
+            OpportunityState::New => true,
+            OpportunityState::Dismissed => !self.hide_dismissed,
+            OpportunityState::Done => false,
+        };
+        wanted_kind && wanted_state
+    }
+}
+
+/// A contribution opportunity together with the project it is for.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectOpportunity {
+    /// Name of the upstream project.
+    pub project: String,
+
+    #[serde(flatten)]
+    pub opportunity: ContributionOpportunity,
+
+    /// What the user did about it so far.
+    pub state: OpportunityState,
+}
+
+/// Query every backend for every project, keeping the opportunities that
+/// `filter` keeps, each once. Stops after `limit` opportunities (0 for no
+/// limit). Backend failures are reported and skipped.
+pub fn find_all_opportunities(
+    backends: &[Box<dyn ContributionBackend>],
+    projects: &[UpstreamProject],
+    filter: &OpportunityFilter,
+    limit: usize,
+) -> Vec<ProjectOpportunity> {
+    let pb = ProgressBar::new(projects.len() as u64);
+    pb.set_style(
+        ProgressStyle::with_template("Finding contributions [{bar:30}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+
+    let mut found: Vec<ProjectOpportunity> = Vec::new();
+    'projects: for project in projects {
+        pb.set_message(project.name.clone());
+        let profile = OnceCell::new();
+        let lookup_profile = || match filter.skills.profile(project) {
+            Ok(profile) => profile,
+            Err(e) => {
+                pb.suspend(|| eprintln!("Warning: could not look up {}: {e}", project.name));
+                None
+            }
+        };
+        for backend in backends {
+            let opportunities = match backend.find_opportunities(project) {
+                Ok(opportunities) => opportunities,
+                Err(e) => {
+                    pb.suspend(|| {
+                        eprintln!(
+                            "Warning: {} failed for {}: {e}",
+                            backend.name(),
+                            project.name
+                        )
+                    });
+                    continue;
+                }
+            };
+            for opportunity in opportunities {
+                let duplicate = found.iter().any(|f| {
+                    f.opportunity.kind == opportunity.kind && f.opportunity.url == opportunity.url
+                });
+                if !filter.keeps(&opportunity)
+                    || duplicate
+                    || !filter.skills.fits(&opportunity, &profile, lookup_profile)
+                {
+                    continue;
+                }
+                found.push(ProjectOpportunity {
+                    project: project.name.clone(),
+                    state: filter.state(&opportunity.url),
+                    opportunity,
+                });
+                if found.len() == limit {
+                    break 'projects;
+                }
+            }
+        }
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+    found
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn contribution_kind_from_str() {
+        for kind in ContributionKind::ALL {
+            assert_eq!(kind.to_string().parse::<ContributionKind>().unwrap(), kind);
+        }
+        assert_eq!(
+            "good-first-issue".parse::<ContributionKind>().unwrap(),
+            ContributionKind::GoodFirstIssue
+        );
+        let err = "fame".parse::<ContributionKind>().unwrap_err();
+        assert!(err.to_string().contains("spread-the-word"));
+    }
+
     #[test]
     fn contribution_kind_display() {
         assert_eq!(ContributionKind::Star.to_string(), "star");
@@ -238,6 +514,12 @@ mod tests {
             ContributionKind::GoodFirstIssue.to_string(),
             "good first issue"
         );
+        assert_eq!(ContributionKind::HelpWanted.to_string(), "help wanted");
+        assert_eq!(
+            ContributionKind::PriorityIssue.to_string(),
+            "priority issue"
+        );
+        assert_eq!(ContributionKind::FundedIssue.to_string(), "funded issue");
         assert_eq!(ContributionKind::BugReport.to_string(), "bug report");
         assert_eq!(ContributionKind::Translation.to_string(), "translation");
         assert_eq!(ContributionKind::Documentation.to_string(), "documentation");
@@ -245,14 +527,18 @@ mod tests {
             ContributionKind::SpreadTheWord.to_string(),
             "spread the word"
         );
+        assert_eq!(ContributionKind::Packaging.to_string(), "packaging");
+        assert_eq!(ContributionKind::Mirroring.to_string(), "mirroring");
     }
 
     #[test]
     fn contribution_kind_ordering() {
         // Enum variants should have a stable ordering for consistent display.
         assert!(ContributionKind::Star < ContributionKind::GoodFirstIssue);
-        assert!(ContributionKind::GoodFirstIssue < ContributionKind::BugReport);
+        assert!(ContributionKind::GoodFirstIssue < ContributionKind::FundedIssue);
+        assert!(ContributionKind::FundedIssue < ContributionKind::BugReport);
         assert!(ContributionKind::Documentation < ContributionKind::SpreadTheWord);
+        assert!(ContributionKind::SpreadTheWord < ContributionKind::Packaging);
     }
 
     #[test]
@@ -332,15 +618,7 @@ mod tests {
         let project = UpstreamProject {
             name: "test-project".to_string(),
             repo_url: Some("https://github.com/example/repo".to_string()),
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let opportunities = backend.find_opportunities(&project).unwrap();
@@ -361,13 +639,157 @@ mod tests {
         assert_eq!(active.len(), 2);
     }
 
+    /// Backend suggesting one issue per project.
+    struct IssuePerProject;
+
+    impl ContributionBackend for IssuePerProject {
+        fn name(&self) -> &str {
+            "issue_per_project"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn find_opportunities(
+            &self,
+            project: &UpstreamProject,
+        ) -> Result<Vec<ContributionOpportunity>> {
+            Ok(vec![ContributionOpportunity {
+                kind: ContributionKind::GoodFirstIssue,
+                title: format!("Fix {}", project.name),
+                description: None,
+                url: format!("https://example.com/{}/issues/1", project.name),
+            }])
+        }
+    }
+
+    fn named_project(name: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_all_opportunities_filters_and_deduplicates() {
+        let backends: Vec<Box<dyn ContributionBackend>> = vec![
+            Box::new(MockBackend { available: true }),
+            Box::new(IssuePerProject),
+        ];
+        let projects = vec![named_project("curl"), named_project("gimp")];
+
+        let all = find_all_opportunities(&backends, &projects, &OpportunityFilter::default(), 0);
+        let found: Vec<_> = all
+            .iter()
+            .map(|f| (f.project.as_str(), f.opportunity.kind.clone()))
+            .collect();
+        // The mock suggests the same star for every project.
+        assert_eq!(
+            found,
+            [
+                ("curl", ContributionKind::Star),
+                ("curl", ContributionKind::GoodFirstIssue),
+                ("gimp", ContributionKind::GoodFirstIssue),
+            ]
+        );
+
+        let filter = OpportunityFilter {
+            kind: Some(ContributionKind::GoodFirstIssue),
+            ..Default::default()
+        };
+        let issues = find_all_opportunities(&backends, &projects, &filter, 1);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].opportunity.title, "Fix curl");
+    }
+
+    #[test]
+    fn find_all_opportunities_honors_states() {
+        let backends: Vec<Box<dyn ContributionBackend>> = vec![Box::new(IssuePerProject)];
+        let projects = vec![
+            named_project("curl"),
+            named_project("gimp"),
+            named_project("vim"),
+        ];
+        let mut filter = OpportunityFilter {
+            states: HashMap::from([
+                (
+                    "https://example.com/curl/issues/1".to_string(),
+                    OpportunityState::Done,
+                ),
+                (
+                    "https://example.com/gimp/issues/1".to_string(),
+                    OpportunityState::Dismissed,
+                ),
+            ]),
+            ..Default::default()
+        };
+
+        let found = find_all_opportunities(&backends, &projects, &filter, 0);
+        let states: Vec<_> = found
+            .iter()
+            .map(|f| (f.project.as_str(), f.state))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("gimp", OpportunityState::Dismissed),
+                ("vim", OpportunityState::New)
+            ]
+        );
+
+        filter.hide_dismissed = true;
+        let found = find_all_opportunities(&backends, &projects, &filter, 0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].project, "vim");
+    }
+
+    #[test]
+    fn project_opportunity_json_is_flat() {
+        let found = ProjectOpportunity {
+            project: "curl".to_string(),
+            opportunity: IssuePerProject
+                .find_opportunities(&named_project("curl"))
+                .unwrap()
+                .remove(0),
+            state: OpportunityState::Dismissed,
+        };
+        let json = serde_json::to_value(&found).unwrap();
+        assert_eq!(json["project"], "curl");
+        assert_eq!(json["kind"], "GoodFirstIssue");
+        assert_eq!(json["url"], "https://example.com/curl/issues/1");
+        assert_eq!(json["state"], "dismissed");
+    }
+
     #[test]
     fn active_backends_returns_registered_backends() {
         let config = Config::default();
         let backends = active_backends(&config);
-        // At least one backend is registered (GitHub good first issues).
-        // Whether it appears depends on whether `gh` is authenticated,
-        // so we just verify the call doesn't panic.
-        let _ = backends;
+        // The GitHub good-first-issues backend works without a token.
+        assert!(
+            backends
+                .iter()
+                .any(|b| b.name() == "github_good_first_issues")
+        );
+        assert!(
+            backends
+                .iter()
+                .any(|b| b.name() == "gitlab_good_first_issues")
+        );
+        assert!(backends.iter().any(|b| b.name() == "gitea_issues"));
+        assert!(backends.iter().any(|b| b.name() == "help_wanted_issues"));
+        assert!(backends.iter().any(|b| b.name() == "documentation"));
+        assert!(backends.iter().any(|b| b.name() == "polar_funded_issues"));
+    }
+
+    #[test]
+    fn active_backends_offline_skips_network_backends() {
+        let config = Config {
+            offline: true,
+            ..Default::default()
+        };
+        let backends = active_backends(&config);
+        let names: Vec<_> = backends.iter().map(|b| b.name()).collect();
+        assert_eq!(names, ["spread_the_word"]);
     }
 }
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Contributions the user already made to their dependencies.
+//!
+//! Looks up the issues and pull requests the authenticated GitHub user
+//! opened, and keeps those in the repositories of the projects they depend
+//! on. With `past_contributions = true` in the `[contribute]` config,
+//! `syld contribute` records them in the database, and `syld report` tells
+//! how many dependencies the user has contributed to.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use super::github_good_first_issues::extract_github_owner_repo;
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+
+/// Results per page of the search API, its maximum.
+const PER_PAGE: usize = 100;
+
+/// The search API returns at most 1000 results.
+const MAX_PAGES: usize = 10;
+
+/// An issue or pull request the user opened in a dependency's repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastContribution {
+    /// GitHub repository (`owner/repo`, lowercase).
+    pub repo: String,
+    pub title: String,
+    pub url: String,
+    pub is_pull_request: bool,
+}
+
+/// Backend that finds the user's issues and pull requests on GitHub.
+pub struct PastContributionsBackend {
+    pub client: GitHubClient,
+}
+
+/// The part of `GET /user` used here.
+#[derive(Debug, Deserialize)]
+struct User {
+    login: String,
+}
+
+/// A page of `GET /search/issues`.
+#[derive(Debug, Deserialize)]
+struct SearchPage {
+    items: Vec<SearchItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchItem {
+    title: String,
+    html_url: String,
+    /// `https://api.github.com/repos/{owner}/{repo}`.
+    repository_url: String,
+    pull_request: Option<serde_json::Value>,
+}
+
+impl PastContributionsBackend {
+    /// Who the user is on GitHub is only known with a token.
+    pub fn is_available(&self) -> bool {
+        self.client.has_token()
+    }
+
+    /// The issues and pull requests the user opened in the GitHub
+    /// repositories of `projects`.
+    pub fn find(&self, projects: &[UpstreamProject]) -> Result<Vec<PastContribution>> {
+        let Some(user) = self.client.get_json::<User>("user")? else {
+            return Ok(Vec::new());
+        };
+
+        let mut items = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let Some(found) = self.client.get_json::<SearchPage>(&format!(
+                "search/issues?q=author:{}&per_page={PER_PAGE}&page={page}",
+                user.login
+            ))?
+            else {
+                break;
+            };
+            let last = found.items.len() < PER_PAGE;
+            items.extend(found.items);
+            if last {
+                break;
+            }
+        }
+
+        Ok(in_dependencies(items, &dependency_repos(projects)))
+    }
+}
+
+/// The GitHub repositories (`owner/repo`, lowercase) of `projects`.
+fn dependency_repos(projects: &[UpstreamProject]) -> HashSet<String> {
+    projects
+        .iter()
+        .filter_map(|p| p.repo_url.as_deref().and_then(extract_github_owner_repo))
+        .map(|r| r.to_lowercase())
+        .collect()
+}
+
+/// The search results in one of `repos`.
+fn in_dependencies(items: Vec<SearchItem>, repos: &HashSet<String>) -> Vec<PastContribution> {
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let repo = item.repository_url.split_once("/repos/")?.1.to_lowercase();
+            repos.contains(&repo).then(|| PastContribution {
+                repo,
+                title: item.title,
+                url: item.html_url,
+                is_pull_request: item.pull_request.is_some(),
+            })
+        })
+        .collect()
+}
+
+/// How many of `projects` have their GitHub repository in `contributed`
+/// (`owner/repo`, lowercase).
+pub fn count_contributed(projects: &[UpstreamProject], contributed: &HashSet<String>) -> usize {
+    projects
+        .iter()
+        .filter_map(|p| p.repo_url.as_deref().and_then(extract_github_owner_repo))
+        .filter(|r| contributed.contains(&r.to_lowercase()))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(repo_url: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: "test".to_string(),
+            repo_url: Some(repo_url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keeps_results_in_dependencies() {
+        let json = r#"{
+            "total_count": 3,
+            "items": [
+                {
+                    "title": "Fix typo in man page",
+                    "html_url": "https://github.com/curl/curl/pull/42",
+                    "repository_url": "https://api.github.com/repos/curl/curl",
+                    "pull_request": {"url": "https://api.github.com/repos/curl/curl/pulls/42"}
+                },
+                {
+                    "title": "Crash on startup",
+                    "html_url": "https://github.com/BurntSushi/ripgrep/issues/7",
+                    "repository_url": "https://api.github.com/repos/BurntSushi/ripgrep"
+                },
+                {
+                    "title": "My own project",
+                    "html_url": "https://github.com/me/dotfiles/issues/1",
+                    "repository_url": "https://api.github.com/repos/me/dotfiles"
+                }
+            ]
+        }"#;
+        let page: SearchPage = serde_json::from_str(json).unwrap();
+        let repos = dependency_repos(&[
+            project("https://github.com/curl/curl"),
+            project("https://github.com/BurntSushi/ripgrep"),
+        ]);
+
+        let found = in_dependencies(page.items, &repos);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].repo, "curl/curl");
+        assert!(found[0].is_pull_request);
+        assert_eq!(found[1].repo, "burntsushi/ripgrep");
+        assert!(!found[1].is_pull_request);
+    }
+
+    #[test]
+    fn counts_contributed_projects() {
+        let projects = vec![
+            project("https://github.com/curl/curl"),
+            project("https://github.com/BurntSushi/ripgrep"),
+            project("https://gitlab.gnome.org/GNOME/gtk"),
+        ];
+        let contributed = HashSet::from(["burntsushi/ripgrep".to_string()]);
+        assert_eq!(count_contributed(&projects, &contributed), 1);
+    }
+
+    #[test]
+    fn unavailable_without_token() {
+        let backend = PastContributionsBackend {
+            client: GitHubClient::new(None).unwrap(),
+        };
+        assert!(!backend.is_available());
+    }
+}
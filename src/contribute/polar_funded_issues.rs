@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Polar funded-issues contribution backend.
+//!
+//! Discovers issues of GitHub repositories that backers have pledged money
+//! towards on Polar, along with the amount pledged and the funding goal.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::github_good_first_issues::extract_github_owner_repo;
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::enrich::net::{self, RequestExt};
+use crate::enrich::polar::API_BASE;
+use crate::project::UpstreamProject;
+
+/// Backend that discovers issues with Polar funding from GitHub repos.
+pub struct PolarFundedIssuesBackend;
+
+/// A page of issues from `GET /issues/search`.
+#[derive(Debug, Deserialize)]
+struct PolarIssues {
+    #[serde(default)]
+    items: Vec<PolarIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolarIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    funding: PolarFunding,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PolarFunding {
+    funding_goal: Option<PolarAmount>,
+    pledges_sum: Option<PolarAmount>,
+}
+
+/// A monetary amount in the smallest unit of its currency (e.g. cents).
+#[derive(Debug, Deserialize)]
+struct PolarAmount {
+    currency: String,
+    amount: u64,
+}
+
+impl ContributionBackend for PolarFundedIssuesBackend {
+    fn name(&self) -> &str {
+        "polar_funded_issues"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        // Polar only funds issues of projects with a Polar page.
+        if !project.funding.iter().any(|f| f.platform == "Polar") {
+            return Ok(Vec::new());
+        }
+
+        let owner_repo = match project
+            .repo_url
+            .as_deref()
+            .and_then(extract_github_owner_repo)
+        {
+            Some(or) => or,
+            None => return Ok(Vec::new()),
+        };
+        let (owner, repo) = owner_repo.split_once('/').unwrap_or_default();
+
+        let response = net::client()
+            .get(format!("{API_BASE}/issues/search"))
+            .query(&[
+                ("platform", "github"),
+                ("organization_name", owner),
+                ("repository_name", repo),
+                ("have_pledge", "true"),
+            ])
+            .send_paced()
+            .context("Failed to query Polar")?;
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let issues: PolarIssues = response.json().context("Failed to parse Polar response")?;
+
+        Ok(issues_to_opportunities(&owner_repo, issues))
+    }
+}
+
+/// Convert Polar issues into contribution opportunities, skipping issues
+/// nobody has pledged to yet.
+fn issues_to_opportunities(owner_repo: &str, issues: PolarIssues) -> Vec<ContributionOpportunity> {
+    issues
+        .items
+        .into_iter()
+        .filter_map(|issue| {
+            let pledged = issue.funding.pledges_sum.filter(|p| p.amount > 0)?;
+            let description = match &issue.funding.funding_goal {
+                Some(goal) => format!(
+                    "{} pledged of {} goal",
+                    format_amount(&pledged),
+                    format_amount(goal)
+                ),
+                None => format!("{} pledged", format_amount(&pledged)),
+            };
+            Some(ContributionOpportunity {
+                kind: ContributionKind::FundedIssue,
+                title: issue.title,
+                description: Some(description),
+                url: format!("https://github.com/{owner_repo}/issues/{}", issue.number),
+            })
+        })
+        .collect()
+}
+
+/// Format an amount in minor units as e.g. `$12.50` or `12.50 EUR`.
+fn format_amount(amount: &PolarAmount) -> String {
+    let value = format!("{}.{:02}", amount.amount / 100, amount.amount % 100);
+    match amount.currency.to_lowercase().as_str() {
+        "usd" => format!("${value}"),
+        currency => format!("{value} {}", currency.to_uppercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_pledged_issues() {
+        let json = r#"{"items": [
+            {
+                "id": "a1",
+                "number": 1234,
+                "title": "Support Python 3.13",
+                "funding": {
+                    "funding_goal": {"currency": "usd", "amount": 50000},
+                    "pledges_sum": {"currency": "usd", "amount": 12550}
+                }
+            },
+            {
+                "id": "a2",
+                "number": 99,
+                "title": "Windows ARM builds",
+                "funding": {
+                    "funding_goal": null,
+                    "pledges_sum": {"currency": "eur", "amount": 2000}
+                }
+            },
+            {
+                "id": "a3",
+                "number": 7,
+                "title": "Badged but unfunded",
+                "funding": {"funding_goal": null, "pledges_sum": {"currency": "usd", "amount": 0}}
+            },
+            {"id": "a4", "number": 8, "title": "No funding info"}
+        ]}"#;
+        let issues: PolarIssues = serde_json::from_str(json).unwrap();
+        let opportunities = issues_to_opportunities("astral-sh/ruff", issues);
+
+        assert_eq!(opportunities.len(), 2);
+        assert_eq!(opportunities[0].kind, ContributionKind::FundedIssue);
+        assert_eq!(opportunities[0].title, "Support Python 3.13");
+        assert_eq!(
+            opportunities[0].description.as_deref(),
+            Some("$125.50 pledged of $500.00 goal")
+        );
+        assert_eq!(
+            opportunities[0].url,
+            "https://github.com/astral-sh/ruff/issues/1234"
+        );
+        assert_eq!(
+            opportunities[1].description.as_deref(),
+            Some("20.00 EUR pledged")
+        );
+    }
+
+    #[test]
+    fn skips_projects_without_polar_page() {
+        let project = UpstreamProject {
+            name: "ruff".to_string(),
+            repo_url: Some("https://github.com/astral-sh/ruff".to_string()),
+            ..Default::default()
+        };
+        let backend = PolarFundedIssuesBackend;
+        assert!(backend.find_opportunities(&project).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Matching contribution opportunities to the user's skills.
+//!
+//! The `[contribute]` config section lists the programming languages the
+//! user writes, the topics they care about, and the hardest work they want
+//! suggested. Issues are matched on the main language of their repository,
+//! every opportunity on the repository's topics and on its difficulty.
+//! Repositories whose language or topics cannot be looked up are not left
+//! out.
+
+use std::cell::OnceCell;
+
+use anyhow::Result;
+
+use super::forge_issues::{Forge, RepoProfile};
+use super::{ContributionKind, ContributionOpportunity};
+use crate::config::{Config, ContributeConfig, Difficulty};
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+
+/// Labels that state the difficulty of an issue, after any `difficulty:` or
+/// `E-` prefix, by difficulty.
+const DIFFICULTY_LABELS: &[(&str, Difficulty)] = &[
+    ("easy", Difficulty::Easy),
+    ("beginner", Difficulty::Easy),
+    ("medium", Difficulty::Medium),
+    ("intermediate", Difficulty::Medium),
+    ("hard", Difficulty::Hard),
+    ("advanced", Difficulty::Hard),
+    ("expert", Difficulty::Hard),
+];
+
+/// Filters opportunities on the `[contribute]` config section.
+#[derive(Default)]
+pub struct SkillFilter {
+    pub config: ContributeConfig,
+
+    /// Client used to look up repositories; `None` offline, where languages
+    /// and topics are unknown.
+    pub client: Option<GitHubClient>,
+}
+
+impl SkillFilter {
+    pub fn from_config(config: &Config) -> Self {
+        let client = if config.offline {
+            None
+        } else {
+            GitHubClient::from_config(config).ok()
+        };
+        Self {
+            config: config.contribute.clone(),
+            client,
+        }
+    }
+
+    /// Whether `opportunity` matches the user's skills. The repository
+    /// `profile` is only looked up, with `lookup`, when languages or topics
+    /// are configured.
+    pub(super) fn fits(
+        &self,
+        opportunity: &ContributionOpportunity,
+        profile: &OnceCell<Option<RepoProfile>>,
+        lookup: impl FnOnce() -> Option<RepoProfile>,
+    ) -> bool {
+        if self
+            .config
+            .max_difficulty
+            .is_some_and(|max| difficulty(opportunity) > max)
+        {
+            return false;
+        }
+
+        let checks_language = !self.config.languages.is_empty() && is_code(&opportunity.kind);
+        let checks_topics = !self.config.topics.is_empty();
+        if !checks_language && !checks_topics {
+            return true;
+        }
+        let Some(profile) = profile.get_or_init(lookup) else {
+            return true;
+        };
+        let language_fits = !checks_language
+            || profile
+                .language
+                .as_deref()
+                .is_none_or(|l| matches_any(&self.config.languages, l));
+        let topics_fit = !checks_topics
+            || profile.topics.is_empty()
+            || profile
+                .topics
+                .iter()
+                .any(|t| matches_any(&self.config.topics, t));
+        language_fits && topics_fit
+    }
+
+    /// The language and topics of the repository of `project`, `None` when
+    /// offline or not on a supported forge.
+    pub(super) fn profile(&self, project: &UpstreamProject) -> Result<Option<RepoProfile>> {
+        let (Some(client), Some(forge)) = (
+            &self.client,
+            project.repo_url.as_deref().and_then(Forge::from_url),
+        ) else {
+            return Ok(None);
+        };
+        forge.profile(client)
+    }
+}
+
+/// Kinds of opportunity that involve writing code.
+fn is_code(kind: &ContributionKind) -> bool {
+    matches!(
+        kind,
+        ContributionKind::GoodFirstIssue
+            | ContributionKind::HelpWanted
+            | ContributionKind::PriorityIssue
+            | ContributionKind::FundedIssue
+    )
+}
+
+fn matches_any(wanted: &[String], value: &str) -> bool {
+    wanted.iter().any(|w| w.eq_ignore_ascii_case(value))
+}
+
+/// How hard an opportunity is: stated by a difficulty label among those in
+/// its description, or else typical of its kind.
+pub fn difficulty(opportunity: &ContributionOpportunity) -> Difficulty {
+    let labelled = opportunity
+        .description
+        .as_deref()
+        .into_iter()
+        .flat_map(|d| d.split(','))
+        .find_map(label_difficulty);
+    labelled.unwrap_or(match opportunity.kind {
+        ContributionKind::Star
+        | ContributionKind::GoodFirstIssue
+        | ContributionKind::BugReport
+        | ContributionKind::SpreadTheWord => Difficulty::Easy,
+        ContributionKind::HelpWanted
+        | ContributionKind::Translation
+        | ContributionKind::Documentation
+        | ContributionKind::Packaging
+        | ContributionKind::Mirroring => Difficulty::Medium,
+        ContributionKind::PriorityIssue | ContributionKind::FundedIssue => Difficulty::Hard,
+    })
+}
+
+/// The difficulty a label such as `difficulty: hard`, `E-easy`, or
+/// `Difficulty/Medium` states.
+fn label_difficulty(label: &str) -> Option<Difficulty> {
+    let label = label.trim().to_lowercase();
+    let label = label
+        .strip_prefix("e-")
+        .or_else(|| label.rsplit_once([':', '/']).map(|(_, l)| l))
+        .unwrap_or(&label)
+        .trim();
+    DIFFICULTY_LABELS
+        .iter()
+        .find(|(name, _)| *name == label)
+        .map(|(_, difficulty)| *difficulty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opportunity(kind: ContributionKind, description: Option<&str>) -> ContributionOpportunity {
+        ContributionOpportunity {
+            kind,
+            title: "Fix the parser".to_string(),
+            description: description.map(str::to_string),
+            url: "https://github.com/owner/repo/issues/1".to_string(),
+        }
+    }
+
+    fn profile(language: &str, topics: &[&str]) -> RepoProfile {
+        RepoProfile {
+            language: Some(language.to_string()),
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn filter(languages: &[&str], topics: &[&str]) -> SkillFilter {
+        SkillFilter {
+            config: ContributeConfig {
+                languages: languages.iter().map(|l| l.to_string()).collect(),
+                topics: topics.iter().map(|t| t.to_string()).collect(),
+                max_difficulty: None,
+                distro_repo: None,
+                past_contributions: false,
+            },
+            client: None,
+        }
+    }
+
+    #[test]
+    fn difficulty_from_labels_or_kind() {
+        let labelled =
+            |labels: &str| difficulty(&opportunity(ContributionKind::HelpWanted, Some(labels)));
+        assert_eq!(labelled("help wanted, difficulty: hard"), Difficulty::Hard);
+        assert_eq!(labelled("E-easy, A-parser"), Difficulty::Easy);
+        assert_eq!(labelled("Difficulty/Medium"), Difficulty::Medium);
+        assert_eq!(labelled("help wanted, enhancement"), Difficulty::Medium);
+
+        assert_eq!(
+            difficulty(&opportunity(ContributionKind::GoodFirstIssue, None)),
+            Difficulty::Easy
+        );
+        assert_eq!(
+            difficulty(&opportunity(ContributionKind::PriorityIssue, Some("bug"))),
+            Difficulty::Hard
+        );
+    }
+
+    #[test]
+    fn max_difficulty() {
+        let mut easy_only = filter(&[], &[]);
+        easy_only.config.max_difficulty = Some(Difficulty::Easy);
+        let no_lookup = || panic!("no profile needed");
+
+        let issue = opportunity(ContributionKind::GoodFirstIssue, None);
+        assert!(easy_only.fits(&issue, &OnceCell::new(), no_lookup));
+        let bug = opportunity(ContributionKind::PriorityIssue, None);
+        assert!(!easy_only.fits(&bug, &OnceCell::new(), no_lookup));
+    }
+
+    #[test]
+    fn languages_apply_to_code() {
+        let python = filter(&["python"], &[]);
+        let kernel = OnceCell::from(Some(profile("C", &[])));
+
+        let issue = opportunity(ContributionKind::GoodFirstIssue, None);
+        assert!(!python.fits(&issue, &kernel, || None));
+        let docs = opportunity(ContributionKind::Documentation, None);
+        assert!(python.fits(&docs, &kernel, || None));
+
+        let django = OnceCell::from(Some(profile("Python", &[])));
+        assert!(python.fits(&issue, &django, || None));
+
+        // Unknown repositories are kept.
+        assert!(python.fits(&issue, &OnceCell::new(), || None));
+    }
+
+    #[test]
+    fn topics_apply_to_everything() {
+        let graphics = filter(&[], &["Graphics"]);
+        let docs = opportunity(ContributionKind::Documentation, None);
+
+        let gimp = OnceCell::from(Some(profile("C", &["graphics", "image-editor"])));
+        assert!(graphics.fits(&docs, &gimp, || None));
+        let curl = OnceCell::from(Some(profile("C", &["http", "networking"])));
+        assert!(!graphics.fits(&docs, &curl, || None));
+        let untagged = OnceCell::from(Some(profile("C", &[])));
+        assert!(graphics.fits(&docs, &untagged, || None));
+    }
+
+    #[test]
+    fn looks_up_profile_once() {
+        let python = filter(&["Python"], &[]);
+        let issue = opportunity(ContributionKind::GoodFirstIssue, None);
+        let cached = OnceCell::new();
+        assert!(!python.fits(&issue, &cached, || Some(profile("C", &[]))));
+        assert!(!python.fits(&issue, &cached, || panic!("looked up twice")));
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Spread-the-word contribution backend.
+//!
+//! Telling others about a project helps it find users, contributors, and
+//! backers. This backend suggests sharing the projects that accept donations
+//! and are still maintained, with a ready-to-post text, and
+//! [`compose_summary`] writes the post that `syld share` publishes: how many
+//! open source projects the user depends on, and a few that need support.
+
+use anyhow::Result;
+
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::mastodon::MAX_STATUS_CHARS;
+use crate::project::UpstreamProject;
+
+/// Number of projects highlighted in a summary.
+pub const HIGHLIGHTED_PROJECTS: usize = 3;
+
+/// Backend that suggests sharing projects that need support.
+pub struct SpreadTheWordBackend;
+
+impl ContributionBackend for SpreadTheWordBackend {
+    fn name(&self) -> &str {
+        "spread_the_word"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    /// Composing the post needs nothing but the project's metadata.
+    fn is_offline(&self) -> bool {
+        true
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        if !needs_support(project) {
+            return Ok(Vec::new());
+        }
+        Ok(vec![ContributionOpportunity {
+            kind: ContributionKind::SpreadTheWord,
+            title: format!("Tell others about {}", project.name),
+            description: Some(format!(
+                "I use {} and it accepts donations: {} #OpenSource",
+                project.name, project.funding[0].url
+            )),
+            url: project.funding[0].url.clone(),
+        }])
+    }
+}
+
+/// Whether a project is worth asking others to support: it takes donations
+/// and is not deprecated.
+fn needs_support(project: &UpstreamProject) -> bool {
+    !project.funding.is_empty() && project.deprecation.is_none()
+}
+
+/// Up to `count` projects that need support, those resting on the fewest
+/// maintainers first, then the most depended upon.
+pub fn projects_needing_support(
+    projects: &[UpstreamProject],
+    count: usize,
+) -> Vec<&UpstreamProject> {
+    let mut candidates: Vec<&UpstreamProject> =
+        projects.iter().filter(|p| needs_support(p)).collect();
+    candidates.sort_by(|a, b| {
+        let bus_factor = |p: &UpstreamProject| p.bus_factor.unwrap_or(u32::MAX);
+        bus_factor(a)
+            .cmp(&bus_factor(b))
+            .then(b.dependents.cmp(&a.dependents))
+            .then(a.name.cmp(&b.name))
+    });
+    candidates.truncate(count);
+    candidates
+}
+
+/// A shareable post saying how many open source projects (`total`) the user
+/// depends on and highlighting a few of `projects` that need support,
+/// shortened to fit in a Mastodon status.
+pub fn compose_summary(total: usize, projects: &[UpstreamProject]) -> String {
+    let highlighted = projects_needing_support(projects, HIGHLIGHTED_PROJECTS);
+    let footer = "\n\nFound with syld #OpenSource";
+    let mut lines: Vec<String> = highlighted
+        .iter()
+        .map(|p| match p.bus_factor {
+            Some(1) => format!("- {} (single maintainer): {}", p.name, p.funding[0].url),
+            _ => format!("- {}: {}", p.name, p.funding[0].url),
+        })
+        .collect();
+
+    loop {
+        let text = match lines.len() {
+            0 => format!("I depend on {total} open source projects.{footer}"),
+            1 => format!(
+                "I depend on {total} open source projects. Here is one that needs support:\n\n{}{footer}",
+                lines[0]
+            ),
+            n => format!(
+                "I depend on {total} open source projects. Here are {n} that need support:\n\n{}{footer}",
+                lines.join("\n")
+            ),
+        };
+        if text.chars().count() <= MAX_STATUS_CHARS || lines.is_empty() {
+            return text;
+        }
+        lines.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{Deprecation, FundingChannel};
+
+    fn project(name: &str, bus_factor: Option<u32>, dependents: Option<u64>) -> UpstreamProject {
+        UpstreamProject {
+            name: name.to_string(),
+            repo_url: Some(format!("https://github.com/{name}/{name}")),
+            funding: vec![FundingChannel {
+                platform: "Open Collective".to_string(),
+                url: format!("https://opencollective.com/{name}"),
+                page: None,
+            }],
+            dependents,
+            bus_factor,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn picks_projects_on_fewest_maintainers() {
+        let mut unfunded = project("unfunded", Some(1), None);
+        unfunded.funding.clear();
+        let mut archived = project("archived", Some(1), None);
+        archived.deprecation = Some(Deprecation {
+            archived: true,
+            replacement: None,
+        });
+        let projects = vec![
+            project("popular", Some(5), Some(10_000)),
+            project("obscure", None, Some(3)),
+            project("solo", Some(1), Some(20)),
+            project("widely-used", Some(5), Some(90_000)),
+            unfunded,
+            archived,
+        ];
+
+        let names: Vec<_> = projects_needing_support(&projects, 3)
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["solo", "widely-used", "popular"]);
+    }
+
+    #[test]
+    fn summary_lists_projects() {
+        let projects = vec![project("curl", Some(1), None), project("gimp", None, None)];
+        let summary = compose_summary(412, &projects);
+        assert_eq!(
+            summary,
+            "I depend on 412 open source projects. Here are 2 that need support:\n\n\
+             - curl (single maintainer): https://opencollective.com/curl\n\
+             - gimp: https://opencollective.com/gimp\n\n\
+             Found with syld #OpenSource"
+        );
+        assert_eq!(
+            compose_summary(7, &[]),
+            "I depend on 7 open source projects.\n\nFound with syld #OpenSource"
+        );
+    }
+
+    #[test]
+    fn summary_fits_in_a_status() {
+        let long = "a".repeat(100);
+        let projects: Vec<_> = (0..3)
+            .map(|i| project(&format!("{long}{i}"), Some(1), None))
+            .collect();
+        let summary = compose_summary(100, &projects);
+        assert!(summary.chars().count() <= MAX_STATUS_CHARS);
+        assert!(summary.contains("Here is one that needs support"));
+    }
+
+    #[test]
+    fn suggests_sharing_funded_projects() {
+        let opportunities = SpreadTheWordBackend
+            .find_opportunities(&project("curl", None, None))
+            .unwrap();
+        assert_eq!(opportunities.len(), 1);
+        assert_eq!(opportunities[0].kind, ContributionKind::SpreadTheWord);
+        assert_eq!(opportunities[0].url, "https://opencollective.com/curl");
+    }
+}
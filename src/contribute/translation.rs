@@ -0,0 +1,274 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Translation contribution backend.
+//!
+//! For projects translated on a Weblate instance or on Crowdin (the
+//! `translation_url` found during enrichment), suggests translating into the
+//! user's languages. Weblate's public API reports how many strings are left
+//! for each language, and languages that are complete are skipped. Crowdin
+//! only reports progress to project members, so its language pages are
+//! suggested without counts.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{ContributionBackend, ContributionKind, ContributionOpportunity};
+use crate::config::Config;
+use crate::enrich::net::{self, RequestExt};
+use crate::project::UpstreamProject;
+
+/// Backend that suggests translating projects into the user's languages.
+pub struct TranslationBackend {
+    /// Language codes, e.g. `de` or `pt_BR`.
+    pub languages: Vec<String>,
+}
+
+/// Translation statistics of one language, from
+/// `GET /api/projects/:slug/languages/`.
+#[derive(Debug, Deserialize)]
+struct WeblateLanguage {
+    code: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    total: u64,
+    #[serde(default)]
+    translated: u64,
+}
+
+/// Older Weblate versions list the languages, newer ones paginate them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum WeblateLanguages {
+    List(Vec<WeblateLanguage>),
+    Page { results: Vec<WeblateLanguage> },
+}
+
+impl ContributionBackend for TranslationBackend {
+    fn name(&self) -> &str {
+        "translation"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.languages.is_empty()
+    }
+
+    fn find_opportunities(
+        &self,
+        project: &UpstreamProject,
+    ) -> Result<Vec<ContributionOpportunity>> {
+        let Some(url) = project.translation_url.as_deref() else {
+            return Ok(Vec::new());
+        };
+
+        if let Some((host, slug)) = weblate_project(url) {
+            let response = net::client()
+                .get(format!("https://{host}/api/projects/{slug}/languages/"))
+                .send_paced()
+                .context("Failed to query Weblate API")?;
+            // Projects that moved or were removed are not an error.
+            if !response.status().is_success() {
+                return Ok(Vec::new());
+            }
+            let languages: WeblateLanguages = response
+                .json()
+                .context("Failed to parse Weblate API response")?;
+            let languages = match languages {
+                WeblateLanguages::List(list) | WeblateLanguages::Page { results: list } => list,
+            };
+            return Ok(self.weblate_opportunities(&project.name, &host, &slug, languages));
+        }
+
+        if let Some(slug) = url.strip_prefix("https://crowdin.com/project/") {
+            return Ok(self
+                .languages
+                .iter()
+                .map(|code| ContributionOpportunity {
+                    kind: ContributionKind::Translation,
+                    title: format!("Translate {} into {code} on Crowdin", project.name),
+                    description: None,
+                    url: format!(
+                        "https://crowdin.com/project/{slug}/{}",
+                        code.replace('_', "-")
+                    ),
+                })
+                .collect());
+        }
+
+        Ok(Vec::new())
+    }
+}
+
+impl TranslationBackend {
+    /// The backend for the languages in `config`, or the language of the
+    /// `LANG` environment variable.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            languages: user_languages(&config.translation.languages, std::env::var("LANG").ok()),
+        }
+    }
+
+    /// One opportunity per user language with untranslated strings. A
+    /// regional language (`pt_BR`) falls back to its base language (`pt`)
+    /// when the project does not have the regional variant.
+    fn weblate_opportunities(
+        &self,
+        name: &str,
+        host: &str,
+        slug: &str,
+        languages: Vec<WeblateLanguage>,
+    ) -> Vec<ContributionOpportunity> {
+        let mut opportunities: Vec<ContributionOpportunity> = Vec::new();
+        for wanted in &self.languages {
+            let wanted = normalize_code(wanted);
+            let base = wanted.split('_').next().unwrap_or(&wanted);
+            let Some(language) = languages
+                .iter()
+                .find(|l| normalize_code(&l.code) == wanted)
+                .or_else(|| languages.iter().find(|l| normalize_code(&l.code) == base))
+            else {
+                continue;
+            };
+            let untranslated = language.total.saturating_sub(language.translated);
+            let url = format!("https://{host}/languages/{}/{slug}/", language.code);
+            if untranslated == 0 || opportunities.iter().any(|o| o.url == url) {
+                continue;
+            }
+            let language_name = language.language.as_deref().unwrap_or(&language.code);
+            opportunities.push(ContributionOpportunity {
+                kind: ContributionKind::Translation,
+                title: format!("Translate {name} into {language_name}"),
+                description: Some(format!(
+                    "{untranslated} of {} strings untranslated",
+                    language.total
+                )),
+                url,
+            });
+        }
+        opportunities
+    }
+}
+
+/// The languages to suggest: the configured ones, or else the language of
+/// a `LANG` value such as `de_DE.UTF-8`. English is skipped, as projects are
+/// written in it.
+fn user_languages(configured: &[String], lang: Option<String>) -> Vec<String> {
+    if !configured.is_empty() {
+        return configured.to_vec();
+    }
+    let Some(lang) = lang else {
+        return Vec::new();
+    };
+    let code = lang.split(['.', '@']).next().unwrap_or_default();
+    if code.is_empty() || code == "C" || code == "POSIX" || code.starts_with("en") {
+        return Vec::new();
+    }
+    vec![code.to_string()]
+}
+
+/// Lowercase a language code and use `_` between language and region, as
+/// Weblate writes codes like `pt_BR` but users may write `pt-br`.
+fn normalize_code(code: &str) -> String {
+    code.to_lowercase().replace('-', "_")
+}
+
+/// Split a Weblate project page (`https://<host>/projects/<slug>/`) into
+/// host and slug.
+fn weblate_project(url: &str) -> Option<(String, String)> {
+    let rest = url.strip_prefix("https://")?;
+    let (host, path) = rest.split_once('/')?;
+    let slug = path.strip_prefix("projects/")?.split('/').next()?;
+    (!slug.is_empty()).then(|| (host.to_string(), slug.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language(code: &str, name: &str, total: u64, translated: u64) -> WeblateLanguage {
+        WeblateLanguage {
+            code: code.to_string(),
+            language: Some(name.to_string()),
+            total,
+            translated,
+        }
+    }
+
+    #[test]
+    fn languages_from_config_or_lang() {
+        let configured = vec!["fr".to_string()];
+        assert_eq!(
+            user_languages(&configured, Some("de_DE.UTF-8".to_string())),
+            ["fr"]
+        );
+        assert_eq!(
+            user_languages(&[], Some("de_DE.UTF-8".to_string())),
+            ["de_DE"]
+        );
+        assert!(user_languages(&[], Some("en_US.UTF-8".to_string())).is_empty());
+        assert!(user_languages(&[], Some("C.UTF-8".to_string())).is_empty());
+        assert!(user_languages(&[], None).is_empty());
+    }
+
+    #[test]
+    fn parse_weblate_languages() {
+        let list = r#"[{"code": "de", "language": "German", "total": 120, "translated": 100}]"#;
+        let page = r#"{"count": 1, "results": [{"code": "de", "total": 120, "translated": 100}]}"#;
+        for json in [list, page] {
+            let languages = match serde_json::from_str(json).unwrap() {
+                WeblateLanguages::List(list) | WeblateLanguages::Page { results: list } => list,
+            };
+            assert_eq!(languages[0].code, "de");
+            assert_eq!(languages[0].total - languages[0].translated, 20);
+        }
+    }
+
+    #[test]
+    fn opportunities_for_incomplete_user_languages() {
+        let backend = TranslationBackend {
+            languages: vec!["de_DE".to_string(), "pt-br".to_string(), "fr".to_string()],
+        };
+        let languages = vec![
+            language("de", "German", 120, 100),
+            language("pt", "Portuguese", 120, 10),
+            language("pt_BR", "Portuguese (Brazil)", 120, 60),
+            language("fr", "French", 120, 120),
+        ];
+        let opportunities =
+            backend.weblate_opportunities("Inkscape", "hosted.weblate.org", "inkscape", languages);
+
+        assert_eq!(opportunities.len(), 2);
+        assert_eq!(opportunities[0].kind, ContributionKind::Translation);
+        assert_eq!(opportunities[0].title, "Translate Inkscape into German");
+        assert_eq!(
+            opportunities[0].description.as_deref(),
+            Some("20 of 120 strings untranslated")
+        );
+        assert_eq!(
+            opportunities[0].url,
+            "https://hosted.weblate.org/languages/de/inkscape/"
+        );
+        assert_eq!(
+            opportunities[1].url,
+            "https://hosted.weblate.org/languages/pt_BR/inkscape/"
+        );
+    }
+
+    #[test]
+    fn weblate_project_urls() {
+        assert_eq!(
+            weblate_project("https://hosted.weblate.org/projects/inkscape/"),
+            Some(("hosted.weblate.org".to_string(), "inkscape".to_string()))
+        );
+        assert_eq!(
+            weblate_project("https://crowdin.com/project/inkscape"),
+            None
+        );
+    }
+
+    #[test]
+    fn unavailable_without_languages() {
+        let backend = TranslationBackend { languages: vec![] };
+        assert!(!backend.is_available());
+    }
+}
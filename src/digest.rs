@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Contribution digests.
+//!
+//! `syld digest` is meant to run from cron or a systemd timer: it looks for
+//! contribution opportunities, keeps those no earlier digest included, and
+//! delivers them by email (through the local `sendmail`) and as a desktop
+//! notification (through `notify-send`), as configured in `[digest]`.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::contribute::ProjectOpportunity;
+
+/// Opportunities listed in a desktop notification; the email lists all.
+const NOTIFICATION_ITEMS: usize = 5;
+
+/// The opportunities of `found` whose URL is not in `previous`.
+pub fn new_opportunities(
+    found: Vec<ProjectOpportunity>,
+    previous: &HashSet<String>,
+) -> Vec<ProjectOpportunity> {
+    found
+        .into_iter()
+        .filter(|f| !previous.contains(&f.opportunity.url))
+        .collect()
+}
+
+/// The one-line summary of a digest, used as email subject and
+/// notification title.
+pub fn subject(opportunities: &[ProjectOpportunity]) -> String {
+    match opportunities.len() {
+        1 => "syld: 1 new way to contribute".to_string(),
+        n => format!("syld: {n} new ways to contribute"),
+    }
+}
+
+/// The plain-text body of a digest: each opportunity with its project, kind,
+/// and URL.
+pub fn body(opportunities: &[ProjectOpportunity]) -> String {
+    let mut text = String::new();
+    for found in opportunities {
+        let opp = &found.opportunity;
+        text.push_str(&format!(
+            "{} ({}): {}\n  {}\n",
+            found.project, opp.kind, opp.title, opp.url
+        ));
+    }
+    text.push_str(
+        "\nMark what you did with `syld contribute done <url>`, \
+         or hide it with `syld contribute dismiss <url>`.\n",
+    );
+    text
+}
+
+/// Send the digest to `to` with the `sendmail` command.
+pub fn send_email(to: &str, opportunities: &[ProjectOpportunity]) -> Result<()> {
+    let mut child = Command::new("sendmail")
+        .args(["-t", "-i"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to run sendmail")?;
+    let message = format!(
+        "To: {to}\nSubject: {}\nContent-Type: text/plain; charset=utf-8\n\n{}",
+        subject(opportunities),
+        body(opportunities)
+    );
+    child
+        .stdin
+        .take()
+        .context("Failed to open sendmail input")?
+        .write_all(message.as_bytes())?;
+    let status = child.wait()?;
+    if !status.success() {
+        anyhow::bail!("sendmail failed: {status}");
+    }
+    Ok(())
+}
+
+/// Show the digest as a desktop notification with `notify-send`, listing
+/// the first few opportunities.
+pub fn notify_desktop(opportunities: &[ProjectOpportunity]) -> Result<()> {
+    let mut lines: Vec<String> = opportunities
+        .iter()
+        .take(NOTIFICATION_ITEMS)
+        .map(|f| format!("{}: {}", f.project, f.opportunity.title))
+        .collect();
+    if opportunities.len() > NOTIFICATION_ITEMS {
+        lines.push(format!(
+            "and {} more: run `syld contribute`",
+            opportunities.len() - NOTIFICATION_ITEMS
+        ));
+    }
+    let status = Command::new("notify-send")
+        .args([
+            "--app-name=syld",
+            &subject(opportunities),
+            &lines.join("\n"),
+        ])
+        .status()
+        .context("Failed to run notify-send")?;
+    if !status.success() {
+        anyhow::bail!("notify-send failed: {status}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contribute::{ContributionKind, ContributionOpportunity, OpportunityState};
+
+    fn found(project: &str, url: &str) -> ProjectOpportunity {
+        ProjectOpportunity {
+            project: project.to_string(),
+            opportunity: ContributionOpportunity {
+                kind: ContributionKind::GoodFirstIssue,
+                title: "Fix typo".to_string(),
+                description: None,
+                url: url.to_string(),
+            },
+            state: OpportunityState::New,
+        }
+    }
+
+    #[test]
+    fn keeps_opportunities_not_in_previous_digests() {
+        let previous = HashSet::from(["https://github.com/curl/curl/issues/1".to_string()]);
+        let new = new_opportunities(
+            vec![
+                found("curl", "https://github.com/curl/curl/issues/1"),
+                found("curl", "https://github.com/curl/curl/issues/2"),
+            ],
+            &previous,
+        );
+        assert_eq!(new.len(), 1);
+        assert_eq!(
+            new[0].opportunity.url,
+            "https://github.com/curl/curl/issues/2"
+        );
+    }
+
+    #[test]
+    fn digest_text() {
+        let one = [found("curl", "https://github.com/curl/curl/issues/2")];
+        assert_eq!(subject(&one), "syld: 1 new way to contribute");
+        assert!(body(&one).starts_with(
+            "curl (good first issue): Fix typo\n  https://github.com/curl/curl/issues/2\n"
+        ));
+
+        let two = [
+            found("curl", "https://github.com/curl/curl/issues/2"),
+            found("vim", "https://github.com/vim/vim/issues/3"),
+        ];
+        assert_eq!(subject(&two), "syld: 2 new ways to contribute");
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers packages installed via apk (Alpine Linux, postmarketOS).
+///
+/// Reads the installed database at `/lib/apk/db/installed` directly. The file
+/// holds one stanza per package, separated by blank lines, where each line is
+/// a single-letter field key, a colon, and the value (e.g. `P:busybox`).
+pub struct ApkDiscoverer;
+
+pub(super) const APK_DB_PATH: &str = "/lib/apk/db/installed";
+
+impl Discoverer for ApkDiscoverer {
+    fn name(&self) -> &str {
+        "apk"
+    }
+
+    fn is_available(&self) -> bool {
+        Path::new(APK_DB_PATH).is_file()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let content =
+            fs::read_to_string(APK_DB_PATH).context("Failed to read apk installed database")?;
+        Ok(parse_installed_db(&content))
+    }
+}
+
+/// Parse the contents of the apk installed database.
+pub(super) fn parse_installed_db(content: &str) -> Vec<InstalledPackage> {
+    let stanzas: Vec<&str> = content
+        .split("\n\n")
+        .filter(|s| !s.trim().is_empty())
+        .collect();
+
+    let pb = ProgressBar::new(stanzas.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {bar:30} {pos}/{len} packages")
+            .unwrap(),
+    );
+
+    let mut packages = Vec::new();
+
+    for stanza in stanzas {
+        match parse_stanza(stanza) {
+            Ok(pkg) => packages.push(pkg),
+            Err(e) => {
+                pb.suspend(|| eprintln!("  Warning: failed to parse apk package entry: {e}"));
+            }
+        }
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+
+    packages
+}
+
+/// Parse a single package stanza.
+///
+/// Relevant fields: `P` (name), `V` (version), `T` (description), `U` (URL),
+/// and `L` (license). All other fields (files, checksums, dependencies) are
+/// ignored.
+fn parse_stanza(stanza: &str) -> Result<InstalledPackage> {
+    let mut name = None;
+    let mut version = None;
+    let mut description = None;
+    let mut url = None;
+    let mut licenses = Vec::new();
+
+    for line in stanza.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        match key {
+            "P" => name = Some(value.to_string()),
+            "V" => version = Some(value.to_string()),
+            "T" => description = Some(value.to_string()),
+            "U" => url = Some(value.to_string()),
+            "L" => licenses.push(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(InstalledPackage {
+        name: name.context("Missing P: (name) field")?,
+        version: version.context("Missing V: (version) field")?,
+        description,
+        url,
+        source: PackageSource::Apk,
+        licenses,
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+C:Q1abc=
+P:musl
+V:1.2.5-r0
+A:x86_64
+S:411034
+I:643072
+T:the musl c library (libc) implementation
+U:https://musl.libc.org/
+L:MIT
+o:musl
+m:Natanael Copa <ncopa@alpinelinux.org>
+t:1709817345
+F:lib
+R:ld-musl-x86_64.so.1
+Z:Q1xyz=
+
+C:Q1def=
+P:busybox
+V:1.36.1-r28
+T:Size optimized toolbox of many common UNIX utilities
+U:https://busybox.net/
+L:GPL-2.0-only
+
+";
+
+    #[test]
+    fn parse_multiple_packages() {
+        let packages = parse_installed_db(SAMPLE);
+        assert_eq!(packages.len(), 2);
+
+        let musl = &packages[0];
+        assert_eq!(musl.name, "musl");
+        assert_eq!(musl.version, "1.2.5-r0");
+        assert_eq!(
+            musl.description.as_deref(),
+            Some("the musl c library (libc) implementation")
+        );
+        assert_eq!(musl.url.as_deref(), Some("https://musl.libc.org/"));
+        assert_eq!(musl.licenses, vec!["MIT"]);
+        assert_eq!(musl.source, PackageSource::Apk);
+
+        assert_eq!(packages[1].name, "busybox");
+        assert_eq!(packages[1].licenses, vec!["GPL-2.0-only"]);
+    }
+
+    #[test]
+    fn parse_minimal_stanza() {
+        let pkg = parse_stanza("P:foo\nV:1.0-r0\n").unwrap();
+        assert_eq!(pkg.name, "foo");
+        assert!(pkg.url.is_none());
+        assert!(pkg.licenses.is_empty());
+    }
+
+    #[test]
+    fn parse_missing_version_errors() {
+        assert!(parse_stanza("P:foo\nT:no version\n").is_err());
+    }
+
+    #[test]
+    fn parse_empty_db() {
+        assert!(parse_installed_db("").is_empty());
+        assert!(parse_installed_db("\n\n\n").is_empty());
+    }
+}
@@ -1,21 +1,38 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
 
 /// Discovers packages installed via apt by reading the dpkg status database.
 ///
 /// The dpkg database is a single file at /var/lib/dpkg/status using the Debian
 /// control file format (RFC 822-style `Key: Value` paragraphs separated by
 /// blank lines).
+///
+/// dpkg does not record licenses, so they are read from each package's
+/// `/usr/share/doc/<pkg>/copyright` file when it follows the machine-readable
+/// [DEP-5] format. Debian license short names are mapped to SPDX identifiers.
+///
+/// apt records the packages it installed automatically in
+/// `/var/lib/apt/extended_states`; those are marked as dependencies and all
+/// others as explicitly installed.
+///
+/// [DEP-5]: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
 pub struct AptDiscoverer;
 
-const DPKG_STATUS_PATH: &str = "/var/lib/dpkg/status";
+pub(super) const DPKG_STATUS_PATH: &str = "/var/lib/dpkg/status";
+
+/// Directory holding each package's documentation, including `copyright`.
+pub(crate) const DOC_DIR: &str = "/usr/share/doc";
+
+/// apt's record of automatically installed packages.
+const EXTENDED_STATES_PATH: &str = "/var/lib/apt/extended_states";
 
 impl Discoverer for AptDiscoverer {
     fn name(&self) -> &str {
@@ -29,7 +46,198 @@ impl Discoverer for AptDiscoverer {
     fn discover(&self) -> Result<Vec<InstalledPackage>> {
         let content =
             fs::read_to_string(DPKG_STATUS_PATH).context("Failed to read dpkg status file")?;
-        parse_dpkg_status(&content)
+        let mut packages = parse_dpkg_status(&content)?;
+        for pkg in &mut packages {
+            pkg.licenses = copyright_licenses(Path::new(DOC_DIR), &pkg.name);
+        }
+
+        // Without the file (e.g. on plain dpkg systems), reasons stay unknown.
+        if let Ok(content) = fs::read_to_string(EXTENDED_STATES_PATH) {
+            let auto = parse_auto_installed(&content);
+            for pkg in &mut packages {
+                pkg.install_reason = if auto.contains(pkg.name.as_str()) {
+                    InstallReason::Dependency
+                } else {
+                    InstallReason::Explicit
+                };
+            }
+        }
+
+        Ok(packages)
+    }
+}
+
+/// Parse apt's `extended_states`, returning the names of packages marked
+/// `Auto-Installed: 1`.
+///
+/// ```text
+/// Package: libcurl4
+/// Architecture: amd64
+/// Auto-Installed: 1
+/// ```
+fn parse_auto_installed(content: &str) -> HashSet<&str> {
+    content
+        .split("\n\n")
+        .filter_map(|paragraph| {
+            let mut name = None;
+            let mut auto = false;
+            for line in paragraph.lines() {
+                match line.split_once(": ") {
+                    Some(("Package", value)) => name = Some(value.trim()),
+                    Some(("Auto-Installed", value)) => auto = value.trim() == "1",
+                    _ => {}
+                }
+            }
+            name.filter(|_| auto)
+        })
+        .collect()
+}
+
+/// Read the licenses declared in `<doc_dir>/<name>/copyright`.
+///
+/// Missing, unreadable, or free-form copyright files yield no licenses.
+fn copyright_licenses(doc_dir: &Path, name: &str) -> Vec<String> {
+    fs::read_to_string(doc_dir.join(name).join("copyright"))
+        .map(|content| parse_copyright(&content))
+        .unwrap_or_default()
+}
+
+/// Extract SPDX license identifiers from a machine-readable copyright file.
+///
+/// Only the `License` fields of the header and `Files` paragraphs are used;
+/// stand-alone `License` paragraphs merely hold the text of licenses already
+/// referenced. Files without a `Format` header are not machine-readable and
+/// yield nothing.
+///
+/// ```text
+/// Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+/// Upstream-Name: curl
+///
+/// Files: *
+/// Copyright: 1996-2023, Daniel Stenberg <daniel@haxx.se>
+/// License: curl
+///
+/// Files: lib/krb5.c
+/// License: BSD-3-clause
+/// ```
+pub(crate) fn parse_copyright(content: &str) -> Vec<String> {
+    let mut licenses: Vec<String> = Vec::new();
+
+    for (index, paragraph) in content.split("\n\n").enumerate() {
+        let fields: Vec<(&str, &str)> = paragraph
+            .lines()
+            .filter(|line| !line.starts_with([' ', '\t']))
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+        let has_field = |name: &str| fields.iter().any(|(key, _)| key.eq_ignore_ascii_case(name));
+
+        if index == 0 && !has_field("Format") {
+            return Vec::new();
+        }
+        if index > 0 && !has_field("Files") {
+            continue;
+        }
+
+        let expressions = fields
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("License"))
+            .map(|(_, value)| *value);
+        for expression in expressions {
+            for license in split_license_expression(expression) {
+                if !licenses.contains(&license) {
+                    licenses.push(license);
+                }
+            }
+        }
+    }
+
+    licenses
+}
+
+/// The upstream URL from the `Source` field of a machine-readable copyright
+/// file's header paragraph, if it names one.
+///
+/// The field is free-form; the first `http(s)` URL in it is used.
+pub(crate) fn copyright_source(content: &str) -> Option<String> {
+    let header = content.split("\n\n").next()?;
+    let mut fields = header
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t']))
+        .filter_map(|line| line.split_once(':'));
+    if !fields
+        .clone()
+        .any(|(key, _)| key.trim().eq_ignore_ascii_case("Format"))
+    {
+        return None;
+    }
+    let (_, value) = fields.find(|(key, _)| key.trim().eq_ignore_ascii_case("Source"))?;
+    value
+        .split_whitespace()
+        .map(|word| word.trim_start_matches(['<', '(']))
+        .find(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .map(|url| url.trim_end_matches([',', ';', '.', ')', '>']).to_string())
+}
+
+/// Split a DEP-5 license expression such as `GPL-2+ or Artistic` into SPDX
+/// identifiers, dropping `with ... exception` clauses.
+fn split_license_expression(expression: &str) -> Vec<String> {
+    let mut licenses = Vec::new();
+    let mut in_exception = false;
+
+    for word in expression.split(|c: char| c.is_whitespace() || c == ',') {
+        match word.to_ascii_lowercase().as_str() {
+            "" => {}
+            "or" | "and" => in_exception = false,
+            "with" => in_exception = true,
+            _ if in_exception => {}
+            _ => licenses.push(debian_to_spdx(word)),
+        }
+    }
+
+    licenses
+}
+
+/// Map a Debian license short name to its SPDX identifier.
+///
+/// Unknown names (e.g. `curl`, `public-domain`) are returned unchanged.
+fn debian_to_spdx(name: &str) -> String {
+    let (base, or_later) = match name.strip_suffix('+') {
+        Some(base) => (base, true),
+        None => (name, false),
+    };
+
+    if let Some((family, version)) = base.split_once('-')
+        && matches!(
+            family.to_ascii_uppercase().as_str(),
+            "GPL" | "LGPL" | "AGPL" | "GFDL"
+        )
+        && version.chars().all(|c| c.is_ascii_digit() || c == '.')
+    {
+        let family = family.to_ascii_uppercase();
+        let version = if version.contains('.') {
+            version.to_string()
+        } else {
+            format!("{version}.0")
+        };
+        let suffix = if or_later { "or-later" } else { "only" };
+        return format!("{family}-{version}-{suffix}");
+    }
+
+    let spdx = match base.to_ascii_lowercase().as_str() {
+        "expat" => "MIT",
+        "bsd-2-clause" => "BSD-2-Clause",
+        "bsd-3-clause" => "BSD-3-Clause",
+        "bsd-4-clause" => "BSD-4-Clause",
+        "artistic" => "Artistic-1.0",
+        "zlib" => "Zlib",
+        "isc" => "ISC",
+        _ => return name.to_string(),
+    };
+    if or_later {
+        format!("{spdx}+")
+    } else {
+        spdx.to_string()
     }
 }
 
@@ -38,7 +246,7 @@ impl Discoverer for AptDiscoverer {
 /// Paragraphs are separated by blank lines. Each paragraph describes one
 /// package. Packages whose `Status` field does not contain "installed" are
 /// skipped (e.g. packages that have been removed but not purged).
-fn parse_dpkg_status(content: &str) -> Result<Vec<InstalledPackage>> {
+pub(super) fn parse_dpkg_status(content: &str) -> Result<Vec<InstalledPackage>> {
     let paragraphs: Vec<&str> = content.split("\n\n").collect();
 
     let pb = ProgressBar::new(paragraphs.len() as u64);
@@ -137,6 +345,9 @@ fn parse_dpkg_entry(entry: &str) -> Result<Option<InstalledPackage>> {
         url: homepage,
         source: PackageSource::Apt,
         licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
     }))
 }
 
@@ -319,4 +530,119 @@ Description: A simple package";
         let pkg = parse_dpkg_entry(entry).unwrap().unwrap();
         assert_eq!(pkg.description.as_deref(), Some("A simple package"));
     }
+
+    #[test]
+    fn parse_machine_readable_copyright() {
+        let content = "\
+Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+Upstream-Name: libfoo
+Source: https://example.org/libfoo
+
+Files: *
+Copyright: 2010-2024 Foo Developers
+License: LGPL-2.1+
+
+Files: tools/*
+Copyright: 2015 Jane Doe
+License: GPL-2+ with OpenSSL exception or Artistic
+
+Files: debian/*
+Copyright: 2020 Debian Maintainer
+License: Expat
+
+License: LGPL-2.1+
+ This library is free software; you can redistribute it and/or
+ modify it under the terms of the GNU Lesser General Public
+ .
+ License: not-a-field
+";
+        assert_eq!(
+            parse_copyright(content),
+            vec![
+                "LGPL-2.1-or-later",
+                "GPL-2.0-or-later",
+                "Artistic-1.0",
+                "MIT"
+            ]
+        );
+    }
+
+    #[test]
+    fn free_form_copyright_yields_nothing() {
+        let content = "\
+This package was debianized by John Doe on Mon, 1 Jan 2001.
+
+License: GPL-2
+";
+        assert!(parse_copyright(content).is_empty());
+        assert!(parse_copyright("").is_empty());
+        assert_eq!(copyright_source(content), None);
+    }
+
+    #[test]
+    fn copyright_source_url() {
+        let content = "\
+Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+Upstream-Name: libfoo
+Source: Downloaded from <https://example.org/libfoo>,
+ repacked to remove non-free files
+
+Files: *
+License: LGPL-2.1+
+";
+        assert_eq!(
+            copyright_source(content).as_deref(),
+            Some("https://example.org/libfoo")
+        );
+        let no_url = "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n\
+                      Source: shipped by upstream on CD-ROM\n";
+        assert_eq!(copyright_source(no_url), None);
+    }
+
+    #[test]
+    fn debian_short_names_to_spdx() {
+        assert_eq!(debian_to_spdx("GPL-3"), "GPL-3.0-only");
+        assert_eq!(debian_to_spdx("GPL-3+"), "GPL-3.0-or-later");
+        assert_eq!(debian_to_spdx("LGPL-2.1"), "LGPL-2.1-only");
+        assert_eq!(debian_to_spdx("BSD-3-clause"), "BSD-3-Clause");
+        assert_eq!(debian_to_spdx("Apache-2.0"), "Apache-2.0");
+        assert_eq!(debian_to_spdx("public-domain"), "public-domain");
+    }
+
+    #[test]
+    fn reads_copyright_from_doc_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("curl")).unwrap();
+        fs::write(
+            dir.path().join("curl/copyright"),
+            "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n\n\
+             Files: *\nLicense: curl\n",
+        )
+        .unwrap();
+
+        assert_eq!(copyright_licenses(dir.path(), "curl"), vec!["curl"]);
+        assert!(copyright_licenses(dir.path(), "missing").is_empty());
+    }
+
+    #[test]
+    fn parse_extended_states() {
+        let content = "\
+Package: libcurl4
+Architecture: amd64
+Auto-Installed: 1
+
+Package: curl
+Architecture: amd64
+Auto-Installed: 0
+
+Package: libssl3
+Architecture: i386
+Auto-Installed: 1
+";
+        let auto = parse_auto_installed(content);
+        assert_eq!(auto.len(), 2);
+        assert!(auto.contains("libcurl4"));
+        assert!(auto.contains("libssl3"));
+        assert!(!auto.contains("curl"));
+    }
 }
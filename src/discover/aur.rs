@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::pacman::{self, PACMAN_DB_PATH};
+use super::{Discoverer, InstalledPackage, Maintainer, PackageSource};
+
+/// Discovers foreign pacman packages, typically built from the AUR.
+///
+/// Foreign packages are identified with `pacman -Qqm` and read from the same
+/// local database as [`PacmanDiscoverer`](super::pacman::PacmanDiscoverer).
+/// AUR-only projects often lack the backing of a distribution, which makes
+/// them worth surfacing separately.
+///
+/// When network access has been opted into (`enrich = true`), the AUR RPC is
+/// queried to fill in missing upstream URLs and record the AUR maintainer.
+pub struct AurDiscoverer {
+    /// Whether to query the AUR RPC interface.
+    pub query_rpc: bool,
+}
+
+const AUR_RPC_URL: &str = "https://aur.archlinux.org/rpc/v5/info";
+
+/// Maximum number of packages per AUR RPC request, keeping URLs well below
+/// the server's length limit.
+const AUR_RPC_BATCH: usize = 100;
+
+impl Discoverer for AurDiscoverer {
+    fn name(&self) -> &str {
+        "aur"
+    }
+
+    fn is_available(&self) -> bool {
+        Path::new(PACMAN_DB_PATH).is_dir()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let foreign = pacman::foreign_package_names()?;
+        if foreign.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let desc_paths: Vec<_> = fs::read_dir(PACMAN_DB_PATH)
+            .context("Failed to read pacman database directory")?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let dir_name = entry.file_name();
+                if !foreign.contains(pacman::package_name_from_dir(&dir_name.to_string_lossy())) {
+                    return None;
+                }
+                let desc_path = entry.path().join("desc");
+                desc_path.is_file().then_some(desc_path)
+            })
+            .collect();
+
+        let pb = ProgressBar::new(desc_paths.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+        for desc_path in &desc_paths {
+            match pacman::parse_desc(desc_path) {
+                Ok(mut pkg) => {
+                    pkg.source = PackageSource::Aur;
+                    packages.push(pkg);
+                }
+                Err(e) => {
+                    pb.suspend(|| {
+                        eprintln!("  Warning: failed to parse {}: {e}", desc_path.display())
+                    });
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        if self.query_rpc {
+            let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+            match fetch_aur_info(&names) {
+                Ok(info) => apply_aur_info(&mut packages, &info),
+                Err(e) => eprintln!("  Warning: AUR lookup failed: {e}"),
+            }
+        }
+
+        Ok(packages)
+    }
+}
+
+/// A package entry returned by the AUR RPC `info` endpoint.
+#[derive(Debug, Deserialize)]
+struct AurInfo {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "URL")]
+    url: Option<String>,
+    /// `None` for orphaned packages.
+    #[serde(rename = "Maintainer")]
+    maintainer: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AurResponse {
+    #[serde(default)]
+    results: Vec<AurInfo>,
+    error: Option<String>,
+}
+
+/// Query the AUR RPC for the given package names, keyed by name.
+fn fetch_aur_info(names: &[&str]) -> Result<HashMap<String, AurInfo>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+
+    let mut info = HashMap::new();
+
+    for batch in names.chunks(AUR_RPC_BATCH) {
+        let query: Vec<(&str, &str)> = batch.iter().map(|name| ("arg[]", *name)).collect();
+        let body = client
+            .get(AUR_RPC_URL)
+            .query(&query)
+            .send()
+            .context("Failed to query the AUR RPC")?
+            .error_for_status()?
+            .text()?;
+
+        info.extend(
+            parse_aur_response(&body)?
+                .into_iter()
+                .map(|entry| (entry.name.clone(), entry)),
+        );
+    }
+
+    Ok(info)
+}
+
+/// Parse an AUR RPC v5 response body.
+fn parse_aur_response(body: &str) -> Result<Vec<AurInfo>> {
+    let response: AurResponse =
+        serde_json::from_str(body).context("Failed to parse AUR RPC response")?;
+
+    if let Some(error) = response.error {
+        anyhow::bail!("AUR RPC error: {error}");
+    }
+
+    Ok(response.results)
+}
+
+/// Fill in missing URLs and record the AUR maintainer of each package.
+///
+/// Packages absent from the AUR (e.g. locally built) are left untouched.
+fn apply_aur_info(packages: &mut [InstalledPackage], info: &HashMap<String, AurInfo>) {
+    for pkg in packages {
+        let Some(entry) = info.get(&pkg.name) else {
+            continue;
+        };
+
+        if pkg.url.is_none() {
+            pkg.url = entry.url.clone().filter(|u| !u.is_empty());
+        }
+
+        pkg.maintainer = Some(match &entry.maintainer {
+            Some(account) => Maintainer::Account(account.clone()),
+            None => Maintainer::Orphaned,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::InstallReason;
+
+    fn aur_package(name: &str, url: Option<&str>) -> InstalledPackage {
+        InstalledPackage {
+            name: name.to_string(),
+            version: "1.0-1".to_string(),
+            description: Some("A tool".to_string()),
+            url: url.map(String::from),
+            source: PackageSource::Aur,
+            licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        }
+    }
+
+    #[test]
+    fn parse_response() {
+        let body = r#"{
+            "resultcount": 2,
+            "results": [
+                {"Name": "yay", "URL": "https://github.com/Jguer/yay", "Maintainer": "jguer", "NumVotes": 2000},
+                {"Name": "old-tool", "URL": null, "Maintainer": null}
+            ],
+            "type": "multiinfo",
+            "version": 5
+        }"#;
+        let results = parse_aur_response(body).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "yay");
+        assert_eq!(results[0].maintainer.as_deref(), Some("jguer"));
+        assert!(results[1].maintainer.is_none());
+    }
+
+    #[test]
+    fn parse_error_response() {
+        let body = r#"{"error": "Incorrect request type specified.", "resultcount": 0, "results": [], "type": "error", "version": 5}"#;
+        assert!(parse_aur_response(body).is_err());
+    }
+
+    #[test]
+    fn apply_info_fills_url_and_maintainer() {
+        let body = r#"{"results": [
+            {"Name": "yay", "URL": "https://github.com/Jguer/yay", "Maintainer": "jguer"},
+            {"Name": "old-tool", "URL": "https://old.example.org", "Maintainer": null}
+        ]}"#;
+        let info: HashMap<_, _> = parse_aur_response(body)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.name.clone(), e))
+            .collect();
+
+        let mut packages = vec![
+            aur_package("yay", None),
+            aur_package("old-tool", Some("https://keep.example.org")),
+            aur_package("local-only", None),
+        ];
+        apply_aur_info(&mut packages, &info);
+
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/Jguer/yay")
+        );
+        assert_eq!(
+            packages[0].maintainer,
+            Some(Maintainer::Account("jguer".to_string()))
+        );
+        assert_eq!(packages[1].url.as_deref(), Some("https://keep.example.org"));
+        assert_eq!(packages[1].maintainer, Some(Maintainer::Orphaned));
+        assert_eq!(packages[2].maintainer, None);
+        assert!(
+            packages
+                .iter()
+                .all(|p| p.description.as_deref() == Some("A tool"))
+        );
+    }
+}
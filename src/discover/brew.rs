@@ -7,7 +7,7 @@ use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::Deserialize;
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
 
 /// Discovers packages installed via Homebrew or Linuxbrew.
 ///
@@ -120,6 +120,9 @@ fn parse_brew_info(json: &str) -> Result<Vec<InstalledPackage>> {
             url: formula.homepage.clone(),
             source: PackageSource::Brew,
             licenses,
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         });
         pb.inc(1);
     }
@@ -137,6 +140,9 @@ fn parse_brew_info(json: &str) -> Result<Vec<InstalledPackage>> {
             url: cask.homepage.clone(),
             source: PackageSource::Brew,
             licenses: Vec::new(),
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         });
         pb.inc(1);
     }
@@ -0,0 +1,476 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Firefox profile roots, relative to the home directory: native, Flatpak,
+/// and Snap installs.
+const FIREFOX_ROOTS: &[&str] = &[
+    ".mozilla/firefox",
+    ".var/app/org.mozilla.firefox/.mozilla/firefox",
+    "snap/firefox/common/.mozilla/firefox",
+];
+
+/// Chromium-family user data directories, relative to `$XDG_CONFIG_HOME`.
+const CHROMIUM_ROOTS: &[&str] = &[
+    "chromium",
+    "google-chrome",
+    "BraveSoftware/Brave-Browser",
+    "microsoft-edge",
+    "vivaldi",
+];
+
+/// Upstream repositories of popular extensions, keyed by Firefox add-on ID
+/// or Chrome Web Store ID. Store listings rarely link to the source, so
+/// these are mapped by hand.
+const KNOWN_EXTENSIONS: &[(&str, &str)] = &[
+    (
+        "uBlock0@raymondhill.net",
+        "https://github.com/gorhill/uBlock",
+    ),
+    (
+        "cjpalhdlnbpafiamejdnhcphjbkeiagm",
+        "https://github.com/gorhill/uBlock",
+    ),
+    (
+        "{446900e4-71c2-419f-a6a7-df9c091e268b}",
+        "https://github.com/bitwarden/clients",
+    ),
+    (
+        "nngceckbapebfimnlniiiahkandclblb",
+        "https://github.com/bitwarden/clients",
+    ),
+    (
+        "addon@darkreader.org",
+        "https://github.com/darkreader/darkreader",
+    ),
+    (
+        "eimadpbcbfnmbkopoojfekhnkhdbieeh",
+        "https://github.com/darkreader/darkreader",
+    ),
+    (
+        "jid1-MnnxcxisBPnSXQ@jetpack",
+        "https://github.com/EFForg/privacybadger",
+    ),
+    (
+        "pkehgijcmpdhfbdbbnkijodmdjhbjlgp",
+        "https://github.com/EFForg/privacybadger",
+    ),
+    (
+        "sponsorBlocker@ajay.app",
+        "https://github.com/ajayyy/SponsorBlock",
+    ),
+    (
+        "mnjggcdmjocbbbhaepdhchncahnbgone",
+        "https://github.com/ajayyy/SponsorBlock",
+    ),
+    (
+        "{d7742d87-e61d-4b78-b8a1-b469842139fa}",
+        "https://github.com/philc/vimium",
+    ),
+    (
+        "dbepggeogbaibhgnhhndojpepiihcmeb",
+        "https://github.com/philc/vimium",
+    ),
+    (
+        "tridactyl.vim@cmcaine.co.uk",
+        "https://github.com/tridactyl/tridactyl",
+    ),
+    (
+        "keepassxc-browser@keepassxc.org",
+        "https://github.com/keepassxreboot/keepassxc-browser",
+    ),
+    (
+        "oboonakemofpalcgghocfoadofidjkkk",
+        "https://github.com/keepassxreboot/keepassxc-browser",
+    ),
+    (
+        "@testpilot-containers",
+        "https://github.com/mozilla/multi-account-containers",
+    ),
+];
+
+/// Discovers add-ons installed in Firefox and Chromium-based browsers.
+///
+/// Firefox keeps a registry of each profile's add-ons in `extensions.json`;
+/// only user-installed extensions and themes are reported, not the ones
+/// bundled with the browser. Chromium-family browsers unpack extensions to
+/// `<profile>/Extensions/<id>/<version>/manifest.json`, whose localized
+/// `__MSG_*__` strings are resolved from `_locales`.
+///
+/// The project URL comes from [`KNOWN_EXTENSIONS`], then the add-on's
+/// homepage, then its store listing.
+pub struct BrowserExtensionsDiscoverer;
+
+impl Discoverer for BrowserExtensionsDiscoverer {
+    fn name(&self) -> &str {
+        "browser-extensions"
+    }
+
+    fn is_available(&self) -> bool {
+        !firefox_registries().is_empty() || !chromium_extension_dirs().is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let registries = firefox_registries();
+        let manifests: Vec<PathBuf> = chromium_extension_dirs()
+            .iter()
+            .flat_map(|dir| subdirs(dir))
+            .flat_map(|ext| subdirs(&ext))
+            .map(|version| version.join("manifest.json"))
+            .filter(|path| path.is_file())
+            .collect();
+
+        let pb = ProgressBar::new((registries.len() + manifests.len()) as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} files")
+                .unwrap(),
+        );
+
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+        let mut insert = |pkg: InstalledPackage| {
+            by_key
+                .entry((pkg.name.clone(), pkg.version.clone()))
+                .or_insert(pkg);
+        };
+
+        for path in &registries {
+            match fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_firefox_extensions(&content))
+            {
+                Ok(packages) => packages.into_iter().for_each(&mut insert),
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        for path in &manifests {
+            match read_chromium_manifest(path) {
+                Ok(pkg) => insert(pkg),
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+/// `extensions.json` of every Firefox profile.
+fn firefox_registries() -> Vec<PathBuf> {
+    let Some(home) = home_dir() else {
+        return Vec::new();
+    };
+    FIREFOX_ROOTS
+        .iter()
+        .flat_map(|root| subdirs(&home.join(root)))
+        .map(|profile| profile.join("extensions.json"))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// `Extensions/` directories of every Chromium-family browser profile.
+fn chromium_extension_dirs() -> Vec<PathBuf> {
+    let Some(config) = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".config")))
+    else {
+        return Vec::new();
+    };
+    CHROMIUM_ROOTS
+        .iter()
+        .flat_map(|root| subdirs(&config.join(root)))
+        .map(|profile| profile.join("Extensions"))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+fn known_repository(id: &str) -> Option<String> {
+    KNOWN_EXTENSIONS
+        .iter()
+        .find(|(known, _)| *known == id)
+        .map(|(_, repo)| repo.to_string())
+}
+
+#[derive(Deserialize)]
+struct FirefoxRegistry {
+    #[serde(default)]
+    addons: Vec<FirefoxAddon>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FirefoxAddon {
+    id: String,
+    version: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    location: Option<String>,
+    default_locale: Option<FirefoxLocale>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FirefoxLocale {
+    name: Option<String>,
+    description: Option<String>,
+    #[serde(rename = "homepageURL")]
+    homepage_url: Option<String>,
+}
+
+/// Parse a Firefox profile's `extensions.json`.
+///
+/// Add-ons outside the `app-profile` location (built-in and system add-ons)
+/// and types other than extensions and themes (dictionaries, language packs)
+/// are skipped.
+fn parse_firefox_extensions(content: &str) -> Result<Vec<InstalledPackage>> {
+    let registry: FirefoxRegistry =
+        serde_json::from_str(content).context("Invalid extensions.json")?;
+
+    Ok(registry
+        .addons
+        .into_iter()
+        .filter(|a| a.location.as_deref() == Some("app-profile"))
+        .filter(|a| matches!(a.kind.as_deref(), Some("extension" | "theme")))
+        .map(|addon| {
+            let locale = addon.default_locale;
+            let (name, description, homepage) = match locale {
+                Some(l) => (l.name, l.description, l.homepage_url),
+                None => (None, None, None),
+            };
+            let url = known_repository(&addon.id)
+                .or(homepage.filter(|u| !u.is_empty()))
+                .unwrap_or_else(|| {
+                    format!("https://addons.mozilla.org/firefox/addon/{}", addon.id)
+                });
+
+            InstalledPackage {
+                name: name.filter(|n| !n.is_empty()).unwrap_or(addon.id),
+                version: addon.version.unwrap_or_else(|| "unknown".to_string()),
+                description: description.filter(|d| !d.is_empty()),
+                url: Some(url),
+                source: PackageSource::BrowserExtension,
+                licenses: Vec::new(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
+            }
+        })
+        .collect())
+}
+
+/// Read a Chromium extension manifest, resolving localized strings from the
+/// `_locales/<default_locale>/messages.json` next to it.
+fn read_chromium_manifest(path: &Path) -> Result<InstalledPackage> {
+    let content = fs::read_to_string(path)?;
+    let dir = path.parent().context("Manifest has no parent directory")?;
+    let id = dir
+        .parent()
+        .and_then(Path::file_name)
+        .map(|n| n.to_string_lossy().into_owned())
+        .context("Manifest is not inside an extension directory")?;
+
+    let locale = serde_json::from_str::<Value>(&content)
+        .ok()
+        .and_then(|m| m.get("default_locale")?.as_str().map(String::from));
+    let messages = locale
+        .and_then(|l| fs::read_to_string(dir.join("_locales").join(l).join("messages.json")).ok())
+        .unwrap_or_default();
+
+    parse_chromium_manifest(&id, &content, &messages)
+}
+
+/// Parse a Chromium extension `manifest.json`.
+///
+/// `messages` is the content of the default locale's `messages.json`, used to
+/// resolve `__MSG_key__` placeholders (keys are case-insensitive).
+fn parse_chromium_manifest(id: &str, content: &str, messages: &str) -> Result<InstalledPackage> {
+    let manifest: Value = serde_json::from_str(content).context("Invalid manifest.json")?;
+    let messages: BTreeMap<String, String> = serde_json::from_str::<Value>(messages)
+        .ok()
+        .and_then(|v| {
+            let obj = v.as_object()?.clone();
+            Some(
+                obj.into_iter()
+                    .filter_map(|(key, entry)| {
+                        let message = entry.get("message")?.as_str()?.to_string();
+                        Some((key.to_lowercase(), message))
+                    })
+                    .collect(),
+            )
+        })
+        .unwrap_or_default();
+
+    let field = |key: &str| {
+        let value = manifest.get(key)?.as_str()?;
+        let value = match value
+            .strip_prefix("__MSG_")
+            .and_then(|v| v.strip_suffix("__"))
+        {
+            Some(msg) => messages.get(&msg.to_lowercase())?.clone(),
+            None => value.to_string(),
+        };
+        Some(value).filter(|v| !v.is_empty())
+    };
+
+    let url = known_repository(id)
+        .or_else(|| field("homepage_url"))
+        .unwrap_or_else(|| format!("https://chromewebstore.google.com/detail/{id}"));
+
+    Ok(InstalledPackage {
+        name: field("name").unwrap_or_else(|| id.to_string()),
+        version: field("version").unwrap_or_else(|| "unknown".to_string()),
+        description: field("description"),
+        url: Some(url),
+        source: PackageSource::BrowserExtension,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_firefox_registry() {
+        let content = r#"{
+            "schemaVersion": 36,
+            "addons": [
+                {
+                    "id": "uBlock0@raymondhill.net",
+                    "version": "1.58.0",
+                    "type": "extension",
+                    "location": "app-profile",
+                    "defaultLocale": {
+                        "name": "uBlock Origin",
+                        "description": "Finally, an efficient blocker.",
+                        "homepageURL": "https://github.com/gorhill/uBlock#ublock-origin"
+                    }
+                },
+                {
+                    "id": "some-addon@example.org",
+                    "version": "2.0",
+                    "type": "extension",
+                    "location": "app-profile",
+                    "defaultLocale": {"name": "Some Addon", "homepageURL": "https://example.org"}
+                },
+                {
+                    "id": "unknown@example.org",
+                    "version": "0.1",
+                    "type": "theme",
+                    "location": "app-profile"
+                },
+                {
+                    "id": "formautofill@mozilla.org",
+                    "version": "1.0.1",
+                    "type": "extension",
+                    "location": "app-builtin"
+                },
+                {
+                    "id": "fr@dictionaries.addons.mozilla.org",
+                    "type": "dictionary",
+                    "location": "app-profile"
+                }
+            ]
+        }"#;
+        let packages = parse_firefox_extensions(content).unwrap();
+        assert_eq!(packages.len(), 3);
+
+        assert_eq!(packages[0].name, "uBlock Origin");
+        assert_eq!(packages[0].version, "1.58.0");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/gorhill/uBlock")
+        );
+        assert_eq!(packages[0].source, PackageSource::BrowserExtension);
+
+        assert_eq!(packages[1].url.as_deref(), Some("https://example.org"));
+
+        assert_eq!(packages[2].name, "unknown@example.org");
+        assert_eq!(
+            packages[2].url.as_deref(),
+            Some("https://addons.mozilla.org/firefox/addon/unknown@example.org")
+        );
+    }
+
+    #[test]
+    fn parse_chromium_localized_manifest() {
+        let manifest = r#"{
+            "manifest_version": 3,
+            "name": "__MSG_extName__",
+            "description": "__MSG_extDescription__",
+            "version": "2024.6.3",
+            "default_locale": "en"
+        }"#;
+        let messages = r#"{
+            "extname": {"message": "Bitwarden Password Manager"},
+            "extDescription": {"message": "A secure and free password manager"}
+        }"#;
+        let pkg = parse_chromium_manifest("nngceckbapebfimnlniiiahkandclblb", manifest, messages)
+            .unwrap();
+        assert_eq!(pkg.name, "Bitwarden Password Manager");
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("A secure and free password manager")
+        );
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/bitwarden/clients")
+        );
+    }
+
+    #[test]
+    fn parse_chromium_unknown_extension() {
+        let manifest = r#"{"name": "__MSG_missing__", "version": "1.0"}"#;
+        let pkg = parse_chromium_manifest("abcdefghijklmnop", manifest, "").unwrap();
+        assert_eq!(pkg.name, "abcdefghijklmnop");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://chromewebstore.google.com/detail/abcdefghijklmnop")
+        );
+
+        let manifest = r#"{"name": "Tool", "version": "1.0", "homepage_url": "https://tool.dev"}"#;
+        let pkg = parse_chromium_manifest("abcdefghijklmnop", manifest, "").unwrap();
+        assert_eq!(pkg.url.as_deref(), Some("https://tool.dev"));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_firefox_extensions("nope").is_err());
+        assert!(parse_chromium_manifest("id", "nope", "").is_err());
+    }
+}
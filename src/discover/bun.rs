@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::npm;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Discovers packages installed globally with `bun add -g`.
+///
+/// Runs `bun pm ls -g` for the top-level global packages, then reads each
+/// package's `package.json` from Bun's global install directory
+/// (`$BUN_INSTALL/install/global/node_modules`) for its repository, license,
+/// and funding, using the same parsing as the npm backend.
+pub struct BunDiscoverer;
+
+impl Discoverer for BunDiscoverer {
+    fn name(&self) -> &str {
+        "bun"
+    }
+
+    fn is_available(&self) -> bool {
+        global_modules_dir().is_some_and(|dir| dir.is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let modules = global_modules_dir().context("Could not determine BUN_INSTALL")?;
+
+        let output = Command::new("bun")
+            .args(["pm", "ls", "-g"])
+            .output()
+            .context("Failed to run bun pm ls")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "bun pm ls failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("bun pm ls output is not valid UTF-8")?;
+        let globals = parse_bun_ls(&stdout);
+
+        let pb = ProgressBar::new(globals.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (name, version) in globals {
+            let manifest = npm::read_package_json(&modules.join(&name).join("package.json"), &pb);
+            packages.push(manifest.unwrap_or_default().into_package(
+                name,
+                version,
+                PackageSource::Bun,
+            ));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// `$BUN_INSTALL/install/global/node_modules`, with `BUN_INSTALL` defaulting
+/// to `~/.bun`.
+fn global_modules_dir() -> Option<PathBuf> {
+    let bun_install = std::env::var_os("BUN_INSTALL")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".bun")))?;
+    Some(bun_install.join("install/global/node_modules"))
+}
+
+/// Parse `bun pm ls -g` into `(name, version)` pairs.
+///
+/// ```text
+/// /home/user/.bun/install/global node_modules (2)
+/// ├── @biomejs/biome@1.8.3
+/// └── cowsay@1.6.0
+/// ```
+fn parse_bun_ls(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let entry = line
+                .trim_start()
+                .strip_prefix("├── ")
+                .or_else(|| line.trim_start().strip_prefix("└── "))?;
+            // Split on the last `@` so scoped names (`@scope/pkg`) stay intact.
+            match entry.trim().rsplit_once('@') {
+                Some((name, version)) if !name.is_empty() => {
+                    Some((name.to_string(), version.to_string()))
+                }
+                _ => Some((entry.trim().to_string(), "unknown".to_string())),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ls_output() {
+        let output = "\
+/home/user/.bun/install/global node_modules (3)
+├── @biomejs/biome@1.8.3
+├── cowsay@1.6.0
+└── typescript@5.5.3
+";
+        assert_eq!(
+            parse_bun_ls(output),
+            vec![
+                ("@biomejs/biome".to_string(), "1.8.3".to_string()),
+                ("cowsay".to_string(), "1.6.0".to_string()),
+                ("typescript".to_string(), "5.5.3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ls_without_version() {
+        assert_eq!(
+            parse_bun_ls("└── @scope/pkg\n"),
+            vec![("@scope/pkg".to_string(), "unknown".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_ls_empty() {
+        assert!(parse_bun_ls("").is_empty());
+        assert!(parse_bun_ls("/home/user/.bun/install/global node_modules (0)\n").is_empty());
+    }
+}
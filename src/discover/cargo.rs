@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Rust binaries installed via `cargo install`.
+///
+/// Reads cargo's install tracking file at `$CARGO_HOME/.crates2.json` (falling
+/// back to `cargo install --list` when it is missing). Each installed crate is
+/// then matched against the unpacked sources in `$CARGO_HOME/registry/src/` to
+/// pick up the repository URL, description, and license from its published
+/// `Cargo.toml`. No network access is performed.
+pub struct CargoDiscoverer;
+
+impl Discoverer for CargoDiscoverer {
+    fn name(&self) -> &str {
+        "cargo"
+    }
+
+    fn is_available(&self) -> bool {
+        cargo_home().is_some_and(|home| home.join("bin").is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let home = cargo_home().context("Could not determine CARGO_HOME")?;
+
+        let crates = match fs::read_to_string(home.join(".crates2.json")) {
+            Ok(content) => parse_crates2_json(&content)?,
+            Err(_) => {
+                let output = Command::new("cargo")
+                    .args(["install", "--list"])
+                    .output()
+                    .context("Failed to run cargo install --list")?;
+
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "cargo install --list failed: {}",
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+
+                let stdout = String::from_utf8(output.stdout)
+                    .context("cargo install --list output is not valid UTF-8")?;
+                parse_install_list(&stdout)
+            }
+        };
+
+        let registry_src = home.join("registry/src");
+
+        let pb = ProgressBar::new(crates.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let packages = crates
+            .into_iter()
+            .map(|krate| {
+                let manifest = find_registry_manifest(&registry_src, &krate.name, &krate.version)
+                    .and_then(|path| fs::read_to_string(path).ok())
+                    .and_then(|content| parse_manifest(&content));
+                pb.inc(1);
+                build_package(krate, manifest)
+            })
+            .collect();
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Resolve `$CARGO_HOME`, defaulting to `~/.cargo`.
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cargo")))
+}
+
+/// A crate recorded by `cargo install`.
+#[derive(Debug, PartialEq)]
+struct InstalledCrate {
+    name: String,
+    version: String,
+    /// Source URL for git installs (e.g. `https://github.com/owner/repo`).
+    git_url: Option<String>,
+}
+
+/// Top-level structure of `.crates2.json`.
+#[derive(Deserialize)]
+struct Crates2 {
+    #[serde(default)]
+    installs: BTreeMap<String, serde_json::Value>,
+}
+
+/// Parse cargo's `.crates2.json` install tracking file.
+///
+/// Keys of the `installs` map are package IDs of the form
+/// `name version (source)`, e.g.
+/// `ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)`.
+fn parse_crates2_json(content: &str) -> Result<Vec<InstalledCrate>> {
+    let crates2: Crates2 =
+        serde_json::from_str(content).context("Failed to parse .crates2.json")?;
+
+    Ok(crates2
+        .installs
+        .keys()
+        .filter_map(|id| parse_package_id(id))
+        .collect())
+}
+
+/// Parse a cargo package ID (`name version (source)`).
+fn parse_package_id(id: &str) -> Option<InstalledCrate> {
+    let mut parts = id.splitn(3, ' ');
+    let name = parts.next().filter(|s| !s.is_empty())?;
+    let version = parts.next().filter(|s| !s.is_empty())?;
+    let source = parts
+        .next()
+        .map(|s| s.trim_start_matches('(').trim_end_matches(')'))
+        .unwrap_or_default();
+
+    // `.crates2.json` spells git sources as `git+<url>`, while
+    // `cargo install --list` prints the bare URL.
+    let git_url = source
+        .strip_prefix("git+")
+        .or_else(|| source.starts_with("http").then_some(source))
+        .map(|url| {
+            // Strip the `?branch=...` query and `#<commit>` fragment.
+            let url = url.split(['?', '#']).next().unwrap_or(url);
+            url.strip_suffix(".git").unwrap_or(url).to_string()
+        });
+
+    Some(InstalledCrate {
+        name: name.to_string(),
+        version: version.to_string(),
+        git_url,
+    })
+}
+
+/// Parse the output of `cargo install --list`.
+///
+/// Each installed crate is a non-indented `name vX.Y.Z:` or
+/// `name vX.Y.Z (source):` line followed by indented binary names.
+fn parse_install_list(output: &str) -> Vec<InstalledCrate> {
+    output
+        .lines()
+        .filter(|l| !l.is_empty() && !l.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let line = line.trim_end_matches(':');
+            let mut parts = line.splitn(3, ' ');
+            let name = parts.next().filter(|s| !s.is_empty())?;
+            let version = parts.next()?.trim_start_matches('v');
+            let id = match parts.next() {
+                Some(source) => format!("{name} {version} {source}"),
+                None => format!("{name} {version}"),
+            };
+            parse_package_id(&id)
+        })
+        .collect()
+}
+
+/// Locate the published `Cargo.toml` for a crate in the local registry cache.
+///
+/// The registry source directory contains one sub-directory per index (e.g.
+/// `index.crates.io-6f17d22bba15001f`), each holding `<name>-<version>/`.
+fn find_registry_manifest(registry_src: &Path, name: &str, version: &str) -> Option<PathBuf> {
+    fs::read_dir(registry_src)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join(format!("{name}-{version}/Cargo.toml")))
+        .find(|path| path.is_file())
+}
+
+/// Metadata read from a crate's `Cargo.toml`.
+#[derive(Debug, Default, PartialEq)]
+struct CrateManifest {
+    description: Option<String>,
+    repository: Option<String>,
+    homepage: Option<String>,
+    license: Option<String>,
+}
+
+/// Extract the `[package]` metadata we care about from a `Cargo.toml`.
+fn parse_manifest(content: &str) -> Option<CrateManifest> {
+    let value: toml::Value = toml::from_str(content).ok()?;
+    let package = value.get("package")?;
+
+    let field = |key: &str| {
+        package
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    Some(CrateManifest {
+        description: field("description"),
+        repository: field("repository"),
+        homepage: field("homepage"),
+        license: field("license"),
+    })
+}
+
+/// Combine an install record with its (optional) manifest metadata.
+///
+/// The repository URL is preferred over the homepage, since that is what
+/// enrichment backends key on. Git installs fall back to the git source URL.
+fn build_package(krate: InstalledCrate, manifest: Option<CrateManifest>) -> InstalledPackage {
+    let manifest = manifest.unwrap_or_default();
+
+    let url = manifest.repository.or(manifest.homepage).or(krate.git_url);
+
+    let licenses = manifest.license.map(|l| vec![l]).unwrap_or_default();
+
+    InstalledPackage {
+        name: krate.name,
+        version: krate.version,
+        description: manifest.description,
+        url,
+        source: PackageSource::Cargo,
+        licenses,
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_crates2_registry_and_git() {
+        let content = r#"{
+            "installs": {
+                "ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)": {
+                    "version_req": null,
+                    "bins": ["rg"]
+                },
+                "foo 0.1.0 (git+https://github.com/owner/foo.git?branch=main#abc123)": {
+                    "bins": ["foo"]
+                }
+            }
+        }"#;
+        let crates = parse_crates2_json(content).unwrap();
+        assert_eq!(crates.len(), 2);
+        assert_eq!(crates[0].name, "foo");
+        assert_eq!(crates[0].version, "0.1.0");
+        assert_eq!(
+            crates[0].git_url.as_deref(),
+            Some("https://github.com/owner/foo")
+        );
+        assert_eq!(crates[1].name, "ripgrep");
+        assert_eq!(crates[1].version, "14.1.0");
+        assert!(crates[1].git_url.is_none());
+    }
+
+    #[test]
+    fn parse_crates2_empty() {
+        assert!(parse_crates2_json("{}").unwrap().is_empty());
+        assert!(
+            parse_crates2_json(r#"{"installs": {}}"#)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn parse_crates2_invalid_json_errors() {
+        assert!(parse_crates2_json("not json").is_err());
+    }
+
+    #[test]
+    fn parse_package_id_path_source() {
+        let krate = parse_package_id("mytool 0.2.0 (path+file:///home/user/mytool)").unwrap();
+        assert_eq!(krate.name, "mytool");
+        assert_eq!(krate.version, "0.2.0");
+        assert!(krate.git_url.is_none());
+    }
+
+    #[test]
+    fn parse_install_list_output() {
+        let output = "\
+bat v0.24.0:
+    bat
+foo v0.1.0 (https://github.com/owner/foo#abc123):
+    foo
+    foo-helper
+";
+        let crates = parse_install_list(output);
+        assert_eq!(crates.len(), 2);
+        assert_eq!(crates[0].name, "bat");
+        assert_eq!(crates[0].version, "0.24.0");
+        assert_eq!(crates[1].name, "foo");
+        assert_eq!(crates[1].version, "0.1.0");
+        assert_eq!(
+            crates[1].git_url.as_deref(),
+            Some("https://github.com/owner/foo")
+        );
+    }
+
+    #[test]
+    fn parse_manifest_fields() {
+        let content = r#"
+[package]
+name = "ripgrep"
+version = "14.1.0"
+description = "ripgrep is a line-oriented search tool"
+homepage = "https://github.com/BurntSushi/ripgrep"
+repository = "https://github.com/BurntSushi/ripgrep"
+license = "Unlicense OR MIT"
+"#;
+        let manifest = parse_manifest(content).unwrap();
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("ripgrep is a line-oriented search tool")
+        );
+        assert_eq!(
+            manifest.repository.as_deref(),
+            Some("https://github.com/BurntSushi/ripgrep")
+        );
+        assert_eq!(manifest.license.as_deref(), Some("Unlicense OR MIT"));
+    }
+
+    #[test]
+    fn parse_manifest_without_package_table() {
+        assert!(parse_manifest("[workspace]\nmembers = []\n").is_none());
+    }
+
+    #[test]
+    fn build_package_prefers_repository() {
+        let krate = InstalledCrate {
+            name: "bat".to_string(),
+            version: "0.24.0".to_string(),
+            git_url: None,
+        };
+        let manifest = CrateManifest {
+            description: Some("A cat(1) clone with wings.".to_string()),
+            repository: Some("https://github.com/sharkdp/bat".to_string()),
+            homepage: Some("https://example.com/bat".to_string()),
+            license: Some("MIT OR Apache-2.0".to_string()),
+        };
+        let pkg = build_package(krate, Some(manifest));
+        assert_eq!(pkg.name, "bat");
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/sharkdp/bat"));
+        assert_eq!(pkg.source, PackageSource::Cargo);
+        assert_eq!(pkg.licenses, vec!["MIT OR Apache-2.0"]);
+    }
+
+    #[test]
+    fn build_package_falls_back_to_git_url() {
+        let krate = InstalledCrate {
+            name: "foo".to_string(),
+            version: "0.1.0".to_string(),
+            git_url: Some("https://github.com/owner/foo".to_string()),
+        };
+        let pkg = build_package(krate, None);
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/owner/foo"));
+        assert!(pkg.description.is_none());
+        assert!(pkg.licenses.is_empty());
+    }
+
+    #[test]
+    fn find_manifest_in_registry_src() {
+        let dir = tempfile::tempdir().unwrap();
+        let crate_dir = dir.path().join("index.crates.io-abc/bat-0.24.0");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(crate_dir.join("Cargo.toml"), "[package]\nname = \"bat\"\n").unwrap();
+
+        assert_eq!(
+            find_registry_manifest(dir.path(), "bat", "0.24.0"),
+            Some(crate_dir.join("Cargo.toml"))
+        );
+        assert!(find_registry_manifest(dir.path(), "bat", "0.23.0").is_none());
+    }
+}
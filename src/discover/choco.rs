@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::nuget::{self, Nuspec};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers packages installed via Chocolatey (Windows).
+///
+/// Runs `choco list --local-only --limit-output` for installed package IDs and
+/// versions, then reads each package's `.nuspec` from
+/// `$ChocolateyInstall\lib\<id>\` for its title, summary, project URL, and
+/// license.
+pub struct ChocoDiscoverer;
+
+impl Discoverer for ChocoDiscoverer {
+    fn name(&self) -> &str {
+        "choco"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(windows) && super::in_path("choco.exe")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let list = run_choco_list()?;
+        let entries = parse_choco_list(&list);
+        let lib_dir = choco_lib_dir();
+
+        let pb = ProgressBar::new(entries.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (id, version) in entries {
+            let nuspec_path = lib_dir.join(&id).join(format!("{id}.nuspec"));
+            let nuspec = nuget::read_nuspec(&nuspec_path, &pb);
+            packages.push(build_package(id, version, nuspec));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run `choco list` for locally installed packages.
+///
+/// Chocolatey 2.x lists local packages by default and rejects the removed
+/// `--local-only` flag, so the listing is retried without it.
+fn run_choco_list() -> Result<String> {
+    let run = |args: &[&str]| {
+        Command::new("choco")
+            .args(args)
+            .output()
+            .context("Failed to run choco list")
+    };
+
+    let mut output = run(&["list", "--local-only", "--limit-output"])?;
+    if !output.status.success() {
+        output = run(&["list", "--limit-output"])?;
+    }
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "choco list failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("choco list output is not valid UTF-8")
+}
+
+/// Chocolatey's package directory: `$ChocolateyInstall\lib`, falling back to
+/// the default install location.
+fn choco_lib_dir() -> PathBuf {
+    std::env::var_os("ChocolateyInstall")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData\chocolatey"))
+        .join("lib")
+}
+
+/// Parse `choco list --limit-output`, one `id|version` pair per line.
+fn parse_choco_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (id, version) = line.trim().split_once('|')?;
+            (!id.is_empty()).then(|| (id.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+fn build_package(id: String, version: String, nuspec: Nuspec) -> InstalledPackage {
+    let description = nuspec.short_description();
+
+    InstalledPackage {
+        name: id,
+        version: Some(version)
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "unknown".to_string()),
+        description,
+        url: nuspec.project_url.or(nuspec.project_source_url),
+        source: PackageSource::Choco,
+        licenses: nuspec.license.or(nuspec.license_url).into_iter().collect(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_output() {
+        let output = "\
+chocolatey|2.3.0
+git|2.45.2
+7zip.install|24.7.0
+not a package line
+";
+        assert_eq!(
+            parse_choco_list(output),
+            vec![
+                ("chocolatey".to_string(), "2.3.0".to_string()),
+                ("git".to_string(), "2.45.2".to_string()),
+                ("7zip.install".to_string(), "24.7.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_package_from_nuspec() {
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd">
+  <metadata>
+    <id>git</id>
+    <version>2.45.2</version>
+    <title>Git</title>
+    <projectUrl>https://git-scm.com/</projectUrl>
+    <projectSourceUrl>https://github.com/git-for-windows/git</projectSourceUrl>
+    <licenseUrl>https://www.gnu.org/licenses/old-licenses/gpl-2.0.html</licenseUrl>
+    <summary>Git (for Windows)</summary>
+    <description>Git for Windows focuses on offering a lightweight,
+native set of tools.</description>
+  </metadata>
+</package>
+"#;
+        let nuspec = nuget::parse_nuspec(content).unwrap();
+        let pkg = build_package("git".to_string(), "2.45.2".to_string(), nuspec);
+        assert_eq!(pkg.description.as_deref(), Some("Git (for Windows)"));
+        assert_eq!(pkg.url.as_deref(), Some("https://git-scm.com/"));
+        assert_eq!(
+            pkg.licenses,
+            vec!["https://www.gnu.org/licenses/old-licenses/gpl-2.0.html"]
+        );
+        assert_eq!(pkg.source, PackageSource::Choco);
+    }
+
+    #[test]
+    fn license_expression_preferred_over_url() {
+        let content = r#"<package><metadata>
+    <id>foo</id>
+    <license type="expression">MIT</license>
+    <licenseUrl>https://licenses.nuget.org/MIT</licenseUrl>
+    <description>First line
+Second line</description>
+</metadata></package>"#;
+        let pkg = build_package(
+            "foo".to_string(),
+            String::new(),
+            nuget::parse_nuspec(content).unwrap(),
+        );
+        assert_eq!(pkg.licenses, vec!["MIT"]);
+        assert_eq!(pkg.description.as_deref(), Some("First line"));
+        assert_eq!(pkg.version, "unknown");
+    }
+}
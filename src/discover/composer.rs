@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::projects;
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use crate::project::FundingChannel;
+
+/// Discovers PHP packages installed with Composer.
+///
+/// Reads the global install record at
+/// `$COMPOSER_HOME/vendor/composer/installed.json` (`COMPOSER_HOME` defaults
+/// to `~/.config/composer`, or `~/.composer` on older setups). When
+/// `[discover] project_dirs` is configured, `composer.lock` files found there
+/// are read as well. Both files embed the full package metadata, including
+/// `support.source` and `funding`.
+pub struct ComposerDiscoverer {
+    /// Development directories to search for `composer.lock` files.
+    pub project_dirs: Vec<PathBuf>,
+}
+
+impl Discoverer for ComposerDiscoverer {
+    fn name(&self) -> &str {
+        "composer"
+    }
+
+    fn is_available(&self) -> bool {
+        global_installed_json().is_some() || !self.project_dirs.is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let mut files: Vec<PathBuf> = global_installed_json().into_iter().collect();
+        files.extend(projects::find_files(&self.project_dirs, &["composer.lock"]));
+
+        let pb = ProgressBar::new(files.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} files")
+                .unwrap(),
+        );
+
+        // Packages shared by several projects are reported once per version.
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+        for path in &files {
+            match read_package_list(path) {
+                Ok(packages) => {
+                    for pkg in packages {
+                        by_key
+                            .entry((pkg.name.clone(), pkg.version.clone()))
+                            .or_insert(pkg);
+                    }
+                }
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+/// Locate the global `installed.json`, honoring `$COMPOSER_HOME`.
+fn global_installed_json() -> Option<PathBuf> {
+    let homes: Vec<PathBuf> = match std::env::var_os("COMPOSER_HOME") {
+        Some(home) => vec![PathBuf::from(home)],
+        None => {
+            let home = PathBuf::from(std::env::var_os("HOME")?);
+            let config = std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home.join(".config"));
+            vec![config.join("composer"), home.join(".composer")]
+        }
+    };
+
+    homes
+        .into_iter()
+        .map(|home| home.join("vendor/composer/installed.json"))
+        .find(|path| path.is_file())
+}
+
+fn read_package_list(path: &Path) -> Result<Vec<InstalledPackage>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if path.file_name().is_some_and(|n| n == "composer.lock") {
+        parse_composer_lock(&content)
+    } else {
+        parse_installed_json(&content)
+    }
+}
+
+#[derive(Deserialize)]
+struct ComposerPackage {
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    homepage: Option<String>,
+    #[serde(default)]
+    license: Vec<String>,
+    source: Option<ComposerSource>,
+    #[serde(default)]
+    support: BTreeMap<String, String>,
+    #[serde(default)]
+    funding: Vec<ComposerFunding>,
+}
+
+#[derive(Deserialize)]
+struct ComposerSource {
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ComposerFunding {
+    url: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+impl ComposerFunding {
+    /// Map the entry to a funding channel.
+    ///
+    /// Composer records GitHub Sponsors as the bare profile URL
+    /// (`https://github.com/<user>`) with type `github`; those are rewritten
+    /// to the sponsors page so the platform is recognized.
+    fn channel(&self) -> Option<FundingChannel> {
+        let url = self.url.as_deref().filter(|u| u.starts_with("http"))?;
+        if self.kind.as_deref() == Some("github")
+            && let Some(user) = url.strip_prefix("https://github.com/")
+            && !user.starts_with("sponsors/")
+        {
+            let user = user.trim_end_matches('/');
+            return Some(FundingChannel::from_url(&format!(
+                "https://github.com/sponsors/{user}"
+            )));
+        }
+        Some(FundingChannel::from_url(url))
+    }
+}
+
+/// `installed.json` is an object with a `packages` array since Composer 2,
+/// and a bare array before that.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InstalledJson {
+    V2 { packages: Vec<ComposerPackage> },
+    V1(Vec<ComposerPackage>),
+}
+
+#[derive(Deserialize)]
+struct ComposerLock {
+    #[serde(default)]
+    packages: Vec<ComposerPackage>,
+    #[serde(default, rename = "packages-dev")]
+    packages_dev: Vec<ComposerPackage>,
+}
+
+/// Parse a global `vendor/composer/installed.json`.
+fn parse_installed_json(content: &str) -> Result<Vec<InstalledPackage>> {
+    let installed: InstalledJson =
+        serde_json::from_str(content).context("Invalid installed.json")?;
+    let packages = match installed {
+        InstalledJson::V2 { packages } | InstalledJson::V1(packages) => packages,
+    };
+    Ok(packages.into_iter().map(build_package).collect())
+}
+
+/// Parse a project's `composer.lock`, including dev dependencies.
+fn parse_composer_lock(content: &str) -> Result<Vec<InstalledPackage>> {
+    let lock: ComposerLock = serde_json::from_str(content).context("Invalid composer.lock")?;
+    Ok(lock
+        .packages
+        .into_iter()
+        .chain(lock.packages_dev)
+        .map(build_package)
+        .collect())
+}
+
+/// Convert a Composer package entry.
+///
+/// The project URL prefers `support.source`, then the VCS `source.url`
+/// (without a trailing `.git`), then `homepage`.
+fn build_package(pkg: ComposerPackage) -> InstalledPackage {
+    let non_empty = |s: Option<String>| s.filter(|s| !s.trim().is_empty());
+
+    let source_url = non_empty(pkg.source.and_then(|s| s.url))
+        .map(|url| url.strip_suffix(".git").map(String::from).unwrap_or(url));
+    let url = non_empty(pkg.support.get("source").cloned())
+        .or(source_url.filter(|u| u.starts_with("https://")))
+        .or(non_empty(pkg.homepage));
+
+    let funding = pkg
+        .funding
+        .iter()
+        .filter_map(ComposerFunding::channel)
+        .collect();
+
+    InstalledPackage {
+        name: pkg.name,
+        version: non_empty(pkg.version)
+            .map(|v| v.trim_start_matches('v').to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: non_empty(pkg.description),
+        url,
+        source: PackageSource::Composer,
+        licenses: pkg.license,
+        funding,
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHPSTAN: &str = r#"{
+        "name": "phpstan/phpstan",
+        "version": "1.11.5",
+        "version_normalized": "1.11.5.0",
+        "source": {"type": "git", "url": "https://github.com/phpstan/phpstan-src.git", "reference": "abc"},
+        "description": "PHPStan - PHP Static Analysis Tool",
+        "license": ["MIT"],
+        "support": {
+            "issues": "https://github.com/phpstan/phpstan/issues",
+            "source": "https://github.com/phpstan/phpstan/tree/1.11.5"
+        },
+        "funding": [
+            {"url": "https://github.com/ondrejmirtes", "type": "github"},
+            {"url": "https://github.com/phpstan", "type": "github"},
+            {"url": "https://opencollective.com/phpstan", "type": "open_collective"}
+        ]
+    }"#;
+
+    #[test]
+    fn parse_installed_json_v2() {
+        let content = format!(r#"{{"packages": [{PHPSTAN}], "dev": true}}"#);
+        let packages = parse_installed_json(&content).unwrap();
+        assert_eq!(packages.len(), 1);
+
+        let pkg = &packages[0];
+        assert_eq!(pkg.name, "phpstan/phpstan");
+        assert_eq!(pkg.version, "1.11.5");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/phpstan/phpstan/tree/1.11.5")
+        );
+        assert_eq!(pkg.licenses, vec!["MIT"]);
+        assert_eq!(pkg.funding.len(), 3);
+        assert_eq!(pkg.funding[0].platform, "GitHub Sponsors");
+        assert_eq!(
+            pkg.funding[0].url,
+            "https://github.com/sponsors/ondrejmirtes"
+        );
+        assert_eq!(pkg.funding[2].platform, "Open Collective");
+        assert_eq!(pkg.source, PackageSource::Composer);
+    }
+
+    #[test]
+    fn parse_installed_json_v1() {
+        let content = format!("[{PHPSTAN}]");
+        assert_eq!(parse_installed_json(&content).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parse_lock_with_dev_packages() {
+        let content = r#"{
+            "packages": [
+                {"name": "monolog/monolog", "version": "3.6.0",
+                 "source": {"type": "git", "url": "https://github.com/Seldaek/monolog.git"}}
+            ],
+            "packages-dev": [
+                {"name": "phpunit/phpunit", "version": "11.2.5", "homepage": "https://phpunit.de/"}
+            ]
+        }"#;
+        let packages = parse_composer_lock(content).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/Seldaek/monolog")
+        );
+        assert_eq!(packages[1].url.as_deref(), Some("https://phpunit.de/"));
+        assert!(packages[1].licenses.is_empty());
+    }
+
+    #[test]
+    fn version_prefix_stripped() {
+        let content = r#"[{"name": "foo/bar", "version": "v2.0.1"}, {"name": "baz/qux"}]"#;
+        let packages = parse_installed_json(content).unwrap();
+        assert_eq!(packages[0].version, "2.0.1");
+        assert_eq!(packages[1].version, "unknown");
+        assert!(packages[1].url.is_none());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_installed_json("not json").is_err());
+        assert!(parse_composer_lock("42").is_err());
+    }
+}
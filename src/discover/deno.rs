@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers scripts installed with `deno install -g`.
+///
+/// Deno installs each script as a small shell (or `.cmd`) launcher in
+/// `$DENO_INSTALL_ROOT/bin` (default `~/.deno/bin`) that runs
+/// `deno run <flags> <module>`. The module specifier is extracted from each
+/// launcher and mapped to a version and source URL: `jsr:` and `npm:`
+/// specifiers point at their registry pages, `deno.land/x` modules at their
+/// module page, and other URLs are kept as they are.
+pub struct DenoDiscoverer;
+
+impl Discoverer for DenoDiscoverer {
+    fn name(&self) -> &str {
+        "deno"
+    }
+
+    fn is_available(&self) -> bool {
+        deno_bin_dir().is_some_and(|dir| dir.is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let bin_dir = deno_bin_dir().context("Could not determine DENO_INSTALL_ROOT")?;
+
+        let mut launchers: Vec<PathBuf> = fs::read_dir(&bin_dir)
+            .with_context(|| format!("Failed to read {}", bin_dir.display()))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect();
+        launchers.sort();
+
+        let pb = ProgressBar::new(launchers.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for path in &launchers {
+            let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                continue;
+            };
+            // The deno binary itself may live here too; it is neither valid
+            // UTF-8 nor a launcher and is skipped.
+            if let Ok(content) = fs::read_to_string(path)
+                && let Some(specifier) = parse_launcher(&content)
+            {
+                packages.push(build_package(name, &specifier));
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// `$DENO_INSTALL_ROOT/bin`, with the root defaulting to `~/.deno`.
+fn deno_bin_dir() -> Option<PathBuf> {
+    let root = std::env::var_os("DENO_INSTALL_ROOT")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".deno")))?;
+    Some(root.join("bin"))
+}
+
+/// Extract the module specifier from a launcher script.
+///
+/// ```text
+/// #!/bin/sh
+/// # generated by deno install
+/// exec deno run --allow-read --no-config 'jsr:@std/http@1.0.0/file-server' "$@"
+/// ```
+///
+/// Windows launchers (`@deno "run" ... "https://..." %*`) are handled the same
+/// way. Returns `None` for files that are not deno launchers.
+fn parse_launcher(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#') && line.contains("deno"))
+        .flat_map(|line| line.split_whitespace())
+        .map(|token| token.trim_matches(['\'', '"']))
+        .find(|token| {
+            ["jsr:", "npm:", "https://", "http://", "file://"]
+                .iter()
+                .any(|prefix| token.starts_with(prefix))
+        })
+        .map(String::from)
+}
+
+/// Split a registry specifier body (`@scope/name@1.0.0/sub/path`) into its
+/// package name and optional version.
+fn split_registry_specifier(body: &str) -> (String, Option<String>) {
+    // Scoped names contain one `/`; the module path starts after that.
+    let segments = if body.starts_with('@') { 2 } else { 1 };
+    let package: String = body
+        .splitn(segments + 1, '/')
+        .take(segments)
+        .collect::<Vec<_>>()
+        .join("/");
+
+    // Skip the first character so a scope's leading `@` is not a separator.
+    let (first, rest) = package.split_at(package.chars().next().map_or(0, char::len_utf8));
+    match rest.split_once('@') {
+        Some((name, version)) => (format!("{first}{name}"), Some(version.to_string())),
+        None => (package, None),
+    }
+}
+
+/// Derive a version and source URL from a module specifier.
+fn specifier_info(specifier: &str) -> (Option<String>, Option<String>) {
+    if let Some(body) = specifier.strip_prefix("jsr:") {
+        let (name, version) = split_registry_specifier(body.trim_start_matches('/'));
+        return (version, Some(format!("https://jsr.io/{name}")));
+    }
+    if let Some(body) = specifier.strip_prefix("npm:") {
+        let (name, version) = split_registry_specifier(body.trim_start_matches('/'));
+        return (
+            version,
+            Some(format!("https://www.npmjs.com/package/{name}")),
+        );
+    }
+    if let Some(rest) = specifier.strip_prefix("https://deno.land/") {
+        // deno.land/x/<module>@<version>/... or deno.land/std@<version>/...
+        let (prefix, rest) = match rest.strip_prefix("x/") {
+            Some(rest) => ("https://deno.land/x/", rest),
+            None => ("https://deno.land/", rest),
+        };
+        let module = rest.split('/').next().unwrap_or(rest);
+        let (name, version) = match module.split_once('@') {
+            Some((name, version)) => (name, Some(version.trim_start_matches('v').to_string())),
+            None => (module, None),
+        };
+        let url = if prefix == "https://deno.land/" && name == "std" {
+            "https://github.com/denoland/std".to_string()
+        } else {
+            format!("{prefix}{name}")
+        };
+        return (version, Some(url));
+    }
+    if specifier.starts_with("file://") {
+        return (None, None);
+    }
+    (None, Some(specifier.to_string()))
+}
+
+fn build_package(name: String, specifier: &str) -> InstalledPackage {
+    let (version, url) = specifier_info(specifier);
+    InstalledPackage {
+        name,
+        version: version.unwrap_or_else(|| "unknown".to_string()),
+        description: None,
+        url,
+        source: PackageSource::Deno,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unix_launcher() {
+        let content = "#!/bin/sh\n# generated by deno install\nexec deno run --allow-read --no-config 'jsr:@std/http@1.0.0/file-server' \"$@\"\n";
+        assert_eq!(
+            parse_launcher(content).as_deref(),
+            Some("jsr:@std/http@1.0.0/file-server")
+        );
+    }
+
+    #[test]
+    fn parse_windows_launcher() {
+        let content = "% generated by deno install %\r\n@deno \"run\" \"--allow-net\" \"https://deno.land/x/denon@2.5.0/denon.ts\" %*\r\n";
+        assert_eq!(
+            parse_launcher(content).as_deref(),
+            Some("https://deno.land/x/denon@2.5.0/denon.ts")
+        );
+    }
+
+    #[test]
+    fn parse_non_launcher() {
+        assert!(parse_launcher("\x7fELF binary").is_none());
+    }
+
+    #[test]
+    fn jsr_and_npm_specifiers() {
+        assert_eq!(
+            specifier_info("jsr:@std/http@1.0.0/file-server"),
+            (
+                Some("1.0.0".to_string()),
+                Some("https://jsr.io/@std/http".to_string())
+            )
+        );
+        assert_eq!(
+            specifier_info("npm:cowsay@1.6.0"),
+            (
+                Some("1.6.0".to_string()),
+                Some("https://www.npmjs.com/package/cowsay".to_string())
+            )
+        );
+        assert_eq!(
+            specifier_info("npm:@biomejs/biome"),
+            (
+                None,
+                Some("https://www.npmjs.com/package/@biomejs/biome".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn deno_land_specifiers() {
+        assert_eq!(
+            specifier_info("https://deno.land/x/denon@v2.5.0/denon.ts"),
+            (
+                Some("2.5.0".to_string()),
+                Some("https://deno.land/x/denon".to_string())
+            )
+        );
+        assert_eq!(
+            specifier_info("https://deno.land/std@0.224.0/http/file_server.ts"),
+            (
+                Some("0.224.0".to_string()),
+                Some("https://github.com/denoland/std".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn other_specifiers() {
+        assert_eq!(
+            specifier_info("https://example.com/tool.ts"),
+            (None, Some("https://example.com/tool.ts".to_string()))
+        );
+        assert_eq!(specifier_info("file:///home/user/tool.ts"), (None, None));
+
+        let pkg = build_package("tool".to_string(), "file:///home/user/tool.ts");
+        assert_eq!(pkg.version, "unknown");
+        assert_eq!(pkg.source, PackageSource::Deno);
+    }
+}
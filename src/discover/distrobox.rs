@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use super::{apk, apt, dnf, pacman};
+
+/// The tool managing a development container.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Manager {
+    Distrobox,
+    Toolbox,
+}
+
+/// A development container and the image it was created from.
+#[derive(Debug, PartialEq)]
+struct DevContainer {
+    name: String,
+    image: String,
+    manager: Manager,
+}
+
+/// Discovers distrobox and toolbox development containers.
+///
+/// Each container is reported as a package named after the container, with
+/// its image's tag as the version. With `[discover] container_packages =
+/// true`, the packages installed inside each container are reported too:
+/// a small script run through `distrobox enter` / `toolbox run` prints the
+/// container's package database (dpkg, apk, pacman, or rpm), which is parsed
+/// with the matching host backend's parser. Those packages are named
+/// `<container>/<package>` so each container's dependencies stay distinct
+/// from the host's and from each other.
+pub struct DistroboxDiscoverer {
+    /// Whether to scan inside each container.
+    pub scan_packages: bool,
+}
+
+impl Discoverer for DistroboxDiscoverer {
+    fn name(&self) -> &str {
+        "distrobox"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("distrobox") || super::in_path("toolbox")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let mut containers: BTreeMap<String, DevContainer> = BTreeMap::new();
+        if super::in_path("distrobox") {
+            let output = run(Command::new("distrobox").args(["list", "--no-color"]))?;
+            for c in parse_distrobox_list(&output) {
+                containers.entry(c.name.clone()).or_insert(c);
+            }
+        }
+        if super::in_path("toolbox") {
+            let output = run(Command::new("toolbox").args(["list", "--containers"]))?;
+            for c in parse_toolbox_list(&output) {
+                containers.entry(c.name.clone()).or_insert(c);
+            }
+        }
+
+        let pb = ProgressBar::new(containers.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} containers")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for container in containers.values() {
+            packages.push(container_package(container));
+            if self.scan_packages {
+                match scan_container(container) {
+                    Ok(inner) => packages.extend(inner),
+                    Err(e) => pb.suspend(|| {
+                        eprintln!(
+                            "  Warning: failed to scan container {}: {e}",
+                            container.name
+                        )
+                    }),
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run a command and return its stdout.
+fn run(command: &mut Command) -> Result<String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {program}"))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).with_context(|| format!("{program} output is not valid UTF-8"))
+}
+
+/// Parse `distrobox list --no-color`.
+///
+/// ```text
+/// ID           | NAME     | STATUS       | IMAGE
+/// d4f1c8e6a1b2 | ubuntu   | Up 2 hours   | quay.io/toolbx/ubuntu-toolbox:22.04
+/// ```
+fn parse_distrobox_list(output: &str) -> Vec<DevContainer> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            match fields.as_slice() {
+                [_, name, _, image, ..] if !name.is_empty() => Some(DevContainer {
+                    name: name.to_string(),
+                    image: image.to_string(),
+                    manager: Manager::Distrobox,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse `toolbox list --containers`.
+///
+/// ```text
+/// CONTAINER ID  CONTAINER NAME     CREATED      STATUS   IMAGE NAME
+/// 5b9c4a1e2f3d  fedora-toolbox-40  2 weeks ago  running  registry.fedoraproject.org/fedora-toolbox:40
+/// ```
+///
+/// Columns are padded with spaces and `CREATED` contains spaces itself, so
+/// only the first two fields and the last one are used.
+fn parse_toolbox_list(output: &str) -> Vec<DevContainer> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, name, .., image] if fields.len() >= 3 => Some(DevContainer {
+                    name: name.to_string(),
+                    image: image.to_string(),
+                    manager: Manager::Toolbox,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Report the container itself, versioned by its image tag.
+fn container_package(container: &DevContainer) -> InstalledPackage {
+    let name_start = container.image.rfind('/').map_or(0, |i| i + 1);
+    let version = container.image[name_start..]
+        .rsplit_once(':')
+        .map(|(_, tag)| tag)
+        .unwrap_or("latest");
+
+    InstalledPackage {
+        name: container.name.clone(),
+        version: version.to_string(),
+        description: Some(container.image.clone()),
+        url: None,
+        source: PackageSource::Distrobox,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+/// Shell script printing the container's package database, preceded by a
+/// line naming its format. pacman's per-package `desc` files are separated
+/// by NUL bytes.
+fn package_dump_script() -> String {
+    format!(
+        r#"if [ -f {dpkg} ]; then echo dpkg; cat {dpkg}
+elif [ -f {apk} ]; then echo apk; cat {apk}
+elif [ -d {pacman} ]; then echo pacman; for f in {pacman}/*/desc; do cat "$f"; printf '\0'; done
+elif command -v rpm >/dev/null 2>&1; then echo rpm; rpm -qa --queryformat '{rpm}'
+else echo none
+fi"#,
+        dpkg = apt::DPKG_STATUS_PATH,
+        apk = apk::APK_DB_PATH,
+        pacman = pacman::PACMAN_DB_PATH,
+        rpm = dnf::RPM_QUERYFORMAT,
+    )
+}
+
+/// Enumerate the packages installed inside a container.
+fn scan_container(container: &DevContainer) -> Result<Vec<InstalledPackage>> {
+    let script = package_dump_script();
+    let mut command = match container.manager {
+        Manager::Distrobox => {
+            let mut c = Command::new("distrobox");
+            c.args(["enter", &container.name, "--", "sh", "-c", &script]);
+            c
+        }
+        Manager::Toolbox => {
+            let mut c = Command::new("toolbox");
+            c.args(["run", "-c", &container.name, "sh", "-c", &script]);
+            c
+        }
+    };
+    let output = run(&mut command)?;
+    parse_package_dump(&container.name, &output)
+}
+
+/// Parse the output of [`package_dump_script`], prefixing every package
+/// name with the container's.
+fn parse_package_dump(container: &str, output: &str) -> Result<Vec<InstalledPackage>> {
+    let (format, content) = output.split_once('\n').unwrap_or((output, ""));
+    let packages = match format.trim() {
+        "dpkg" => apt::parse_dpkg_status(content)?,
+        "apk" => apk::parse_installed_db(content),
+        "pacman" => content
+            .split('\0')
+            .filter(|desc| !desc.trim().is_empty())
+            .filter_map(|desc| pacman::parse_desc_content(desc).ok())
+            .collect(),
+        "rpm" => dnf::parse_rpm_output(content)?,
+        "none" => Vec::new(),
+        other => anyhow::bail!("Unexpected package database marker: {other}"),
+    };
+
+    Ok(packages
+        .into_iter()
+        .map(|pkg| InstalledPackage {
+            name: format!("{container}/{}", pkg.name),
+            source: PackageSource::Distrobox,
+            ..pkg
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_distrobox_output() {
+        let output = "\
+ID           | NAME                 | STATUS             | IMAGE
+d4f1c8e6a1b2 | ubuntu               | Up 2 hours         | quay.io/toolbx/ubuntu-toolbox:22.04
+0a1b2c3d4e5f | arch                 | Exited (0) 2 days ago | quay.io/toolbx/arch-toolbox:latest
+";
+        let containers = parse_distrobox_list(output);
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].name, "ubuntu");
+        assert_eq!(containers[0].image, "quay.io/toolbx/ubuntu-toolbox:22.04");
+        assert_eq!(containers[0].manager, Manager::Distrobox);
+        assert_eq!(containers[1].name, "arch");
+    }
+
+    #[test]
+    fn parse_toolbox_output() {
+        let output = "\
+CONTAINER ID  CONTAINER NAME     CREATED      STATUS   IMAGE NAME
+5b9c4a1e2f3d  fedora-toolbox-40  2 weeks ago  running  registry.fedoraproject.org/fedora-toolbox:40
+";
+        let containers = parse_toolbox_list(output);
+        assert_eq!(
+            containers,
+            vec![DevContainer {
+                name: "fedora-toolbox-40".to_string(),
+                image: "registry.fedoraproject.org/fedora-toolbox:40".to_string(),
+                manager: Manager::Toolbox,
+            }]
+        );
+        assert!(parse_toolbox_list("").is_empty());
+    }
+
+    #[test]
+    fn container_is_versioned_by_image_tag() {
+        let pkg = container_package(&DevContainer {
+            name: "dev".to_string(),
+            image: "localhost:5000/dev-image".to_string(),
+            manager: Manager::Distrobox,
+        });
+        assert_eq!(pkg.name, "dev");
+        assert_eq!(pkg.version, "latest");
+        assert_eq!(pkg.source, PackageSource::Distrobox);
+    }
+
+    #[test]
+    fn parse_dpkg_dump() {
+        let output = "\
+dpkg
+Package: curl
+Status: install ok installed
+Version: 8.5.0-2ubuntu10
+Homepage: https://curl.se/
+Description: command line tool for transferring data with URL syntax
+";
+        let packages = parse_package_dump("ubuntu", output).unwrap();
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "ubuntu/curl");
+        assert_eq!(packages[0].url.as_deref(), Some("https://curl.se/"));
+        assert_eq!(packages[0].source, PackageSource::Distrobox);
+    }
+
+    #[test]
+    fn parse_pacman_dump() {
+        let output = "pacman\n%NAME%\nripgrep\n\n%VERSION%\n14.1.0-1\n\0%NAME%\nfd\n\n%VERSION%\n10.1.0-1\n\0";
+        let packages = parse_package_dump("arch", output).unwrap();
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["arch/ripgrep", "arch/fd"]);
+    }
+
+    #[test]
+    fn parse_rpm_dump() {
+        let output = "rpm\ngit\t2.45.2-1.fc40\tFast Version Control System\thttps://git-scm.com/\tGPL-2.0-only\n";
+        let packages = parse_package_dump("fedora", output).unwrap();
+        assert_eq!(packages[0].name, "fedora/git");
+        assert_eq!(packages[0].version, "2.45.2-1.fc40");
+    }
+
+    #[test]
+    fn parse_unknown_dump() {
+        assert!(parse_package_dump("x", "none\n").unwrap().is_empty());
+        assert!(parse_package_dump("x", "garbage\n").is_err());
+    }
+}
@@ -1,20 +1,28 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
 
 /// Discovers packages installed via dnf/rpm (Fedora, RHEL, and derivatives).
 ///
 /// Queries the RPM database using `rpm -qa --queryformat` to enumerate all
 /// installed packages. Falls back to the `rpm` command rather than linking
 /// against librpm directly.
+///
+/// When dnf is installed, `dnf history userinstalled` tells which packages
+/// were installed on purpose; every other package is marked as a dependency.
 pub struct DnfDiscoverer;
 
+/// `rpm -qa` output format understood by [`parse_rpm_output`].
+pub(crate) const RPM_QUERYFORMAT: &str =
+    "%{NAME}\t%{VERSION}-%{RELEASE}\t%{SUMMARY}\t%{URL}\t%{LICENSE}\n";
+
 impl Discoverer for DnfDiscoverer {
     fn name(&self) -> &str {
         "dnf"
@@ -26,11 +34,7 @@ impl Discoverer for DnfDiscoverer {
 
     fn discover(&self) -> Result<Vec<InstalledPackage>> {
         let output = Command::new("rpm")
-            .args([
-                "-qa",
-                "--queryformat",
-                "%{NAME}\t%{VERSION}-%{RELEASE}\t%{SUMMARY}\t%{URL}\t%{LICENSE}\n",
-            ])
+            .args(["-qa", "--queryformat", RPM_QUERYFORMAT])
             .output()
             .context("Failed to run rpm -qa")?;
 
@@ -44,14 +48,86 @@ impl Discoverer for DnfDiscoverer {
         let stdout =
             String::from_utf8(output.stdout).context("rpm -qa output is not valid UTF-8")?;
 
-        parse_rpm_output(&stdout)
+        let mut packages = parse_rpm_output(&stdout)?;
+
+        if super::in_path("dnf") {
+            match user_installed() {
+                Ok(names) => mark_install_reasons(&mut packages, &names),
+                Err(e) => {
+                    eprintln!("  Warning: could not determine user-installed packages: {e}")
+                }
+            }
+        }
+
+        Ok(packages)
+    }
+}
+
+/// List the names of packages the user installed explicitly, according to
+/// the dnf history database.
+fn user_installed() -> Result<HashSet<String>> {
+    let output = Command::new("dnf")
+        .args(["history", "userinstalled"])
+        .output()
+        .context("Failed to run dnf history userinstalled")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "dnf history userinstalled failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .context("dnf history userinstalled output is not valid UTF-8")?;
+
+    Ok(parse_userinstalled(&stdout))
+}
+
+/// Parse `dnf history userinstalled`, which prints one NEVRA per line after
+/// a header.
+///
+/// ```text
+/// Packages installed by user
+/// bash-5.2.26-3.fc40.x86_64
+/// vim-enhanced-2:9.1.158-1.fc40.x86_64
+/// ```
+fn parse_userinstalled(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter_map(|line| nevra_name(line.trim()))
+        .map(String::from)
+        .collect()
+}
+
+/// Extract the package name from a `name-[epoch:]version-release.arch`
+/// string. Lines that do not have this shape (e.g. headers) yield `None`.
+fn nevra_name(nevra: &str) -> Option<&str> {
+    let (rest, arch) = nevra.rsplit_once('.')?;
+    if arch.is_empty() || arch.contains(char::is_whitespace) {
+        return None;
+    }
+    let mut parts = rest.rsplitn(3, '-');
+    let (_release, _version, name) = (parts.next()?, parts.next()?, parts.next()?);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Mark packages in `user_installed` as explicit and all others as
+/// dependencies.
+fn mark_install_reasons(packages: &mut [InstalledPackage], user_installed: &HashSet<String>) {
+    for pkg in packages {
+        pkg.install_reason = if user_installed.contains(&pkg.name) {
+            InstallReason::Explicit
+        } else {
+            InstallReason::Dependency
+        };
     }
 }
 
 /// Parse the tab-separated output of `rpm -qa --queryformat`.
 ///
 /// Expected columns: NAME, VERSION-RELEASE, SUMMARY, URL, LICENSE.
-fn parse_rpm_output(output: &str) -> Result<Vec<InstalledPackage>> {
+pub(super) fn parse_rpm_output(output: &str) -> Result<Vec<InstalledPackage>> {
     let lines: Vec<&str> = output.lines().filter(|l| !l.is_empty()).collect();
 
     let pb = ProgressBar::new(lines.len() as u64);
@@ -87,7 +163,7 @@ fn parse_rpm_output(output: &str) -> Result<Vec<InstalledPackage>> {
 ///
 /// Expected columns: NAME, VERSION-RELEASE, SUMMARY, URL, LICENSE.
 /// RPM uses the literal string `(none)` for missing fields.
-fn parse_rpm_line(line: &str) -> Result<InstalledPackage> {
+pub(crate) fn parse_rpm_line(line: &str) -> Result<InstalledPackage> {
     let fields: Vec<&str> = line.split('\t').collect();
 
     let name = fields
@@ -125,6 +201,9 @@ fn parse_rpm_line(line: &str) -> Result<InstalledPackage> {
         url,
         source: PackageSource::Dnf,
         licenses,
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
     })
 }
 
@@ -224,4 +303,34 @@ vim-enhanced\t9.1.158-1.fc40\tA version of the VIM editor\thttps://www.vim.org\t
         assert_eq!(packages.len(), 1);
         assert_eq!(packages[0].version, "unknown");
     }
+
+    #[test]
+    fn parse_userinstalled_output() {
+        let output = "\
+Packages installed by user
+bash-5.2.26-3.fc40.x86_64
+vim-enhanced-2:9.1.158-1.fc40.x86_64
+python3-dnf-plugins-core-4.6.0-1.fc40.noarch
+";
+        let names = parse_userinstalled(output);
+        assert_eq!(names.len(), 3);
+        assert!(names.contains("bash"));
+        assert!(names.contains("vim-enhanced"));
+        assert!(names.contains("python3-dnf-plugins-core"));
+    }
+
+    #[test]
+    fn install_reasons_are_marked() {
+        let output = "\
+bash\t5.2.26-3.fc40\tThe GNU Bourne Again shell\thttps://www.gnu.org/software/bash\tGPL-3.0-or-later
+glibc\t2.39-6.fc40\tThe GNU libc libraries\thttps://www.gnu.org/software/glibc/\tLGPL-2.1-or-later
+";
+        let mut packages = parse_rpm_output(output).unwrap();
+        assert_eq!(packages[0].install_reason, InstallReason::Unknown);
+
+        let user_installed = parse_userinstalled("bash-5.2.26-3.fc40.x86_64\n");
+        mark_install_reasons(&mut packages, &user_installed);
+        assert_eq!(packages[0].install_reason, InstallReason::Explicit);
+        assert_eq!(packages[1].install_reason, InstallReason::Dependency);
+    }
 }
@@ -17,7 +17,15 @@ use super::{Discoverer, InstalledPackage, PackageSource};
 /// images, then inspects each image via `docker inspect` to extract OCI metadata
 /// labels (description, source URL, licenses). Dangling images (those with
 /// `<none>` as repository) are filtered out.
-pub struct DockerDiscoverer;
+///
+/// Images sharing a digest (the same image under several tags or names) are
+/// reported once. When network access has been opted into (`enrich = true`),
+/// images without OCI source labels are looked up in their registry for the
+/// labels and annotations published there.
+pub struct DockerDiscoverer {
+    /// Whether to query image registries for missing labels.
+    pub query_registry: bool,
+}
 
 impl Discoverer for DockerDiscoverer {
     fn name(&self) -> &str {
@@ -48,7 +56,12 @@ impl Discoverer for DockerDiscoverer {
         let stdout = String::from_utf8(output.stdout)
             .context("docker image ls output is not valid UTF-8")?;
 
-        let images = parse_image_list(&stdout)?;
+        let images = oci::dedupe_by_digest(parse_image_list(&stdout)?, |image| &image.id);
+        let client = if self.query_registry {
+            Some(oci::registry_client()?)
+        } else {
+            None
+        };
 
         let pb = ProgressBar::new(images.len() as u64);
         pb.set_style(
@@ -60,7 +73,22 @@ impl Discoverer for DockerDiscoverer {
         let packages: Vec<InstalledPackage> = images
             .iter()
             .map(|image| {
-                let labels = fetch_image_labels(&image.id);
+                let mut labels = fetch_image_labels(&image.id);
+                if let Some(client) = &client
+                    && let Err(e) = oci::merge_registry_labels(
+                        client,
+                        &image.repository,
+                        &image.tag,
+                        &mut labels,
+                    )
+                {
+                    pb.suspend(|| {
+                        eprintln!(
+                            "  Warning: registry lookup failed for {}:{}: {e}",
+                            image.repository, image.tag
+                        )
+                    });
+                }
                 let pkg = oci::build_package_from_labels(
                     &image.repository,
                     &image.tag,
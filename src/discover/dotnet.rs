@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::nuget::{self, Nuspec};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers .NET global tools installed with `dotnet tool install -g`.
+///
+/// Runs `dotnet tool list --global` for package IDs and versions, then reads
+/// each tool's `.nuspec` from the tool store (`~/.dotnet/tools/.store`) for
+/// its description, repository URL, and license.
+pub struct DotnetToolsDiscoverer;
+
+impl Discoverer for DotnetToolsDiscoverer {
+    fn name(&self) -> &str {
+        "dotnet"
+    }
+
+    fn is_available(&self) -> bool {
+        dotnet_tools_dir().is_some_and(|dir| dir.is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let store = dotnet_tools_dir()
+            .context("Could not determine .NET tools directory")?
+            .join(".store");
+
+        let output = Command::new("dotnet")
+            .args(["tool", "list", "--global"])
+            .output()
+            .context("Failed to run dotnet tool list")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "dotnet tool list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("dotnet tool list output is not valid UTF-8")?;
+        let tools = parse_tool_list(&stdout);
+
+        let pb = ProgressBar::new(tools.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (id, version) in tools {
+            let nuspec = nuget::read_nuspec(&nuspec_path(&store, &id, &version), &pb);
+            packages.push(build_package(id, version, nuspec));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// `~/.dotnet/tools`, honoring `$DOTNET_CLI_HOME` as the home directory.
+fn dotnet_tools_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("DOTNET_CLI_HOME")
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf()))?;
+    Some(home.join(".dotnet/tools"))
+}
+
+/// Location of a tool's nuspec inside the tool store.
+///
+/// The store lays packages out as
+/// `<id>/<version>/<id>/<version>/<id>.nuspec`, with the ID lowercased.
+fn nuspec_path(store: &Path, id: &str, version: &str) -> PathBuf {
+    let id = id.to_lowercase();
+    store
+        .join(&id)
+        .join(version)
+        .join(&id)
+        .join(version)
+        .join(format!("{id}.nuspec"))
+}
+
+/// Parse `dotnet tool list --global` into `(package id, version)` pairs.
+///
+/// ```text
+/// Package Id      Version      Commands
+/// -------------------------------------
+/// dotnet-ef       8.0.6        dotnet-ef
+/// ```
+fn parse_tool_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let id = fields.next()?;
+            let version = fields.next().unwrap_or("unknown");
+            Some((id.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+fn build_package(id: String, version: String, nuspec: Nuspec) -> InstalledPackage {
+    InstalledPackage {
+        name: id,
+        version,
+        description: nuspec.short_description(),
+        url: nuspec
+            .repository_url
+            .or(nuspec.project_source_url)
+            .or(nuspec.project_url),
+        source: PackageSource::Dotnet,
+        licenses: nuspec.license.or(nuspec.license_url).into_iter().collect(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_output() {
+        let output = "\
+Package Id                      Version      Commands
+------------------------------------------------------------
+dotnet-ef                       8.0.6        dotnet-ef
+Microsoft.dotnet-interactive    1.0.522904   dotnet-interactive
+";
+        assert_eq!(
+            parse_tool_list(output),
+            vec![
+                ("dotnet-ef".to_string(), "8.0.6".to_string()),
+                (
+                    "Microsoft.dotnet-interactive".to_string(),
+                    "1.0.522904".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_list_empty() {
+        let output =
+            "Package Id      Version      Commands\n-------------------------------------\n";
+        assert!(parse_tool_list(output).is_empty());
+        assert!(parse_tool_list("").is_empty());
+    }
+
+    #[test]
+    fn nuspec_path_is_lowercased() {
+        let path = nuspec_path(Path::new("/store"), "Microsoft.Tool", "1.0.0");
+        assert_eq!(
+            path,
+            Path::new("/store/microsoft.tool/1.0.0/microsoft.tool/1.0.0/microsoft.tool.nuspec")
+        );
+    }
+
+    #[test]
+    fn build_package_prefers_repository_url() {
+        let nuspec = Nuspec {
+            project_url: Some("https://learn.microsoft.com/ef/core/".to_string()),
+            repository_url: Some("https://github.com/dotnet/efcore".to_string()),
+            license: Some("MIT".to_string()),
+            ..Default::default()
+        };
+        let pkg = build_package("dotnet-ef".to_string(), "8.0.6".to_string(), nuspec);
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/dotnet/efcore"));
+        assert_eq!(pkg.licenses, vec!["MIT"]);
+        assert_eq!(pkg.source, PackageSource::Dotnet);
+    }
+}
@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers packages installed via eopkg (Solus).
+///
+/// Runs `eopkg list-installed` for the set of installed package names, then
+/// reads each package's `metadata.xml` from the eopkg database under
+/// `/var/lib/eopkg/package/` for its version, summary, homepage, and licenses.
+pub struct EopkgDiscoverer;
+
+const EOPKG_DB_PATH: &str = "/var/lib/eopkg/package";
+
+impl Discoverer for EopkgDiscoverer {
+    fn name(&self) -> &str {
+        "eopkg"
+    }
+
+    fn is_available(&self) -> bool {
+        Path::new(EOPKG_DB_PATH).is_dir()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("eopkg")
+            .args(["list-installed", "--no-color"])
+            .output()
+            .context("Failed to run eopkg list-installed")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "eopkg list-installed failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("eopkg list-installed output is not valid UTF-8")?;
+        let names = parse_list_installed(&stdout);
+
+        let metadata_paths: Vec<_> = fs::read_dir(EOPKG_DB_PATH)
+            .context("Failed to read eopkg package database")?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path().join("metadata.xml");
+                path.is_file().then_some(path)
+            })
+            .collect();
+
+        let pb = ProgressBar::new(metadata_paths.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut by_name: HashMap<String, InstalledPackage> = HashMap::new();
+        for path in &metadata_paths {
+            let parsed = fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_metadata_xml(&content));
+            match parsed {
+                Ok(pkg) => {
+                    by_name.insert(pkg.name.clone(), pkg);
+                }
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                by_name.remove(&name).unwrap_or_else(|| InstalledPackage {
+                    name,
+                    version: "unknown".to_string(),
+                    description: None,
+                    url: None,
+                    source: PackageSource::Eopkg,
+                    licenses: Vec::new(),
+                    funding: Vec::new(),
+                    install_reason: InstallReason::Unknown,
+                    maintainer: None,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Parse `eopkg list-installed` output.
+///
+/// Each line is `<name> - <summary>`, with the name padded to a fixed width.
+fn parse_list_installed(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(String::from)
+        .collect()
+}
+
+/// Parse a package's `metadata.xml`.
+///
+/// ```xml
+/// <PISI>
+///   <Source><Name>nano</Name><Homepage>https://www.nano-editor.org/</Homepage></Source>
+///   <Package>
+///     <Name>nano</Name>
+///     <Summary xml:lang="en">Small, friendly text editor</Summary>
+///     <License>GPL-3.0-or-later</License>
+///     <History><Update release="52"><Version>7.2</Version></Update></History>
+///   </Package>
+/// </PISI>
+/// ```
+///
+/// The version is taken from the newest (first) history entry and combined
+/// with its release number, matching how eopkg displays versions.
+fn parse_metadata_xml(content: &str) -> Result<InstalledPackage> {
+    let doc = roxmltree::Document::parse(content).context("Invalid metadata.xml")?;
+    let root = doc.root_element();
+
+    let package = child(root, "Package").context("Missing <Package> element")?;
+    let source = child(root, "Source");
+
+    let name = text(child(package, "Name")).context("Missing package <Name>")?;
+
+    let update = child(package, "History").and_then(|h| child(h, "Update"));
+    let version = text(update.and_then(|u| child(u, "Version")))
+        .map(|v| match update.and_then(|u| u.attribute("release")) {
+            Some(release) => format!("{v}-{release}"),
+            None => v,
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let description = text(child(package, "Summary"));
+    let url = text(source.and_then(|s| child(s, "Homepage")));
+    let licenses = package
+        .children()
+        .filter(|n| n.has_tag_name("License"))
+        .filter_map(|n| text(Some(n)))
+        .collect();
+
+    Ok(InstalledPackage {
+        name,
+        version,
+        description,
+        url,
+        source: PackageSource::Eopkg,
+        licenses,
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    })
+}
+
+/// First child element of `node` named `tag`.
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    tag: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+/// Trimmed, non-empty text content of an element.
+fn text(node: Option<roxmltree::Node<'_, '_>>) -> Option<String> {
+    node.and_then(|n| n.text())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NANO_XML: &str = r#"<?xml version="1.0" ?>
+<PISI>
+    <Source>
+        <Name>nano</Name>
+        <Homepage>https://www.nano-editor.org/</Homepage>
+        <Packager>
+            <Name>Solus Team</Name>
+            <Email>root@getsol.us</Email>
+        </Packager>
+    </Source>
+    <Package>
+        <Name>nano</Name>
+        <Summary xml:lang="en">Small, friendly text editor</Summary>
+        <Description xml:lang="en">GNU nano is an easy-to-use text editor.</Description>
+        <PartOf>system.utils</PartOf>
+        <License>GPL-3.0-or-later</License>
+        <History>
+            <Update release="52">
+                <Date>2024-01-21</Date>
+                <Version>7.2</Version>
+            </Update>
+            <Update release="51">
+                <Date>2023-05-02</Date>
+                <Version>7.1</Version>
+            </Update>
+        </History>
+        <Distribution>Solus</Distribution>
+        <Architecture>x86_64</Architecture>
+    </Package>
+</PISI>
+"#;
+
+    #[test]
+    fn parse_nano_metadata() {
+        let pkg = parse_metadata_xml(NANO_XML).unwrap();
+        assert_eq!(pkg.name, "nano");
+        assert_eq!(pkg.version, "7.2-52");
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("Small, friendly text editor")
+        );
+        assert_eq!(pkg.url.as_deref(), Some("https://www.nano-editor.org/"));
+        assert_eq!(pkg.licenses, vec!["GPL-3.0-or-later"]);
+        assert_eq!(pkg.source, PackageSource::Eopkg);
+    }
+
+    #[test]
+    fn parse_metadata_without_history() {
+        let xml = "<PISI><Package><Name>foo</Name><License>MIT</License><License>BSD-3-Clause</License></Package></PISI>";
+        let pkg = parse_metadata_xml(xml).unwrap();
+        assert_eq!(pkg.version, "unknown");
+        assert!(pkg.url.is_none());
+        assert_eq!(pkg.licenses, vec!["MIT", "BSD-3-Clause"]);
+    }
+
+    #[test]
+    fn parse_metadata_missing_package_errors() {
+        assert!(parse_metadata_xml("<PISI><Source/></PISI>").is_err());
+        assert!(parse_metadata_xml("not xml").is_err());
+    }
+
+    #[test]
+    fn parse_list_output() {
+        let output = "\
+alsa-lib                       - Advanced Linux Sound Architecture (ALSA) library
+nano                           - Small, friendly text editor
+";
+        assert_eq!(parse_list_installed(output), vec!["alsa-lib", "nano"]);
+    }
+}
@@ -0,0 +1,388 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, HashMap};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use crate::project::FundingChannel;
+
+/// The main F-Droid repository's index.
+const FDROID_INDEX_URL: &str = "https://f-droid.org/repo/index-v2.json";
+
+/// Package names of F-Droid clients, as recorded by Android as the installer
+/// of the apps they install.
+const FDROID_INSTALLERS: &[&str] = &[
+    "org.fdroid.fdroid",
+    "org.fdroid.fdroid.privileged",
+    "org.fdroid.basic",
+    "com.looker.droidify",
+    "com.machiav3lli.fdroid",
+    "eu.bubu1.fdroidclassic",
+];
+
+/// An app installed on a device.
+#[derive(Debug, PartialEq)]
+struct DeviceApp {
+    package: String,
+    version_code: Option<u64>,
+    installer: Option<String>,
+}
+
+/// Discovers F-Droid apps installed on connected Android devices.
+///
+/// Opt-in via `[discover] android = true`, since it downloads the F-Droid
+/// index. For every device listed by `adb devices`, the third-party packages
+/// are listed with `pm list packages` along with their installer, and those
+/// installed by an F-Droid client are looked up in the F-Droid index for
+/// their version name, summary, license, source repository, and donation
+/// links.
+pub struct FdroidDiscoverer {
+    /// Whether Android discovery is enabled in the config.
+    pub enabled: bool,
+}
+
+impl Discoverer for FdroidDiscoverer {
+    fn name(&self) -> &str {
+        "fdroid"
+    }
+
+    fn is_available(&self) -> bool {
+        self.enabled && super::in_path("adb")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let devices = parse_adb_devices(&run_adb(&["devices"])?);
+        if devices.is_empty() {
+            anyhow::bail!("No Android device connected");
+        }
+
+        let mut apps = Vec::new();
+        for serial in &devices {
+            let output = run_adb(&[
+                "-s",
+                serial,
+                "shell",
+                "pm",
+                "list",
+                "packages",
+                "-3",
+                "-i",
+                "--show-versioncode",
+            ])?;
+            apps.extend(parse_package_list(&output).into_iter().filter(|app| {
+                app.installer
+                    .as_deref()
+                    .is_some_and(|i| FDROID_INSTALLERS.contains(&i))
+            }));
+        }
+        if apps.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let index = fetch_index()?;
+
+        let mut packages: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+        for app in apps {
+            let pkg = build_package(&app, index.packages.get(&app.package));
+            packages
+                .entry((pkg.name.clone(), pkg.version.clone()))
+                .or_insert(pkg);
+        }
+        Ok(packages.into_values().collect())
+    }
+}
+
+/// Run an adb command and return its stdout.
+fn run_adb(args: &[&str]) -> Result<String> {
+    let output = Command::new("adb")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run adb {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "adb {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("adb output is not valid UTF-8")
+}
+
+/// Parse `adb devices`, keeping the serials of devices that are online
+/// (not `offline` or `unauthorized`).
+///
+/// ```text
+/// List of devices attached
+/// R58M12ABCDE    device
+/// ```
+fn parse_adb_devices(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                (Some(serial), Some("device")) => Some(serial.to_string()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse `pm list packages -i --show-versioncode`.
+///
+/// ```text
+/// package:org.fdroid.fdroid versionCode:1019050 installer=org.fdroid.fdroid
+/// package:com.example.app versionCode:42 installer=null
+/// ```
+fn parse_package_list(output: &str) -> Vec<DeviceApp> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut app = DeviceApp {
+                package: String::new(),
+                version_code: None,
+                installer: None,
+            };
+            for field in line.split_whitespace() {
+                if let Some(package) = field.strip_prefix("package:") {
+                    app.package = package.to_string();
+                } else if let Some(code) = field.strip_prefix("versionCode:") {
+                    app.version_code = code.parse().ok();
+                } else if let Some(installer) = field.strip_prefix("installer=") {
+                    app.installer = Some(installer.to_string()).filter(|i| i != "null");
+                }
+            }
+            (!app.package.is_empty()).then_some(app)
+        })
+        .collect()
+}
+
+/// Download the F-Droid index.
+fn fetch_index() -> Result<FdroidIndex> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .build()?;
+    let body = client
+        .get(FDROID_INDEX_URL)
+        .send()
+        .context("Failed to download the F-Droid index")?
+        .error_for_status()?
+        .text()?;
+    parse_index(&body)
+}
+
+/// The parts of the F-Droid `index-v2.json` that syld cares about.
+#[derive(Deserialize)]
+struct FdroidIndex {
+    #[serde(default)]
+    packages: HashMap<String, IndexPackage>,
+}
+
+#[derive(Deserialize)]
+struct IndexPackage {
+    metadata: IndexMetadata,
+    #[serde(default)]
+    versions: HashMap<String, IndexVersion>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexMetadata {
+    summary: Option<BTreeMap<String, String>>,
+    source_code: Option<String>,
+    web_site: Option<String>,
+    license: Option<String>,
+    #[serde(default)]
+    donate: Vec<String>,
+    liberapay: Option<String>,
+    open_collective: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IndexVersion {
+    manifest: IndexManifest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexManifest {
+    version_name: Option<String>,
+    version_code: u64,
+}
+
+fn parse_index(body: &str) -> Result<FdroidIndex> {
+    serde_json::from_str(body).context("Failed to parse the F-Droid index")
+}
+
+/// Pick the English text of a localized index field, or any translation if
+/// there is none.
+fn localized(field: &Option<BTreeMap<String, String>>) -> Option<String> {
+    let field = field.as_ref()?;
+    field
+        .get("en-US")
+        .or_else(|| field.values().next())
+        .cloned()
+}
+
+/// Combine an installed app with its index entry. Apps missing from the
+/// index (e.g. installed from a third-party repository) are reported under
+/// their version code without metadata.
+fn build_package(app: &DeviceApp, entry: Option<&IndexPackage>) -> InstalledPackage {
+    let version_code = app.version_code.map(|c| c.to_string());
+    let Some(entry) = entry else {
+        return InstalledPackage {
+            name: app.package.clone(),
+            version: version_code.unwrap_or_else(|| "unknown".to_string()),
+            description: None,
+            url: None,
+            source: PackageSource::Fdroid,
+            licenses: Vec::new(),
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        };
+    };
+
+    let version_name = entry
+        .versions
+        .values()
+        .find(|v| Some(v.manifest.version_code) == app.version_code)
+        .and_then(|v| v.manifest.version_name.clone());
+
+    let meta = &entry.metadata;
+    let url = meta
+        .source_code
+        .clone()
+        .or_else(|| meta.web_site.clone())
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| format!("https://f-droid.org/packages/{}/", app.package));
+
+    let mut funding: Vec<FundingChannel> =
+        meta.liberapay
+            .iter()
+            .map(|user| FundingChannel::from_url(&format!("https://liberapay.com/{user}")))
+            .chain(meta.open_collective.iter().map(|slug| {
+                FundingChannel::from_url(&format!("https://opencollective.com/{slug}"))
+            }))
+            .collect();
+    funding.extend(
+        meta.donate
+            .iter()
+            .filter(|u| u.starts_with("http"))
+            .map(|u| FundingChannel::from_url(u)),
+    );
+
+    InstalledPackage {
+        name: app.package.clone(),
+        version: version_name
+            .or(version_code)
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: localized(&meta.summary),
+        url: Some(url),
+        source: PackageSource::Fdroid,
+        licenses: meta.license.iter().cloned().collect(),
+        funding,
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_devices() {
+        let output = "\
+List of devices attached
+R58M12ABCDE\tdevice
+emulator-5554\toffline
+0123456789\tunauthorized
+
+";
+        assert_eq!(parse_adb_devices(output), vec!["R58M12ABCDE"]);
+        assert!(parse_adb_devices("List of devices attached\n\n").is_empty());
+    }
+
+    #[test]
+    fn parse_packages() {
+        let output = "\
+package:org.fdroid.fdroid versionCode:1019050 installer=org.fdroid.fdroid
+package:de.danoeh.antennapod versionCode:3070295 installer=com.looker.droidify
+package:com.example.app versionCode:42 installer=null
+";
+        let apps = parse_package_list(output);
+        assert_eq!(apps.len(), 3);
+        assert_eq!(
+            apps[1],
+            DeviceApp {
+                package: "de.danoeh.antennapod".to_string(),
+                version_code: Some(3070295),
+                installer: Some("com.looker.droidify".to_string()),
+            }
+        );
+        assert_eq!(apps[2].installer, None);
+    }
+
+    #[test]
+    fn index_metadata_is_applied() {
+        let body = r#"{
+            "repo": {"name": {"en-US": "F-Droid"}},
+            "packages": {
+                "de.danoeh.antennapod": {
+                    "metadata": {
+                        "name": {"en-US": "AntennaPod"},
+                        "summary": {"en-US": "Easy-to-use podcast manager"},
+                        "sourceCode": "https://github.com/AntennaPod/AntennaPod",
+                        "webSite": "https://antennapod.org",
+                        "license": "GPL-3.0-only",
+                        "donate": ["https://antennapod.org/contribute/donate"],
+                        "openCollective": "antennapod"
+                    },
+                    "versions": {
+                        "abc": {"manifest": {"versionName": "3.7.0", "versionCode": 3070295}},
+                        "def": {"manifest": {"versionName": "3.6.2", "versionCode": 3060295}}
+                    }
+                }
+            }
+        }"#;
+        let index = parse_index(body).unwrap();
+        let app = DeviceApp {
+            package: "de.danoeh.antennapod".to_string(),
+            version_code: Some(3070295),
+            installer: Some("org.fdroid.fdroid".to_string()),
+        };
+        let pkg = build_package(&app, index.packages.get(&app.package));
+        assert_eq!(pkg.version, "3.7.0");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/AntennaPod/AntennaPod")
+        );
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("Easy-to-use podcast manager")
+        );
+        assert_eq!(pkg.licenses, vec!["GPL-3.0-only"]);
+        let platforms: Vec<&str> = pkg.funding.iter().map(|f| f.platform.as_str()).collect();
+        assert_eq!(platforms, vec!["Open Collective", "Custom"]);
+        assert_eq!(pkg.source, PackageSource::Fdroid);
+    }
+
+    #[test]
+    fn app_missing_from_index() {
+        let app = DeviceApp {
+            package: "com.example.izzy".to_string(),
+            version_code: Some(7),
+            installer: Some("org.fdroid.fdroid".to_string()),
+        };
+        let pkg = build_package(&app, None);
+        assert_eq!(pkg.version, "7");
+        assert!(pkg.url.is_none());
+    }
+}
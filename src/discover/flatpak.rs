@@ -1,18 +1,27 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use crate::project::FundingChannel;
+
+/// The system-wide Flatpak installation.
+const SYSTEM_INSTALLATION: &str = "/var/lib/flatpak";
 
 /// Discovers applications installed via Flatpak.
 ///
 /// Runs `flatpak list --app` to enumerate user-facing applications from both
 /// system and user installations. Runtimes are excluded to focus on apps the
 /// user has explicitly installed.
+///
+/// Each app ships an AppStream metainfo file in its deployment, from which
+/// the project URL (source repository, else homepage), the project license,
+/// and `<url type="donation">` links are read.
 pub struct FlatpakDiscoverer;
 
 impl Discoverer for FlatpakDiscoverer {
@@ -44,10 +53,135 @@ impl Discoverer for FlatpakDiscoverer {
         let stdout =
             String::from_utf8(output.stdout).context("flatpak list output is not valid UTF-8")?;
 
-        parse_flatpak_output(&stdout)
+        let mut packages = parse_flatpak_output(&stdout)?;
+
+        for pkg in &mut packages {
+            let Some(path) = find_metainfo(&pkg.name) else {
+                continue;
+            };
+            match fs::read_to_string(&path)
+                .context("Failed to read metainfo")
+                .and_then(|content| parse_metainfo(&content))
+            {
+                Ok(metainfo) => metainfo.apply(pkg),
+                Err(e) => eprintln!("  Warning: failed to parse {}: {e}", path.display()),
+            }
+        }
+
+        Ok(packages)
+    }
+}
+
+/// Flatpak installation directories: the user's, then the system one.
+fn installations() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".local/share/flatpak"));
+    }
+    dirs.push(PathBuf::from(SYSTEM_INSTALLATION));
+    dirs
+}
+
+/// Locate the AppStream metainfo file of an installed app.
+fn find_metainfo(app_id: &str) -> Option<PathBuf> {
+    installations()
+        .iter()
+        .map(|root| root.join("app").join(app_id).join("current/active/files"))
+        .find_map(|files| find_metainfo_in(&files, app_id))
+}
+
+/// Look for `<app_id>.metainfo.xml` (or the legacy `.appdata.xml` name and
+/// `appdata/` directory) under a deployment's `files` directory, falling back
+/// to any metainfo file, since some apps use a different component ID.
+fn find_metainfo_in(files: &Path, app_id: &str) -> Option<PathBuf> {
+    let dirs = ["share/metainfo", "share/appdata"].map(|d| files.join(d));
+
+    let exact = dirs.iter().find_map(|dir| {
+        ["metainfo.xml", "appdata.xml"]
+            .iter()
+            .map(|ext| dir.join(format!("{app_id}.{ext}")))
+            .find(|p| p.is_file())
+    });
+
+    exact.or_else(|| {
+        dirs.iter().find_map(|dir| {
+            let mut xml: Vec<PathBuf> = fs::read_dir(dir)
+                .ok()?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|e| e == "xml"))
+                .collect();
+            xml.sort();
+            xml.into_iter().next()
+        })
+    })
+}
+
+/// Upstream metadata read from an AppStream metainfo file.
+#[derive(Debug, Default, PartialEq)]
+struct Metainfo {
+    homepage: Option<String>,
+    vcs_browser: Option<String>,
+    license: Option<String>,
+    donation: Vec<String>,
+}
+
+impl Metainfo {
+    /// Fill in the package's URL, license, and funding channels. The source
+    /// repository is preferred over the homepage, since that is what
+    /// enrichment backends key on.
+    fn apply(self, pkg: &mut InstalledPackage) {
+        if pkg.url.is_none() {
+            pkg.url = self.vcs_browser.or(self.homepage);
+        }
+        if pkg.licenses.is_empty() {
+            pkg.licenses.extend(self.license);
+        }
+        pkg.funding.extend(
+            self.donation
+                .iter()
+                .map(|url| FundingChannel::from_url(url)),
+        );
     }
 }
 
+/// Parse an AppStream metainfo (`<component>`) document.
+///
+/// ```xml
+/// <component type="desktop-application">
+///   <id>org.gimp.GIMP</id>
+///   <project_license>GPL-3.0-or-later</project_license>
+///   <url type="homepage">https://www.gimp.org/</url>
+///   <url type="vcs-browser">https://gitlab.gnome.org/GNOME/gimp</url>
+///   <url type="donation">https://www.gimp.org/donating/</url>
+/// </component>
+/// ```
+fn parse_metainfo(content: &str) -> Result<Metainfo> {
+    let doc = roxmltree::Document::parse(content).context("Invalid metainfo XML")?;
+    let component = doc.root_element();
+    if !component.has_tag_name("component") {
+        anyhow::bail!("Expected a <component> root element");
+    }
+
+    let mut metainfo = Metainfo::default();
+    for node in component.children().filter(|n| n.is_element()) {
+        let text = node
+            .text()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from);
+        match (node.tag_name().name(), node.attribute("type")) {
+            ("project_license", _) => metainfo.license = text,
+            ("url", Some("homepage")) => metainfo.homepage = text,
+            ("url", Some("vcs-browser")) => metainfo.vcs_browser = text,
+            ("url", Some("donation")) => metainfo.donation.extend(text),
+            _ => {}
+        }
+    }
+
+    Ok(metainfo)
+}
+
 /// Parse the tab-separated output of `flatpak list --columns=application,version,description,origin`.
 fn parse_flatpak_output(output: &str) -> Result<Vec<InstalledPackage>> {
     let lines: Vec<&str> = output.lines().filter(|l| !l.is_empty()).collect();
@@ -111,6 +245,11 @@ fn parse_flatpak_line(line: &str) -> Result<InstalledPackage> {
         url: None,
         source: PackageSource::Flatpak,
         licenses: Vec::new(),
+        funding: Vec::new(),
+        // Only apps are listed; runtimes and extensions, which Flatpak pulls
+        // in as dependencies, are not.
+        install_reason: InstallReason::Explicit,
+        maintainer: None,
     })
 }
 
@@ -133,6 +272,7 @@ mod tests {
         assert_eq!(pkg.source, PackageSource::Flatpak);
         assert!(pkg.url.is_none());
         assert!(pkg.licenses.is_empty());
+        assert_eq!(pkg.install_reason, InstallReason::Explicit);
     }
 
     #[test]
@@ -188,6 +328,67 @@ com.spotify.Client\t1.2.26\tOnline music streaming service\tflathub
         assert_eq!(packages.len(), 1);
     }
 
+    #[test]
+    fn parse_metainfo_urls_and_license() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<component type="desktop-application">
+  <id>org.gimp.GIMP</id>
+  <metadata_license>CC0-1.0</metadata_license>
+  <project_license>GPL-3.0-or-later AND LGPL-3.0-or-later</project_license>
+  <name>GNU Image Manipulation Program</name>
+  <url type="homepage">https://www.gimp.org/</url>
+  <url type="bugtracker">https://gitlab.gnome.org/GNOME/gimp/issues</url>
+  <url type="vcs-browser">https://gitlab.gnome.org/GNOME/gimp</url>
+  <url type="donation">https://www.gimp.org/donating/</url>
+  <url type="donation">https://liberapay.com/GIMP</url>
+</component>
+"#;
+        let metainfo = parse_metainfo(content).unwrap();
+
+        let mut pkg = parse_flatpak_line("org.gimp.GIMP\t2.10.38\tGIMP\tflathub").unwrap();
+        metainfo.apply(&mut pkg);
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://gitlab.gnome.org/GNOME/gimp")
+        );
+        assert_eq!(pkg.licenses, vec!["GPL-3.0-or-later AND LGPL-3.0-or-later"]);
+        let platforms: Vec<&str> = pkg.funding.iter().map(|f| f.platform.as_str()).collect();
+        assert_eq!(platforms, vec!["Custom", "Liberapay"]);
+    }
+
+    #[test]
+    fn parse_metainfo_homepage_fallback() {
+        let content = r#"<component><id>org.example.App</id><url type="homepage">https://example.org</url></component>"#;
+        let metainfo = parse_metainfo(content).unwrap();
+        assert_eq!(metainfo.homepage.as_deref(), Some("https://example.org"));
+        assert!(metainfo.license.is_none());
+        assert!(parse_metainfo("<application/>").is_err());
+        assert!(parse_metainfo("not xml").is_err());
+    }
+
+    #[test]
+    fn find_metainfo_prefers_app_id() {
+        let tmp = tempfile::tempdir().unwrap();
+        let files = tmp.path();
+        fs::create_dir_all(files.join("share/metainfo")).unwrap();
+        fs::write(files.join("share/metainfo/aaa.metainfo.xml"), "").unwrap();
+        assert_eq!(
+            find_metainfo_in(files, "org.example.App"),
+            Some(files.join("share/metainfo/aaa.metainfo.xml"))
+        );
+
+        fs::write(
+            files.join("share/metainfo/org.example.App.metainfo.xml"),
+            "",
+        )
+        .unwrap();
+        assert_eq!(
+            find_metainfo_in(files, "org.example.App"),
+            Some(files.join("share/metainfo/org.example.App.metainfo.xml"))
+        );
+        assert_eq!(find_metainfo_in(&files.join("missing"), "x"), None);
+    }
+
     #[test]
     fn parse_empty_application_id_skipped() {
         let output = "\t1.0\tSome App\tflathub\n";
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Prints each font's primary family name and revision, tab-separated.
+const FC_LIST_FORMAT: &str = "%{family[0]}\t%{fontversion}\n";
+
+/// Family name prefixes of well-known open-source font projects, with their
+/// upstream repository and license. Matched case-insensitively, longest
+/// entries first where one is a prefix of another.
+const KNOWN_FONTS: &[(&str, &str, &str)] = &[
+    (
+        "Cantarell",
+        "https://gitlab.gnome.org/GNOME/cantarell-fonts",
+        "OFL-1.1",
+    ),
+    (
+        "Cascadia",
+        "https://github.com/microsoft/cascadia-code",
+        "OFL-1.1",
+    ),
+    (
+        "DejaVu",
+        "https://github.com/dejavu-fonts/dejavu-fonts",
+        "Bitstream-Vera",
+    ),
+    ("Fira Code", "https://github.com/tonsky/FiraCode", "OFL-1.1"),
+    ("Fira", "https://github.com/mozilla/Fira", "OFL-1.1"),
+    (
+        "Font Awesome",
+        "https://github.com/FortAwesome/Font-Awesome",
+        "OFL-1.1",
+    ),
+    ("Hack", "https://github.com/source-foundry/Hack", "MIT"),
+    ("IBM Plex", "https://github.com/IBM/plex", "OFL-1.1"),
+    ("Inter", "https://github.com/rsms/inter", "OFL-1.1"),
+    ("Iosevka", "https://github.com/be5invis/Iosevka", "OFL-1.1"),
+    (
+        "JetBrains Mono",
+        "https://github.com/JetBrains/JetBrainsMono",
+        "OFL-1.1",
+    ),
+    (
+        "Liberation",
+        "https://github.com/liberationfonts/liberation-fonts",
+        "OFL-1.1",
+    ),
+    (
+        "Monaspace",
+        "https://github.com/githubnext/monaspace",
+        "OFL-1.1",
+    ),
+    ("Noto", "https://github.com/notofonts", "OFL-1.1"),
+    (
+        "Recursive",
+        "https://github.com/arrowtype/recursive",
+        "OFL-1.1",
+    ),
+    (
+        "Roboto",
+        "https://github.com/googlefonts/roboto-3-classic",
+        "OFL-1.1",
+    ),
+    (
+        "Source Code Pro",
+        "https://github.com/adobe-fonts/source-code-pro",
+        "OFL-1.1",
+    ),
+    (
+        "Source Han",
+        "https://github.com/adobe-fonts/source-han-sans",
+        "OFL-1.1",
+    ),
+    (
+        "Source Sans",
+        "https://github.com/adobe-fonts/source-sans",
+        "OFL-1.1",
+    ),
+    (
+        "Source Serif",
+        "https://github.com/adobe-fonts/source-serif",
+        "OFL-1.1",
+    ),
+    (
+        "Terminus",
+        "https://terminus-font.sourceforge.net",
+        "OFL-1.1",
+    ),
+    (
+        "Victor Mono",
+        "https://github.com/rubjo/victor-mono",
+        "OFL-1.1",
+    ),
+];
+
+/// Patched families are named `<Font> Nerd Font`, `<Font> Nerd Font Mono`,
+/// and so on, and are credited to the Nerd Fonts project rather than to the
+/// original font.
+const NERD_FONTS: (&str, &str) = ("https://github.com/ryanoasis/nerd-fonts", "MIT");
+
+/// Discovers installed font families via fontconfig.
+///
+/// Runs `fc-list` and reports each font family once, versioned by the
+/// font's revision. Families of well-known open-source font projects
+/// ([`KNOWN_FONTS`], and any Nerd Fonts patched family) get their upstream
+/// repository and license; other families are reported without a URL.
+pub struct FontsDiscoverer;
+
+impl Discoverer for FontsDiscoverer {
+    fn name(&self) -> &str {
+        "fonts"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("fc-list")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("fc-list")
+            .args(["--format", FC_LIST_FORMAT])
+            .output()
+            .context("Failed to run fc-list")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "fc-list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(parse_fc_list(&stdout))
+    }
+}
+
+/// Parse `fc-list` output in [`FC_LIST_FORMAT`].
+///
+/// ```text
+/// Inter<TAB>262144
+/// JetBrainsMono Nerd Font<TAB>150994
+/// ```
+///
+/// Every style of a family (Regular, Bold, ...) is a separate line; they
+/// collapse into one package per family and version.
+fn parse_fc_list(output: &str) -> Vec<InstalledPackage> {
+    let mut packages: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+    for line in output.lines() {
+        let (family, version) = line.split_once('\t').unwrap_or((line, ""));
+        let family = family.trim();
+        if family.is_empty() {
+            continue;
+        }
+        let version = format_font_version(version.trim());
+
+        packages
+            .entry((family.to_string(), version.clone()))
+            .or_insert_with(|| {
+                let known = lookup_font(family);
+                InstalledPackage {
+                    name: family.to_string(),
+                    version,
+                    description: None,
+                    url: known.map(|(url, _)| url.to_string()),
+                    source: PackageSource::Font,
+                    licenses: known
+                        .map(|(_, license)| license.to_string())
+                        .into_iter()
+                        .collect(),
+                    funding: Vec::new(),
+                    install_reason: InstallReason::Unknown,
+                    maintainer: None,
+                }
+            });
+    }
+
+    packages.into_values().collect()
+}
+
+/// fontconfig reports the font revision as a 16.16 fixed-point integer
+/// (`131072` is 2.0); render it with up to three decimals like font tools
+/// do.
+fn format_font_version(raw: &str) -> String {
+    match raw.parse::<u32>() {
+        Ok(0) | Err(_) => "unknown".to_string(),
+        Ok(fixed) => {
+            let formatted = format!("{:.3}", f64::from(fixed) / 65536.0);
+            let trimmed = formatted.trim_end_matches('0');
+            if trimmed.ends_with('.') {
+                format!("{trimmed}0")
+            } else {
+                trimmed.to_string()
+            }
+        }
+    }
+}
+
+/// Find the upstream `(url, license)` of a font family.
+fn lookup_font(family: &str) -> Option<(&'static str, &'static str)> {
+    if family.contains("Nerd Font") {
+        return Some(NERD_FONTS);
+    }
+    let lower = family.to_lowercase();
+    KNOWN_FONTS
+        .iter()
+        .find(|(prefix, _, _)| {
+            let prefix = prefix.to_lowercase();
+            lower == prefix || lower.starts_with(&format!("{prefix} "))
+        })
+        .map(|(_, url, license)| (*url, *license))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_families() {
+        let output = "\
+Inter\t262144
+Inter\t262144
+JetBrainsMono Nerd Font Mono\t150994
+Fira Code\t393216
+Fira Sans Condensed\t288358
+Interstate\t65536
+Cousine
+";
+        let packages = parse_fc_list(output);
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Cousine",
+                "Fira Code",
+                "Fira Sans Condensed",
+                "Inter",
+                "Interstate",
+                "JetBrainsMono Nerd Font Mono"
+            ]
+        );
+
+        assert_eq!(packages[0].version, "unknown");
+        assert!(packages[0].url.is_none());
+
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/tonsky/FiraCode")
+        );
+        assert_eq!(
+            packages[2].url.as_deref(),
+            Some("https://github.com/mozilla/Fira")
+        );
+
+        assert_eq!(packages[3].version, "4.0");
+        assert_eq!(packages[3].licenses, vec!["OFL-1.1"]);
+        assert_eq!(packages[3].source, PackageSource::Font);
+
+        assert!(packages[4].url.is_none());
+
+        assert_eq!(packages[5].version, "2.304");
+        assert_eq!(packages[5].url.as_deref(), Some(NERD_FONTS.0));
+    }
+
+    #[test]
+    fn font_versions() {
+        assert_eq!(format_font_version("65536"), "1.0");
+        assert_eq!(format_font_version("98304"), "1.5");
+        assert_eq!(format_font_version("0"), "unknown");
+        assert_eq!(format_font_version(""), "unknown");
+    }
+}
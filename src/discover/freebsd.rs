@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers ports and packages installed via FreeBSD's `pkg`.
+///
+/// Uses `pkg query` to read name, version, WWW URL, and comment for every
+/// installed package. Licenses are a multi-line field in `pkg query`
+/// (one output line per license), so they are fetched with a second query
+/// and joined by package name.
+pub struct FreeBsdPkgDiscoverer;
+
+const PKG_DB_PATH: &str = "/var/db/pkg/local.sqlite";
+
+impl Discoverer for FreeBsdPkgDiscoverer {
+    fn name(&self) -> &str {
+        "pkg"
+    }
+
+    fn is_available(&self) -> bool {
+        Path::new(PKG_DB_PATH).is_file()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let packages = run_pkg_query("%n\t%v\t%w\t%c")?;
+        let licenses = run_pkg_query("%n\t%Ln")?;
+        Ok(parse_pkg_query(&packages, &licenses))
+    }
+}
+
+/// Run `pkg query <format>` and return its stdout.
+fn run_pkg_query(format: &str) -> Result<String> {
+    let output = Command::new("pkg")
+        .args(["query", format])
+        .output()
+        .context("Failed to run pkg query")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "pkg query failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("pkg query output is not valid UTF-8")
+}
+
+/// Combine the package and license queries into `InstalledPackage`s.
+///
+/// `packages` has one `name\tversion\twww\tcomment` line per package;
+/// `licenses` has one `name\tlicense` line per package license.
+fn parse_pkg_query(packages: &str, licenses: &str) -> Vec<InstalledPackage> {
+    let mut licenses_by_name: HashMap<&str, Vec<String>> = HashMap::new();
+    for line in licenses.lines() {
+        if let Some((name, license)) = line.split_once('\t')
+            && !license.trim().is_empty()
+        {
+            licenses_by_name
+                .entry(name)
+                .or_default()
+                .push(license.trim().to_string());
+        }
+    }
+
+    let lines: Vec<&str> = packages.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    let pb = ProgressBar::new(lines.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {bar:30} {pos}/{len} packages")
+            .unwrap(),
+    );
+
+    let mut result = Vec::new();
+
+    for line in lines {
+        let mut fields = line.splitn(4, '\t');
+        match (fields.next(), fields.next()) {
+            (Some(name), Some(version)) if !name.is_empty() => {
+                let non_empty = |s: Option<&str>| {
+                    s.map(str::trim)
+                        .filter(|s| !s.is_empty() && *s != "UNKNOWN")
+                        .map(String::from)
+                };
+                let url = non_empty(fields.next());
+                let description = non_empty(fields.next());
+
+                result.push(InstalledPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    description,
+                    url,
+                    source: PackageSource::FreeBsdPkg,
+                    licenses: licenses_by_name.remove(name).unwrap_or_default(),
+                    funding: Vec::new(),
+                    install_reason: InstallReason::Unknown,
+                    maintainer: None,
+                });
+            }
+            _ => {
+                pb.suspend(|| eprintln!("  Warning: failed to parse pkg query line: {line}"));
+            }
+        }
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_packages_with_licenses() {
+        let packages = "\
+curl\t8.8.0\thttps://curl.se/\tCommand line tool and library for transferring data with URLs
+git\t2.45.2\thttps://git-scm.com/\tDistributed source code management tool
+pkg\t1.21.3\tUNKNOWN\tPackage manager
+";
+        let licenses = "\
+curl\tMIT
+git\tGPLv2
+git\tLGPL21
+";
+        let result = parse_pkg_query(packages, licenses);
+        assert_eq!(result.len(), 3);
+
+        assert_eq!(result[0].name, "curl");
+        assert_eq!(result[0].version, "8.8.0");
+        assert_eq!(result[0].url.as_deref(), Some("https://curl.se/"));
+        assert_eq!(
+            result[0].description.as_deref(),
+            Some("Command line tool and library for transferring data with URLs")
+        );
+        assert_eq!(result[0].licenses, vec!["MIT"]);
+        assert_eq!(result[0].source, PackageSource::FreeBsdPkg);
+
+        assert_eq!(result[1].licenses, vec!["GPLv2", "LGPL21"]);
+
+        assert!(result[2].url.is_none());
+        assert!(result[2].licenses.is_empty());
+    }
+
+    #[test]
+    fn parse_skips_malformed_lines() {
+        let result = parse_pkg_query("just-a-name\n\n", "");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!(parse_pkg_query("", "").is_empty());
+    }
+}
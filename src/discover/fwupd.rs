@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// The Linux Vendor Firmware Service, the project every fwupd-updated
+/// device depends on.
+const LVFS_URL: &str = "https://fwupd.org";
+
+/// Discovers devices whose firmware is updated through fwupd and the LVFS.
+///
+/// Runs `fwupdmgr get-devices --json` and reports every updatable device
+/// with its current firmware version and vendor. The firmware itself is
+/// rarely open source, so the project URL is the homepage of the available
+/// release when the vendor provides one, and otherwise the LVFS, which hosts
+/// firmware for free and is the open-source infrastructure worth supporting.
+pub struct FwupdDiscoverer;
+
+impl Discoverer for FwupdDiscoverer {
+    fn name(&self) -> &str {
+        "fwupd"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("fwupdmgr")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("fwupdmgr")
+            .args(["get-devices", "--json"])
+            .output()
+            .context("Failed to run fwupdmgr get-devices")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "fwupdmgr get-devices failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("fwupdmgr output is not valid UTF-8")?;
+        parse_devices(&stdout)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeviceList {
+    #[serde(default)]
+    devices: Vec<Device>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Device {
+    name: String,
+    vendor: Option<String>,
+    version: Option<String>,
+    summary: Option<String>,
+    #[serde(default)]
+    flags: Vec<String>,
+    #[serde(default)]
+    releases: Vec<Release>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Release {
+    homepage: Option<String>,
+    license: Option<String>,
+}
+
+/// Parse `fwupdmgr get-devices --json`.
+///
+/// ```json
+/// {"Devices": [{"Name": "System Firmware", "Vendor": "LENOVO", "Version": "0.1.45",
+///   "Flags": ["internal", "updatable"], "Releases": [{"License": "LicenseRef-proprietary"}]}]}
+/// ```
+///
+/// Devices without the `updatable` (or `updatable-hidden`) flag do not
+/// receive firmware through fwupd and are skipped.
+fn parse_devices(output: &str) -> Result<Vec<InstalledPackage>> {
+    let list: DeviceList = serde_json::from_str(output).context("Failed to parse fwupdmgr JSON")?;
+
+    Ok(list
+        .devices
+        .into_iter()
+        .filter(|d| {
+            d.flags
+                .iter()
+                .any(|f| f == "updatable" || f == "updatable-hidden")
+        })
+        .map(|device| {
+            let release = device.releases.into_iter().next();
+            let (homepage, license) = match release {
+                Some(r) => (r.homepage, r.license),
+                None => (None, None),
+            };
+            let name = match device.vendor.filter(|v| !v.is_empty()) {
+                Some(vendor) => format!("{vendor} {}", device.name),
+                None => device.name,
+            };
+
+            InstalledPackage {
+                name,
+                version: device
+                    .version
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                description: device.summary.filter(|s| !s.is_empty()),
+                url: Some(
+                    homepage
+                        .filter(|h| !h.is_empty())
+                        .unwrap_or_else(|| LVFS_URL.to_string()),
+                ),
+                source: PackageSource::Fwupd,
+                licenses: license.into_iter().collect(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_device_list() {
+        let output = r#"{
+            "Devices": [
+                {
+                    "Name": "System Firmware",
+                    "DeviceId": "a45df35ac0e948ee180fe216a5f703f32dda163f",
+                    "Vendor": "LENOVO",
+                    "Version": "0.1.45",
+                    "Summary": "UEFI ESRT device",
+                    "Plugin": "uefi_capsule",
+                    "Flags": ["internal", "updatable", "require-ac"],
+                    "Releases": [
+                        {"Version": "0.1.46", "License": "LicenseRef-proprietary", "Homepage": "https://www.lenovo.com/"}
+                    ]
+                },
+                {
+                    "Name": "Embedded Controller",
+                    "Vendor": "Star Labs",
+                    "Version": "1.0",
+                    "Flags": ["updatable-hidden"]
+                },
+                {
+                    "Name": "TPM",
+                    "Version": "7.2.0.1",
+                    "Flags": ["internal"]
+                }
+            ]
+        }"#;
+        let packages = parse_devices(output).unwrap();
+        assert_eq!(packages.len(), 2);
+
+        assert_eq!(packages[0].name, "LENOVO System Firmware");
+        assert_eq!(packages[0].version, "0.1.45");
+        assert_eq!(packages[0].url.as_deref(), Some("https://www.lenovo.com/"));
+        assert_eq!(packages[0].licenses, vec!["LicenseRef-proprietary"]);
+        assert_eq!(packages[0].source, PackageSource::Fwupd);
+
+        assert_eq!(packages[1].url.as_deref(), Some(LVFS_URL));
+        assert!(packages[1].licenses.is_empty());
+    }
+
+    #[test]
+    fn parse_empty_and_invalid() {
+        assert!(parse_devices("{}").unwrap().is_empty());
+        assert!(parse_devices("nope").is_err());
+    }
+}
@@ -0,0 +1,496 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OpenFlags};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Open-source games distributed through game stores, with their upstream
+/// repository and license. Names are matched after [`normalize_title`], so
+/// alternative store titles get their own entry.
+const KNOWN_GAMES: &[(&str, &str, &str)] = &[
+    (
+        "0 A.D.",
+        "https://gitea.wildfiregames.com/0ad/0ad",
+        "GPL-2.0-or-later",
+    ),
+    (
+        "Battle for Wesnoth",
+        "https://github.com/wesnoth/wesnoth",
+        "GPL-2.0-or-later",
+    ),
+    (
+        "Cataclysm: Dark Days Ahead",
+        "https://github.com/CleverRaven/Cataclysm-DDA",
+        "CC-BY-SA-3.0",
+    ),
+    ("DDraceNetwork", "https://github.com/ddnet/ddnet", "Zlib"),
+    (
+        "Endless Sky",
+        "https://github.com/endless-sky/endless-sky",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Hedgewars",
+        "https://github.com/hedgewars/hw",
+        "GPL-2.0-only",
+    ),
+    (
+        "Luanti",
+        "https://github.com/luanti-org/luanti",
+        "LGPL-2.1-or-later",
+    ),
+    (
+        "Mindustry",
+        "https://github.com/Anuken/Mindustry",
+        "GPL-3.0-only",
+    ),
+    (
+        "Minetest",
+        "https://github.com/luanti-org/luanti",
+        "LGPL-2.1-or-later",
+    ),
+    (
+        "OpenTTD",
+        "https://github.com/OpenTTD/OpenTTD",
+        "GPL-2.0-only",
+    ),
+    (
+        "Pixel Wheels",
+        "https://github.com/agateau/pixelwheels",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Shattered Pixel Dungeon",
+        "https://github.com/00-Evan/shattered-pixel-dungeon",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "SuperTuxKart",
+        "https://github.com/supertuxkart/stk-code",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Tales of Maj'Eyal",
+        "https://git.net-core.org/tome/t-engine4",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Teeworlds",
+        "https://github.com/teeworlds/teeworlds",
+        "Zlib",
+    ),
+    (
+        "The Battle for Wesnoth",
+        "https://github.com/wesnoth/wesnoth",
+        "GPL-2.0-or-later",
+    ),
+    (
+        "Unvanquished",
+        "https://github.com/Unvanquished/Unvanquished",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Veloren",
+        "https://gitlab.com/veloren/veloren",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Xonotic",
+        "https://gitlab.com/xonotic/xonotic",
+        "GPL-3.0-or-later",
+    ),
+    (
+        "Zero-K",
+        "https://github.com/ZeroK-RTS/Zero-K",
+        "GPL-2.0-or-later",
+    ),
+];
+
+/// Steam installations relative to `$HOME`: the `~/.steam` symlink, the
+/// native install, and the Flatpak.
+const STEAM_ROOTS: &[&str] = &[
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+];
+
+/// Lutris game databases relative to `$HOME`, native and Flatpak.
+const LUTRIS_DBS: &[&str] = &[
+    ".local/share/lutris/pga.db",
+    ".var/app/net.lutris.Lutris/data/lutris/pga.db",
+];
+
+/// The itch app's butler database relative to `$HOME`.
+const ITCH_DB: &str = ".config/itch/db/butler.db";
+
+/// A game installed in one of the supported libraries.
+#[derive(Debug, PartialEq)]
+struct LibraryGame {
+    title: String,
+    version: Option<String>,
+}
+
+/// Discovers open-source games installed through Steam, Lutris, or the itch
+/// app.
+///
+/// Store games are mostly proprietary, so rather than reporting every
+/// installed title, the local libraries are cross-referenced with
+/// [`KNOWN_GAMES`] and only the matching open-source games are reported,
+/// with their upstream repository and license. Steam games are versioned by
+/// their Steam build ID; Lutris and itch do not record one.
+pub struct GamesDiscoverer;
+
+impl Discoverer for GamesDiscoverer {
+    fn name(&self) -> &str {
+        "games"
+    }
+
+    fn is_available(&self) -> bool {
+        !steam_library_dirs().is_empty()
+            || !existing_dbs(LUTRIS_DBS).is_empty()
+            || !existing_dbs(&[ITCH_DB]).is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let mut games = Vec::new();
+
+        for dir in steam_library_dirs() {
+            games.extend(read_steam_library(&dir));
+        }
+        for db in existing_dbs(LUTRIS_DBS) {
+            match read_lutris_db(&db) {
+                Ok(found) => games.extend(found),
+                Err(e) => eprintln!("  Warning: failed to read {}: {e}", db.display()),
+            }
+        }
+        for db in existing_dbs(&[ITCH_DB]) {
+            match read_itch_db(&db) {
+                Ok(found) => games.extend(found),
+                Err(e) => eprintln!("  Warning: failed to read {}: {e}", db.display()),
+            }
+        }
+
+        Ok(match_known_games(games))
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+fn existing_dbs(relative: &[&str]) -> Vec<PathBuf> {
+    let Some(home) = home_dir() else {
+        return Vec::new();
+    };
+    relative
+        .iter()
+        .map(|p| home.join(p))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+/// Every `steamapps` directory: the one in each Steam installation plus the
+/// extra libraries listed in its `libraryfolders.vdf`, deduplicated since
+/// `~/.steam/steam` usually links to the native install.
+fn steam_library_dirs() -> Vec<PathBuf> {
+    let Some(home) = home_dir() else {
+        return Vec::new();
+    };
+    let mut dirs = BTreeSet::new();
+    for root in STEAM_ROOTS {
+        let steamapps = home.join(root).join("steamapps");
+        if !steamapps.is_dir() {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(steamapps.join("libraryfolders.vdf")) {
+            for (key, value) in vdf_pairs(&content) {
+                if key == "path" {
+                    dirs.insert(PathBuf::from(value).join("steamapps"));
+                }
+            }
+        }
+        dirs.insert(steamapps);
+    }
+    dirs.into_iter()
+        .filter(|d| d.is_dir())
+        .filter_map(|d| d.canonicalize().ok())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Extract the `"key" "value"` pairs of a Valve KeyValues (VDF/ACF) file,
+/// ignoring nesting. Escaped characters are not unescaped beyond `\\`.
+fn vdf_pairs(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut quoted = line.trim().split('"').skip(1).step_by(2);
+            let key = quoted.next()?;
+            let value = quoted.next()?;
+            Some((key.to_string(), value.replace("\\\\", "\\")))
+        })
+        .collect()
+}
+
+/// Read the `appmanifest_<appid>.acf` files of a `steamapps` directory.
+fn read_steam_library(dir: &Path) -> Vec<LibraryGame> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("appmanifest_") && n.ends_with(".acf"))
+        })
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .filter_map(|content| parse_app_manifest(&content))
+        .collect()
+}
+
+/// Parse a Steam app manifest; top-level keys precede the nested sections,
+/// so the first `name` and `buildid` are the app's.
+///
+/// ```text
+/// "AppState"
+/// {
+///     "appid"     "1127400"
+///     "name"      "Mindustry"
+///     "buildid"   "14389592"
+/// }
+/// ```
+fn parse_app_manifest(content: &str) -> Option<LibraryGame> {
+    let pairs = vdf_pairs(content);
+    let get = |key: &str| {
+        pairs
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.clone())
+    };
+    Some(LibraryGame {
+        title: get("name").filter(|n| !n.is_empty())?,
+        version: get("buildid").filter(|b| !b.is_empty() && b != "0"),
+    })
+}
+
+fn open_read_only(path: &Path) -> Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+/// Read the installed games of a Lutris `pga.db`.
+fn read_lutris_db(path: &Path) -> Result<Vec<LibraryGame>> {
+    query_titles(
+        &open_read_only(path)?,
+        "SELECT name FROM games WHERE installed = 1",
+    )
+}
+
+/// Read the installed games ("caves") of the itch app's `butler.db`.
+fn read_itch_db(path: &Path) -> Result<Vec<LibraryGame>> {
+    query_titles(
+        &open_read_only(path)?,
+        "SELECT games.title FROM caves JOIN games ON games.id = caves.game_id",
+    )
+}
+
+fn query_titles(conn: &Connection, sql: &str) -> Result<Vec<LibraryGame>> {
+    let mut stmt = conn.prepare(sql).context("Unexpected database schema")?;
+    let titles = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(titles
+        .into_iter()
+        .map(|title| LibraryGame {
+            title,
+            version: None,
+        })
+        .collect())
+}
+
+/// Lowercase and drop everything but letters and digits, so that store
+/// titles like `Mindustry™` or `Zero K` match.
+fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Keep the games found in [`KNOWN_GAMES`], one package per title and
+/// version.
+fn match_known_games(games: Vec<LibraryGame>) -> Vec<InstalledPackage> {
+    let mut packages: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+    for game in games {
+        let normalized = normalize_title(&game.title);
+        let Some((name, url, license)) = KNOWN_GAMES
+            .iter()
+            .find(|(name, _, _)| normalize_title(name) == normalized)
+        else {
+            continue;
+        };
+        let version = game.version.unwrap_or_else(|| "unknown".to_string());
+
+        packages
+            .entry((name.to_string(), version.clone()))
+            .or_insert_with(|| InstalledPackage {
+                name: name.to_string(),
+                version,
+                description: None,
+                url: Some(url.to_string()),
+                source: PackageSource::Game,
+                licenses: vec![license.to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
+            });
+    }
+
+    packages.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steam_manifest() {
+        let content = r#"
+"AppState"
+{
+	"appid"		"1127400"
+	"universe"		"1"
+	"name"		"Mindustry"
+	"StateFlags"		"4"
+	"installdir"		"Mindustry"
+	"buildid"		"14389592"
+	"InstalledDepots"
+	{
+		"1127401"
+		{
+			"manifest"		"5765431011392335582"
+		}
+	}
+}
+"#;
+        assert_eq!(
+            parse_app_manifest(content),
+            Some(LibraryGame {
+                title: "Mindustry".to_string(),
+                version: Some("14389592".to_string()),
+            })
+        );
+        assert_eq!(parse_app_manifest("\"AppState\"\n{\n}\n"), None);
+    }
+
+    #[test]
+    fn library_folder_paths() {
+        let content = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/user/.local/share/Steam"
+		"apps"
+		{
+			"1127400"		"512000000"
+		}
+	}
+	"1"
+	{
+		"path"		"D:\\Games\\SteamLibrary"
+	}
+}
+"#;
+        let paths: Vec<String> = vdf_pairs(content)
+            .into_iter()
+            .filter(|(k, _)| k == "path")
+            .map(|(_, v)| v)
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["/home/user/.local/share/Steam", "D:\\Games\\SteamLibrary"]
+        );
+    }
+
+    #[test]
+    fn read_lutris_and_itch_databases() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let lutris = dir.path().join("pga.db");
+        let conn = Connection::open(&lutris).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE games (id INTEGER PRIMARY KEY, name TEXT, slug TEXT, installed INTEGER);
+             INSERT INTO games (name, slug, installed) VALUES ('Veloren', 'veloren', 1);
+             INSERT INTO games (name, slug, installed) VALUES ('Xonotic', 'xonotic', 0);",
+        )
+        .unwrap();
+        drop(conn);
+        let games = read_lutris_db(&lutris).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].title, "Veloren");
+
+        let itch = dir.path().join("butler.db");
+        let conn = Connection::open(&itch).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE games (id INTEGER PRIMARY KEY, title TEXT, url TEXT);
+             CREATE TABLE caves (id TEXT PRIMARY KEY, game_id INTEGER);
+             INSERT INTO games VALUES (1, 'Pixel Wheels', 'https://agateau.itch.io/pixelwheels');
+             INSERT INTO games VALUES (2, 'Not Installed', 'https://example.itch.io/x');
+             INSERT INTO caves VALUES ('cave-1', 1);",
+        )
+        .unwrap();
+        drop(conn);
+        let games = read_itch_db(&itch).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].title, "Pixel Wheels");
+
+        assert!(read_itch_db(&lutris).is_err());
+    }
+
+    #[test]
+    fn only_known_games_are_reported() {
+        let games = vec![
+            LibraryGame {
+                title: "Mindustry™".to_string(),
+                version: Some("14389592".to_string()),
+            },
+            LibraryGame {
+                title: "Some Proprietary Game".to_string(),
+                version: Some("1".to_string()),
+            },
+            LibraryGame {
+                title: "veloren".to_string(),
+                version: None,
+            },
+            LibraryGame {
+                title: "Veloren".to_string(),
+                version: None,
+            },
+        ];
+        let packages = match_known_games(games);
+        assert_eq!(packages.len(), 2);
+
+        assert_eq!(packages[0].name, "Mindustry");
+        assert_eq!(packages[0].version, "14389592");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/Anuken/Mindustry")
+        );
+        assert_eq!(packages[0].source, PackageSource::Game);
+
+        assert_eq!(packages[1].name, "Veloren");
+        assert_eq!(packages[1].version, "unknown");
+        assert_eq!(packages[1].licenses, vec!["GPL-3.0-or-later"]);
+    }
+}
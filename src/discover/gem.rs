@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use crate::project::FundingChannel;
+
+/// Discovers Ruby gems installed locally.
+///
+/// Runs `gem list --local` for the installed gems and `gem env gempath` to
+/// locate their specifications, then reads each gem's installed `.gemspec`
+/// for its summary, licenses, homepage, and the `source_code_uri` and
+/// `funding_uri` metadata entries.
+pub struct GemDiscoverer;
+
+impl Discoverer for GemDiscoverer {
+    fn name(&self) -> &str {
+        "gem"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("gem")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let list = run_gem(&["list", "--local"])?;
+        let gempath = run_gem(&["env", "gempath"])?;
+        let spec_dirs: Vec<PathBuf> = std::env::split_paths(gempath.trim())
+            .flat_map(|p| {
+                let specs = p.join("specifications");
+                [specs.join("default"), specs]
+            })
+            .filter(|p| p.is_dir())
+            .collect();
+
+        let gems = parse_gem_list(&list);
+
+        let pb = ProgressBar::new(gems.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let packages = gems
+            .into_iter()
+            .map(|(name, version)| {
+                let spec = find_gemspec(&spec_dirs, &name, &version)
+                    .and_then(|path| fs::read_to_string(path).ok())
+                    .map(|content| parse_gemspec(&content))
+                    .unwrap_or_default();
+                pb.inc(1);
+                build_package(name, version, spec)
+            })
+            .collect();
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run a gem subcommand and return its stdout.
+fn run_gem(args: &[&str]) -> Result<String> {
+    let output = Command::new("gem")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run gem {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "gem {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("gem output is not valid UTF-8")
+}
+
+/// Parse `gem list --local` output into `(name, version)` pairs.
+///
+/// Lines look like `rake (13.2.1, 13.1.0)` or `bundler (default: 2.5.9)`.
+/// Only the newest (first listed) version of each gem is kept.
+fn parse_gem_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, rest) = line.trim().split_once(" (")?;
+            let versions = rest.strip_suffix(')')?;
+            let newest = versions.split(',').next()?.trim();
+            let newest = newest.strip_prefix("default: ").unwrap_or(newest);
+            // Drop the platform of native gems (`1.16.5 x86_64-linux`)
+            let newest = newest.split_whitespace().next()?;
+            Some((name.to_string(), newest.to_string()))
+        })
+        .collect()
+}
+
+/// Locate the installed gemspec for `name`/`version`.
+///
+/// Platform-specific gems carry a suffix (e.g.
+/// `nokogiri-1.16.5-x86_64-linux.gemspec`), so a prefix match is used as a
+/// fallback.
+fn find_gemspec(spec_dirs: &[PathBuf], name: &str, version: &str) -> Option<PathBuf> {
+    let stem = format!("{name}-{version}");
+
+    spec_dirs.iter().find_map(|dir| {
+        let exact = dir.join(format!("{stem}.gemspec"));
+        if exact.is_file() {
+            return Some(exact);
+        }
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| is_platform_gemspec(p, &stem))
+    })
+}
+
+fn is_platform_gemspec(path: &Path, stem: &str) -> bool {
+    path.file_name()
+        .and_then(|f| f.to_str())
+        .and_then(|f| f.strip_suffix(".gemspec"))
+        .and_then(|f| f.strip_prefix(stem))
+        .is_some_and(|platform| platform.starts_with('-'))
+}
+
+/// Metadata read from an installed gemspec.
+#[derive(Debug, Default, PartialEq)]
+struct GemSpec {
+    summary: Option<String>,
+    homepage: Option<String>,
+    licenses: Vec<String>,
+    source_code_uri: Option<String>,
+    homepage_uri: Option<String>,
+    funding_uri: Option<String>,
+}
+
+/// Parse the generated Ruby gemspec written by RubyGems on install.
+///
+/// These files are machine-generated with one attribute per line, e.g.
+/// `s.homepage = "https://github.com/ruby/rake".freeze` and
+/// `s.metadata = { "source_code_uri" => "…", "funding_uri" => "…" }`, so a
+/// line-based scan of quoted strings is sufficient.
+fn parse_gemspec(content: &str) -> GemSpec {
+    let mut spec = GemSpec::default();
+
+    for line in content.lines() {
+        let Some((attr, value)) = line.trim().split_once(" = ") else {
+            continue;
+        };
+        let strings = quoted_strings(value);
+
+        match attr.trim() {
+            "s.summary" => spec.summary = strings.into_iter().next(),
+            "s.homepage" => spec.homepage = strings.into_iter().next(),
+            "s.licenses" | "s.license" => spec.licenses = strings,
+            "s.metadata" => {
+                for pair in strings.chunks_exact(2) {
+                    let value = Some(pair[1].clone());
+                    match pair[0].as_str() {
+                        "source_code_uri" => spec.source_code_uri = value,
+                        "homepage_uri" => spec.homepage_uri = value,
+                        "funding_uri" => spec.funding_uri = value,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    spec
+}
+
+/// Extract the contents of every double-quoted string in `s`, in order.
+fn quoted_strings(s: &str) -> Vec<String> {
+    s.split('"')
+        .skip(1)
+        .step_by(2)
+        .map(|part| part.replace("\\#", "#"))
+        .collect()
+}
+
+/// Trim a forge URL that points into a specific tree or file (e.g.
+/// `https://github.com/ruby/rake/tree/v13.2.1`) back to the repository root.
+fn repository_root(url: &str) -> &str {
+    ["/-/", "/tree/", "/blob/"]
+        .iter()
+        .find_map(|marker| url.find(marker).map(|pos| &url[..pos]))
+        .unwrap_or(url)
+        .trim_end_matches('/')
+}
+
+fn build_package(name: String, version: String, spec: GemSpec) -> InstalledPackage {
+    let url = spec
+        .source_code_uri
+        .as_deref()
+        .map(|u| repository_root(u).to_string())
+        .or(spec.homepage_uri)
+        .or(spec.homepage)
+        .filter(|u| !u.is_empty());
+
+    InstalledPackage {
+        name,
+        version,
+        description: spec.summary,
+        url,
+        source: PackageSource::Gem,
+        licenses: spec.licenses,
+        funding: spec
+            .funding_uri
+            .iter()
+            .map(|u| FundingChannel::from_url(u))
+            .collect(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAKE_GEMSPEC: &str = r##"# -*- encoding: utf-8 -*-
+# stub: rake 13.2.1 ruby lib
+
+Gem::Specification.new do |s|
+  s.name = "rake".freeze
+  s.version = "13.2.1".freeze
+
+  s.required_rubygems_version = Gem::Requirement.new(">= 1.3.2".freeze) if s.respond_to? :required_rubygems_version=
+  s.metadata = { "bug_tracker_uri" => "https://github.com/ruby/rake/issues", "funding_uri" => "https://github.com/sponsors/ruby", "source_code_uri" => "https://github.com/ruby/rake/tree/v13.2.1" } if s.respond_to? :metadata=
+  s.homepage = "https://github.com/ruby/rake".freeze
+  s.licenses = ["MIT".freeze]
+  s.summary = "Rake is a Make-like program implemented in Ruby".freeze
+end
+"##;
+
+    #[test]
+    fn parse_list() {
+        let output = "\
+*** LOCAL GEMS ***
+
+bundler (default: 2.5.9)
+nokogiri (1.16.5 x86_64-linux)
+rake (13.2.1, 13.1.0)
+";
+        assert_eq!(
+            parse_gem_list(output),
+            vec![
+                ("bundler".to_string(), "2.5.9".to_string()),
+                ("nokogiri".to_string(), "1.16.5".to_string()),
+                ("rake".to_string(), "13.2.1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rake_gemspec() {
+        let spec = parse_gemspec(RAKE_GEMSPEC);
+        assert_eq!(
+            spec.summary.as_deref(),
+            Some("Rake is a Make-like program implemented in Ruby")
+        );
+        assert_eq!(spec.licenses, vec!["MIT"]);
+        assert_eq!(
+            spec.source_code_uri.as_deref(),
+            Some("https://github.com/ruby/rake/tree/v13.2.1")
+        );
+        assert_eq!(
+            spec.funding_uri.as_deref(),
+            Some("https://github.com/sponsors/ruby")
+        );
+    }
+
+    #[test]
+    fn build_package_prefers_source_code_uri() {
+        let pkg = build_package(
+            "rake".to_string(),
+            "13.2.1".to_string(),
+            parse_gemspec(RAKE_GEMSPEC),
+        );
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/ruby/rake"));
+        assert_eq!(pkg.source, PackageSource::Gem);
+        assert_eq!(pkg.funding.len(), 1);
+        assert_eq!(pkg.funding[0].platform, "GitHub Sponsors");
+    }
+
+    #[test]
+    fn build_package_without_gemspec() {
+        let pkg = build_package("foo".to_string(), "1.0".to_string(), GemSpec::default());
+        assert!(pkg.url.is_none());
+        assert!(pkg.funding.is_empty());
+    }
+
+    #[test]
+    fn find_platform_gemspec() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nokogiri-1.16.5-x86_64-linux.gemspec");
+        fs::write(&path, "").unwrap();
+        fs::write(dir.path().join("nokogiri-1.16.50.gemspec"), "").unwrap();
+
+        let dirs = vec![dir.path().to_path_buf()];
+        assert_eq!(find_gemspec(&dirs, "nokogiri", "1.16.5"), Some(path));
+        assert_eq!(find_gemspec(&dirs, "rake", "13.2.1"), None);
+    }
+
+    #[test]
+    fn repository_root_trims_tree_paths() {
+        assert_eq!(
+            repository_root("https://gitlab.com/foo/bar/-/tree/main"),
+            "https://gitlab.com/foo/bar"
+        );
+        assert_eq!(
+            repository_root("https://github.com/foo/bar/"),
+            "https://github.com/foo/bar"
+        );
+    }
+}
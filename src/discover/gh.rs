@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers GitHub CLI extensions installed with `gh extension install`.
+///
+/// Runs `gh extension list`, which (when not writing to a terminal) prints
+/// one tab-separated line per extension: command name, repository, and the
+/// installed release tag or commit. Every extension is a GitHub repository,
+/// so the URL is always known.
+pub struct GhExtensionsDiscoverer;
+
+impl Discoverer for GhExtensionsDiscoverer {
+    fn name(&self) -> &str {
+        "gh-extension"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("gh")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("gh")
+            .args(["extension", "list"])
+            .output()
+            .context("Failed to run gh extension list")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "gh extension list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("gh extension list output is not valid UTF-8")?;
+        Ok(parse_extension_list(&stdout))
+    }
+}
+
+/// Parse `gh extension list` output.
+///
+/// ```text
+/// gh dash    dlvhdr/gh-dash    v4.5.4
+/// gh poi     seachicken/gh-poi 1a2b3c4
+/// ```
+///
+/// Columns are tab-separated. Repositories on a GitHub Enterprise host are
+/// listed as `host/owner/repo`. The package is named after the repository.
+fn parse_extension_list(output: &str) -> Vec<InstalledPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t').map(str::trim);
+            let _command = fields.next()?;
+            let repo = fields.next().filter(|r| r.contains('/'))?;
+            let version = fields.next().filter(|v| !v.is_empty()).unwrap_or("unknown");
+
+            let url = if repo.matches('/').count() >= 2 {
+                format!("https://{repo}")
+            } else {
+                format!("https://github.com/{repo}")
+            };
+
+            Some(InstalledPackage {
+                name: repo.rsplit('/').next().unwrap_or(repo).to_string(),
+                version: version.to_string(),
+                description: None,
+                url: Some(url),
+                source: PackageSource::GhExtension,
+                licenses: Vec::new(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_output() {
+        let output = "\
+gh dash\tdlvhdr/gh-dash\tv4.5.4
+gh poi\tseachicken/gh-poi\t1a2b3c4
+gh internal\tghe.example.com/tools/gh-internal\t
+";
+        let packages = parse_extension_list(output);
+        assert_eq!(packages.len(), 3);
+
+        assert_eq!(packages[0].name, "gh-dash");
+        assert_eq!(packages[0].version, "v4.5.4");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/dlvhdr/gh-dash")
+        );
+        assert_eq!(packages[0].source, PackageSource::GhExtension);
+
+        assert_eq!(packages[1].version, "1a2b3c4");
+
+        assert_eq!(packages[2].version, "unknown");
+        assert_eq!(
+            packages[2].url.as_deref(),
+            Some("https://ghe.example.com/tools/gh-internal")
+        );
+    }
+
+    #[test]
+    fn parse_empty_and_malformed() {
+        assert!(parse_extension_list("").is_empty());
+        assert!(parse_extension_list("no tabs here\n").is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Haskell packages registered with GHC, cabal, and stack.
+///
+/// The compiler's global package database (GHC itself and its boot
+/// libraries) is read with `ghc-pkg dump --global`. Libraries built by
+/// `cabal install` and stack live in their own package databases (the cabal
+/// store and stack's snapshot `pkgdb` directories); their `.conf` files use
+/// the same format and are read directly. Packages present in several
+/// databases are reported once per version.
+pub struct GhcPkgDiscoverer;
+
+impl Discoverer for GhcPkgDiscoverer {
+    fn name(&self) -> &str {
+        "ghc-pkg"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("ghc-pkg") || !package_db_dirs().is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+        let mut insert = |pkg: InstalledPackage| {
+            by_key
+                .entry((pkg.name.clone(), pkg.version.clone()))
+                .or_insert(pkg);
+        };
+
+        if super::in_path("ghc-pkg") {
+            let output = Command::new("ghc-pkg")
+                .args(["dump", "--global"])
+                .output()
+                .context("Failed to run ghc-pkg dump")?;
+
+            if !output.status.success() {
+                anyhow::bail!(
+                    "ghc-pkg dump failed: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+
+            let stdout =
+                String::from_utf8(output.stdout).context("ghc-pkg output is not valid UTF-8")?;
+            parse_ghc_pkg_dump(&stdout)
+                .into_iter()
+                .for_each(&mut insert);
+        }
+
+        let conf_files: Vec<PathBuf> = package_db_dirs()
+            .iter()
+            .filter_map(|db| fs::read_dir(db).ok())
+            .flatten()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "conf"))
+            .collect();
+
+        let pb = ProgressBar::new(conf_files.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        for path in &conf_files {
+            match fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_package_conf(&content))
+            {
+                Ok(pkg) => insert(pkg),
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+/// Package databases written by cabal and stack.
+///
+/// - cabal store: `<cabal dir>/store/ghc-<version>/package.db`, where the
+///   cabal dir is `$CABAL_DIR`, `~/.cabal`, or the XDG location
+///   `~/.local/state/cabal`.
+/// - stack: `<stack root>/snapshots/<platform>/<hash>/<ghc version>/pkgdb`,
+///   where the root is `$STACK_ROOT` or `~/.stack`.
+fn package_db_dirs() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+
+    let cabal_dirs = match std::env::var_os("CABAL_DIR") {
+        Some(dir) => vec![PathBuf::from(dir)],
+        None => vec![home.join(".cabal"), home.join(".local/state/cabal")],
+    };
+    let stack_root = std::env::var_os("STACK_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".stack"));
+
+    let mut dirs: Vec<PathBuf> = cabal_dirs
+        .iter()
+        .flat_map(|dir| subdirs(&dir.join("store")))
+        .map(|ghc| ghc.join("package.db"))
+        .collect();
+
+    dirs.extend(
+        subdirs(&stack_root.join("snapshots"))
+            .iter()
+            .flat_map(|platform| subdirs(platform))
+            .flat_map(|hash| subdirs(&hash))
+            .map(|ghc| ghc.join("pkgdb")),
+    );
+
+    dirs.retain(|d| d.is_dir());
+    dirs
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+/// Parse `ghc-pkg dump` output: package descriptions separated by `---`.
+fn parse_ghc_pkg_dump(output: &str) -> Vec<InstalledPackage> {
+    let mut packages = Vec::new();
+    let mut block = String::new();
+
+    for line in output.lines().chain(std::iter::once("---")) {
+        if line.trim() == "---" {
+            if !block.trim().is_empty() {
+                match parse_package_conf(&block) {
+                    Ok(pkg) => packages.push(pkg),
+                    Err(e) => eprintln!("  Warning: failed to parse ghc-pkg entry: {e}"),
+                }
+            }
+            block.clear();
+        } else {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    packages
+}
+
+/// Parse a single installed package description (a `.conf` file, or one
+/// entry of `ghc-pkg dump`).
+///
+/// ```text
+/// name:                 text
+/// version:              2.0.2
+/// license:              BSD-2-Clause
+/// homepage:             https://github.com/haskell/text
+/// synopsis:             An efficient packed Unicode text type.
+/// description:
+///     An efficient packed, immutable Unicode text type ...
+/// ```
+///
+/// Continuation lines are indented and belong to the previous field. Only
+/// single-line values of the relevant fields are used.
+fn parse_package_conf(content: &str) -> Result<InstalledPackage> {
+    let mut fields: BTreeMap<&str, String> = BTreeMap::new();
+    let mut current: Option<&str> = None;
+
+    for line in content.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(key) = current
+                && let Some(value) = fields.get_mut(key)
+                && value.is_empty()
+            {
+                *value = line.trim().to_string();
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim();
+            fields.insert(key, value.trim().to_string());
+            current = Some(key);
+        }
+    }
+
+    let mut take = |key: &str| fields.remove(key).filter(|v| !v.is_empty());
+
+    Ok(InstalledPackage {
+        name: take("name").context("Missing name field")?,
+        version: take("version").unwrap_or_else(|| "unknown".to_string()),
+        description: take("synopsis"),
+        url: take("homepage"),
+        source: PackageSource::GhcPkg,
+        licenses: take("license").into_iter().collect(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT_CONF: &str = "\
+name:                 text
+version:              2.0.2
+visibility:           public
+id:                   text-2.0.2
+key:                  text-2.0.2
+license:              BSD-2-Clause
+maintainer:           Haskell Text Team <andrew.lelechenko@gmail.com>
+homepage:             https://github.com/haskell/text
+synopsis:             An efficient packed Unicode text type.
+description:
+    An efficient packed, immutable Unicode text type (both strict and
+    lazy).
+category:             Data, Text
+exposed:              True
+";
+
+    #[test]
+    fn parse_conf_file() {
+        let pkg = parse_package_conf(TEXT_CONF).unwrap();
+        assert_eq!(pkg.name, "text");
+        assert_eq!(pkg.version, "2.0.2");
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/haskell/text"));
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("An efficient packed Unicode text type.")
+        );
+        assert_eq!(pkg.licenses, vec!["BSD-2-Clause"]);
+        assert_eq!(pkg.source, PackageSource::GhcPkg);
+    }
+
+    #[test]
+    fn multiline_synopsis_uses_first_line() {
+        let conf = "name: foo\nversion: 1.0\nsynopsis:\n    First line\n    second line\n";
+        let pkg = parse_package_conf(conf).unwrap();
+        assert_eq!(pkg.description.as_deref(), Some("First line"));
+        assert!(pkg.url.is_none());
+    }
+
+    #[test]
+    fn parse_dump_with_multiple_entries() {
+        let output =
+            format!("{TEXT_CONF}---\nname: ghc\nversion: 9.8.2\nlicense: BSD-3-Clause\n---\n");
+        let packages = parse_ghc_pkg_dump(&output);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "text");
+        assert_eq!(packages[1].name, "ghc");
+        assert_eq!(packages[1].version, "9.8.2");
+    }
+
+    #[test]
+    fn parse_conf_missing_name() {
+        assert!(parse_package_conf("version: 1.0\n").is_err());
+    }
+}
@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Shared helpers for discoverers whose packages are plain git checkouts
+//! (editor and shell plugin managers).
+//!
+//! Repository metadata is read straight from the `.git` directory instead of
+//! spawning `git`, since plugin directories number in the dozens and only the
+//! `origin` remote and the checked-out commit are needed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{InstallReason, InstalledPackage, PackageSource};
+
+/// Length of the abbreviated commit hash used as a checkout's version.
+const SHORT_HASH_LEN: usize = 12;
+
+/// Build a package for a plugin checked out at `dir`.
+///
+/// The package is named after the directory. The `origin` remote (as an
+/// HTTPS URL) is the project URL and the abbreviated `HEAD` commit is the
+/// version; directories that are not git checkouts are still reported, with
+/// an unknown version and no URL.
+pub(super) fn checkout_package(dir: &Path, source: PackageSource) -> InstalledPackage {
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let git_dir = git_dir(dir);
+
+    InstalledPackage {
+        name,
+        version: git_dir
+            .as_deref()
+            .and_then(head_commit)
+            .map(|hash| hash.chars().take(SHORT_HASH_LEN).collect())
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: None,
+        url: git_dir
+            .as_deref()
+            .and_then(|d| fs::read_to_string(d.join("config")).ok())
+            .and_then(|config| origin_url(&config))
+            .and_then(|url| remote_to_https(&url)),
+        source,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+/// Locate the git directory of a checkout.
+///
+/// Submodules and worktrees have a `.git` *file* containing
+/// `gitdir: <path>`, relative to the checkout.
+fn git_dir(dir: &Path) -> Option<PathBuf> {
+    let dot_git = dir.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let target = content.trim().strip_prefix("gitdir:")?.trim();
+    Some(dir.join(target))
+}
+
+/// Resolve `HEAD` to a commit hash, following one level of symbolic ref
+/// through loose refs or `packed-refs`.
+fn head_commit(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    let Some(reference) = head.strip_prefix("ref:").map(str::trim) else {
+        return Some(head.to_string()).filter(|h| is_hash(h));
+    };
+
+    if let Ok(hash) = fs::read_to_string(git_dir.join(reference)) {
+        return Some(hash.trim().to_string()).filter(|h| is_hash(h));
+    }
+
+    let packed = fs::read_to_string(git_dir.join("packed-refs")).ok()?;
+    packed_ref(&packed, reference)
+}
+
+fn is_hash(s: &str) -> bool {
+    s.len() >= 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Look up `reference` in a `packed-refs` file (`<hash> <ref>` lines).
+fn packed_ref(packed: &str, reference: &str) -> Option<String> {
+    packed
+        .lines()
+        .filter(|l| !l.starts_with('#') && !l.starts_with('^'))
+        .filter_map(|l| l.split_once(' '))
+        .find(|(_, name)| name.trim() == reference)
+        .map(|(hash, _)| hash.to_string())
+        .filter(|h| is_hash(h))
+}
+
+/// Extract the `origin` remote's URL from a git `config` file.
+///
+/// ```text
+/// [remote "origin"]
+///     url = https://github.com/folke/lazy.nvim.git
+///     fetch = +refs/heads/*:refs/remotes/origin/*
+/// ```
+fn origin_url(config: &str) -> Option<String> {
+    let mut in_origin = false;
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_origin = line == r#"[remote "origin"]"#;
+        } else if in_origin
+            && let Some((key, value)) = line.split_once('=')
+            && key.trim() == "url"
+        {
+            return Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        }
+    }
+    None
+}
+
+/// Turn a git remote into a browsable HTTPS URL.
+///
+/// Handles `https://` (dropping embedded credentials), `git://`, `ssh://`,
+/// and scp-like `git@host:owner/repo` remotes, stripping a trailing `.git`.
+/// Local paths yield `None`.
+fn remote_to_https(remote: &str) -> Option<String> {
+    let rest = if let Some((scheme, rest)) = remote.split_once("://") {
+        if !matches!(scheme, "https" | "http" | "git" | "ssh" | "git+ssh") {
+            return None;
+        }
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host = authority.rsplit('@').next().unwrap_or(authority);
+        // Drop an explicit port (common with ssh remotes).
+        let host = host.split(':').next().unwrap_or(host);
+        format!("{host}/{path}")
+    } else {
+        let (user_host, path) = remote.split_once(':')?;
+        // A single letter is a Windows drive, a slash means a local path.
+        if user_host.len() < 2 || user_host.contains('/') {
+            return None;
+        }
+        let host = user_host.rsplit('@').next().unwrap_or(user_host);
+        format!("{host}/{}", path.trim_start_matches('/'))
+    };
+
+    let rest = rest.trim_end_matches('/');
+    let rest = rest.strip_suffix(".git").unwrap_or(rest);
+    Some(format!("https://{rest}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_forms() {
+        let cases = [
+            (
+                "https://github.com/folke/lazy.nvim.git",
+                "https://github.com/folke/lazy.nvim",
+            ),
+            (
+                "https://token@github.com/tpope/vim-fugitive",
+                "https://github.com/tpope/vim-fugitive",
+            ),
+            (
+                "git@github.com:ohmyzsh/ohmyzsh.git",
+                "https://github.com/ohmyzsh/ohmyzsh",
+            ),
+            (
+                "ssh://git@gitlab.com:2222/foo/bar.git",
+                "https://gitlab.com/foo/bar",
+            ),
+            (
+                "git://git.sr.ht/~user/plugin",
+                "https://git.sr.ht/~user/plugin",
+            ),
+        ];
+        for (remote, expected) in cases {
+            assert_eq!(
+                remote_to_https(remote).as_deref(),
+                Some(expected),
+                "{remote}"
+            );
+        }
+        assert_eq!(remote_to_https("/home/me/src/plugin"), None);
+        assert_eq!(remote_to_https("file:///home/me/plugin"), None);
+        assert_eq!(remote_to_https("../plugin"), None);
+    }
+
+    #[test]
+    fn origin_from_config() {
+        let config = "\
+[core]
+\trepositoryformatversion = 0
+[remote \"upstream\"]
+\turl = https://example.org/upstream.git
+[remote \"origin\"]
+\turl = https://github.com/junegunn/fzf.vim.git
+\tfetch = +refs/heads/*:refs/remotes/origin/*
+";
+        assert_eq!(
+            origin_url(config).as_deref(),
+            Some("https://github.com/junegunn/fzf.vim.git")
+        );
+        assert_eq!(origin_url("[core]\n"), None);
+    }
+
+    #[test]
+    fn packed_refs_lookup() {
+        let packed = "\
+# pack-refs with: peeled fully-peeled sorted
+0123456789abcdef0123456789abcdef01234567 refs/heads/main
+^89abcdef0123456789abcdef0123456789abcdef
+";
+        assert_eq!(
+            packed_ref(packed, "refs/heads/main").as_deref(),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert_eq!(packed_ref(packed, "refs/heads/dev"), None);
+    }
+
+    #[test]
+    fn checkout_from_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let plugin = tmp.path().join("vim-surround");
+        let git = plugin.join(".git");
+        fs::create_dir_all(git.join("refs/heads")).unwrap();
+        fs::write(git.join("HEAD"), "ref: refs/heads/master\n").unwrap();
+        fs::write(
+            git.join("refs/heads/master"),
+            "3d188ed2113431cf8dac77be61b842acb64433d9\n",
+        )
+        .unwrap();
+        fs::write(
+            git.join("config"),
+            "[remote \"origin\"]\n\turl = https://github.com/tpope/vim-surround.git\n",
+        )
+        .unwrap();
+
+        let pkg = checkout_package(&plugin, PackageSource::VimPlugin);
+        assert_eq!(pkg.name, "vim-surround");
+        assert_eq!(pkg.version, "3d188ed21134");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/tpope/vim-surround")
+        );
+
+        let plain = tmp.path().join("local-plugin");
+        fs::create_dir(&plain).unwrap();
+        let pkg = checkout_package(&plain, PackageSource::VimPlugin);
+        assert_eq!(pkg.version, "unknown");
+        assert!(pkg.url.is_none());
+    }
+}
@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Go binaries installed via `go install`.
+///
+/// Runs `go version -m` over `$GOBIN` (or `$GOPATH/bin`, defaulting to
+/// `~/go/bin`) to read the build info embedded in each binary. The main
+/// module path and version identify the package, and the module path is
+/// mapped to its source repository URL.
+pub struct GoDiscoverer;
+
+impl Discoverer for GoDiscoverer {
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("go") && gobin_dirs().iter().any(|dir| dir.is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let mut packages = Vec::new();
+
+        for dir in gobin_dirs().iter().filter(|dir| dir.is_dir()) {
+            // Non-Go files in the directory are skipped by `go version`.
+            let output = Command::new("go")
+                .arg("version")
+                .arg("-m")
+                .arg(dir)
+                .output()
+                .context("Failed to run go version -m")?;
+
+            if !output.status.success() {
+                anyhow::bail!(
+                    "go version -m failed: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+
+            let stdout = String::from_utf8(output.stdout)
+                .context("go version -m output is not valid UTF-8")?;
+
+            packages.extend(parse_go_version_output(&stdout));
+        }
+
+        Ok(packages)
+    }
+}
+
+/// Directories `go install` writes binaries to.
+///
+/// `$GOBIN` if set, otherwise `bin/` under each `$GOPATH` entry (default
+/// `~/go`).
+fn gobin_dirs() -> Vec<PathBuf> {
+    if let Some(gobin) = std::env::var_os("GOBIN").filter(|v| !v.is_empty()) {
+        return vec![PathBuf::from(gobin)];
+    }
+
+    match std::env::var_os("GOPATH").filter(|v| !v.is_empty()) {
+        Some(gopath) => std::env::split_paths(&gopath)
+            .map(|p| p.join("bin"))
+            .collect(),
+        None => std::env::var_os("HOME")
+            .map(|h| vec![PathBuf::from(h).join("go/bin")])
+            .unwrap_or_default(),
+    }
+}
+
+/// Parse the output of `go version -m <dir>`.
+///
+/// Each binary produces a block like:
+/// ```text
+/// /home/user/go/bin/gopls: go1.22.2
+///         path    golang.org/x/tools/gopls
+///         mod     golang.org/x/tools/gopls        v0.15.3 h1:...
+///         dep     ...
+/// ```
+fn parse_go_version_output(output: &str) -> Vec<InstalledPackage> {
+    // Split into one block per binary; block headers are not indented.
+    let mut blocks: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if blocks.is_empty() || !line.starts_with(char::is_whitespace) {
+            blocks.push(String::new());
+        }
+        if let Some(block) = blocks.last_mut() {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    let pb = ProgressBar::new(blocks.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {bar:30} {pos}/{len} packages")
+            .unwrap(),
+    );
+
+    let mut packages = Vec::new();
+
+    for block in &blocks {
+        match parse_block(block) {
+            Some(pkg) => packages.push(pkg),
+            None => {
+                let header = block.lines().next().unwrap_or_default();
+                pb.suspend(|| eprintln!("  Warning: failed to parse go binary info: {header}"));
+            }
+        }
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+
+    packages
+}
+
+/// Parse a single binary's build info block.
+fn parse_block(block: &str) -> Option<InstalledPackage> {
+    let mut lines = block.lines();
+    let header = lines.next()?;
+    let (binary, _go_version) = header.rsplit_once(": ")?;
+    let name = Path::new(binary)
+        .file_name()?
+        .to_string_lossy()
+        .into_owned();
+
+    let mut path = None;
+    let mut module = None;
+    let mut version = None;
+
+    for line in lines {
+        let mut fields = line.split('\t').map(str::trim).filter(|f| !f.is_empty());
+        match fields.next() {
+            Some("path") => path = fields.next(),
+            Some("mod") => {
+                module = fields.next();
+                version = fields.next();
+            }
+            _ => {}
+        }
+    }
+
+    let module = module.or(path)?;
+    let version = version
+        .filter(|v| *v != "(devel)")
+        .unwrap_or("unknown")
+        .to_string();
+
+    Some(InstalledPackage {
+        name,
+        version,
+        description: None,
+        url: Some(module_url(module)),
+        source: PackageSource::Go,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    })
+}
+
+/// Derive a repository URL from a Go module path.
+///
+/// Modules on well-known forges map to the repository root (dropping any
+/// sub-package and major-version suffix). `golang.org/x/*` maps to its
+/// GitHub mirror. Vanity import paths fall back to their pkg.go.dev page.
+pub(super) fn module_url(module: &str) -> String {
+    let segments: Vec<&str> = module.split('/').collect();
+
+    match segments.as_slice() {
+        ["golang.org", "x", repo, ..] => format!("https://github.com/golang/{repo}"),
+        [
+            host @ ("github.com" | "gitlab.com" | "bitbucket.org" | "codeberg.org" | "git.sr.ht"),
+            owner,
+            repo,
+            ..,
+        ] => format!("https://{host}/{owner}/{repo}"),
+        _ => format!("https://pkg.go.dev/{module}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+/home/user/go/bin/gopls: go1.22.2
+\tpath\tgolang.org/x/tools/gopls
+\tmod\tgolang.org/x/tools/gopls\tv0.15.3\th1:abc=
+\tdep\tgithub.com/google/go-cmp\tv0.6.0\th1:def=
+\tbuild\t-buildmode=exe
+/home/user/go/bin/golangci-lint: go1.22.2
+\tpath\tgithub.com/golangci/golangci-lint/cmd/golangci-lint
+\tmod\tgithub.com/golangci/golangci-lint\tv1.59.1\th1:xyz=
+/home/user/go/bin/mytool: go1.22.2
+\tpath\texample.com/mytool
+\tmod\texample.com/mytool\t(devel)\t
+";
+
+    #[test]
+    fn parse_multiple_binaries() {
+        let packages = parse_go_version_output(SAMPLE);
+        assert_eq!(packages.len(), 3);
+
+        assert_eq!(packages[0].name, "gopls");
+        assert_eq!(packages[0].version, "v0.15.3");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/golang/tools")
+        );
+        assert_eq!(packages[0].source, PackageSource::Go);
+
+        assert_eq!(packages[1].name, "golangci-lint");
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/golangci/golangci-lint")
+        );
+
+        assert_eq!(packages[2].version, "unknown");
+        assert_eq!(
+            packages[2].url.as_deref(),
+            Some("https://pkg.go.dev/example.com/mytool")
+        );
+    }
+
+    #[test]
+    fn parse_empty_output() {
+        assert!(parse_go_version_output("").is_empty());
+    }
+
+    #[test]
+    fn parse_block_without_module_info() {
+        assert!(parse_block("/home/user/go/bin/broken: go1.22.2\n").is_none());
+    }
+
+    #[test]
+    fn parse_block_path_only() {
+        let pkg = parse_block("/bin/foo: go1.21.0\n\tpath\tgithub.com/a/foo\n").unwrap();
+        assert_eq!(pkg.version, "unknown");
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/a/foo"));
+    }
+
+    #[test]
+    fn module_urls() {
+        assert_eq!(
+            module_url("github.com/charmbracelet/glow/v2"),
+            "https://github.com/charmbracelet/glow"
+        );
+        assert_eq!(
+            module_url("git.sr.ht/~rjarry/aerc"),
+            "https://git.sr.ht/~rjarry/aerc"
+        );
+        assert_eq!(
+            module_url("mvdan.cc/gofumpt"),
+            "https://pkg.go.dev/mvdan.cc/gofumpt"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Helm charts deployed to the current Kubernetes cluster.
+///
+/// Opt-in via `[discover] kubernetes = true`, like
+/// [`KubernetesDiscoverer`](super::kubernetes::KubernetesDiscoverer), since
+/// it talks to a (possibly remote) cluster. Runs `helm list -A -o json` for
+/// the releases in every namespace and reports each distinct chart version
+/// once. To find the upstream project, the chart is looked up in the locally
+/// configured repositories (`helm search repo`) and its `Chart.yaml` is read
+/// with `helm show chart` for the description, `sources`, `home`, and
+/// Artifact Hub license annotation. Charts that cannot be resolved (e.g.
+/// installed from a local directory or an OCI registry) are reported without
+/// metadata.
+pub struct HelmDiscoverer {
+    /// Whether cluster discovery is enabled in the config.
+    pub enabled: bool,
+}
+
+impl Discoverer for HelmDiscoverer {
+    fn name(&self) -> &str {
+        "helm"
+    }
+
+    fn is_available(&self) -> bool {
+        self.enabled && super::in_path("helm")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let stdout = run_helm(&["list", "--all-namespaces", "--output", "json"])?;
+        let charts = parse_helm_list(&stdout)?;
+
+        let pb = ProgressBar::new(charts.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (name, version) in charts {
+            let chart = resolve_chart(&name, &version).unwrap_or_else(|e| {
+                pb.suspend(|| eprintln!("  Warning: could not resolve chart {name}: {e}"));
+                None
+            });
+            packages.push(build_package(name, version, chart.unwrap_or_default()));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run a helm subcommand and return its stdout.
+fn run_helm(args: &[&str]) -> Result<String> {
+    let output = Command::new("helm")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run helm {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "helm {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("helm output is not valid UTF-8")
+}
+
+/// Find `name` at `version` in the configured chart repositories and read
+/// its `Chart.yaml`. Returns `None` when no repository provides it.
+fn resolve_chart(name: &str, version: &str) -> Result<Option<ChartMetadata>> {
+    let search = run_helm(&[
+        "search",
+        "repo",
+        name,
+        "--version",
+        version,
+        "--output",
+        "json",
+    ])?;
+    let Some(reference) = find_repo_chart(&search, name)? else {
+        return Ok(None);
+    };
+    let chart = run_helm(&["show", "chart", &reference, "--version", version])?;
+    parse_chart_yaml(&chart).map(Some)
+}
+
+#[derive(Deserialize)]
+struct HelmRelease {
+    chart: String,
+}
+
+/// Parse `helm list -o json` into distinct `(chart name, chart version)`
+/// pairs.
+///
+/// ```json
+/// [{"name": "web", "namespace": "default", "chart": "nginx-15.1.0", "app_version": "1.25.1"}]
+/// ```
+fn parse_helm_list(output: &str) -> Result<BTreeSet<(String, String)>> {
+    if output.trim().is_empty() {
+        return Ok(BTreeSet::new());
+    }
+    let releases: Vec<HelmRelease> =
+        serde_json::from_str(output).context("Failed to parse helm list output")?;
+    Ok(releases.iter().map(|r| split_chart(&r.chart)).collect())
+}
+
+/// Split helm's `<name>-<version>` chart label.
+///
+/// Both parts may contain dashes (`cert-manager-v1.14.0`,
+/// `app-1.0.0-rc.1`), so the version starts at the first dash followed by a
+/// digit or by `v` and a digit.
+fn split_chart(chart: &str) -> (String, String) {
+    for (i, _) in chart.match_indices('-') {
+        let rest = &chart[i + 1..];
+        let numeric = rest.trim_start_matches('v');
+        if numeric.starts_with(|c: char| c.is_ascii_digit()) {
+            return (chart[..i].to_string(), rest.to_string());
+        }
+    }
+    (chart.to_string(), "unknown".to_string())
+}
+
+#[derive(Deserialize)]
+struct SearchResult {
+    name: String,
+}
+
+/// Pick the `<repo>/<name>` reference from `helm search repo -o json`,
+/// which also returns charts whose name merely contains the keyword.
+fn find_repo_chart(output: &str, name: &str) -> Result<Option<String>> {
+    let results: Vec<SearchResult> =
+        serde_json::from_str(output).context("Failed to parse helm search output")?;
+    Ok(results
+        .into_iter()
+        .map(|r| r.name)
+        .find(|r| r.rsplit('/').next() == Some(name)))
+}
+
+/// The parts of `Chart.yaml` that syld cares about.
+#[derive(Debug, Default, Deserialize)]
+struct ChartMetadata {
+    description: Option<String>,
+    home: Option<String>,
+    #[serde(default)]
+    sources: Vec<String>,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+/// Parse `helm show chart` output (a `Chart.yaml`).
+///
+/// ```yaml
+/// apiVersion: v2
+/// name: nginx
+/// version: 15.1.0
+/// description: NGINX Open Source is a web server ...
+/// home: https://bitnami.com
+/// sources:
+///   - https://github.com/bitnami/charts/tree/main/bitnami/nginx
+/// annotations:
+///   artifacthub.io/license: Apache-2.0
+/// ```
+fn parse_chart_yaml(content: &str) -> Result<ChartMetadata> {
+    serde_yaml::from_str(content).context("Invalid Chart.yaml")
+}
+
+/// The first entry of `sources` is preferred over `home`, which often points
+/// at the publisher rather than the project.
+fn build_package(name: String, version: String, chart: ChartMetadata) -> InstalledPackage {
+    let url = chart
+        .sources
+        .into_iter()
+        .find(|s| s.starts_with("http"))
+        .or(chart.home.filter(|h| !h.is_empty()));
+
+    InstalledPackage {
+        name,
+        version,
+        description: chart.description.filter(|d| !d.is_empty()),
+        url,
+        source: PackageSource::Helm,
+        licenses: chart
+            .annotations
+            .get("artifacthub.io/license")
+            .cloned()
+            .into_iter()
+            .collect(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_chart_labels() {
+        let cases = [
+            ("nginx-15.1.0", ("nginx", "15.1.0")),
+            ("cert-manager-v1.14.0", ("cert-manager", "v1.14.0")),
+            ("ingress-nginx-4.10.0", ("ingress-nginx", "4.10.0")),
+            ("app-1.0.0-rc.1", ("app", "1.0.0-rc.1")),
+            ("noversion", ("noversion", "unknown")),
+        ];
+        for (label, (name, version)) in cases {
+            assert_eq!(
+                split_chart(label),
+                (name.to_string(), version.to_string()),
+                "{label}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_list_dedupes_charts() {
+        let output = r#"[
+            {"name": "web", "namespace": "default", "revision": "3", "chart": "nginx-15.1.0", "app_version": "1.25.1"},
+            {"name": "web2", "namespace": "staging", "revision": "1", "chart": "nginx-15.1.0", "app_version": "1.25.1"},
+            {"name": "cm", "namespace": "cert-manager", "revision": "1", "chart": "cert-manager-v1.14.0", "app_version": "v1.14.0"}
+        ]"#;
+        let charts = parse_helm_list(output).unwrap();
+        assert_eq!(charts.len(), 2);
+        assert!(charts.contains(&("nginx".to_string(), "15.1.0".to_string())));
+        assert!(parse_helm_list("").unwrap().is_empty());
+        assert!(parse_helm_list("nope").is_err());
+    }
+
+    #[test]
+    fn search_picks_exact_chart_name() {
+        let output = r#"[
+            {"name": "bitnami/nginx-ingress-controller", "version": "11.3.0"},
+            {"name": "bitnami/nginx", "version": "15.1.0"}
+        ]"#;
+        assert_eq!(
+            find_repo_chart(output, "nginx").unwrap().as_deref(),
+            Some("bitnami/nginx")
+        );
+        assert_eq!(find_repo_chart("[]", "nginx").unwrap(), None);
+    }
+
+    #[test]
+    fn parse_chart_metadata() {
+        let content = "\
+apiVersion: v2
+name: nginx
+version: 15.1.0
+appVersion: 1.25.1
+description: NGINX Open Source is a web server.
+home: https://bitnami.com
+sources:
+  - https://github.com/bitnami/charts/tree/main/bitnami/nginx
+annotations:
+  artifacthub.io/license: Apache-2.0
+  category: Infrastructure
+maintainers:
+  - name: VMware, Inc.
+";
+        let chart = parse_chart_yaml(content).unwrap();
+        let pkg = build_package("nginx".to_string(), "15.1.0".to_string(), chart);
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/bitnami/charts/tree/main/bitnami/nginx")
+        );
+        assert_eq!(pkg.licenses, vec!["Apache-2.0"]);
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("NGINX Open Source is a web server.")
+        );
+        assert_eq!(pkg.source, PackageSource::Helm);
+    }
+
+    #[test]
+    fn unresolved_chart_has_no_metadata() {
+        let pkg = build_package(
+            "internal".to_string(),
+            "0.1.0".to_string(),
+            ChartMetadata::default(),
+        );
+        assert!(pkg.url.is_none());
+        assert!(pkg.licenses.is_empty());
+    }
+}
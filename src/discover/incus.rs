@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Project homepages of the distributions published on the public image
+/// servers, keyed by the lowercased `os` image property.
+const OS_HOMEPAGES: &[(&str, &str)] = &[
+    ("almalinux", "https://almalinux.org"),
+    ("alpine", "https://alpinelinux.org"),
+    ("alt", "https://www.altlinux.org"),
+    ("archlinux", "https://archlinux.org"),
+    ("centos", "https://www.centos.org"),
+    ("debian", "https://www.debian.org"),
+    ("devuan", "https://www.devuan.org"),
+    ("fedora", "https://fedoraproject.org"),
+    ("gentoo", "https://www.gentoo.org"),
+    ("kali", "https://www.kali.org"),
+    ("nixos", "https://nixos.org"),
+    ("opensuse", "https://www.opensuse.org"),
+    ("oracle", "https://www.oracle.com/linux"),
+    ("rockylinux", "https://rockylinux.org"),
+    ("ubuntu", "https://ubuntu.com"),
+    ("voidlinux", "https://voidlinux.org"),
+];
+
+/// Discovers Incus (and LXD) system container images and instances.
+///
+/// Runs `incus image list` and `incus list` with `--format json` (or the
+/// same commands through LXD's `lxc` client). Images are reported under
+/// their first alias, instances under their name; both are versioned by the
+/// image's release and described by the image description. The upstream URL
+/// is the homepage of the image's distribution, falling back to the image
+/// server it was downloaded from.
+pub struct IncusDiscoverer;
+
+impl Discoverer for IncusDiscoverer {
+    fn name(&self) -> &str {
+        "incus"
+    }
+
+    fn is_available(&self) -> bool {
+        client().is_some()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let client = client().context("Neither incus nor lxc is installed")?;
+
+        let images = run(client, &["image", "list", "--format", "json"])?;
+        let instances = run(client, &["list", "--format", "json"])?;
+
+        let mut packages = parse_image_list(&images)?;
+        packages.extend(parse_instance_list(&instances)?);
+        Ok(packages)
+    }
+}
+
+/// The CLI client to use: `incus`, or LXD's `lxc`.
+fn client() -> Option<&'static str> {
+    ["incus", "lxc"].into_iter().find(|bin| super::in_path(bin))
+}
+
+fn run(client: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(client)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {client} {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "{client} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).with_context(|| format!("{client} output is not valid UTF-8"))
+}
+
+#[derive(Deserialize)]
+struct Image {
+    fingerprint: String,
+    #[serde(default)]
+    aliases: Vec<ImageAlias>,
+    #[serde(default)]
+    properties: BTreeMap<String, String>,
+    update_source: Option<UpdateSource>,
+}
+
+#[derive(Deserialize)]
+struct ImageAlias {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct UpdateSource {
+    server: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Instance {
+    name: String,
+    #[serde(default)]
+    config: BTreeMap<String, String>,
+}
+
+/// Parse `incus image list --format json`.
+///
+/// ```json
+/// [{"fingerprint": "8f3c...", "aliases": [{"name": "debian/12"}],
+///   "properties": {"os": "Debian", "release": "bookworm", "description": "Debian bookworm amd64 (20240101_05:24)"},
+///   "update_source": {"alias": "debian/12", "server": "https://images.linuxcontainers.org"}}]
+/// ```
+fn parse_image_list(output: &str) -> Result<Vec<InstalledPackage>> {
+    let images: Vec<Image> =
+        serde_json::from_str(output).context("Failed to parse image list JSON")?;
+
+    Ok(images
+        .into_iter()
+        .map(|image| {
+            let name = image
+                .aliases
+                .into_iter()
+                .next()
+                .map(|a| a.name)
+                .unwrap_or_else(|| image.fingerprint.chars().take(12).collect());
+            let server = image.update_source.and_then(|s| s.server);
+            build_package(name, |key| image.properties.get(key).cloned(), server)
+        })
+        .collect())
+}
+
+/// Parse `incus list --format json`; image metadata is copied into each
+/// instance's config as `image.*` keys.
+fn parse_instance_list(output: &str) -> Result<Vec<InstalledPackage>> {
+    let instances: Vec<Instance> =
+        serde_json::from_str(output).context("Failed to parse instance list JSON")?;
+
+    Ok(instances
+        .into_iter()
+        .map(|instance| {
+            let config = instance.config;
+            build_package(
+                instance.name,
+                |key| config.get(&format!("image.{key}")).cloned(),
+                None,
+            )
+        })
+        .collect())
+}
+
+fn build_package(
+    name: String,
+    property: impl Fn(&str) -> Option<String>,
+    server: Option<String>,
+) -> InstalledPackage {
+    let non_empty = |s: Option<String>| s.filter(|s| !s.is_empty());
+
+    let os_homepage = non_empty(property("os")).and_then(|os| {
+        let os = os.to_lowercase();
+        OS_HOMEPAGES
+            .iter()
+            .find(|(known, _)| *known == os)
+            .map(|(_, url)| url.to_string())
+    });
+
+    InstalledPackage {
+        name,
+        version: non_empty(property("release"))
+            .or_else(|| non_empty(property("serial")))
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: non_empty(property("description")),
+        url: os_homepage.or(non_empty(server)),
+        source: PackageSource::Incus,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_images() {
+        let output = r#"[
+            {
+                "fingerprint": "8f3c1a2b3c4d5e6f",
+                "aliases": [{"name": "debian/12", "description": ""}],
+                "properties": {
+                    "os": "Debian",
+                    "release": "bookworm",
+                    "description": "Debian bookworm amd64 (20240101_05:24)"
+                },
+                "update_source": {"alias": "debian/12", "server": "https://images.linuxcontainers.org", "protocol": "simplestreams"}
+            },
+            {
+                "fingerprint": "0123456789abcdef0123",
+                "aliases": [],
+                "properties": {"os": "MyDistro", "serial": "20240601"},
+                "update_source": {"server": "https://images.example.org"}
+            }
+        ]"#;
+        let packages = parse_image_list(output).unwrap();
+        assert_eq!(packages.len(), 2);
+
+        assert_eq!(packages[0].name, "debian/12");
+        assert_eq!(packages[0].version, "bookworm");
+        assert_eq!(packages[0].url.as_deref(), Some("https://www.debian.org"));
+        assert_eq!(packages[0].source, PackageSource::Incus);
+
+        assert_eq!(packages[1].name, "0123456789ab");
+        assert_eq!(packages[1].version, "20240601");
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://images.example.org")
+        );
+    }
+
+    #[test]
+    fn parse_instances() {
+        let output = r#"[
+            {
+                "name": "web",
+                "status": "Running",
+                "type": "container",
+                "config": {
+                    "image.os": "Alpine",
+                    "image.release": "3.20",
+                    "image.description": "Alpine 3.20 amd64 (20240601_13:00)",
+                    "volatile.base_image": "abc"
+                }
+            },
+            {"name": "bare", "status": "Stopped", "config": {}}
+        ]"#;
+        let packages = parse_instance_list(output).unwrap();
+        assert_eq!(packages[0].name, "web");
+        assert_eq!(packages[0].version, "3.20");
+        assert_eq!(packages[0].url.as_deref(), Some("https://alpinelinux.org"));
+        assert_eq!(packages[1].version, "unknown");
+        assert!(packages[1].url.is_none());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_image_list("nope").is_err());
+        assert!(parse_instance_list("{}").is_err());
+    }
+}
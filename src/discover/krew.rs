@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers kubectl plugins installed with krew.
+///
+/// Runs `kubectl krew list` for the installed plugin names, then reads each
+/// plugin's install receipt (`$KREW_ROOT/receipts/<name>.yaml`), which is a
+/// copy of the plugin manifest with its version, homepage, and short
+/// description.
+pub struct KrewDiscoverer;
+
+impl Discoverer for KrewDiscoverer {
+    fn name(&self) -> &str {
+        "krew"
+    }
+
+    fn is_available(&self) -> bool {
+        krew_root().is_some_and(|root| root.join("receipts").is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let receipts = krew_root()
+            .context("Could not determine krew root")?
+            .join("receipts");
+
+        let output = Command::new("kubectl")
+            .args(["krew", "list"])
+            .output()
+            .context("Failed to run kubectl krew list")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "kubectl krew list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("kubectl krew list output is not valid UTF-8")?;
+        let plugins = parse_krew_list(&stdout);
+
+        let pb = ProgressBar::new(plugins.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (name, version) in plugins {
+            let receipt = read_receipt(&receipts, &name, &pb);
+            packages.push(build_package(name, version, receipt));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// `$KREW_ROOT`, defaulting to `~/.krew`.
+fn krew_root() -> Option<PathBuf> {
+    std::env::var_os("KREW_ROOT")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".krew")))
+}
+
+/// Parse `kubectl krew list` into `(name, version)` pairs.
+///
+/// ```text
+/// PLUGIN  VERSION
+/// ctx     v0.9.5
+/// myindex/foo v1.0.0
+/// ```
+///
+/// When not writing to a terminal, krew prints only the names. Plugins from
+/// custom indexes are prefixed with the index name, which is dropped.
+fn parse_krew_list(output: &str) -> Vec<(String, Option<String>)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            if name == "PLUGIN" {
+                return None;
+            }
+            let name = name.rsplit('/').next().unwrap_or(name);
+            Some((name.to_string(), fields.next().map(String::from)))
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Receipt {
+    #[serde(default)]
+    spec: ReceiptSpec,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceiptSpec {
+    version: Option<String>,
+    homepage: Option<String>,
+    short_description: Option<String>,
+}
+
+fn read_receipt(dir: &Path, name: &str, pb: &ProgressBar) -> Receipt {
+    let path = dir.join(format!("{name}.yaml"));
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Receipt::default();
+    };
+    parse_receipt(&content).unwrap_or_else(|e| {
+        pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+        Receipt::default()
+    })
+}
+
+/// Parse a krew receipt (a plugin manifest plus install status).
+///
+/// ```yaml
+/// apiVersion: krew.googlecontainertools.github.com/v1alpha2
+/// kind: Plugin
+/// metadata:
+///   name: ctx
+/// spec:
+///   version: v0.9.5
+///   homepage: https://github.com/ahmetb/kubectx
+///   shortDescription: Switch between contexts in your kubeconfig
+/// ```
+fn parse_receipt(content: &str) -> Result<Receipt> {
+    serde_yaml::from_str(content).context("Invalid krew receipt")
+}
+
+/// The receipt's version wins over the list's, as it is always present.
+fn build_package(name: String, version: Option<String>, receipt: Receipt) -> InstalledPackage {
+    let spec = receipt.spec;
+    InstalledPackage {
+        name,
+        version: spec
+            .version
+            .or(version)
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: spec.short_description.filter(|d| !d.is_empty()),
+        url: spec.homepage.filter(|u| !u.is_empty()),
+        source: PackageSource::Krew,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_with_and_without_versions() {
+        let output = "PLUGIN  VERSION\nctx     v0.9.5\nmyindex/foo v1.0.0\n";
+        assert_eq!(
+            parse_krew_list(output),
+            vec![
+                ("ctx".to_string(), Some("v0.9.5".to_string())),
+                ("foo".to_string(), Some("v1.0.0".to_string())),
+            ]
+        );
+        assert_eq!(
+            parse_krew_list("ctx\nns\n"),
+            vec![("ctx".to_string(), None), ("ns".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn parse_receipt_manifest() {
+        let content = "\
+apiVersion: krew.googlecontainertools.github.com/v1alpha2
+kind: Plugin
+metadata:
+  name: ctx
+spec:
+  version: v0.9.5
+  homepage: https://github.com/ahmetb/kubectx
+  shortDescription: Switch between contexts in your kubeconfig
+  platforms:
+  - uri: https://github.com/ahmetb/kubectx/releases/download/v0.9.5/kubectx.tar.gz
+    bin: kubectx
+status:
+  source:
+    name: default
+";
+        let receipt = parse_receipt(content).unwrap();
+        let pkg = build_package("ctx".to_string(), None, receipt);
+        assert_eq!(pkg.version, "v0.9.5");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/ahmetb/kubectx")
+        );
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("Switch between contexts in your kubeconfig")
+        );
+        assert_eq!(pkg.source, PackageSource::Krew);
+    }
+
+    #[test]
+    fn missing_receipt_uses_list_version() {
+        let pkg = build_package(
+            "ns".to_string(),
+            Some("v0.9.5".to_string()),
+            Receipt::default(),
+        );
+        assert_eq!(pkg.version, "v0.9.5");
+        assert!(pkg.url.is_none());
+    }
+
+    #[test]
+    fn parse_invalid_receipt() {
+        assert!(parse_receipt("spec: [unclosed").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeSet, HashMap};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::Value;
+
+use super::oci;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Prints the image of every container and init container, one per line.
+const IMAGES_JSONPATH: &str = concat!(
+    r#"jsonpath={range .items[*]}"#,
+    r#"{range .spec.containers[*]}{.image}{"\n"}{end}"#,
+    r#"{range .spec.initContainers[*]}{.image}{"\n"}{end}"#,
+    r#"{end}"#,
+);
+
+/// Discovers container images running in the current Kubernetes context.
+///
+/// Opt-in via `[discover] kubernetes = true`, since it talks to a (possibly
+/// remote) cluster. Pod images are listed with `kubectl get pods` across all
+/// namespaces, falling back to the context's namespace when listing cluster
+/// wide is not permitted. The images usually are not present locally, so
+/// their OCI labels are read from the local Docker or Podman store when
+/// available and otherwise fetched from the registry with `skopeo`; the
+/// labels then go through the same pipeline as the docker/podman backends.
+pub struct KubernetesDiscoverer {
+    /// Whether cluster discovery is enabled in the config.
+    pub enabled: bool,
+}
+
+impl Discoverer for KubernetesDiscoverer {
+    fn name(&self) -> &str {
+        "kubernetes"
+    }
+
+    fn is_available(&self) -> bool {
+        self.enabled && super::in_path("kubectl")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let stdout = run_kubectl(&["get", "pods", "--all-namespaces", "-o", IMAGES_JSONPATH])
+            .or_else(|_| run_kubectl(&["get", "pods", "-o", IMAGES_JSONPATH]))?;
+        let images = parse_image_list(&stdout);
+
+        let pb = ProgressBar::new(images.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let packages = images
+            .iter()
+            .map(|image| {
+                let (name, tag) = split_image_reference(image);
+                let labels = fetch_image_labels(image);
+                pb.inc(1);
+                oci::build_package_from_labels(&name, &tag, &labels, PackageSource::Kubernetes)
+            })
+            .collect();
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run a kubectl command and return its stdout.
+fn run_kubectl(args: &[&str]) -> Result<String> {
+    let output = Command::new("kubectl")
+        .args(args)
+        .output()
+        .context("Failed to run kubectl get pods")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "kubectl get pods failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("kubectl output is not valid UTF-8")
+}
+
+/// Collect the distinct image references printed by [`IMAGES_JSONPATH`].
+fn parse_image_list(output: &str) -> BTreeSet<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Split an image reference into repository and tag.
+///
+/// `registry:5000/app:1.2@sha256:...` → (`registry:5000/app`, `1.2`). An
+/// untagged reference is `latest`; one pinned only by digest has no usable
+/// tag and reports `<none>`.
+fn split_image_reference(image: &str) -> (String, String) {
+    let (reference, digest) = match image.split_once('@') {
+        Some((reference, _)) => (reference, true),
+        None => (image, false),
+    };
+    let name_start = reference.rfind('/').map_or(0, |i| i + 1);
+    match reference[name_start..].rsplit_once(':') {
+        Some((_, tag)) => (
+            reference[..reference.len() - tag.len() - 1].to_string(),
+            tag.to_string(),
+        ),
+        None if digest => (reference.to_string(), "<none>".to_string()),
+        None => (reference.to_string(), "latest".to_string()),
+    }
+}
+
+/// Read an image's OCI labels from a local runtime, or from its registry.
+///
+/// Returns an empty map if no source has the image.
+fn fetch_image_labels(image: &str) -> HashMap<String, String> {
+    for runtime in ["docker", "podman"] {
+        if !super::in_path(runtime) {
+            continue;
+        }
+        let output = Command::new(runtime)
+            .args([
+                "image",
+                "inspect",
+                "--format",
+                "{{json .Config.Labels}}",
+                image,
+            ])
+            .output();
+        if let Ok(output) = output
+            && output.status.success()
+            && let Ok(labels) = oci::parse_labels(&String::from_utf8_lossy(&output.stdout))
+        {
+            return labels;
+        }
+    }
+
+    if super::in_path("skopeo") {
+        let output = Command::new("skopeo")
+            .args(["inspect", "--no-tags", &format!("docker://{image}")])
+            .output();
+        if let Ok(output) = output
+            && output.status.success()
+        {
+            return parse_skopeo_labels(&String::from_utf8_lossy(&output.stdout));
+        }
+    }
+
+    HashMap::new()
+}
+
+/// Extract the `Labels` object from `skopeo inspect` output.
+fn parse_skopeo_labels(output: &str) -> HashMap<String, String> {
+    serde_json::from_str::<Value>(output)
+        .ok()
+        .and_then(|v| v.get("Labels").map(Value::to_string))
+        .and_then(|labels| oci::parse_labels(&labels).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_list_is_deduplicated() {
+        let output = "nginx:1.25\nregistry.k8s.io/pause:3.9\nnginx:1.25\n\n";
+        let images = parse_image_list(output);
+        assert_eq!(images.len(), 2);
+        assert!(images.contains("registry.k8s.io/pause:3.9"));
+    }
+
+    #[test]
+    fn split_references() {
+        let cases = [
+            ("nginx", ("nginx", "latest")),
+            ("nginx:1.25", ("nginx", "1.25")),
+            ("ghcr.io/owner/app:v2", ("ghcr.io/owner/app", "v2")),
+            ("localhost:5000/app", ("localhost:5000/app", "latest")),
+            ("localhost:5000/app:dev", ("localhost:5000/app", "dev")),
+            ("quay.io/app@sha256:abcd", ("quay.io/app", "<none>")),
+            ("quay.io/app:1.0@sha256:abcd", ("quay.io/app", "1.0")),
+        ];
+        for (image, (name, tag)) in cases {
+            assert_eq!(
+                split_image_reference(image),
+                (name.to_string(), tag.to_string()),
+                "{image}"
+            );
+        }
+    }
+
+    #[test]
+    fn skopeo_labels() {
+        let output = r#"{
+            "Name": "ghcr.io/owner/app",
+            "Digest": "sha256:abcd",
+            "Labels": {"org.opencontainers.image.source": "https://github.com/owner/app"}
+        }"#;
+        let labels = parse_skopeo_labels(output);
+        let pkg = oci::build_package_from_labels(
+            "ghcr.io/owner/app",
+            "<none>",
+            &labels,
+            PackageSource::Kubernetes,
+        );
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/owner/app"));
+        assert_eq!(pkg.version, "unknown");
+
+        assert!(parse_skopeo_labels(r#"{"Labels": null}"#).is_empty());
+        assert!(parse_skopeo_labels("garbage").is_empty());
+    }
+}
@@ -0,0 +1,530 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::projects;
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use super::{go, python_lock};
+
+/// The lockfiles this discoverer understands.
+const LOCKFILE_NAMES: [&str; 4] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "go.sum",
+    "requirements.txt",
+];
+
+/// The crates.io index as spelled in `Cargo.lock` sources.
+const CRATES_IO_SOURCES: &[&str] = &[
+    "registry+https://github.com/rust-lang/crates.io-index",
+    "sparse+https://index.crates.io/",
+];
+
+/// Discovers the direct dependencies of the user's own Rust, JavaScript, Go,
+/// and Python projects.
+///
+/// Opt-in: only runs when `[discover] project_dirs` is configured. Those
+/// directories are searched for `Cargo.lock`, `package-lock.json`, `go.sum`,
+/// and `requirements.txt` files. Only the dependencies a project declares
+/// itself are reported, not the whole transitive tree: the workspace
+/// members' dependencies in `Cargo.lock`, the root package's in
+/// `package-lock.json`, and the non-`// indirect` requirements of the
+/// `go.mod` next to `go.sum`. Each one gets its registry page (crates.io,
+/// npm, PyPI) or repository as its URL.
+pub struct LockfileDiscoverer {
+    /// Development directories to search for lockfiles.
+    pub project_dirs: Vec<PathBuf>,
+}
+
+impl Discoverer for LockfileDiscoverer {
+    fn name(&self) -> &str {
+        "lockfile"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.project_dirs.is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let lockfiles = projects::find_files(&self.project_dirs, &LOCKFILE_NAMES);
+
+        let pb = ProgressBar::new(lockfiles.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} lockfiles")
+                .unwrap(),
+        );
+
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+        for path in &lockfiles {
+            match parse_lockfile(path) {
+                Ok(packages) => {
+                    for pkg in packages {
+                        by_key
+                            .entry((pkg.name.clone(), pkg.version.clone()))
+                            .or_insert(pkg);
+                    }
+                }
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+fn parse_lockfile(path: &Path) -> Result<Vec<InstalledPackage>> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+    };
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    match file_name {
+        "Cargo.lock" => parse_cargo_lock(&read(path)?),
+        "package-lock.json" => parse_package_lock(&read(path)?),
+        // go.sum lists every module in the build graph; which ones are
+        // direct is only recorded in go.mod.
+        "go.sum" => parse_go_mod(&read(&path.with_file_name("go.mod"))?),
+        _ => Ok(parse_requirements(&read(path)?)),
+    }
+}
+
+fn locked_package(name: String, version: Option<String>, url: Option<String>) -> InstalledPackage {
+    InstalledPackage {
+        name,
+        version: version
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: None,
+        url,
+        source: PackageSource::Lockfile,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[derive(Deserialize)]
+struct CargoLock {
+    #[serde(default)]
+    package: Vec<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// Parse a `Cargo.lock` file.
+///
+/// Packages without a `source` are the workspace's own crates; their
+/// `dependencies` entries (`name`, `name version`, or `name version
+/// (source)` when ambiguous) are the direct dependencies. Path dependencies
+/// are skipped, git dependencies use their repository URL.
+fn parse_cargo_lock(content: &str) -> Result<Vec<InstalledPackage>> {
+    let lock: CargoLock = toml::from_str(content).context("Invalid Cargo.lock")?;
+
+    let direct: BTreeSet<(&str, Option<&str>)> = lock
+        .package
+        .iter()
+        .filter(|p| p.source.is_none())
+        .flat_map(|p| &p.dependencies)
+        .map(|dep| {
+            let mut parts = dep.split(' ');
+            (parts.next().unwrap_or_default(), parts.next())
+        })
+        .collect();
+
+    Ok(lock
+        .package
+        .iter()
+        .filter(|p| {
+            direct.contains(&(p.name.as_str(), None))
+                || direct.contains(&(p.name.as_str(), Some(p.version.as_str())))
+        })
+        .filter_map(|p| {
+            let source = p.source.as_deref()?;
+            let url = if CRATES_IO_SOURCES.contains(&source) {
+                Some(format!("https://crates.io/crates/{}", p.name))
+            } else if let Some(git) = source.strip_prefix("git+") {
+                let git = git.split(['?', '#']).next().unwrap_or(git);
+                Some(git.strip_suffix(".git").unwrap_or(git).to_string())
+            } else {
+                None
+            };
+            Some(locked_package(p.name.clone(), Some(p.version.clone()), url))
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct PackageLock {
+    #[serde(rename = "lockfileVersion", default)]
+    lockfile_version: u32,
+    #[serde(default)]
+    packages: BTreeMap<String, LockedNpmPackage>,
+}
+
+#[derive(Default, Deserialize)]
+struct LockedNpmPackage {
+    version: Option<String>,
+    #[serde(default)]
+    link: bool,
+    #[serde(default)]
+    dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(rename = "devDependencies", default)]
+    dev_dependencies: BTreeMap<String, serde_json::Value>,
+    #[serde(rename = "optionalDependencies", default)]
+    optional_dependencies: BTreeMap<String, serde_json::Value>,
+}
+
+/// Parse a `package-lock.json` file (lockfile version 2 or 3, npm 7+).
+///
+/// The root package is the `""` entry of `packages`; each dependency it
+/// declares is resolved from its `node_modules/<name>` entry. Workspace
+/// links are skipped.
+fn parse_package_lock(content: &str) -> Result<Vec<InstalledPackage>> {
+    let lock: PackageLock = serde_json::from_str(content).context("Invalid package-lock.json")?;
+    if lock.lockfile_version < 2 {
+        anyhow::bail!(
+            "lockfileVersion {} is not supported, regenerate it with npm 7 or later",
+            lock.lockfile_version
+        );
+    }
+
+    let Some(root) = lock.packages.get("") else {
+        return Ok(Vec::new());
+    };
+
+    Ok(root
+        .dependencies
+        .keys()
+        .chain(root.dev_dependencies.keys())
+        .chain(root.optional_dependencies.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|name| {
+            let entry = lock.packages.get(&format!("node_modules/{name}"))?;
+            if entry.link {
+                return None;
+            }
+            Some(locked_package(
+                name.clone(),
+                entry.version.clone(),
+                Some(format!("https://www.npmjs.com/package/{name}")),
+            ))
+        })
+        .collect())
+}
+
+/// Parse the direct requirements of a `go.mod` file.
+///
+/// Both `require path version` lines and `require ( ... )` blocks are
+/// read; requirements marked `// indirect` are skipped.
+fn parse_go_mod(content: &str) -> Result<Vec<InstalledPackage>> {
+    let mut packages = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        let requirement = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+
+        let (requirement, comment) = requirement.split_once("//").unwrap_or((requirement, ""));
+        if comment.trim() == "indirect" {
+            continue;
+        }
+        let mut fields = requirement.split_whitespace();
+        let (Some(module), Some(version)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        packages.push(locked_package(
+            module.to_string(),
+            Some(version.to_string()),
+            Some(go::module_url(module)),
+        ));
+    }
+
+    if !content
+        .lines()
+        .any(|l| l.trim_start().starts_with("module "))
+    {
+        anyhow::bail!("Invalid go.mod: no module directive");
+    }
+    Ok(packages)
+}
+
+/// Parse a pip `requirements.txt` file.
+///
+/// Every requirement in the file is treated as direct. Only `==` pins give
+/// a version; options (`-r`, `-e`, `--index-url`, ...), local paths, and
+/// bare URLs are skipped, while `name @ url` direct references use the URL.
+fn parse_requirements(content: &str) -> Vec<InstalledPackage> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split(" #").next().unwrap_or(line).trim();
+            if line.is_empty() || line.starts_with(['#', '-', '.', '/']) || line.starts_with("http")
+            {
+                return None;
+            }
+            let line = line.split(';').next().unwrap_or(line).trim();
+
+            if let Some((name, url)) = line.split_once(" @ ") {
+                let name = name.split('[').next().unwrap_or(name).trim();
+                return Some(locked_package(
+                    name.to_string(),
+                    None,
+                    Some(url.trim().to_string()),
+                ));
+            }
+
+            let name_end = line
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(line.len());
+            let name = &line[..name_end];
+            if name.is_empty() {
+                return None;
+            }
+            let spec = line[name_end..].trim_start();
+            let spec = match spec.strip_prefix('[') {
+                Some(rest) => rest.split_once(']').map_or("", |(_, s)| s.trim_start()),
+                None => spec,
+            };
+            let version = spec
+                .strip_prefix("===")
+                .or_else(|| spec.strip_prefix("=="))
+                .map(|v| v.split(',').next().unwrap_or(v).trim().to_string());
+
+            Some(locked_package(
+                name.to_string(),
+                version,
+                Some(python_lock::pypi_url(name)),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_lock_direct_dependencies() {
+        let content = r#"
+version = 4
+
+[[package]]
+name = "myapp"
+version = "0.1.0"
+dependencies = [
+ "anyhow",
+ "helper",
+ "mygit",
+ "syn 2.0.66",
+]
+
+[[package]]
+name = "helper"
+version = "0.1.0"
+
+[[package]]
+name = "anyhow"
+version = "1.0.86"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc"
+
+[[package]]
+name = "mygit"
+version = "0.2.0"
+source = "git+https://github.com/example/mygit.git?branch=main#0123abcd"
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "2.0.66"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["proc-macro2"]
+
+[[package]]
+name = "proc-macro2"
+version = "1.0.85"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+        let packages = parse_cargo_lock(content).unwrap();
+        let ids: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![("anyhow", "1.0.86"), ("mygit", "0.2.0"), ("syn", "2.0.66")]
+        );
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://crates.io/crates/anyhow")
+        );
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/example/mygit")
+        );
+        assert_eq!(packages[0].source, PackageSource::Lockfile);
+    }
+
+    #[test]
+    fn package_lock_direct_dependencies() {
+        let content = r#"{
+            "name": "web",
+            "lockfileVersion": 3,
+            "packages": {
+                "": {
+                    "name": "web",
+                    "dependencies": {"react": "^18.3.1", "shared": "file:../shared"},
+                    "devDependencies": {"vite": "^5.3.0"}
+                },
+                "node_modules/react": {"version": "18.3.1", "resolved": "https://registry.npmjs.org/react/-/react-18.3.1.tgz"},
+                "node_modules/loose-envify": {"version": "1.4.0"},
+                "node_modules/shared": {"resolved": "../shared", "link": true},
+                "node_modules/vite": {"version": "5.3.1", "dev": true}
+            }
+        }"#;
+        let packages = parse_package_lock(content).unwrap();
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["react", "vite"]);
+        assert_eq!(packages[0].version, "18.3.1");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://www.npmjs.com/package/react")
+        );
+
+        assert!(parse_package_lock(r#"{"lockfileVersion": 1, "dependencies": {}}"#).is_err());
+    }
+
+    #[test]
+    fn go_mod_direct_requirements() {
+        let content = "\
+module example.com/app
+
+go 1.22
+
+require github.com/spf13/cobra v1.8.1
+
+require (
+\tgolang.org/x/sync v0.7.0
+\tgithub.com/inconshreveable/mousetrap v1.1.0 // indirect
+)
+";
+        let packages = parse_go_mod(content).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "github.com/spf13/cobra");
+        assert_eq!(packages[0].version, "v1.8.1");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/spf13/cobra")
+        );
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/golang/sync")
+        );
+
+        assert!(parse_go_mod("not a go.mod").is_err());
+    }
+
+    #[test]
+    fn requirements_txt_entries() {
+        let content = "\
+# core
+requests==2.32.3  # pinned
+Django>=5.0,<6
+uvicorn[standard]==0.30.1 ; python_version >= \"3.8\"
+mylib @ git+https://github.com/example/mylib.git
+-r dev-requirements.txt
+-e .
+./vendor/pkg.whl
+https://example.com/pkg.tar.gz
+";
+        let packages = parse_requirements(content);
+        let ids: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("requests", "2.32.3"),
+                ("Django", "unknown"),
+                ("uvicorn", "0.30.1"),
+                ("mylib", "unknown"),
+            ]
+        );
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://pypi.org/project/requests/")
+        );
+        assert_eq!(
+            packages[3].url.as_deref(),
+            Some("git+https://github.com/example/mylib.git")
+        );
+    }
+
+    #[test]
+    fn discover_reads_go_mod_next_to_go_sum() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("tool");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("go.sum"), "").unwrap();
+        fs::write(
+            project.join("go.mod"),
+            "module example.com/tool\n\nrequire github.com/BurntSushi/toml v1.4.0\n",
+        )
+        .unwrap();
+        fs::write(project.join("requirements.txt"), "toml==0.10.2\n").unwrap();
+
+        let discoverer = LockfileDiscoverer {
+            project_dirs: vec![tmp.path().to_path_buf()],
+        };
+        assert!(discoverer.is_available());
+
+        let packages = discoverer.discover().unwrap();
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["github.com/BurntSushi/toml", "toml"]);
+    }
+}
@@ -7,7 +7,7 @@ use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::Deserialize;
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
 
 /// Discovers tools installed via mise (dev tool version manager).
 ///
@@ -128,6 +128,9 @@ fn parse_mise_output(output: &str) -> Result<Vec<InstalledPackage>> {
                 url: None,
                 source: PackageSource::Mise,
                 licenses: Vec::new(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             });
             pb.inc(1);
         }
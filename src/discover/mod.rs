@@ -20,22 +20,69 @@
 //!
 //! See [`pacman::PacmanDiscoverer`] for a reference implementation.
 
-mod apt;
+mod apk;
+pub(crate) mod apt;
+mod aur;
 mod brew;
-mod dnf;
+mod browser;
+mod bun;
+mod cargo;
+mod choco;
+mod composer;
+mod deno;
+mod distrobox;
+pub(crate) mod dnf;
 mod docker;
+mod dotnet;
+mod eopkg;
+mod fdroid;
 mod flatpak;
+mod fonts;
+mod freebsd;
+mod fwupd;
+mod games;
+mod gem;
+mod gh;
+mod ghc;
+mod git;
+mod go;
+mod helm;
+mod incus;
+mod krew;
+mod kubernetes;
+mod lockfile;
 mod mise;
+mod msys2;
 mod nix;
+mod npm;
+mod nuget;
 mod oci;
-mod pacman;
+mod opam;
+pub(crate) mod pacman;
+mod pip;
+mod pipx;
+mod plasma;
+mod pnpm;
 mod podman;
+mod projects;
+mod python;
+mod python_lock;
+mod rustup;
+mod scoop;
+mod shell;
 mod snap;
+mod termux;
+mod uv;
+mod vim;
+mod vscode;
+mod winget;
+mod yarn;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
+use crate::project::FundingChannel;
 
 /// A discovered package installed on the system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +120,66 @@ pub struct InstalledPackage {
     /// not available, the raw license strings reported by the package manager
     /// are stored instead.
     pub licenses: Vec<String>,
+    /// Funding channels declared in the package's own metadata.
+    ///
+    /// Some ecosystems (e.g. npm's `funding` field in `package.json`) let
+    /// authors advertise donation links directly. These are seeded into
+    /// [`UpstreamProject::funding`](crate::project::UpstreamProject::funding)
+    /// so they show up without any network enrichment. Empty for backends
+    /// that do not expose such metadata.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub funding: Vec<FundingChannel>,
+    /// Whether the user asked for this package or it was pulled in as a
+    /// dependency.
+    ///
+    /// Lets reports and budget allocation weight deliberately chosen software
+    /// above transitive dependencies. [`InstallReason::Unknown`] for backends
+    /// that do not track the install reason.
+    #[serde(default, skip_serializing_if = "InstallReason::is_unknown")]
+    pub install_reason: InstallReason,
+    /// Who maintains the package in the repository it comes from.
+    ///
+    /// Lets reports single out packages nobody looks after, such as orphaned
+    /// AUR packages. `None` for backends that do not expose a maintainer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintainer: Option<Maintainer>,
+}
+
+/// Who maintains a package in the repository it comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Maintainer {
+    /// Maintained by this account.
+    Account(String),
+    /// Left without a maintainer.
+    Orphaned,
+}
+
+/// Why a package is installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InstallReason {
+    /// Installed deliberately by the user.
+    Explicit,
+    /// Installed to satisfy another package's dependency.
+    Dependency,
+    /// The package manager does not record why the package is installed.
+    #[default]
+    Unknown,
+}
+
+impl InstallReason {
+    pub fn is_unknown(&self) -> bool {
+        *self == InstallReason::Unknown
+    }
+}
+
+impl std::fmt::Display for InstallReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstallReason::Explicit => write!(f, "explicit"),
+            InstallReason::Dependency => write!(f, "dependency"),
+            InstallReason::Unknown => write!(f, "unknown"),
+        }
+    }
 }
 
 /// The package manager that installed a package.
@@ -88,6 +195,48 @@ pub enum PackageSource {
     Mise,
     Docker,
     Podman,
+    Cargo,
+    Pipx,
+    Pip,
+    Npm,
+    Go,
+    Gem,
+    Aur,
+    Apk,
+    Eopkg,
+    FreeBsdPkg,
+    Winget,
+    Scoop,
+    Choco,
+    Msys2,
+    Termux,
+    Rustup,
+    Uv,
+    PythonLockfile,
+    Deno,
+    Bun,
+    Pnpm,
+    Yarn,
+    Dotnet,
+    Composer,
+    Opam,
+    GhcPkg,
+    Vscode,
+    BrowserExtension,
+    Plasma,
+    VimPlugin,
+    ShellPlugin,
+    GhExtension,
+    Krew,
+    Helm,
+    Kubernetes,
+    Distrobox,
+    Incus,
+    Fwupd,
+    Font,
+    Game,
+    Lockfile,
+    Fdroid,
 }
 
 impl std::fmt::Display for PackageSource {
@@ -103,6 +252,48 @@ impl std::fmt::Display for PackageSource {
             PackageSource::Mise => write!(f, "mise"),
             PackageSource::Docker => write!(f, "docker"),
             PackageSource::Podman => write!(f, "podman"),
+            PackageSource::Cargo => write!(f, "cargo"),
+            PackageSource::Pipx => write!(f, "pipx"),
+            PackageSource::Pip => write!(f, "pip"),
+            PackageSource::Npm => write!(f, "npm"),
+            PackageSource::Go => write!(f, "go"),
+            PackageSource::Gem => write!(f, "gem"),
+            PackageSource::Aur => write!(f, "aur"),
+            PackageSource::Apk => write!(f, "apk"),
+            PackageSource::Eopkg => write!(f, "eopkg"),
+            PackageSource::FreeBsdPkg => write!(f, "pkg"),
+            PackageSource::Winget => write!(f, "winget"),
+            PackageSource::Scoop => write!(f, "scoop"),
+            PackageSource::Choco => write!(f, "choco"),
+            PackageSource::Msys2 => write!(f, "msys2"),
+            PackageSource::Termux => write!(f, "termux"),
+            PackageSource::Rustup => write!(f, "rustup"),
+            PackageSource::Uv => write!(f, "uv"),
+            PackageSource::PythonLockfile => write!(f, "python-lockfile"),
+            PackageSource::Deno => write!(f, "deno"),
+            PackageSource::Bun => write!(f, "bun"),
+            PackageSource::Pnpm => write!(f, "pnpm"),
+            PackageSource::Yarn => write!(f, "yarn"),
+            PackageSource::Dotnet => write!(f, "dotnet"),
+            PackageSource::Composer => write!(f, "composer"),
+            PackageSource::Opam => write!(f, "opam"),
+            PackageSource::GhcPkg => write!(f, "ghc-pkg"),
+            PackageSource::Vscode => write!(f, "vscode"),
+            PackageSource::BrowserExtension => write!(f, "browser-extension"),
+            PackageSource::Plasma => write!(f, "plasma"),
+            PackageSource::VimPlugin => write!(f, "vim-plugin"),
+            PackageSource::ShellPlugin => write!(f, "shell-plugin"),
+            PackageSource::GhExtension => write!(f, "gh-extension"),
+            PackageSource::Krew => write!(f, "krew"),
+            PackageSource::Helm => write!(f, "helm"),
+            PackageSource::Kubernetes => write!(f, "kubernetes"),
+            PackageSource::Distrobox => write!(f, "distrobox"),
+            PackageSource::Incus => write!(f, "incus"),
+            PackageSource::Fwupd => write!(f, "fwupd"),
+            PackageSource::Font => write!(f, "font"),
+            PackageSource::Game => write!(f, "game"),
+            PackageSource::Lockfile => write!(f, "lockfile"),
+            PackageSource::Fdroid => write!(f, "fdroid"),
         }
     }
 }
@@ -160,18 +351,80 @@ pub trait Discoverer {
 /// entry to the `candidates` vector below. The new backend will automatically
 /// be included whenever its [`is_available()`](Discoverer::is_available)
 /// check passes.
-pub fn active_discoverers(_config: &Config) -> Vec<Box<dyn Discoverer>> {
+pub fn active_discoverers(config: &Config) -> Vec<Box<dyn Discoverer>> {
     let candidates: Vec<Box<dyn Discoverer>> = vec![
         Box::new(apt::AptDiscoverer),
         Box::new(brew::BrewDiscoverer),
         Box::new(dnf::DnfDiscoverer),
         Box::new(pacman::PacmanDiscoverer),
+        Box::new(aur::AurDiscoverer {
+            query_rpc: config.enrich && !config.offline,
+        }),
         Box::new(flatpak::FlatpakDiscoverer),
         Box::new(snap::SnapDiscoverer),
         Box::new(nix::NixDiscoverer),
         Box::new(mise::MiseDiscoverer),
-        Box::new(docker::DockerDiscoverer),
-        Box::new(podman::PodmanDiscoverer),
+        Box::new(docker::DockerDiscoverer {
+            query_registry: config.enrich && !config.offline,
+        }),
+        Box::new(podman::PodmanDiscoverer {
+            query_registry: config.enrich && !config.offline,
+        }),
+        Box::new(cargo::CargoDiscoverer),
+        Box::new(pipx::PipxDiscoverer),
+        Box::new(pip::PipDiscoverer),
+        Box::new(npm::NpmDiscoverer),
+        Box::new(go::GoDiscoverer),
+        Box::new(gem::GemDiscoverer),
+        Box::new(apk::ApkDiscoverer),
+        Box::new(eopkg::EopkgDiscoverer),
+        Box::new(freebsd::FreeBsdPkgDiscoverer),
+        Box::new(winget::WingetDiscoverer),
+        Box::new(scoop::ScoopDiscoverer),
+        Box::new(choco::ChocoDiscoverer),
+        Box::new(msys2::Msys2Discoverer),
+        Box::new(termux::TermuxDiscoverer),
+        Box::new(rustup::RustupDiscoverer),
+        Box::new(uv::UvDiscoverer),
+        Box::new(python_lock::PythonLockDiscoverer {
+            project_dirs: config.discover.project_dirs.clone(),
+        }),
+        Box::new(deno::DenoDiscoverer),
+        Box::new(bun::BunDiscoverer),
+        Box::new(pnpm::PnpmDiscoverer),
+        Box::new(yarn::YarnDiscoverer),
+        Box::new(dotnet::DotnetToolsDiscoverer),
+        Box::new(composer::ComposerDiscoverer {
+            project_dirs: config.discover.project_dirs.clone(),
+        }),
+        Box::new(opam::OpamDiscoverer),
+        Box::new(ghc::GhcPkgDiscoverer),
+        Box::new(vscode::VscodeExtensionsDiscoverer),
+        Box::new(browser::BrowserExtensionsDiscoverer),
+        Box::new(plasma::PlasmaDiscoverer),
+        Box::new(vim::VimPluginDiscoverer),
+        Box::new(shell::ShellPluginDiscoverer),
+        Box::new(gh::GhExtensionsDiscoverer),
+        Box::new(krew::KrewDiscoverer),
+        Box::new(helm::HelmDiscoverer {
+            enabled: config.discover.kubernetes && !config.offline,
+        }),
+        Box::new(kubernetes::KubernetesDiscoverer {
+            enabled: config.discover.kubernetes && !config.offline,
+        }),
+        Box::new(distrobox::DistroboxDiscoverer {
+            scan_packages: config.discover.container_packages,
+        }),
+        Box::new(incus::IncusDiscoverer),
+        Box::new(fwupd::FwupdDiscoverer),
+        Box::new(fonts::FontsDiscoverer),
+        Box::new(games::GamesDiscoverer),
+        Box::new(lockfile::LockfileDiscoverer {
+            project_dirs: config.discover.project_dirs.clone(),
+        }),
+        Box::new(fdroid::FdroidDiscoverer {
+            enabled: config.discover.android && !config.offline,
+        }),
     ];
 
     candidates
@@ -179,3 +432,13 @@ pub fn active_discoverers(_config: &Config) -> Vec<Box<dyn Discoverer>> {
         .filter(|d| d.is_available())
         .collect()
 }
+
+/// Returns `true` if an executable named `bin` exists in a `$PATH` directory.
+///
+/// Only checks for the file's presence, so it is cheap enough for
+/// [`Discoverer::is_available()`] implementations of tools that are commonly
+/// installed in user-specific locations (version managers, `~/.local/bin`).
+pub(crate) fn in_path(bin: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(bin).is_file()))
+}
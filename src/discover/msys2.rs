@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::pacman;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Discovers packages installed in an MSYS2 environment (Windows).
+///
+/// MSYS2 uses pacman with the same local database format as Arch Linux, but
+/// rooted at the MSYS2 installation directory (`<root>\var\lib\pacman\local`)
+/// rather than `/`. Packages are parsed with
+/// [`pacman::parse_desc`] and attributed to [`PackageSource::Msys2`], so
+/// toolchain packages such as `mingw-w64-ucrt-x86_64-gcc` are not mistaken
+/// for Arch packages.
+pub struct Msys2Discoverer;
+
+/// Default install locations checked when MSYS2 is not found on `PATH`.
+const DEFAULT_ROOTS: [&str; 2] = [r"C:\msys64", r"C:\msys32"];
+
+/// Relative path of the local pacman database inside an MSYS2 root.
+const LOCAL_DB: &str = "var/lib/pacman/local";
+
+impl Discoverer for Msys2Discoverer {
+    fn name(&self) -> &str {
+        "msys2"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(windows) && msys2_root().is_some()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let db_path = msys2_root()
+            .context("Could not find MSYS2 installation")?
+            .join(LOCAL_DB);
+
+        let desc_paths: Vec<_> = fs::read_dir(&db_path)
+            .with_context(|| format!("Failed to read {}", db_path.display()))?
+            .filter_map(|entry| {
+                let desc_path = entry.ok()?.path().join("desc");
+                desc_path.is_file().then_some(desc_path)
+            })
+            .collect();
+
+        let pb = ProgressBar::new(desc_paths.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+        for desc_path in &desc_paths {
+            match pacman::parse_desc(desc_path) {
+                Ok(mut pkg) => {
+                    pkg.source = PackageSource::Msys2;
+                    packages.push(pkg);
+                }
+                Err(e) => {
+                    pb.suspend(|| {
+                        eprintln!("  Warning: failed to parse {}: {e}", desc_path.display())
+                    });
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Locate the MSYS2 installation root.
+///
+/// When running from an MSYS2 shell, its `usr\bin` directory is on `PATH` and
+/// identifies the root directly. Otherwise the default install locations are
+/// checked.
+fn msys2_root() -> Option<PathBuf> {
+    let from_path = std::env::var_os("PATH")
+        .and_then(|path| std::env::split_paths(&path).find_map(|dir| root_from_bin_dir(&dir)));
+
+    from_path.or_else(|| {
+        DEFAULT_ROOTS
+            .iter()
+            .map(PathBuf::from)
+            .find(|root| root.join(LOCAL_DB).is_dir())
+    })
+}
+
+/// If `dir` is an MSYS2 `usr/bin` directory, return the installation root.
+///
+/// The directory must contain the MSYS2 runtime (`msys-2.0.dll`) and the root
+/// two levels up must hold a pacman local database.
+fn root_from_bin_dir(dir: &Path) -> Option<PathBuf> {
+    if !dir.join("msys-2.0.dll").is_file() {
+        return None;
+    }
+    let root = dir.parent()?.parent()?;
+    root.join(LOCAL_DB).is_dir().then(|| root.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_detected_from_usr_bin() {
+        let tmp = tempfile::tempdir().unwrap();
+        let bin = tmp.path().join("usr/bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::create_dir_all(tmp.path().join(LOCAL_DB)).unwrap();
+
+        // Without the MSYS2 runtime the directory is not recognized.
+        assert!(root_from_bin_dir(&bin).is_none());
+
+        fs::write(bin.join("msys-2.0.dll"), b"").unwrap();
+        assert_eq!(root_from_bin_dir(&bin).as_deref(), Some(tmp.path()));
+    }
+
+    #[test]
+    fn root_requires_pacman_db() {
+        let tmp = tempfile::tempdir().unwrap();
+        let bin = tmp.path().join("usr/bin");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join("msys-2.0.dll"), b"").unwrap();
+
+        assert!(root_from_bin_dir(&bin).is_none());
+    }
+}
@@ -1,12 +1,15 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use serde_json::Value;
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
 
 /// Discovers packages installed via Nix (both NixOS system packages and user profiles).
 ///
@@ -14,6 +17,10 @@ use super::{Discoverer, InstalledPackage, PackageSource};
 /// and also reads NixOS system packages from `/run/current-system/sw/` when
 /// available. Package name and version are extracted from Nix store paths which
 /// follow the pattern `/nix/store/<hash>-<name>-<version>`.
+///
+/// Store paths carry no metadata, so the packages are then matched against
+/// `nix-env -qa --json --meta` on the `<nixpkgs>` channel to fill in their
+/// homepage, description, and licenses from nixpkgs `meta`.
 pub struct NixDiscoverer;
 
 impl Discoverer for NixDiscoverer {
@@ -41,6 +48,13 @@ impl Discoverer for NixDiscoverer {
         // Deduplicate by name (prefer system packages which come second)
         dedup_packages(&mut packages);
 
+        if !packages.is_empty() {
+            match query_nixpkgs_meta() {
+                Ok(index) => index.apply(&mut packages),
+                Err(e) => eprintln!("  Warning: could not read nixpkgs metadata: {e}"),
+            }
+        }
+
         Ok(packages)
     }
 }
@@ -233,6 +247,130 @@ fn discover_system_packages() -> Result<Vec<InstalledPackage>> {
     Ok(packages)
 }
 
+/// Evaluate the `<nixpkgs>` channel with `nix-env -qa --json --meta`.
+fn query_nixpkgs_meta() -> Result<NixpkgsIndex> {
+    let output = Command::new("nix-env")
+        .args(["-f", "<nixpkgs>", "-qa", "--json", "--meta"])
+        .output()
+        .context("Failed to run nix-env -qa")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "nix-env -qa failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let stdout = String::from_utf8(output.stdout).context("nix-env output is not valid UTF-8")?;
+
+    parse_nixpkgs_meta(&stdout)
+}
+
+/// An entry of `nix-env -qa --json --meta`, keyed by attribute path.
+///
+/// ```json
+/// {"firefox": {"name": "firefox-128.0", "pname": "firefox", "version": "128.0",
+///   "meta": {"description": "Web browser built from Firefox source tree",
+///            "homepage": "http://www.mozilla.com/en-US/firefox/",
+///            "license": {"spdxId": "MPL-2.0", "shortName": "mpl20"}}}}
+/// ```
+#[derive(Deserialize)]
+struct NixEnvEntry {
+    name: String,
+    pname: Option<String>,
+    #[serde(default)]
+    meta: NixMeta,
+}
+
+#[derive(Clone, Default, Deserialize)]
+struct NixMeta {
+    description: Option<String>,
+    /// A URL, or a list of URLs.
+    homepage: Option<Value>,
+    /// A license attrset, a list of them, or a bare string.
+    license: Option<Value>,
+}
+
+/// nixpkgs metadata indexed by derivation name (`firefox-128.0`) and by
+/// package name (`firefox`).
+struct NixpkgsIndex {
+    by_name: HashMap<String, NixMeta>,
+    by_pname: HashMap<String, NixMeta>,
+}
+
+impl NixpkgsIndex {
+    /// Fill in missing metadata, preferring an exact name and version match
+    /// and falling back to the package name when the channel has a different
+    /// version than the one installed.
+    fn apply(&self, packages: &mut [InstalledPackage]) {
+        for pkg in packages {
+            let meta = self
+                .by_name
+                .get(&format!("{}-{}", pkg.name, pkg.version))
+                .or_else(|| self.by_pname.get(&pkg.name));
+            let Some(meta) = meta else {
+                continue;
+            };
+
+            if pkg.description.is_none() {
+                pkg.description = meta.description.clone().filter(|d| !d.is_empty());
+            }
+            if pkg.url.is_none() {
+                pkg.url = meta.homepage.as_ref().and_then(first_url);
+            }
+            if pkg.licenses.is_empty() {
+                pkg.licenses = meta.license.as_ref().map(licenses).unwrap_or_default();
+            }
+        }
+    }
+}
+
+fn parse_nixpkgs_meta(json: &str) -> Result<NixpkgsIndex> {
+    let entries: HashMap<String, NixEnvEntry> =
+        serde_json::from_str(json).context("Failed to parse nix-env JSON output")?;
+
+    let mut index = NixpkgsIndex {
+        by_name: HashMap::new(),
+        by_pname: HashMap::new(),
+    };
+    for entry in entries.into_values() {
+        let pname = entry
+            .pname
+            .unwrap_or_else(|| split_name_version(&entry.name).0);
+        index
+            .by_pname
+            .entry(pname)
+            .or_insert_with(|| entry.meta.clone());
+        index.by_name.entry(entry.name).or_insert(entry.meta);
+    }
+
+    Ok(index)
+}
+
+/// The first HTTP(S) URL of a `meta.homepage` value.
+fn first_url(homepage: &Value) -> Option<String> {
+    match homepage {
+        Value::String(url) => Some(url.clone()).filter(|u| u.starts_with("http")),
+        Value::Array(urls) => urls.iter().find_map(first_url),
+        _ => None,
+    }
+}
+
+/// License identifiers from a `meta.license` value, preferring `spdxId`
+/// over the nixpkgs `shortName`.
+fn licenses(license: &Value) -> Vec<String> {
+    match license {
+        Value::String(name) => vec![name.clone()],
+        Value::Array(items) => items.iter().flat_map(licenses).collect(),
+        Value::Object(attrs) => ["spdxId", "shortName", "fullName"]
+            .iter()
+            .find_map(|key| attrs.get(*key).and_then(Value::as_str))
+            .map(|id| vec![id.to_string()])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
 /// Parse a Nix store path to extract package name and version.
 ///
 /// Store paths have the format: `/nix/store/<hash>-<name>-<version>`
@@ -265,6 +403,9 @@ fn parse_store_path(path: &str) -> Option<InstalledPackage> {
         url: None,
         source: PackageSource::Nix,
         licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
     })
 }
 
@@ -422,6 +563,9 @@ Store paths:        /nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-git-2.45.0
                 url: None,
                 source: PackageSource::Nix,
                 licenses: Vec::new(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
             InstalledPackage {
                 name: "firefox".to_string(),
@@ -430,10 +574,69 @@ Store paths:        /nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-git-2.45.0
                 url: None,
                 source: PackageSource::Nix,
                 licenses: Vec::new(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
         ];
         dedup_packages(&mut packages);
         assert_eq!(packages.len(), 1);
         assert_eq!(packages[0].version, "128.0");
     }
+
+    #[test]
+    fn nixpkgs_meta_is_applied() {
+        let json = r#"{
+            "firefox": {
+                "name": "firefox-129.0",
+                "pname": "firefox",
+                "version": "129.0",
+                "meta": {
+                    "description": "Web browser built from Firefox source tree",
+                    "homepage": "http://www.mozilla.com/en-US/firefox/",
+                    "license": {"spdxId": "MPL-2.0", "shortName": "mpl20", "free": true}
+                }
+            },
+            "git": {
+                "name": "git-2.45.0",
+                "pname": "git",
+                "meta": {
+                    "homepage": ["https://git-scm.com/", "https://github.com/git/git"],
+                    "license": [{"spdxId": "GPL-2.0-only"}, {"shortName": "lgpl21Plus"}]
+                }
+            },
+            "hello": {"name": "hello-2.12.1", "meta": {}}
+        }"#;
+        let index = parse_nixpkgs_meta(json).unwrap();
+
+        let mut packages = vec![
+            parse_store_path("/nix/store/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa-firefox-128.0").unwrap(),
+            parse_store_path("/nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-git-2.45.0").unwrap(),
+            parse_store_path("/nix/store/cccccccccccccccccccccccccccccccc-unknown-pkg-1.0")
+                .unwrap(),
+        ];
+        index.apply(&mut packages);
+
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("http://www.mozilla.com/en-US/firefox/")
+        );
+        assert_eq!(
+            packages[0].description.as_deref(),
+            Some("Web browser built from Firefox source tree")
+        );
+        assert_eq!(packages[0].licenses, vec!["MPL-2.0"]);
+
+        assert_eq!(packages[1].url.as_deref(), Some("https://git-scm.com/"));
+        assert_eq!(packages[1].licenses, vec!["GPL-2.0-only", "lgpl21Plus"]);
+        assert!(packages[1].description.is_none());
+
+        assert!(packages[2].url.is_none());
+        assert!(index.by_pname.contains_key("hello"));
+    }
+
+    #[test]
+    fn parse_nixpkgs_meta_invalid() {
+        assert!(parse_nixpkgs_meta("not json").is_err());
+    }
 }
@@ -0,0 +1,432 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use crate::project::FundingChannel;
+
+/// Discovers globally installed npm packages.
+///
+/// Runs `npm ls -g --json --depth=0` for the list of top-level global
+/// packages and `npm root -g` to locate them, then reads each package's
+/// `package.json` for its description, repository/homepage, license, and
+/// `funding` declaration.
+pub struct NpmDiscoverer;
+
+impl Discoverer for NpmDiscoverer {
+    fn name(&self) -> &str {
+        "npm"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("npm")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let root = run_npm(&["root", "-g"])?;
+        let root = Path::new(root.trim());
+
+        // `npm ls` exits non-zero on dependency problems (e.g. missing peers)
+        // while still printing a usable tree, so its status is not checked.
+        let output = Command::new("npm")
+            .args(["ls", "-g", "--json", "--depth=0"])
+            .output()
+            .context("Failed to run npm ls")?;
+        let stdout =
+            String::from_utf8(output.stdout).context("npm ls output is not valid UTF-8")?;
+
+        let globals = parse_npm_ls(&stdout)?;
+
+        let pb = ProgressBar::new(globals.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (name, version) in globals {
+            let manifest = read_package_json(&root.join(&name).join("package.json"), &pb);
+            packages.push(build_package(name, version, manifest));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run an npm subcommand and return its stdout.
+fn run_npm(args: &[&str]) -> Result<String> {
+    let output = Command::new("npm")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run npm {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "npm {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("npm output is not valid UTF-8")
+}
+
+#[derive(Deserialize)]
+struct NpmLs {
+    #[serde(default)]
+    dependencies: BTreeMap<String, NpmLsEntry>,
+}
+
+#[derive(Deserialize)]
+struct NpmLsEntry {
+    version: Option<String>,
+}
+
+/// Parse `npm ls -g --json --depth=0` into `(name, version)` pairs.
+///
+/// ```json
+/// { "name": "lib", "dependencies": { "typescript": { "version": "5.4.5" } } }
+/// ```
+fn parse_npm_ls(output: &str) -> Result<Vec<(String, String)>> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ls: NpmLs = serde_json::from_str(output).context("Failed to parse npm ls output")?;
+
+    Ok(ls
+        .dependencies
+        .into_iter()
+        .map(|(name, entry)| {
+            let version = entry.version.unwrap_or_else(|| "unknown".to_string());
+            (name, version)
+        })
+        .collect())
+}
+
+/// The fields of `package.json` that syld cares about.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct PackageManifest {
+    description: Option<String>,
+    url: Option<String>,
+    licenses: Vec<String>,
+    funding: Vec<FundingChannel>,
+}
+
+impl PackageManifest {
+    /// Convert into an [`InstalledPackage`] attributed to `source`.
+    pub(super) fn into_package(
+        self,
+        name: String,
+        version: String,
+        source: PackageSource,
+    ) -> InstalledPackage {
+        InstalledPackage {
+            name,
+            version,
+            description: self.description,
+            url: self.url,
+            source,
+            licenses: self.licenses,
+            funding: self.funding,
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        }
+    }
+}
+
+/// Read and parse a package's `package.json`, if present.
+///
+/// Parse failures are reported as warnings above the progress bar; a missing
+/// file is silently treated as having no metadata.
+pub(super) fn read_package_json(path: &Path, pb: &ProgressBar) -> Option<PackageManifest> {
+    let content = fs::read_to_string(path).ok()?;
+    match parse_package_json(&content) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+            None
+        }
+    }
+}
+
+/// Parse a `package.json`.
+///
+/// `repository`, `license`, and `funding` each accept several shapes (string,
+/// object, or array); all documented forms are handled. The repository URL is
+/// preferred over `homepage` as the project URL. VS Code extension manifests
+/// declare funding as `sponsor: { "url" }` instead, which is read as well.
+pub(super) fn parse_package_json(content: &str) -> Result<PackageManifest> {
+    let json: Value = serde_json::from_str(content).context("Invalid package.json")?;
+
+    let description = json
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(String::from);
+
+    let repository = match json.get("repository") {
+        Some(Value::String(s)) => Some(s.as_str()),
+        Some(Value::Object(obj)) => obj.get("url").and_then(Value::as_str),
+        _ => None,
+    };
+    let homepage = json.get("homepage").and_then(Value::as_str);
+    let url = repository
+        .and_then(normalize_repository_url)
+        .or_else(|| homepage.filter(|s| !s.is_empty()).map(String::from));
+
+    let mut licenses = Vec::new();
+    match json.get("license") {
+        Some(Value::String(s)) => licenses.push(s.clone()),
+        Some(Value::Object(obj)) => licenses.extend(license_type(obj)),
+        _ => {}
+    }
+    // Legacy `licenses: [{ "type": "MIT" }]` form
+    if let Some(Value::Array(items)) = json.get("licenses") {
+        licenses.extend(
+            items
+                .iter()
+                .filter_map(Value::as_object)
+                .filter_map(license_type),
+        );
+    }
+
+    let mut funding = Vec::new();
+    match json.get("funding") {
+        Some(Value::Array(items)) => funding.extend(items.iter().filter_map(funding_url)),
+        Some(value) => funding.extend(funding_url(value)),
+        None => {}
+    }
+    funding.extend(json.get("sponsor").and_then(funding_url));
+    let funding = funding
+        .iter()
+        .map(|url| FundingChannel::from_url(url))
+        .collect();
+
+    Ok(PackageManifest {
+        description,
+        url,
+        licenses,
+        funding,
+    })
+}
+
+fn license_type(obj: &serde_json::Map<String, Value>) -> Option<String> {
+    obj.get("type").and_then(Value::as_str).map(String::from)
+}
+
+/// Extract the URL from a `funding` entry (`"url"` or `{ "type", "url" }`).
+fn funding_url(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(s) => Some(s.as_str()),
+        Value::Object(obj) => obj.get("url").and_then(Value::as_str),
+        _ => None,
+    }
+    .filter(|s| s.starts_with("http"))
+}
+
+/// Turn an npm `repository` value into a browsable HTTPS URL.
+///
+/// Handles the shorthand forms (`github:user/repo`, `gitlab:user/repo`,
+/// `bitbucket:user/repo`, bare `user/repo`) and git transport URLs
+/// (`git+https://…`, `git://…`, `git+ssh://git@host/…`, `git@host:…`).
+fn normalize_repository_url(repo: &str) -> Option<String> {
+    let repo = repo.trim();
+    if repo.is_empty() {
+        return None;
+    }
+
+    let url = if let Some(path) = repo.strip_prefix("github:") {
+        format!("https://github.com/{path}")
+    } else if let Some(path) = repo.strip_prefix("gitlab:") {
+        format!("https://gitlab.com/{path}")
+    } else if let Some(path) = repo.strip_prefix("bitbucket:") {
+        format!("https://bitbucket.org/{path}")
+    } else if let Some(rest) = repo.strip_prefix("git@") {
+        let (host, path) = rest.split_once(':')?;
+        format!("https://{host}/{path}")
+    } else if !repo.contains(':') && repo.matches('/').count() == 1 {
+        format!("https://github.com/{repo}")
+    } else {
+        let rest = repo.trim_start_matches("git+");
+        let (_, rest) = rest.split_once("://")?;
+        let rest = rest.strip_prefix("git@").unwrap_or(rest);
+        format!("https://{rest}")
+    };
+
+    let url = url.split(['#', '?']).next().unwrap_or(&url);
+    let url = url.trim_end_matches('/');
+    Some(url.strip_suffix(".git").unwrap_or(url).to_string())
+}
+
+fn build_package(
+    name: String,
+    version: String,
+    manifest: Option<PackageManifest>,
+) -> InstalledPackage {
+    manifest
+        .unwrap_or_default()
+        .into_package(name, version, PackageSource::Npm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ls_output() {
+        let output = r#"{
+            "name": "lib",
+            "dependencies": {
+                "typescript": { "version": "5.4.5", "overridden": false },
+                "@vue/cli": { "version": "5.0.8" },
+                "broken": {}
+            }
+        }"#;
+        let globals = parse_npm_ls(output).unwrap();
+        assert_eq!(
+            globals,
+            vec![
+                ("@vue/cli".to_string(), "5.0.8".to_string()),
+                ("broken".to_string(), "unknown".to_string()),
+                ("typescript".to_string(), "5.4.5".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ls_empty() {
+        assert!(parse_npm_ls("").unwrap().is_empty());
+        assert!(parse_npm_ls(r#"{"name": "lib"}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_package_json_full() {
+        let content = r#"{
+            "name": "eslint",
+            "version": "9.5.0",
+            "description": "An AST-based pattern checker for JavaScript.",
+            "homepage": "https://eslint.org",
+            "repository": { "type": "git", "url": "git+https://github.com/eslint/eslint.git" },
+            "license": "MIT",
+            "funding": [
+                { "type": "opencollective", "url": "https://opencollective.com/eslint" },
+                "https://github.com/sponsors/eslint"
+            ]
+        }"#;
+        let manifest = parse_package_json(content).unwrap();
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("An AST-based pattern checker for JavaScript.")
+        );
+        assert_eq!(
+            manifest.url.as_deref(),
+            Some("https://github.com/eslint/eslint")
+        );
+        assert_eq!(manifest.licenses, vec!["MIT"]);
+        assert_eq!(
+            manifest.funding,
+            vec![
+                FundingChannel {
+                    platform: "Open Collective".to_string(),
+                    url: "https://opencollective.com/eslint".to_string(),
+                    page: None,
+                },
+                FundingChannel {
+                    platform: "GitHub Sponsors".to_string(),
+                    url: "https://github.com/sponsors/eslint".to_string(),
+                    page: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_package_json_single_funding_object() {
+        let content = r#"{"funding": {"type": "patreon", "url": "https://www.patreon.com/foo"}}"#;
+        let manifest = parse_package_json(content).unwrap();
+        assert_eq!(manifest.funding.len(), 1);
+        assert_eq!(manifest.funding[0].platform, "Patreon");
+    }
+
+    #[test]
+    fn parse_package_json_vscode_sponsor() {
+        let content = r#"{"sponsor": {"url": "https://github.com/sponsors/eamodio"}}"#;
+        let manifest = parse_package_json(content).unwrap();
+        assert_eq!(manifest.funding.len(), 1);
+        assert_eq!(manifest.funding[0].platform, "GitHub Sponsors");
+    }
+
+    #[test]
+    fn parse_package_json_homepage_fallback() {
+        let content = r#"{"homepage": "https://example.org/tool"}"#;
+        let manifest = parse_package_json(content).unwrap();
+        assert_eq!(manifest.url.as_deref(), Some("https://example.org/tool"));
+        assert!(manifest.funding.is_empty());
+    }
+
+    #[test]
+    fn parse_package_json_legacy_licenses() {
+        let content = r#"{"licenses": [{"type": "MIT"}, {"type": "Apache-2.0"}]}"#;
+        let manifest = parse_package_json(content).unwrap();
+        assert_eq!(manifest.licenses, vec!["MIT", "Apache-2.0"]);
+    }
+
+    #[test]
+    fn parse_package_json_invalid() {
+        assert!(parse_package_json("not json").is_err());
+    }
+
+    #[test]
+    fn normalize_repository_shorthands() {
+        let cases = [
+            ("github:npm/cli", "https://github.com/npm/cli"),
+            ("gitlab:foo/bar", "https://gitlab.com/foo/bar"),
+            ("bitbucket:foo/bar", "https://bitbucket.org/foo/bar"),
+            ("npm/cli", "https://github.com/npm/cli"),
+            (
+                "git+https://github.com/npm/cli.git",
+                "https://github.com/npm/cli",
+            ),
+            ("git://github.com/npm/cli.git", "https://github.com/npm/cli"),
+            (
+                "git+ssh://git@github.com/npm/cli.git",
+                "https://github.com/npm/cli",
+            ),
+            ("git@github.com:npm/cli.git", "https://github.com/npm/cli"),
+            (
+                "https://github.com/npm/cli#readme",
+                "https://github.com/npm/cli",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                normalize_repository_url(input).as_deref(),
+                Some(expected),
+                "{input}"
+            );
+        }
+        assert_eq!(normalize_repository_url(""), None);
+    }
+
+    #[test]
+    fn build_package_without_manifest() {
+        let pkg = build_package("typescript".to_string(), "5.4.5".to_string(), None);
+        assert_eq!(pkg.source, PackageSource::Npm);
+        assert!(pkg.url.is_none());
+        assert!(pkg.funding.is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Shared helpers for backends whose packages carry NuGet `.nuspec`
+//! manifests (Chocolatey, .NET tools).
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use indicatif::ProgressBar;
+
+/// Fields of interest from a `.nuspec` manifest.
+#[derive(Debug, Default, PartialEq)]
+pub struct Nuspec {
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub project_url: Option<String>,
+    pub project_source_url: Option<String>,
+    /// `url` attribute of the `<repository>` element.
+    pub repository_url: Option<String>,
+    pub license: Option<String>,
+    pub license_url: Option<String>,
+}
+
+impl Nuspec {
+    /// First line of the summary (or description), falling back to the title.
+    pub fn short_description(&self) -> Option<String> {
+        self.summary
+            .as_deref()
+            .and_then(|s| s.lines().next())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .or_else(|| self.title.clone())
+    }
+}
+
+/// Parse a NuGet `.nuspec` manifest.
+///
+/// ```xml
+/// <package xmlns="http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd">
+///   <metadata>
+///     <id>git</id>
+///     <title>Git</title>
+///     <projectUrl>https://git-scm.com/</projectUrl>
+///     <licenseUrl>https://git-scm.com/about/free-and-open-source</licenseUrl>
+///   </metadata>
+/// </package>
+/// ```
+///
+/// Element names are matched without their namespace, since the schema URI
+/// varies between nuspec versions.
+pub fn parse_nuspec(content: &str) -> Result<Nuspec> {
+    let doc = roxmltree::Document::parse(content).context("Invalid nuspec")?;
+    let metadata = doc
+        .root_element()
+        .children()
+        .find(|n| n.has_tag_name("metadata"))
+        .context("Missing <metadata> element")?;
+
+    let element = |tag: &str| metadata.children().find(|n| n.has_tag_name(tag));
+    let field = |tag: &str| {
+        element(tag)
+            .and_then(|n| n.text())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+    };
+
+    Ok(Nuspec {
+        title: field("title"),
+        summary: field("summary").or_else(|| field("description")),
+        project_url: field("projectUrl"),
+        project_source_url: field("projectSourceUrl"),
+        repository_url: element("repository")
+            .and_then(|n| n.attribute("url"))
+            .filter(|u| !u.is_empty())
+            .map(String::from),
+        license: field("license"),
+        license_url: field("licenseUrl"),
+    })
+}
+
+/// Read and parse a `.nuspec` file, reporting parse failures as warnings.
+///
+/// A missing or unreadable manifest yields an empty [`Nuspec`].
+pub fn read_nuspec(path: &Path, pb: &ProgressBar) -> Nuspec {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Nuspec::default();
+    };
+    parse_nuspec(&content).unwrap_or_else(|e| {
+        pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+        Nuspec::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_nuspec_with_namespace() {
+        let content = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd">
+  <metadata>
+    <id>git</id>
+    <version>2.45.2</version>
+    <title>Git</title>
+    <projectUrl>https://git-scm.com/</projectUrl>
+    <projectSourceUrl>https://github.com/git-for-windows/git</projectSourceUrl>
+    <licenseUrl>https://www.gnu.org/licenses/old-licenses/gpl-2.0.html</licenseUrl>
+    <summary>Git (for Windows)</summary>
+    <description>Git for Windows focuses on offering a lightweight,
+native set of tools.</description>
+  </metadata>
+</package>
+"#;
+        let nuspec = parse_nuspec(content).unwrap();
+        assert_eq!(nuspec.title.as_deref(), Some("Git"));
+        assert_eq!(nuspec.project_url.as_deref(), Some("https://git-scm.com/"));
+        assert_eq!(
+            nuspec.project_source_url.as_deref(),
+            Some("https://github.com/git-for-windows/git")
+        );
+        assert_eq!(
+            nuspec.short_description().as_deref(),
+            Some("Git (for Windows)")
+        );
+        assert!(nuspec.repository_url.is_none());
+    }
+
+    #[test]
+    fn parse_nuspec_repository_element() {
+        let content = r#"<package><metadata>
+    <id>dotnet-ef</id>
+    <license type="expression">MIT</license>
+    <repository type="git" url="https://github.com/dotnet/efcore" commit="abc" />
+    <description>Entity Framework Core Tools
+for the .NET CLI.</description>
+</metadata></package>"#;
+        let nuspec = parse_nuspec(content).unwrap();
+        assert_eq!(
+            nuspec.repository_url.as_deref(),
+            Some("https://github.com/dotnet/efcore")
+        );
+        assert_eq!(nuspec.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            nuspec.short_description().as_deref(),
+            Some("Entity Framework Core Tools")
+        );
+    }
+
+    #[test]
+    fn parse_nuspec_missing_metadata() {
+        assert!(parse_nuspec("<package/>").is_err());
+        assert!(parse_nuspec("not xml").is_err());
+    }
+}
@@ -1,17 +1,27 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-//! Shared helpers for OCI-compatible container image discoverers (Docker, Podman).
+//! Shared helpers for OCI-compatible container image discoverers (Docker,
+//! Podman, Kubernetes).
 //!
 //! Both Docker and Podman use the same OCI label conventions and produce
 //! structurally similar output. This module extracts the common parsing logic
 //! so that each backend only needs to handle its own command invocation and
 //! image-list format.
+//!
+//! Images without OCI source labels can optionally be looked up in their
+//! registry (Docker Hub, ghcr.io, or any other registry speaking the
+//! distribution API), whose manifests and image configs carry the labels and
+//! annotations the local copy may lack.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ACCEPT, AUTHORIZATION, WWW_AUTHENTICATE};
+use serde::Deserialize;
 
-use super::{InstalledPackage, PackageSource};
+use super::{InstallReason, InstalledPackage, PackageSource};
 
 /// Parse a JSON object of OCI labels into a `HashMap`.
 ///
@@ -45,8 +55,8 @@ pub fn build_package_from_labels(
     };
 
     let url = labels
-        .get("org.opencontainers.image.source")
-        .or_else(|| labels.get("org.opencontainers.image.url"))
+        .get(SOURCE_LABEL)
+        .or_else(|| labels.get(URL_LABEL))
         .cloned();
 
     let description = labels.get("org.opencontainers.image.description").cloned();
@@ -63,7 +73,300 @@ pub fn build_package_from_labels(
         url,
         source,
         licenses,
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+/// Keep only the first of several images sharing a digest.
+///
+/// The same image is listed once per tag (e.g. `nginx:latest` and
+/// `nginx:1.25`) and again when retagged under another name; all of them
+/// refer to the same software.
+pub fn dedupe_by_digest<T>(images: Vec<T>, digest: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut seen = HashSet::new();
+    images
+        .into_iter()
+        .filter(|image| seen.insert(digest(image).to_string()))
+        .collect()
+}
+
+const SOURCE_LABEL: &str = "org.opencontainers.image.source";
+const URL_LABEL: &str = "org.opencontainers.image.url";
+
+/// Media types accepted when fetching a manifest, covering both multi-platform
+/// indexes and single-platform manifests in OCI and Docker formats.
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+
+/// Build the HTTP client used for registry lookups.
+pub fn registry_client() -> Result<Client> {
+    Ok(Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?)
+}
+
+/// Fill in labels missing from a local image with those published in its
+/// registry, unless the image already names its source.
+///
+/// Local labels take precedence over the remote ones.
+pub fn merge_registry_labels(
+    client: &Client,
+    name: &str,
+    tag: &str,
+    labels: &mut HashMap<String, String>,
+) -> Result<()> {
+    if labels.contains_key(SOURCE_LABEL) || labels.contains_key(URL_LABEL) {
+        return Ok(());
+    }
+    let Some(reference) = ImageReference::parse(name, tag) else {
+        return Ok(());
+    };
+
+    for (key, value) in fetch_registry_labels(client, &reference)? {
+        labels.entry(key).or_insert(value);
+    }
+    Ok(())
+}
+
+/// An image reference split into its registry API host, repository, and tag.
+#[derive(Debug, PartialEq)]
+struct ImageReference {
+    host: String,
+    repository: String,
+    tag: String,
+}
+
+impl ImageReference {
+    /// Split a local image name such as `nginx`, `docker.io/library/nginx`,
+    /// or `ghcr.io/owner/app`.
+    ///
+    /// Returns `None` for untagged images and images that only exist locally.
+    fn parse(name: &str, tag: &str) -> Option<Self> {
+        if tag.is_empty() || tag == "<none>" {
+            return None;
+        }
+
+        // The first component names a registry only if it looks like a host.
+        let (registry, repository) = match name.split_once('/') {
+            Some((first, rest))
+                if first.contains('.') || first.contains(':') || first == "localhost" =>
+            {
+                (first, rest.to_string())
+            }
+            _ => ("docker.io", name.to_string()),
+        };
+        if registry == "localhost" || registry.starts_with("localhost:") {
+            return None;
+        }
+
+        let (host, repository) = if registry == "docker.io" {
+            let repository = if repository.contains('/') {
+                repository
+            } else {
+                format!("library/{repository}")
+            };
+            ("registry-1.docker.io".to_string(), repository)
+        } else {
+            (registry.to_string(), repository)
+        };
+
+        Some(Self {
+            host,
+            repository,
+            tag: tag.to_string(),
+        })
     }
+
+    fn url(&self, path: &str) -> String {
+        format!("https://{}/v2/{}/{path}", self.host, self.repository)
+    }
+}
+
+/// A manifest or image index. Only the fields needed to reach the image
+/// config are kept.
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+    config: Option<Descriptor>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct Descriptor {
+    digest: String,
+    platform: Option<Platform>,
+}
+
+#[derive(Deserialize)]
+struct Platform {
+    os: String,
+    architecture: String,
+}
+
+#[derive(Deserialize)]
+struct ImageConfig {
+    #[serde(default)]
+    config: ImageConfigDetails,
+}
+
+#[derive(Default, Deserialize)]
+struct ImageConfigDetails {
+    #[serde(rename = "Labels")]
+    labels: Option<HashMap<String, String>>,
+}
+
+/// Fetch the labels of an image from its registry, combining the image
+/// config's labels with the annotations of its manifest and index.
+fn fetch_registry_labels(
+    client: &Client,
+    reference: &ImageReference,
+) -> Result<HashMap<String, String>> {
+    let mut session = RegistrySession {
+        client,
+        token: None,
+    };
+
+    let mut manifest: Manifest =
+        session.get_json(&reference.url(&format!("manifests/{}", reference.tag)))?;
+    let mut annotations = std::mem::take(&mut manifest.annotations);
+
+    if manifest.config.is_none() {
+        let digest = pick_platform(&manifest.manifests)
+            .context("Image index lists no usable manifest")?
+            .digest
+            .clone();
+        manifest = session.get_json(&reference.url(&format!("manifests/{digest}")))?;
+        annotations.extend(std::mem::take(&mut manifest.annotations));
+    }
+
+    let config_digest = &manifest.config.context("Manifest has no config")?.digest;
+    let config: ImageConfig =
+        session.get_json(&reference.url(&format!("blobs/{config_digest}")))?;
+
+    let mut labels = config.config.labels.unwrap_or_default();
+    for (key, value) in annotations {
+        labels.entry(key).or_insert(value);
+    }
+    Ok(labels)
+}
+
+/// Pick the manifest matching the host architecture, falling back to the
+/// first Linux one. Attestation manifests (`unknown/unknown`) are skipped.
+fn pick_platform(manifests: &[Descriptor]) -> Option<&Descriptor> {
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        other => other,
+    };
+    let linux = |d: &&Descriptor| d.platform.as_ref().is_none_or(|p| p.os == "linux");
+
+    manifests
+        .iter()
+        .filter(linux)
+        .find(|d| d.platform.as_ref().is_some_and(|p| p.architecture == arch))
+        .or_else(|| manifests.iter().find(linux))
+}
+
+/// Requests against one registry, holding the anonymous pull token once the
+/// registry has asked for one.
+struct RegistrySession<'a> {
+    client: &'a Client,
+    token: Option<String>,
+}
+
+impl RegistrySession<'_> {
+    fn get_json<T: serde::de::DeserializeOwned>(&mut self, url: &str) -> Result<T> {
+        let mut response = self.get(url)?;
+        if response.status() == StatusCode::UNAUTHORIZED && self.token.is_none() {
+            let challenge = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|h| h.to_str().ok())
+                .context("Registry requires authentication")?
+                .to_string();
+            self.token = Some(self.fetch_token(&challenge)?);
+            response = self.get(url)?;
+        }
+
+        let body = response
+            .error_for_status()
+            .with_context(|| format!("Failed to fetch {url}"))?
+            .text()?;
+        serde_json::from_str(&body).with_context(|| format!("Invalid JSON from {url}"))
+    }
+
+    fn get(&self, url: &str) -> Result<Response> {
+        let mut request = self.client.get(url).header(ACCEPT, MANIFEST_ACCEPT);
+        if let Some(token) = &self.token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        request
+            .send()
+            .with_context(|| format!("Failed to fetch {url}"))
+    }
+
+    /// Obtain an anonymous token as directed by a `WWW-Authenticate: Bearer`
+    /// challenge.
+    fn fetch_token(&self, challenge: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            token: Option<String>,
+            access_token: Option<String>,
+        }
+
+        let params = parse_bearer_challenge(challenge);
+        let realm = params
+            .iter()
+            .find(|(key, _)| key == "realm")
+            .map(|(_, value)| value.clone())
+            .context("Registry auth challenge has no realm")?;
+        let query: Vec<&(String, String)> =
+            params.iter().filter(|(key, _)| key != "realm").collect();
+
+        let response: TokenResponse = self
+            .client
+            .get(&realm)
+            .query(&query)
+            .send()
+            .context("Failed to fetch registry token")?
+            .error_for_status()?
+            .json()
+            .context("Invalid registry token response")?;
+
+        response
+            .token
+            .or(response.access_token)
+            .context("Registry token response has no token")
+    }
+}
+
+/// Parse `Bearer realm="https://auth.docker.io/token",service="...",scope="..."`
+/// into its key/value parameters.
+fn parse_bearer_challenge(challenge: &str) -> Vec<(String, String)> {
+    let Some(params) = challenge
+        .strip_prefix("Bearer ")
+        .or_else(|| challenge.strip_prefix("bearer "))
+    else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    let mut rest = params.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        result.push((key.trim().to_string(), value.to_string()));
+        rest = remaining.trim_start_matches([',', ' ']);
+    }
+    result
 }
 
 #[cfg(test)]
@@ -178,4 +481,98 @@ mod tests {
         let pkg = build_package_from_labels("nginx", "latest", &labels, PackageSource::Podman);
         assert_eq!(pkg.url.as_deref(), Some("https://nginx.org"));
     }
+
+    #[test]
+    fn dedupe_keeps_first_per_digest() {
+        let images = vec![
+            ("nginx", "sha256:a"),
+            ("nginx", "sha256:a"),
+            ("redis", "sha256:b"),
+        ];
+        let deduped = dedupe_by_digest(images, |(_, digest)| digest);
+        assert_eq!(deduped, vec![("nginx", "sha256:a"), ("redis", "sha256:b")]);
+    }
+
+    #[test]
+    fn parse_image_references() {
+        assert_eq!(
+            ImageReference::parse("nginx", "1.25"),
+            Some(ImageReference {
+                host: "registry-1.docker.io".to_string(),
+                repository: "library/nginx".to_string(),
+                tag: "1.25".to_string(),
+            })
+        );
+        let podman = ImageReference::parse("docker.io/library/postgres", "16").unwrap();
+        assert_eq!(podman.host, "registry-1.docker.io");
+        assert_eq!(podman.repository, "library/postgres");
+
+        let ghcr = ImageReference::parse("ghcr.io/owner/app", "v1").unwrap();
+        assert_eq!(
+            ghcr.url("manifests/v1"),
+            "https://ghcr.io/v2/owner/app/manifests/v1"
+        );
+
+        let user = ImageReference::parse("grafana/grafana", "latest").unwrap();
+        assert_eq!(user.repository, "grafana/grafana");
+
+        assert!(ImageReference::parse("nginx", "<none>").is_none());
+        assert!(ImageReference::parse("localhost/myimage", "dev").is_none());
+        assert!(ImageReference::parse("localhost:5000/myimage", "dev").is_none());
+    }
+
+    #[test]
+    fn parse_challenge() {
+        let challenge = r#"Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:library/nginx:pull""#;
+        assert_eq!(
+            parse_bearer_challenge(challenge),
+            vec![
+                (
+                    "realm".to_string(),
+                    "https://auth.docker.io/token".to_string()
+                ),
+                ("service".to_string(), "registry.docker.io".to_string()),
+                (
+                    "scope".to_string(),
+                    "repository:library/nginx:pull".to_string()
+                ),
+            ]
+        );
+        assert!(parse_bearer_challenge("Basic realm=\"x\"").is_empty());
+    }
+
+    #[test]
+    fn pick_platform_skips_attestations() {
+        let index: Manifest = serde_json::from_str(
+            r#"{"manifests": [
+                {"digest": "sha256:att", "platform": {"os": "unknown", "architecture": "unknown"}},
+                {"digest": "sha256:s390x", "platform": {"os": "linux", "architecture": "s390x"}},
+                {"digest": "sha256:amd64", "platform": {"os": "linux", "architecture": "amd64"}},
+                {"digest": "sha256:arm64", "platform": {"os": "linux", "architecture": "arm64"}}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(index.config.is_none());
+
+        let picked = pick_platform(&index.manifests).unwrap();
+        let expected = match std::env::consts::ARCH {
+            "x86_64" => "sha256:amd64",
+            "aarch64" => "sha256:arm64",
+            _ => "sha256:s390x",
+        };
+        assert_eq!(picked.digest, expected);
+    }
+
+    #[test]
+    fn merge_skips_images_with_source() {
+        let client = registry_client().unwrap();
+        let mut labels = HashMap::new();
+        labels.insert(
+            "org.opencontainers.image.source".to_string(),
+            "https://github.com/nginx/nginx".to_string(),
+        );
+        // Returns before any request is made.
+        merge_registry_labels(&client, "nginx", "latest", &mut labels).unwrap();
+        assert_eq!(labels.len(), 1);
+    }
 }
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers OCaml packages installed with opam in the current switch.
+///
+/// Runs `opam list --installed` with explicit columns for name, version,
+/// synopsis, and the `homepage:`/`license:` fields of each package's opam
+/// file, separated by tabs. Field columns are printed in opam's own syntax
+/// (quoted strings, or lists of them), which is unquoted here.
+pub struct OpamDiscoverer;
+
+/// Column separator passed to `opam list`.
+const SEPARATOR: &str = "\t";
+
+impl Discoverer for OpamDiscoverer {
+    fn name(&self) -> &str {
+        "opam"
+    }
+
+    fn is_available(&self) -> bool {
+        opam_root().is_some_and(|root| root.is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("opam")
+            .args([
+                "list",
+                "--installed",
+                "--columns=name,version,synopsis,homepage:,license:",
+                "--separator",
+                SEPARATOR,
+                "--color=never",
+            ])
+            .output()
+            .context("Failed to run opam list")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "opam list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("opam list output is not valid UTF-8")?;
+        Ok(parse_opam_list(&stdout))
+    }
+}
+
+/// `$OPAMROOT`, defaulting to `~/.opam`.
+fn opam_root() -> Option<PathBuf> {
+    std::env::var_os("OPAMROOT")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".opam")))
+}
+
+/// Parse `opam list` output.
+///
+/// ```text
+/// # Packages matching: installed
+/// # Name  # Version  # Synopsis  # homepage:  # license:
+/// dune    3.16.0     Fast, portable, ...  "https://github.com/ocaml/dune"  "MIT"
+/// ```
+///
+/// Columns are tab-separated; header lines start with `#`.
+fn parse_opam_list(output: &str) -> Vec<InstalledPackage> {
+    let lines: Vec<&str> = output
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .collect();
+
+    let pb = ProgressBar::new(lines.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {bar:30} {pos}/{len} packages")
+            .unwrap(),
+    );
+
+    let mut packages = Vec::new();
+
+    for line in lines {
+        let fields: Vec<&str> = line.split(SEPARATOR).map(str::trim).collect();
+        match fields.as_slice() {
+            [name, version, rest @ ..] if !name.is_empty() => {
+                let field = |i: usize| rest.get(i).copied().unwrap_or_default();
+                let description = Some(field(0))
+                    .filter(|s| !s.is_empty() && *s != "--")
+                    .map(String::from);
+
+                packages.push(InstalledPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    description,
+                    url: opam_strings(field(1)).into_iter().next(),
+                    source: PackageSource::Opam,
+                    licenses: opam_strings(field(2)),
+                    funding: Vec::new(),
+                    install_reason: InstallReason::Unknown,
+                    maintainer: None,
+                });
+            }
+            _ => {
+                pb.suspend(|| eprintln!("  Warning: failed to parse opam list line: {line}"));
+            }
+        }
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+
+    packages
+}
+
+/// Extract the quoted strings from an opam field value
+/// (`"MIT"` or `["MIT" "ISC"]`), handling backslash escapes.
+fn opam_strings(value: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current: Option<String> = None;
+    let mut escaped = false;
+
+    for c in value.chars() {
+        match current.as_mut() {
+            None if c == '"' => current = Some(String::new()),
+            None => {}
+            Some(s) if escaped => {
+                s.push(c);
+                escaped = false;
+            }
+            Some(_) if c == '\\' => escaped = true,
+            Some(_) if c == '"' => {
+                strings.extend(current.take().filter(|s| !s.is_empty()));
+            }
+            Some(s) => s.push(c),
+        }
+    }
+
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_output() {
+        let output = "\
+# Packages matching: installed
+# Name\t# Version\t# Synopsis\t# homepage:\t# license:
+base-threads\tbase\t\t\t
+dune\t3.16.0\tFast, portable, and opinionated build system\t\"https://github.com/ocaml/dune\"\t\"MIT\"
+ocaml\t5.2.0\tThe OCaml compiler (virtual package)\t\"https://ocaml.org\"\t[\"LGPL-2.1-or-later WITH OCaml-LGPL-linking-exception\" \"MIT\"]
+";
+        let packages = parse_opam_list(output);
+        assert_eq!(packages.len(), 3);
+
+        assert_eq!(packages[0].name, "base-threads");
+        assert!(packages[0].url.is_none());
+        assert!(packages[0].description.is_none());
+
+        assert_eq!(packages[1].name, "dune");
+        assert_eq!(packages[1].version, "3.16.0");
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/ocaml/dune")
+        );
+        assert_eq!(packages[1].licenses, vec!["MIT"]);
+        assert_eq!(packages[1].source, PackageSource::Opam);
+
+        assert_eq!(
+            packages[2].licenses,
+            vec!["LGPL-2.1-or-later WITH OCaml-LGPL-linking-exception", "MIT"]
+        );
+    }
+
+    #[test]
+    fn opam_string_values() {
+        assert_eq!(opam_strings(r#""MIT""#), vec!["MIT"]);
+        assert_eq!(opam_strings(r#"["a" "b"]"#), vec!["a", "b"]);
+        assert_eq!(opam_strings(r#""say \"hi\"""#), vec![r#"say "hi""#]);
+        assert!(opam_strings("").is_empty());
+    }
+
+    #[test]
+    fn parse_skips_malformed_lines() {
+        assert!(parse_opam_list("lonely\n").is_empty());
+    }
+}
@@ -1,21 +1,27 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
 
 /// Discovers packages installed via pacman by reading the local database directly.
 ///
 /// The pacman database lives at /var/lib/pacman/local/ and contains one directory
 /// per installed package. Each directory has a "desc" file with package metadata.
+///
+/// Foreign packages (those not found in any sync repository, typically built
+/// from the AUR) are skipped here and reported by
+/// [`AurDiscoverer`](super::aur::AurDiscoverer) instead.
 pub struct PacmanDiscoverer;
 
-const PACMAN_DB_PATH: &str = "/var/lib/pacman/local";
+pub(crate) const PACMAN_DB_PATH: &str = "/var/lib/pacman/local";
 
 impl Discoverer for PacmanDiscoverer {
     fn name(&self) -> &str {
@@ -29,10 +35,19 @@ impl Discoverer for PacmanDiscoverer {
     fn discover(&self) -> Result<Vec<InstalledPackage>> {
         let db_path = Path::new(PACMAN_DB_PATH);
 
+        let foreign = foreign_package_names().unwrap_or_else(|e| {
+            eprintln!("  Warning: failed to list foreign packages: {e}");
+            HashSet::new()
+        });
+
         let desc_paths: Vec<_> = fs::read_dir(db_path)
             .context("Failed to read pacman database directory")?
             .filter_map(|entry| {
                 let entry = entry.ok()?;
+                let dir_name = entry.file_name();
+                if foreign.contains(package_name_from_dir(&dir_name.to_string_lossy())) {
+                    return None;
+                }
                 let desc_path = entry.path().join("desc");
                 desc_path.is_file().then_some(desc_path)
             })
@@ -68,8 +83,44 @@ impl Discoverer for PacmanDiscoverer {
     }
 }
 
+/// Names of installed packages not found in any sync database (`pacman -Qqm`).
+pub(super) fn foreign_package_names() -> Result<HashSet<String>> {
+    let output = Command::new("pacman")
+        .args(["-Qqm"])
+        .output()
+        .context("Failed to run pacman -Qqm")?;
+
+    // pacman exits with status 1 and no output when nothing matches.
+    if !output.status.success() && !output.stdout.is_empty() {
+        anyhow::bail!(
+            "pacman -Qqm failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let stdout =
+        String::from_utf8(output.stdout).context("pacman -Qqm output is not valid UTF-8")?;
+
+    Ok(stdout
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Extract the package name from a local database directory name
+/// (`<name>-<pkgver>-<pkgrel>`).
+pub(crate) fn package_name_from_dir(dir_name: &str) -> &str {
+    let mut parts = dir_name.rsplitn(3, '-');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(_pkgrel), Some(_pkgver), Some(name)) => name,
+        _ => dir_name,
+    }
+}
+
 /// Read and parse a pacman desc file into an InstalledPackage.
-fn parse_desc(path: &Path) -> Result<InstalledPackage> {
+pub(crate) fn parse_desc(path: &Path) -> Result<InstalledPackage> {
     let content =
         fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
     parse_desc_content(&content)
@@ -79,12 +130,15 @@ fn parse_desc(path: &Path) -> Result<InstalledPackage> {
 ///
 /// The desc file format uses %FIELD% headers followed by values on subsequent lines,
 /// separated by blank lines.
-fn parse_desc_content(content: &str) -> Result<InstalledPackage> {
+pub(super) fn parse_desc_content(content: &str) -> Result<InstalledPackage> {
     let mut name = None;
     let mut version = None;
     let mut description = None;
     let mut url = None;
     let mut licenses = Vec::new();
+    // pacman only writes %REASON% for dependencies (`1`); explicitly
+    // installed packages have no such field.
+    let mut install_reason = InstallReason::Explicit;
 
     let mut current_field: Option<&str> = None;
 
@@ -107,6 +161,7 @@ fn parse_desc_content(content: &str) -> Result<InstalledPackage> {
             Some("%DESC%") => description = Some(line.to_string()),
             Some("%URL%") => url = Some(line.to_string()),
             Some("%LICENSE%") => licenses.push(line.to_string()),
+            Some("%REASON%") if line == "1" => install_reason = InstallReason::Dependency,
             _ => {}
         }
     }
@@ -118,6 +173,9 @@ fn parse_desc_content(content: &str) -> Result<InstalledPackage> {
         url,
         source: PackageSource::Pacman,
         licenses,
+        funding: Vec::new(),
+        install_reason,
+        maintainer: None,
     })
 }
 
@@ -162,6 +220,25 @@ gtk3
             pkg.licenses,
             vec!["MPL-2.0", "GPL-2.0-only", "LGPL-2.1-only"]
         );
+        assert_eq!(pkg.install_reason, InstallReason::Explicit);
+    }
+
+    #[test]
+    fn parse_dependency_reason() {
+        let content = "%NAME%\nlibfoo\n\n%VERSION%\n1.0-1\n\n%REASON%\n1\n";
+        let pkg = parse_desc_content(content).unwrap();
+        assert_eq!(pkg.install_reason, InstallReason::Dependency);
+    }
+
+    #[test]
+    fn package_name_from_dir_names() {
+        assert_eq!(package_name_from_dir("firefox-128.0-1"), "firefox");
+        assert_eq!(
+            package_name_from_dir("python-requests-2.32.3-1"),
+            "python-requests"
+        );
+        assert_eq!(package_name_from_dir("zoom-6.1.1-1"), "zoom");
+        assert_eq!(package_name_from_dir("weird"), "weird");
     }
 
     #[test]
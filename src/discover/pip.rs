@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use super::{python, python_lock};
+
+/// Discovers Python packages installed with `pip install --user`.
+///
+/// Uses `python3 -m pip list --user --format=json` for the package list, then
+/// reads each distribution's `dist-info/METADATA` from the user site-packages
+/// (`~/.local/lib/python*/site-packages`) for the description, project URL,
+/// and license. Packages that declare no URL point at their PyPI page.
+pub struct PipDiscoverer;
+
+impl Discoverer for PipDiscoverer {
+    fn name(&self) -> &str {
+        "pip"
+    }
+
+    fn is_available(&self) -> bool {
+        user_base().is_some_and(|base| !python::site_packages_dirs(&base).is_empty())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("python3")
+            .args(["-m", "pip", "list", "--user", "--format=json"])
+            .output()
+            .context("Failed to run pip list")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "pip list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("pip list output is not valid UTF-8")?;
+
+        let site_packages = user_base()
+            .map(|base| python::site_packages_dirs(&base))
+            .unwrap_or_default();
+
+        parse_pip_list(&stdout, |name| {
+            python::find_dist_metadata(&site_packages, name)
+        })
+    }
+}
+
+/// Resolve the user installation base (`$PYTHONUSERBASE`, default `~/.local`).
+fn user_base() -> Option<PathBuf> {
+    std::env::var_os("PYTHONUSERBASE")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local")))
+}
+
+#[derive(Deserialize)]
+struct PipListEntry {
+    name: String,
+    version: String,
+}
+
+/// Parse `pip list --format=json` output, looking up each package's
+/// installed metadata through `lookup`.
+fn parse_pip_list(
+    output: &str,
+    lookup: impl Fn(&str) -> Option<python::DistMetadata>,
+) -> Result<Vec<InstalledPackage>> {
+    let entries: Vec<PipListEntry> =
+        serde_json::from_str(output).context("Failed to parse pip list output")?;
+
+    let pb = ProgressBar::new(entries.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("  {bar:30} {pos}/{len} packages")
+            .unwrap(),
+    );
+
+    let packages = entries
+        .into_iter()
+        .map(|entry| {
+            let mut pkg = match lookup(&entry.name) {
+                Some(dist) => {
+                    let mut pkg = dist.into_package(PackageSource::Pip);
+                    pkg.name = entry.name;
+                    pkg.version = entry.version;
+                    pkg
+                }
+                None => InstalledPackage {
+                    name: entry.name,
+                    version: entry.version,
+                    description: None,
+                    url: None,
+                    source: PackageSource::Pip,
+                    licenses: Vec::new(),
+                    funding: Vec::new(),
+                    install_reason: InstallReason::Unknown,
+                    maintainer: None,
+                },
+            };
+            // Without a declared URL, enrichment can still resolve the
+            // project through PyPI.
+            if pkg.url.is_none() {
+                pkg.url = Some(python_lock::pypi_url(&pkg.name));
+            }
+            pb.inc(1);
+            pkg
+        })
+        .collect();
+
+    pb.finish_and_clear();
+
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list_without_metadata() {
+        let output = r#"[{"name": "requests", "version": "2.32.3"}, {"name": "yt-dlp", "version": "2024.8.6"}]"#;
+        let packages = parse_pip_list(output, |_| None).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].name, "requests");
+        assert_eq!(packages[0].version, "2.32.3");
+        assert_eq!(packages[1].source, PackageSource::Pip);
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://pypi.org/project/yt-dlp/")
+        );
+    }
+
+    #[test]
+    fn parse_list_with_metadata() {
+        let output = r#"[{"name": "requests", "version": "2.32.3"}]"#;
+        let packages = parse_pip_list(output, |_| {
+            python::parse_metadata(
+                "Name: requests\nVersion: 2.32.3\nSummary: Python HTTP for Humans.\n\
+                 Project-URL: Source, https://github.com/psf/requests\nLicense: Apache-2.0\n",
+            )
+        })
+        .unwrap();
+        assert_eq!(
+            packages[0].description.as_deref(),
+            Some("Python HTTP for Humans.")
+        );
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/psf/requests")
+        );
+        assert_eq!(packages[0].licenses, vec!["Apache-2.0"]);
+    }
+
+    #[test]
+    fn parse_empty_list() {
+        assert!(parse_pip_list("[]", |_| None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid_output() {
+        assert!(parse_pip_list("not json", |_| None).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::python::{self, DistMetadata};
+use super::python_lock;
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Python applications installed via `pipx`.
+///
+/// Each application lives in its own virtual environment under
+/// `$PIPX_HOME/venvs/<name>/`. The main package name and version are read from
+/// the venv's `pipx_metadata.json`, and the description, project URL, and
+/// license from the package's `dist-info/METADATA` inside the venv.
+pub struct PipxDiscoverer;
+
+impl Discoverer for PipxDiscoverer {
+    fn name(&self) -> &str {
+        "pipx"
+    }
+
+    fn is_available(&self) -> bool {
+        pipx_venvs_dir().is_some()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let venvs_dir = pipx_venvs_dir().context("Could not find pipx venvs directory")?;
+
+        let mut venvs: Vec<PathBuf> = fs::read_dir(&venvs_dir)
+            .with_context(|| format!("Failed to read {}", venvs_dir.display()))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        venvs.sort();
+
+        let pb = ProgressBar::new(venvs.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for venv in &venvs {
+            let metadata_path = venv.join("pipx_metadata.json");
+            let parsed = fs::read_to_string(&metadata_path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_pipx_metadata(&content));
+
+            match parsed {
+                Ok(main) => {
+                    let site_packages = python::site_packages_dirs(venv);
+                    let dist = python::find_dist_metadata(&site_packages, &main.name);
+                    packages.push(build_package(main, dist));
+                }
+                Err(e) => {
+                    pb.suspend(|| {
+                        eprintln!(
+                            "  Warning: failed to parse {}: {e}",
+                            metadata_path.display()
+                        )
+                    });
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Locate pipx's `venvs` directory.
+///
+/// Honors `$PIPX_HOME`, then checks the current default
+/// (`~/.local/share/pipx`) and the legacy location (`~/.local/pipx`).
+fn pipx_venvs_dir() -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = match std::env::var_os("PIPX_HOME") {
+        Some(home) => vec![PathBuf::from(home)],
+        None => {
+            let home = PathBuf::from(std::env::var_os("HOME")?);
+            vec![home.join(".local/share/pipx"), home.join(".local/pipx")]
+        }
+    };
+
+    candidates
+        .into_iter()
+        .map(|dir| dir.join("venvs"))
+        .find(|dir| dir.is_dir())
+}
+
+/// The main package of a pipx venv.
+#[derive(Debug, PartialEq)]
+struct PipxPackage {
+    name: String,
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct PipxMetadata {
+    main_package: MainPackage,
+}
+
+#[derive(Deserialize)]
+struct MainPackage {
+    package: String,
+    package_version: Option<String>,
+}
+
+/// Parse a venv's `pipx_metadata.json`.
+fn parse_pipx_metadata(content: &str) -> Result<PipxPackage> {
+    let metadata: PipxMetadata =
+        serde_json::from_str(content).context("Invalid pipx_metadata.json")?;
+
+    Ok(PipxPackage {
+        name: metadata.main_package.package,
+        version: metadata
+            .main_package
+            .package_version
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "unknown".to_string()),
+    })
+}
+
+/// Combine the pipx record with the installed distribution's metadata, if any.
+///
+/// Without a declared URL, the package points at its PyPI page so that
+/// enrichment can still resolve the project.
+fn build_package(main: PipxPackage, dist: Option<DistMetadata>) -> InstalledPackage {
+    let mut pkg = match dist {
+        Some(dist) => {
+            let mut pkg = dist.into_package(PackageSource::Pipx);
+            pkg.name = main.name;
+            pkg.version = main.version;
+            pkg
+        }
+        None => InstalledPackage {
+            name: main.name,
+            version: main.version,
+            description: None,
+            url: None,
+            source: PackageSource::Pipx,
+            licenses: Vec::new(),
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        },
+    };
+    if pkg.url.is_none() {
+        pkg.url = Some(python_lock::pypi_url(&pkg.name));
+    }
+    pkg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_metadata_json() {
+        let json = r#"{
+            "injected_packages": {},
+            "main_package": {
+                "app_paths": [],
+                "package": "black",
+                "package_or_url": "black",
+                "package_version": "24.4.2",
+                "pip_args": []
+            },
+            "pipx_metadata_version": "0.4",
+            "python_version": "Python 3.12.3"
+        }"#;
+        let pkg = parse_pipx_metadata(json).unwrap();
+        assert_eq!(
+            pkg,
+            PipxPackage {
+                name: "black".to_string(),
+                version: "24.4.2".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_metadata_missing_version() {
+        let json = r#"{"main_package": {"package": "httpie", "package_version": ""}}"#;
+        assert_eq!(parse_pipx_metadata(json).unwrap().version, "unknown");
+    }
+
+    #[test]
+    fn parse_metadata_invalid() {
+        assert!(parse_pipx_metadata("{}").is_err());
+        assert!(parse_pipx_metadata("not json").is_err());
+    }
+
+    #[test]
+    fn build_package_without_dist_metadata() {
+        let pkg = build_package(
+            PipxPackage {
+                name: "black".to_string(),
+                version: "24.4.2".to_string(),
+            },
+            None,
+        );
+        assert_eq!(pkg.source, PackageSource::Pipx);
+        assert_eq!(pkg.url.as_deref(), Some("https://pypi.org/project/black/"));
+        assert!(pkg.licenses.is_empty());
+    }
+
+    #[test]
+    fn build_package_with_dist_metadata() {
+        let dist = python::parse_metadata(
+            "Name: Black\nVersion: 24.4.2\nHome-page: https://github.com/psf/black\nLicense: MIT\n",
+        );
+        let pkg = build_package(
+            PipxPackage {
+                name: "black".to_string(),
+                version: "24.4.2".to_string(),
+            },
+            dist,
+        );
+        assert_eq!(pkg.name, "black");
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/psf/black"));
+        assert_eq!(pkg.licenses, vec!["MIT"]);
+    }
+}
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Directories under `$XDG_DATA_HOME` holding user-installed Plasma add-ons,
+/// one sub-directory per add-on type (`plasmoids`, `look-and-feel`,
+/// `desktoptheme`, `wallpapers`, kwin `scripts` and `effects`, ...).
+const ADDON_ROOTS: &[&str] = &["plasma", "kwin"];
+
+/// Discovers KDE Plasma widgets, themes, and KWin scripts installed per user,
+/// typically from the KDE Store via "Get New Stuff".
+///
+/// Each add-on is a package directory with a `metadata.json` (Plasma 6 and
+/// late Plasma 5) or a legacy `metadata.desktop`. The plugin ID, version,
+/// website, and license are read from it; the authors are appended to the
+/// description, since store add-ons are often one-person projects with no
+/// other upstream presence.
+pub struct PlasmaDiscoverer;
+
+impl Discoverer for PlasmaDiscoverer {
+    fn name(&self) -> &str {
+        "plasma"
+    }
+
+    fn is_available(&self) -> bool {
+        data_home().is_some_and(|data| ADDON_ROOTS.iter().any(|r| data.join(r).is_dir()))
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let data = data_home().context("Could not determine XDG data directory")?;
+
+        let addons: Vec<PathBuf> = ADDON_ROOTS
+            .iter()
+            .flat_map(|root| subdirs(&data.join(root)))
+            .flat_map(|kind| subdirs(&kind))
+            .filter(|dir| {
+                dir.join("metadata.json").is_file() || dir.join("metadata.desktop").is_file()
+            })
+            .collect();
+
+        let pb = ProgressBar::new(addons.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for dir in &addons {
+            match read_addon(dir) {
+                Ok(pkg) => packages.push(pkg),
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", dir.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// `$XDG_DATA_HOME`, defaulting to `~/.local/share`.
+fn data_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+/// Read an add-on package, preferring `metadata.json` over the legacy
+/// `metadata.desktop`. The directory name is the fallback plugin ID.
+fn read_addon(dir: &Path) -> Result<InstalledPackage> {
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let metadata = match fs::read_to_string(dir.join("metadata.json")) {
+        Ok(content) => parse_metadata_json(&content)?,
+        Err(_) => parse_metadata_desktop(&fs::read_to_string(dir.join("metadata.desktop"))?)?,
+    };
+    Ok(metadata.into_package(&dir_name))
+}
+
+/// Fields shared by both metadata formats.
+#[derive(Debug, Default, PartialEq)]
+struct AddonMetadata {
+    id: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    website: Option<String>,
+    license: Option<String>,
+    authors: Vec<String>,
+}
+
+impl AddonMetadata {
+    fn into_package(self, fallback_id: &str) -> InstalledPackage {
+        let non_empty = |s: Option<String>| s.filter(|s| !s.trim().is_empty());
+
+        let description = non_empty(self.description).or(non_empty(self.name));
+        let description = match (description, self.authors.is_empty()) {
+            (Some(d), false) => Some(format!("{d} (by {})", self.authors.join(", "))),
+            (None, false) => Some(format!("by {}", self.authors.join(", "))),
+            (d, true) => d,
+        };
+
+        InstalledPackage {
+            name: non_empty(self.id).unwrap_or_else(|| fallback_id.to_string()),
+            version: non_empty(self.version).unwrap_or_else(|| "unknown".to_string()),
+            description,
+            url: non_empty(self.website),
+            source: PackageSource::Plasma,
+            licenses: non_empty(self.license).into_iter().collect(),
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MetadataJson {
+    #[serde(rename = "KPlugin")]
+    kplugin: KPlugin,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KPlugin {
+    id: Option<String>,
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    website: Option<String>,
+    license: Option<String>,
+    #[serde(default)]
+    authors: Vec<KPluginAuthor>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KPluginAuthor {
+    name: Option<String>,
+}
+
+/// Parse a `metadata.json`; everything of interest lives in `KPlugin`.
+///
+/// ```json
+/// { "KPlugin": { "Id": "org.kde.plasma.foo", "Version": "1.2",
+///   "Authors": [{ "Name": "Jane", "Email": "jane@example.org" }],
+///   "Website": "https://store.kde.org/p/123", "License": "GPL-2.0+" } }
+/// ```
+fn parse_metadata_json(content: &str) -> Result<AddonMetadata> {
+    let metadata: MetadataJson = serde_json::from_str(content).context("Invalid metadata.json")?;
+    let plugin = metadata.kplugin;
+    Ok(AddonMetadata {
+        id: plugin.id,
+        name: plugin.name,
+        description: plugin.description,
+        version: plugin.version,
+        website: plugin.website,
+        license: plugin.license,
+        authors: plugin.authors.into_iter().filter_map(|a| a.name).collect(),
+    })
+}
+
+/// Parse a legacy `metadata.desktop`.
+///
+/// ```text
+/// [Desktop Entry]
+/// Name=Foo
+/// Comment=Shows foo
+/// X-KDE-PluginInfo-Author=Jane
+/// X-KDE-PluginInfo-Name=org.kde.plasma.foo
+/// X-KDE-PluginInfo-Version=1.2
+/// X-KDE-PluginInfo-Website=https://store.kde.org/p/123
+/// X-KDE-PluginInfo-License=GPL
+/// ```
+///
+/// Localized keys (`Name[de]=`) and other groups are ignored.
+fn parse_metadata_desktop(content: &str) -> Result<AddonMetadata> {
+    let mut fields: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut in_entry = false;
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+        } else if in_entry && let Some((key, value)) = line.split_once('=') {
+            fields.insert(key.trim(), value.trim());
+        }
+    }
+
+    if fields.is_empty() {
+        anyhow::bail!("Missing [Desktop Entry] group");
+    }
+
+    let field = |key: &str| fields.get(key).map(|v| v.to_string());
+
+    Ok(AddonMetadata {
+        id: field("X-KDE-PluginInfo-Name"),
+        name: field("Name"),
+        description: field("Comment"),
+        version: field("X-KDE-PluginInfo-Version"),
+        website: field("X-KDE-PluginInfo-Website"),
+        license: field("X-KDE-PluginInfo-License"),
+        authors: field("X-KDE-PluginInfo-Author")
+            .map(|a| {
+                a.split(',')
+                    .map(str::trim)
+                    .filter(|a| !a.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_metadata() {
+        let content = r#"{
+            "KPackageStructure": "Plasma/Applet",
+            "KPlugin": {
+                "Authors": [{"Email": "zren@example.org", "Name": "Chris Holland"}],
+                "Category": "Date and Time",
+                "Description": "A calendar with events",
+                "Description[de]": "Ein Kalender",
+                "Id": "com.github.zren.eventcalendar",
+                "License": "GPL-2.0+",
+                "Name": "Event Calendar",
+                "Version": "76",
+                "Website": "https://github.com/Zren/plasma-applet-eventcalendar"
+            }
+        }"#;
+        let pkg = parse_metadata_json(content).unwrap().into_package("dir");
+        assert_eq!(pkg.name, "com.github.zren.eventcalendar");
+        assert_eq!(pkg.version, "76");
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("A calendar with events (by Chris Holland)")
+        );
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/Zren/plasma-applet-eventcalendar")
+        );
+        assert_eq!(pkg.licenses, vec!["GPL-2.0+"]);
+        assert_eq!(pkg.source, PackageSource::Plasma);
+    }
+
+    #[test]
+    fn parse_desktop_metadata() {
+        let content = "\
+[Desktop Entry]
+Name=Sweet
+Name[fr]=Doux
+Comment=A dark theme
+X-KDE-PluginInfo-Author=Eliver Lara, Someone Else
+X-KDE-PluginInfo-Name=Sweet
+X-KDE-PluginInfo-Version=3.0
+X-KDE-PluginInfo-Website=https://store.kde.org/p/1294729
+X-KDE-PluginInfo-License=GPL
+
+[Wallpaper]
+defaultWallpaperTheme=Sweet
+";
+        let metadata = parse_metadata_desktop(content).unwrap();
+        assert_eq!(metadata.authors, vec!["Eliver Lara", "Someone Else"]);
+        assert_eq!(metadata.name.as_deref(), Some("Sweet"));
+
+        let pkg = metadata.into_package("dir");
+        assert_eq!(pkg.name, "Sweet");
+        assert_eq!(pkg.version, "3.0");
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("A dark theme (by Eliver Lara, Someone Else)")
+        );
+        assert_eq!(pkg.url.as_deref(), Some("https://store.kde.org/p/1294729"));
+    }
+
+    #[test]
+    fn minimal_metadata_uses_directory_name() {
+        let pkg = parse_metadata_json(r#"{"KPlugin": {}}"#)
+            .unwrap()
+            .into_package("org.example.widget");
+        assert_eq!(pkg.name, "org.example.widget");
+        assert_eq!(pkg.version, "unknown");
+        assert!(pkg.description.is_none());
+        assert!(pkg.url.is_none());
+    }
+
+    #[test]
+    fn parse_invalid_metadata() {
+        assert!(parse_metadata_json("{}").is_err());
+        assert!(parse_metadata_desktop("Name=orphan\n").is_err());
+    }
+}
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::npm;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Discovers packages installed globally with `pnpm add -g`.
+///
+/// Runs `pnpm ls -g --json`, which reports each top-level global package with
+/// its version and install path, then reads the package's `package.json` with
+/// the npm backend's parser. Tools installed by both npm and pnpm therefore
+/// resolve to the same upstream project while keeping their own
+/// [`PackageSource`].
+pub struct PnpmDiscoverer;
+
+impl Discoverer for PnpmDiscoverer {
+    fn name(&self) -> &str {
+        "pnpm"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("pnpm")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let output = Command::new("pnpm")
+            .args(["ls", "-g", "--json"])
+            .output()
+            .context("Failed to run pnpm ls")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "pnpm ls failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("pnpm ls output is not valid UTF-8")?;
+        let globals = parse_pnpm_ls(&stdout)?;
+
+        let pb = ProgressBar::new(globals.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for global in globals {
+            let manifest = global
+                .path
+                .and_then(|path| npm::read_package_json(&path.join("package.json"), &pb));
+            packages.push(manifest.unwrap_or_default().into_package(
+                global.name,
+                global.version,
+                PackageSource::Pnpm,
+            ));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+#[derive(Deserialize)]
+struct PnpmLsProject {
+    #[serde(default)]
+    dependencies: BTreeMap<String, PnpmLsEntry>,
+}
+
+#[derive(Deserialize)]
+struct PnpmLsEntry {
+    version: Option<String>,
+    path: Option<PathBuf>,
+}
+
+/// A top-level global package reported by `pnpm ls -g --json`.
+#[derive(Debug, PartialEq)]
+struct PnpmGlobal {
+    name: String,
+    version: String,
+    /// Directory holding the package's `package.json`.
+    path: Option<PathBuf>,
+}
+
+/// Parse `pnpm ls -g --json`.
+///
+/// ```json
+/// [{ "path": "~/.local/share/pnpm/global/5",
+///    "dependencies": { "typescript": { "version": "5.5.3", "path": "..." } } }]
+/// ```
+fn parse_pnpm_ls(output: &str) -> Result<Vec<PnpmGlobal>> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let projects: Vec<PnpmLsProject> =
+        serde_json::from_str(output).context("Failed to parse pnpm ls output")?;
+
+    Ok(projects
+        .into_iter()
+        .flat_map(|project| project.dependencies)
+        .map(|(name, entry)| PnpmGlobal {
+            name,
+            version: entry.version.unwrap_or_else(|| "unknown".to_string()),
+            path: entry.path,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn parse_ls_output() {
+        let output = r#"[
+  {
+    "path": "/home/user/.local/share/pnpm/global/5",
+    "private": false,
+    "dependencies": {
+      "typescript": {
+        "from": "typescript",
+        "version": "5.5.3",
+        "resolved": "https://registry.npmjs.org/typescript/-/typescript-5.5.3.tgz",
+        "path": "/home/user/.local/share/pnpm/global/5/node_modules/.pnpm/typescript@5.5.3/node_modules/typescript"
+      },
+      "@biomejs/biome": {
+        "from": "@biomejs/biome",
+        "version": "1.8.3"
+      }
+    }
+  }
+]"#;
+        let globals = parse_pnpm_ls(output).unwrap();
+        assert_eq!(globals.len(), 2);
+
+        assert_eq!(globals[0].name, "@biomejs/biome");
+        assert_eq!(globals[0].version, "1.8.3");
+        assert!(globals[0].path.is_none());
+
+        assert_eq!(globals[1].name, "typescript");
+        assert_eq!(
+            globals[1].path.as_deref(),
+            Some(Path::new(
+                "/home/user/.local/share/pnpm/global/5/node_modules/.pnpm/typescript@5.5.3/node_modules/typescript"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_ls_empty() {
+        assert!(parse_pnpm_ls("").unwrap().is_empty());
+        assert!(parse_pnpm_ls("[]").unwrap().is_empty());
+        assert!(parse_pnpm_ls(r#"[{"path": "/x"}]"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_ls_invalid() {
+        assert!(parse_pnpm_ls("not json").is_err());
+    }
+}
@@ -20,7 +20,15 @@ use super::{Discoverer, InstalledPackage, PackageSource};
 ///
 /// Podman supports both rootful and rootless modes; this discoverer queries
 /// the current user's image store.
-pub struct PodmanDiscoverer;
+///
+/// Images sharing a digest (the same image under several tags or names) are
+/// reported once. When network access has been opted into (`enrich = true`),
+/// images without OCI source labels are looked up in their registry for the
+/// labels and annotations published there.
+pub struct PodmanDiscoverer {
+    /// Whether to query image registries for missing labels.
+    pub query_registry: bool,
+}
 
 impl Discoverer for PodmanDiscoverer {
     fn name(&self) -> &str {
@@ -47,7 +55,12 @@ impl Discoverer for PodmanDiscoverer {
         let stdout = String::from_utf8(output.stdout)
             .context("podman image ls output is not valid UTF-8")?;
 
-        let images = parse_image_list(&stdout)?;
+        let images = oci::dedupe_by_digest(parse_image_list(&stdout)?, |image| &image.id);
+        let client = if self.query_registry {
+            Some(oci::registry_client()?)
+        } else {
+            None
+        };
 
         let pb = ProgressBar::new(images.len() as u64);
         pb.set_style(
@@ -59,8 +72,15 @@ impl Discoverer for PodmanDiscoverer {
         let packages: Vec<InstalledPackage> = images
             .iter()
             .map(|image| {
-                let labels = fetch_image_labels(&image.id);
+                let mut labels = fetch_image_labels(&image.id);
                 let (name, tag) = image.name_and_tag();
+                if let Some(client) = &client
+                    && let Err(e) = oci::merge_registry_labels(client, &name, &tag, &mut labels)
+                {
+                    pb.suspend(|| {
+                        eprintln!("  Warning: registry lookup failed for {name}:{tag}: {e}")
+                    });
+                }
                 let pkg =
                     oci::build_package_from_labels(&name, &tag, &labels, PackageSource::Podman);
                 pb.inc(1);
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Shared helpers for discoverers that read dependency lockfiles from the
+//! user's development directories (configured via `[discover] project_dirs`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How deep below each configured directory to look for lockfiles.
+const MAX_DEPTH: usize = 4;
+
+/// Directories that never contain a project's own lockfile but can be huge.
+const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "venv",
+    "__pycache__",
+    "site-packages",
+];
+
+/// Expand a leading `~` to the home directory.
+pub(super) fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(rest),
+            None => path.to_path_buf(),
+        },
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Find files named one of `names` under `roots`.
+///
+/// Hidden directories and well-known dependency/build directories are not
+/// descended into, and the search stops [`MAX_DEPTH`] levels below each
+/// root. Results are sorted for stable output.
+pub(super) fn find_files(roots: &[PathBuf], names: &[&str]) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    for root in roots {
+        walk(&expand_home(root), names, 0, &mut found);
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn walk(dir: &Path, names: &[&str], depth: usize, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if depth < MAX_DEPTH
+                && !file_name.starts_with('.')
+                && !SKIP_DIRS.contains(&file_name.as_ref())
+            {
+                walk(&entry.path(), names, depth + 1, found);
+            }
+        } else if names.contains(&file_name.as_ref()) {
+            found.push(entry.path());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_lockfiles_and_skips_ignored_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for dir in ["app", "app/node_modules/dep", ".hidden", "a/b/c/d/e"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("app/poetry.lock"), "").unwrap();
+        fs::write(root.join("app/node_modules/dep/poetry.lock"), "").unwrap();
+        fs::write(root.join(".hidden/poetry.lock"), "").unwrap();
+        fs::write(root.join("a/b/c/d/poetry.lock"), "").unwrap();
+        fs::write(root.join("a/b/c/d/e/poetry.lock"), "").unwrap();
+        fs::write(root.join("app/README.md"), "").unwrap();
+
+        let found = find_files(&[root.to_path_buf()], &["poetry.lock"]);
+        assert_eq!(
+            found,
+            vec![
+                root.join("a/b/c/d/poetry.lock"),
+                root.join("app/poetry.lock")
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_root_is_ignored() {
+        let found = find_files(&[PathBuf::from("/nonexistent/syld")], &["poetry.lock"]);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn expand_home_only_touches_leading_tilde() {
+        assert_eq!(expand_home(Path::new("/work")), PathBuf::from("/work"));
+        assert_eq!(
+            expand_home(Path::new("src/~foo")),
+            PathBuf::from("src/~foo")
+        );
+    }
+}
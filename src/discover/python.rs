@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Shared helpers for Python package discoverers (pipx, pip, uv).
+//!
+//! Installed Python distributions carry their metadata in a
+//! `<name>-<version>.dist-info/METADATA` file using the RFC 822-style core
+//! metadata format. This module locates and parses those files so that each
+//! backend only needs to know where its environments live.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{InstallReason, InstalledPackage, PackageSource};
+
+/// Core metadata of an installed Python distribution.
+#[derive(Debug, Default, PartialEq)]
+pub struct DistMetadata {
+    pub name: String,
+    pub version: String,
+    pub summary: Option<String>,
+    pub home_page: Option<String>,
+    /// `Project-URL` entries as `(label, url)` pairs.
+    pub project_urls: Vec<(String, String)>,
+    pub license: Option<String>,
+}
+
+/// `Project-URL` labels that point at the source repository, in order of
+/// preference.
+const SOURCE_LABELS: &[&str] = &["source", "source code", "repository", "code", "github"];
+
+impl DistMetadata {
+    /// Pick the best upstream URL: a source repository link if one is
+    /// declared, then `Home-page`, then a `Homepage` project URL.
+    pub fn upstream_url(&self) -> Option<String> {
+        let by_label = |label: &str| {
+            self.project_urls
+                .iter()
+                .find(|(l, _)| l.eq_ignore_ascii_case(label))
+                .map(|(_, url)| url.clone())
+        };
+
+        SOURCE_LABELS
+            .iter()
+            .find_map(|label| by_label(label))
+            .or_else(|| self.home_page.clone())
+            .or_else(|| by_label("homepage"))
+    }
+
+    /// Convert into an [`InstalledPackage`] attributed to `source`.
+    pub fn into_package(self, source: PackageSource) -> InstalledPackage {
+        let url = self.upstream_url();
+        InstalledPackage {
+            name: self.name,
+            version: self.version,
+            description: self.summary,
+            url,
+            source,
+            licenses: self.license.into_iter().collect(),
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        }
+    }
+}
+
+/// Parse the contents of a `METADATA` (or `PKG-INFO`) file.
+///
+/// Only the header block is read; the long description body after the first
+/// blank line is ignored. Returns `None` if `Name` or `Version` is missing.
+pub fn parse_metadata(content: &str) -> Option<DistMetadata> {
+    let mut meta = DistMetadata::default();
+    let mut license_expression = None;
+    let mut license = None;
+    let mut license_is_multiline = false;
+    let mut last_key = "";
+
+    for line in content.lines() {
+        if line.is_empty() {
+            break;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            if last_key == "License" {
+                license_is_multiline = true;
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        last_key = key;
+
+        let non_empty = || (!value.is_empty() && value != "UNKNOWN").then(|| value.to_string());
+
+        match key {
+            "Name" => meta.name = value.to_string(),
+            "Version" => meta.version = value.to_string(),
+            "Summary" => meta.summary = non_empty(),
+            "Home-page" => meta.home_page = non_empty(),
+            "License-Expression" => license_expression = non_empty(),
+            "License" => license = non_empty(),
+            "Project-URL" => {
+                if let Some((label, url)) = value.split_once(',') {
+                    meta.project_urls
+                        .push((label.trim().to_string(), url.trim().to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if meta.name.is_empty() || meta.version.is_empty() {
+        return None;
+    }
+
+    // Some projects paste their entire license text into `License`; only keep
+    // it when it looks like an identifier.
+    meta.license =
+        license_expression.or_else(|| license.filter(|l| !license_is_multiline && l.len() <= 64));
+
+    Some(meta)
+}
+
+/// Normalize a distribution name per PEP 503 (lowercase, runs of `-`, `_`,
+/// and `.` collapsed to a single `-`).
+pub fn normalize_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut prev_sep = false;
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !prev_sep {
+                out.push('-');
+            }
+            prev_sep = true;
+        } else {
+            out.extend(c.to_lowercase());
+            prev_sep = false;
+        }
+    }
+    out
+}
+
+/// List `lib/python*/site-packages` directories under an environment prefix.
+pub fn site_packages_dirs(prefix: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(prefix.join("lib")) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with("python"))
+        .map(|e| e.path().join("site-packages"))
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Find and parse the metadata for distribution `name` in the given
+/// site-packages directories.
+pub fn find_dist_metadata(site_packages: &[PathBuf], name: &str) -> Option<DistMetadata> {
+    let wanted = normalize_name(name);
+
+    site_packages.iter().find_map(|dir| {
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                let file_name = e.file_name();
+                let file_name = file_name.to_string_lossy();
+                file_name
+                    .strip_suffix(".dist-info")
+                    .and_then(|stem| stem.split_once('-'))
+                    .is_some_and(|(dist, _)| normalize_name(dist) == wanted)
+            })
+            .find_map(|e| {
+                let content = fs::read_to_string(e.path().join("METADATA")).ok()?;
+                parse_metadata(&content)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK_METADATA: &str = "\
+Metadata-Version: 2.3
+Name: black
+Version: 24.4.2
+Summary: The uncompromising code formatter.
+Project-URL: Changelog, https://github.com/psf/black/blob/main/CHANGES.md
+Project-URL: Homepage, https://github.com/psf/black
+Project-URL: Source, https://github.com/psf/black
+License: MIT
+Classifier: License :: OSI Approved :: MIT License
+
+Long description here.
+Name: not-a-header
+";
+
+    #[test]
+    fn parse_full_metadata() {
+        let meta = parse_metadata(BLACK_METADATA).unwrap();
+        assert_eq!(meta.name, "black");
+        assert_eq!(meta.version, "24.4.2");
+        assert_eq!(
+            meta.summary.as_deref(),
+            Some("The uncompromising code formatter.")
+        );
+        assert_eq!(meta.project_urls.len(), 3);
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            meta.upstream_url().as_deref(),
+            Some("https://github.com/psf/black")
+        );
+    }
+
+    #[test]
+    fn parse_missing_version_is_none() {
+        assert!(parse_metadata("Name: foo\n").is_none());
+    }
+
+    #[test]
+    fn upstream_url_falls_back_to_home_page() {
+        let meta = parse_metadata("Name: foo\nVersion: 1.0\nHome-page: https://foo.org\n").unwrap();
+        assert_eq!(meta.upstream_url().as_deref(), Some("https://foo.org"));
+    }
+
+    #[test]
+    fn unknown_values_are_dropped() {
+        let meta =
+            parse_metadata("Name: foo\nVersion: 1.0\nHome-page: UNKNOWN\nLicense: UNKNOWN\n")
+                .unwrap();
+        assert!(meta.home_page.is_none());
+        assert!(meta.license.is_none());
+    }
+
+    #[test]
+    fn license_expression_preferred() {
+        let meta = parse_metadata(
+            "Name: foo\nVersion: 1.0\nLicense: Apache Software License\nLicense-Expression: Apache-2.0\n",
+        )
+        .unwrap();
+        assert_eq!(meta.license.as_deref(), Some("Apache-2.0"));
+    }
+
+    #[test]
+    fn multiline_license_text_is_ignored() {
+        let meta = parse_metadata(
+            "Name: foo\nVersion: 1.0\nLicense: Copyright (c) 2024\n        Permission is hereby granted\n",
+        )
+        .unwrap();
+        assert!(meta.license.is_none());
+    }
+
+    #[test]
+    fn normalize_names() {
+        assert_eq!(
+            normalize_name("Django_REST.framework"),
+            "django-rest-framework"
+        );
+        assert_eq!(normalize_name("foo__bar"), "foo-bar");
+    }
+
+    #[test]
+    fn find_metadata_in_site_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("lib/python3.12/site-packages");
+        let dist_info = site.join("black-24.4.2.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("METADATA"), BLACK_METADATA).unwrap();
+
+        let dirs = site_packages_dirs(dir.path());
+        assert_eq!(dirs, vec![site]);
+
+        let meta = find_dist_metadata(&dirs, "Black").unwrap();
+        assert_eq!(meta.version, "24.4.2");
+        assert!(find_dist_metadata(&dirs, "isort").is_none());
+    }
+
+    #[test]
+    fn into_package_sets_source() {
+        let pkg = parse_metadata(BLACK_METADATA)
+            .unwrap()
+            .into_package(PackageSource::Pipx);
+        assert_eq!(pkg.source, PackageSource::Pipx);
+        assert_eq!(pkg.url.as_deref(), Some("https://github.com/psf/black"));
+        assert_eq!(pkg.licenses, vec!["MIT"]);
+    }
+}
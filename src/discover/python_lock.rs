@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::projects;
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Python libraries locked by Poetry or Pipenv projects.
+///
+/// Opt-in: only runs when `[discover] project_dirs` is configured. Those
+/// directories are searched for `poetry.lock` and `Pipfile.lock` files, and
+/// every locked dependency is reported with its PyPI project page (or git
+/// repository) as its URL. Dependencies shared by several projects are
+/// reported once per version.
+pub struct PythonLockDiscoverer {
+    /// Development directories to search for lockfiles.
+    pub project_dirs: Vec<PathBuf>,
+}
+
+const LOCKFILE_NAMES: [&str; 2] = ["poetry.lock", "Pipfile.lock"];
+
+impl Discoverer for PythonLockDiscoverer {
+    fn name(&self) -> &str {
+        "python-lockfile"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.project_dirs.is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let lockfiles = projects::find_files(&self.project_dirs, &LOCKFILE_NAMES);
+
+        let pb = ProgressBar::new(lockfiles.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} lockfiles")
+                .unwrap(),
+        );
+
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+        for path in &lockfiles {
+            match parse_lockfile(path) {
+                Ok(packages) => {
+                    for pkg in packages {
+                        by_key
+                            .entry((pkg.name.clone(), pkg.version.clone()))
+                            .or_insert(pkg);
+                    }
+                }
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+fn parse_lockfile(path: &Path) -> Result<Vec<InstalledPackage>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if path.file_name().is_some_and(|n| n == "Pipfile.lock") {
+        parse_pipfile_lock(&content)
+    } else {
+        parse_poetry_lock(&content)
+    }
+}
+
+/// PyPI project page for a distribution.
+pub(super) fn pypi_url(name: &str) -> String {
+    format!("https://pypi.org/project/{name}/")
+}
+
+fn locked_package(
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    url: Option<String>,
+) -> InstalledPackage {
+    InstalledPackage {
+        name,
+        version: version
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "unknown".to_string()),
+        description: description.filter(|d| !d.is_empty()),
+        url,
+        source: PackageSource::PythonLockfile,
+        licenses: Vec::new(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[derive(Deserialize)]
+struct PoetryLock {
+    #[serde(default)]
+    package: Vec<PoetryPackage>,
+}
+
+#[derive(Deserialize)]
+struct PoetryPackage {
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    source: Option<PoetrySource>,
+}
+
+#[derive(Deserialize)]
+struct PoetrySource {
+    #[serde(rename = "type")]
+    kind: String,
+    url: Option<String>,
+}
+
+/// Parse a `poetry.lock` file.
+///
+/// Packages without a `[package.source]` table come from PyPI. Git sources
+/// use the repository URL; local paths and private indexes get no URL.
+fn parse_poetry_lock(content: &str) -> Result<Vec<InstalledPackage>> {
+    let lock: PoetryLock = toml::from_str(content).context("Invalid poetry.lock")?;
+
+    Ok(lock
+        .package
+        .into_iter()
+        .map(|p| {
+            let url = match p.source {
+                None => Some(pypi_url(&p.name)),
+                Some(source) if source.kind == "git" => source.url,
+                Some(_) => None,
+            };
+            locked_package(p.name, p.version, p.description, url)
+        })
+        .collect())
+}
+
+/// Parse a `Pipfile.lock` file.
+///
+/// Both the `default` and `develop` sections are read. Versions are pinned
+/// as `==x.y.z`; git dependencies have a `git` URL instead of a version.
+fn parse_pipfile_lock(content: &str) -> Result<Vec<InstalledPackage>> {
+    let lock: Value = serde_json::from_str(content).context("Invalid Pipfile.lock")?;
+
+    let mut packages = Vec::new();
+    for section in ["default", "develop"] {
+        let Some(entries) = lock.get(section).and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, entry) in entries {
+            let version = entry
+                .get("version")
+                .and_then(Value::as_str)
+                .map(|v| v.trim_start_matches("==").to_string());
+            let url = match entry.get("git").and_then(Value::as_str) {
+                Some(git) => Some(git.to_string()),
+                None if entry.get("path").is_some() || entry.get("file").is_some() => None,
+                None => Some(pypi_url(name)),
+            };
+            packages.push(locked_package(name.clone(), version, None, url));
+        }
+    }
+
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_poetry_lock_packages() {
+        let content = r#"
+[[package]]
+name = "requests"
+version = "2.32.3"
+description = "Python HTTP for Humans."
+optional = false
+python-versions = ">=3.8"
+files = [
+    {file = "requests-2.32.3-py3-none-any.whl", hash = "sha256:abc"},
+]
+
+[package.dependencies]
+certifi = ">=2017.4.17"
+
+[[package]]
+name = "mylib"
+version = "0.1.0"
+description = ""
+optional = false
+python-versions = "*"
+files = []
+
+[package.source]
+type = "git"
+url = "https://github.com/example/mylib.git"
+reference = "main"
+resolved_reference = "0123abcd"
+
+[[package]]
+name = "local-helper"
+version = "0.0.1"
+description = "Local helper"
+
+[package.source]
+type = "directory"
+url = "../helper"
+
+[metadata]
+lock-version = "2.0"
+"#;
+        let packages = parse_poetry_lock(content).unwrap();
+        assert_eq!(packages.len(), 3);
+
+        assert_eq!(packages[0].name, "requests");
+        assert_eq!(packages[0].version, "2.32.3");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://pypi.org/project/requests/")
+        );
+        assert_eq!(
+            packages[0].description.as_deref(),
+            Some("Python HTTP for Humans.")
+        );
+        assert_eq!(packages[0].source, PackageSource::PythonLockfile);
+
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/example/mylib.git")
+        );
+        assert!(packages[1].description.is_none());
+
+        assert!(packages[2].url.is_none());
+    }
+
+    #[test]
+    fn parse_pipfile_lock_sections() {
+        let content = r#"{
+    "_meta": {"hash": {"sha256": "abc"}, "pipfile-spec": 6},
+    "default": {
+        "flask": {"hashes": ["sha256:abc"], "index": "pypi", "version": "==3.0.3"},
+        "mylib": {"git": "https://github.com/example/mylib.git", "ref": "0123abcd"}
+    },
+    "develop": {
+        "pytest": {"hashes": [], "version": "==8.2.2"},
+        "local": {"path": "./local", "editable": true}
+    }
+}"#;
+        let packages = parse_pipfile_lock(content).unwrap();
+        assert_eq!(packages.len(), 4);
+
+        assert_eq!(packages[0].name, "flask");
+        assert_eq!(packages[0].version, "3.0.3");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://pypi.org/project/flask/")
+        );
+
+        assert_eq!(packages[1].version, "unknown");
+        assert_eq!(
+            packages[1].url.as_deref(),
+            Some("https://github.com/example/mylib.git")
+        );
+
+        assert!(packages.iter().any(|p| p.name == "pytest"));
+        let local = packages.iter().find(|p| p.name == "local").unwrap();
+        assert!(local.url.is_none());
+    }
+
+    #[test]
+    fn parse_invalid_lockfiles() {
+        assert!(parse_poetry_lock("[[package]]\nversion = 1").is_err());
+        assert!(parse_pipfile_lock("not json").is_err());
+    }
+
+    #[test]
+    fn discover_dedupes_across_projects() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lock = "[[package]]\nname = \"rich\"\nversion = \"13.7.1\"\n";
+        for project in ["one", "two"] {
+            fs::create_dir_all(tmp.path().join(project)).unwrap();
+            fs::write(tmp.path().join(project).join("poetry.lock"), lock).unwrap();
+        }
+
+        let discoverer = PythonLockDiscoverer {
+            project_dirs: vec![tmp.path().to_path_buf()],
+        };
+        assert!(discoverer.is_available());
+
+        let packages = discoverer.discover().unwrap();
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].name, "rich");
+    }
+}
@@ -0,0 +1,302 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Rust toolchains and components managed by rustup.
+///
+/// Runs `rustup toolchain list` for the installed toolchains and
+/// `rustup component list --installed --toolchain <name>` for each one. Every
+/// toolchain and component is attributed to its rust-lang repository (e.g.
+/// `clippy` to `rust-lang/rust-clippy`), so the Rust project shows up as an
+/// upstream even though nothing was installed through a package manager.
+/// rustup itself is reported as well.
+pub struct RustupDiscoverer;
+
+/// License shared by the Rust toolchain, its components, and rustup.
+const RUST_LICENSE: &str = "MIT OR Apache-2.0";
+
+impl Discoverer for RustupDiscoverer {
+    fn name(&self) -> &str {
+        "rustup"
+    }
+
+    fn is_available(&self) -> bool {
+        rustup_home().is_some_and(|home| home.join("toolchains").is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let toolchains = parse_toolchain_list(&run_rustup(&["toolchain", "list"])?);
+
+        let pb = ProgressBar::new(toolchains.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        if let Ok(version) = run_rustup(&["--version"]) {
+            packages.push(rust_package(
+                "rustup",
+                parse_rustup_version(&version),
+                "The Rust toolchain installer",
+                "https://github.com/rust-lang/rustup",
+            ));
+        }
+
+        for toolchain in &toolchains {
+            let channel = toolchain_channel(toolchain);
+            packages.push(rust_package(
+                toolchain,
+                channel.to_string(),
+                "Rust toolchain",
+                "https://github.com/rust-lang/rust",
+            ));
+
+            match run_rustup(&["component", "list", "--installed", "--toolchain", toolchain]) {
+                Ok(output) => {
+                    for component in parse_component_list(&output, toolchain) {
+                        let url = component_repository(&component);
+                        packages.push(rust_package(
+                            &component,
+                            channel.to_string(),
+                            &format!("Rust toolchain component ({toolchain})"),
+                            url,
+                        ));
+                    }
+                }
+                Err(e) => {
+                    pb.suspend(|| {
+                        eprintln!("  Warning: failed to list components of {toolchain}: {e}")
+                    });
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// `$RUSTUP_HOME`, defaulting to `~/.rustup`.
+fn rustup_home() -> Option<PathBuf> {
+    std::env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".rustup")))
+}
+
+/// Run a rustup subcommand and return its stdout.
+fn run_rustup(args: &[&str]) -> Result<String> {
+    let output = Command::new("rustup")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run rustup {}", args[0]))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "rustup {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("rustup output is not valid UTF-8")
+}
+
+fn rust_package(name: &str, version: String, description: &str, url: &str) -> InstalledPackage {
+    InstalledPackage {
+        name: name.to_string(),
+        version,
+        description: Some(description.to_string()),
+        url: Some(url.to_string()),
+        source: PackageSource::Rustup,
+        licenses: vec![RUST_LICENSE.to_string()],
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+/// Parse `rustup toolchain list`, dropping the `(default)` / `(active)`
+/// annotations.
+fn parse_toolchain_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| *name != "no" && !name.starts_with('('))
+        .map(String::from)
+        .collect()
+}
+
+/// Extract the version from `rustup --version` (`rustup 1.27.1 (54dd3d00f 2024-04-24)`).
+fn parse_rustup_version(output: &str) -> String {
+    output
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// The release channel of a toolchain name, without its host triple.
+///
+/// `stable-x86_64-unknown-linux-gnu` → `stable`,
+/// `nightly-2024-06-01-aarch64-apple-darwin` → `nightly-2024-06-01`,
+/// `1.79.0-x86_64-pc-windows-msvc` → `1.79.0`. Custom (linked) toolchains
+/// are returned unchanged.
+fn toolchain_channel(toolchain: &str) -> &str {
+    match toolchain_host(toolchain) {
+        Some(host) => &toolchain[..toolchain.len() - host.len() - 1],
+        None => toolchain,
+    }
+}
+
+/// The host triple suffix of a toolchain name, if it has one.
+///
+/// Triples are recognized by their architecture component, which is the
+/// first segment after the channel.
+fn toolchain_host(toolchain: &str) -> Option<&str> {
+    const ARCHES: [&str; 12] = [
+        "x86_64",
+        "i686",
+        "i586",
+        "aarch64",
+        "arm",
+        "armv7",
+        "loongarch64",
+        "powerpc",
+        "powerpc64",
+        "powerpc64le",
+        "riscv64gc",
+        "s390x",
+    ];
+    toolchain.match_indices('-').find_map(|(i, _)| {
+        let rest = &toolchain[i + 1..];
+        let arch = rest.split('-').next()?;
+        ARCHES.contains(&arch).then_some(rest)
+    })
+}
+
+/// Parse `rustup component list --installed`, stripping the host triple from
+/// component names (`cargo-x86_64-unknown-linux-gnu` → `cargo`).
+///
+/// Cross-compilation targets keep their triple
+/// (`rust-std-wasm32-unknown-unknown`), since they are distinct components.
+fn parse_component_list(output: &str, toolchain: &str) -> Vec<String> {
+    let host_suffix = toolchain_host(toolchain).map(|host| format!("-{host}"));
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            host_suffix
+                .as_deref()
+                .and_then(|suffix| line.strip_suffix(suffix))
+                .unwrap_or(line)
+                .to_string()
+        })
+        .collect()
+}
+
+/// Upstream repository of a toolchain component.
+///
+/// Tools developed in their own rust-lang repositories map there; everything
+/// else (rustc, rust-std, rust-src, rust-docs, llvm-tools, ...) is built from
+/// `rust-lang/rust`.
+fn component_repository(component: &str) -> &'static str {
+    match component {
+        "cargo" => "https://github.com/rust-lang/cargo",
+        "clippy" | "clippy-preview" => "https://github.com/rust-lang/rust-clippy",
+        "rustfmt" | "rustfmt-preview" => "https://github.com/rust-lang/rustfmt",
+        "rust-analyzer" | "rust-analyzer-preview" => "https://github.com/rust-lang/rust-analyzer",
+        "miri" | "miri-preview" => "https://github.com/rust-lang/miri",
+        "rls" | "rls-preview" => "https://github.com/rust-lang/rls",
+        _ => "https://github.com/rust-lang/rust",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_toolchains() {
+        let output = "\
+stable-x86_64-unknown-linux-gnu (active, default)
+nightly-2024-06-01-x86_64-unknown-linux-gnu
+1.79.0-x86_64-unknown-linux-gnu
+my-local
+";
+        assert_eq!(
+            parse_toolchain_list(output),
+            vec![
+                "stable-x86_64-unknown-linux-gnu",
+                "nightly-2024-06-01-x86_64-unknown-linux-gnu",
+                "1.79.0-x86_64-unknown-linux-gnu",
+                "my-local",
+            ]
+        );
+        assert!(parse_toolchain_list("no installed toolchains\n").is_empty());
+    }
+
+    #[test]
+    fn channels_strip_host_triple() {
+        assert_eq!(
+            toolchain_channel("stable-x86_64-unknown-linux-gnu"),
+            "stable"
+        );
+        assert_eq!(
+            toolchain_channel("nightly-2024-06-01-aarch64-apple-darwin"),
+            "nightly-2024-06-01"
+        );
+        assert_eq!(toolchain_channel("1.79.0-x86_64-pc-windows-msvc"), "1.79.0");
+        assert_eq!(toolchain_channel("my-local"), "my-local");
+    }
+
+    #[test]
+    fn parse_components() {
+        let output = "\
+cargo-x86_64-unknown-linux-gnu
+clippy-x86_64-unknown-linux-gnu
+rust-src
+rust-std-wasm32-unknown-unknown
+rust-std-x86_64-unknown-linux-gnu
+";
+        let components = parse_component_list(output, "stable-x86_64-unknown-linux-gnu");
+        assert_eq!(
+            components,
+            vec![
+                "cargo",
+                "clippy",
+                "rust-src",
+                "rust-std-wasm32-unknown-unknown",
+                "rust-std"
+            ]
+        );
+        assert_eq!(
+            component_repository(&components[1]),
+            "https://github.com/rust-lang/rust-clippy"
+        );
+        assert_eq!(
+            component_repository(&components[2]),
+            "https://github.com/rust-lang/rust"
+        );
+    }
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            parse_rustup_version("rustup 1.27.1 (54dd3d00f 2024-04-24)\n"),
+            "1.27.1"
+        );
+        assert_eq!(parse_rustup_version(""), "unknown");
+    }
+}
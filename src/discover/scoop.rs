@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers apps installed via Scoop (Windows).
+///
+/// Reads `<scoop>/apps/<app>/current/manifest.json` for every installed app,
+/// where `<scoop>` is `$SCOOP` or `~/scoop`. The `current` directory is a
+/// junction to the active version, so its manifest describes what is in use.
+pub struct ScoopDiscoverer;
+
+impl Discoverer for ScoopDiscoverer {
+    fn name(&self) -> &str {
+        "scoop"
+    }
+
+    fn is_available(&self) -> bool {
+        scoop_apps_dir().is_some()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let apps_dir = scoop_apps_dir().context("Could not find scoop apps directory")?;
+
+        let mut manifests: Vec<(String, PathBuf)> = fs::read_dir(&apps_dir)
+            .with_context(|| format!("Failed to read {}", apps_dir.display()))?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                let manifest = e.path().join("current").join("manifest.json");
+                manifest.is_file().then_some((name, manifest))
+            })
+            .collect();
+        manifests.sort();
+
+        let pb = ProgressBar::new(manifests.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (name, path) in &manifests {
+            let parsed = fs::read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_manifest(name, &content));
+            match parsed {
+                Ok(pkg) => packages.push(pkg),
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Locate Scoop's `apps` directory, honoring `$SCOOP`.
+fn scoop_apps_dir() -> Option<PathBuf> {
+    let root = match std::env::var_os("SCOOP") {
+        Some(root) => PathBuf::from(root),
+        None => directories::BaseDirs::new()?.home_dir().join("scoop"),
+    };
+    let apps = root.join("apps");
+    apps.is_dir().then_some(apps)
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    version: Option<String>,
+    description: Option<String>,
+    homepage: Option<String>,
+    license: Option<Value>,
+}
+
+/// Parse an app's `manifest.json`.
+///
+/// The app name is not part of the manifest; it is the name of the app's
+/// directory. `license` is either an SPDX string or an object with an
+/// `identifier` and `url`.
+fn parse_manifest(name: &str, content: &str) -> Result<InstalledPackage> {
+    let manifest: Manifest = serde_json::from_str(content).context("Invalid manifest.json")?;
+
+    let license = match manifest.license {
+        Some(Value::String(s)) => Some(s),
+        Some(Value::Object(obj)) => obj
+            .get("identifier")
+            .and_then(Value::as_str)
+            .map(String::from),
+        _ => None,
+    };
+
+    let non_empty = |s: Option<String>| s.filter(|s| !s.trim().is_empty());
+
+    Ok(InstalledPackage {
+        name: name.to_string(),
+        version: non_empty(manifest.version).unwrap_or_else(|| "unknown".to_string()),
+        description: non_empty(manifest.description),
+        url: non_empty(manifest.homepage),
+        source: PackageSource::Scoop,
+        licenses: non_empty(license).into_iter().collect(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_with_string_license() {
+        let content = r#"{
+            "version": "2.45.2",
+            "description": "Distributed version control system",
+            "homepage": "https://gitforwindows.org",
+            "license": "GPL-2.0-only",
+            "architecture": {}
+        }"#;
+        let pkg = parse_manifest("git", content).unwrap();
+        assert_eq!(pkg.name, "git");
+        assert_eq!(pkg.version, "2.45.2");
+        assert_eq!(
+            pkg.description.as_deref(),
+            Some("Distributed version control system")
+        );
+        assert_eq!(pkg.url.as_deref(), Some("https://gitforwindows.org"));
+        assert_eq!(pkg.licenses, vec!["GPL-2.0-only"]);
+        assert_eq!(pkg.source, PackageSource::Scoop);
+    }
+
+    #[test]
+    fn parse_manifest_with_object_license() {
+        let content = r#"{
+            "version": "24.07",
+            "homepage": "https://www.7-zip.org/",
+            "license": {
+                "identifier": "Freeware,LGPL-2.1-only,BSD-3-Clause",
+                "url": "https://www.7-zip.org/license.txt"
+            }
+        }"#;
+        let pkg = parse_manifest("7zip", content).unwrap();
+        assert_eq!(pkg.licenses, vec!["Freeware,LGPL-2.1-only,BSD-3-Clause"]);
+        assert!(pkg.description.is_none());
+    }
+
+    #[test]
+    fn parse_minimal_manifest() {
+        let pkg = parse_manifest("foo", "{}").unwrap();
+        assert_eq!(pkg.version, "unknown");
+        assert!(pkg.url.is_none());
+        assert!(pkg.licenses.is_empty());
+    }
+
+    #[test]
+    fn parse_invalid_manifest() {
+        assert!(parse_manifest("foo", "not json").is_err());
+    }
+}
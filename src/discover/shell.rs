@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::git;
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers zsh and fish plugins managed by common plugin frameworks.
+///
+/// - oh-my-zsh: the framework checkout itself (`$ZSH`, default
+///   `~/.oh-my-zsh`) plus third-party plugins and themes cloned into
+///   `$ZSH_CUSTOM/{plugins,themes}`. Bundled plugins are part of oh-my-zsh
+///   and not reported separately.
+/// - zinit: plugins cloned to `<zinit home>/plugins/<owner>---<repo>`, and
+///   zinit itself.
+/// - fisher: the `fish_plugins` file listing `owner/repo` specifiers (fisher
+///   keeps no checkouts, so versions are unknown).
+///
+/// Checkouts are reported via [`git::checkout_package`].
+pub struct ShellPluginDiscoverer;
+
+impl Discoverer for ShellPluginDiscoverer {
+    fn name(&self) -> &str {
+        "shell-plugin"
+    }
+
+    fn is_available(&self) -> bool {
+        oh_my_zsh_dir().is_some_and(|d| d.is_dir())
+            || zinit_home().is_some_and(|d| d.is_dir())
+            || fish_plugins_file().is_some_and(|f| f.is_file())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let mut checkouts: Vec<(PathBuf, Option<String>)> = Vec::new();
+
+        if let Some(omz) = oh_my_zsh_dir().filter(|d| d.is_dir()) {
+            let custom = std::env::var_os("ZSH_CUSTOM")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| omz.join("custom"));
+            checkouts.push((omz, Some("oh-my-zsh".to_string())));
+            for kind in ["plugins", "themes"] {
+                checkouts.extend(
+                    subdirs(&custom.join(kind))
+                        .into_iter()
+                        .filter(|dir| dir.file_name().is_some_and(|n| n != "example"))
+                        .map(|dir| (dir, None)),
+                );
+            }
+        }
+
+        if let Some(zinit) = zinit_home().filter(|d| d.is_dir()) {
+            checkouts.push((zinit.join("zinit.git"), Some("zinit".to_string())));
+            checkouts.extend(
+                subdirs(&zinit.join("plugins"))
+                    .into_iter()
+                    .filter_map(|dir| {
+                        let name = zinit_plugin_name(&dir.file_name()?.to_string_lossy())?;
+                        Some((dir, Some(name)))
+                    }),
+            );
+        }
+        checkouts.retain(|(dir, _)| dir.is_dir());
+
+        let pb = ProgressBar::new(checkouts.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (dir, name) in checkouts {
+            let mut pkg = git::checkout_package(&dir, PackageSource::ShellPlugin);
+            if let Some(name) = name {
+                pkg.name = name;
+            }
+            packages.push(pkg);
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        if let Some(content) = fish_plugins_file().and_then(|f| fs::read_to_string(f).ok()) {
+            packages.extend(parse_fish_plugins(&content));
+        }
+
+        Ok(packages)
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+/// `$ZSH`, defaulting to `~/.oh-my-zsh`.
+fn oh_my_zsh_dir() -> Option<PathBuf> {
+    std::env::var_os("ZSH")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".oh-my-zsh")))
+}
+
+/// zinit's home: `$XDG_DATA_HOME/zinit`, or the legacy `~/.zinit`.
+fn zinit_home() -> Option<PathBuf> {
+    let home = home_dir()?;
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"));
+    [data.join("zinit"), home.join(".zinit")]
+        .into_iter()
+        .find(|d| d.is_dir())
+}
+
+/// `$__fish_config_dir/fish_plugins`, i.e. `~/.config/fish/fish_plugins`.
+fn fish_plugins_file() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".config")))?;
+    Some(config.join("fish/fish_plugins"))
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+/// zinit names plugin directories `<owner>---<repo>`; `_local---*` entries
+/// are local snippets, not cloned plugins.
+fn zinit_plugin_name(dir_name: &str) -> Option<String> {
+    let (owner, repo) = dir_name.split_once("---")?;
+    if owner.is_empty() || repo.is_empty() || owner == "_local" {
+        return None;
+    }
+    Some(format!("{owner}/{repo}"))
+}
+
+/// Parse fisher's `fish_plugins`.
+///
+/// ```text
+/// jorgebucaran/fisher
+/// ilancosman/tide@v6
+/// gitlab.com/owner/plugin
+/// ~/src/my-local-plugin
+/// ```
+///
+/// `owner/repo` refers to GitHub; an explicit host is kept. An `@ref`
+/// suffix is the requested version. Local paths are skipped.
+fn parse_fish_plugins(content: &str) -> Vec<InstalledPackage> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter(|l| !l.starts_with(['/', '~', '.', '$']))
+        .filter_map(|line| {
+            let (spec, reference) = match line.split_once('@') {
+                Some((spec, reference)) => (spec, Some(reference)),
+                None => (line, None),
+            };
+            let segments: Vec<&str> = spec.split('/').collect();
+            let url = match segments.as_slice() {
+                [owner, repo] if !owner.is_empty() && !repo.is_empty() => {
+                    format!("https://github.com/{spec}")
+                }
+                [host, _, ..] if host.contains('.') => format!("https://{spec}"),
+                _ => return None,
+            };
+
+            Some(InstalledPackage {
+                name: spec.to_string(),
+                version: reference
+                    .filter(|r| !r.is_empty())
+                    .unwrap_or("unknown")
+                    .to_string(),
+                description: None,
+                url: Some(url),
+                source: PackageSource::ShellPlugin,
+                licenses: Vec::new(),
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fish_plugins_file() {
+        let content = "\
+jorgebucaran/fisher
+ilancosman/tide@v6
+gitlab.com/owner/plugin
+~/src/my-local-plugin
+# comment
+
+not-a-plugin
+";
+        let packages = parse_fish_plugins(content);
+        assert_eq!(packages.len(), 3);
+
+        assert_eq!(packages[0].name, "jorgebucaran/fisher");
+        assert_eq!(packages[0].version, "unknown");
+        assert_eq!(
+            packages[0].url.as_deref(),
+            Some("https://github.com/jorgebucaran/fisher")
+        );
+        assert_eq!(packages[0].source, PackageSource::ShellPlugin);
+
+        assert_eq!(packages[1].name, "ilancosman/tide");
+        assert_eq!(packages[1].version, "v6");
+
+        assert_eq!(
+            packages[2].url.as_deref(),
+            Some("https://gitlab.com/owner/plugin")
+        );
+    }
+
+    #[test]
+    fn zinit_directory_names() {
+        assert_eq!(
+            zinit_plugin_name("zsh-users---zsh-autosuggestions").as_deref(),
+            Some("zsh-users/zsh-autosuggestions")
+        );
+        assert_eq!(zinit_plugin_name("_local---zinit"), None);
+        assert_eq!(zinit_plugin_name("custom"), None);
+    }
+}
@@ -1,19 +1,24 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use std::collections::BTreeMap;
 use std::path::Path;
-use std::process::Command;
 
 use anyhow::{Context, Result};
-use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
 
-use super::{Discoverer, InstalledPackage, PackageSource};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+use crate::project::FundingChannel;
+
+/// The snapd REST API socket.
+const SNAPD_SOCKET: &str = "/run/snapd.socket";
 
 /// Discovers applications installed via Snap.
 ///
-/// Runs `snap list` to enumerate installed snap packages. The tabular output
-/// contains columns for name, version, revision, tracking channel, publisher,
-/// and notes. Additional metadata (description) is read from each snap's
-/// `meta/snap.yaml` file when available.
+/// Queries the snapd REST API (`GET /v2/snaps` on [`SNAPD_SOCKET`]), which
+/// exposes metadata that `snap list` does not print: the summary, license,
+/// publisher, website, contact, and the store's `links` (source, donation).
+/// The project URL is the first source link, falling back to the website and
+/// an HTTP(S) contact address; the publisher is appended to the description.
 pub struct SnapDiscoverer;
 
 impl Discoverer for SnapDiscoverer {
@@ -22,221 +27,247 @@ impl Discoverer for SnapDiscoverer {
     }
 
     fn is_available(&self) -> bool {
-        Path::new("/usr/bin/snap").is_file() || Path::new("/snap").is_dir()
+        Path::new(SNAPD_SOCKET).exists()
     }
 
     fn discover(&self) -> Result<Vec<InstalledPackage>> {
-        let output = Command::new("snap")
-            .args(["list"])
-            .output()
-            .context("Failed to run snap list")?;
-
-        if !output.status.success() {
-            anyhow::bail!(
-                "snap list failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-        }
-
-        let stdout =
-            String::from_utf8(output.stdout).context("snap list output is not valid UTF-8")?;
-
-        parse_snap_output(&stdout)
+        let response = query_snapd("/v2/snaps")?;
+        let body = http_body(&response)?;
+        parse_snaps_response(body)
     }
 }
 
-/// Parse the columnar output of `snap list`.
+/// Send a GET request to the snapd socket and return the raw HTTP response.
 ///
-/// The first line is a header row. Subsequent lines contain whitespace-separated
-/// fields: Name, Version, Rev, Tracking, Publisher, Notes.
-fn parse_snap_output(output: &str) -> Result<Vec<InstalledPackage>> {
-    let lines: Vec<&str> = output
-        .lines()
-        .filter(|l| !l.is_empty())
-        .skip(1) // skip header row
-        .collect();
-
-    let pb = ProgressBar::new(lines.len() as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("  {bar:30} {pos}/{len} packages")
-            .unwrap(),
-    );
-
-    let packages: Vec<InstalledPackage> = lines
-        .iter()
-        .filter_map(|line| {
-            let result = parse_snap_line(line);
-            pb.inc(1);
-            match result {
-                Ok(pkg) => Some(pkg),
-                Err(e) => {
-                    pb.suspend(|| {
-                        eprintln!("  Warning: failed to parse snap entry: {e}");
-                    });
-                    None
-                }
-            }
-        })
-        .collect();
+/// HTTP/1.0 is used so that snapd answers with a plain, unchunked body and
+/// closes the connection when done.
+#[cfg(unix)]
+fn query_snapd(path: &str) -> Result<String> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(SNAPD_SOCKET)
+        .with_context(|| format!("Failed to connect to {SNAPD_SOCKET}"))?;
+    write!(stream, "GET {path} HTTP/1.0\r\nHost: localhost\r\n\r\n")
+        .context("Failed to send snapd request")?;
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .context("Failed to read snapd response")?;
+    Ok(response)
+}
 
-    pb.finish_and_clear();
+#[cfg(not(unix))]
+fn query_snapd(_path: &str) -> Result<String> {
+    anyhow::bail!("The snapd API is only available on Unix")
+}
 
-    // Enrich packages with descriptions from snap.yaml when available.
-    let packages = enrich_with_descriptions(packages);
+/// Split the body off a raw HTTP response, failing on non-2xx statuses.
+fn http_body(response: &str) -> Result<&str> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("Malformed HTTP response from snapd")?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .context("Missing HTTP status line")?;
+    if !status.starts_with('2') {
+        anyhow::bail!("snapd returned HTTP {status}: {body}");
+    }
+    Ok(body)
+}
 
-    Ok(packages)
+#[derive(Deserialize)]
+struct SnapdResponse {
+    #[serde(default)]
+    result: Vec<Snap>,
 }
 
-/// Parse a single line from `snap list` output.
-///
-/// Expected columns (whitespace-separated):
-/// Name  Version  Rev  Tracking  Publisher  Notes
-fn parse_snap_line(line: &str) -> Result<InstalledPackage> {
-    let fields: Vec<&str> = line.split_whitespace().collect();
-
-    let name = fields
-        .first()
-        .filter(|s| !s.is_empty())
-        .context("Missing snap name")?
-        .to_string();
-
-    let version = fields
-        .get(1)
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .unwrap_or_else(|| "unknown".to_string());
-
-    Ok(InstalledPackage {
-        name,
-        version,
-        description: None,
-        url: None,
-        source: PackageSource::Snap,
-        licenses: Vec::new(),
-    })
+#[derive(Deserialize)]
+struct Snap {
+    name: String,
+    version: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    license: Option<String>,
+    website: Option<String>,
+    contact: Option<String>,
+    publisher: Option<Publisher>,
+    #[serde(default)]
+    links: BTreeMap<String, Vec<String>>,
 }
 
-/// Attempt to read the description from `/snap/<name>/current/meta/snap.yaml`.
-fn enrich_with_descriptions(mut packages: Vec<InstalledPackage>) -> Vec<InstalledPackage> {
-    for pkg in &mut packages {
-        let yaml_path = format!("/snap/{}/current/meta/snap.yaml", pkg.name);
-        if let Ok(contents) = std::fs::read_to_string(&yaml_path)
-            && let Some(desc) = extract_description(&contents)
-        {
-            pkg.description = Some(desc);
-        }
-    }
-    packages
+#[derive(Deserialize)]
+struct Publisher {
+    username: Option<String>,
+    #[serde(rename = "display-name")]
+    display_name: Option<String>,
 }
 
-/// Extract the `description` field from a snap.yaml file.
+/// Parse the JSON body of `GET /v2/snaps`.
 ///
-/// Performs simple line-based parsing to avoid pulling in a YAML dependency.
-/// Looks for a line starting with `description:` and extracts its value.
-fn extract_description(yaml: &str) -> Option<String> {
-    for line in yaml.lines() {
-        let trimmed = line.trim();
-        if let Some(value) = trimmed.strip_prefix("description:") {
-            let value = value.trim().trim_matches(|c| c == '\'' || c == '"');
-            if !value.is_empty() {
-                return Some(value.to_string());
-            }
-        }
+/// ```json
+/// {"type": "sync", "status-code": 200, "result": [{"name": "firefox", "version": "128.0.3",
+///   "summary": "Mozilla Firefox web browser", "license": "MPL-2.0",
+///   "publisher": {"username": "mozilla", "display-name": "Mozilla"},
+///   "website": "https://www.mozilla.org/firefox/", "contact": "https://support.mozilla.org/kb/",
+///   "links": {"source": ["https://hg.mozilla.org/"], "donation": ["https://donate.mozilla.org/"]}}]}
+/// ```
+fn parse_snaps_response(body: &str) -> Result<Vec<InstalledPackage>> {
+    let response: SnapdResponse =
+        serde_json::from_str(body).context("Failed to parse snapd response")?;
+
+    Ok(response.result.into_iter().map(build_package).collect())
+}
+
+fn build_package(snap: Snap) -> InstalledPackage {
+    let non_empty = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let first_link = |links: &BTreeMap<String, Vec<String>>, key: &str| {
+        links
+            .get(key)
+            .and_then(|urls| urls.iter().find(|u| u.starts_with("http")))
+            .cloned()
+    };
+
+    let url = first_link(&snap.links, "source")
+        .or_else(|| non_empty(snap.website.clone()))
+        .or_else(|| first_link(&snap.links, "website"))
+        .or_else(|| non_empty(snap.contact.clone()).filter(|c| c.starts_with("http")));
+
+    let publisher = snap
+        .publisher
+        .and_then(|p| non_empty(p.display_name).or(non_empty(p.username)));
+    let description = match (
+        non_empty(snap.summary).or(non_empty(snap.description)),
+        publisher,
+    ) {
+        (Some(desc), Some(publisher)) => Some(format!("{desc} (by {publisher})")),
+        (None, Some(publisher)) => Some(format!("by {publisher}")),
+        (desc, None) => desc,
+    };
+
+    // The store reports `unset` (or `Other`) when the publisher declared no
+    // SPDX license.
+    let licenses = non_empty(snap.license)
+        .filter(|l| l != "unset" && !l.eq_ignore_ascii_case("other"))
+        .into_iter()
+        .collect();
+
+    let funding = snap
+        .links
+        .get("donation")
+        .into_iter()
+        .flatten()
+        .filter(|u| u.starts_with("http"))
+        .map(|u| FundingChannel::from_url(u))
+        .collect();
+
+    InstalledPackage {
+        name: snap.name,
+        version: non_empty(snap.version).unwrap_or_else(|| "unknown".to_string()),
+        description,
+        url,
+        source: PackageSource::Snap,
+        licenses,
+        funding,
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const HEADER: &str = "Name    Version   Rev    Tracking       Publisher   Notes";
-
-    #[test]
-    fn parse_full_output() {
-        let output = format!("{HEADER}\nfirefox  128.0.3   4793   latest/stable  mozilla**   -\n");
-        let packages = parse_snap_output(&output).unwrap();
-        assert_eq!(packages.len(), 1);
-        let pkg = &packages[0];
-        assert_eq!(pkg.name, "firefox");
-        assert_eq!(pkg.version, "128.0.3");
-        assert_eq!(pkg.source, PackageSource::Snap);
-        assert!(pkg.licenses.is_empty());
-    }
+    const SNAPS_BODY: &str = r#"{
+        "type": "sync",
+        "status-code": 200,
+        "status": "OK",
+        "result": [
+            {
+                "id": "3wdHCAVyZEmYsCMFDE9qt92UV8rC8Wdk",
+                "name": "firefox",
+                "summary": "Mozilla Firefox web browser",
+                "description": "Firefox is a powerful, extensible web browser.",
+                "version": "128.0.3-1",
+                "revision": "4793",
+                "type": "app",
+                "license": "MPL-2.0",
+                "publisher": {"id": "abc", "username": "mozilla", "display-name": "Mozilla", "validation": "verified"},
+                "website": "https://www.mozilla.org/firefox/",
+                "contact": "https://support.mozilla.org/kb/",
+                "links": {
+                    "contact": ["https://support.mozilla.org/kb/"],
+                    "donation": ["https://foundation.mozilla.org/donate/"],
+                    "source": ["https://hg.mozilla.org/mozilla-central"],
+                    "website": ["https://www.mozilla.org/firefox/"]
+                }
+            },
+            {
+                "name": "core22",
+                "version": "20240408",
+                "summary": "Runtime environment based on Ubuntu 22.04",
+                "type": "base",
+                "license": "unset",
+                "publisher": {"username": "canonical", "display-name": "Canonical"},
+                "contact": "mailto:snaps@canonical.com"
+            },
+            {
+                "name": "mytool",
+                "version": "",
+                "website": "",
+                "links": {"website": ["https://mytool.example.org"], "donation": ["https://liberapay.com/mytool"]}
+            }
+        ]
+    }"#;
 
     #[test]
-    fn parse_multiple_snaps() {
-        let output = format!(
-            "{HEADER}
-core20      20240227  2318   latest/stable  canonical**  base
-firefox     128.0.3   4793   latest/stable  mozilla**    -
-snapd       2.63      21759  latest/stable  canonical**  snapd
-"
-        );
-        let packages = parse_snap_output(&output).unwrap();
+    fn parse_snaps_with_metadata() {
+        let packages = parse_snaps_response(SNAPS_BODY).unwrap();
         assert_eq!(packages.len(), 3);
-        assert_eq!(packages[0].name, "core20");
-        assert_eq!(packages[1].name, "firefox");
-        assert_eq!(packages[2].name, "snapd");
-    }
-
-    #[test]
-    fn parse_empty_output() {
-        let packages = parse_snap_output("").unwrap();
-        assert!(packages.is_empty());
-    }
-
-    #[test]
-    fn parse_header_only() {
-        let output = format!("{HEADER}\n");
-        let packages = parse_snap_output(&output).unwrap();
-        assert!(packages.is_empty());
-    }
 
-    #[test]
-    fn parse_skips_blank_lines() {
-        let output = format!("{HEADER}\n\nfirefox  128.0  4793  latest/stable  mozilla**  -\n\n");
-        let packages = parse_snap_output(&output).unwrap();
-        assert_eq!(packages.len(), 1);
-    }
-
-    #[test]
-    fn parse_minimal_line() {
-        let output = format!("{HEADER}\nsomepkg  1.0\n");
-        let packages = parse_snap_output(&output).unwrap();
-        assert_eq!(packages.len(), 1);
-        assert_eq!(packages[0].name, "somepkg");
-        assert_eq!(packages[0].version, "1.0");
-    }
-
-    #[test]
-    fn extract_description_simple() {
-        let yaml = "name: firefox\ndescription: Fast web browser\nversion: 128.0\n";
+        let firefox = &packages[0];
+        assert_eq!(firefox.name, "firefox");
+        assert_eq!(firefox.version, "128.0.3-1");
         assert_eq!(
-            extract_description(yaml),
-            Some("Fast web browser".to_string())
+            firefox.description.as_deref(),
+            Some("Mozilla Firefox web browser (by Mozilla)")
         );
-    }
-
-    #[test]
-    fn extract_description_quoted() {
-        let yaml = "description: 'A quoted description'\n";
         assert_eq!(
-            extract_description(yaml),
-            Some("A quoted description".to_string())
+            firefox.url.as_deref(),
+            Some("https://hg.mozilla.org/mozilla-central")
         );
+        assert_eq!(firefox.licenses, vec!["MPL-2.0"]);
+        assert_eq!(firefox.funding.len(), 1);
+        assert_eq!(firefox.source, PackageSource::Snap);
+
+        let core = &packages[1];
+        assert!(core.licenses.is_empty());
+        assert!(core.url.is_none());
+
+        let mytool = &packages[2];
+        assert_eq!(mytool.version, "unknown");
+        assert!(mytool.description.is_none());
+        assert_eq!(mytool.url.as_deref(), Some("https://mytool.example.org"));
+        assert_eq!(mytool.funding[0].platform, "Liberapay");
     }
 
     #[test]
-    fn extract_description_missing() {
-        let yaml = "name: firefox\nversion: 128.0\n";
-        assert_eq!(extract_description(yaml), None);
+    fn parse_empty_and_invalid_responses() {
+        let body = r#"{"type": "sync", "status-code": 200, "result": []}"#;
+        assert!(parse_snaps_response(body).unwrap().is_empty());
+        assert!(parse_snaps_response("not json").is_err());
     }
 
     #[test]
-    fn extract_description_empty() {
-        let yaml = "description:\n";
-        assert_eq!(extract_description(yaml), None);
+    fn http_body_checks_status() {
+        let ok = "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n{\"result\": []}";
+        assert_eq!(http_body(ok).unwrap(), "{\"result\": []}");
+
+        let denied = "HTTP/1.0 403 Forbidden\r\n\r\n{\"type\": \"error\"}";
+        assert!(http_body(denied).is_err());
+        assert!(http_body("garbage").is_err());
     }
 }
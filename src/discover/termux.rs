@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::apt;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Discovers packages installed via Termux's `pkg`/apt on Android.
+///
+/// Termux ships its own dpkg database under its install prefix
+/// (`$PREFIX/var/lib/dpkg/status`) instead of `/var/lib/dpkg/status`. The file
+/// uses the regular dpkg status format and is parsed by
+/// [`apt::parse_dpkg_status`], with packages attributed to
+/// [`PackageSource::Termux`].
+pub struct TermuxDiscoverer;
+
+/// Termux's install prefix when `$PREFIX` is not set.
+const DEFAULT_PREFIX: &str = "/data/data/com.termux/files/usr";
+
+impl Discoverer for TermuxDiscoverer {
+    fn name(&self) -> &str {
+        "termux"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "android") && dpkg_status_path().is_file()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let path = dpkg_status_path();
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let mut packages = apt::parse_dpkg_status(&content)?;
+        for pkg in &mut packages {
+            pkg.source = PackageSource::Termux;
+        }
+        Ok(packages)
+    }
+}
+
+/// Path of the Termux dpkg status file, honoring `$PREFIX`.
+fn dpkg_status_path() -> PathBuf {
+    std::env::var_os("PREFIX")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_PREFIX))
+        .join("var/lib/dpkg/status")
+}
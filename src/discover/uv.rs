@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::python::{self, DistMetadata};
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers Python tools installed via `uv tool install`.
+///
+/// Installed tools and their versions come from `uv tool list`; when uv is
+/// not on `PATH` the tool directories themselves are listed instead. Each tool
+/// lives in its own environment under the uv tools directory, whose
+/// `uv-receipt.toml` names the requested package. That package's dist-info
+/// metadata is read from the environment for its summary, license, and
+/// project URLs.
+pub struct UvDiscoverer;
+
+impl Discoverer for UvDiscoverer {
+    fn name(&self) -> &str {
+        "uv"
+    }
+
+    fn is_available(&self) -> bool {
+        uv_tools_dir().is_some()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let tools_dir = uv_tools_dir().context("Could not find uv tools directory")?;
+
+        let tools = match run_uv_tool_list() {
+            Ok(output) => parse_uv_tool_list(&output),
+            Err(_) => {
+                let mut names: Vec<UvTool> = fs::read_dir(&tools_dir)
+                    .with_context(|| format!("Failed to read {}", tools_dir.display()))?
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| UvTool {
+                        name: e.file_name().to_string_lossy().into_owned(),
+                        version: None,
+                    })
+                    .collect();
+                names.sort_by(|a, b| a.name.cmp(&b.name));
+                names
+            }
+        };
+
+        let pb = ProgressBar::new(tools.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for tool in tools {
+            let env = tools_dir.join(&tool.name);
+            let receipt_path = env.join("uv-receipt.toml");
+            let package_name = match fs::read_to_string(&receipt_path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_receipt(&content))
+            {
+                Ok(name) => name,
+                Err(e) => {
+                    pb.suspend(|| {
+                        eprintln!("  Warning: failed to parse {}: {e}", receipt_path.display())
+                    });
+                    None
+                }
+            }
+            .unwrap_or_else(|| tool.name.clone());
+
+            let site_packages = python::site_packages_dirs(&env);
+            let dist = python::find_dist_metadata(&site_packages, &package_name);
+            packages.push(build_package(tool, dist));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Locate uv's tools directory.
+///
+/// Honors `$UV_TOOL_DIR`, then `$XDG_DATA_HOME/uv/tools`, then
+/// `~/.local/share/uv/tools`.
+fn uv_tools_dir() -> Option<PathBuf> {
+    let dir = match std::env::var_os("UV_TOOL_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => match std::env::var_os("XDG_DATA_HOME") {
+            Some(data) => PathBuf::from(data).join("uv/tools"),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".local/share/uv/tools"),
+        },
+    };
+    dir.is_dir().then_some(dir)
+}
+
+fn run_uv_tool_list() -> Result<String> {
+    let output = Command::new("uv")
+        .args(["tool", "list"])
+        .output()
+        .context("Failed to run uv tool list")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "uv tool list failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("uv tool list output is not valid UTF-8")
+}
+
+/// A tool reported by `uv tool list`.
+#[derive(Debug, PartialEq)]
+struct UvTool {
+    name: String,
+    version: Option<String>,
+}
+
+/// Parse `uv tool list` output.
+///
+/// ```text
+/// black v24.4.2
+/// - black
+/// - blackd
+/// ruff v0.5.0 [required: ==0.5.0]
+/// - ruff
+/// ```
+///
+/// Lines starting with `-` list a tool's executables and are skipped.
+fn parse_uv_tool_list(output: &str) -> Vec<UvTool> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('-'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            let version = parts
+                .next()
+                .and_then(|v| v.strip_prefix('v'))
+                .map(String::from);
+            Some(UvTool { name, version })
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct Receipt {
+    tool: ReceiptTool,
+}
+
+#[derive(Deserialize)]
+struct ReceiptTool {
+    #[serde(default)]
+    requirements: Vec<Requirement>,
+}
+
+#[derive(Deserialize)]
+struct Requirement {
+    name: String,
+}
+
+/// Parse a tool's `uv-receipt.toml` and return the name of the requested
+/// package (the first requirement; later ones come from `--with`).
+fn parse_receipt(content: &str) -> Result<Option<String>> {
+    let receipt: Receipt = toml::from_str(content).context("Invalid uv-receipt.toml")?;
+    Ok(receipt.tool.requirements.into_iter().next().map(|r| r.name))
+}
+
+/// Combine the listed tool with its distribution's metadata, if found.
+///
+/// The version reported by uv takes precedence over the dist-info version.
+fn build_package(tool: UvTool, dist: Option<DistMetadata>) -> InstalledPackage {
+    match dist {
+        Some(dist) => {
+            let mut pkg = dist.into_package(PackageSource::Uv);
+            pkg.name = tool.name;
+            if let Some(version) = tool.version {
+                pkg.version = version;
+            }
+            pkg
+        }
+        None => InstalledPackage {
+            name: tool.name,
+            version: tool.version.unwrap_or_else(|| "unknown".to_string()),
+            description: None,
+            url: None,
+            source: PackageSource::Uv,
+            licenses: Vec::new(),
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tool_list() {
+        let output = "\
+black v24.4.2
+- black
+- blackd
+ruff v0.5.0 [required: ==0.5.0]
+- ruff
+";
+        assert_eq!(
+            parse_uv_tool_list(output),
+            vec![
+                UvTool {
+                    name: "black".to_string(),
+                    version: Some("24.4.2".to_string()),
+                },
+                UvTool {
+                    name: "ruff".to_string(),
+                    version: Some("0.5.0".to_string()),
+                },
+            ]
+        );
+        assert!(parse_uv_tool_list("").is_empty());
+    }
+
+    #[test]
+    fn parse_receipt_requirements() {
+        let content = r#"
+[tool]
+requirements = [{ name = "mkdocs" }, { name = "mkdocs-material" }]
+entrypoints = [
+    { name = "mkdocs", install-path = "/home/user/.local/bin/mkdocs" },
+]
+"#;
+        assert_eq!(parse_receipt(content).unwrap().as_deref(), Some("mkdocs"));
+        assert_eq!(parse_receipt("[tool]\n").unwrap(), None);
+        assert!(parse_receipt("not toml [").is_err());
+    }
+
+    #[test]
+    fn build_package_with_dist_metadata() {
+        let dist = DistMetadata {
+            name: "ruff".to_string(),
+            version: "0.4.0".to_string(),
+            summary: Some("An extremely fast Python linter".to_string()),
+            project_urls: vec![(
+                "Repository".to_string(),
+                "https://github.com/astral-sh/ruff".to_string(),
+            )],
+            license: Some("MIT".to_string()),
+            ..Default::default()
+        };
+        let tool = UvTool {
+            name: "ruff".to_string(),
+            version: Some("0.5.0".to_string()),
+        };
+        let pkg = build_package(tool, Some(dist));
+        assert_eq!(pkg.version, "0.5.0");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/astral-sh/ruff")
+        );
+        assert_eq!(pkg.licenses, vec!["MIT"]);
+        assert_eq!(pkg.source, PackageSource::Uv);
+    }
+
+    #[test]
+    fn build_package_without_metadata() {
+        let tool = UvTool {
+            name: "foo".to_string(),
+            version: None,
+        };
+        let pkg = build_package(tool, None);
+        assert_eq!(pkg.version, "unknown");
+        assert!(pkg.url.is_none());
+    }
+}
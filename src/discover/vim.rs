@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::git;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Discovers Vim and Neovim plugins installed by plugin managers or through
+/// the native package mechanism.
+///
+/// Plugin managers clone each plugin into its own directory:
+///
+/// - lazy.nvim: `~/.local/share/nvim/lazy/<plugin>`
+/// - vim-plug: `~/.vim/plugged/<plugin>`, `~/.local/share/nvim/plugged/<plugin>`
+/// - packer and native packages: `<packpath>/pack/<group>/{start,opt}/<plugin>`
+///   under `~/.vim`, `~/.config/nvim`, and `~/.local/share/nvim/site`
+///
+/// Each directory is reported via its git checkout (see [`git::checkout_package`]).
+/// A plugin shared by Vim and Neovim is reported once per commit.
+pub struct VimPluginDiscoverer;
+
+impl Discoverer for VimPluginDiscoverer {
+    fn name(&self) -> &str {
+        "vim-plugin"
+    }
+
+    fn is_available(&self) -> bool {
+        plugin_roots().iter().any(|root| root.is_dir())
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let plugins: Vec<PathBuf> = plugin_roots()
+            .iter()
+            .flat_map(|root| subdirs(root))
+            .collect();
+
+        let pb = ProgressBar::new(plugins.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+        for dir in &plugins {
+            let pkg = git::checkout_package(dir, PackageSource::VimPlugin);
+            by_key
+                .entry((pkg.name.clone(), pkg.version.clone()))
+                .or_insert(pkg);
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+/// Directories whose sub-directories are individual plugins.
+fn plugin_roots() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"))
+        .join("nvim");
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"))
+        .join("nvim");
+
+    let mut roots = vec![
+        data.join("lazy"),
+        data.join("plugged"),
+        home.join(".vim/plugged"),
+    ];
+    for packpath in [home.join(".vim"), config, data.join("site")] {
+        roots.extend(pack_dirs(&packpath));
+    }
+    roots
+}
+
+/// `pack/*/start` and `pack/*/opt` below a `packpath` entry.
+fn pack_dirs(packpath: &Path) -> Vec<PathBuf> {
+    subdirs(&packpath.join("pack"))
+        .into_iter()
+        .flat_map(|group| [group.join("start"), group.join("opt")])
+        .collect()
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_dirs_cover_start_and_opt() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("pack/packer/start/plenary.nvim")).unwrap();
+        fs::create_dir_all(tmp.path().join("pack/vendor/opt")).unwrap();
+
+        let mut dirs = pack_dirs(tmp.path());
+        dirs.sort();
+        assert_eq!(
+            dirs,
+            vec![
+                tmp.path().join("pack/packer/opt"),
+                tmp.path().join("pack/packer/start"),
+                tmp.path().join("pack/vendor/opt"),
+                tmp.path().join("pack/vendor/start"),
+            ]
+        );
+        assert!(pack_dirs(&tmp.path().join("missing")).is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Deserialize;
+
+use super::npm;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Editor data directories (relative to the home directory) whose
+/// `extensions/` folder holds installed extensions: VS Code, VS Code
+/// Insiders, VSCodium, and Cursor.
+const EDITOR_DIRS: &[&str] = &[".vscode", ".vscode-insiders", ".vscode-oss", ".cursor"];
+
+/// Discovers editor extensions installed in VS Code and its forks.
+///
+/// Each extension is unpacked into `<editor dir>/extensions/<publisher>.<name>-<version>/`
+/// with an npm-style `package.json`, which is read with the npm backend's
+/// parser (repository, license, and the `sponsor` field). Directories listed
+/// in `extensions/.obsolete` are pending removal and skipped. Extensions are
+/// reported as `publisher.name`, once per version across editors.
+pub struct VscodeExtensionsDiscoverer;
+
+impl Discoverer for VscodeExtensionsDiscoverer {
+    fn name(&self) -> &str {
+        "vscode"
+    }
+
+    fn is_available(&self) -> bool {
+        !extension_dirs().is_empty()
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let manifests: Vec<PathBuf> = extension_dirs()
+            .iter()
+            .flat_map(|dir| installed_extensions(dir))
+            .map(|ext| ext.join("package.json"))
+            .filter(|path| path.is_file())
+            .collect();
+
+        let pb = ProgressBar::new(manifests.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut by_key: BTreeMap<(String, String), InstalledPackage> = BTreeMap::new();
+
+        for path in &manifests {
+            match read_extension(path) {
+                Ok(pkg) => {
+                    by_key
+                        .entry((pkg.name.clone(), pkg.version.clone()))
+                        .or_insert(pkg);
+                }
+                Err(e) => {
+                    pb.suspend(|| eprintln!("  Warning: failed to parse {}: {e}", path.display()));
+                }
+            }
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(by_key.into_values().collect())
+    }
+}
+
+/// Existing `extensions/` directories of the known editors.
+fn extension_dirs() -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    EDITOR_DIRS
+        .iter()
+        .map(|dir| home.join(dir).join("extensions"))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// Extension directories in `dir`, minus those marked obsolete.
+fn installed_extensions(dir: &Path) -> Vec<PathBuf> {
+    let obsolete = fs::read_to_string(dir.join(".obsolete"))
+        .map(|content| parse_obsolete(&content))
+        .unwrap_or_default();
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            !name.starts_with('.') && !obsolete.contains(&name)
+        })
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+/// Parse `extensions/.obsolete`: `{"<dir name>": true, ...}`.
+fn parse_obsolete(content: &str) -> BTreeSet<String> {
+    serde_json::from_str::<BTreeMap<String, bool>>(content)
+        .map(|entries| {
+            entries
+                .into_iter()
+                .filter(|(_, obsolete)| *obsolete)
+                .map(|(name, _)| name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_extension(path: &Path) -> Result<InstalledPackage> {
+    let content = fs::read_to_string(path)?;
+    let nls = path
+        .parent()
+        .and_then(|dir| fs::read_to_string(dir.join("package.nls.json")).ok())
+        .and_then(|nls| serde_json::from_str(&nls).ok())
+        .unwrap_or_default();
+    parse_extension(&content, &nls)
+}
+
+#[derive(Deserialize)]
+struct ExtensionManifest {
+    name: String,
+    publisher: Option<String>,
+    version: Option<String>,
+    #[serde(rename = "displayName")]
+    display_name: Option<String>,
+}
+
+/// Parse an extension's `package.json`.
+///
+/// Marketplace manifests often localize `description` and `displayName` as
+/// `%key%` placeholders, resolved against the bundled `package.nls.json`
+/// (passed as `nls`). The display name is used when there is no description.
+fn parse_extension(content: &str, nls: &BTreeMap<String, String>) -> Result<InstalledPackage> {
+    let ext: ExtensionManifest =
+        serde_json::from_str(content).context("Invalid extension manifest")?;
+    let manifest = npm::parse_package_json(content)?;
+
+    let name = match ext.publisher {
+        Some(publisher) => format!("{publisher}.{}", ext.name),
+        None => ext.name,
+    };
+    let version = ext.version.unwrap_or_else(|| "unknown".to_string());
+
+    let mut pkg = manifest.into_package(name, version, PackageSource::Vscode);
+    pkg.description = pkg
+        .description
+        .map(|d| localize(&d, nls))
+        .or_else(|| ext.display_name.map(|d| localize(&d, nls)))
+        .filter(|d| !d.is_empty() && !d.starts_with('%'));
+    Ok(pkg)
+}
+
+/// Resolve a `%key%` placeholder, leaving other values untouched.
+fn localize(value: &str, nls: &BTreeMap<String, String>) -> String {
+    value
+        .strip_prefix('%')
+        .and_then(|v| v.strip_suffix('%'))
+        .and_then(|key| nls.get(key))
+        .cloned()
+        .unwrap_or_else(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extension_manifest() {
+        let content = r#"{
+            "name": "gitlens",
+            "displayName": "GitLens",
+            "description": "Supercharge Git within VS Code",
+            "version": "15.1.0",
+            "publisher": "eamodio",
+            "license": "SEE LICENSE IN LICENSE",
+            "repository": {"type": "git", "url": "https://github.com/gitkraken/vscode-gitlens.git"},
+            "sponsor": {"url": "https://github.com/sponsors/eamodio"}
+        }"#;
+        let pkg = parse_extension(content, &BTreeMap::new()).unwrap();
+        assert_eq!(pkg.name, "eamodio.gitlens");
+        assert_eq!(pkg.version, "15.1.0");
+        assert_eq!(
+            pkg.url.as_deref(),
+            Some("https://github.com/gitkraken/vscode-gitlens")
+        );
+        assert_eq!(pkg.funding.len(), 1);
+        assert_eq!(pkg.funding[0].platform, "GitHub Sponsors");
+        assert_eq!(pkg.source, PackageSource::Vscode);
+    }
+
+    #[test]
+    fn localized_description() {
+        let content = r#"{
+            "name": "python",
+            "publisher": "ms-python",
+            "version": "2024.8.1",
+            "description": "%extension.description%"
+        }"#;
+        let nls = BTreeMap::from([(
+            "extension.description".to_string(),
+            "Python language support".to_string(),
+        )]);
+        let pkg = parse_extension(content, &nls).unwrap();
+        assert_eq!(pkg.description.as_deref(), Some("Python language support"));
+
+        let pkg = parse_extension(content, &BTreeMap::new()).unwrap();
+        assert!(pkg.description.is_none());
+    }
+
+    #[test]
+    fn display_name_fallback() {
+        let content = r#"{"name": "theme", "displayName": "Nice Theme"}"#;
+        let pkg = parse_extension(content, &BTreeMap::new()).unwrap();
+        assert_eq!(pkg.name, "theme");
+        assert_eq!(pkg.version, "unknown");
+        assert_eq!(pkg.description.as_deref(), Some("Nice Theme"));
+    }
+
+    #[test]
+    fn obsolete_entries() {
+        let obsolete = parse_obsolete(r#"{"foo.bar-1.0.0": true, "foo.baz-2.0.0": false}"#);
+        assert!(obsolete.contains("foo.bar-1.0.0"));
+        assert!(!obsolete.contains("foo.baz-2.0.0"));
+        assert!(parse_obsolete("garbage").is_empty());
+    }
+
+    #[test]
+    fn parse_extension_invalid() {
+        assert!(parse_extension("{}", &BTreeMap::new()).is_err());
+    }
+}
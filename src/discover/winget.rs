@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::{Discoverer, InstallReason, InstalledPackage, PackageSource};
+
+/// Discovers applications installed via winget (Windows Package Manager).
+///
+/// Parses the table printed by `winget list` for names, IDs, and versions,
+/// then runs `winget show` for each package that came from a winget source to
+/// pick up its description, homepage (or publisher URL), and license.
+///
+/// Only available on Windows. The parsing code is compiled on every target so
+/// that it stays covered by the test suite.
+pub struct WingetDiscoverer;
+
+/// Flags that keep winget from prompting for source agreements.
+const NON_INTERACTIVE: [&str; 2] = ["--accept-source-agreements", "--disable-interactivity"];
+
+impl Discoverer for WingetDiscoverer {
+    fn name(&self) -> &str {
+        "winget"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(windows) && super::in_path("winget.exe")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let list = run_winget(&["list"])?;
+        let entries = parse_winget_list(&list);
+
+        let pb = ProgressBar::new(entries.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let packages = entries
+            .into_iter()
+            .map(|entry| {
+                // Entries without a source (e.g. MSIX or ARP-only installs)
+                // cannot be looked up with `winget show`.
+                let details = if entry.source.is_some() {
+                    run_winget(&["show", "--id", &entry.id, "--exact"])
+                        .map(|out| parse_winget_show(&out))
+                        .unwrap_or_else(|e| {
+                            pb.suspend(|| {
+                                eprintln!("  Warning: winget show {} failed: {e}", entry.id)
+                            });
+                            WingetDetails::default()
+                        })
+                } else {
+                    WingetDetails::default()
+                };
+                pb.inc(1);
+                build_package(entry, details)
+            })
+            .collect();
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run a winget subcommand non-interactively and return its stdout.
+fn run_winget(args: &[&str]) -> Result<String> {
+    let output = Command::new("winget")
+        .args(args)
+        .args(NON_INTERACTIVE)
+        .output()
+        .with_context(|| format!("Failed to run winget {}", args[0]))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "winget {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("winget output is not valid UTF-8")
+}
+
+/// A row of the `winget list` table.
+#[derive(Debug, PartialEq)]
+struct WingetEntry {
+    name: String,
+    id: String,
+    version: String,
+    source: Option<String>,
+}
+
+/// Parse the fixed-width table printed by `winget list`.
+///
+/// ```text
+/// Name              Id                 Version   Available Source
+/// ---------------------------------------------------------------
+/// Mozilla Firefox   Mozilla.Firefox    128.0     129.0     winget
+/// ```
+///
+/// Column headers are localized, so columns are located by position: the
+/// header is the line directly above the dashed separator, and each run of
+/// non-space characters in it starts a column. The first three columns are
+/// always name, ID, and version; the last is the source when there are five.
+fn parse_winget_list(output: &str) -> Vec<WingetEntry> {
+    // winget draws a spinner using carriage returns before the table.
+    let lines: Vec<&str> = output
+        .lines()
+        .map(|l| l.rsplit('\r').next().unwrap_or(l))
+        .collect();
+
+    let Some(sep) = lines
+        .iter()
+        .position(|l| l.len() > 3 && l.trim().chars().all(|c| c == '-'))
+    else {
+        return Vec::new();
+    };
+    let Some(header) = sep.checked_sub(1).map(|i| lines[i]) else {
+        return Vec::new();
+    };
+
+    let starts = column_starts(header);
+    if starts.len() < 3 {
+        return Vec::new();
+    }
+
+    lines[sep + 1..]
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            let cell = |i: usize| -> String {
+                let start = starts[i].min(chars.len());
+                let end = starts
+                    .get(i + 1)
+                    .copied()
+                    .unwrap_or(chars.len())
+                    .min(chars.len());
+                chars[start..end]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            };
+
+            let name = cell(0);
+            let id = cell(1);
+            if name.is_empty() || id.is_empty() {
+                return None;
+            }
+            let version = Some(cell(2))
+                .filter(|v| !v.is_empty() && v != "Unknown")
+                .unwrap_or_else(|| "unknown".to_string());
+            let source = (starts.len() >= 5)
+                .then(|| cell(starts.len() - 1))
+                .filter(|s| !s.is_empty());
+
+            Some(WingetEntry {
+                name,
+                id,
+                version,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Character offsets at which each header word begins.
+fn column_starts(header: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut prev_space = true;
+    for (i, c) in header.chars().enumerate() {
+        if !c.is_whitespace() && prev_space {
+            starts.push(i);
+        }
+        prev_space = c.is_whitespace();
+    }
+    starts
+}
+
+/// Fields of interest from `winget show`.
+#[derive(Debug, Default, PartialEq)]
+struct WingetDetails {
+    description: Option<String>,
+    homepage: Option<String>,
+    publisher_url: Option<String>,
+    license: Option<String>,
+}
+
+/// Parse the `Key: value` output of `winget show`.
+fn parse_winget_show(output: &str) -> WingetDetails {
+    let mut details = WingetDetails::default();
+    let mut in_description = false;
+
+    for line in output.lines() {
+        // The description may span several indented lines; only the first
+        // is kept.
+        if in_description && line.starts_with(char::is_whitespace) {
+            if details.description.is_none() {
+                details.description = Some(line.trim().to_string()).filter(|s| !s.is_empty());
+            }
+            continue;
+        }
+        in_description = false;
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+
+        match key.trim() {
+            "Description" => {
+                details.description = value;
+                in_description = true;
+            }
+            "Homepage" => details.homepage = value,
+            "Publisher Url" => details.publisher_url = value,
+            "License" => details.license = value,
+            _ => {}
+        }
+    }
+
+    details
+}
+
+fn build_package(entry: WingetEntry, details: WingetDetails) -> InstalledPackage {
+    InstalledPackage {
+        name: entry.name,
+        version: entry.version,
+        description: details.description,
+        url: details.homepage.or(details.publisher_url),
+        source: PackageSource::Winget,
+        licenses: details.license.into_iter().collect(),
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = "\r   - \r   \\ \r
+Name                       Id                          Version        Available Source
+--------------------------------------------------------------------------------------
+Mozilla Firefox (x64 en-US) Mozilla.Firefox            128.0          129.0     winget
+7-Zip 23.01 (x64)          7zip.7zip                   23.01                    winget
+Microsoft Edge             Microsoft.Edge              126.0.2592.87
+";
+
+    #[test]
+    fn parse_list_table() {
+        let entries = parse_winget_list(LIST);
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[1].name, "7-Zip 23.01 (x64)");
+        assert_eq!(entries[1].id, "7zip.7zip");
+        assert_eq!(entries[1].version, "23.01");
+        assert_eq!(entries[1].source.as_deref(), Some("winget"));
+
+        assert_eq!(entries[2].name, "Microsoft Edge");
+        assert_eq!(entries[2].version, "126.0.2592.87");
+        assert!(entries[2].source.is_none());
+    }
+
+    #[test]
+    fn parse_list_without_table() {
+        assert!(
+            parse_winget_list("No installed package found matching input criteria.").is_empty()
+        );
+        assert!(parse_winget_list("").is_empty());
+    }
+
+    #[test]
+    fn parse_show_output() {
+        let output = "\
+Found 7-Zip [7zip.7zip]
+Version: 23.01
+Publisher: Igor Pavlov
+Publisher Url: https://www.7-zip.org/
+Author: Igor Pavlov
+Moniker: 7zip
+Description:
+  7-Zip is a free and open source file archiver with a high compression ratio.
+  Second line.
+Homepage: https://www.7-zip.org/
+License: LGPL-2.1-or-later
+Installer:
+  Installer Type: exe
+";
+        let details = parse_winget_show(output);
+        assert_eq!(
+            details.description.as_deref(),
+            Some("7-Zip is a free and open source file archiver with a high compression ratio.")
+        );
+        assert_eq!(details.homepage.as_deref(), Some("https://www.7-zip.org/"));
+        assert_eq!(details.license.as_deref(), Some("LGPL-2.1-or-later"));
+    }
+
+    #[test]
+    fn build_package_falls_back_to_publisher_url() {
+        let entry = WingetEntry {
+            name: "Foo".to_string(),
+            id: "Foo.Foo".to_string(),
+            version: "1.0".to_string(),
+            source: Some("winget".to_string()),
+        };
+        let details = WingetDetails {
+            publisher_url: Some("https://foo.example".to_string()),
+            ..Default::default()
+        };
+        let pkg = build_package(entry, details);
+        assert_eq!(pkg.url.as_deref(), Some("https://foo.example"));
+        assert_eq!(pkg.source, PackageSource::Winget);
+    }
+}
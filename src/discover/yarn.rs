@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::npm;
+use super::{Discoverer, InstalledPackage, PackageSource};
+
+/// Discovers packages installed with `yarn global add` (Yarn 1.x).
+///
+/// Runs `yarn global list` for the installed packages and `yarn global dir`
+/// to locate them, then reads each package's `package.json` with the npm
+/// backend's parser so that results line up with npm-installed tools.
+/// Yarn 2+ has no global packages.
+pub struct YarnDiscoverer;
+
+impl Discoverer for YarnDiscoverer {
+    fn name(&self) -> &str {
+        "yarn"
+    }
+
+    fn is_available(&self) -> bool {
+        super::in_path("yarn")
+    }
+
+    fn discover(&self) -> Result<Vec<InstalledPackage>> {
+        let global_dir = PathBuf::from(run_yarn(&["global", "dir"])?.trim());
+        let globals = parse_yarn_global_list(&run_yarn(&["global", "list"])?);
+
+        let pb = ProgressBar::new(globals.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {bar:30} {pos}/{len} packages")
+                .unwrap(),
+        );
+
+        let mut packages = Vec::new();
+
+        for (name, version) in globals {
+            let manifest_path = global_dir
+                .join("node_modules")
+                .join(&name)
+                .join("package.json");
+            let manifest = npm::read_package_json(&manifest_path, &pb);
+            packages.push(manifest.unwrap_or_default().into_package(
+                name,
+                version,
+                PackageSource::Yarn,
+            ));
+            pb.inc(1);
+        }
+
+        pb.finish_and_clear();
+
+        Ok(packages)
+    }
+}
+
+/// Run a yarn subcommand and return its stdout.
+fn run_yarn(args: &[&str]) -> Result<String> {
+    let output = Command::new("yarn")
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run yarn {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "yarn {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    String::from_utf8(output.stdout).context("yarn output is not valid UTF-8")
+}
+
+/// Parse `yarn global list` into `(name, version)` pairs.
+///
+/// ```text
+/// yarn global v1.22.22
+/// info "typescript@5.5.3" has binaries:
+///    - tsc
+///    - tsserver
+/// Done in 0.05s.
+/// ```
+fn parse_yarn_global_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("info \"")?;
+            let (spec, _) = rest.split_once('"')?;
+            // Split on the last `@` so scoped names (`@scope/pkg`) stay intact.
+            match spec.rsplit_once('@') {
+                Some((name, version)) if !name.is_empty() => {
+                    Some((name.to_string(), version.to_string()))
+                }
+                _ => Some((spec.to_string(), "unknown".to_string())),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_global_list() {
+        let output = "\
+yarn global v1.22.22
+info \"@vue/cli@5.0.8\" has binaries:
+   - vue
+info \"typescript@5.5.3\" has binaries:
+   - tsc
+   - tsserver
+Done in 0.05s.
+";
+        assert_eq!(
+            parse_yarn_global_list(output),
+            vec![
+                ("@vue/cli".to_string(), "5.0.8".to_string()),
+                ("typescript".to_string(), "5.5.3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_global_list_empty() {
+        assert!(parse_yarn_global_list("yarn global v1.22.22\nDone in 0.03s.\n").is_empty());
+    }
+}
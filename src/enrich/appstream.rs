@@ -0,0 +1,305 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Offline AppStream catalog enrichment backend.
+//!
+//! Distributions ship AppStream metadata for the desktop applications in
+//! their repositories as a catalog under `/usr/share/swcatalog/xml` (or the
+//! legacy `/usr/share/app-info/xmls`). This backend reads that catalog to
+//! fill in the homepage, license, and donation URLs of matching projects.
+//! No network access required.
+//!
+//! Projects are matched by their homepage or repository URL, falling back to
+//! the distro package name recorded in the catalog.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+
+use super::{EnrichmentBackend, pool};
+use crate::project::{FundingChannel, UpstreamProject, canonicalize_url};
+
+/// Catalog directories, current location first.
+const CATALOG_DIRS: &[&str] = &["/usr/share/swcatalog/xml", "/usr/share/app-info/xmls"];
+
+#[derive(Default)]
+pub struct AppstreamBackend {
+    /// Loaded on first use, so that a run with nothing to enrich does not
+    /// pay for parsing the catalog.
+    catalog: OnceLock<Catalog>,
+}
+
+/// The fields of an AppStream `<component>` used for enrichment.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Component {
+    pkgname: Option<String>,
+    homepage: Option<String>,
+    vcs_browser: Option<String>,
+    license: Option<String>,
+    donation: Vec<String>,
+}
+
+/// Components indexed by canonical URL and by package name.
+#[derive(Debug, Default)]
+struct Catalog {
+    components: Vec<Component>,
+    by_url: HashMap<String, usize>,
+    by_pkgname: HashMap<String, usize>,
+}
+
+impl Catalog {
+    fn add(&mut self, component: Component) {
+        let index = self.components.len();
+        for url in [&component.homepage, &component.vcs_browser]
+            .into_iter()
+            .flatten()
+        {
+            self.by_url.entry(canonicalize_url(url)).or_insert(index);
+        }
+        if let Some(pkgname) = &component.pkgname {
+            self.by_pkgname
+                .entry(pkgname.to_lowercase())
+                .or_insert(index);
+        }
+        self.components.push(component);
+    }
+
+    /// Find the component describing a project.
+    fn find(&self, project: &UpstreamProject) -> Option<&Component> {
+        [&project.homepage, &project.repo_url]
+            .into_iter()
+            .flatten()
+            .find_map(|url| self.by_url.get(&canonicalize_url(url)))
+            .or_else(|| self.by_pkgname.get(&project.name.to_lowercase()))
+            .map(|&index| &self.components[index])
+    }
+}
+
+impl EnrichmentBackend for AppstreamBackend {
+    fn name(&self) -> &str {
+        "appstream"
+    }
+
+    fn is_available(&self) -> bool {
+        CATALOG_DIRS.iter().any(|dir| Path::new(dir).is_dir())
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let catalog = self.catalog.get_or_init(load_catalog);
+        let Some(component) = catalog.find(project) else {
+            return Ok(project.clone());
+        };
+        Ok(apply_component(project, component))
+    }
+
+    fn max_concurrency(&self) -> usize {
+        pool::WORKERS
+    }
+
+    fn is_offline(&self) -> bool {
+        true
+    }
+}
+
+/// Read every catalog file from the catalog directories. Files that cannot
+/// be read or parsed are skipped.
+fn load_catalog() -> Catalog {
+    let mut catalog = Catalog::default();
+    for dir in CATALOG_DIRS {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            let Ok(content) = read_catalog_file(&path) else {
+                continue;
+            };
+            let Ok(components) = parse_catalog(&content) else {
+                continue;
+            };
+            for component in components {
+                catalog.add(component);
+            }
+        }
+    }
+    catalog
+}
+
+/// Read a catalog file, decompressing `.xml.gz` files.
+fn read_catalog_file(path: &Path) -> Result<String> {
+    let name = path.to_string_lossy();
+    if name.ends_with(".xml.gz") {
+        let file = fs::File::open(path).with_context(|| format!("Failed to open {name}"))?;
+        let mut content = String::new();
+        GzDecoder::new(file)
+            .read_to_string(&mut content)
+            .with_context(|| format!("Failed to decompress {name}"))?;
+        Ok(content)
+    } else if name.ends_with(".xml") {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {name}"))
+    } else {
+        anyhow::bail!("Not an AppStream catalog file: {name}")
+    }
+}
+
+/// Parse the `<component>` entries of an AppStream catalog.
+fn parse_catalog(content: &str) -> Result<Vec<Component>> {
+    let doc = roxmltree::Document::parse(content).context("Invalid AppStream catalog XML")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("components") {
+        anyhow::bail!("Expected a <components> root element");
+    }
+
+    let components = root
+        .children()
+        .filter(|n| n.has_tag_name("component"))
+        .map(|node| {
+            let mut component = Component::default();
+            for child in node.children().filter(|n| n.is_element()) {
+                let text = child
+                    .text()
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(String::from);
+                match (child.tag_name().name(), child.attribute("type")) {
+                    ("pkgname", _) => component.pkgname = component.pkgname.or(text),
+                    ("project_license", _) => component.license = text,
+                    ("url", Some("homepage")) => component.homepage = text,
+                    ("url", Some("vcs-browser")) => component.vcs_browser = text,
+                    ("url", Some("donation")) => component.donation.extend(text),
+                    _ => {}
+                }
+            }
+            component
+        })
+        .collect();
+
+    Ok(components)
+}
+
+/// Fill in the fields a project is missing from its catalog entry.
+fn apply_component(project: &UpstreamProject, component: &Component) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    if enriched.homepage.is_none() {
+        enriched.homepage = component.homepage.clone();
+    }
+    if enriched.licenses.is_empty()
+        && let Some(license) = &component.license
+    {
+        enriched.licenses = vec![license.clone()];
+    }
+    for url in &component.donation {
+        if !enriched.funding.iter().any(|f| &f.url == url) {
+            enriched.funding.push(FundingChannel::from_url(url));
+        }
+    }
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<components version="0.16" origin="fedora">
+  <component type="desktop-application">
+    <id>org.gimp.GIMP</id>
+    <pkgname>gimp</pkgname>
+    <name>GNU Image Manipulation Program</name>
+    <project_license>GPL-3.0-or-later AND LGPL-3.0-or-later</project_license>
+    <url type="homepage">https://www.gimp.org/</url>
+    <url type="bugtracker">https://gitlab.gnome.org/GNOME/gimp/issues</url>
+    <url type="vcs-browser">https://gitlab.gnome.org/GNOME/gimp</url>
+    <url type="donation">https://www.gimp.org/donating/</url>
+    <url type="donation">https://liberapay.com/GIMP</url>
+  </component>
+  <component type="desktop-application">
+    <id>org.kde.krita</id>
+    <pkgname>krita</pkgname>
+    <name>Krita</name>
+    <url type="homepage">https://krita.org/</url>
+  </component>
+</components>
+"#;
+
+    fn project(name: &str, repo_url: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: name.to_string(),
+            repo_url: Some(repo_url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn catalog() -> Catalog {
+        let mut catalog = Catalog::default();
+        for component in parse_catalog(CATALOG).unwrap() {
+            catalog.add(component);
+        }
+        catalog
+    }
+
+    #[test]
+    fn parses_catalog_components() {
+        let components = parse_catalog(CATALOG).unwrap();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].pkgname.as_deref(), Some("gimp"));
+        assert_eq!(
+            components[0].vcs_browser.as_deref(),
+            Some("https://gitlab.gnome.org/GNOME/gimp")
+        );
+        assert_eq!(components[0].donation.len(), 2);
+        assert_eq!(components[1].license, None);
+
+        assert!(parse_catalog("<component/>").is_err());
+    }
+
+    #[test]
+    fn matches_by_url_then_package_name() {
+        let catalog = catalog();
+
+        let by_url = project("gimp-2.10", "https://gitlab.gnome.org/GNOME/gimp/");
+        assert_eq!(
+            catalog.find(&by_url).and_then(|c| c.pkgname.as_deref()),
+            Some("gimp")
+        );
+        let by_name = project("Krita", "https://invent.kde.org/graphics/krita");
+        assert_eq!(
+            catalog.find(&by_name).and_then(|c| c.pkgname.as_deref()),
+            Some("krita")
+        );
+        assert!(
+            catalog
+                .find(&project("inkscape", "https://inkscape.org"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn fills_missing_fields() {
+        let catalog = catalog();
+        let mut base = project("gimp", "https://gitlab.gnome.org/GNOME/gimp");
+        base.funding
+            .push(FundingChannel::from_url("https://liberapay.com/GIMP"));
+
+        let enriched = apply_component(&base, catalog.find(&base).unwrap());
+        assert_eq!(enriched.homepage.as_deref(), Some("https://www.gimp.org/"));
+        assert_eq!(
+            enriched.licenses,
+            vec!["GPL-3.0-or-later AND LGPL-3.0-or-later"]
+        );
+        assert_eq!(enriched.funding.len(), 2);
+        assert_eq!(enriched.funding[1].url, "https://www.gimp.org/donating/");
+
+        let mut licensed = base.clone();
+        licensed.licenses = vec!["GPL-3.0-only".to_string()];
+        let enriched = apply_component(&licensed, catalog.find(&licensed).unwrap());
+        assert_eq!(enriched.licenses, vec!["GPL-3.0-only"]);
+    }
+}
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Bitbucket enrichment backend.
+//!
+//! Queries the public Bitbucket Cloud API for repositories hosted on
+//! bitbucket.org. Fills in the homepage, issue tracker, and time of the last
+//! update, and scans the README for links to known funding platforms.
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::project::{Activity, FundingChannel, UpstreamProject};
+
+const API_BASE: &str = "https://api.bitbucket.org/2.0/repositories";
+
+/// README file names tried, in order, on the main branch.
+const README_NAMES: &[&str] = &["README.md", "README.rst", "README.txt", "README"];
+
+pub struct BitbucketBackend;
+
+#[derive(Debug, Deserialize)]
+struct BbRepo {
+    website: Option<String>,
+    #[serde(default)]
+    has_issues: bool,
+    links: Option<BbLinks>,
+    mainbranch: Option<BbBranch>,
+    /// Last update to the repository, which is usually the last push
+    updated_on: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BbLinks {
+    html: Option<BbLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BbLink {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BbBranch {
+    name: String,
+}
+
+impl EnrichmentBackend for BitbucketBackend {
+    fn name(&self) -> &str {
+        "bitbucket"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let workspace_repo = match project
+            .repo_url
+            .as_deref()
+            .and_then(extract_bitbucket_workspace_repo)
+        {
+            Some(wr) => wr,
+            None => return Ok(project.clone()),
+        };
+
+        let client = net::client();
+
+        let repo = match fetch_repo_metadata(client, &workspace_repo) {
+            Ok(repo) => repo,
+            Err(_) => return Ok(project.clone()),
+        };
+
+        let mut enriched = project.clone();
+
+        if enriched.homepage.is_none()
+            && let Some(website) = &repo.website
+            && !website.is_empty()
+        {
+            enriched.homepage = Some(website.clone());
+        }
+
+        let html_url = repo
+            .links
+            .and_then(|l| l.html)
+            .map(|h| h.href)
+            .unwrap_or_else(|| format!("https://bitbucket.org/{workspace_repo}"));
+        if enriched.bug_tracker.is_none() && repo.has_issues {
+            enriched.bug_tracker = Some(format!("{html_url}/issues"));
+        }
+        if enriched.activity.is_none() && repo.updated_on.is_some() {
+            enriched.activity = Some(Activity {
+                last_commit_at: repo.updated_on.clone(),
+                last_release_at: None,
+                release_cadence_days: None,
+            });
+        }
+
+        if let Some(branch) = &repo.mainbranch
+            && let Some(readme) = fetch_readme(client, &workspace_repo, &branch.name)
+        {
+            for channel in extract_funding_links(&readme) {
+                if !enriched.funding.iter().any(|f| f.url == channel.url) {
+                    enriched.funding.push(channel);
+                }
+            }
+        }
+
+        Ok(enriched)
+    }
+}
+
+/// Extract `"workspace/repo"` from a Bitbucket URL.
+///
+/// Handles:
+/// - `https://bitbucket.org/workspace/repo`
+/// - `https://bitbucket.org/workspace/repo.git`
+/// - `https://user@bitbucket.org/workspace/repo.git`
+/// - `git@bitbucket.org:workspace/repo.git`
+///
+/// Returns `None` if the URL is not a recognized Bitbucket URL.
+pub(crate) fn extract_bitbucket_workspace_repo(url: &str) -> Option<String> {
+    let path = if let Some(rest) = url.strip_prefix("git@bitbucket.org:") {
+        rest
+    } else {
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?;
+        let rest = rest.split_once('@').map_or(rest, |(_, host)| host);
+        let rest = rest.strip_prefix("www.").unwrap_or(rest);
+        rest.strip_prefix("bitbucket.org/")?
+    };
+
+    let path = path.trim_end_matches('/');
+    let mut parts = path.splitn(3, '/');
+    let workspace = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    Some(format!("{workspace}/{repo}"))
+}
+
+fn fetch_repo_metadata(client: &Client, workspace_repo: &str) -> Result<BbRepo> {
+    let response = client
+        .get(format!("{API_BASE}/{workspace_repo}"))
+        .send_paced()
+        .context("Failed to query Bitbucket API")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Bitbucket API returned {}", response.status());
+    }
+
+    response
+        .json()
+        .context("Failed to parse Bitbucket API response")
+}
+
+/// Fetch the raw README from the repository's main branch, trying each of
+/// [`README_NAMES`] in turn.
+fn fetch_readme(client: &Client, workspace_repo: &str, branch: &str) -> Option<String> {
+    README_NAMES.iter().find_map(|name| {
+        let response = client
+            .get(format!("{API_BASE}/{workspace_repo}/src/{branch}/{name}"))
+            .send_paced()
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.text().ok()
+    })
+}
+
+/// Collect links to known funding platforms from README text.
+///
+/// Any `http(s)://` URL is considered, whether bare or inside Markdown or
+/// reStructuredText link syntax. Links to unrecognized hosts are ignored,
+/// since a README links to far more than just donation pages.
+fn extract_funding_links(readme: &str) -> Vec<FundingChannel> {
+    let mut channels: Vec<FundingChannel> = Vec::new();
+
+    for (start, _) in readme.match_indices("http") {
+        let rest = &readme[start..];
+        if !rest.starts_with("https://") && !rest.starts_with("http://") {
+            continue;
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"' | '\'' | '`'))
+            .unwrap_or(rest.len());
+        let url = rest[..end].trim_end_matches(['.', ',', ';', ':']);
+
+        let channel = FundingChannel::from_url(url);
+        if channel.platform != "Custom" && !channels.iter().any(|c| c.url == channel.url) {
+            channels.push(channel);
+        }
+    }
+
+    channels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_workspace_repo_formats() {
+        let cases = [
+            "https://bitbucket.org/atlassian/python-bitbucket",
+            "https://bitbucket.org/atlassian/python-bitbucket/",
+            "https://bitbucket.org/atlassian/python-bitbucket.git",
+            "https://jdoe@bitbucket.org/atlassian/python-bitbucket.git",
+            "https://bitbucket.org/atlassian/python-bitbucket/src/master/",
+            "git@bitbucket.org:atlassian/python-bitbucket.git",
+        ];
+        for url in cases {
+            assert_eq!(
+                extract_bitbucket_workspace_repo(url).as_deref(),
+                Some("atlassian/python-bitbucket"),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn extract_workspace_repo_rejects_other_hosts() {
+        assert_eq!(
+            extract_bitbucket_workspace_repo("https://github.com/owner/repo"),
+            None
+        );
+        assert_eq!(
+            extract_bitbucket_workspace_repo("https://bitbucket.org/workspace"),
+            None
+        );
+        assert_eq!(extract_bitbucket_workspace_repo("not a url"), None);
+    }
+
+    #[test]
+    fn parse_repo_response() {
+        let json = r#"{
+            "full_name": "atlassian/python-bitbucket",
+            "website": "https://example.org",
+            "has_issues": true,
+            "links": {"html": {"href": "https://bitbucket.org/atlassian/python-bitbucket"}},
+            "mainbranch": {"name": "master", "type": "branch"},
+            "updated_on": "2024-02-19T14:03:11.504614+00:00"
+        }"#;
+        let repo: BbRepo = serde_json::from_str(json).unwrap();
+        assert_eq!(repo.website.as_deref(), Some("https://example.org"));
+        assert!(repo.has_issues);
+        assert_eq!(
+            repo.links.unwrap().html.unwrap().href,
+            "https://bitbucket.org/atlassian/python-bitbucket"
+        );
+        assert_eq!(repo.mainbranch.unwrap().name, "master");
+        assert_eq!(
+            repo.updated_on.as_deref(),
+            Some("2024-02-19T14:03:11.504614+00:00")
+        );
+    }
+
+    #[test]
+    fn funding_links_from_readme() {
+        let readme = "\
+# Project
+
+See <https://example.org/docs>.
+
+[Support us on Liberapay](https://liberapay.com/project/donate) or
+[Ko-fi](https://ko-fi.com/project). Also https://opencollective.com/project.
+
+.. _Patreon: https://www.patreon.com/project
+
+Again: https://ko-fi.com/project
+";
+        let channels = extract_funding_links(readme);
+        let found: Vec<(&str, &str)> = channels
+            .iter()
+            .map(|c| (c.platform.as_str(), c.url.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Liberapay", "https://liberapay.com/project/donate"),
+                ("Ko-fi", "https://ko-fi.com/project"),
+                ("Open Collective", "https://opencollective.com/project"),
+                ("Patreon", "https://www.patreon.com/project"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Bus factor enrichment backend.
+//!
+//! Estimates how many people keep a project going: the smallest number of
+//! authors who together wrote at least half of the commits of the past year.
+//! Commits are read from the GitHub REST API or the Bitbucket Cloud API, up
+//! to [`MAX_COMMITS`] of the most recent ones. Bot accounts are not counted.
+//!
+//! A bus factor of 1 marks a single-maintainer project, which budget
+//! planning may want to favor.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::EnrichmentBackend;
+use super::bitbucket::extract_bitbucket_workspace_repo;
+use super::net::{self, RequestExt};
+use crate::contribute::github_good_first_issues::extract_github_owner_repo;
+use crate::github::GitHubClient;
+use crate::project::UpstreamProject;
+
+/// How far back commits are counted.
+const WINDOW_DAYS: i64 = 365;
+
+/// Most recent commits considered, i.e. one page of results.
+const MAX_COMMITS: u32 = 100;
+
+const BITBUCKET_API_BASE: &str = "https://api.bitbucket.org/2.0/repositories";
+
+pub struct BusFactorBackend {
+    pub client: Arc<GitHubClient>,
+}
+
+/// A commit from `GET /repos/{owner}/{repo}/commits`. `author` is the
+/// GitHub account, null when the commit email is not linked to one.
+#[derive(Debug, Deserialize)]
+struct GhCommit {
+    commit: GhCommitDetail,
+    author: Option<GhUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCommitDetail {
+    author: Option<GhGitAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhGitAuthor {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhUser {
+    login: String,
+}
+
+/// A page of `GET /repositories/{workspace}/{repo}/commits`, newest first.
+#[derive(Debug, Deserialize)]
+struct BbCommitPage {
+    #[serde(default)]
+    values: Vec<BbCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BbCommit {
+    date: Option<String>,
+    author: Option<BbAuthor>,
+}
+
+/// `raw` is the git author line (`Name <email>`); `user` is set when it
+/// matches a Bitbucket account.
+#[derive(Debug, Deserialize)]
+struct BbAuthor {
+    raw: Option<String>,
+    user: Option<BbUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BbUser {
+    uuid: Option<String>,
+}
+
+impl EnrichmentBackend for BusFactorBackend {
+    fn name(&self) -> &str {
+        "bus_factor"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let Some(repo_url) = project.repo_url.as_deref() else {
+            return Ok(project.clone());
+        };
+        if project.bus_factor.is_some() {
+            return Ok(project.clone());
+        }
+
+        let since = Utc::now() - chrono::Duration::days(WINDOW_DAYS);
+        let authors = if let Some(owner_repo) = extract_github_owner_repo(repo_url) {
+            self.github_authors(&owner_repo, since)?
+        } else if let Some(workspace_repo) = extract_bitbucket_workspace_repo(repo_url) {
+            bitbucket_authors(&workspace_repo, since)?
+        } else {
+            return Ok(project.clone());
+        };
+
+        let mut enriched = project.clone();
+        enriched.bus_factor = bus_factor(&authors);
+        Ok(enriched)
+    }
+}
+
+impl BusFactorBackend {
+    /// Authors of the commits made since `since`.
+    fn github_authors(&self, owner_repo: &str, since: DateTime<Utc>) -> Result<Vec<String>> {
+        let commits: Option<Vec<GhCommit>> = self.client.get_json(&format!(
+            "repos/{owner_repo}/commits?since={}&per_page={MAX_COMMITS}",
+            since.format("%Y-%m-%dT%H:%M:%SZ")
+        ))?;
+        Ok(commits
+            .unwrap_or_default()
+            .iter()
+            .filter_map(github_author)
+            .collect())
+    }
+}
+
+/// Identify a commit's author by account, falling back to the git author.
+fn github_author(commit: &GhCommit) -> Option<String> {
+    if let Some(user) = &commit.author {
+        return (!user.login.ends_with("[bot]")).then(|| user.login.to_lowercase());
+    }
+    let git = commit.commit.author.as_ref()?;
+    git.email
+        .as_deref()
+        .or(git.name.as_deref())
+        .filter(|id| !id.is_empty() && !id.contains("[bot]"))
+        .map(str::to_lowercase)
+}
+
+/// Authors of the commits made since `since`.
+fn bitbucket_authors(workspace_repo: &str, since: DateTime<Utc>) -> Result<Vec<String>> {
+    let client = net::client();
+    let response = client
+        .get(format!(
+            "{BITBUCKET_API_BASE}/{workspace_repo}/commits?pagelen={MAX_COMMITS}"
+        ))
+        .send_paced()
+        .context("Failed to query Bitbucket commits")?;
+    if !response.status().is_success() {
+        return Ok(vec![]);
+    }
+    let page: BbCommitPage = response
+        .json()
+        .context("Failed to parse Bitbucket commits")?;
+    Ok(bitbucket_page_authors(&page, since))
+}
+
+fn bitbucket_page_authors(page: &BbCommitPage, since: DateTime<Utc>) -> Vec<String> {
+    page.values
+        .iter()
+        .filter(|c| {
+            c.date
+                .as_deref()
+                .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                .is_some_and(|d| d >= since)
+        })
+        .filter_map(|c| {
+            let author = c.author.as_ref()?;
+            author
+                .user
+                .as_ref()
+                .and_then(|u| u.uuid.clone())
+                .or_else(|| author.raw.as_ref().map(|raw| raw.to_lowercase()))
+        })
+        .collect()
+}
+
+/// The smallest number of authors who together made at least half of the
+/// commits, given one entry per commit. `None` without commits.
+fn bus_factor(authors: &[String]) -> Option<u32> {
+    if authors.is_empty() {
+        return None;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for author in authors {
+        *counts.entry(author).or_default() += 1;
+    }
+    let mut counts: Vec<usize> = counts.into_values().collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut covered = 0;
+    for (i, count) in counts.iter().enumerate() {
+        covered += count;
+        if covered * 2 >= authors.len() {
+            return Some(i as u32 + 1);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authors(spec: &[(&str, usize)]) -> Vec<String> {
+        spec.iter()
+            .flat_map(|(name, n)| std::iter::repeat_n(name.to_string(), *n))
+            .collect()
+    }
+
+    #[test]
+    fn smallest_group_with_half_the_commits() {
+        assert_eq!(bus_factor(&authors(&[("alice", 80), ("bob", 20)])), Some(1));
+        assert_eq!(
+            bus_factor(&authors(&[("alice", 30), ("bob", 30), ("carol", 40)])),
+            Some(2)
+        );
+        assert_eq!(
+            bus_factor(&authors(&[("a", 1), ("b", 1), ("c", 1), ("d", 1)])),
+            Some(2)
+        );
+        assert_eq!(bus_factor(&[]), None);
+    }
+
+    #[test]
+    fn github_commit_authors() {
+        let json = r#"[
+            {"commit": {"author": {"name": "Alice", "email": "alice@example.org"}},
+             "author": {"login": "Alice"}},
+            {"commit": {"author": {"name": "Bob", "email": "bob@example.org"}},
+             "author": null},
+            {"commit": {"author": {"name": "dependabot[bot]", "email": "49699333+dependabot[bot]@users.noreply.github.com"}},
+             "author": {"login": "dependabot[bot]"}}
+        ]"#;
+        let commits: Vec<GhCommit> = serde_json::from_str(json).unwrap();
+        let authors: Vec<_> = commits.iter().filter_map(github_author).collect();
+        assert_eq!(authors, vec!["alice", "bob@example.org"]);
+    }
+
+    #[test]
+    fn bitbucket_commit_authors_within_window() {
+        let json = r#"{"pagelen": 100, "values": [
+            {"date": "2024-06-01T10:00:00+00:00",
+             "author": {"raw": "Alice <alice@example.org>", "user": {"uuid": "{a1}"}}},
+            {"date": "2024-05-01T10:00:00+00:00",
+             "author": {"raw": "Bob <bob@example.org>"}},
+            {"date": "2022-01-01T10:00:00+00:00",
+             "author": {"raw": "Carol <carol@example.org>"}}
+        ]}"#;
+        let page: BbCommitPage = serde_json::from_str(json).unwrap();
+        let since = DateTime::parse_from_rfc3339("2023-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            bitbucket_page_authors(&page, since),
+            vec!["{a1}", "bob <bob@example.org>"]
+        );
+    }
+}
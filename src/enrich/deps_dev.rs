@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! deps.dev enrichment backend.
+//!
+//! Queries Google's Open Source Insights API (deps.dev) for projects hosted
+//! on GitHub, GitLab, or Bitbucket. The project endpoint provides the license
+//! and OpenSSF Scorecard results; the dependency count and the criticality
+//! come from the default version of the main package published from the
+//! repository, for the ecosystems deps.dev supports (npm, PyPI, Cargo, Go,
+//! Maven, NuGet).
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::project::{Scorecard, UpstreamProject};
+
+const API_BASE: &str = "https://api.deps.dev/v3";
+
+/// Dependent counts are only in the alpha version of the API.
+const ALPHA_API_BASE: &str = "https://api.deps.dev/v3alpha";
+
+/// Dependents from which a project gets the full criticality score, the
+/// threshold of the dependents signal of the OpenSSF criticality score.
+const CRITICAL_DEPENDENTS: u64 = 500_000;
+
+/// Hosts deps.dev knows projects for.
+const PROJECT_HOSTS: &[&str] = &["github.com", "gitlab.com", "bitbucket.org"];
+
+pub struct DepsDevBackend;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DdProject {
+    license: Option<String>,
+    scorecard: Option<DdScorecard>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DdScorecard {
+    date: Option<String>,
+    overall_score: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DdPackageVersions {
+    #[serde(default)]
+    versions: Vec<DdVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DdVersion {
+    version_key: DdVersionKey,
+    #[serde(default)]
+    is_default: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct DdVersionKey {
+    system: String,
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DdPackage {
+    #[serde(default)]
+    versions: Vec<DdVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DdDependencies {
+    #[serde(default)]
+    nodes: Vec<DdNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DdNode {
+    relation: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DdDependents {
+    dependent_count: u64,
+}
+
+impl EnrichmentBackend for DepsDevBackend {
+    fn name(&self) -> &str {
+        "deps_dev"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let key = match project.repo_url.as_deref().and_then(project_key) {
+            Some(key) => key,
+            None => return Ok(project.clone()),
+        };
+
+        let client = net::client();
+
+        let mut enriched = project.clone();
+
+        let path = format!("{API_BASE}/projects/{}", encode(&key));
+        if let Some(dd) = get_json::<DdProject>(client, &path)? {
+            if let Some(license) = dd.license
+                && license != "non-standard"
+                && !enriched.licenses.contains(&license)
+            {
+                enriched.licenses.push(license);
+            }
+            if enriched.scorecard.is_none() {
+                enriched.scorecard = dd.scorecard.and_then(|s| {
+                    Some(Scorecard {
+                        score: s.overall_score?,
+                        date: s.date,
+                    })
+                });
+            }
+        }
+
+        if (enriched.dependencies.is_none() || enriched.criticality.is_none())
+            && let Some(version) = default_version_path(client, &key)?
+        {
+            if enriched.dependencies.is_none() {
+                let path = format!("{API_BASE}/{version}:dependencies");
+                enriched.dependencies = get_json::<DdDependencies>(client, &path)?
+                    .map(|deps| count_dependencies(&deps));
+            }
+            if enriched.criticality.is_none() {
+                let path = format!("{ALPHA_API_BASE}/{version}:dependents");
+                enriched.criticality = get_json::<DdDependents>(client, &path)?
+                    .map(|d| criticality(d.dependent_count));
+            }
+        }
+
+        Ok(enriched)
+    }
+}
+
+/// The API path (`systems/{system}/packages/{name}/versions/{version}`) of
+/// the default version of the first package published from the project.
+fn default_version_path(client: &Client, key: &str) -> Result<Option<String>> {
+    let path = format!("{API_BASE}/projects/{}:packageversions", encode(key));
+    let Some(published) = get_json::<DdPackageVersions>(client, &path)? else {
+        return Ok(None);
+    };
+    let Some(first) = published.versions.first() else {
+        return Ok(None);
+    };
+    let system = first.version_key.system.to_lowercase();
+    let name = encode(&first.version_key.name);
+
+    let path = format!("{API_BASE}/systems/{system}/packages/{name}");
+    let Some(package) = get_json::<DdPackage>(client, &path)? else {
+        return Ok(None);
+    };
+    let Some(default) = package.versions.iter().find(|v| v.is_default) else {
+        return Ok(None);
+    };
+    Ok(Some(format!(
+        "systems/{system}/packages/{name}/versions/{}",
+        encode(&default.version_key.version)
+    )))
+}
+
+/// Criticality score of a project with `dependents` dependents: logarithmic,
+/// reaching 1 at [`CRITICAL_DEPENDENTS`], as the OpenSSF criticality score
+/// rates its dependents signal.
+pub fn criticality(dependents: u64) -> f64 {
+    let max = dependents.max(CRITICAL_DEPENDENTS) as f64;
+    (dependents as f64).ln_1p() / max.ln_1p()
+}
+
+/// Number of nodes in a resolved dependency graph, excluding the package
+/// itself.
+fn count_dependencies(deps: &DdDependencies) -> u64 {
+    deps.nodes.iter().filter(|n| n.relation != "SELF").count() as u64
+}
+
+/// GET an API URL, returning `None` for resources deps.dev does not know.
+fn get_json<T: DeserializeOwned>(client: &Client, url: &str) -> Result<Option<T>> {
+    let response = client
+        .get(url)
+        .send_paced()
+        .context("Failed to query deps.dev")?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        anyhow::bail!("deps.dev returned {} for {url}", response.status());
+    }
+
+    Ok(Some(
+        response
+            .json()
+            .context("Failed to parse deps.dev response")?,
+    ))
+}
+
+/// Build a deps.dev project key (`github.com/owner/repo`) from a repository
+/// URL. Returns `None` for hosts deps.dev does not track.
+fn project_key(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+
+    let mut parts = rest.split('/');
+    let host = parts.next()?.to_lowercase();
+    if !PROJECT_HOSTS.contains(&host.as_str()) {
+        return None;
+    }
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+
+    Some(format!("{host}/{owner}/{repo}").to_lowercase())
+}
+
+/// Percent-encode a value for use as a single URL path segment.
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_keys() {
+        assert_eq!(
+            project_key("https://github.com/Rust-Lang/Cargo.git").as_deref(),
+            Some("github.com/rust-lang/cargo")
+        );
+        assert_eq!(
+            project_key("https://gitlab.com/inkscape/inkscape/-/issues").as_deref(),
+            Some("gitlab.com/inkscape/inkscape")
+        );
+        assert_eq!(project_key("https://kernel.org"), None);
+        assert_eq!(project_key("https://github.com/torvalds"), None);
+    }
+
+    #[test]
+    fn encodes_path_segments() {
+        assert_eq!(
+            encode("github.com/facebook/react"),
+            "github.com%2Ffacebook%2Freact"
+        );
+        assert_eq!(encode("@types/node"), "%40types%2Fnode");
+        assert_eq!(encode("1.2.3-rc.1"), "1.2.3-rc.1");
+    }
+
+    #[test]
+    fn parse_project_response() {
+        let json = r#"{
+            "projectKey": {"id": "github.com/facebook/react"},
+            "starsCount": 220000,
+            "license": "MIT",
+            "scorecard": {
+                "date": "2024-07-29T00:00:00Z",
+                "overallScore": 6.8,
+                "checks": [{"name": "Maintained", "score": 10}]
+            }
+        }"#;
+        let project: DdProject = serde_json::from_str(json).unwrap();
+        assert_eq!(project.license.as_deref(), Some("MIT"));
+        let scorecard = project.scorecard.unwrap();
+        assert_eq!(scorecard.overall_score, Some(6.8));
+        assert_eq!(scorecard.date.as_deref(), Some("2024-07-29T00:00:00Z"));
+    }
+
+    #[test]
+    fn criticality_from_dependents() {
+        let json = r#"{"dependentCount": 5000, "directDependentCount": 120, "indirectDependentCount": 4880}"#;
+        let dependents: DdDependents = serde_json::from_str(json).unwrap();
+        assert_eq!(dependents.dependent_count, 5000);
+
+        assert_eq!(criticality(0), 0.0);
+        assert!((criticality(5000) - 0.65).abs() < 0.01);
+        assert_eq!(criticality(500_000), 1.0);
+        assert_eq!(criticality(2_000_000), 1.0);
+    }
+
+    #[test]
+    fn counts_dependencies_without_self() {
+        let json = r#"{"nodes": [
+            {"versionKey": {"system": "NPM", "name": "react", "version": "18.3.1"}, "relation": "SELF"},
+            {"versionKey": {"system": "NPM", "name": "loose-envify", "version": "1.4.0"}, "relation": "DIRECT"},
+            {"versionKey": {"system": "NPM", "name": "js-tokens", "version": "4.0.0"}, "relation": "INDIRECT"}
+        ]}"#;
+        let deps: DdDependencies = serde_json::from_str(json).unwrap();
+        assert_eq!(count_dependencies(&deps), 2);
+    }
+}
@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Offline distro packaging metadata enrichment backend.
+//!
+//! Mines the packaging metadata already on the system for a project's
+//! licenses and upstream URL, so that the network backends have less to look
+//! up:
+//!
+//! - pacman's local database (`%URL%` and `%LICENSE%`)
+//! - `.SRCINFO` files left in the build caches of AUR helpers (yay, paru)
+//! - machine-readable Debian `/usr/share/doc/<pkg>/copyright` files
+//! - RPM package headers, queried with `rpm -q`
+//!
+//! Projects are looked up by package name. No network access required.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+use super::{EnrichmentBackend, pool};
+use crate::discover::apt::{self, DOC_DIR};
+use crate::discover::dnf::{self, RPM_QUERYFORMAT};
+use crate::discover::pacman::{self, PACMAN_DB_PATH};
+use crate::project::{UpstreamProject, canonicalize_url};
+
+const RPM_PATH: &str = "/usr/bin/rpm";
+
+#[derive(Default)]
+pub struct DistroBackend {
+    /// pacman desc files by package name, built on first use.
+    pacman_index: OnceLock<HashMap<String, PathBuf>>,
+}
+
+/// What the local packaging metadata says about a package.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct LocalMetadata {
+    pub url: Option<String>,
+    pub licenses: Vec<String>,
+}
+
+impl LocalMetadata {
+    /// Take the fields still missing from `other`.
+    fn fill(&mut self, other: LocalMetadata) {
+        if self.url.is_none() {
+            self.url = other.url;
+        }
+        if self.licenses.is_empty() {
+            self.licenses = other.licenses;
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.url.is_some() && !self.licenses.is_empty()
+    }
+}
+
+impl EnrichmentBackend for DistroBackend {
+    fn name(&self) -> &str {
+        "distro"
+    }
+
+    fn is_available(&self) -> bool {
+        Path::new(PACMAN_DB_PATH).is_dir()
+            || Path::new(DOC_DIR).is_dir()
+            || Path::new(RPM_PATH).is_file()
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        if project.homepage.is_some() && !project.licenses.is_empty() {
+            return Ok(project.clone());
+        }
+        Ok(apply_metadata(project, self.lookup(&project.name)))
+    }
+
+    // Reads local files and runs `rpm`; nothing remote to protect.
+    fn max_concurrency(&self) -> usize {
+        pool::WORKERS
+    }
+
+    fn is_offline(&self) -> bool {
+        true
+    }
+}
+
+impl DistroBackend {
+    /// Look a package up in every local source. Earlier sources take
+    /// precedence; later ones only fill in what is still missing.
+    pub(crate) fn lookup(&self, name: &str) -> LocalMetadata {
+        let sources: [fn(&Self, &str) -> Option<LocalMetadata>; 4] =
+            [Self::pacman, Self::srcinfo, Self::debian, Self::rpm];
+
+        let mut metadata = LocalMetadata::default();
+        for source in sources {
+            if metadata.is_complete() {
+                break;
+            }
+            if let Some(found) = source(self, name) {
+                metadata.fill(found);
+            }
+        }
+        metadata
+    }
+
+    fn pacman(&self, name: &str) -> Option<LocalMetadata> {
+        let index = self.pacman_index.get_or_init(|| {
+            let Ok(entries) = fs::read_dir(PACMAN_DB_PATH) else {
+                return HashMap::new();
+            };
+            entries
+                .flatten()
+                .map(|entry| {
+                    let dir_name = entry.file_name().to_string_lossy().into_owned();
+                    let name = pacman::package_name_from_dir(&dir_name).to_string();
+                    (name, entry.path().join("desc"))
+                })
+                .collect()
+        });
+        let pkg = pacman::parse_desc(index.get(name)?).ok()?;
+        Some(LocalMetadata {
+            url: pkg.url,
+            licenses: pkg.licenses,
+        })
+    }
+
+    fn srcinfo(&self, name: &str) -> Option<LocalMetadata> {
+        srcinfo_paths(name)
+            .into_iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|content| parse_srcinfo(&content))
+    }
+
+    fn debian(&self, name: &str) -> Option<LocalMetadata> {
+        let content = fs::read_to_string(Path::new(DOC_DIR).join(name).join("copyright")).ok()?;
+        Some(LocalMetadata {
+            url: apt::copyright_source(&content),
+            licenses: apt::parse_copyright(&content),
+        })
+    }
+
+    fn rpm(&self, name: &str) -> Option<LocalMetadata> {
+        if !Path::new(RPM_PATH).is_file() {
+            return None;
+        }
+        let output = Command::new(RPM_PATH)
+            .args(["-q", "--queryformat", RPM_QUERYFORMAT, name])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Multilib packages are listed once per architecture.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let pkg = dnf::parse_rpm_line(stdout.lines().next()?).ok()?;
+        Some(LocalMetadata {
+            url: pkg.url,
+            licenses: pkg.licenses,
+        })
+    }
+}
+
+/// Where AUR helpers keep the `.SRCINFO` of the packages they built.
+fn srcinfo_paths(name: &str) -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return vec![];
+    };
+    let cache = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".cache"));
+    vec![
+        cache.join("yay").join(name).join(".SRCINFO"),
+        cache.join("paru/clone").join(name).join(".SRCINFO"),
+    ]
+}
+
+/// Parse the `url` and `license` fields of a `.SRCINFO` file's `pkgbase`
+/// section.
+///
+/// ```text
+/// pkgbase = foo-git
+///     pkgver = 1.2.r3.gabcdef
+///     url = https://github.com/foo/foo
+///     license = MIT
+///
+/// pkgname = foo-git
+/// ```
+fn parse_srcinfo(content: &str) -> LocalMetadata {
+    let mut metadata = LocalMetadata::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "pkgname" => break,
+            "url" if !value.is_empty() => metadata.url = Some(value.to_string()),
+            "license" if !value.is_empty() => metadata.licenses.push(value.to_string()),
+            _ => {}
+        }
+    }
+    metadata
+}
+
+/// Fill in the licenses and upstream URL a project is missing. The URL
+/// becomes the repository URL of projects that have none, and the homepage
+/// of the others when it points elsewhere.
+fn apply_metadata(project: &UpstreamProject, metadata: LocalMetadata) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    if enriched.licenses.is_empty() {
+        enriched.licenses = metadata.licenses;
+    }
+    if let Some(url) = metadata.url {
+        match &enriched.repo_url {
+            None => enriched.repo_url = Some(url),
+            Some(repo_url) => {
+                if enriched.homepage.is_none()
+                    && canonicalize_url(repo_url) != canonicalize_url(&url)
+                {
+                    enriched.homepage = Some(url);
+                }
+            }
+        }
+    }
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(repo_url: Option<&str>) -> UpstreamProject {
+        UpstreamProject {
+            name: "foo".to_string(),
+            repo_url: repo_url.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_srcinfo_pkgbase() {
+        let content = "\
+pkgbase = foo-git
+\tpkgdesc = Foo, from git
+\tpkgver = 1.2.r3.gabcdef
+\turl = https://github.com/foo/foo
+\tarch = x86_64
+\tlicense = MIT
+\tlicense = Apache-2.0
+
+pkgname = foo-git
+\turl = https://example.org/split
+\tlicense = GPL-3.0-only
+";
+        assert_eq!(
+            parse_srcinfo(content),
+            LocalMetadata {
+                url: Some("https://github.com/foo/foo".to_string()),
+                licenses: vec!["MIT".to_string(), "Apache-2.0".to_string()],
+            }
+        );
+        assert_eq!(parse_srcinfo(""), LocalMetadata::default());
+    }
+
+    #[test]
+    fn earlier_sources_take_precedence() {
+        let mut metadata = LocalMetadata {
+            url: None,
+            licenses: vec!["MIT".to_string()],
+        };
+        assert!(!metadata.is_complete());
+        metadata.fill(LocalMetadata {
+            url: Some("https://example.org/foo".to_string()),
+            licenses: vec!["GPL-2.0-only".to_string()],
+        });
+        assert_eq!(metadata.url.as_deref(), Some("https://example.org/foo"));
+        assert_eq!(metadata.licenses, vec!["MIT"]);
+        assert!(metadata.is_complete());
+    }
+
+    #[test]
+    fn applies_url_and_licenses() {
+        let metadata = LocalMetadata {
+            url: Some("https://foo.example.org/".to_string()),
+            licenses: vec!["MIT".to_string()],
+        };
+
+        let enriched = apply_metadata(&project(None), metadata.clone());
+        assert_eq!(
+            enriched.repo_url.as_deref(),
+            Some("https://foo.example.org/")
+        );
+        assert_eq!(enriched.homepage, None);
+        assert_eq!(enriched.licenses, vec!["MIT"]);
+
+        let hosted = project(Some("https://github.com/foo/foo"));
+        let enriched = apply_metadata(&hosted, metadata);
+        assert_eq!(
+            enriched.homepage.as_deref(),
+            Some("https://foo.example.org/")
+        );
+
+        let same = LocalMetadata {
+            url: Some("https://github.com/foo/foo/".to_string()),
+            licenses: vec![],
+        };
+        assert_eq!(apply_metadata(&hosted, same).homepage, None);
+    }
+}
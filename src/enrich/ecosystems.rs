@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! ecosyste.ms enrichment backend.
+//!
+//! Looks a project up in the ecosyste.ms package index, which covers most
+//! language registries, by its repository URL. Serves as a fallback for
+//! whatever the host-specific backends could not fill in: repository URL,
+//! homepage, funding links, dependent package counts, and the latest release.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::project::{FundingChannel, Release, UpstreamProject};
+
+const LOOKUP_URL: &str = "https://packages.ecosyste.ms/api/v1/packages/lookup";
+
+pub struct EcosystemsBackend;
+
+/// A registry package from the lookup endpoint.
+#[derive(Debug, Deserialize)]
+struct EcosystemsPackage {
+    repository_url: Option<String>,
+    homepage: Option<String>,
+    #[serde(default)]
+    funding_links: Vec<String>,
+    dependent_packages_count: Option<u64>,
+    latest_release_number: Option<String>,
+    latest_release_published_at: Option<String>,
+}
+
+impl EnrichmentBackend for EcosystemsBackend {
+    fn name(&self) -> &str {
+        "ecosystems"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let repo_url = match &project.repo_url {
+            Some(url) => url,
+            None => return Ok(project.clone()),
+        };
+
+        let client = net::client();
+
+        let response = client
+            .get(LOOKUP_URL)
+            .query(&[("repository_url", repo_url)])
+            .send_paced()
+            .context("Failed to query ecosyste.ms")?;
+
+        if !response.status().is_success() {
+            return Ok(project.clone());
+        }
+
+        let packages: Vec<EcosystemsPackage> = response
+            .json()
+            .context("Failed to parse ecosyste.ms response")?;
+
+        Ok(apply_packages(project, &packages))
+    }
+}
+
+/// Fill a project in from the registry packages published from its
+/// repository.
+///
+/// A repository may publish to several registries (e.g. a library with npm
+/// and PyPI bindings), so dependents are summed across them and the most
+/// recently published release is kept.
+fn apply_packages(project: &UpstreamProject, packages: &[EcosystemsPackage]) -> UpstreamProject {
+    let mut enriched = project.clone();
+    if packages.is_empty() {
+        return enriched;
+    }
+
+    let non_empty = |url: &Option<String>| url.clone().filter(|u| !u.is_empty());
+
+    if let Some(url) = packages.iter().find_map(|p| non_empty(&p.repository_url)) {
+        enriched.repo_url = Some(url);
+    }
+    if enriched.homepage.is_none() {
+        enriched.homepage = packages.iter().find_map(|p| non_empty(&p.homepage));
+    }
+
+    for url in packages.iter().flat_map(|p| &p.funding_links) {
+        if !url.is_empty() && !enriched.funding.iter().any(|f| &f.url == url) {
+            enriched.funding.push(FundingChannel::from_url(url));
+        }
+    }
+
+    if enriched.dependents.is_none() {
+        let counts: Vec<u64> = packages
+            .iter()
+            .filter_map(|p| p.dependent_packages_count)
+            .collect();
+        if !counts.is_empty() {
+            enriched.dependents = Some(counts.iter().sum());
+        }
+    }
+
+    if enriched.latest_release.is_none() {
+        // RFC 3339 timestamps in the same zone compare correctly as strings.
+        enriched.latest_release = packages
+            .iter()
+            .filter_map(|p| {
+                Some(Release {
+                    version: non_empty(&p.latest_release_number)?,
+                    published_at: p.latest_release_published_at.clone(),
+                })
+            })
+            .max_by(|a, b| a.published_at.cmp(&b.published_at));
+    }
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(repo_url: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: "test".to_string(),
+            repo_url: Some(repo_url.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn aggregates_packages_across_registries() {
+        let json = r#"[
+            {
+                "name": "protobuf",
+                "ecosystem": "pypi",
+                "repository_url": "https://github.com/protocolbuffers/protobuf",
+                "homepage": "https://developers.google.com/protocol-buffers/",
+                "funding_links": [],
+                "dependent_packages_count": 9000,
+                "latest_release_number": "5.27.2",
+                "latest_release_published_at": "2024-06-25T20:06:43.000Z"
+            },
+            {
+                "name": "google-protobuf",
+                "ecosystem": "npm",
+                "repository_url": "https://github.com/protocolbuffers/protobuf",
+                "homepage": null,
+                "funding_links": ["https://opencollective.com/protobuf"],
+                "dependent_packages_count": 3000,
+                "latest_release_number": "3.21.4",
+                "latest_release_published_at": "2024-07-30T17:40:02.000Z"
+            },
+            {
+                "name": "protobuf-unreleased",
+                "ecosystem": "cargo",
+                "repository_url": "https://github.com/protocolbuffers/protobuf",
+                "dependent_packages_count": null,
+                "latest_release_number": null
+            }
+        ]"#;
+        let packages: Vec<EcosystemsPackage> = serde_json::from_str(json).unwrap();
+        let enriched = apply_packages(&project("https://protobuf.dev"), &packages);
+
+        assert_eq!(
+            enriched.repo_url.as_deref(),
+            Some("https://github.com/protocolbuffers/protobuf")
+        );
+        assert_eq!(
+            enriched.homepage.as_deref(),
+            Some("https://developers.google.com/protocol-buffers/")
+        );
+        assert_eq!(enriched.funding.len(), 1);
+        assert_eq!(enriched.funding[0].platform, "Open Collective");
+        assert_eq!(enriched.dependents, Some(12000));
+        assert_eq!(
+            enriched.latest_release,
+            Some(Release {
+                version: "3.21.4".to_string(),
+                published_at: Some("2024-07-30T17:40:02.000Z".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn no_packages_leaves_project_unchanged() {
+        let enriched = apply_packages(&project("https://example.org"), &[]);
+        assert_eq!(enriched.repo_url.as_deref(), Some("https://example.org"));
+        assert_eq!(enriched.dependents, None);
+        assert_eq!(enriched.latest_release, None);
+    }
+}
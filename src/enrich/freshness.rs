@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Per-field expiry of cached enrichment.
+//!
+//! Cached projects record which backend supplied each field and when (see
+//! [`UpstreamProject::provenance`]). Figures that keep changing, such as
+//! stars, activity, or known vulnerabilities, expire after
+//! [`VOLATILE_MAX_AGE_DAYS`]: they are cleared, and only the backends that
+//! supplied them run again. Whole cache entries expire after
+//! [`crate::storage::ENRICHMENT_MAX_AGE_DAYS`], so that fields no backend
+//! found are looked up again eventually.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::project::UpstreamProject;
+
+/// Days after which a volatile field is fetched again.
+pub const VOLATILE_MAX_AGE_DAYS: i64 = 7;
+
+/// Fields whose values change from week to week. Funding figures are
+/// tracked per platform, as `funding_status.<platform>`.
+const VOLATILE_FIELDS: &[&str] = &[
+    "stars",
+    "dependents",
+    "dependencies",
+    "criticality",
+    "latest_release",
+    "scorecard",
+    "vulnerabilities",
+    "activity",
+    "bus_factor",
+    "deprecation",
+    "funding_status",
+];
+
+/// Clear the volatile fields of `project` that were fetched more than
+/// [`VOLATILE_MAX_AGE_DAYS`] before `now`, returning the names of the
+/// backends that supplied them.
+pub fn expire_stale_fields(project: &mut UpstreamProject, now: DateTime<Utc>) -> BTreeSet<String> {
+    let max_age = Duration::days(VOLATILE_MAX_AGE_DAYS);
+    let stale: Vec<String> = project
+        .provenance
+        .iter()
+        .filter(|(field, source)| is_volatile(field) && now - source.fetched_at > max_age)
+        .map(|(field, _)| field.clone())
+        .collect();
+
+    let mut backends = BTreeSet::new();
+    for field in stale {
+        clear_field(project, &field);
+        if let Some(source) = project.provenance.remove(&field) {
+            backends.insert(source.backend);
+        }
+    }
+    backends
+}
+
+fn is_volatile(field: &str) -> bool {
+    let field = field.split_once('.').map_or(field, |(name, _)| name);
+    VOLATILE_FIELDS.contains(&field)
+}
+
+fn clear_field(project: &mut UpstreamProject, field: &str) {
+    match field.split_once('.') {
+        Some(("funding_status", platform)) => {
+            project.funding_status.retain(|s| s.platform != platform)
+        }
+        _ => match field {
+            "stars" => project.stars = None,
+            "dependents" => project.dependents = None,
+            "dependencies" => project.dependencies = None,
+            "criticality" => project.criticality = None,
+            "latest_release" => project.latest_release = None,
+            "scorecard" => project.scorecard = None,
+            "vulnerabilities" => project.vulnerabilities = None,
+            "activity" => project.activity = None,
+            "bus_factor" => project.bus_factor = None,
+            "deprecation" => project.deprecation = None,
+            _ => {}
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrich::merge_backend_result;
+    use crate::project::{Activity, FundingStatus};
+
+    fn project() -> UpstreamProject {
+        UpstreamProject {
+            name: "curl".to_string(),
+            repo_url: Some("https://github.com/curl/curl".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn status(platform: &str) -> FundingStatus {
+        FundingStatus {
+            platform: platform.to_string(),
+            currency: "USD".to_string(),
+            balance: Some(100.0),
+            yearly_income: None,
+            goals: vec![],
+        }
+    }
+
+    #[test]
+    fn expires_only_old_volatile_fields() {
+        let now = Utc::now();
+        let old = now - Duration::days(VOLATILE_MAX_AGE_DAYS + 1);
+
+        let mut github = project();
+        github.stars = Some(42);
+        github.homepage = Some("https://curl.se".to_string());
+        github.activity = Some(Activity {
+            last_commit_at: Some("2024-01-01T00:00:00Z".to_string()),
+            last_release_at: None,
+            release_cadence_days: None,
+        });
+        let mut deps_dev = project();
+        deps_dev.dependencies = Some(3);
+
+        let cached = merge_backend_result(&project(), &github, "github", old);
+        let mut cached = merge_backend_result(&cached, &deps_dev, "deps_dev", now);
+
+        let backends = expire_stale_fields(&mut cached, now);
+        assert_eq!(backends, BTreeSet::from(["github".to_string()]));
+        assert_eq!(cached.stars, None);
+        assert_eq!(cached.activity, None);
+        assert!(!cached.provenance.contains_key("stars"));
+        // Stable fields and recent figures are kept.
+        assert_eq!(cached.homepage.as_deref(), Some("https://curl.se"));
+        assert_eq!(cached.provenance["homepage"].backend, "github");
+        assert_eq!(cached.dependencies, Some(3));
+    }
+
+    #[test]
+    fn expires_funding_figures_per_platform() {
+        let now = Utc::now();
+        let old = now - Duration::days(VOLATILE_MAX_AGE_DAYS + 1);
+
+        let mut open_collective = project();
+        open_collective.funding_status = vec![status("open_collective")];
+        let mut liberapay = project();
+        liberapay.funding_status = vec![status("liberapay")];
+
+        let cached = merge_backend_result(&project(), &open_collective, "open_collective", old);
+        let mut cached = merge_backend_result(&cached, &liberapay, "liberapay", now);
+
+        let backends = expire_stale_fields(&mut cached, now);
+        assert_eq!(backends, BTreeSet::from(["open_collective".to_string()]));
+        let platforms: Vec<_> = cached.funding_status.iter().map(|s| &s.platform).collect();
+        assert_eq!(platforms, ["liberapay"]);
+    }
+
+    #[test]
+    fn fields_from_package_metadata_never_expire() {
+        let mut cached = project();
+        cached.stars = Some(7);
+        assert!(expire_stale_fields(&mut cached, Utc::now()).is_empty());
+        assert_eq!(cached.stars, Some(7));
+    }
+}
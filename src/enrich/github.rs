@@ -2,51 +2,121 @@
 
 //! GitHub enrichment backend.
 //!
-//! Uses the `gh` CLI to fetch repository metadata and FUNDING.yml from GitHub.
+//! Fetches repository metadata (including whether it is archived or
+//! deprecated), recent releases, and FUNDING.yml through the GitHub REST
+//! API, and the sponsorship tiers of GitHub Sponsors accounts through the GraphQL
+//! API when a token is configured.
 
-use std::process::Command;
+use std::sync::Arc;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use serde::Deserialize;
 
-use super::EnrichmentBackend;
+use super::kofi::kofi_handle;
+use super::{EnrichmentBackend, detect_deprecation, release_cadence_days};
 use crate::contribute::github_good_first_issues::extract_github_owner_repo;
-use crate::project::{FundingChannel, UpstreamProject};
+use crate::github::GitHubClient;
+use crate::project::{
+    Activity, FundingChannel, FundingPage, FundingTier, Release, UpstreamProject,
+};
 
-pub struct GitHubBackend;
+pub struct GitHubBackend {
+    pub client: Arc<GitHubClient>,
+}
 
+/// The subset of `GET /repos/{owner}/{repo}` used for enrichment.
 #[derive(Debug, Deserialize)]
 struct GhRepo {
-    #[serde(rename = "stargazerCount")]
-    stargazer_count: Option<u64>,
-    #[serde(rename = "homepageUrl")]
-    homepage_url: Option<String>,
-    #[serde(rename = "licenseInfo")]
-    license_info: Option<GhLicense>,
-    #[serde(rename = "hasIssuesEnabled")]
-    has_issues_enabled: Option<bool>,
-    url: Option<String>,
-    #[allow(dead_code)]
+    stargazers_count: Option<u64>,
+    homepage: Option<String>,
+    license: Option<GhLicense>,
+    #[serde(default)]
+    has_issues: bool,
+    html_url: Option<String>,
+    pushed_at: Option<String>,
     description: Option<String>,
+    #[serde(default)]
+    archived: bool,
 }
 
+/// A release from `GET /repos/{owner}/{repo}/releases`, newest first.
+#[derive(Debug, Deserialize)]
+struct GhRelease {
+    tag_name: String,
+    published_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// Releases fetched to estimate the release cadence.
+const RELEASES_PER_PAGE: u32 = 10;
+
 #[derive(Debug, Deserialize)]
 struct GhLicense {
-    #[serde(rename = "spdxId")]
     spdx_id: Option<String>,
 }
 
+/// A file from `GET /repos/{owner}/{repo}/contents/{path}`.
+#[derive(Debug, Deserialize)]
+struct GhContent {
+    content: String,
+}
+
+const SPONSOR_TIERS_QUERY: &str = "query($login: String!) {
+  repositoryOwner(login: $login) {
+    ... on Sponsorable {
+      sponsorsListing {
+        tiers(first: 20) {
+          nodes { name monthlyPriceInDollars isOneTime isCustomAmount }
+        }
+      }
+    }
+  }
+}";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhSponsorsData {
+    repository_owner: Option<GhSponsorable>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhSponsorable {
+    sponsors_listing: Option<GhSponsorsListing>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhSponsorsListing {
+    tiers: GhTierConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhTierConnection {
+    #[serde(default)]
+    nodes: Vec<GhTier>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhTier {
+    name: String,
+    monthly_price_in_dollars: u64,
+    #[serde(default)]
+    is_one_time: bool,
+    #[serde(default)]
+    is_custom_amount: bool,
+}
+
 impl EnrichmentBackend for GitHubBackend {
     fn name(&self) -> &str {
         "github"
     }
 
     fn is_available(&self) -> bool {
-        Command::new("gh")
-            .args(["auth", "status"])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        true
     }
 
     fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
@@ -63,25 +133,31 @@ impl EnrichmentBackend for GitHubBackend {
         let mut enriched = project.clone();
 
         // Fetch repo metadata
-        if let Ok(repo) = fetch_repo_metadata(&owner_repo) {
+        let mut pushed_at = None;
+        if let Ok(Some(repo)) = self.fetch_repo_metadata(&owner_repo) {
+            pushed_at = repo.pushed_at.clone();
+            if enriched.deprecation.is_none() {
+                enriched.deprecation =
+                    detect_deprecation(repo.archived, repo.description.as_deref(), "github.com");
+            }
             if enriched.stars.is_none() {
-                enriched.stars = repo.stargazer_count;
+                enriched.stars = repo.stargazers_count;
             }
             if enriched.homepage.is_none()
-                && let Some(hp) = &repo.homepage_url
+                && let Some(hp) = &repo.homepage
                 && !hp.is_empty()
             {
                 enriched.homepage = Some(hp.clone());
             }
-            if let Some(license) = &repo.license_info
+            if let Some(license) = &repo.license
                 && let Some(spdx) = &license.spdx_id
                 && spdx != "NOASSERTION"
                 && !enriched.licenses.iter().any(|l| l == spdx)
             {
                 enriched.licenses.push(spdx.clone());
             }
-            if let Some(html_url) = &repo.url {
-                if enriched.bug_tracker.is_none() && repo.has_issues_enabled.unwrap_or(false) {
+            if let Some(html_url) = &repo.html_url {
+                if enriched.bug_tracker.is_none() && repo.has_issues {
                     enriched.bug_tracker = Some(format!("{html_url}/issues"));
                 }
                 if enriched.good_first_issues_url.is_none() {
@@ -96,8 +172,18 @@ impl EnrichmentBackend for GitHubBackend {
             }
         }
 
+        // Fetch recent releases
+        let releases = self.fetch_releases(&owner_repo).unwrap_or_default();
+        let (latest_release, activity) = release_activity(&releases, pushed_at);
+        if enriched.latest_release.is_none() {
+            enriched.latest_release = latest_release;
+        }
+        if enriched.activity.is_none() {
+            enriched.activity = activity;
+        }
+
         // Fetch FUNDING.yml
-        if let Ok(channels) = fetch_funding_yml(&owner_repo) {
+        if let Ok(channels) = self.fetch_funding_yml(&owner_repo) {
             for channel in channels {
                 if !enriched.funding.iter().any(|f| f.url == channel.url) {
                     enriched.funding.push(channel);
@@ -105,80 +191,114 @@ impl EnrichmentBackend for GitHubBackend {
             }
         }
 
+        // Fetch sponsorship tiers (GraphQL only, which needs a token)
+        if self.client.has_token() {
+            for channel in enriched.funding.iter_mut().filter(|f| f.page.is_none()) {
+                if let Some(login) = sponsors_login(&channel.url)
+                    && let Ok(tiers) = self.fetch_sponsor_tiers(login)
+                    && !tiers.is_empty()
+                {
+                    channel.page = Some(FundingPage {
+                        title: None,
+                        goal: None,
+                        tiers,
+                    });
+                }
+            }
+        }
+
         Ok(enriched)
     }
 }
 
-fn fetch_repo_metadata(owner_repo: &str) -> Result<GhRepo> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{owner_repo}"),
-            "--jq",
-            ".",
-            "--cache",
-            "1h",
-        ])
-        .output()
-        .context("Failed to run gh api")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("gh api failed for {owner_repo}: {stderr}");
+impl GitHubBackend {
+    fn fetch_sponsor_tiers(&self, login: &str) -> Result<Vec<FundingTier>> {
+        let data: Option<GhSponsorsData> = self
+            .client
+            .graphql(SPONSOR_TIERS_QUERY, serde_json::json!({ "login": login }))?;
+        Ok(data.map(sponsor_tiers).unwrap_or_default())
     }
 
-    // gh api returns REST JSON; map to our struct
-    let raw: serde_json::Value =
-        serde_json::from_slice(&output.stdout).context("Failed to parse gh api JSON")?;
-
-    let repo = GhRepo {
-        stargazer_count: raw.get("stargazers_count").and_then(|v| v.as_u64()),
-        homepage_url: raw
-            .get("homepage")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-        license_info: raw.get("license").and_then(|v| {
-            v.get("spdx_id")
-                .and_then(|s| s.as_str())
-                .map(|spdx| GhLicense {
-                    spdx_id: Some(spdx.to_string()),
-                })
-        }),
-        has_issues_enabled: raw.get("has_issues").and_then(|v| v.as_bool()),
-        url: raw
-            .get("html_url")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-        description: raw
-            .get("description")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-    };
+    fn fetch_repo_metadata(&self, owner_repo: &str) -> Result<Option<GhRepo>> {
+        self.client.get_json(&format!("repos/{owner_repo}"))
+    }
 
-    Ok(repo)
-}
+    fn fetch_releases(&self, owner_repo: &str) -> Result<Vec<GhRelease>> {
+        let releases: Option<Vec<GhRelease>> = self.client.get_json(&format!(
+            "repos/{owner_repo}/releases?per_page={RELEASES_PER_PAGE}"
+        ))?;
+        Ok(releases.unwrap_or_default())
+    }
+
+    fn fetch_funding_yml(&self, owner_repo: &str) -> Result<Vec<FundingChannel>> {
+        let file: Option<GhContent> = self
+            .client
+            .get_json(&format!("repos/{owner_repo}/contents/.github/FUNDING.yml"))?;
 
-fn fetch_funding_yml(owner_repo: &str) -> Result<Vec<FundingChannel>> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{owner_repo}/contents/.github/FUNDING.yml"),
-            "--jq",
-            ".content",
-            "--cache",
-            "1h",
-        ])
-        .output()
-        .context("Failed to run gh api for FUNDING.yml")?;
-
-    if !output.status.success() {
-        return Ok(Vec::new());
+        Ok(file
+            .map(|f| parse_funding_yml(&decode_base64_content(&f.content)))
+            .unwrap_or_default())
     }
+}
 
-    let stdout = String::from_utf8(output.stdout).unwrap_or_default();
-    let content = decode_base64_content(&stdout);
+/// The latest stable release and the repository's activity, from its recent
+/// releases and the time of the last push. Drafts and pre-releases are
+/// ignored.
+fn release_activity(
+    releases: &[GhRelease],
+    pushed_at: Option<String>,
+) -> (Option<Release>, Option<Activity>) {
+    let stable: Vec<&GhRelease> = releases
+        .iter()
+        .filter(|r| !r.draft && !r.prerelease)
+        .collect();
+    let latest = stable.first().map(|r| Release {
+        version: r.tag_name.trim_start_matches('v').to_string(),
+        published_at: r.published_at.clone(),
+    });
+    let last_release_at = latest.as_ref().and_then(|r| r.published_at.clone());
+    let release_cadence_days =
+        release_cadence_days(stable.iter().filter_map(|r| r.published_at.as_deref()));
+
+    let activity = (pushed_at.is_some() || last_release_at.is_some()).then_some(Activity {
+        last_commit_at: pushed_at,
+        last_release_at,
+        release_cadence_days,
+    });
+    (latest, activity)
+}
 
-    Ok(parse_funding_yml(&content))
+/// Extract the account login from a GitHub Sponsors URL
+/// (`https://github.com/sponsors/<login>`).
+fn sponsors_login(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    rest.strip_prefix("github.com/sponsors/")?
+        .split(['/', '?', '#'])
+        .next()
+        .filter(|login| !login.is_empty())
+}
+
+/// The fixed-price tiers of a sponsors listing, cheapest first. Custom-amount
+/// tiers ("pay what you want") have no price to suggest.
+fn sponsor_tiers(data: GhSponsorsData) -> Vec<FundingTier> {
+    let mut tiers: Vec<FundingTier> = data
+        .repository_owner
+        .and_then(|owner| owner.sponsors_listing)
+        .map(|listing| listing.tiers.nodes)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|tier| !tier.is_custom_amount)
+        .map(|tier| FundingTier {
+            name: tier.name,
+            amount: tier.monthly_price_in_dollars,
+            one_time: tier.is_one_time,
+        })
+        .collect();
+    tiers.sort_by_key(|tier| (tier.one_time, tier.amount));
+    tiers
 }
 
 /// Decode base64 content from GitHub API (may contain newlines within the encoding).
@@ -236,7 +356,33 @@ fn base64_decode(input: &str) -> Option<String> {
     String::from_utf8(buf).ok()
 }
 
-/// Parse a FUNDING.yml file line-by-line (simple key: value format, no full YAML).
+/// Funding platforms of FUNDING.yml whose entries are account names, with
+/// the platform label and the URL prefix of their pages.
+const FUNDING_PLATFORMS: &[(&str, &str, &str)] = &[
+    ("github", "GitHub Sponsors", "https://github.com/sponsors/"),
+    (
+        "open_collective",
+        "Open Collective",
+        "https://opencollective.com/",
+    ),
+    ("patreon", "Patreon", "https://www.patreon.com/"),
+    ("liberapay", "Liberapay", "https://liberapay.com/"),
+    (
+        "community_bridge",
+        "Community Bridge",
+        "https://funding.communitybridge.org/projects/",
+    ),
+    ("issuehunt", "IssueHunt", "https://issuehunt.io/r/"),
+    ("polar", "Polar", "https://polar.sh/"),
+    (
+        "buy_me_a_coffee",
+        "Buy Me a Coffee",
+        "https://buymeacoffee.com/",
+    ),
+    ("thanks_dev", "thanks.dev", "https://thanks.dev/d/gh/"),
+];
+
+/// Parse a FUNDING.yml file.
 ///
 /// Recognizes common funding platforms:
 /// - `github: username` or `github: [user1, user2]`
@@ -245,166 +391,78 @@ fn base64_decode(input: &str) -> Option<String> {
 /// - `patreon: username`
 /// - `liberapay: username`
 /// - `custom: [url1, url2]` or `custom: url`
+///
+/// Values may be scalars, flow or block sequences, and quoted. Empty keys,
+/// such as those of GitHub's commented template, and malformed files yield
+/// no channels.
 fn parse_funding_yml(content: &str) -> Vec<FundingChannel> {
-    let mut channels = Vec::new();
-
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
+    let Ok(serde_yaml::Value::Mapping(entries)) = serde_yaml::from_str(content) else {
+        return Vec::new();
+    };
 
-        let Some((key, value)) = line.split_once(':') else {
+    let mut channels = Vec::new();
+    for (key, value) in &entries {
+        let Some(key) = key.as_str().map(str::to_lowercase) else {
             continue;
         };
-
-        let key = key.trim().to_lowercase();
-        // Strip inline YAML comments (` #` or a bare `#` at the start of the
-        // value).  This filters out template placeholders such as
-        // `github: # Replace with up to 4 GitHub Sponsors-enabled usernames`.
-        let value = value.trim();
-        let value = match value.find(" #") {
-            Some(pos) => value[..pos].trim(),
-            None if value.starts_with('#') => "",
-            None => value,
-        };
-
-        if value.is_empty() {
-            continue;
-        }
+        let values = yaml_strings(value);
 
         match key.as_str() {
-            "github" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "GitHub Sponsors".to_string(),
-                            url: format!("https://github.com/sponsors/{name}"),
-                        });
-                    }
-                }
-            }
-            "open_collective" => {
-                for slug in parse_yaml_value(value) {
-                    if !slug.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Open Collective".to_string(),
-                            url: format!("https://opencollective.com/{slug}"),
-                        });
-                    }
-                }
-            }
             "ko_fi" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Ko-fi".to_string(),
-                            url: format!("https://ko-fi.com/{name}"),
-                        });
-                    }
-                }
-            }
-            "patreon" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Patreon".to_string(),
-                            url: format!("https://www.patreon.com/{name}"),
-                        });
-                    }
-                }
-            }
-            "liberapay" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Liberapay".to_string(),
-                            url: format!("https://liberapay.com/{name}"),
-                        });
-                    }
-                }
-            }
-            "community_bridge" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Community Bridge".to_string(),
-                            url: format!("https://funding.communitybridge.org/projects/{name}"),
-                        });
-                    }
-                }
-            }
-            "issuehunt" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "IssueHunt".to_string(),
-                            url: format!("https://issuehunt.io/r/{name}"),
-                        });
-                    }
-                }
-            }
-            "polar" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Polar".to_string(),
-                            url: format!("https://polar.sh/{name}"),
-                        });
-                    }
-                }
-            }
-            "buy_me_a_coffee" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Buy Me a Coffee".to_string(),
-                            url: format!("https://buymeacoffee.com/{name}"),
-                        });
-                    }
+                // Also accepts full ko-fi.com URLs, which people often paste here.
+                for handle in values.iter().filter_map(|v| kofi_handle(v)) {
+                    channels.push(FundingChannel {
+                        platform: "Ko-fi".to_string(),
+                        url: format!("https://ko-fi.com/{handle}"),
+                        page: None,
+                    });
                 }
             }
-            "thanks_dev" => {
-                for name in parse_yaml_value(value) {
-                    if !name.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "thanks.dev".to_string(),
-                            url: format!("https://thanks.dev/d/gh/{name}"),
-                        });
-                    }
+            "custom" => {
+                for url in values {
+                    channels.push(FundingChannel {
+                        platform: "Custom".to_string(),
+                        url,
+                        page: None,
+                    });
                 }
             }
-            "custom" => {
-                for url in parse_yaml_value(value) {
-                    if !url.is_empty() {
-                        channels.push(FundingChannel {
-                            platform: "Custom".to_string(),
-                            url: url.trim_matches('"').trim_matches('\'').to_string(),
-                        });
-                    }
+            _ => {
+                let Some((_, platform, prefix)) =
+                    FUNDING_PLATFORMS.iter().find(|(k, _, _)| *k == key)
+                else {
+                    continue;
+                };
+                for name in values {
+                    channels.push(FundingChannel {
+                        platform: platform.to_string(),
+                        url: format!("{prefix}{name}"),
+                        page: None,
+                    });
                 }
             }
-            _ => {}
         }
     }
 
     channels
 }
 
-/// Parse a YAML value that might be a scalar or an inline array `[a, b, c]`.
-fn parse_yaml_value(value: &str) -> Vec<String> {
-    let value = value.trim();
-
-    if value.starts_with('[') && value.ends_with(']') {
-        let inner = &value[1..value.len() - 1];
-        inner
-            .split(',')
-            .map(|s| s.trim().trim_matches('"').trim_matches('\'').to_string())
-            .filter(|s| !s.is_empty())
-            .collect()
-    } else {
-        vec![value.trim_matches('"').trim_matches('\'').to_string()]
-    }
+/// The non-empty strings of a YAML scalar or sequence.
+fn yaml_strings(value: &serde_yaml::Value) -> Vec<String> {
+    let scalar = |v: &serde_yaml::Value| match v {
+        serde_yaml::Value::String(s) => Some(s.trim().to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    let items = match value {
+        serde_yaml::Value::Sequence(items) => items.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    items
+        .iter()
+        .filter_map(scalar)
+        .filter(|s| !s.is_empty())
+        .collect()
 }
 
 #[cfg(test)]
@@ -482,6 +540,13 @@ ko_fi: realuser
         assert_eq!(channels[0].url, "https://ko-fi.com/realuser");
     }
 
+    #[test]
+    fn parse_funding_yml_ko_fi_url() {
+        let channels = parse_funding_yml("ko_fi: https://ko-fi.com/RealUser\n");
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].url, "https://ko-fi.com/realuser");
+    }
+
     #[test]
     fn parse_funding_yml_value_with_trailing_comment() {
         let content = "github: octocat # my sponsor page\n";
@@ -500,18 +565,47 @@ ko_fi: realuser
     }
 
     #[test]
-    fn parse_yaml_value_scalar() {
-        assert_eq!(parse_yaml_value("hello"), vec!["hello"]);
+    fn parse_funding_yml_block_sequence() {
+        let content = "\
+github:
+  - octocat   # maintainer
+  - surftocat
+custom:
+  - 'https://example.com/donate?via=github'
+";
+        let channels = parse_funding_yml(content);
+        let urls: Vec<_> = channels.iter().map(|c| c.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://github.com/sponsors/octocat",
+                "https://github.com/sponsors/surftocat",
+                "https://example.com/donate?via=github",
+            ]
+        );
     }
 
     #[test]
-    fn parse_yaml_value_array() {
-        assert_eq!(parse_yaml_value("[a, b, c]"), vec!["a", "b", "c"]);
+    fn parse_funding_yml_quoted_url_with_colon() {
+        let content = "custom: \"https://example.com:8443/donate#monthly\" # with port\n";
+        let channels = parse_funding_yml(content);
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].url, "https://example.com:8443/donate#monthly");
+    }
+
+    #[test]
+    fn parse_funding_yml_multiline_value() {
+        let content = "custom: >-\n  https://example.com/donate\nliberapay: 12345\n";
+        let channels = parse_funding_yml(content);
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].url, "https://example.com/donate");
+        assert_eq!(channels[1].url, "https://liberapay.com/12345");
     }
 
     #[test]
-    fn parse_yaml_value_quoted() {
-        assert_eq!(parse_yaml_value("\"hello\""), vec!["hello"]);
+    fn parse_funding_yml_malformed() {
+        assert!(parse_funding_yml("github: [octocat\n").is_empty());
+        assert!(parse_funding_yml("just some text\n").is_empty());
     }
 
     #[test]
@@ -536,4 +630,94 @@ ko_fi: realuser
         assert_eq!(channels.len(), 1);
         assert_eq!(channels[0].platform, "GitHub Sponsors");
     }
+
+    #[test]
+    fn sponsors_logins() {
+        assert_eq!(
+            sponsors_login("https://github.com/sponsors/octocat"),
+            Some("octocat")
+        );
+        assert_eq!(
+            sponsors_login("https://github.com/sponsors/octocat/dashboard"),
+            Some("octocat")
+        );
+        assert_eq!(sponsors_login("https://github.com/octocat"), None);
+        assert_eq!(sponsors_login("https://github.com/sponsors/"), None);
+    }
+
+    #[test]
+    fn parse_sponsor_tiers() {
+        let json = r#"{"repositoryOwner": {"sponsorsListing": {"tiers": {"nodes": [
+            {"name": "$25 a month", "monthlyPriceInDollars": 25, "isOneTime": false, "isCustomAmount": false},
+            {"name": "$5 a month", "monthlyPriceInDollars": 5, "isOneTime": false, "isCustomAmount": false},
+            {"name": "$10 one time", "monthlyPriceInDollars": 10, "isOneTime": true, "isCustomAmount": false},
+            {"name": "Custom", "monthlyPriceInDollars": 1, "isOneTime": false, "isCustomAmount": true}
+        ]}}}}"#;
+        let data: GhSponsorsData = serde_json::from_str(json).unwrap();
+        let tiers = sponsor_tiers(data);
+        let summary: Vec<(u64, bool)> = tiers.iter().map(|t| (t.amount, t.one_time)).collect();
+        assert_eq!(summary, vec![(5, false), (25, false), (10, true)]);
+        assert_eq!(tiers[0].name, "$5 a month");
+
+        // Accounts without a sponsors listing
+        let data: GhSponsorsData = serde_json::from_str(r#"{"repositoryOwner": {}}"#).unwrap();
+        assert!(sponsor_tiers(data).is_empty());
+    }
+
+    #[test]
+    fn parse_repo_response() {
+        let json = r#"{
+            "full_name": "torvalds/linux",
+            "html_url": "https://github.com/torvalds/linux",
+            "homepage": "",
+            "stargazers_count": 180000,
+            "has_issues": false,
+            "archived": false,
+            "description": "Linux kernel source tree",
+            "license": {"key": "other", "spdx_id": "NOASSERTION"}
+        }"#;
+        let repo: GhRepo = serde_json::from_str(json).unwrap();
+        assert_eq!(repo.stargazers_count, Some(180000));
+        assert_eq!(repo.homepage.as_deref(), Some(""));
+        assert!(!repo.has_issues);
+        assert!(!repo.archived);
+        assert_eq!(
+            repo.html_url.as_deref(),
+            Some("https://github.com/torvalds/linux")
+        );
+        assert_eq!(
+            repo.license.unwrap().spdx_id.as_deref(),
+            Some("NOASSERTION")
+        );
+    }
+
+    #[test]
+    fn activity_from_releases() {
+        let json = r#"[
+            {"tag_name": "v2.1.0-rc1", "published_at": "2024-07-01T00:00:00Z", "draft": false, "prerelease": true},
+            {"tag_name": "v2.0.0", "published_at": "2024-05-01T00:00:00Z", "draft": false, "prerelease": false},
+            {"tag_name": "next", "published_at": null, "draft": true, "prerelease": false},
+            {"tag_name": "v1.1.0", "published_at": "2024-03-02T00:00:00Z", "draft": false, "prerelease": false},
+            {"tag_name": "v1.0.0", "published_at": "2024-01-02T00:00:00Z", "draft": false, "prerelease": false}
+        ]"#;
+        let releases: Vec<GhRelease> = serde_json::from_str(json).unwrap();
+        let (latest, activity) =
+            release_activity(&releases, Some("2024-08-15T10:00:00Z".to_string()));
+
+        let latest = latest.unwrap();
+        assert_eq!(latest.version, "2.0.0");
+        assert_eq!(latest.published_at.as_deref(), Some("2024-05-01T00:00:00Z"));
+        let activity = activity.unwrap();
+        assert_eq!(
+            activity.last_commit_at.as_deref(),
+            Some("2024-08-15T10:00:00Z")
+        );
+        assert_eq!(
+            activity.last_release_at.as_deref(),
+            Some("2024-05-01T00:00:00Z")
+        );
+        assert_eq!(activity.release_cadence_days, Some(60.0));
+
+        assert_eq!(release_activity(&[], None), (None, None));
+    }
 }
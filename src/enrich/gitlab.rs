@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! GitLab enrichment backend.
+//!
+//! Queries the public REST API of gitlab.com and of the GitLab instances
+//! run by large communities (GNOME, KDE, freedesktop.org, Debian, ...).
+//! Fills in the star count, issue tracker, and time of the last activity,
+//! and records whether the project is archived or described as deprecated.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::net::{self, RequestExt};
+use super::{EnrichmentBackend, detect_deprecation};
+use crate::project::{Activity, UpstreamProject};
+
+/// Hosts known to run GitLab besides those named `gitlab.*`.
+const KNOWN_HOSTS: &[&str] = &[
+    "invent.kde.org",
+    "salsa.debian.org",
+    "framagit.org",
+    "code.videolan.org",
+];
+
+pub struct GitLabBackend;
+
+/// The subset of `GET /api/v4/projects/:id` used for enrichment.
+#[derive(Debug, Deserialize)]
+struct GlProject {
+    web_url: Option<String>,
+    description: Option<String>,
+    star_count: Option<u64>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    issues_enabled: bool,
+    last_activity_at: Option<String>,
+}
+
+impl EnrichmentBackend for GitLabBackend {
+    fn name(&self) -> &str {
+        "gitlab"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let Some((host, path)) = project.repo_url.as_deref().and_then(gitlab_project) else {
+            return Ok(project.clone());
+        };
+
+        let client = net::client();
+        let response = client
+            .get(format!(
+                "https://{host}/api/v4/projects/{}",
+                path.replace('/', "%2F")
+            ))
+            .send_paced()
+            .context("Failed to query GitLab API")?;
+        if !response.status().is_success() {
+            return Ok(project.clone());
+        }
+
+        let gl: GlProject = response
+            .json()
+            .context("Failed to parse GitLab API response")?;
+        Ok(apply_project(project, gl, &host))
+    }
+}
+
+/// Split a GitLab project URL into host and project path
+/// (`group/subgroup/project`), dropping `.git` and UI paths after `/-/`.
+pub(crate) fn gitlab_project(url: &str) -> Option<(String, String)> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_lowercase();
+    if !host.starts_with("gitlab.") && !KNOWN_HOSTS.contains(&host.as_str()) {
+        return None;
+    }
+
+    let path = path.split(['?', '#']).next()?;
+    let path = path.split("/-/").next()?.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if !path.contains('/') || path.split('/').any(str::is_empty) {
+        return None;
+    }
+    Some((host, path.to_string()))
+}
+
+fn apply_project(project: &UpstreamProject, gl: GlProject, host: &str) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    if enriched.stars.is_none() {
+        enriched.stars = gl.star_count;
+    }
+    if enriched.bug_tracker.is_none()
+        && gl.issues_enabled
+        && let Some(web_url) = &gl.web_url
+    {
+        enriched.bug_tracker = Some(format!("{web_url}/-/issues"));
+    }
+    if enriched.activity.is_none() && gl.last_activity_at.is_some() {
+        enriched.activity = Some(Activity {
+            last_commit_at: gl.last_activity_at,
+            last_release_at: None,
+            release_cadence_days: None,
+        });
+    }
+    if enriched.deprecation.is_none() {
+        enriched.deprecation = detect_deprecation(gl.archived, gl.description.as_deref(), host);
+    }
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_paths_from_urls() {
+        assert_eq!(
+            gitlab_project("https://gitlab.gnome.org/GNOME/gimp.git"),
+            Some(("gitlab.gnome.org".to_string(), "GNOME/gimp".to_string()))
+        );
+        assert_eq!(
+            gitlab_project("https://invent.kde.org/graphics/krita/-/tree/master?ref_type=heads"),
+            Some(("invent.kde.org".to_string(), "graphics/krita".to_string()))
+        );
+        assert_eq!(
+            gitlab_project("https://gitlab.com/group/sub/project/"),
+            Some(("gitlab.com".to_string(), "group/sub/project".to_string()))
+        );
+        assert_eq!(gitlab_project("https://gitlab.com/group"), None);
+        assert_eq!(gitlab_project("https://github.com/owner/repo"), None);
+    }
+
+    #[test]
+    fn applies_archived_project() {
+        let json = r#"{
+            "id": 123,
+            "web_url": "https://gitlab.com/old/tool",
+            "description": "Archived. Development moved to new/tool.",
+            "star_count": 42,
+            "archived": true,
+            "issues_enabled": true,
+            "last_activity_at": "2021-03-04T05:06:07.000Z"
+        }"#;
+        let gl: GlProject = serde_json::from_str(json).unwrap();
+        let base = UpstreamProject {
+            name: "tool".to_string(),
+            repo_url: Some("https://gitlab.com/old/tool".to_string()),
+            ..Default::default()
+        };
+        let enriched = apply_project(&base, gl, "gitlab.com");
+
+        assert_eq!(enriched.stars, Some(42));
+        assert_eq!(
+            enriched.bug_tracker.as_deref(),
+            Some("https://gitlab.com/old/tool/-/issues")
+        );
+        assert_eq!(
+            enriched.activity.unwrap().last_commit_at.as_deref(),
+            Some("2021-03-04T05:06:07.000Z")
+        );
+        let deprecation = enriched.deprecation.unwrap();
+        assert!(deprecation.archived);
+        assert_eq!(
+            deprecation.replacement.as_deref(),
+            Some("https://gitlab.com/new/tool")
+        );
+    }
+}
@@ -0,0 +1,361 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Homepage scraping enrichment backend.
+//!
+//! Many projects not hosted on GitHub only advertise funding on their
+//! website. This backend fetches the project homepage and collects:
+//!
+//! - `<link rel="funding">` and `<a rel="funding">` targets
+//! - `rel="me"` links to known funding platforms
+//! - anchors labelled "Donate", "Sponsor", "Support us", ...
+//! - the AppStream donation URL of a desktop application, when the page
+//!   links to the application on Flathub
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::project::{FundingChannel, UpstreamProject};
+
+/// Anchor texts (lowercase) that mark a link as a donation page.
+const DONATE_LABELS: &[&str] = &[
+    "donate",
+    "donation",
+    "sponsor",
+    "support us",
+    "support the project",
+    "support development",
+];
+
+const FLATHUB_APPSTREAM_URL: &str = "https://flathub.org/api/v2/appstream";
+
+pub struct HomepageBackend;
+
+/// The subset of Flathub's AppStream data used for enrichment.
+#[derive(Debug, Deserialize)]
+struct FlathubApp {
+    urls: Option<FlathubUrls>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FlathubUrls {
+    donation: Option<String>,
+}
+
+impl EnrichmentBackend for HomepageBackend {
+    fn name(&self) -> &str {
+        "homepage"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let homepage = match &project.homepage {
+            Some(url) if url.starts_with("https://") || url.starts_with("http://") => url,
+            _ => return Ok(project.clone()),
+        };
+
+        let client = net::client();
+
+        let response = client
+            .get(homepage)
+            .send_paced()
+            .context("Failed to fetch homepage")?;
+        if !response.status().is_success() {
+            return Ok(project.clone());
+        }
+        let html = response.text().context("Failed to read homepage")?;
+
+        let mut urls = extract_funding_urls(&html, homepage);
+        if let Some(app_id) = find_flathub_app_id(&html)
+            && let Some(url) = fetch_flathub_donation_url(client, &app_id)
+        {
+            urls.push(url);
+        }
+
+        let mut enriched = project.clone();
+        for url in urls {
+            if !enriched.funding.iter().any(|f| f.url == url) {
+                enriched.funding.push(FundingChannel::from_url(&url));
+            }
+        }
+        Ok(enriched)
+    }
+}
+
+/// Look up the donation URL an application declares in its AppStream
+/// metadata, as published by Flathub.
+fn fetch_flathub_donation_url(client: &Client, app_id: &str) -> Option<String> {
+    let response = client
+        .get(format!("{FLATHUB_APPSTREAM_URL}/{app_id}"))
+        .send_paced()
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let app: FlathubApp = response.json().ok()?;
+    app.urls?.donation.filter(|url| !url.is_empty())
+}
+
+/// Collect funding links from homepage HTML, resolved against `base`.
+fn extract_funding_urls(html: &str, base: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    let mut push = |href: &str| {
+        if let Some(url) = resolve_url(base, href)
+            && !urls.contains(&url)
+        {
+            urls.push(url);
+        }
+    };
+
+    for (tag, text) in tags(html) {
+        let Some(href) = attribute(tag, "href") else {
+            continue;
+        };
+        let rel = attribute(tag, "rel").unwrap_or_default().to_lowercase();
+        let rels: Vec<&str> = rel.split_whitespace().collect();
+
+        if rels.contains(&"funding") {
+            push(&href);
+        } else if rels.contains(&"me") {
+            if FundingChannel::from_url(&href).platform != "Custom" {
+                push(&href);
+            }
+        } else if let Some(text) = text {
+            let text = text.to_lowercase();
+            if DONATE_LABELS.iter().any(|label| text.contains(label)) {
+                push(&href);
+            }
+        }
+    }
+
+    urls
+}
+
+/// Iterate over the `<link>` and `<a>` tags of an HTML document, yielding
+/// each opening tag and, for anchors, their text content without markup.
+fn tags(html: &str) -> impl Iterator<Item = (&str, Option<String>)> {
+    let lower = html.to_ascii_lowercase();
+    let mut positions: Vec<usize> = lower
+        .match_indices("<link")
+        .chain(lower.match_indices("<a"))
+        .map(|(i, _)| i)
+        .filter(|&i| {
+            // Skip longer tag names such as <abbr> or <article>.
+            let name_len = if lower[i..].starts_with("<link") {
+                5
+            } else {
+                2
+            };
+            lower
+                .as_bytes()
+                .get(i + name_len)
+                .is_some_and(|&b| b.is_ascii_whitespace() || b == b'>' || b == b'/')
+        })
+        .collect();
+    positions.sort_unstable();
+
+    positions.into_iter().filter_map(move |start| {
+        let end = start + html[start..].find('>')?;
+        let tag = &html[start..=end];
+        let text = if lower[start..].starts_with("<a") {
+            let close = lower[end..].find("</a").map(|i| end + i)?;
+            Some(strip_tags(&html[end + 1..close]))
+        } else {
+            None
+        };
+        Some((tag, text))
+    })
+}
+
+/// Text content of an HTML fragment, with tags removed and whitespace
+/// collapsed.
+fn strip_tags(fragment: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in fragment.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The value of an attribute in an opening tag, quoted or not.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name).map(|i| from + i) {
+        from = pos + name.len();
+        // Must be a whole attribute name followed by `=`.
+        let preceded = lower[..pos].ends_with(|c: char| c.is_whitespace());
+        let rest = lower[from..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let value = match value.chars().next()? {
+            quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+            _ => value
+                .split(|c: char| c.is_whitespace() || c == '>')
+                .next()?,
+        };
+        return Some(value.replace("&amp;", "&"));
+    }
+    None
+}
+
+/// Resolve a link target against the page URL. Returns `None` for links
+/// that do not lead to a web page (fragments, `mailto:`, scripts).
+fn resolve_url(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    if href.starts_with("https://") || href.starts_with("http://") {
+        return Some(href.to_string());
+    }
+    if href.contains(':') && !href.starts_with('/') {
+        // mailto:, javascript:, ...
+        return None;
+    }
+
+    let (scheme, rest) = base.split_once("://")?;
+    if let Some(path) = href.strip_prefix("//") {
+        return Some(format!("{scheme}://{path}"));
+    }
+    let origin_end = rest.find('/').unwrap_or(rest.len());
+    let origin = &rest[..origin_end];
+    if href.starts_with('/') {
+        return Some(format!("{scheme}://{origin}{href}"));
+    }
+    let path = &rest[origin_end..];
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+    let dir = if dir.is_empty() { "/" } else { dir };
+    Some(format!("{scheme}://{origin}{dir}{href}"))
+}
+
+/// Find the Flathub application ID the page links to, if any
+/// (`https://flathub.org/apps/<id>` or the older `/apps/details/<id>`).
+fn find_flathub_app_id(html: &str) -> Option<String> {
+    html.match_indices("flathub.org/apps/")
+        .find_map(|(start, needle)| {
+            let rest = &html[start + needle.len()..];
+            let rest = rest.strip_prefix("details/").unwrap_or(rest);
+            let id: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+                .collect();
+            // Application IDs are reverse-DNS names.
+            (id.matches('.').count() >= 2).then_some(id)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_funding_links() {
+        let html = r#"<!DOCTYPE html>
+<html><head>
+  <link rel="stylesheet" href="/style.css">
+  <link rel="funding" href="https://example.org/fund.json">
+  <link rel="me" href="https://liberapay.com/example">
+  <link rel="me" href="https://mastodon.social/@example">
+</head><body>
+  <nav>
+    <a href="/docs/">Docs</a>
+    <a class="btn" href="donate.html"><span class="icon"></span> Donate</a>
+    <A HREF='https://opencollective.com/example'>Become a <b>Sponsor</b></A>
+    <a href="mailto:donations@example.org">Donate by email</a>
+  </nav>
+  <abbr title="Donate">D</abbr>
+</body></html>"#;
+        let urls = extract_funding_urls(html, "https://example.org/en/index.html");
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.org/fund.json",
+                "https://liberapay.com/example",
+                "https://example.org/en/donate.html",
+                "https://opencollective.com/example",
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_attributes() {
+        let tag = r#"<a data-href="x" href = 'https://a.example/?x=1&amp;y=2' rel=me>"#;
+        assert_eq!(
+            attribute(tag, "href").as_deref(),
+            Some("https://a.example/?x=1&y=2")
+        );
+        assert_eq!(attribute(tag, "rel").as_deref(), Some("me"));
+        assert_eq!(attribute(tag, "class"), None);
+    }
+
+    #[test]
+    fn resolves_relative_links() {
+        let base = "https://example.org/docs/guide.html?lang=en";
+        assert_eq!(
+            resolve_url(base, "/donate").as_deref(),
+            Some("https://example.org/donate")
+        );
+        assert_eq!(
+            resolve_url(base, "donate.html").as_deref(),
+            Some("https://example.org/docs/donate.html")
+        );
+        assert_eq!(
+            resolve_url(base, "//cdn.example.org/x").as_deref(),
+            Some("https://cdn.example.org/x")
+        );
+        assert_eq!(
+            resolve_url("https://example.org", "donate").as_deref(),
+            Some("https://example.org/donate")
+        );
+        assert_eq!(resolve_url(base, "#support"), None);
+        assert_eq!(resolve_url(base, "javascript:void(0)"), None);
+    }
+
+    #[test]
+    fn finds_flathub_app_ids() {
+        let html = r#"<a href="https://flathub.org/apps/org.gnome.Podcasts"><img alt="Get it on Flathub"></a>"#;
+        assert_eq!(
+            find_flathub_app_id(html).as_deref(),
+            Some("org.gnome.Podcasts")
+        );
+        let old = "https://flathub.org/apps/details/org.kde.krita";
+        assert_eq!(find_flathub_app_id(old).as_deref(), Some("org.kde.krita"));
+        assert_eq!(find_flathub_app_id("https://flathub.org/apps/search"), None);
+    }
+
+    #[test]
+    fn parses_flathub_appstream() {
+        let json = r#"{
+            "id": "org.gnome.Podcasts",
+            "name": "Podcasts",
+            "urls": {
+                "homepage": "https://apps.gnome.org/Podcasts/",
+                "donation": "https://www.gnome.org/donate/"
+            }
+        }"#;
+        let app: FlathubApp = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            app.urls.unwrap().donation.as_deref(),
+            Some("https://www.gnome.org/donate/")
+        );
+    }
+}
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Ko-fi enrichment backend.
+//!
+//! Validates Ko-fi handles declared in `FUNDING.yml` or linked from the
+//! project homepage by fetching the creator page, and records the page
+//! title and current goal so the budget planner can show what the money goes
+//! toward. Handles whose page does not exist are not added.
+//!
+//! Ko-fi has no public API; the page is scraped on a best-effort basis and
+//! any detail that cannot be found is left empty.
+
+use anyhow::Result;
+use reqwest::blocking::Client;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::project::{FundingChannel, FundingPage, UpstreamProject};
+
+/// First path segments of ko-fi.com URLs that are not creator pages.
+const RESERVED_PATHS: &[&str] = &[
+    "about", "account", "album", "blog", "explore", "home", "i", "login", "manage", "post",
+    "register", "s", "shop", "summary",
+];
+
+pub struct KofiBackend;
+
+impl EnrichmentBackend for KofiBackend {
+    fn name(&self) -> &str {
+        "kofi"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let client = net::client();
+
+        let mut handles: Vec<String> = project
+            .funding
+            .iter()
+            .filter(|f| f.platform == "Ko-fi" && f.page.is_none())
+            .filter_map(|f| kofi_handle(&f.url))
+            .collect();
+        if !project.funding.iter().any(|f| f.platform == "Ko-fi")
+            && let Some(handle) = project
+                .homepage
+                .as_deref()
+                .and_then(|url| fetch_page(client, url))
+                .and_then(|html| find_kofi_handle(&html))
+        {
+            handles.push(handle);
+        }
+
+        let mut enriched = project.clone();
+        for handle in handles {
+            let Some(html) = fetch_page(client, &format!("https://ko-fi.com/{handle}")) else {
+                continue;
+            };
+            let page = parse_creator_page(&html);
+            let url = format!("https://ko-fi.com/{handle}");
+            match enriched
+                .funding
+                .iter_mut()
+                .find(|f| kofi_handle(&f.url).as_deref() == Some(handle.as_str()))
+            {
+                Some(existing) => existing.page = Some(page),
+                None => enriched.funding.push(FundingChannel {
+                    platform: "Ko-fi".to_string(),
+                    url,
+                    page: Some(page),
+                }),
+            }
+        }
+
+        Ok(enriched)
+    }
+}
+
+/// GET a page as text. Ko-fi redirects unknown handles to its front page,
+/// so a response that ends up at a different path counts as not found.
+fn fetch_page(client: &Client, url: &str) -> Option<String> {
+    let response = client.get(url).send_paced().ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    if let Some(handle) = kofi_handle(url)
+        && kofi_handle(response.url().as_str()).as_deref() != Some(handle.as_str())
+    {
+        return None;
+    }
+    response.text().ok()
+}
+
+/// Extract the creator handle from a Ko-fi URL or a bare handle, as found in
+/// the `ko_fi` key of `FUNDING.yml`. Handles are case-insensitive and
+/// returned in lowercase.
+pub(crate) fn kofi_handle(value: &str) -> Option<String> {
+    let value = value.trim();
+    let handle = match value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+    {
+        Some(rest) => {
+            let (host, path) = rest.split_once('/')?;
+            if !matches!(host.to_lowercase().as_str(), "ko-fi.com" | "www.ko-fi.com") {
+                return None;
+            }
+            path.split(['/', '?', '#']).next()?
+        }
+        None => value
+            .strip_prefix("ko-fi.com/")
+            .unwrap_or(value)
+            .trim_matches('/'),
+    };
+
+    let handle = handle.to_lowercase();
+    if handle.is_empty()
+        || RESERVED_PATHS.contains(&handle.as_str())
+        || !handle
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+    {
+        return None;
+    }
+    Some(handle)
+}
+
+/// Find the first Ko-fi creator page linked from HTML text.
+fn find_kofi_handle(html: &str) -> Option<String> {
+    html.match_indices("http").find_map(|(start, _)| {
+        let rest = &html[start..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>'))
+            .unwrap_or(rest.len());
+        let url = &rest[..end];
+        if url.starts_with("https://") || url.starts_with("http://") {
+            kofi_handle(url)
+        } else {
+            None
+        }
+    })
+}
+
+/// Scrape the page title and current goal from a Ko-fi creator page.
+fn parse_creator_page(html: &str) -> FundingPage {
+    let title = meta_content(html, "og:title")
+        .or_else(|| element_text(html, "<title"))
+        .map(|t| {
+            // "Support Jane on Ko-fi! ❤️. ko-fi.com/jane" → "Support Jane on Ko-fi!"
+            t.split(" ❤️").next().unwrap_or(&t).trim().to_string()
+        })
+        .filter(|t| !t.is_empty());
+    let goal = element_text(html, "class=\"goal-title")
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty());
+    FundingPage {
+        title,
+        goal,
+        tiers: vec![],
+    }
+}
+
+/// The `content` of the `<meta property="...">` tag with the given property.
+fn meta_content(html: &str, property: &str) -> Option<String> {
+    let needle = format!("property=\"{property}\"");
+    html.match_indices("<meta").find_map(|(start, _)| {
+        let tag = &html[start..start + html[start..].find('>')?];
+        if !tag.contains(&needle) {
+            return None;
+        }
+        let content = tag.split_once("content=\"")?.1;
+        Some(decode_entities(content.split('"').next()?))
+    })
+}
+
+/// The text of the first element whose opening tag contains `marker`, up to
+/// the next tag.
+fn element_text(html: &str, marker: &str) -> Option<String> {
+    let start = html.find(marker)?;
+    let rest = &html[start..];
+    let rest = &rest[rest.find('>')? + 1..];
+    Some(decode_entities(&rest[..rest.find('<')?]))
+}
+
+/// Decode the HTML entities commonly found in page titles.
+fn decode_entities(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_from_urls_and_funding_yml() {
+        assert_eq!(kofi_handle("JaneDoe").as_deref(), Some("janedoe"));
+        assert_eq!(kofi_handle("ko-fi.com/janedoe").as_deref(), Some("janedoe"));
+        assert_eq!(
+            kofi_handle("https://ko-fi.com/janedoe").as_deref(),
+            Some("janedoe")
+        );
+        assert_eq!(
+            kofi_handle("https://www.ko-fi.com/jane_doe/shop?ref=x").as_deref(),
+            Some("jane_doe")
+        );
+        // A URL pasted into FUNDING.yml's `ko_fi` key.
+        assert_eq!(
+            kofi_handle("https://ko-fi.com/https://ko-fi.com/janedoe"),
+            None
+        );
+        assert_eq!(kofi_handle("https://ko-fi.com/s/8a4f1c"), None);
+        assert_eq!(kofi_handle("https://ko-fi.com/"), None);
+        assert_eq!(kofi_handle("https://example.org/janedoe"), None);
+        assert_eq!(kofi_handle("jane doe"), None);
+    }
+
+    #[test]
+    fn finds_handle_in_homepage() {
+        let html = r#"<footer><a href="https://ko-fi.com/post/Thanks-A1B2">News</a>
+            <a href="https://ko-fi.com/JaneDoe"><img src="kofi.png"></a></footer>"#;
+        assert_eq!(find_kofi_handle(html).as_deref(), Some("janedoe"));
+        assert_eq!(find_kofi_handle("<a href=\"/donate\">Donate</a>"), None);
+    }
+
+    #[test]
+    fn parses_creator_page() {
+        let html = r#"<html><head>
+            <title>Support Jane Doe on Ko-fi! ❤️. ko-fi.com/janedoe - Ko-fi ❤️ Where creators get support from fans</title>
+            <meta property="og:title" content="Support Jane Doe on Ko-fi! ❤️. ko-fi.com/janedoe" />
+            </head><body>
+            <div class="goal-title text-left">New test hardware &amp; CI runners</div>
+            </body></html>"#;
+        let page = parse_creator_page(html);
+        assert_eq!(page.title.as_deref(), Some("Support Jane Doe on Ko-fi!"));
+        assert_eq!(page.goal.as_deref(), Some("New test hardware & CI runners"));
+
+        let page = parse_creator_page("<html><title>Ko-fi</title></html>");
+        assert_eq!(page.title.as_deref(), Some("Ko-fi"));
+        assert_eq!(page.goal, None);
+    }
+}
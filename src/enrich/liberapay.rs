@@ -3,15 +3,37 @@
 //! Liberapay enrichment backend.
 //!
 //! Checks if a project has a Liberapay account by querying their public API.
-//! Adds a funding channel if found.
+//! Adds a funding channel if found, along with the account's current income
+//! and funding goal where the account makes them public.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Deserialize;
 
 use super::EnrichmentBackend;
-use crate::project::{FundingChannel, UpstreamProject};
+use super::net::{self, RequestExt};
+use crate::project::{FundingChannel, FundingGoal, FundingStatus, GoalKind, UpstreamProject};
+
+/// Liberapay amounts are weekly.
+const WEEKS_PER_YEAR: f64 = 52.0;
 
 pub struct LiberapayBackend;
 
+/// An account from `/<username>/public.json`. `receiving` and `goal` are
+/// null when the account hides them.
+#[derive(Debug, Deserialize)]
+struct LpAccount {
+    username: String,
+    receiving: Option<LpAmount>,
+    goal: Option<LpAmount>,
+}
+
+/// A weekly amount, as a decimal string.
+#[derive(Debug, Deserialize)]
+struct LpAmount {
+    amount: String,
+    currency: String,
+}
+
 impl EnrichmentBackend for LiberapayBackend {
     fn name(&self) -> &str {
         "liberapay"
@@ -22,31 +44,161 @@ impl EnrichmentBackend for LiberapayBackend {
     }
 
     fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
-        let name = &project.name;
-
-        // Skip if we already have a Liberapay funding channel
-        if project.funding.iter().any(|f| f.platform == "Liberapay") {
+        if project
+            .funding_status
+            .iter()
+            .any(|s| s.platform == "Liberapay")
+        {
             return Ok(project.clone());
         }
 
+        // Prefer the account the project links to (e.g. from FUNDING.yml),
+        // otherwise try the project name
+        let name = project
+            .funding
+            .iter()
+            .filter(|f| f.platform == "Liberapay")
+            .find_map(|f| liberapay_username(&f.url))
+            .unwrap_or_else(|| project.name.clone());
+
         let url = format!("https://liberapay.com/{name}/public.json");
 
-        let client = reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()?;
+        let client = net::client();
 
-        let response = client.get(&url).send();
+        let response = client.get(&url).send_paced();
 
         match response {
             Ok(resp) if resp.status().is_success() => {
-                let mut enriched = project.clone();
-                enriched.funding.push(FundingChannel {
-                    platform: "Liberapay".to_string(),
-                    url: format!("https://liberapay.com/{name}"),
-                });
-                Ok(enriched)
+                let account: LpAccount =
+                    resp.json().context("Failed to parse Liberapay response")?;
+                Ok(apply_account(project, account))
             }
             _ => Ok(project.clone()),
         }
     }
 }
+
+/// Extract the username from a Liberapay URL.
+fn liberapay_username(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let path = rest.strip_prefix("liberapay.com/")?;
+    path.split(['/', '?', '#'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// Add the account's funding channel and yearly figures to a project.
+fn apply_account(project: &UpstreamProject, account: LpAccount) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    if !enriched.funding.iter().any(|f| {
+        liberapay_username(&f.url).is_some_and(|n| n.eq_ignore_ascii_case(&account.username))
+    }) {
+        enriched.funding.push(FundingChannel {
+            platform: "Liberapay".to_string(),
+            url: format!("https://liberapay.com/{}", account.username),
+            page: None,
+        });
+    }
+
+    let yearly = |amount: &LpAmount| {
+        amount
+            .amount
+            .parse::<f64>()
+            .ok()
+            .map(|weekly| weekly * WEEKS_PER_YEAR)
+    };
+    let Some(currency) = account
+        .receiving
+        .as_ref()
+        .or(account.goal.as_ref())
+        .map(|a| a.currency.clone())
+    else {
+        return enriched;
+    };
+
+    enriched.funding_status.push(FundingStatus {
+        platform: "Liberapay".to_string(),
+        currency,
+        balance: None,
+        yearly_income: account.receiving.as_ref().and_then(yearly),
+        goals: account
+            .goal
+            .as_ref()
+            .and_then(yearly)
+            .map(|amount| FundingGoal {
+                title: None,
+                amount,
+                kind: GoalKind::YearlyIncome,
+            })
+            .into_iter()
+            .collect(),
+    });
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> UpstreamProject {
+        UpstreamProject {
+            name: "mpv".to_string(),
+            repo_url: Some("https://github.com/mpv-player/mpv".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn applies_income_and_goal() {
+        let json = r#"{
+            "id": 1234,
+            "kind": "group",
+            "username": "mpv",
+            "receiving": {"amount": "25.50", "currency": "EUR"},
+            "goal": {"amount": "100.00", "currency": "EUR"},
+            "giving": null
+        }"#;
+        let account: LpAccount = serde_json::from_str(json).unwrap();
+        let enriched = apply_account(&project(), account);
+
+        assert_eq!(enriched.funding.len(), 1);
+        assert_eq!(enriched.funding[0].url, "https://liberapay.com/mpv");
+
+        let status = &enriched.funding_status[0];
+        assert_eq!(status.platform, "Liberapay");
+        assert_eq!(status.currency, "EUR");
+        assert_eq!(status.yearly_income, Some(1326.0));
+        assert_eq!(status.goals[0].amount, 5200.0);
+        assert_eq!(status.funding_gap(), Some(3874.0));
+    }
+
+    #[test]
+    fn hidden_figures_add_only_the_channel() {
+        let mut linked = project();
+        linked
+            .funding
+            .push(FundingChannel::from_url("https://liberapay.com/MPV/donate"));
+        let json = r#"{"username": "mpv", "receiving": null, "goal": null}"#;
+        let account: LpAccount = serde_json::from_str(json).unwrap();
+        let enriched = apply_account(&linked, account);
+
+        assert_eq!(enriched.funding.len(), 1);
+        assert!(enriched.funding_status.is_empty());
+    }
+
+    #[test]
+    fn usernames_from_urls() {
+        assert_eq!(
+            liberapay_username("https://liberapay.com/mpv/donate").as_deref(),
+            Some("mpv")
+        );
+        assert_eq!(liberapay_username("https://liberapay.com/"), None);
+        assert_eq!(liberapay_username("https://ko-fi.com/mpv"), None);
+    }
+}
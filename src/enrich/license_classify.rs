@@ -7,7 +7,7 @@
 
 use anyhow::Result;
 
-use super::EnrichmentBackend;
+use super::{EnrichmentBackend, pool};
 use crate::project::UpstreamProject;
 
 pub struct LicenseClassifyBackend;
@@ -34,6 +34,14 @@ impl EnrichmentBackend for LicenseClassifyBackend {
 
         Ok(enriched)
     }
+
+    fn max_concurrency(&self) -> usize {
+        pool::WORKERS
+    }
+
+    fn is_offline(&self) -> bool {
+        true
+    }
 }
 
 /// Normalize an SPDX identifier for lookup: lowercase, strip `-or-later`/`-only`
@@ -218,16 +226,8 @@ mod tests {
         let backend = LicenseClassifyBackend;
         let project = UpstreamProject {
             name: "test".to_string(),
-            repo_url: None,
-            homepage: None,
             licenses: vec!["MIT".to_string()],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let enriched = backend.enrich(&project).unwrap();
@@ -239,16 +239,8 @@ mod tests {
         let backend = LicenseClassifyBackend;
         let project = UpstreamProject {
             name: "test".to_string(),
-            repo_url: None,
-            homepage: None,
             licenses: vec!["proprietary".to_string()],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let enriched = backend.enrich(&project).unwrap();
@@ -260,16 +252,7 @@ mod tests {
         let backend = LicenseClassifyBackend;
         let project = UpstreamProject {
             name: "test".to_string(),
-            repo_url: None,
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let enriched = backend.enrich(&project).unwrap();
@@ -281,16 +264,8 @@ mod tests {
         let backend = LicenseClassifyBackend;
         let project = UpstreamProject {
             name: "test".to_string(),
-            repo_url: None,
-            homepage: None,
             licenses: vec!["MIT".to_string(), "proprietary".to_string()],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         let enriched = backend.enrich(&project).unwrap();
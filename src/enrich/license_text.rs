@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! License text enrichment backend.
+//!
+//! For projects that no other source gave a license, fetches the license
+//! file from the default branch of the GitHub or GitLab repository and
+//! identifies it by the phrases that set each common license text apart.
+//! Rust-style dual licensing (`LICENSE-MIT` and `LICENSE-APACHE`) yields
+//! both identifiers. Unrecognized texts are left alone rather than guessed.
+//!
+//! Identifiers follow GitHub's license detection: a GPL text alone does not
+//! say whether later versions apply, so it is reported as e.g. `GPL-3.0`.
+
+use anyhow::Result;
+
+use super::net;
+use super::{EnrichmentBackend, raw_file_base};
+use crate::project::UpstreamProject;
+
+/// License file names tried, in order, for a single license.
+const LICENSE_FILES: &[&str] = &["LICENSE", "LICENSE.md", "LICENSE.txt", "LICENCE", "COPYING"];
+
+/// License files of dual-licensed projects, tried when none of
+/// [`LICENSE_FILES`] exists.
+const DUAL_LICENSE_FILES: &[&str] = &["LICENSE-MIT", "LICENSE-APACHE"];
+
+/// Distinguishing phrases of license texts, lowercased with punctuation
+/// collapsed into single spaces. A text matches an entry when it contains
+/// all of its phrases; the first match wins, so more specific entries come
+/// first.
+const SIGNATURES: &[(&str, &[&str])] = &[
+    ("AGPL-3.0", &["gnu affero general public license version 3"]),
+    ("LGPL-3.0", &["gnu lesser general public license version 3"]),
+    (
+        "LGPL-2.1",
+        &["gnu lesser general public license version 2 1"],
+    ),
+    (
+        "LGPL-2.0",
+        &["gnu library general public license version 2"],
+    ),
+    (
+        "GPL-3.0",
+        &["gnu general public license version 3 29 june 2007"],
+    ),
+    (
+        "GPL-2.0",
+        &["gnu general public license version 2 june 1991"],
+    ),
+    ("Apache-2.0", &["apache license version 2 0"]),
+    ("MPL-2.0", &["mozilla public license version 2 0"]),
+    ("EPL-2.0", &["eclipse public license v 2 0"]),
+    ("EPL-1.0", &["eclipse public license v 1 0"]),
+    ("EUPL-1.2", &["european union public licence v 1 2"]),
+    ("Artistic-2.0", &["the artistic license 2 0"]),
+    ("BSL-1.0", &["boost software license version 1 0"]),
+    (
+        "Unlicense",
+        &["this is free and unencumbered software released into the public domain"],
+    ),
+    ("CC0-1.0", &["cc0 1 0 universal"]),
+    ("WTFPL", &["do what the fuck you want to public license"]),
+    (
+        "Zlib",
+        &["altered source versions must be plainly marked as such"],
+    ),
+    (
+        "MIT",
+        &[
+            "permission is hereby granted free of charge to any person obtaining a copy",
+            "the above copyright notice and this permission notice shall be included",
+        ],
+    ),
+    (
+        "ISC",
+        &[
+            "permission to use copy modify and or distribute this software for any purpose",
+            "provided that the above copyright notice and this permission notice appear in all copies",
+        ],
+    ),
+    (
+        "0BSD",
+        &["permission to use copy modify and or distribute this software for any purpose"],
+    ),
+    (
+        "BSD-4-Clause",
+        &[
+            "redistribution and use in source and binary forms with or without modification are permitted",
+            "all advertising materials mentioning features or use of this software",
+        ],
+    ),
+    (
+        "BSD-3-Clause",
+        &[
+            "redistribution and use in source and binary forms with or without modification are permitted",
+            "to endorse or promote products derived from this software",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &[
+            "redistribution and use in source and binary forms with or without modification are permitted",
+        ],
+    ),
+];
+
+pub struct LicenseTextBackend;
+
+impl EnrichmentBackend for LicenseTextBackend {
+    fn name(&self) -> &str {
+        "license_text"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        if !project.licenses.is_empty() {
+            return Ok(project.clone());
+        }
+        let Some(base) = project.repo_url.as_deref().and_then(raw_file_base) else {
+            return Ok(project.clone());
+        };
+
+        let client = net::client();
+        let fetch = |name: &str| net::fetch_text(client, &format!("{base}/{name}"));
+
+        let licenses: Vec<&str> = match LICENSE_FILES.iter().find_map(|name| fetch(name)) {
+            Some(text) => classify_license_text(&text).into_iter().collect(),
+            None => DUAL_LICENSE_FILES
+                .iter()
+                .filter_map(|name| fetch(name))
+                .filter_map(|text| classify_license_text(&text))
+                .collect(),
+        };
+
+        let mut enriched = project.clone();
+        enriched.licenses = licenses.into_iter().map(str::to_string).collect();
+        Ok(enriched)
+    }
+}
+
+/// Identify a license text, returning its SPDX identifier.
+fn classify_license_text(text: &str) -> Option<&'static str> {
+    let text = normalize_text(text);
+    SIGNATURES
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|p| text.contains(p)))
+        .map(|(id, _)| *id)
+}
+
+/// Lowercase a text and collapse everything but letters and digits into
+/// single spaces, so that line wrapping, markdown, and punctuation do not
+/// matter.
+fn normalize_text(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_common_licenses() {
+        let mit = "MIT License\n\nCopyright (c) 2024 Jane Doe\n\n\
+            Permission is hereby granted, free of charge, to any person obtaining a copy\n\
+            of this software and associated documentation files (the \"Software\"), ...\n\n\
+            The above copyright notice and this permission notice shall be included in all\n\
+            copies or substantial portions of the Software.";
+        assert_eq!(classify_license_text(mit), Some("MIT"));
+
+        let apache = "                                 Apache License\n\
+            \x20                          Version 2.0, January 2004\n\
+            \x20                       http://www.apache.org/licenses/";
+        assert_eq!(classify_license_text(apache), Some("Apache-2.0"));
+
+        let gpl3 = "                    GNU GENERAL PUBLIC LICENSE\n\
+            \x20                      Version 3, 29 June 2007\n\n\
+            ... use the GNU Lesser General Public License instead of this License.";
+        assert_eq!(classify_license_text(gpl3), Some("GPL-3.0"));
+
+        let lgpl21 = "# GNU Lesser General Public License\n\n_Version 2.1, February 1999_";
+        assert_eq!(classify_license_text(lgpl21), Some("LGPL-2.1"));
+    }
+
+    #[test]
+    fn tells_bsd_variants_apart() {
+        let bsd2 = "Redistribution and use in source and binary forms, with or without\n\
+            modification, are permitted provided that the following conditions are met:";
+        assert_eq!(classify_license_text(bsd2), Some("BSD-2-Clause"));
+
+        let bsd3 = format!(
+            "{bsd2}\n3. Neither the name of the copyright holder nor the names of its\n\
+            contributors may be used to endorse or promote products derived from\n\
+            this software without specific prior written permission."
+        );
+        assert_eq!(classify_license_text(&bsd3), Some("BSD-3-Clause"));
+    }
+
+    #[test]
+    fn tells_isc_from_0bsd() {
+        let zero_bsd = "Permission to use, copy, modify, and/or distribute this software for\n\
+            any purpose with or without fee is hereby granted.";
+        assert_eq!(classify_license_text(zero_bsd), Some("0BSD"));
+
+        let isc = "Permission to use, copy, modify, and/or distribute this software for any\n\
+            purpose with or without fee is hereby granted, provided that the above\n\
+            copyright notice and this permission notice appear in all copies.";
+        assert_eq!(classify_license_text(isc), Some("ISC"));
+    }
+
+    #[test]
+    fn unknown_text_is_not_guessed() {
+        assert_eq!(classify_license_text("All rights reserved."), None);
+        assert_eq!(classify_license_text(""), None);
+    }
+}
@@ -3,34 +3,82 @@
 //! Network-based enrichment of project metadata.
 //!
 //! Enrichment fills in missing fields on [`UpstreamProject`] — stars, homepage,
-//! bug tracker, contributing URL, documentation URL, funding channels, and
-//! license-based OSI classification.
+//! bug tracker, contributing URL, documentation URL, funding channels,
+//! dependent and dependency counts, criticality, latest release, OpenSSF
+//! Scorecard, known vulnerabilities, translation platform, and license-based
+//! OSI classification.
 //!
 //! Controlled at runtime via `--enrich` CLI flag or `enrich = true` in config.
 //!
 //! Enrichment sources:
-//! - GitHub API (via `gh` CLI) — stars, homepage, license, issues, FUNDING.yml
+//! - GitHub REST API — stars, homepage, license, issues, FUNDING.yml, releases and
+//!   activity, archived/deprecated state
+//! - GitLab API — stars, issues, last activity, archived/deprecated state
+//! - Bitbucket Cloud API — homepage, issues, README funding links, last update
+//! - Distro packaging metadata — licenses and upstream URLs from pacman, `.SRCINFO`,
+//!   Debian copyright files, and RPM headers (offline)
+//! - AppStream catalog — homepage, license, donation URLs of desktop applications (offline)
+//! - Repository license files — SPDX identifiers for repositories without license metadata
 //! - License classification — OSI-approved status from SPDX identifiers
+//! - PyPI JSON API — source repository, documentation, funding for Python packages
+//! - Repology API — upstream URL for distro packages that declare none
+//! - GitHub and Bitbucket commit history — bus factor
+//! - Wikidata — canonical homepage, license, developer organization, parent projects
 //! - Open Collective API — funding channel lookup
 //! - Liberapay API — funding channel lookup
+//! - Polar API — funding channel lookup for GitHub organizations
+//! - Patreon — creator pages linked from the README or homepage
+//! - Ko-fi — creator page validation, title and goal
+//! - Project homepages — funding links, donate buttons, Flathub AppStream data
+//! - Weblate, Crowdin, Transifex — translation platform from `.weblate`, README badges,
+//!   or Hosted Weblate
+//! - ecosyste.ms API — repository, funding, dependents, latest release (fallback)
+//! - deps.dev API — dependency counts, criticality, OpenSSF Scorecard, license
+//! - OSV API — known vulnerabilities in installed package versions
 
+pub mod appstream;
+pub mod bitbucket;
+pub mod bus_factor;
+pub mod deps_dev;
+pub mod distro;
+pub mod ecosystems;
+pub mod freshness;
 pub mod github;
+pub mod gitlab;
+pub mod homepage;
+pub mod kofi;
 pub mod liberapay;
 pub mod license_classify;
+pub mod license_text;
+pub mod net;
 pub mod open_collective;
+pub mod osv;
+pub mod patreon;
+pub mod polar;
+pub mod pool;
+pub mod pypi;
+pub mod redirect;
+pub mod repology;
+pub mod translation;
+pub mod wikidata;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
 
 use crate::config::Config;
+use crate::contribute::github_good_first_issues::extract_github_owner_repo;
 use crate::discover::InstalledPackage;
-use crate::project::{FundingChannel, UpstreamProject};
-use crate::report::terminal::normalize_url;
+use crate::github::GitHubClient;
+use crate::project::{Deprecation, FieldSource, FundingChannel, UpstreamProject, canonicalize_url};
 use crate::storage::Storage;
 
-/// Enriched project metadata keyed by normalized package URL.
+/// Enriched project metadata keyed by canonical package URL (see
+/// [`canonicalize_url`]).
 pub type EnrichmentMap = HashMap<String, UpstreamProject>;
 
 /// Trait for enrichment backends.
@@ -38,7 +86,11 @@ pub type EnrichmentMap = HashMap<String, UpstreamProject>;
 /// Each implementation enriches an [`UpstreamProject`] with additional metadata
 /// from a particular source. The enriched project is returned as a new value —
 /// the caller merges it with the base using [`merge_enrichment`].
-pub trait EnrichmentBackend {
+///
+/// Backends are shared by the worker threads of [`enrich_packages`], which
+/// call [`enrich`](EnrichmentBackend::enrich) for different projects at the
+/// same time, up to [`max_concurrency`](EnrichmentBackend::max_concurrency).
+pub trait EnrichmentBackend: Send + Sync {
     /// A stable, lowercase identifier for this backend.
     fn name(&self) -> &str;
 
@@ -51,63 +103,290 @@ pub trait EnrichmentBackend {
     /// Fields that this backend cannot determine should be left as-is (cloned
     /// from the input).
     fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject>;
+
+    /// Maximum number of projects this backend enriches at the same time.
+    ///
+    /// Network backends keep the default to stay polite to the services they
+    /// query; local backends may raise it to [`pool::WORKERS`].
+    fn max_concurrency(&self) -> usize {
+        pool::DEFAULT_BACKEND_CONCURRENCY
+    }
+
+    /// Whether this backend works from local data alone. Only these run in
+    /// offline mode.
+    fn is_offline(&self) -> bool {
+        false
+    }
 }
 
-/// Returns all enrichment backends that are available in the current environment.
-pub fn active_backends(_config: &Config) -> Vec<Box<dyn EnrichmentBackend>> {
-    let candidates: Vec<Box<dyn EnrichmentBackend>> = vec![
-        Box::new(license_classify::LicenseClassifyBackend),
-        Box::new(github::GitHubBackend),
-        Box::new(open_collective::OpenCollectiveBackend),
-        Box::new(liberapay::LiberapayBackend),
+/// Returns the enrichment backends that are enabled in `config` and
+/// available in the current environment, in the order they run. In offline
+/// mode, only offline backends are returned.
+pub fn active_backends(config: &Config) -> Vec<Box<dyn EnrichmentBackend>> {
+    // The local metadata backends run first, so that network backends skip
+    // what they found.
+    let mut candidates: Vec<Box<dyn EnrichmentBackend>> = vec![
+        Box::new(distro::DistroBackend::default()),
+        Box::new(appstream::AppstreamBackend::default()),
     ];
+    // PyPI runs before the code-host backends, since it resolves the source
+    // repository they look up.
+    candidates.push(Box::new(pypi::PypiBackend));
+    let wants_github = !config.offline
+        && ["github", "bus_factor"]
+            .iter()
+            .any(|name| config.enrichment.is_enabled(name));
+    let github_client = match GitHubClient::from_config(config) {
+        _ if !wants_github => None,
+        Ok(client) => Some(Arc::new(client)),
+        Err(e) => {
+            eprintln!("Warning: GitHub enrichment unavailable: {e}");
+            None
+        }
+    };
+    if let Some(client) = &github_client {
+        candidates.push(Box::new(github::GitHubBackend {
+            client: Arc::clone(client),
+        }));
+    }
+    candidates.push(Box::new(gitlab::GitLabBackend));
+    candidates.push(Box::new(bitbucket::BitbucketBackend));
+    // Reads the license file of repositories the code hosts found no license for.
+    candidates.push(Box::new(license_text::LicenseTextBackend));
+    if let Some(client) = github_client {
+        candidates.push(Box::new(bus_factor::BusFactorBackend { client }));
+    }
+    candidates.push(Box::new(wikidata::WikidataBackend));
+    candidates.push(Box::new(open_collective::OpenCollectiveBackend));
+    candidates.push(Box::new(liberapay::LiberapayBackend));
+    candidates.push(Box::new(polar::PolarBackend));
+    candidates.push(Box::new(patreon::PatreonBackend));
+    candidates.push(Box::new(kofi::KofiBackend));
+    candidates.push(Box::new(homepage::HomepageBackend));
+    candidates.push(Box::new(translation::TranslationBackend));
+    // Cross-ecosystem fallback for whatever the backends above left empty.
+    candidates.push(Box::new(ecosystems::EcosystemsBackend));
+    candidates.push(Box::new(deps_dev::DepsDevBackend));
+    // License classification runs last, so that it sees the licenses found
+    // by every backend above.
+    candidates.push(Box::new(license_classify::LicenseClassifyBackend));
 
-    candidates
+    let mut backends: Vec<_> = candidates
         .into_iter()
-        .filter(|b| b.is_available())
-        .collect()
+        .filter(|b| {
+            config.enrichment.is_enabled(b.name())
+                && (b.is_offline() || !config.offline)
+                && b.is_available()
+        })
+        .collect();
+    prioritize(&mut backends, &config.enrichment.priority);
+    backends
+}
+
+/// Move the backends named in `priority` to the front, in that order, so
+/// that what they find takes precedence when [`merge_enrichment`] combines
+/// results. The others keep their relative order, and license
+/// classification stays last.
+fn prioritize(backends: &mut [Box<dyn EnrichmentBackend>], priority: &[String]) {
+    backends.sort_by_key(|b| {
+        let name = b.name();
+        (
+            name == "license_classify",
+            priority
+                .iter()
+                .position(|p| p == name)
+                .unwrap_or(usize::MAX),
+        )
+    });
 }
 
 /// Merge enriched data onto a base project.
 ///
 /// Non-empty fields from `enriched` overlay `base`. Funding channels are
-/// deduplicated by URL.
+/// deduplicated by URL, filling in page details missing from `base`. A PyPI
+/// project page in `repo_url` is replaced by the source repository once a
+/// backend has resolved it, as is any other URL not on a code host once a
+/// backend found one that is.
+///
+/// Fields taken from `enriched` keep the provenance recorded for them there.
 pub fn merge_enrichment(base: &UpstreamProject, enriched: &UpstreamProject) -> UpstreamProject {
+    merge(base, enriched, None)
+}
+
+/// Merge what `backend` found onto a base project like [`merge_enrichment`],
+/// recording the backend as the source of every field taken from `found`
+/// (see [`UpstreamProject::provenance`]).
+pub fn merge_backend_result(
+    base: &UpstreamProject,
+    found: &UpstreamProject,
+    backend: &str,
+    fetched_at: DateTime<Utc>,
+) -> UpstreamProject {
+    let source = FieldSource {
+        backend: backend.to_string(),
+        fetched_at,
+    };
+    merge(base, found, Some(&source))
+}
+
+/// Merge `enriched` onto `base`, attributing the fields taken from it to
+/// `source`, or else to their source in `enriched`. Funding figures are
+/// attributed per platform, as `funding_status.<platform>`, and other list
+/// fields to the source that last added to them.
+fn merge(
+    base: &UpstreamProject,
+    enriched: &UpstreamProject,
+    source: Option<&FieldSource>,
+) -> UpstreamProject {
     let mut result = base.clone();
+    let mut taken: Vec<&str> = Vec::new();
+    let mut taken_statuses: Vec<&str> = Vec::new();
+
+    if let Some(repo_url) = &enriched.repo_url {
+        let replace = match result.repo_url.as_deref() {
+            None => true,
+            Some(url) if pypi::extract_pypi_name(url).is_some() => true,
+            Some(url) => !is_code_host(url) && is_code_host(repo_url),
+        };
+        if replace {
+            result.repo_url = Some(repo_url.clone());
+            taken.push("repo_url");
+        }
+    }
 
     if result.homepage.is_none() && enriched.homepage.is_some() {
         result.homepage = enriched.homepage.clone();
+        taken.push("homepage");
     }
     if result.bug_tracker.is_none() && enriched.bug_tracker.is_some() {
         result.bug_tracker = enriched.bug_tracker.clone();
+        taken.push("bug_tracker");
     }
     if result.contributing_url.is_none() && enriched.contributing_url.is_some() {
         result.contributing_url = enriched.contributing_url.clone();
+        taken.push("contributing_url");
     }
     if result.documentation_url.is_none() && enriched.documentation_url.is_some() {
         result.documentation_url = enriched.documentation_url.clone();
+        taken.push("documentation_url");
     }
     if result.good_first_issues_url.is_none() && enriched.good_first_issues_url.is_some() {
         result.good_first_issues_url = enriched.good_first_issues_url.clone();
+        taken.push("good_first_issues_url");
     }
     if result.is_open_source.is_none() && enriched.is_open_source.is_some() {
         result.is_open_source = enriched.is_open_source;
+        taken.push("is_open_source");
     }
     if result.stars.is_none() && enriched.stars.is_some() {
         result.stars = enriched.stars;
+        taken.push("stars");
+    }
+    if result.dependents.is_none() && enriched.dependents.is_some() {
+        result.dependents = enriched.dependents;
+        taken.push("dependents");
+    }
+    if result.latest_release.is_none() && enriched.latest_release.is_some() {
+        result.latest_release = enriched.latest_release.clone();
+        taken.push("latest_release");
+    }
+    if result.dependencies.is_none() && enriched.dependencies.is_some() {
+        result.dependencies = enriched.dependencies;
+        taken.push("dependencies");
+    }
+    if result.scorecard.is_none() && enriched.scorecard.is_some() {
+        result.scorecard = enriched.scorecard.clone();
+        taken.push("scorecard");
+    }
+    if result.vulnerabilities.is_none() && enriched.vulnerabilities.is_some() {
+        result.vulnerabilities = enriched.vulnerabilities.clone();
+        taken.push("vulnerabilities");
+    }
+    if result.organization.is_none() && enriched.organization.is_some() {
+        result.organization = enriched.organization.clone();
+        taken.push("organization");
+    }
+    if result.deprecation.is_none() && enriched.deprecation.is_some() {
+        result.deprecation = enriched.deprecation.clone();
+        taken.push("deprecation");
+    }
+    if result.translation_url.is_none() && enriched.translation_url.is_some() {
+        result.translation_url = enriched.translation_url.clone();
+        taken.push("translation_url");
+    }
+    if result.criticality.is_none() && enriched.criticality.is_some() {
+        result.criticality = enriched.criticality;
+        taken.push("criticality");
+    }
+    if result.bus_factor.is_none() && enriched.bus_factor.is_some() {
+        result.bus_factor = enriched.bus_factor;
+        taken.push("bus_factor");
+    }
+    if result.part_of.is_empty() && !enriched.part_of.is_empty() {
+        result.part_of = enriched.part_of.clone();
+        taken.push("part_of");
+    }
+    let activity_before = result.activity.clone();
+    if let Some(activity) = &enriched.activity {
+        let existing = result.activity.get_or_insert_with(|| activity.clone());
+        if existing.last_commit_at.is_none() {
+            existing.last_commit_at = activity.last_commit_at.clone();
+        }
+        if existing.last_release_at.is_none() {
+            existing.last_release_at = activity.last_release_at.clone();
+        }
+        if existing.release_cadence_days.is_none() {
+            existing.release_cadence_days = activity.release_cadence_days;
+        }
+    }
+    if result.activity != activity_before {
+        taken.push("activity");
+    }
+
+    // Merge funding figures (one set per platform)
+    for status in &enriched.funding_status {
+        if !result
+            .funding_status
+            .iter()
+            .any(|s| s.platform == status.platform)
+        {
+            result.funding_status.push(status.clone());
+            taken_statuses.push(&status.platform);
+        }
     }
 
     // Merge licenses (deduplicate)
     for license in &enriched.licenses {
         if !result.licenses.contains(license) {
             result.licenses.push(license.clone());
+            taken.push("licenses");
         }
     }
 
-    // Merge funding channels (deduplicate by URL)
+    // Merge funding channels (deduplicate by URL, keeping page details)
     for channel in &enriched.funding {
-        if !result.funding.iter().any(|f| f.url == channel.url) {
-            result.funding.push(channel.clone());
+        match result.funding.iter_mut().find(|f| f.url == channel.url) {
+            Some(existing) => {
+                if existing.page.is_none() && channel.page.is_some() {
+                    existing.page = channel.page.clone();
+                    taken.push("funding");
+                }
+            }
+            None => {
+                result.funding.push(channel.clone());
+                taken.push("funding");
+            }
+        }
+    }
+
+    let fields = taken.into_iter().map(str::to_string).chain(
+        taken_statuses
+            .into_iter()
+            .map(|platform| format!("funding_status.{platform}")),
+    );
+    for field in fields {
+        if let Some(field_source) = source.or_else(|| enriched.provenance.get(&field)) {
+            result.provenance.insert(field, field_source.clone());
         }
     }
 
@@ -116,20 +395,41 @@ pub fn merge_enrichment(base: &UpstreamProject, enriched: &UpstreamProject) -> U
 
 /// Enrich packages using all available backends.
 ///
-/// Deduplicates packages by normalized URL, checks the enrichment cache first,
+/// Deduplicates packages by canonical URL, checks the enrichment cache first,
 /// and runs each backend on cache misses. Results are saved back to cache.
 ///
-/// Returns an `EnrichmentMap` keyed by normalized URL.
+/// Cache misses are enriched concurrently by [`pool::WORKERS`] threads. Each
+/// project still goes through the backends in order, since later backends
+/// build on what earlier ones found, and each backend serves at most its
+/// [`max_concurrency`](EnrichmentBackend::max_concurrency) projects at once.
+///
+/// Cached projects whose volatile fields expired (see [`freshness`]) are
+/// only run through the backends that supplied those fields.
+///
+/// In offline mode, cached projects are served as they are, however old
+/// their fields, and the rest only go through the offline backends. Their
+/// results are not cached, so that a later online run enriches them fully.
+///
+/// Returns an `EnrichmentMap` keyed by canonical URL.
 pub fn enrich_packages(
     packages: &[InstalledPackage],
     storage: &Storage,
     config: &Config,
 ) -> Result<EnrichmentMap> {
-    let backends = active_backends(config);
+    let mut backends = active_backends(config);
+    // OSV needs the installed versions, and runs first while `repo_url` still
+    // matches the URL its packages were grouped by.
+    let osv = osv::OsvBackend::new(packages);
+    if !config.offline && config.enrichment.is_enabled(osv.name()) && osv.is_available() {
+        backends.insert(0, Box::new(osv));
+    }
 
-    if backends.is_empty() {
+    if config.offline {
+        eprintln!("Offline: using cached enrichment and offline backends only.");
+    }
+    if backends.is_empty() && !config.offline {
         eprintln!("No enrichment backends available.");
-        return Ok(EnrichmentMap::new());
+        return Ok(package_funding(packages));
     }
 
     eprintln!(
@@ -141,86 +441,470 @@ pub fn enrich_packages(
             .join(", ")
     );
 
-    // Collect unique URLs to enrich
+    let url_to_project = seed_projects(packages);
+
+    let total = url_to_project.len();
+    let pb = ProgressBar::new(total as u64);
+    pb.set_style(
+        ProgressStyle::with_template("Enriching [{bar:30}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+
+    let mut enrichment_map = EnrichmentMap::new();
+
+    // The cache lives in SQLite, which stays on this thread: serve hits
+    // first, and collect the misses for the workers, along with cached
+    // projects whose volatile fields expired and the backends to refresh
+    // them from.
+    let now = Utc::now();
+    let mut misses: Vec<Miss> = Vec::new();
+    for (canonical_url, base_project) in &url_to_project {
+        match storage.get_enrichment(canonical_url) {
+            Ok(Some(mut cached)) => {
+                let stale = if config.offline {
+                    BTreeSet::new()
+                } else {
+                    freshness::expire_stale_fields(&mut cached, now)
+                };
+                // Funding declared by the packages themselves may be newer
+                // than the cache entry, so fold it back in.
+                let project = merge_enrichment(&cached, base_project);
+                if stale.is_empty() {
+                    enrichment_map.insert(canonical_url.clone(), project);
+                    pb.inc(1);
+                } else {
+                    misses.push(Miss {
+                        canonical_url,
+                        project,
+                        only: Some(stale),
+                    });
+                }
+            }
+            _ => misses.push(Miss {
+                canonical_url,
+                project: base_project.clone(),
+                only: None,
+            }),
+        }
+    }
+
+    let limiters: Vec<pool::Limiter> = backends
+        .iter()
+        .map(|b| pool::Limiter::new(b.max_concurrency()))
+        .collect();
+    let breakers: Vec<net::CircuitBreaker> = backends.iter().map(|_| Default::default()).collect();
+    let workers = rayon::ThreadPoolBuilder::new()
+        .num_threads(pool::WORKERS)
+        .build()?;
+    let enriched: Vec<(&Miss, UpstreamProject, bool)> = workers.install(|| {
+        misses
+            .par_iter()
+            .map(|miss| {
+                pb.set_message(miss.project.name.clone());
+                let mut enriched = miss.project.clone();
+                // Whether every backend to run answered.
+                let mut complete = true;
+                for ((backend, limiter), breaker) in backends.iter().zip(&limiters).zip(&breakers) {
+                    let skipped = miss
+                        .only
+                        .as_ref()
+                        .is_some_and(|only| !only.contains(backend.name()));
+                    if skipped {
+                        continue;
+                    }
+                    if breaker.is_open() {
+                        complete = false;
+                        continue;
+                    }
+                    match limiter.run(|| backend.enrich(&enriched)) {
+                        Ok(result) => {
+                            breaker.record(true);
+                            enriched = merge_backend_result(
+                                &enriched,
+                                &result,
+                                backend.name(),
+                                Utc::now(),
+                            );
+                        }
+                        Err(e) => pb.suspend(|| {
+                            complete = false;
+                            eprintln!(
+                                "Warning: {} enrichment failed for {}: {e}",
+                                backend.name(),
+                                miss.project.name
+                            );
+                            if breaker.record(false) {
+                                eprintln!(
+                                    "Warning: disabling {} enrichment for the rest of the run \
+                                     after {} consecutive failures",
+                                    backend.name(),
+                                    net::BREAKER_THRESHOLD
+                                );
+                            }
+                        }),
+                    }
+                }
+                pb.inc(1);
+                (miss, enriched, complete)
+            })
+            .collect()
+    });
+
+    for (miss, enriched, complete) in enriched {
+        let canonical_url = miss.canonical_url;
+        // A failed refresh leaves the expired fields cached, to be retried
+        // on the next run, rather than dropping them.
+        let saved = match miss.only {
+            _ if config.offline => Ok(()),
+            Some(_) if !complete => Ok(()),
+            Some(_) => storage.refresh_enrichment(canonical_url, &enriched),
+            None => storage.save_enrichment(canonical_url, &enriched),
+        };
+        if let Err(e) = saved {
+            eprintln!(
+                "Warning: failed to cache enrichment for {}: {e}",
+                enriched.name
+            );
+        }
+        enrichment_map.insert(canonical_url.clone(), enriched);
+    }
+
+    pb.finish_with_message("done");
+    eprintln!("Enriched {} projects", enrichment_map.len());
+
+    Ok(enrichment_map)
+}
+
+/// A project for the workers of [`enrich_packages`] to enrich.
+struct Miss<'a> {
+    canonical_url: &'a String,
+    project: UpstreamProject,
+    /// Names of the backends to run, or `None` for all of them
+    only: Option<BTreeSet<String>>,
+}
+
+/// Build the base project for each canonical upstream URL from package
+/// metadata alone, without any network access.
+///
+/// The first package seen for a URL names the project. Funding channels
+/// declared by any package sharing the URL are merged, deduplicated by URL.
+pub fn seed_projects(packages: &[InstalledPackage]) -> HashMap<String, UpstreamProject> {
     let mut url_to_project: HashMap<String, UpstreamProject> = HashMap::new();
     for pkg in packages {
         if let Some(url) = &pkg.url {
-            let normalized = normalize_url(url);
-            if !normalized.is_empty() {
-                url_to_project
-                    .entry(normalized)
+            let canonical = canonicalize_url(url);
+            if !canonical.is_empty() {
+                let project = url_to_project
+                    .entry(canonical)
                     .or_insert_with(|| UpstreamProject {
                         name: pkg.name.clone(),
                         repo_url: Some(url.clone()),
-                        homepage: None,
                         licenses: pkg.licenses.clone(),
-                        funding: vec![],
-                        bug_tracker: None,
-                        contributing_url: None,
-                        is_open_source: None,
-                        documentation_url: None,
-                        good_first_issues_url: None,
-                        stars: None,
+                        ..Default::default()
                     });
+                for channel in &pkg.funding {
+                    if !project.funding.iter().any(|f| f.url == channel.url) {
+                        project.funding.push(channel.clone());
+                    }
+                }
             }
         }
     }
+    url_to_project
+}
 
-    let total = url_to_project.len();
-    let pb = ProgressBar::new(total as u64);
+/// Fill in upstream URLs for distro packages whose metadata declares none,
+/// from other local packaging metadata (see [`distro`]) or else by looking
+/// their names up on Repology.
+///
+/// Runs before [`enrich_packages`] so that resolved packages are grouped and
+/// enriched like any other. Lookups are cached as URL resolutions under a
+/// `repology:<name>` key, including those that found nothing. Offline, only
+/// cached lookups are used.
+pub fn resolve_missing_urls(packages: &mut [InstalledPackage], storage: &Storage, config: &Config) {
+    let local = distro::DistroBackend::default();
+    for pkg in packages.iter_mut() {
+        if pkg.url.is_none() && repology::covers(&pkg.source) {
+            pkg.url = local.lookup(&pkg.name).url;
+        }
+    }
+
+    let names: BTreeSet<String> = packages
+        .iter()
+        .filter(|p| p.url.is_none() && repology::covers(&p.source))
+        .map(|p| p.name.clone())
+        .collect();
+    if names.is_empty() {
+        return;
+    }
+
+    let backend = repology::RepologyBackend;
+    let pb = ProgressBar::new(names.len() as u64);
     pb.set_style(
-        ProgressStyle::with_template("Enriching [{bar:30}] {pos}/{len} {msg}")
+        ProgressStyle::with_template("Resolving [{bar:30}] {pos}/{len} {msg}")
             .unwrap()
             .progress_chars("=> "),
     );
 
-    let mut enrichment_map = EnrichmentMap::new();
+    let mut resolved: HashMap<String, String> = HashMap::new();
+    for name in &names {
+        pb.set_message(name.clone());
+        let cache_key = format!("repology:{name}");
+
+        let url = match storage.url_resolution(&cache_key) {
+            Ok(Some(cached)) => cached,
+            _ if config.offline => {
+                pb.inc(1);
+                continue;
+            }
+            _ => {
+                let seed = UpstreamProject {
+                    name: name.clone(),
+                    ..Default::default()
+                };
+                match backend.enrich(&seed) {
+                    Ok(project) => {
+                        let url = project.repo_url.or(project.homepage);
+                        if let Err(e) = storage.save_url_resolution(&cache_key, url.as_deref()) {
+                            pb.suspend(|| {
+                                eprintln!(
+                                    "Warning: failed to cache Repology lookup for {name}: {e}"
+                                )
+                            });
+                        }
+                        url
+                    }
+                    Err(e) => {
+                        pb.suspend(|| eprintln!("Warning: Repology lookup failed for {name}: {e}"));
+                        pb.inc(1);
+                        continue;
+                    }
+                }
+            }
+        };
 
-    for (normalized_url, base_project) in &url_to_project {
-        pb.set_message(base_project.name.clone());
+        if let Some(url) = url {
+            resolved.insert(name.clone(), url);
+        }
+        pb.inc(1);
+    }
 
-        // Check cache first (use the original URL from repo_url as cache key)
-        let cache_key = base_project.repo_url.as_deref().unwrap_or(normalized_url);
+    pb.finish_and_clear();
 
-        if let Ok(Some(cached)) = storage.get_enrichment(cache_key) {
-            enrichment_map.insert(normalized_url.clone(), cached);
-            pb.inc(1);
-            continue;
+    for pkg in packages {
+        if pkg.url.is_none()
+            && repology::covers(&pkg.source)
+            && let Some(url) = resolved.get(&pkg.name)
+        {
+            pkg.url = Some(url.clone());
         }
+    }
+}
 
-        // Run all backends
-        let mut enriched = base_project.clone();
-        for backend in &backends {
-            match backend.enrich(&enriched) {
-                Ok(result) => {
-                    enriched = merge_enrichment(&enriched, &result);
-                }
-                Err(e) => {
-                    eprintln!(
-                        "Warning: {} enrichment failed for {}: {e}",
-                        backend.name(),
-                        base_project.name
-                    );
+/// The cached enrichment of the project at `url`, after the redirect that
+/// [`resolve_redirects`] cached for it, if any.
+pub fn cached_project(storage: &Storage, url: &str) -> Result<Option<UpstreamProject>> {
+    let canonical = canonicalize_url(url);
+    let redirect = storage.url_resolution(&format!("redirect:{canonical}"))?;
+    let canonical = match redirect.flatten() {
+        Some(destination) => canonicalize_url(&destination),
+        None => canonical,
+    };
+    storage.get_enrichment(&canonical)
+}
+
+/// Replace package URLs that lead elsewhere with their destination: renamed
+/// repositories and moved homepages with where they redirect to, and Read
+/// the Docs sites with the repository they are built from (see [`redirect`]).
+///
+/// Runs after [`resolve_missing_urls`] and before [`enrich_packages`], so
+/// that every spelling of a project's URL groups as one project. Lookups are
+/// cached as URL resolutions under a `redirect:<canonical url>` key,
+/// including those that found nothing. Offline, only cached lookups are used.
+pub fn resolve_redirects(packages: &mut [InstalledPackage], storage: &Storage, config: &Config) {
+    let urls: BTreeSet<String> = packages.iter().filter_map(|p| p.url.clone()).collect();
+    if urls.is_empty() {
+        return;
+    }
+
+    let pb = ProgressBar::new(urls.len() as u64);
+    pb.set_style(
+        ProgressStyle::with_template("Resolving redirects [{bar:30}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+
+    let mut resolved: HashMap<String, String> = HashMap::new();
+    for url in &urls {
+        pb.set_message(url.clone());
+        let cache_key = format!("redirect:{}", canonicalize_url(url));
+
+        let destination = match storage.url_resolution(&cache_key) {
+            Ok(Some(cached)) => cached,
+            _ if config.offline => None,
+            _ => match redirect::resolve(net::client(), url) {
+                Ok(destination) => {
+                    if let Err(e) = storage.save_url_resolution(&cache_key, destination.as_deref())
+                    {
+                        pb.suspend(|| {
+                            eprintln!("Warning: failed to cache redirect lookup for {url}: {e}")
+                        });
+                    }
+                    destination
                 }
-            }
+                // Unreachable hosts are retried on the next run.
+                Err(_) => None,
+            },
+        };
+
+        if let Some(destination) = destination {
+            resolved.insert(url.clone(), destination);
         }
+        pb.inc(1);
+    }
 
-        // Save to cache
-        if let Err(e) = storage.save_enrichment(cache_key, &enriched) {
-            eprintln!(
-                "Warning: failed to cache enrichment for {}: {e}",
-                base_project.name
-            );
+    pb.finish_and_clear();
+
+    for pkg in packages {
+        if let Some(destination) = pkg.url.as_ref().and_then(|url| resolved.get(url)) {
+            pkg.url = Some(destination.clone());
         }
+    }
+}
 
-        enrichment_map.insert(normalized_url.clone(), enriched);
-        pb.inc(1);
+/// Whether `url` points at a source code hosting platform rather than a
+/// plain homepage.
+pub(crate) fn is_code_host(url: &str) -> bool {
+    const CODE_HOSTS: &[&str] = &[
+        "github.com",
+        "gitlab.com",
+        "codeberg.org",
+        "bitbucket.org",
+        "sr.ht",
+    ];
+
+    let host = url
+        .split("://")
+        .nth(1)
+        .unwrap_or(url)
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("www.");
+    CODE_HOSTS.contains(&host)
+}
+
+/// URL prefix of raw files on the default branch of a GitHub or GitLab
+/// repository.
+pub(crate) fn raw_file_base(repo_url: &str) -> Option<String> {
+    if let Some(owner_repo) = extract_github_owner_repo(repo_url) {
+        return Some(format!(
+            "https://raw.githubusercontent.com/{owner_repo}/HEAD"
+        ));
     }
+    let (host, path) = gitlab::gitlab_project(repo_url)?;
+    Some(format!("https://{host}/{path}/-/raw/HEAD"))
+}
 
-    pb.finish_with_message("done");
-    eprintln!("Enriched {} projects", enrichment_map.len());
+/// Average number of days between releases, given their RFC 3339
+/// publication times in any order. Needs at least two parseable times.
+pub(crate) fn release_cadence_days<'a>(
+    published_at: impl IntoIterator<Item = &'a str>,
+) -> Option<f64> {
+    let mut times: Vec<_> = published_at
+        .into_iter()
+        .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .collect();
+    if times.len() < 2 {
+        return None;
+    }
+    times.sort();
+    let span = *times.last()? - times[0];
+    Some(span.num_seconds() as f64 / 86_400.0 / (times.len() - 1) as f64)
+}
 
-    Ok(enrichment_map)
+/// Phrases in a repository description that mark a project as no longer
+/// maintained.
+const DEPRECATION_MARKERS: &[&str] = &[
+    "deprecated",
+    "unmaintained",
+    "no longer maintained",
+    "not maintained",
+    "moved to",
+    "has moved",
+    "superseded by",
+];
+
+/// Phrases in a repository description that introduce the replacement of a
+/// deprecated project.
+const REPLACEMENT_MARKERS: &[&str] = &[
+    "moved to",
+    "in favor of",
+    "in favour of",
+    "replaced by",
+    "superseded by",
+    "use",
+];
+
+/// Whether a repository on `host` is archived or described as deprecated,
+/// and what replaces it. A replacement is taken from the first link in the
+/// description, or from an `owner/repo` name following a phrase such as
+/// "moved to".
+pub(crate) fn detect_deprecation(
+    archived: bool,
+    description: Option<&str>,
+    host: &str,
+) -> Option<Deprecation> {
+    let description = description.unwrap_or_default();
+    let lower = description.to_ascii_lowercase();
+    if !archived && !DEPRECATION_MARKERS.iter().any(|m| lower.contains(m)) {
+        return None;
+    }
+
+    let trim = |word: &str| {
+        word.trim_matches(|c: char| {
+            matches!(c, '.' | ',' | ';' | ':' | '(' | ')' | '`' | '"' | '\'')
+        })
+        .to_string()
+    };
+    let link = description
+        .split_whitespace()
+        .map(trim)
+        .find(|w| w.starts_with("https://") || w.starts_with("http://"));
+    let named = || {
+        REPLACEMENT_MARKERS.iter().find_map(|marker| {
+            let pos = lower.match_indices(marker).find_map(|(i, _)| {
+                let before = lower[..i].chars().next_back();
+                (before.is_none_or(|c| !c.is_alphanumeric())).then_some(i + marker.len())
+            })?;
+            let word = trim(description[pos..].split_whitespace().next()?);
+            let (owner, repo) = word.split_once('/')?;
+            let valid = |s: &str| {
+                !s.is_empty()
+                    && s.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            };
+            (valid(owner) && valid(repo)).then(|| format!("https://{host}/{owner}/{repo}"))
+        })
+    };
+
+    Some(Deprecation {
+        archived,
+        replacement: link.or_else(named),
+    })
+}
+
+/// Funding channels declared directly in package metadata (e.g. npm's
+/// `funding` field), keyed by canonical URL.
+///
+/// Used in place of full enrichment when it is disabled, so that funding
+/// information that needs no network lookup is still reported.
+pub fn package_funding(packages: &[InstalledPackage]) -> EnrichmentMap {
+    seed_projects(packages)
+        .into_iter()
+        .filter(|(_, project)| !project.funding.is_empty())
+        .collect()
 }
 
 /// Build a `FundingChannel` — convenience constructor used across backends.
@@ -228,29 +912,49 @@ pub fn funding_channel(platform: &str, url: String) -> FundingChannel {
     FundingChannel {
         platform: platform.to_string(),
         url,
+        page: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::discover::InstallReason;
+    use crate::project::{Activity, FundingPage};
 
     fn empty_project(name: &str) -> UpstreamProject {
         UpstreamProject {
             name: name.to_string(),
-            repo_url: None,
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         }
     }
 
+    #[test]
+    fn cached_project_follows_cached_redirects() {
+        let storage = Storage::open_path(std::path::Path::new(":memory:")).unwrap();
+        let project = UpstreamProject {
+            repo_url: Some("https://github.com/new/tool".to_string()),
+            ..empty_project("tool")
+        };
+        storage
+            .save_enrichment("github.com/new/tool", &project)
+            .unwrap();
+        storage
+            .save_url_resolution(
+                "redirect:github.com/old/tool",
+                Some("https://github.com/new/tool"),
+            )
+            .unwrap();
+
+        let cached = cached_project(&storage, "https://github.com/old/tool/").unwrap();
+        assert_eq!(cached.unwrap().name, "tool");
+        assert!(
+            cached_project(&storage, "https://github.com/other/tool")
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn merge_fills_empty_fields() {
         let base = empty_project("test");
@@ -290,12 +994,39 @@ mod tests {
         assert_eq!(result.stars, Some(100));
     }
 
+    #[test]
+    fn merge_replaces_pypi_page_with_source_repo() {
+        let pypi_page = UpstreamProject {
+            repo_url: Some("https://pypi.org/project/requests/".to_string()),
+            ..empty_project("requests")
+        };
+        let resolved = UpstreamProject {
+            repo_url: Some("https://github.com/psf/requests".to_string()),
+            ..empty_project("requests")
+        };
+
+        let result = merge_enrichment(&pypi_page, &resolved);
+        assert_eq!(
+            result.repo_url.as_deref(),
+            Some("https://github.com/psf/requests")
+        );
+
+        // A real repository is never replaced, e.g. when a cached entry is
+        // merged with the freshly seeded project.
+        let result = merge_enrichment(&resolved, &pypi_page);
+        assert_eq!(
+            result.repo_url.as_deref(),
+            Some("https://github.com/psf/requests")
+        );
+    }
+
     #[test]
     fn merge_deduplicates_funding_by_url() {
         let base = UpstreamProject {
             funding: vec![FundingChannel {
                 platform: "GitHub Sponsors".to_string(),
                 url: "https://github.com/sponsors/test".to_string(),
+                page: None,
             }],
             ..empty_project("test")
         };
@@ -304,10 +1035,12 @@ mod tests {
                 FundingChannel {
                     platform: "GitHub Sponsors".to_string(),
                     url: "https://github.com/sponsors/test".to_string(), // duplicate
+                    page: None,
                 },
                 FundingChannel {
                     platform: "Open Collective".to_string(),
                     url: "https://opencollective.com/test".to_string(), // new
+                    page: None,
                 },
             ],
             ..empty_project("test")
@@ -319,6 +1052,31 @@ mod tests {
         assert_eq!(result.funding[1].platform, "Open Collective");
     }
 
+    #[test]
+    fn merge_fills_in_funding_page() {
+        let channel = FundingChannel::from_url("https://ko-fi.com/test");
+        let base = UpstreamProject {
+            funding: vec![channel.clone()],
+            ..empty_project("test")
+        };
+        let page = FundingPage {
+            title: Some("Support test".to_string()),
+            goal: Some("New laptop".to_string()),
+            tiers: vec![],
+        };
+        let enriched = UpstreamProject {
+            funding: vec![FundingChannel {
+                page: Some(page.clone()),
+                ..channel
+            }],
+            ..empty_project("test")
+        };
+
+        let result = merge_enrichment(&base, &enriched);
+        assert_eq!(result.funding.len(), 1);
+        assert_eq!(result.funding[0].page, Some(page));
+    }
+
     #[test]
     fn merge_deduplicates_licenses() {
         let base = UpstreamProject {
@@ -334,6 +1092,94 @@ mod tests {
         assert_eq!(result.licenses, vec!["MIT", "Apache-2.0"]);
     }
 
+    #[test]
+    fn merge_fills_in_activity_fields() {
+        let base = UpstreamProject {
+            activity: Some(Activity {
+                last_commit_at: None,
+                last_release_at: Some("2024-05-01T00:00:00Z".to_string()),
+                release_cadence_days: None,
+            }),
+            ..empty_project("test")
+        };
+        let enriched = UpstreamProject {
+            activity: Some(Activity {
+                last_commit_at: Some("2024-06-01T00:00:00Z".to_string()),
+                last_release_at: Some("2023-01-01T00:00:00Z".to_string()),
+                release_cadence_days: Some(45.0),
+            }),
+            ..empty_project("test")
+        };
+
+        let activity = merge_enrichment(&base, &enriched).activity.unwrap();
+        assert_eq!(
+            activity.last_commit_at.as_deref(),
+            Some("2024-06-01T00:00:00Z")
+        );
+        assert_eq!(
+            activity.last_release_at.as_deref(),
+            Some("2024-05-01T00:00:00Z")
+        );
+        assert_eq!(activity.release_cadence_days, Some(45.0));
+    }
+
+    #[test]
+    fn release_cadence_from_publication_times() {
+        let cadence = release_cadence_days([
+            "2024-03-01T00:00:00Z",
+            "2024-01-01T00:00:00Z",
+            "2024-01-31T00:00:00Z",
+            "garbage",
+        ]);
+        assert_eq!(cadence, Some(30.0));
+        assert_eq!(release_cadence_days(["2024-01-01T00:00:00Z"]), None);
+    }
+
+    #[test]
+    fn deprecation_from_description() {
+        assert_eq!(
+            detect_deprecation(false, Some("A fast JSON parser"), "github.com"),
+            None
+        );
+        assert_eq!(
+            detect_deprecation(true, None, "github.com"),
+            Some(Deprecation {
+                archived: true,
+                replacement: None,
+            })
+        );
+        assert_eq!(
+            detect_deprecation(
+                false,
+                Some("DEPRECATED: use `serde-rs/json` instead."),
+                "github.com"
+            ),
+            Some(Deprecation {
+                archived: false,
+                replacement: Some("https://github.com/serde-rs/json".to_string()),
+            })
+        );
+        assert_eq!(
+            detect_deprecation(
+                true,
+                Some("This project has moved to https://codeberg.org/foo/foo."),
+                "github.com"
+            )
+            .and_then(|d| d.replacement),
+            Some("https://codeberg.org/foo/foo".to_string())
+        );
+        // "use" inside another word is not a replacement marker.
+        assert_eq!(
+            detect_deprecation(
+                true,
+                Some("Unmaintained; it is not in use anymore"),
+                "gitlab.com"
+            )
+            .and_then(|d| d.replacement),
+            None
+        );
+    }
+
     #[test]
     fn merge_empty_enriched_is_noop() {
         let base = UpstreamProject {
@@ -348,6 +1194,38 @@ mod tests {
         assert_eq!(result.stars, Some(42));
     }
 
+    #[test]
+    fn merge_records_provenance_of_taken_fields() {
+        let base = UpstreamProject {
+            homepage: Some("https://original.com".to_string()),
+            ..empty_project("test")
+        };
+        let found = UpstreamProject {
+            homepage: Some("https://new.com".to_string()),
+            stars: Some(200),
+            licenses: vec!["MIT".to_string()],
+            ..empty_project("test")
+        };
+        let fetched_at = Utc::now();
+
+        let result = merge_backend_result(&base, &found, "github", fetched_at);
+        assert_eq!(
+            result.provenance["stars"],
+            FieldSource {
+                backend: "github".to_string(),
+                fetched_at,
+            }
+        );
+        assert_eq!(result.provenance["licenses"].backend, "github");
+        // The homepage came from package metadata, not from GitHub.
+        assert!(!result.provenance.contains_key("homepage"));
+
+        // Plain merges carry provenance over with the fields.
+        let merged = merge_enrichment(&empty_project("test"), &result);
+        assert_eq!(merged.provenance["stars"].backend, "github");
+        assert!(!merged.provenance.contains_key("homepage"));
+    }
+
     #[test]
     fn active_backends_does_not_panic() {
         let config = Config::default();
@@ -355,4 +1233,113 @@ mod tests {
         // License classify is always available
         assert!(backends.iter().any(|b| b.name() == "license_classify"));
     }
+
+    #[test]
+    fn active_backends_honors_disabled() {
+        let mut config = Config::default();
+        config.enrichment.disabled = vec!["wikidata".to_string(), "github".to_string()];
+        let backends = active_backends(&config);
+        assert!(!backends.iter().any(|b| b.name() == "wikidata"));
+        assert!(!backends.iter().any(|b| b.name() == "github"));
+        assert!(backends.iter().any(|b| b.name() == "deps_dev"));
+    }
+
+    #[test]
+    fn active_backends_offline_keeps_offline_backends() {
+        let config = Config {
+            offline: true,
+            ..Default::default()
+        };
+        let backends = active_backends(&config);
+        assert!(backends.iter().all(|b| b.is_offline()));
+        assert!(backends.iter().any(|b| b.name() == "license_classify"));
+    }
+
+    struct Named(&'static str);
+
+    impl EnrichmentBackend for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+            Ok(project.clone())
+        }
+    }
+
+    #[test]
+    fn prioritize_moves_listed_backends_first() {
+        let mut backends: Vec<Box<dyn EnrichmentBackend>> =
+            ["distro", "github", "wikidata", "license_classify"]
+                .into_iter()
+                .map(|name| Box::new(Named(name)) as Box<dyn EnrichmentBackend>)
+                .collect();
+        let priority = ["wikidata", "license_classify", "distro"].map(String::from);
+        prioritize(&mut backends, &priority);
+        let order: Vec<_> = backends.iter().map(|b| b.name()).collect();
+        assert_eq!(order, ["wikidata", "distro", "github", "license_classify"]);
+    }
+
+    fn package(name: &str, url: &str, funding: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            url: Some(url.to_string()),
+            source: crate::discover::PackageSource::Npm,
+            licenses: vec![],
+            funding: funding
+                .iter()
+                .map(|u| FundingChannel::from_url(u))
+                .collect(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        }
+    }
+
+    #[test]
+    fn seed_projects_merges_package_funding() {
+        let packages = vec![
+            package(
+                "eslint",
+                "https://github.com/eslint/eslint",
+                &["https://opencollective.com/eslint"],
+            ),
+            package(
+                "eslint-cli",
+                "https://github.com/eslint/eslint",
+                &[
+                    "https://opencollective.com/eslint",
+                    "https://tidelift.com/funding/github/npm/eslint",
+                ],
+            ),
+        ];
+
+        let projects = seed_projects(&packages);
+        assert_eq!(projects.len(), 1);
+        let project = projects.values().next().unwrap();
+        assert_eq!(project.name, "eslint");
+        assert_eq!(project.funding.len(), 2);
+        assert_eq!(project.funding[1].platform, "Tidelift");
+    }
+
+    #[test]
+    fn package_funding_skips_projects_without_funding() {
+        let packages = vec![
+            package(
+                "chalk",
+                "https://github.com/chalk/chalk",
+                &["https://github.com/chalk/chalk?sponsor=1"],
+            ),
+            package("left-pad", "https://github.com/left-pad/left-pad", &[]),
+        ];
+
+        let map = package_funding(&packages);
+        assert_eq!(map.len(), 1);
+        assert!(map.values().all(|p| p.name == "chalk"));
+    }
 }
@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Shared HTTP layer for network enrichment backends.
+//!
+//! Backends send their requests through [`RequestExt::send_paced`], which
+//! spaces requests to the same host (at least [`DEFAULT_HOST_INTERVAL`]
+//! apart, more for hosts listed in [`HOST_INTERVALS`]) across all worker
+//! threads, and retries with exponential backoff when a server answers
+//! `429 Too Many Requests` or a 5xx error, or a connection fails.
+//!
+//! The enrichment engine also keeps a [`CircuitBreaker`] per backend, which
+//! disables a backend for the rest of the run once it keeps failing.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+
+/// Minimum delay between two requests to the same host.
+pub const DEFAULT_HOST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Hosts that ask clients for a slower pace.
+const HOST_INTERVALS: &[(&str, Duration)] = &[
+    // One request per second, per the Repology API documentation.
+    ("repology.org", Duration::from_secs(1)),
+    ("query.wikidata.org", Duration::from_millis(500)),
+];
+
+/// Retries after the first attempt.
+const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry, doubled for each further one.
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between two attempts, including server-requested ones.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Consecutive failures after which a backend is disabled.
+pub const BREAKER_THRESHOLD: u32 = 5;
+
+/// The HTTP client shared by network backends, with a 10 second timeout and
+/// a user agent identifying syld, which some APIs require.
+pub fn client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .user_agent(concat!(
+                "syld/",
+                env!("CARGO_PKG_VERSION"),
+                " (+https://github.com/bombfork/syld)"
+            ))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("HTTP client configuration is valid")
+    })
+}
+
+pub trait RequestExt {
+    /// Send the request, pacing requests per host and retrying transient
+    /// failures. Returns the last response or error once retries run out.
+    fn send_paced(self) -> reqwest::Result<Response>;
+}
+
+impl RequestExt for RequestBuilder {
+    fn send_paced(self) -> reqwest::Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let host = request.url().host_str().unwrap_or_default().to_string();
+
+        let mut attempt = 0;
+        loop {
+            wait_for_host(&host);
+            // Requests with streaming bodies cannot be cloned, and are sent once.
+            let Some(retry) = request.try_clone().filter(|_| attempt < MAX_RETRIES) else {
+                return client.execute(request);
+            };
+
+            let delay = match client.execute(retry) {
+                Ok(response) if is_transient(&response) => {
+                    backoff_delay(attempt, retry_after(&response))
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => backoff_delay(attempt, None),
+                result => return result,
+            };
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+}
+
+/// GET a URL as text, returning `None` on any failure.
+pub(crate) fn fetch_text(client: &Client, url: &str) -> Option<String> {
+    let response = client.get(url).send_paced().ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.text().ok()
+}
+
+/// Block until the next request to `host` may be sent, and reserve that
+/// slot for the caller.
+fn wait_for_host(host: &str) {
+    static NEXT_SLOT: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+    let interval = host_interval(host);
+    let wait = {
+        let mut slots = NEXT_SLOT
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let slot = slots.get(host).copied().unwrap_or(now).max(now);
+        slots.insert(host.to_string(), slot + interval);
+        slot - now
+    };
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
+
+fn host_interval(host: &str) -> Duration {
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    HOST_INTERVALS
+        .iter()
+        .find(|(h, _)| *h == host)
+        .map_or(DEFAULT_HOST_INTERVAL, |(_, interval)| *interval)
+}
+
+/// Whether a response is worth retrying: the server is overloaded or asks
+/// the client to slow down. Exhausted quotas (GitHub-style
+/// `x-ratelimit-remaining: 0`) do not recover within a retry, and are not.
+fn is_transient(response: &Response) -> bool {
+    let remaining = response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .and_then(|v| v.to_str().ok());
+    is_transient_status(response.status()) && remaining != Some("0")
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The delay requested by a `Retry-After` header in seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Delay before retry number `attempt` (from 0): what the server asked for,
+/// or else exponential backoff, capped at [`MAX_BACKOFF`].
+fn backoff_delay(attempt: u32, requested: Option<Duration>) -> Duration {
+    requested
+        .unwrap_or_else(|| BASE_BACKOFF.saturating_mul(1 << attempt.min(16)))
+        .min(MAX_BACKOFF)
+}
+
+/// Disables a backend after [`BREAKER_THRESHOLD`] consecutive failures, so
+/// that a service that is down or rejecting us is not queried for every
+/// remaining project.
+#[derive(Default)]
+pub struct CircuitBreaker {
+    consecutive_failures: AtomicU32,
+    open: AtomicBool,
+}
+
+impl CircuitBreaker {
+    /// Whether the backend is disabled.
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Relaxed)
+    }
+
+    /// Record the outcome of a call. Returns `true` for the failure that
+    /// opens the breaker, so that the caller reports it once.
+    pub fn record(&self, ok: bool) -> bool {
+        if ok {
+            self.consecutive_failures.store(0, Ordering::Relaxed);
+            return false;
+        }
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        failures >= BREAKER_THRESHOLD && !self.open.swap(true, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(backoff_delay(0, None), Duration::from_secs(1));
+        assert_eq!(backoff_delay(1, None), Duration::from_secs(2));
+        assert_eq!(backoff_delay(2, None), Duration::from_secs(4));
+        assert_eq!(backoff_delay(10, None), MAX_BACKOFF);
+        assert_eq!(backoff_delay(40, None), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn backoff_honors_retry_after() {
+        assert_eq!(
+            backoff_delay(0, Some(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            backoff_delay(0, Some(Duration::from_secs(3600))),
+            MAX_BACKOFF
+        );
+    }
+
+    #[test]
+    fn transient_statuses() {
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+        assert!(!is_transient_status(StatusCode::FORBIDDEN));
+        assert!(!is_transient_status(StatusCode::OK));
+    }
+
+    #[test]
+    fn slower_hosts() {
+        assert_eq!(host_interval("repology.org"), Duration::from_secs(1));
+        assert_eq!(host_interval("www.repology.org"), Duration::from_secs(1));
+        assert_eq!(host_interval("api.github.com"), DEFAULT_HOST_INTERVAL);
+    }
+
+    #[test]
+    fn breaker_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::default();
+        for _ in 0..BREAKER_THRESHOLD - 1 {
+            assert!(!breaker.record(false));
+        }
+        // A success resets the count.
+        assert!(!breaker.record(true));
+        for _ in 0..BREAKER_THRESHOLD - 1 {
+            assert!(!breaker.record(false));
+        }
+        assert!(!breaker.is_open());
+
+        assert!(breaker.record(false));
+        assert!(breaker.is_open());
+        // Only the failure that opened it reports it.
+        assert!(!breaker.record(false));
+    }
+}
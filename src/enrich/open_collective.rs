@@ -2,16 +2,80 @@
 
 //! Open Collective enrichment backend.
 //!
-//! Checks if a project has an Open Collective page by querying their public API.
-//! Adds a funding channel if found.
+//! Looks a project's collective up with the public Open Collective GraphQL
+//! API. Adds a funding channel if found, along with the collective's current
+//! balance, yearly budget, and active goals, so that allocation can take the
+//! project's funding gap into account.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Deserialize;
 
 use super::EnrichmentBackend;
-use crate::project::{FundingChannel, UpstreamProject};
+use super::net::{self, RequestExt};
+use crate::project::{FundingChannel, FundingGoal, FundingStatus, GoalKind, UpstreamProject};
+
+const GRAPHQL_URL: &str = "https://api.opencollective.com/graphql/v2";
+
+const ACCOUNT_QUERY: &str = "query($slug: String) {
+  account(slug: $slug) {
+    slug
+    currency
+    settings
+    stats {
+      balance { valueInCents currency }
+      yearlyBudget { valueInCents currency }
+    }
+  }
+}";
 
 pub struct OpenCollectiveBackend;
 
+#[derive(Debug, Deserialize)]
+struct OcResponse {
+    data: Option<OcData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OcData {
+    account: Option<OcAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OcAccount {
+    slug: String,
+    currency: Option<String>,
+    settings: Option<OcSettings>,
+    stats: Option<OcStats>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OcSettings {
+    #[serde(default)]
+    goals: Vec<OcGoal>,
+}
+
+/// A goal from the collective's settings. Amounts are in cents.
+#[derive(Debug, Deserialize)]
+struct OcGoal {
+    #[serde(rename = "type")]
+    kind: String,
+    amount: Option<u64>,
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OcStats {
+    balance: Option<OcAmount>,
+    yearly_budget: Option<OcAmount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OcAmount {
+    value_in_cents: Option<i64>,
+}
+
 impl EnrichmentBackend for OpenCollectiveBackend {
     fn name(&self) -> &str {
         "open_collective"
@@ -22,36 +86,212 @@ impl EnrichmentBackend for OpenCollectiveBackend {
     }
 
     fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
-        // Try to derive a slug from the project name
-        let slug = project.name.to_lowercase().replace(' ', "-");
-
-        // Skip if we already have an Open Collective funding channel
         if project
-            .funding
+            .funding_status
             .iter()
-            .any(|f| f.platform == "Open Collective")
+            .any(|s| s.platform == "Open Collective")
         {
             return Ok(project.clone());
         }
 
-        let url = format!("https://api.opencollective.com/v1/collectives/{slug}");
+        // Prefer the collective the project links to (e.g. from FUNDING.yml),
+        // otherwise try to derive a slug from the project name
+        let slug = project
+            .funding
+            .iter()
+            .filter(|f| f.platform == "Open Collective")
+            .find_map(|f| collective_slug(&f.url))
+            .unwrap_or_else(|| project.name.to_lowercase().replace(' ', "-"));
 
-        let client = reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()?;
+        let client = net::client();
 
-        let response = client.get(&url).send();
+        let response = client
+            .post(GRAPHQL_URL)
+            .json(&serde_json::json!({
+                "query": ACCOUNT_QUERY,
+                "variables": { "slug": slug },
+            }))
+            .send_paced()
+            .context("Failed to query Open Collective")?;
 
-        match response {
-            Ok(resp) if resp.status().is_success() => {
-                let mut enriched = project.clone();
-                enriched.funding.push(FundingChannel {
-                    platform: "Open Collective".to_string(),
-                    url: format!("https://opencollective.com/{slug}"),
-                });
-                Ok(enriched)
-            }
-            _ => Ok(project.clone()),
+        if !response.status().is_success() {
+            return Ok(project.clone());
         }
+
+        // Unknown slugs come back as a GraphQL error with a null account.
+        let response: OcResponse = response
+            .json()
+            .context("Failed to parse Open Collective response")?;
+        match response.data.and_then(|d| d.account) {
+            Some(account) => Ok(apply_account(project, account)),
+            None => Ok(project.clone()),
+        }
+    }
+}
+
+/// Extract the collective slug from an Open Collective URL.
+fn collective_slug(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let path = rest.strip_prefix("opencollective.com/")?;
+    path.split(['/', '?', '#'])
+        .next()
+        .filter(|slug| !slug.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Add the collective's funding channel and figures to a project.
+fn apply_account(project: &UpstreamProject, account: OcAccount) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    let url = format!("https://opencollective.com/{}", account.slug);
+    if !enriched
+        .funding
+        .iter()
+        .any(|f| collective_slug(&f.url).as_deref() == Some(account.slug.as_str()))
+    {
+        enriched.funding.push(FundingChannel {
+            platform: "Open Collective".to_string(),
+            url,
+            page: None,
+        });
+    }
+
+    let units = |amount: &Option<OcAmount>| {
+        amount
+            .as_ref()
+            .and_then(|a| a.value_in_cents)
+            .map(|cents| cents as f64 / 100.0)
+    };
+    let stats = account.stats.as_ref();
+    let goals = account
+        .settings
+        .iter()
+        .flat_map(|s| &s.goals)
+        .filter_map(|goal| {
+            let kind = match goal.kind.as_str() {
+                "yearlyBudget" => GoalKind::YearlyIncome,
+                "balance" => GoalKind::Balance,
+                _ => return None,
+            };
+            Some(FundingGoal {
+                title: goal.title.clone().filter(|t| !t.is_empty()),
+                amount: goal.amount? as f64 / 100.0,
+                kind,
+            })
+        })
+        .collect();
+
+    enriched.funding_status.push(FundingStatus {
+        platform: "Open Collective".to_string(),
+        currency: account.currency.unwrap_or_else(|| "USD".to_string()),
+        balance: stats.and_then(|s| units(&s.balance)),
+        yearly_income: stats.and_then(|s| units(&s.yearly_budget)),
+        goals,
+    });
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> UpstreamProject {
+        UpstreamProject {
+            name: "webpack".to_string(),
+            repo_url: Some("https://github.com/webpack/webpack".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn slugs_from_urls() {
+        assert_eq!(
+            collective_slug("https://opencollective.com/Webpack/donate").as_deref(),
+            Some("webpack")
+        );
+        assert_eq!(
+            collective_slug("https://www.opencollective.com/webpack").as_deref(),
+            Some("webpack")
+        );
+        assert_eq!(collective_slug("https://opencollective.com/"), None);
+        assert_eq!(collective_slug("https://liberapay.com/webpack"), None);
+    }
+
+    #[test]
+    fn applies_account_figures() {
+        let json = r#"{"data": {"account": {
+            "slug": "webpack",
+            "currency": "USD",
+            "settings": {
+                "goals": [
+                    {"type": "yearlyBudget", "amount": 50000000, "title": "Full-time maintainers"},
+                    {"type": "balance", "amount": 1000000, "title": ""},
+                    {"type": "unknown", "amount": 1}
+                ],
+                "collectivePage": {}
+            },
+            "stats": {
+                "balance": {"valueInCents": 1234567, "currency": "USD"},
+                "yearlyBudget": {"valueInCents": 32000000, "currency": "USD"}
+            }
+        }}}"#;
+        let response: OcResponse = serde_json::from_str(json).unwrap();
+        let account = response.data.unwrap().account.unwrap();
+        let enriched = apply_account(&project(), account);
+
+        assert_eq!(enriched.funding.len(), 1);
+        assert_eq!(
+            enriched.funding[0].url,
+            "https://opencollective.com/webpack"
+        );
+
+        let status = &enriched.funding_status[0];
+        assert_eq!(status.platform, "Open Collective");
+        assert_eq!(status.currency, "USD");
+        assert_eq!(status.balance, Some(12345.67));
+        assert_eq!(status.yearly_income, Some(320000.0));
+        assert_eq!(
+            status.goals,
+            vec![
+                FundingGoal {
+                    title: Some("Full-time maintainers".to_string()),
+                    amount: 500000.0,
+                    kind: GoalKind::YearlyIncome,
+                },
+                FundingGoal {
+                    title: None,
+                    amount: 10000.0,
+                    kind: GoalKind::Balance,
+                },
+            ]
+        );
+        assert_eq!(status.funding_gap(), Some(180000.0));
+    }
+
+    #[test]
+    fn unknown_collective_has_no_account() {
+        let json = r#"{"data": {"account": null},
+            "errors": [{"message": "No collective found with slug nope"}]}"#;
+        let response: OcResponse = serde_json::from_str(json).unwrap();
+        assert!(response.data.unwrap().account.is_none());
+    }
+
+    #[test]
+    fn keeps_linked_channel() {
+        let mut linked = project();
+        linked.funding.push(FundingChannel::from_url(
+            "https://opencollective.com/Webpack",
+        ));
+        let json = r#"{"slug": "webpack", "currency": "EUR", "settings": null, "stats": null}"#;
+        let account: OcAccount = serde_json::from_str(json).unwrap();
+        let enriched = apply_account(&linked, account);
+
+        assert_eq!(enriched.funding.len(), 1);
+        assert_eq!(enriched.funding_status[0].currency, "EUR");
+        assert_eq!(enriched.funding_status[0].funding_gap(), None);
     }
 }
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! OSV vulnerability enrichment backend.
+//!
+//! Queries the OSV database (osv.dev) for known vulnerabilities affecting the
+//! installed versions of a project's packages, and records how many there
+//! are and how severe the worst one is.
+//!
+//! Only packages from language ecosystems are checked: OSV advisories for
+//! Linux distributions are keyed by distro release, which syld does not
+//! track. Unlike the other backends, this one needs the installed packages
+//! themselves, so it is built per run by [`super::enrich_packages`].
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::discover::{InstalledPackage, PackageSource};
+use crate::project::{Severity, UpstreamProject, VulnerabilitySummary, canonicalize_url};
+
+const QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+pub struct OsvBackend {
+    /// Installed packages to check, keyed by canonical package URL.
+    packages: HashMap<String, Vec<OsvQuery>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct OsvQuery {
+    version: String,
+    package: OsvPackage,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct OsvPackage {
+    name: String,
+    ecosystem: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvResponse {
+    #[serde(default)]
+    vulns: Vec<OsvVuln>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVuln {
+    id: String,
+    database_specific: Option<OsvDatabaseSpecific>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvDatabaseSpecific {
+    severity: Option<String>,
+}
+
+impl OsvBackend {
+    pub fn new(packages: &[InstalledPackage]) -> Self {
+        let mut by_url: HashMap<String, Vec<OsvQuery>> = HashMap::new();
+        for pkg in packages {
+            let (Some(url), Some(ecosystem)) = (&pkg.url, osv_ecosystem(&pkg.source)) else {
+                continue;
+            };
+            if pkg.version.is_empty() || pkg.version == "unknown" {
+                continue;
+            }
+            let query = OsvQuery {
+                version: pkg.version.clone(),
+                package: OsvPackage {
+                    name: pkg.name.clone(),
+                    ecosystem,
+                },
+            };
+            let queries = by_url.entry(canonicalize_url(url)).or_default();
+            if !queries.contains(&query) {
+                queries.push(query);
+            }
+        }
+        Self { packages: by_url }
+    }
+}
+
+impl EnrichmentBackend for OsvBackend {
+    fn name(&self) -> &str {
+        "osv"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.packages.is_empty()
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        // Looked up by the URL the packages were seeded with, so this backend
+        // must run before any other may replace `repo_url`.
+        let queries = match project
+            .repo_url
+            .as_deref()
+            .and_then(|url| self.packages.get(&canonicalize_url(url)))
+        {
+            Some(queries) => queries,
+            None => return Ok(project.clone()),
+        };
+
+        let client = net::client();
+
+        let mut vulns = Vec::new();
+        for query in queries {
+            let response: OsvResponse = client
+                .post(QUERY_URL)
+                .json(query)
+                .send_paced()
+                .and_then(|r| r.error_for_status())
+                .with_context(|| format!("Failed to query OSV for {}", query.package.name))?
+                .json()
+                .context("Failed to parse OSV response")?;
+            vulns.extend(response.vulns);
+        }
+
+        let mut enriched = project.clone();
+        enriched.vulnerabilities = summarize(&vulns);
+        Ok(enriched)
+    }
+}
+
+/// The OSV ecosystem name for packages from `source`, if OSV can match them
+/// by name and version alone.
+fn osv_ecosystem(source: &PackageSource) -> Option<&'static str> {
+    match source {
+        PackageSource::Npm | PackageSource::Pnpm | PackageSource::Yarn | PackageSource::Bun => {
+            Some("npm")
+        }
+        PackageSource::Pip
+        | PackageSource::Pipx
+        | PackageSource::Uv
+        | PackageSource::PythonLockfile => Some("PyPI"),
+        PackageSource::Cargo => Some("crates.io"),
+        PackageSource::Go => Some("Go"),
+        PackageSource::Gem => Some("RubyGems"),
+        PackageSource::Composer => Some("Packagist"),
+        PackageSource::Dotnet => Some("NuGet"),
+        PackageSource::GhcPkg => Some("Hackage"),
+        _ => None,
+    }
+}
+
+/// Summarize the vulnerabilities found across a project's packages, counting
+/// each advisory once. Returns `None` when there are none.
+fn summarize(vulns: &[OsvVuln]) -> Option<VulnerabilitySummary> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut max_severity = None;
+
+    for vuln in vulns {
+        if ids.contains(&vuln.id) {
+            continue;
+        }
+        ids.push(vuln.id.clone());
+
+        let severity = vuln
+            .database_specific
+            .as_ref()
+            .and_then(|d| d.severity.as_deref())
+            .and_then(parse_severity);
+        max_severity = max_severity.max(severity);
+    }
+
+    if ids.is_empty() {
+        return None;
+    }
+    ids.sort();
+
+    Some(VulnerabilitySummary {
+        count: ids.len() as u64,
+        max_severity,
+        ids,
+    })
+}
+
+/// Parse an advisory's severity rating (GitHub advisories use `LOW`,
+/// `MODERATE`, `HIGH`, and `CRITICAL`).
+fn parse_severity(s: &str) -> Option<Severity> {
+    match s.to_ascii_uppercase().as_str() {
+        "LOW" => Some(Severity::Low),
+        "MODERATE" | "MEDIUM" => Some(Severity::Moderate),
+        "HIGH" => Some(Severity::High),
+        "CRITICAL" => Some(Severity::Critical),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discover::InstallReason;
+
+    fn package(name: &str, version: &str, source: PackageSource, url: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: None,
+            url: Some(url.to_string()),
+            source,
+            licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
+        }
+    }
+
+    #[test]
+    fn groups_queries_by_project_url() {
+        let backend = OsvBackend::new(&[
+            package(
+                "requests",
+                "2.31.0",
+                PackageSource::Pip,
+                "https://github.com/psf/requests",
+            ),
+            package(
+                "requests",
+                "2.31.0",
+                PackageSource::Pipx,
+                "https://github.com/psf/requests/",
+            ),
+            package("bash", "5.2", PackageSource::Pacman, "https://gnu.org/bash"),
+            package(
+                "left-pad",
+                "unknown",
+                PackageSource::Npm,
+                "https://github.com/left-pad/left-pad",
+            ),
+        ]);
+
+        assert_eq!(backend.packages.len(), 1);
+        let queries = &backend.packages[&canonicalize_url("https://github.com/psf/requests")];
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].package.ecosystem, "PyPI");
+        assert_eq!(
+            serde_json::to_value(&queries[0]).unwrap(),
+            serde_json::json!({
+                "version": "2.31.0",
+                "package": {"name": "requests", "ecosystem": "PyPI"}
+            })
+        );
+    }
+
+    #[test]
+    fn summarizes_distinct_vulnerabilities() {
+        let json = r#"{"vulns": [
+            {"id": "GHSA-j8r2-6x86-q33q", "database_specific": {"severity": "MODERATE"}},
+            {"id": "GHSA-9wx4-h78v-vm56", "database_specific": {"severity": "HIGH"}},
+            {"id": "PYSEC-2023-74"}
+        ]}"#;
+        let response: OsvResponse = serde_json::from_str(json).unwrap();
+        let mut vulns = response.vulns;
+        vulns.push(OsvVuln {
+            id: "GHSA-9wx4-h78v-vm56".to_string(),
+            database_specific: None,
+        });
+
+        let summary = summarize(&vulns).unwrap();
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.max_severity, Some(Severity::High));
+        assert_eq!(
+            summary.ids,
+            vec![
+                "GHSA-9wx4-h78v-vm56",
+                "GHSA-j8r2-6x86-q33q",
+                "PYSEC-2023-74"
+            ]
+        );
+    }
+
+    #[test]
+    fn no_vulnerabilities_yields_none() {
+        let response: OsvResponse = serde_json::from_str("{}").unwrap();
+        assert_eq!(summarize(&response.vulns), None);
+    }
+}
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Patreon enrichment backend.
+//!
+//! Many maintainers link their Patreon page from the project README or
+//! website without declaring it in `FUNDING.yml`. This backend scans the
+//! README of GitHub-hosted projects and the project homepage for Patreon
+//! links, and normalizes the first creator page found into a funding channel.
+
+use anyhow::Result;
+use reqwest::blocking::Client;
+
+use super::EnrichmentBackend;
+use super::net;
+use crate::contribute::github_good_first_issues::extract_github_owner_repo;
+use crate::project::{FundingChannel, UpstreamProject};
+
+/// README file names tried, in order, on the default branch.
+const README_NAMES: &[&str] = &["README.md", "README.rst", "README.txt", "README"];
+
+/// First path segments of patreon.com URLs that are not creator pages.
+const RESERVED_PATHS: &[&str] = &[
+    "about", "apps", "c", "creators", "explore", "home", "join", "login", "m", "messages",
+    "policy", "posts", "pricing", "product", "search", "settings", "signup",
+];
+
+pub struct PatreonBackend;
+
+impl EnrichmentBackend for PatreonBackend {
+    fn name(&self) -> &str {
+        "patreon"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        // Skip if we already have a Patreon funding channel
+        if project.funding.iter().any(|f| f.platform == "Patreon") {
+            return Ok(project.clone());
+        }
+
+        let client = net::client();
+
+        let readme = project
+            .repo_url
+            .as_deref()
+            .and_then(extract_github_owner_repo)
+            .and_then(|owner_repo| fetch_readme(client, &owner_repo));
+        let homepage = project
+            .homepage
+            .as_deref()
+            .and_then(|url| net::fetch_text(client, url));
+
+        let mut enriched = project.clone();
+        if let Some(url) = [readme, homepage]
+            .iter()
+            .flatten()
+            .find_map(|text| find_patreon_url(text))
+        {
+            enriched.funding.push(FundingChannel {
+                platform: "Patreon".to_string(),
+                url,
+                page: None,
+            });
+        }
+
+        Ok(enriched)
+    }
+}
+
+/// Fetch the raw README from the repository's default branch, trying each
+/// of [`README_NAMES`] in turn.
+fn fetch_readme(client: &Client, owner_repo: &str) -> Option<String> {
+    README_NAMES.iter().find_map(|name| {
+        net::fetch_text(
+            client,
+            &format!("https://raw.githubusercontent.com/{owner_repo}/HEAD/{name}"),
+        )
+    })
+}
+
+/// Find the first Patreon creator page linked from README or HTML text.
+fn find_patreon_url(text: &str) -> Option<String> {
+    text.match_indices("http").find_map(|(start, _)| {
+        let rest = &text[start..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"' | '\'' | '`'))
+            .unwrap_or(rest.len());
+        normalize_patreon_url(rest[..end].trim_end_matches(['.', ',', ';', ':']))
+    })
+}
+
+/// Normalize a link to a Patreon creator page to its canonical form,
+/// `https://www.patreon.com/<name>` (or `https://www.patreon.com/user?u=<id>`
+/// for creators without a vanity name).
+///
+/// Returns `None` for links to other hosts and to pages of patreon.com that
+/// do not identify a creator (posts, login, ...).
+fn normalize_patreon_url(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    if !matches!(
+        host.to_lowercase().as_str(),
+        "patreon.com" | "www.patreon.com"
+    ) {
+        return None;
+    }
+
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let first = segments.next()?.to_lowercase();
+
+    if first == "user" || first == "bepatron" {
+        let id = query
+            .split(['&', ';'])
+            .find_map(|param| param.strip_prefix("u="))
+            .filter(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))?;
+        return Some(format!("https://www.patreon.com/user?u={id}"));
+    }
+
+    // `/c/<name>` and `/join/<name>` are alternate forms of a creator page.
+    let name = if first == "c" || first == "join" {
+        segments.next()?.to_lowercase()
+    } else {
+        first
+    };
+    if RESERVED_PATHS.contains(&name.as_str())
+        || !name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+    {
+        return None;
+    }
+
+    Some(format!("https://www.patreon.com/{name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_creator_pages() {
+        let cases = [
+            (
+                "https://www.patreon.com/Blender",
+                "https://www.patreon.com/blender",
+            ),
+            (
+                "http://patreon.com/blender/",
+                "https://www.patreon.com/blender",
+            ),
+            (
+                "https://www.patreon.com/c/blender/posts",
+                "https://www.patreon.com/blender",
+            ),
+            (
+                "https://www.patreon.com/join/blender?",
+                "https://www.patreon.com/blender",
+            ),
+            (
+                "https://www.patreon.com/user?u=12345&utm_source=x",
+                "https://www.patreon.com/user?u=12345",
+            ),
+            (
+                "https://www.patreon.com/bePatron?u=12345",
+                "https://www.patreon.com/user?u=12345",
+            ),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(
+                normalize_patreon_url(url).as_deref(),
+                Some(expected),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_creator_links() {
+        for url in [
+            "https://www.patreon.com/",
+            "https://www.patreon.com/posts/release-notes-123",
+            "https://www.patreon.com/login",
+            "https://www.patreon.com/user?u=abc",
+            "https://notpatreon.com/blender",
+            "https://github.com/sponsors/blender",
+        ] {
+            assert_eq!(normalize_patreon_url(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn finds_first_patreon_link() {
+        let readme = "\
+# Project
+
+Read the [announcement](https://www.patreon.com/posts/v2-released-42).
+Support development on [Patreon](https://www.patreon.com/Project).
+";
+        assert_eq!(
+            find_patreon_url(readme).as_deref(),
+            Some("https://www.patreon.com/project")
+        );
+
+        let html =
+            r#"<a class="btn" href="https://patreon.com/project?utm_source=site">Donate</a>"#;
+        assert_eq!(
+            find_patreon_url(html).as_deref(),
+            Some("https://www.patreon.com/project")
+        );
+
+        assert_eq!(
+            find_patreon_url("No funding here: https://example.org"),
+            None
+        );
+    }
+}
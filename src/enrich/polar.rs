@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Polar enrichment backend.
+//!
+//! Checks whether the GitHub organization or user owning a project has a
+//! Polar page (issue funding, subscriptions) by querying the public Polar
+//! API. Adds a funding channel if found. Funded issues themselves are
+//! surfaced by [`crate::contribute::polar_funded_issues`].
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::contribute::github_good_first_issues::extract_github_owner_repo;
+use crate::project::{FundingChannel, UpstreamProject};
+
+/// Base URL of the public Polar API.
+pub(crate) const API_BASE: &str = "https://api.polar.sh/v1";
+
+pub struct PolarBackend;
+
+/// A page of organizations from `GET /organizations/`.
+#[derive(Debug, Deserialize)]
+struct PolarOrganizations {
+    #[serde(default)]
+    items: Vec<PolarOrganization>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolarOrganization {
+    slug: String,
+}
+
+impl EnrichmentBackend for PolarBackend {
+    fn name(&self) -> &str {
+        "polar"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        // Skip if we already have a Polar funding channel (e.g. from FUNDING.yml)
+        if project.funding.iter().any(|f| f.platform == "Polar") {
+            return Ok(project.clone());
+        }
+
+        let owner_repo = match project
+            .repo_url
+            .as_deref()
+            .and_then(extract_github_owner_repo)
+        {
+            Some(or) => or,
+            None => return Ok(project.clone()),
+        };
+        let owner = owner_repo.split('/').next().unwrap_or_default();
+
+        let client = net::client();
+
+        let response = client
+            .get(format!("{API_BASE}/organizations/"))
+            .query(&[("slug", owner)])
+            .send_paced()
+            .context("Failed to query Polar")?;
+
+        if !response.status().is_success() {
+            return Ok(project.clone());
+        }
+
+        let organizations: PolarOrganizations =
+            response.json().context("Failed to parse Polar response")?;
+
+        Ok(apply_organizations(project, owner, &organizations))
+    }
+}
+
+/// Add the Polar page of the organization whose slug matches `owner`.
+///
+/// Polar slugs are derived from, but not bound to, GitHub account names, so
+/// an organization is only used if its slug matches the owner exactly.
+fn apply_organizations(
+    project: &UpstreamProject,
+    owner: &str,
+    organizations: &PolarOrganizations,
+) -> UpstreamProject {
+    let mut enriched = project.clone();
+    if let Some(org) = organizations
+        .items
+        .iter()
+        .find(|o| o.slug.eq_ignore_ascii_case(owner))
+    {
+        enriched.funding.push(FundingChannel {
+            platform: "Polar".to_string(),
+            url: format!("https://polar.sh/{}", org.slug),
+            page: None,
+        });
+    }
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> UpstreamProject {
+        UpstreamProject {
+            name: "ruff".to_string(),
+            repo_url: Some("https://github.com/astral-sh/ruff".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn adds_matching_organization() {
+        let json = r#"{
+            "items": [{"id": "2f0c4a6e", "slug": "astral-sh", "name": "Astral"}],
+            "pagination": {"total_count": 1, "max_page": 1}
+        }"#;
+        let organizations: PolarOrganizations = serde_json::from_str(json).unwrap();
+        let enriched = apply_organizations(&project(), "Astral-sh", &organizations);
+
+        assert_eq!(enriched.funding.len(), 1);
+        assert_eq!(enriched.funding[0].platform, "Polar");
+        assert_eq!(enriched.funding[0].url, "https://polar.sh/astral-sh");
+    }
+
+    #[test]
+    fn ignores_other_organizations() {
+        let json = r#"{"items": [{"slug": "astral"}]}"#;
+        let organizations: PolarOrganizations = serde_json::from_str(json).unwrap();
+        let enriched = apply_organizations(&project(), "astral-sh", &organizations);
+        assert!(enriched.funding.is_empty());
+
+        let empty: PolarOrganizations = serde_json::from_str("{}").unwrap();
+        assert!(
+            apply_organizations(&project(), "astral-sh", &empty)
+                .funding
+                .is_empty()
+        );
+    }
+}
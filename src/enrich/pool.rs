@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Concurrency limits for the enrichment engine.
+//!
+//! [`super::enrich_packages`] enriches up to [`WORKERS`] projects at once,
+//! each running through the backends in order. A [`Limiter`] per backend
+//! caps how many of those projects are inside that backend at the same time
+//! (see [`super::EnrichmentBackend::max_concurrency`]), so that a slow or
+//! strict API is not flooded while local backends run freely.
+
+use std::sync::{Condvar, Mutex};
+
+/// Number of projects enriched at the same time.
+pub const WORKERS: usize = 8;
+
+/// Default number of projects a backend enriches at the same time.
+pub const DEFAULT_BACKEND_CONCURRENCY: usize = 4;
+
+/// A counting semaphore.
+pub struct Limiter {
+    permits: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Returns its permit to the [`Limiter`] when dropped.
+struct Permit<'a>(&'a Limiter);
+
+impl Limiter {
+    /// A limiter letting `permits` callers (at least one) in at a time.
+    pub fn new(permits: usize) -> Self {
+        Self {
+            permits: Mutex::new(permits.max(1)),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Run `f` once a permit is available, blocking until then.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _permit = self.acquire();
+        f()
+    }
+
+    fn acquire(&self) -> Permit<'_> {
+        let mut permits = self.permits.lock().unwrap_or_else(|e| e.into_inner());
+        while *permits == 0 {
+            permits = self
+                .released
+                .wait(permits)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        *permits -= 1;
+        Permit(self)
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        *self.0.permits.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+        self.0.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn never_exceeds_permits() {
+        let limiter = Limiter::new(2);
+        let inside = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    limiter.run(|| {
+                        let now = inside.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(10));
+                        inside.fetch_sub(1, Ordering::SeqCst);
+                    })
+                });
+            }
+        });
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn zero_permits_still_admits_one() {
+        assert_eq!(Limiter::new(0).run(|| 42), 42);
+    }
+}
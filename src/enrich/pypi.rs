@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! PyPI enrichment backend.
+//!
+//! Python packages that declare no upstream URL are reported under their PyPI
+//! project page. This backend looks those up in the PyPI JSON API and resolves
+//! the source repository, documentation, and funding links from the
+//! project's `project_urls`.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use super::net::{self, RequestExt};
+use super::{EnrichmentBackend, is_code_host};
+use crate::project::{FundingChannel, UpstreamProject};
+
+pub struct PypiBackend;
+
+/// `project_urls` labels that point at the source repository, in order of
+/// preference.
+const SOURCE_LABELS: &[&str] = &["source", "source code", "repository", "code", "github"];
+
+/// `project_urls` labels that point at the documentation.
+const DOCS_LABELS: &[&str] = &["documentation", "docs"];
+
+/// `project_urls` labels that point at a donation page.
+const FUNDING_LABELS: &[&str] = &["funding", "sponsor", "sponsors", "donate", "donation"];
+
+#[derive(Debug, Deserialize)]
+struct PypiResponse {
+    info: PypiInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiInfo {
+    home_page: Option<String>,
+    docs_url: Option<String>,
+    project_urls: Option<HashMap<String, String>>,
+}
+
+impl EnrichmentBackend for PypiBackend {
+    fn name(&self) -> &str {
+        "pypi"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let name = match project.repo_url.as_deref().and_then(extract_pypi_name) {
+            Some(name) => name,
+            None => return Ok(project.clone()),
+        };
+
+        let client = net::client();
+
+        let response = client
+            .get(format!("https://pypi.org/pypi/{name}/json"))
+            .send_paced();
+
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                let info = resp.json::<PypiResponse>()?.info;
+                Ok(apply_info(project, info))
+            }
+            _ => Ok(project.clone()),
+        }
+    }
+}
+
+/// Extract the distribution name from a PyPI project page URL such as
+/// `https://pypi.org/project/requests/` (optionally followed by a version).
+pub fn extract_pypi_name(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let path = rest
+        .strip_prefix("pypi.org/project/")
+        .or_else(|| rest.strip_prefix("pypi.python.org/pypi/"))?;
+    path.split('/').next().filter(|name| !name.is_empty())
+}
+
+/// Fill a project in from its PyPI metadata.
+///
+/// The PyPI page is replaced as `repo_url` once the source repository is
+/// known, so that code-host backends (GitHub, Bitbucket) can pick it up.
+fn apply_info(project: &UpstreamProject, info: PypiInfo) -> UpstreamProject {
+    let mut enriched = project.clone();
+    let urls: Vec<(String, String)> = info
+        .project_urls
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, url)| !url.is_empty())
+        .collect();
+    let by_labels = |labels: &[&str]| {
+        labels.iter().find_map(|label| {
+            urls.iter()
+                .find(|(l, _)| l.trim().eq_ignore_ascii_case(label))
+                .map(|(_, url)| url.clone())
+        })
+    };
+
+    let home_page = info.home_page.filter(|url| !url.is_empty());
+    let home_page = home_page.or_else(|| by_labels(&["homepage", "home"]));
+
+    let source =
+        by_labels(SOURCE_LABELS).or_else(|| home_page.clone().filter(|url| is_code_host(url)));
+    if let Some(source) = source {
+        enriched.repo_url = Some(source);
+    }
+
+    if enriched.homepage.is_none() {
+        enriched.homepage = home_page;
+    }
+
+    if enriched.documentation_url.is_none() {
+        enriched.documentation_url =
+            by_labels(DOCS_LABELS).or_else(|| info.docs_url.filter(|url| !url.is_empty()));
+    }
+
+    let mut funding: Vec<&(String, String)> = urls
+        .iter()
+        .filter(|(label, _)| {
+            FUNDING_LABELS
+                .iter()
+                .any(|f| label.trim().eq_ignore_ascii_case(f))
+        })
+        .collect();
+    // project_urls is unordered; keep the output stable.
+    funding.sort();
+    for (_, url) in funding {
+        if !enriched.funding.iter().any(|f| &f.url == url) {
+            enriched.funding.push(FundingChannel::from_url(url));
+        }
+    }
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pypi_project(name: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: name.to_string(),
+            repo_url: Some(format!("https://pypi.org/project/{name}/")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn extract_name_from_project_pages() {
+        assert_eq!(
+            extract_pypi_name("https://pypi.org/project/requests/"),
+            Some("requests")
+        );
+        assert_eq!(
+            extract_pypi_name("https://pypi.org/project/yt-dlp/2024.8.6/"),
+            Some("yt-dlp")
+        );
+        assert_eq!(
+            extract_pypi_name("https://pypi.python.org/pypi/six"),
+            Some("six")
+        );
+        assert_eq!(extract_pypi_name("https://github.com/psf/requests"), None);
+        assert_eq!(extract_pypi_name("https://pypi.org/project/"), None);
+    }
+
+    #[test]
+    fn apply_project_urls() {
+        let json = r#"{"info": {
+            "home_page": "",
+            "docs_url": null,
+            "project_urls": {
+                "Documentation": "https://requests.readthedocs.io",
+                "Homepage": "https://requests.readthedocs.io",
+                "Source": "https://github.com/psf/requests",
+                "Funding": "https://opencollective.com/requests",
+                "Sponsor": "https://github.com/sponsors/psf"
+            }
+        }}"#;
+        let info = serde_json::from_str::<PypiResponse>(json).unwrap().info;
+        let enriched = apply_info(&pypi_project("requests"), info);
+
+        assert_eq!(
+            enriched.repo_url.as_deref(),
+            Some("https://github.com/psf/requests")
+        );
+        assert_eq!(
+            enriched.homepage.as_deref(),
+            Some("https://requests.readthedocs.io")
+        );
+        assert_eq!(
+            enriched.documentation_url.as_deref(),
+            Some("https://requests.readthedocs.io")
+        );
+        let funding: Vec<_> = enriched
+            .funding
+            .iter()
+            .map(|f| (f.platform.as_str(), f.url.as_str()))
+            .collect();
+        assert_eq!(
+            funding,
+            vec![
+                ("Open Collective", "https://opencollective.com/requests"),
+                ("GitHub Sponsors", "https://github.com/sponsors/psf"),
+            ]
+        );
+    }
+
+    #[test]
+    fn code_host_home_page_becomes_repo() {
+        let json = r#"{"info": {
+            "home_page": "https://github.com/benjaminp/six",
+            "docs_url": "https://pythonhosted.org/six/",
+            "project_urls": null
+        }}"#;
+        let info = serde_json::from_str::<PypiResponse>(json).unwrap().info;
+        let enriched = apply_info(&pypi_project("six"), info);
+
+        assert_eq!(
+            enriched.repo_url.as_deref(),
+            Some("https://github.com/benjaminp/six")
+        );
+        assert_eq!(
+            enriched.documentation_url.as_deref(),
+            Some("https://pythonhosted.org/six/")
+        );
+        assert!(enriched.funding.is_empty());
+    }
+}
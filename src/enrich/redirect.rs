@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Redirect resolution for package URLs.
+//!
+//! Renamed repositories and moved homepages keep redirecting from their old
+//! URL, which package metadata often still carries. Following the redirect
+//! lets the old and new spellings group as one project. Read the Docs sites
+//! are resolved to the repository the documentation is built from, as
+//! reported by the Read the Docs API.
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use super::net::RequestExt;
+use crate::project::canonicalize_url;
+
+const READTHEDOCS_API: &str = "https://readthedocs.org/api/v2/project/";
+
+/// `GET /api/v2/project/?slug=<slug>`.
+#[derive(Debug, Deserialize)]
+struct RtdProjectList {
+    #[serde(default)]
+    results: Vec<RtdProject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RtdProject {
+    slug: String,
+    repo: Option<String>,
+}
+
+/// Where `url` actually leads: the repository of a Read the Docs site, or the
+/// destination of its redirects. `None` when it leads nowhere else (after
+/// canonicalization) or cannot be reached.
+pub(crate) fn resolve(client: &Client, url: &str) -> Result<Option<String>> {
+    let canonical = canonicalize_url(url);
+    if let Some(slug) = canonical.strip_suffix(".readthedocs.io") {
+        return readthedocs_repo(client, slug);
+    }
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Ok(None);
+    }
+
+    // Some servers refuse HEAD requests, so fall back to GET.
+    let mut response = client
+        .head(url)
+        .send_paced()
+        .with_context(|| format!("Failed to request {url}"))?;
+    if matches!(
+        response.status(),
+        StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+    ) {
+        response = client
+            .get(url)
+            .send_paced()
+            .with_context(|| format!("Failed to request {url}"))?;
+    }
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+
+    let destination = response.url().as_str();
+    Ok((canonicalize_url(destination) != canonical).then(|| destination.to_string()))
+}
+
+/// The repository that the Read the Docs project `slug` builds from.
+fn readthedocs_repo(client: &Client, slug: &str) -> Result<Option<String>> {
+    let response = client
+        .get(READTHEDOCS_API)
+        .query(&[("slug", slug)])
+        .send_paced()
+        .context("Failed to query Read the Docs API")?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+    let list: RtdProjectList = response
+        .json()
+        .context("Failed to parse Read the Docs API response")?;
+    Ok(repo_of(list, slug))
+}
+
+fn repo_of(list: RtdProjectList, slug: &str) -> Option<String> {
+    list.results
+        .into_iter()
+        .find(|p| p.slug == slug)?
+        .repo
+        .filter(|repo| repo.starts_with("https://") || repo.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readthedocs_project_repo() {
+        let json = r#"{"count": 1, "results": [
+            {"id": 1, "name": "Requests", "slug": "requests",
+             "repo": "https://github.com/psf/requests", "repo_type": "git"}
+        ]}"#;
+        let list: RtdProjectList = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            repo_of(list, "requests").as_deref(),
+            Some("https://github.com/psf/requests")
+        );
+
+        let json = r#"{"count": 1, "results": [
+            {"slug": "private", "repo": "git@example.org:team/private.git"}
+        ]}"#;
+        let list: RtdProjectList = serde_json::from_str(json).unwrap();
+        assert_eq!(repo_of(list, "private"), None);
+    }
+}
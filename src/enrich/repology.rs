@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Repology enrichment backend.
+//!
+//! Resolves distro packages whose metadata carries no URL to their upstream
+//! project by name, using the Repology API. Repology aggregates the homepage
+//! links recorded by hundreds of repositories, so a package that one distro
+//! ships without a URL usually has one elsewhere.
+//!
+//! Repology asks API clients to send at most one request per second, which
+//! the shared network layer ([`super::net`]) sees to.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::net::{self, RequestExt};
+use super::{EnrichmentBackend, is_code_host};
+use crate::discover::PackageSource;
+use crate::project::UpstreamProject;
+
+pub struct RepologyBackend;
+
+/// One repository's package of a project, from `/api/v1/project/<name>`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RepologyPackage {
+    /// Repository, e.g. `arch` or `debian_12`.
+    pub repo: String,
+    srcname: Option<String>,
+    binname: Option<String>,
+    visiblename: Option<String>,
+    #[serde(default)]
+    pub version: String,
+    /// `newest`, `outdated`, `legacy`, ... relative to other repositories.
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    www: Vec<String>,
+}
+
+impl RepologyPackage {
+    /// Whether the package is called `name` (lowercase) under any of its
+    /// names.
+    pub fn is_named(&self, name: &str) -> bool {
+        [&self.binname, &self.srcname, &self.visiblename]
+            .into_iter()
+            .flatten()
+            .any(|n| n.to_lowercase() == name)
+    }
+}
+
+impl EnrichmentBackend for RepologyBackend {
+    fn name(&self) -> &str {
+        "repology"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        if project.repo_url.is_some() {
+            return Ok(project.clone());
+        }
+
+        let packages = fetch_project(&project.name.to_lowercase())?;
+        Ok(apply_packages(project, &packages))
+    }
+}
+
+/// The packages of the Repology project `name`, none when it is unknown.
+pub(crate) fn fetch_project(name: &str) -> Result<Vec<RepologyPackage>> {
+    let response = net::client()
+        .get(format!("https://repology.org/api/v1/project/{name}"))
+        .send_paced()
+        .context("Failed to query Repology")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Repology returned {}", response.status());
+    }
+
+    response.json().context("Failed to parse Repology response")
+}
+
+/// Whether packages from `source` are named like distro packages, so that a
+/// Repology lookup by name is meaningful.
+pub fn covers(source: &PackageSource) -> bool {
+    matches!(
+        source,
+        PackageSource::Pacman
+            | PackageSource::Aur
+            | PackageSource::Apt
+            | PackageSource::Dnf
+            | PackageSource::Apk
+            | PackageSource::Eopkg
+            | PackageSource::FreeBsdPkg
+            | PackageSource::Msys2
+            | PackageSource::Termux
+            | PackageSource::Nix
+            | PackageSource::Brew
+            | PackageSource::Scoop
+            | PackageSource::Choco
+    )
+}
+
+/// Fill in the project's homepage and repository from Repology packages.
+///
+/// Repology project names do not always match package names, so the result
+/// is only used if one of the packages is actually called `project.name`.
+/// The most widely recorded URL wins; a URL on a code host is also used as
+/// the repository.
+fn apply_packages(project: &UpstreamProject, packages: &[RepologyPackage]) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    let name = project.name.to_lowercase();
+    if !packages.iter().any(|p| p.is_named(&name)) {
+        return enriched;
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for pkg in packages {
+        // Count each URL once per package, however often it repeats there.
+        let mut seen: Vec<&str> = Vec::new();
+        for url in &pkg.www {
+            let url = url.as_str();
+            if !url.is_empty() && !seen.contains(&url) {
+                seen.push(url);
+                *counts.entry(url).or_default() += 1;
+            }
+        }
+    }
+
+    let mut ranked: Vec<(&str, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+    if enriched.homepage.is_none() {
+        enriched.homepage = ranked.first().map(|(url, _)| url.to_string());
+    }
+    if let Some((url, _)) = ranked.iter().find(|(url, _)| is_code_host(url)) {
+        enriched.repo_url = Some(url.to_string());
+    }
+
+    enriched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> UpstreamProject {
+        UpstreamProject {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn parse(json: &str) -> Vec<RepologyPackage> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn picks_most_common_urls() {
+        let packages = parse(
+            r#"[
+                {"repo": "arch", "srcname": "zstd", "binname": "zstd", "visiblename": "zstd",
+                 "www": ["https://facebook.github.io/zstd/"]},
+                {"repo": "debian_12", "srcname": "libzstd", "binname": "zstd", "visiblename": "libzstd",
+                 "www": ["https://github.com/facebook/zstd", "https://github.com/facebook/zstd"]},
+                {"repo": "fedora_40", "srcname": "zstd", "binname": "zstd",
+                 "www": ["https://github.com/facebook/zstd"]}
+            ]"#,
+        );
+        let enriched = apply_packages(&project("zstd"), &packages);
+        assert_eq!(
+            enriched.homepage.as_deref(),
+            Some("https://github.com/facebook/zstd")
+        );
+        assert_eq!(
+            enriched.repo_url.as_deref(),
+            Some("https://github.com/facebook/zstd")
+        );
+    }
+
+    #[test]
+    fn homepage_only_when_no_code_host() {
+        let packages = parse(
+            r#"[{"repo": "arch", "srcname": "bash", "binname": "bash",
+                 "www": ["https://www.gnu.org/software/bash/"]}]"#,
+        );
+        let enriched = apply_packages(&project("bash"), &packages);
+        assert_eq!(
+            enriched.homepage.as_deref(),
+            Some("https://www.gnu.org/software/bash/")
+        );
+        assert_eq!(enriched.repo_url, None);
+    }
+
+    #[test]
+    fn ignores_projects_without_matching_package() {
+        let packages = parse(
+            r#"[{"repo": "arch", "srcname": "python-portal", "binname": "python-portal",
+                 "www": ["https://github.com/example/portal"]}]"#,
+        );
+        let enriched = apply_packages(&project("portal"), &packages);
+        assert_eq!(enriched.homepage, None);
+        assert_eq!(enriched.repo_url, None);
+    }
+
+    #[test]
+    fn covers_only_distro_sources() {
+        assert!(covers(&PackageSource::Pacman));
+        assert!(covers(&PackageSource::Apt));
+        assert!(!covers(&PackageSource::Npm));
+        assert!(!covers(&PackageSource::Game));
+    }
+}
@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Translation platform enrichment backend.
+//!
+//! Finds where a project is translated, so that users who speak another
+//! language can help. The Weblate instance named in a `.weblate` file at the
+//! root of the repository wins; otherwise the README (on GitHub and GitLab)
+//! and the homepage are scanned for Weblate, Crowdin, or Transifex links,
+//! which projects usually publish as status badges. As a last resort, Hosted
+//! Weblate is asked for a project named after the package whose website
+//! matches.
+
+use anyhow::Result;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use super::net::{self, RequestExt};
+use super::{EnrichmentBackend, raw_file_base};
+use crate::project::{UpstreamProject, canonicalize_url};
+
+/// README file names tried, in order, on the default branch.
+const README_NAMES: &[&str] = &["README.md", "README.rst", "README.txt", "README"];
+
+const HOSTED_WEBLATE: &str = "https://hosted.weblate.org";
+
+/// Weblate instances run by distributions and foundations, whose host name
+/// does not mention Weblate.
+const WEBLATE_HOSTS: &[&str] = &[
+    "translate.fedoraproject.org",
+    "translate.codeberg.org",
+    "l10n.opensuse.org",
+    "translations.documentfoundation.org",
+];
+
+/// First path segments of transifex.com URLs that are not organizations.
+const TRANSIFEX_RESERVED: &[&str] = &[
+    "accounts",
+    "api",
+    "blog",
+    "customers",
+    "features",
+    "pricing",
+    "product",
+    "signin",
+    "signup",
+    "user",
+];
+
+pub struct TranslationBackend;
+
+/// The subset of Weblate's `GET /api/projects/:slug/` used for matching.
+#[derive(Debug, Deserialize)]
+struct WeblateProject {
+    /// The project's own website.
+    web: Option<String>,
+    /// The project's page on the Weblate instance.
+    web_url: String,
+}
+
+impl EnrichmentBackend for TranslationBackend {
+    fn name(&self) -> &str {
+        "translation"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        if project.translation_url.is_some() {
+            return Ok(project.clone());
+        }
+
+        let client = net::client();
+        let raw_base = project.repo_url.as_deref().and_then(raw_file_base);
+
+        let from_config = raw_base
+            .as_deref()
+            .and_then(|base| net::fetch_text(client, &format!("{base}/.weblate")))
+            .and_then(|text| parse_weblate_config(&text));
+        let translation_url = from_config
+            .or_else(|| {
+                let readme = raw_base.as_deref().and_then(|base| {
+                    README_NAMES
+                        .iter()
+                        .find_map(|name| net::fetch_text(client, &format!("{base}/{name}")))
+                });
+                let homepage = project
+                    .homepage
+                    .as_deref()
+                    .and_then(|url| net::fetch_text(client, url));
+                [readme, homepage]
+                    .iter()
+                    .flatten()
+                    .find_map(|text| find_translation_url(text))
+            })
+            .or_else(|| lookup_hosted_weblate(client, project));
+
+        let mut enriched = project.clone();
+        enriched.translation_url = translation_url;
+        Ok(enriched)
+    }
+}
+
+/// Ask Hosted Weblate for a project whose slug is the package name, and
+/// accept it only if its website is the project's repository or homepage.
+fn lookup_hosted_weblate(client: &Client, project: &UpstreamProject) -> Option<String> {
+    let slug = project.name.to_lowercase();
+    if !is_slug(&slug) {
+        return None;
+    }
+    let response = client
+        .get(format!("{HOSTED_WEBLATE}/api/projects/{slug}/"))
+        .send_paced()
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let weblate: WeblateProject = response.json().ok()?;
+    weblate_project_matches(&weblate, project).then_some(weblate.web_url)
+}
+
+fn weblate_project_matches(weblate: &WeblateProject, project: &UpstreamProject) -> bool {
+    let Some(web) = weblate.web.as_deref().map(canonicalize_url) else {
+        return false;
+    };
+    let web = web.strip_suffix(".git").unwrap_or(&web);
+    [project.repo_url.as_deref(), project.homepage.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(canonicalize_url)
+        .any(|url| url.strip_suffix(".git").unwrap_or(&url) == web)
+}
+
+/// Read the project page from a `.weblate` file, as written by
+/// `wlc`:
+///
+/// ```ini
+/// [weblate]
+/// url = https://hosted.weblate.org/api/
+/// translation = project/component
+/// ```
+fn parse_weblate_config(text: &str) -> Option<String> {
+    let mut in_section = false;
+    let mut api_url = None;
+    let mut translation = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line == "[weblate]";
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "url" => api_url = Some(value.trim()),
+                "translation" => translation = Some(value.trim()),
+                _ => {}
+            }
+        }
+    }
+
+    let base = api_url?.trim_end_matches('/');
+    let base = base.strip_suffix("/api").unwrap_or(base);
+    let project = translation?.split('/').next().filter(|p| is_slug(p))?;
+    if !(base.starts_with("https://") || base.starts_with("http://")) {
+        return None;
+    }
+    Some(format!("{base}/projects/{project}/"))
+}
+
+/// Find the first translation platform project linked from README or HTML
+/// text.
+fn find_translation_url(text: &str) -> Option<String> {
+    text.match_indices("http").find_map(|(start, _)| {
+        let rest = &text[start..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"' | '\'' | '`'))
+            .unwrap_or(rest.len());
+        normalize_translation_url(rest[..end].trim_end_matches(['.', ',', ';', ':']))
+    })
+}
+
+/// Normalize a link to a project on a translation platform, including
+/// status badge images, to the project's page:
+///
+/// - Weblate: `https://<host>/projects/<project>/`
+/// - Crowdin: `https://crowdin.com/project/<project>`
+/// - Transifex: `https://app.transifex.com/<organization>/<project>/`
+///
+/// Returns `None` for links to other hosts and to pages that do not identify
+/// a project.
+fn normalize_translation_url(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_lowercase();
+    let path = path.split(['?', '#']).next()?;
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match host.as_str() {
+        "crowdin.com" | "www.crowdin.com" => match segments.as_slice() {
+            ["project", name, ..] if is_slug(name) => {
+                Some(format!("https://crowdin.com/project/{name}"))
+            }
+            _ => None,
+        },
+        "badges.crowdin.net" => match segments.as_slice() {
+            [name, ..] if is_slug(name) => Some(format!("https://crowdin.com/project/{name}")),
+            _ => None,
+        },
+        "transifex.com" | "www.transifex.com" | "app.transifex.com" => match segments.as_slice() {
+            ["projects", "p", name, ..] if is_slug(name) => {
+                Some(format!("https://www.transifex.com/projects/p/{name}/"))
+            }
+            [org, name, ..]
+                if !TRANSIFEX_RESERVED.contains(org) && is_slug(org) && is_slug(name) =>
+            {
+                Some(format!("https://app.transifex.com/{org}/{name}/"))
+            }
+            _ => None,
+        },
+        _ if host.contains("weblate") || WEBLATE_HOSTS.contains(&host.as_str()) => {
+            match segments.as_slice() {
+                ["engage" | "projects" | "widgets" | "widget", name, ..] if is_slug(name) => {
+                    Some(format!("https://{host}/projects/{name}/"))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether `s` can be a project slug on a translation platform.
+fn is_slug(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_platform_links() {
+        let cases = [
+            (
+                "https://hosted.weblate.org/engage/gnu-mailman/",
+                Some("https://hosted.weblate.org/projects/gnu-mailman/"),
+            ),
+            (
+                "https://hosted.weblate.org/widgets/tor/-/svg-badge.svg",
+                Some("https://hosted.weblate.org/projects/tor/"),
+            ),
+            (
+                "https://translate.fedoraproject.org/projects/anaconda/master/",
+                Some("https://translate.fedoraproject.org/projects/anaconda/"),
+            ),
+            (
+                "https://crowdin.com/project/joplin?utm_source=badge",
+                Some("https://crowdin.com/project/joplin"),
+            ),
+            (
+                "https://badges.crowdin.net/joplin/localized.svg",
+                Some("https://crowdin.com/project/joplin"),
+            ),
+            (
+                "https://www.transifex.com/otf/torproject/dashboard/",
+                Some("https://app.transifex.com/otf/torproject/"),
+            ),
+            (
+                "https://www.transifex.com/projects/p/qgis/",
+                Some("https://www.transifex.com/projects/p/qgis/"),
+            ),
+            ("https://hosted.weblate.org/", None),
+            ("https://crowdin.com/pricing", None),
+            ("https://www.transifex.com/signin/next", None),
+            ("https://github.com/weblate/weblate", None),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(normalize_translation_url(url).as_deref(), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn finds_badge_in_readme() {
+        let readme = "[![Build](https://github.com/o/r/actions/workflows/ci.yml/badge.svg)](https://github.com/o/r/actions)\n\
+            [![Translation status](https://hosted.weblate.org/widgets/godot-engine/-/svg-badge.svg)](https://hosted.weblate.org/engage/godot-engine/?utm_source=widget)";
+        assert_eq!(
+            find_translation_url(readme).as_deref(),
+            Some("https://hosted.weblate.org/projects/godot-engine/")
+        );
+        assert_eq!(find_translation_url("No translations yet."), None);
+    }
+
+    #[test]
+    fn parses_weblate_config() {
+        let config =
+            "[weblate]\nurl = https://hosted.weblate.org/api/\ntranslation = phpmyadmin/master\n";
+        assert_eq!(
+            parse_weblate_config(config).as_deref(),
+            Some("https://hosted.weblate.org/projects/phpmyadmin/")
+        );
+
+        let other_section = "[other]\nurl = https://example.org/api/\ntranslation = a/b\n";
+        assert_eq!(parse_weblate_config(other_section), None);
+        assert_eq!(
+            parse_weblate_config("[weblate]\nurl = https://hosted.weblate.org/api/\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn weblate_project_must_match_website() {
+        let weblate = WeblateProject {
+            web: Some("https://github.com/owner/tool".to_string()),
+            web_url: "https://hosted.weblate.org/projects/tool/".to_string(),
+        };
+        let mut project = UpstreamProject {
+            name: "tool".to_string(),
+            repo_url: Some("https://github.com/owner/tool.git".to_string()),
+            ..Default::default()
+        };
+        assert!(weblate_project_matches(&weblate, &project));
+
+        project.repo_url = Some("https://github.com/someone-else/tool".to_string());
+        assert!(!weblate_project_matches(&weblate, &project));
+    }
+}
@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Wikidata enrichment backend.
+//!
+//! Looks a project up on the Wikidata Query Service, first by source code
+//! repository (property P1324), then by English label among items that have
+//! a repository. Fills in the official website (P856) and licenses (P275, as
+//! SPDX identifiers where Wikidata has them), and records the developing
+//! organization (P178) and the larger projects it is part of (P361), such as
+//! GNOME or KDE.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::EnrichmentBackend;
+use super::net::{self, RequestExt};
+use crate::project::UpstreamProject;
+
+const SPARQL_URL: &str = "https://query.wikidata.org/sparql";
+
+pub struct WikidataBackend;
+
+/// SPARQL JSON results: one binding per row, keyed by variable name.
+/// Variables left unbound by an `OPTIONAL` are absent.
+#[derive(Debug, Deserialize)]
+struct SparqlResponse {
+    results: SparqlResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct SparqlResults {
+    bindings: Vec<HashMap<String, SparqlValue>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SparqlValue {
+    value: String,
+}
+
+impl EnrichmentBackend for WikidataBackend {
+    fn name(&self) -> &str {
+        "wikidata"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn enrich(&self, project: &UpstreamProject) -> Result<UpstreamProject> {
+        let repo_urls = project
+            .repo_url
+            .as_deref()
+            .map(repo_url_variants)
+            .unwrap_or_default();
+        let query = build_query(&repo_urls, &project.name);
+
+        let response = net::client()
+            .get(SPARQL_URL)
+            .query(&[("query", query.as_str()), ("format", "json")])
+            .timeout(Duration::from_secs(15))
+            .send_paced()
+            .context("Failed to query Wikidata")?;
+        if !response.status().is_success() {
+            return Ok(project.clone());
+        }
+
+        let response: SparqlResponse = response
+            .json()
+            .context("Failed to parse Wikidata response")?;
+        Ok(apply_bindings(project, &response.results.bindings))
+    }
+
+    // The query service allows only a few parallel queries per client.
+    fn max_concurrency(&self) -> usize {
+        2
+    }
+}
+
+/// The spellings a repository URL may have on Wikidata, which stores it
+/// verbatim: with or without a trailing slash or `.git` suffix.
+fn repo_url_variants(url: &str) -> Vec<String> {
+    let base = url.trim().trim_end_matches('/');
+    let base = base.strip_suffix(".git").unwrap_or(base);
+    if !(base.starts_with("https://") || base.starts_with("http://"))
+        || base.contains(|c: char| c.is_whitespace() || "<>\"{}|^`\\".contains(c))
+    {
+        return vec![];
+    }
+    vec![base.to_string(), format!("{base}/"), format!("{base}.git")]
+}
+
+/// Build the lookup query. Rows matched by repository are tagged
+/// `?match = "repo"` so that they win over label matches.
+fn build_query(repo_urls: &[String], name: &str) -> String {
+    let mut branches: Vec<String> = Vec::new();
+    if !repo_urls.is_empty() {
+        let values: Vec<String> = repo_urls.iter().map(|u| format!("<{u}>")).collect();
+        branches.push(format!(
+            "{{ VALUES ?repo {{ {} }} ?item wdt:P1324 ?repo . BIND(\"repo\" AS ?match) }}",
+            values.join(" ")
+        ));
+    }
+    let label = name.replace('\\', "\\\\").replace('"', "\\\"");
+    branches.push(format!(
+        "{{ ?item rdfs:label \"{label}\"@en ; wdt:P1324 [] . BIND(\"name\" AS ?match) }}"
+    ));
+
+    format!(
+        "SELECT ?item ?match ?website ?spdx ?licenseLabel ?developerLabel ?partOfLabel WHERE {{
+  {}
+  OPTIONAL {{ ?item wdt:P856 ?website }}
+  OPTIONAL {{ ?item wdt:P275 ?license . OPTIONAL {{ ?license wdt:P2479 ?spdx }} }}
+  OPTIONAL {{ ?item wdt:P178 ?developer . FILTER NOT EXISTS {{ ?developer wdt:P31 wd:Q5 }} }}
+  OPTIONAL {{ ?item wdt:P361 ?partOf }}
+  SERVICE wikibase:label {{ bd:serviceParam wikibase:language \"en\". }}
+}}
+LIMIT 100",
+        branches.join("\n  UNION\n  ")
+    )
+}
+
+/// Fill in a project from the rows of the best matching item.
+fn apply_bindings(
+    project: &UpstreamProject,
+    bindings: &[HashMap<String, SparqlValue>],
+) -> UpstreamProject {
+    let mut enriched = project.clone();
+
+    let value = |row: &HashMap<String, SparqlValue>, var: &str| {
+        row.get(var)
+            .map(|v| v.value.trim().to_string())
+            // Items without an English label come back as their Q-id.
+            .filter(|v| !v.is_empty() && !is_entity_id(v))
+    };
+    let Some(item) = bindings
+        .iter()
+        .find(|row| value(row, "match").as_deref() == Some("repo"))
+        .or_else(|| bindings.first())
+        .and_then(|row| row.get("item"))
+        .map(|v| v.value.as_str())
+    else {
+        return enriched;
+    };
+    let rows: Vec<_> = bindings
+        .iter()
+        .filter(|row| row.get("item").is_some_and(|v| v.value == item))
+        .collect();
+
+    let mut licenses: Vec<String> = Vec::new();
+    for row in &rows {
+        if enriched.homepage.is_none() {
+            enriched.homepage = value(row, "website");
+        }
+        if let Some(license) = value(row, "spdx").or_else(|| value(row, "licenseLabel"))
+            && !licenses.contains(&license)
+        {
+            licenses.push(license);
+        }
+        if enriched.organization.is_none() {
+            enriched.organization = value(row, "developerLabel");
+        }
+        if let Some(parent) = value(row, "partOfLabel")
+            && !enriched.part_of.contains(&parent)
+        {
+            enriched.part_of.push(parent);
+        }
+    }
+    if enriched.licenses.is_empty() {
+        enriched.licenses = licenses;
+    }
+
+    enriched
+}
+
+/// Whether `s` is a bare Wikidata entity ID such as `Q42`.
+fn is_entity_id(s: &str) -> bool {
+    s.strip_prefix('Q')
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> UpstreamProject {
+        UpstreamProject {
+            name: "krita".to_string(),
+            repo_url: Some("https://invent.kde.org/graphics/krita".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn repo_url_spellings() {
+        assert_eq!(
+            repo_url_variants("https://github.com/curl/curl.git/"),
+            vec![
+                "https://github.com/curl/curl",
+                "https://github.com/curl/curl/",
+                "https://github.com/curl/curl.git",
+            ]
+        );
+        assert!(repo_url_variants("git://example.org/foo").is_empty());
+        assert!(repo_url_variants("https://example.org/a>b").is_empty());
+    }
+
+    #[test]
+    fn query_escapes_name() {
+        let query = build_query(&[], "say \"hi\"");
+        assert!(query.contains(r#"rdfs:label "say \"hi\""@en"#));
+        assert!(!query.contains("VALUES ?repo"));
+
+        let query = build_query(&repo_url_variants("https://github.com/curl/curl"), "curl");
+        assert!(query.contains("VALUES ?repo { <https://github.com/curl/curl> "));
+    }
+
+    #[test]
+    fn applies_repo_match() {
+        let json = r#"{"head": {"vars": []}, "results": {"bindings": [
+            {"item": {"type": "uri", "value": "http://www.wikidata.org/entity/Q999"},
+             "match": {"type": "literal", "value": "name"},
+             "website": {"type": "uri", "value": "https://krita.example/"}},
+            {"item": {"type": "uri", "value": "http://www.wikidata.org/entity/Q288287"},
+             "match": {"type": "literal", "value": "repo"},
+             "website": {"type": "uri", "value": "https://krita.org/"},
+             "spdx": {"type": "literal", "value": "GPL-3.0-or-later"},
+             "licenseLabel": {"type": "literal", "value": "GNU General Public License, version 3.0 or later"},
+             "developerLabel": {"type": "literal", "value": "KDE"},
+             "partOfLabel": {"type": "literal", "value": "KDE Gear"}},
+            {"item": {"type": "uri", "value": "http://www.wikidata.org/entity/Q288287"},
+             "match": {"type": "literal", "value": "repo"},
+             "licenseLabel": {"type": "literal", "value": "Q1234567"},
+             "partOfLabel": {"type": "literal", "value": "Calligra"}}
+        ]}}"#;
+        let response: SparqlResponse = serde_json::from_str(json).unwrap();
+        let enriched = apply_bindings(&project(), &response.results.bindings);
+
+        assert_eq!(enriched.homepage.as_deref(), Some("https://krita.org/"));
+        assert_eq!(enriched.licenses, vec!["GPL-3.0-or-later"]);
+        assert_eq!(enriched.organization.as_deref(), Some("KDE"));
+        assert_eq!(enriched.part_of, vec!["KDE Gear", "Calligra"]);
+    }
+
+    #[test]
+    fn no_match_leaves_project_unchanged() {
+        let enriched = apply_bindings(&project(), &[]);
+        assert_eq!(enriched.homepage, None);
+        assert!(enriched.licenses.is_empty());
+        assert_eq!(enriched.organization, None);
+    }
+}
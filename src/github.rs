@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Minimal client for the GitHub REST and GraphQL APIs.
+//!
+//! Shared by the GitHub enrichment and contribution backends. Requests are
+//! authenticated with the token from the `[github]` config section or the
+//! `GITHUB_TOKEN` environment variable; without one, GitHub's much lower
+//! anonymous rate limit applies.
+//!
+//! Once GitHub reports the rate limit as exhausted, the client stops issuing
+//! requests for the rest of the run instead of failing one call at a time.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::config::Config;
+use crate::enrich::net::RequestExt;
+
+const API_BASE: &str = "https://api.github.com";
+
+/// Environment variable consulted when no token is configured.
+const TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+pub struct GitHubClient {
+    http: Client,
+    token: Option<String>,
+    rate_limited: AtomicBool,
+}
+
+impl GitHubClient {
+    /// Create a client using the configured token, falling back to
+    /// `GITHUB_TOKEN`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let token = resolve_token(
+            config.github.token.as_deref(),
+            std::env::var(TOKEN_ENV).ok(),
+        );
+        Self::new(token)
+    }
+
+    /// Create a client with an explicit token (or none, for anonymous access).
+    pub fn new(token: Option<String>) -> Result<Self> {
+        let http = Client::builder()
+            .user_agent(concat!("syld/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        Ok(Self {
+            http,
+            token,
+            rate_limited: AtomicBool::new(false),
+        })
+    }
+
+    /// GET `path` (relative to the API root, e.g. `repos/owner/repo`) and
+    /// decode the JSON response.
+    ///
+    /// Returns `Ok(None)` when the resource does not exist or is no longer
+    /// available (deleted repos, disabled issue trackers, ...). Access refused
+    /// to the token is an error.
+    pub fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let request = self
+            .http
+            .get(format!("{API_BASE}/{path}"))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        self.send(request, path)
+    }
+
+    /// Whether requests are authenticated. The GraphQL API rejects anonymous
+    /// requests.
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Run a GraphQL query and decode its `data`.
+    ///
+    /// Returns `Ok(None)` without a token, and when the query resolved to
+    /// nothing (e.g. an unknown login), in which case GitHub reports errors
+    /// alongside null data.
+    pub fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<Option<T>> {
+        if !self.has_token() {
+            return Ok(None);
+        }
+
+        let request = self
+            .http
+            .post(format!("{API_BASE}/graphql"))
+            .json(&serde_json::json!({ "query": query, "variables": variables }));
+        let response: Option<GraphQlResponse<T>> = self.send(request, "graphql")?;
+        Ok(response.and_then(|r| r.data))
+    }
+
+    /// Whether the authenticated user has starred `owner_repo`.
+    pub fn is_starred(&self, owner_repo: &str) -> Result<bool> {
+        let path = format!("user/starred/{owner_repo}");
+        let request = self.http.get(format!("{API_BASE}/{path}"));
+        // 204 when starred, 404 when not.
+        Ok(self.execute(request, &path)?.is_some())
+    }
+
+    /// Star `owner_repo` as the authenticated user.
+    pub fn star(&self, owner_repo: &str) -> Result<()> {
+        if !self.has_token() {
+            anyhow::bail!("Starring repositories requires a GitHub token");
+        }
+        let path = format!("user/starred/{owner_repo}");
+        let request = self
+            .http
+            .put(format!("{API_BASE}/{path}"))
+            .header(reqwest::header::CONTENT_LENGTH, 0);
+        match self.execute(request, &path)? {
+            Some(_) => Ok(()),
+            None => anyhow::bail!("GitHub refused to star {owner_repo}"),
+        }
+    }
+
+    /// Send an authenticated request and decode the JSON response, handling
+    /// rate limiting and inaccessible resources the same way for all calls.
+    fn send<T: DeserializeOwned>(&self, request: RequestBuilder, path: &str) -> Result<Option<T>> {
+        let Some(response) = self.execute(request, path)? else {
+            return Ok(None);
+        };
+        let value = response
+            .json()
+            .with_context(|| format!("Failed to parse GitHub API response for {path}"))?;
+        Ok(Some(value))
+    }
+
+    /// Send an authenticated request, returning `Ok(None)` when the resource
+    /// does not exist or is no longer available.
+    fn execute(&self, request: RequestBuilder, path: &str) -> Result<Option<Response>> {
+        if self.rate_limited.load(Ordering::Relaxed) {
+            anyhow::bail!("GitHub API rate limit exceeded");
+        }
+
+        let request = match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+
+        let response = request
+            .send_paced()
+            .with_context(|| format!("Failed to query GitHub API for {path}"))?;
+        let status = response.status();
+
+        if is_rate_limited(&response) {
+            // Only the first request to hit the limit explains it.
+            if !self.rate_limited.swap(true, Ordering::Relaxed) {
+                let hint = if self.token.is_none() {
+                    " (set GITHUB_TOKEN or [github] token in the config for a higher limit)"
+                } else {
+                    ""
+                };
+                eprintln!("Warning: GitHub API rate limit exceeded{hint}");
+            }
+            anyhow::bail!("GitHub API rate limit exceeded");
+        }
+
+        match status {
+            StatusCode::NOT_FOUND
+            | StatusCode::GONE
+            | StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS => {
+                return Ok(None);
+            }
+            StatusCode::FORBIDDEN => {
+                let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok());
+                anyhow::bail!(
+                    "{}",
+                    forbidden_message(
+                        path,
+                        self.has_token(),
+                        header("x-github-sso").is_some(),
+                        header("x-accepted-oauth-scopes"),
+                    )
+                );
+            }
+            s if !s.is_success() => anyhow::bail!("GitHub API returned {s} for {path}"),
+            _ => {}
+        }
+
+        Ok(Some(response))
+    }
+}
+
+/// The envelope of a GraphQL response; `errors` are ignored.
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+}
+
+/// Pick the API token: the config value wins over the environment, and
+/// empty values count as unset.
+fn resolve_token(configured: Option<&str>, env: Option<String>) -> Option<String> {
+    configured
+        .map(str::to_string)
+        .into_iter()
+        .chain(env)
+        .map(|t| t.trim().to_string())
+        .find(|t| !t.is_empty())
+}
+
+/// GitHub signals an exhausted rate limit with 429, or with 403 and
+/// `x-ratelimit-remaining: 0`.
+fn is_rate_limited(response: &Response) -> bool {
+    let remaining = response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .and_then(|v| v.to_str().ok());
+    rate_limit_status(response.status(), remaining)
+}
+
+fn rate_limit_status(status: StatusCode, remaining: Option<&str>) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN && remaining == Some("0"))
+}
+
+/// Why GitHub refused access to `path` other than for the rate limit: no
+/// token, a token not authorized for an organization's SAML single sign-on
+/// (`sso`), or one missing a scope among the `accepted_scopes` GitHub lists.
+fn forbidden_message(
+    path: &str,
+    has_token: bool,
+    sso: bool,
+    accepted_scopes: Option<&str>,
+) -> String {
+    let reason = if !has_token {
+        "it needs a token (set GITHUB_TOKEN or [github] token in the config)".to_string()
+    } else if sso {
+        "the token is not authorized for the organization's SAML single sign-on".to_string()
+    } else {
+        match accepted_scopes.map(str::trim).filter(|s| !s.is_empty()) {
+            Some(scopes) => format!("the token lacks a required scope ({scopes})"),
+            None => "the token lacks the required scope or permission".to_string(),
+        }
+    };
+    format!("GitHub API refused access to {path}: {reason}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_token_takes_precedence() {
+        assert_eq!(
+            resolve_token(Some("from-config"), Some("from-env".to_string())).as_deref(),
+            Some("from-config")
+        );
+        assert_eq!(
+            resolve_token(None, Some("from-env".to_string())).as_deref(),
+            Some("from-env")
+        );
+        assert_eq!(
+            resolve_token(Some(""), Some("from-env".to_string())).as_deref(),
+            Some("from-env")
+        );
+        assert_eq!(resolve_token(None, Some("  ".to_string())), None);
+        assert_eq!(resolve_token(None, None), None);
+    }
+
+    #[test]
+    fn detects_rate_limit_responses() {
+        assert!(rate_limit_status(StatusCode::TOO_MANY_REQUESTS, None));
+        assert!(rate_limit_status(StatusCode::FORBIDDEN, Some("0")));
+        assert!(!rate_limit_status(StatusCode::FORBIDDEN, Some("42")));
+        assert!(!rate_limit_status(StatusCode::FORBIDDEN, None));
+        assert!(!rate_limit_status(StatusCode::OK, Some("0")));
+    }
+
+    #[test]
+    fn explains_refused_access() {
+        let path = "user/starred/curl/curl";
+        assert_eq!(
+            forbidden_message(path, true, false, Some("repo, public_repo")),
+            "GitHub API refused access to user/starred/curl/curl: \
+             the token lacks a required scope (repo, public_repo)"
+        );
+        assert!(forbidden_message(path, true, true, None).contains("single sign-on"));
+        assert!(forbidden_message(path, false, false, None).contains("GITHUB_TOKEN"));
+        assert!(forbidden_message(path, true, false, Some("")).ends_with("scope or permission"));
+    }
+}
@@ -3,8 +3,11 @@
 pub mod budget;
 pub mod config;
 pub mod contribute;
+pub mod digest;
 pub mod discover;
 pub mod enrich;
+pub mod github;
+pub mod mastodon;
 pub mod project;
 pub mod report;
 pub mod storage;
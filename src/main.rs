@@ -2,14 +2,29 @@
 
 use std::env;
 use std::fs;
+use std::io::{self, Write};
 use std::process::Command;
 
 use anyhow::{Context, Result};
+use chrono::Datelike;
 use clap::{Parser, Subcommand};
 
-use syld::config::Config;
+use syld::budget::{self, PlanOverrides};
+use syld::config::{BudgetConfig, Cadence, Config};
+use syld::contribute::github_stars::GitHubStarsBackend;
+use syld::contribute::hacktoberfest::{self, HacktoberfestBackend};
+use syld::contribute::past_contributions::{self, PastContributionsBackend};
+use syld::contribute::skills::SkillFilter;
+use syld::contribute::spread_the_word;
+use syld::contribute::{
+    ContributionBackend, ContributionKind, OpportunityFilter, OpportunityState,
+};
+use syld::digest;
 use syld::discover;
 use syld::enrich::EnrichmentMap;
+use syld::github::GitHubClient;
+use syld::mastodon::MastodonClient;
+use syld::project::{UpstreamProject, canonicalize_url};
 use syld::report::{ContributionMap, html, json, terminal};
 use syld::storage::Storage;
 
@@ -22,6 +37,10 @@ use syld::storage::Storage;
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Never access the network: use cached data and offline sources only
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
 #[derive(Subcommand)]
@@ -31,6 +50,10 @@ enum Commands {
         /// Maximum number of projects to display (0 for all)
         #[arg(long, default_value = "20")]
         limit: usize,
+
+        /// Only record packages added or removed since the previous scan
+        #[arg(long)]
+        incremental: bool,
     },
 
     /// Generate a report from the last scan
@@ -44,12 +67,71 @@ enum Commands {
         enrich: bool,
     },
 
+    /// Find ways to contribute to the projects you use, beyond money
+    Contribute {
+        #[command(subcommand)]
+        command: Option<ContributeCommands>,
+
+        /// Only show one kind of contribution, e.g. good-first-issue
+        #[arg(long)]
+        kind: Option<ContributionKind>,
+
+        /// Maximum number of opportunities to find (0 for all)
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Output format
+        #[arg(long, default_value = "terminal")]
+        format: ReportFormat,
+
+        /// Leave out opportunities you dismissed
+        #[arg(long)]
+        hide_dismissed: bool,
+
+        /// Only suggest issues of repositories taking part in Hacktoberfest,
+        /// with a plan for October
+        #[arg(long)]
+        hacktoberfest: bool,
+    },
+
     /// Manage your support budget
     Budget {
         #[command(subcommand)]
         command: BudgetCommands,
     },
 
+    /// Star the GitHub repositories of the projects you use
+    Star {
+        /// Star every unstarred repository without asking
+        #[arg(long, conflicts_with = "interactive")]
+        all: bool,
+
+        /// Ask before starring each repository
+        #[arg(long)]
+        interactive: bool,
+    },
+
+    /// Deliver the contribution opportunities found since the last digest,
+    /// for running from cron or a systemd timer
+    Digest {
+        /// Print the new opportunities without delivering or remembering them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Compose a post about the open source you depend on
+    Share {
+        /// Publish the post on the Mastodon account from the config
+        #[arg(long)]
+        mastodon: bool,
+    },
+
+    /// Inspect enriched upstream projects
+    Project {
+        #[command(subcommand)]
+        command: ProjectCommands,
+    },
+
     /// Show or edit configuration
     Config {
         #[command(subcommand)]
@@ -74,6 +156,10 @@ enum BudgetCommands {
         /// Budget cadence
         #[arg(long, default_value = "monthly")]
         cadence: BudgetCadence,
+
+        /// Currency code, such as EUR [default: the currency from the config]
+        #[arg(long)]
+        currency: Option<String>,
     },
 
     /// Generate a donation plan based on your budget
@@ -81,10 +167,63 @@ enum BudgetCommands {
         /// Allocation strategy
         #[arg(long, default_value = "equal")]
         strategy: AllocationStrategy,
+
+        /// Output format
+        #[arg(long, default_value = "terminal")]
+        format: PlanFormat,
     },
 
     /// Show current budget settings
     Show,
+
+    /// Always include a project in donation plans
+    Pin {
+        /// URL of the project (package, repository, or homepage URL)
+        url: String,
+
+        /// Fixed amount per budget period, taken before the rest is split
+        #[arg(long)]
+        amount: Option<f64>,
+    },
+
+    /// Stop pinning a project
+    Unpin {
+        /// URL the project was pinned by
+        url: String,
+    },
+
+    /// Never allocate to a project, e.g. one backed by a company
+    Exclude {
+        /// URL of the project (package, repository, or homepage URL)
+        url: String,
+    },
+
+    /// Allow an excluded project in donation plans again
+    Include {
+        /// URL the project was excluded by
+        url: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContributeCommands {
+    /// Mark an opportunity as acted upon, so it is no longer suggested
+    Done {
+        /// URL of the opportunity
+        url: String,
+    },
+
+    /// Mark an opportunity as not interesting
+    Dismiss {
+        /// URL of the opportunity
+        url: String,
+    },
+
+    /// Suggest a done or dismissed opportunity again
+    Reopen {
+        /// URL of the opportunity
+        url: String,
+    },
 }
 
 #[derive(Clone, clap::ValueEnum)]
@@ -93,10 +232,28 @@ enum BudgetCadence {
     Yearly,
 }
 
+#[derive(Clone, clap::ValueEnum)]
+enum PlanFormat {
+    Terminal,
+    Json,
+}
+
 #[derive(Clone, clap::ValueEnum)]
 enum AllocationStrategy {
     Equal,
     Weighted,
+    InversePopularity,
+    Criticality,
+    FundingGap,
+}
+
+#[derive(Subcommand)]
+enum ProjectCommands {
+    /// Show the cached enrichment of a project, with the source of each field
+    Show {
+        /// Installed package name or project URL
+        project: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -110,18 +267,43 @@ enum ConfigCommands {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let config = Config::load()?;
+    let mut config = Config::load()?;
+    config.offline |= cli.offline;
 
     match cli.command {
-        None => cmd_scan(&config, 20),
-        Some(Commands::Scan { limit }) => cmd_scan(&config, limit),
+        None => cmd_scan(&config, 20, false),
+        Some(Commands::Scan { limit, incremental }) => cmd_scan(&config, limit, incremental),
         Some(Commands::Report { format, enrich }) => cmd_report(&config, &format, enrich),
+        Some(Commands::Contribute {
+            command: Some(command),
+            ..
+        }) => cmd_contribute_state(&command),
+        Some(Commands::Contribute {
+            command: None,
+            kind,
+            limit,
+            format,
+            hide_dismissed,
+            hacktoberfest,
+        }) => {
+            let filter = OpportunityFilter {
+                kind,
+                hide_dismissed,
+                skills: SkillFilter::from_config(&config),
+                ..Default::default()
+            };
+            cmd_contribute(&config, filter, limit, &format, hacktoberfest)
+        }
         Some(Commands::Budget { command }) => cmd_budget(&config, &command),
+        Some(Commands::Star { all, interactive }) => cmd_star(&config, all, interactive),
+        Some(Commands::Digest { dry_run }) => cmd_digest(&config, dry_run),
+        Some(Commands::Share { mastodon }) => cmd_share(&config, mastodon),
+        Some(Commands::Project { command }) => cmd_project(&command),
         Some(Commands::Config { command }) => cmd_config(&config, &command),
     }
 }
 
-fn cmd_scan(config: &Config, limit: usize) -> Result<()> {
+fn cmd_scan(config: &Config, limit: usize, incremental: bool) -> Result<()> {
     let discoverers = discover::active_discoverers(config);
 
     if discoverers.is_empty() {
@@ -146,6 +328,14 @@ fn cmd_scan(config: &Config, limit: usize) -> Result<()> {
     eprintln!("\nTotal: {} packages discovered", all_packages.len());
 
     match Storage::open() {
+        Ok(storage) if incremental => match storage.save_incremental_scan(&all_packages) {
+            Ok((_, diff)) => eprintln!(
+                "Scan saved ({} added, {} removed)",
+                diff.added.len(),
+                diff.removed.len()
+            ),
+            Err(e) => eprintln!("Warning: failed to save scan: {e}"),
+        },
         Ok(storage) => match storage.save_scan(&all_packages) {
             Ok(_) => eprintln!("Scan saved ({} packages)", all_packages.len()),
             Err(e) => eprintln!("Warning: failed to save scan: {e}"),
@@ -179,33 +369,579 @@ fn cmd_report(config: &Config, format: &ReportFormat, enrich: bool) -> Result<()
         }
     };
 
-    // Run enrichment if requested via CLI flag or config
+    // Run enrichment if requested via CLI flag or config; otherwise still
+    // report funding channels that packages declare in their own metadata.
+    let mut packages = scan.packages;
     let enrichment = if enrich || config.enrich {
-        syld::enrich::enrich_packages(&scan.packages, &storage, config)?
+        syld::enrich::resolve_missing_urls(&mut packages, &storage, config);
+        syld::enrich::resolve_redirects(&mut packages, &storage, config);
+        syld::enrich::enrich_packages(&packages, &storage, config)?
     } else {
-        syld::enrich::EnrichmentMap::new()
+        syld::enrich::package_funding(&packages)
     };
     let contributions = ContributionMap::new();
 
     match format {
         ReportFormat::Terminal => {
-            let mut packages = scan.packages;
             terminal::sort_packages(&mut packages);
             terminal::print_summary(&packages, 0, scan.timestamp, &contributions, &enrichment);
+            let done = storage
+                .opportunity_states()
+                .context("Failed to read opportunity states")?
+                .values()
+                .filter(|s| **s == OpportunityState::Done)
+                .count();
+            if done > 0 {
+                println!("\nYou have completed {done} contribution(s) to the projects you use.");
+            }
+            let contributed_repos = storage
+                .contributed_repos()
+                .context("Failed to read past contributions")?;
+            if !contributed_repos.is_empty() {
+                let projects = scan_projects(&storage, &packages);
+                let contributed =
+                    past_contributions::count_contributed(&projects, &contributed_repos);
+                println!(
+                    "You have already contributed to {contributed} of your {} dependencies.",
+                    projects.len()
+                );
+            }
         }
         ReportFormat::Json => {
-            json::print_json(&scan.packages, scan.timestamp, &contributions, &enrichment)?;
+            json::print_json(&packages, scan.timestamp, &contributions, &enrichment)?;
         }
         ReportFormat::Html => {
-            html::print_html(&scan.packages, scan.timestamp, &contributions, &enrichment);
+            html::print_html(&packages, scan.timestamp, &contributions, &enrichment);
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_contribute(
+    config: &Config,
+    mut filter: OpportunityFilter,
+    limit: usize,
+    format: &ReportFormat,
+    hacktoberfest: bool,
+) -> Result<()> {
+    if hacktoberfest && config.offline {
+        eprintln!(
+            "Finding Hacktoberfest issues needs network access, which offline mode disables."
+        );
+        return Ok(());
+    }
+
+    let storage = Storage::open().context("Failed to open database")?;
+    let Some(scan) = storage
+        .latest_scan()
+        .context("Failed to read latest scan")?
+    else {
+        eprintln!("No scan data found. Run `syld scan` first.");
+        return Ok(());
+    };
+
+    let mut projects = scan_projects(&storage, &scan.packages);
+    projects.sort_by_key(|p| p.name.to_lowercase());
+    let backends: Vec<Box<dyn ContributionBackend>> = if hacktoberfest {
+        let client = GitHubClient::from_config(config)?;
+        vec![Box::new(HacktoberfestBackend { client })]
+    } else {
+        syld::contribute::active_backends(config)
+    };
+    if config.offline {
+        eprintln!("Offline: only suggesting contributions that need no network access.");
+    } else if config.contribute.past_contributions {
+        record_past_contributions(config, &storage, &projects);
+    }
+    filter.states = storage
+        .opportunity_states()
+        .context("Failed to read opportunity states")?;
+    let opportunities =
+        syld::contribute::find_all_opportunities(&backends, &projects, &filter, limit);
+
+    match format {
+        ReportFormat::Terminal => terminal::print_opportunities(&opportunities),
+        ReportFormat::Json => json::print_opportunities(&opportunities)?,
+        ReportFormat::Html => html::print_opportunities(&opportunities),
+    }
+    if hacktoberfest && matches!(format, ReportFormat::Terminal) {
+        let today = chrono::Local::now().date_naive();
+        // After October, plan for the next one.
+        let year = if today.month() > 10 {
+            today.year() + 1
+        } else {
+            today.year()
+        };
+        terminal::print_hacktoberfest_plan(&hacktoberfest::plan(&opportunities, year), year);
+    }
+    Ok(())
+}
+
+/// Record the issues and pull requests the user opened in the repositories
+/// of `projects`, when a GitHub token tells who they are.
+fn record_past_contributions(config: &Config, storage: &Storage, projects: &[UpstreamProject]) {
+    let backend = match GitHubClient::from_config(config) {
+        Ok(client) => PastContributionsBackend { client },
+        Err(e) => {
+            eprintln!("Warning: could not look up your past contributions: {e}");
+            return;
         }
+    };
+    if !backend.is_available() {
+        return;
     }
+    eprintln!("Looking up your past contributions...");
+    match backend.find(projects) {
+        Ok(found) => {
+            for contribution in &found {
+                if let Err(e) = storage.record_contribution(contribution) {
+                    eprintln!("Warning: could not record {}: {e}", contribution.url);
+                }
+            }
+        }
+        Err(e) => eprintln!("Warning: could not look up your past contributions: {e}"),
+    }
+}
 
+fn cmd_contribute_state(command: &ContributeCommands) -> Result<()> {
+    let (url, state, message) = match command {
+        ContributeCommands::Done { url } => (url, OpportunityState::Done, "Marked as done"),
+        ContributeCommands::Dismiss { url } => (url, OpportunityState::Dismissed, "Dismissed"),
+        ContributeCommands::Reopen { url } => (url, OpportunityState::New, "Reopened"),
+    };
+    let storage = Storage::open().context("Failed to open database")?;
+    storage
+        .set_opportunity_state(url, state)
+        .context("Failed to record opportunity state")?;
+    println!("{message}: {url}");
     Ok(())
 }
 
-fn cmd_budget(_config: &Config, _command: &BudgetCommands) -> Result<()> {
-    eprintln!("Budget management not yet implemented.");
+fn cmd_digest(config: &Config, dry_run: bool) -> Result<()> {
+    let storage = Storage::open().context("Failed to open database")?;
+    let Some(scan) = storage
+        .latest_scan()
+        .context("Failed to read latest scan")?
+    else {
+        eprintln!("No scan data found. Run `syld scan` first.");
+        return Ok(());
+    };
+
+    let mut projects = scan_projects(&storage, &scan.packages);
+    projects.sort_by_key(|p| p.name.to_lowercase());
+    let backends = syld::contribute::active_backends(config);
+    let filter = OpportunityFilter {
+        states: storage
+            .opportunity_states()
+            .context("Failed to read opportunity states")?,
+        hide_dismissed: true,
+        skills: SkillFilter::from_config(config),
+        ..Default::default()
+    };
+    let found = syld::contribute::find_all_opportunities(&backends, &projects, &filter, 0);
+    let previous = storage
+        .digested_urls()
+        .context("Failed to read previous digests")?;
+    let new = digest::new_opportunities(found, &previous);
+    // Stay quiet when there is nothing new, so that cron sends no mail.
+    if new.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}\n", digest::subject(&new));
+    print!("{}", digest::body(&new));
+    if dry_run {
+        return Ok(());
+    }
+
+    if let Some(to) = &config.digest.email
+        && let Err(e) = digest::send_email(to, &new)
+    {
+        eprintln!("Warning: could not email the digest: {e}");
+    }
+    if config.digest.desktop
+        && let Err(e) = digest::notify_desktop(&new)
+    {
+        eprintln!("Warning: could not show a desktop notification: {e}");
+    }
+    let urls: Vec<&str> = new.iter().map(|f| f.opportunity.url.as_str()).collect();
+    storage
+        .record_digest(&urls)
+        .context("Failed to record digest")?;
+    Ok(())
+}
+
+fn cmd_budget(config: &Config, command: &BudgetCommands) -> Result<()> {
+    match command {
+        BudgetCommands::Set {
+            amount,
+            cadence,
+            currency,
+        } => cmd_budget_set(config, *amount, cadence, currency.as_deref()),
+        BudgetCommands::Plan { strategy, format } => cmd_budget_plan(config, strategy, format),
+        BudgetCommands::Show => cmd_budget_show(config),
+        BudgetCommands::Pin { url, amount } => cmd_budget_pin(config, url, *amount),
+        BudgetCommands::Unpin { url } => {
+            let storage = Storage::open().context("Failed to open database")?;
+            match storage.unpin_project(&canonicalize_url(url))? {
+                true => println!("Unpinned {url}"),
+                false => eprintln!("{url} was not pinned."),
+            }
+            Ok(())
+        }
+        BudgetCommands::Exclude { url } => {
+            let storage = Storage::open().context("Failed to open database")?;
+            let url = canonicalize_url(url);
+            storage.unpin_project(&url)?;
+            storage
+                .exclude_project(&url)
+                .context("Failed to exclude project")?;
+            println!("Excluded {url} from donation plans");
+            Ok(())
+        }
+        BudgetCommands::Include { url } => {
+            let storage = Storage::open().context("Failed to open database")?;
+            match storage.include_project(&canonicalize_url(url))? {
+                true => println!("Included {url} in donation plans again"),
+                false => eprintln!("{url} was not excluded."),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn cmd_budget_pin(config: &Config, url: &str, amount: Option<f64>) -> Result<()> {
+    if let Some(amount) = amount {
+        budget::check_amount(amount)?;
+    }
+    let storage = Storage::open().context("Failed to open database")?;
+    let url = canonicalize_url(url);
+    storage.include_project(&url)?;
+    storage
+        .pin_project(&url, amount)
+        .context("Failed to pin project")?;
+    match amount {
+        Some(amount) => {
+            let budget = current_budget(config, &storage)?;
+            println!(
+                "Pinned {url} at {amount:.2} {} {}",
+                budget.currency,
+                budget::cadence_label(budget.cadence)
+            );
+        }
+        None => println!("Pinned {url}"),
+    }
+    Ok(())
+}
+
+fn cmd_budget_set(
+    config: &Config,
+    amount: f64,
+    cadence: &BudgetCadence,
+    currency: Option<&str>,
+) -> Result<()> {
+    let cadence = match cadence {
+        BudgetCadence::Monthly => Cadence::Monthly,
+        BudgetCadence::Yearly => Cadence::Yearly,
+    };
+    let currency = currency.unwrap_or(&config.budget.currency);
+    let budget = budget::new_budget(amount, currency, cadence)?;
+
+    let storage = Storage::open().context("Failed to open database")?;
+    storage
+        .save_budget(&budget)
+        .context("Failed to save budget")?;
+    println!(
+        "Budget set to {amount:.2} {} {}.",
+        budget.currency,
+        budget::cadence_label(cadence)
+    );
+    Ok(())
+}
+
+fn cmd_budget_plan(
+    config: &Config,
+    strategy: &AllocationStrategy,
+    format: &PlanFormat,
+) -> Result<()> {
+    let storage = Storage::open().context("Failed to open database")?;
+    let budget = current_budget(config, &storage)?;
+    let Some(scan) = storage
+        .latest_scan()
+        .context("Failed to read latest scan")?
+    else {
+        eprintln!("No scan data found. Run `syld scan` first.");
+        return Ok(());
+    };
+    let projects = scan_project_map(&storage, &scan.packages);
+
+    let overrides = PlanOverrides {
+        pins: storage
+            .budget_pins()
+            .context("Failed to read pinned projects")?,
+        exclusions: storage
+            .budget_exclusions()
+            .context("Failed to read excluded projects")?,
+    };
+    let plan = match strategy {
+        AllocationStrategy::Equal => budget::plan_equal(&budget, &projects, &overrides),
+        AllocationStrategy::Weighted => {
+            budget::plan_weighted(&budget, &scan.packages, &projects, &overrides)
+        }
+        AllocationStrategy::InversePopularity => {
+            budget::plan_inverse_popularity(&budget, &projects, &overrides)
+        }
+        AllocationStrategy::Criticality => budget::plan_criticality(&budget, &projects, &overrides),
+        AllocationStrategy::FundingGap => budget::plan_funding_gap(&budget, &projects, &overrides),
+    };
+    let Some(plan) = plan else {
+        eprintln!("No budget set. Set one with `syld budget set <amount>`.");
+        return Ok(());
+    };
+    for warning in &plan.warnings {
+        eprintln!("Warning: {warning}");
+    }
+    match format {
+        PlanFormat::Terminal => terminal::print_donation_plan(&plan),
+        PlanFormat::Json => json::print_donation_plan(&plan)?,
+    }
+    Ok(())
+}
+
+fn cmd_budget_show(config: &Config) -> Result<()> {
+    let storage = Storage::open().context("Failed to open database")?;
+    let budget = current_budget(config, &storage)?;
+    let Some(amount) = budget.amount else {
+        eprintln!("No budget set. Set one with `syld budget set <amount>`.");
+        return Ok(());
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let end = budget::period_end(budget.cadence, today);
+    let days = budget::days_left(budget.cadence, today);
+    println!(
+        "Budget: {amount:.2} {} {}",
+        budget.currency,
+        budget::cadence_label(budget.cadence)
+    );
+    println!(
+        "Current period ends on {end} ({days} day{} left).",
+        if days == 1 { "" } else { "s" }
+    );
+
+    let mut pins: Vec<String> = storage
+        .budget_pins()
+        .context("Failed to read pinned projects")?
+        .into_iter()
+        .map(|(url, amount)| match amount {
+            Some(amount) => format!("{url} ({amount:.2} {})", budget.currency),
+            None => url,
+        })
+        .collect();
+    pins.sort();
+    if !pins.is_empty() {
+        println!("Pinned: {}", pins.join(", "));
+    }
+    let mut exclusions: Vec<String> = storage
+        .budget_exclusions()
+        .context("Failed to read excluded projects")?
+        .into_iter()
+        .collect();
+    exclusions.sort();
+    if !exclusions.is_empty() {
+        println!("Excluded: {}", exclusions.join(", "));
+    }
+    Ok(())
+}
+
+/// The budget set with `syld budget set`, or else the one in the config.
+fn current_budget(config: &Config, storage: &Storage) -> Result<BudgetConfig> {
+    let saved = storage.get_budget().context("Failed to read budget")?;
+    Ok(match saved {
+        // The database only holds the amount, currency, and cadence.
+        Some(saved) if saved.amount.is_some() => BudgetConfig {
+            amount: saved.amount,
+            currency: saved.currency,
+            cadence: saved.cadence,
+            ..config.budget.clone()
+        },
+        _ => config.budget.clone(),
+    })
+}
+
+fn cmd_star(config: &Config, all: bool, interactive: bool) -> Result<()> {
+    if config.offline {
+        eprintln!("Starring repositories needs network access, which offline mode disables.");
+        return Ok(());
+    }
+
+    let storage = Storage::open().context("Failed to open database")?;
+    let Some(scan) = storage
+        .latest_scan()
+        .context("Failed to read latest scan")?
+    else {
+        eprintln!("No scan data found. Run `syld scan` first.");
+        return Ok(());
+    };
+
+    let backend = GitHubStarsBackend {
+        client: GitHubClient::from_config(config)?,
+        starred: storage.starred_repos()?,
+    };
+    if !backend.is_available() {
+        eprintln!(
+            "Starring repositories needs a GitHub token: set GITHUB_TOKEN or [github] token \
+             in the config."
+        );
+        return Ok(());
+    }
+
+    let projects = scan_projects(&storage, &scan.packages);
+    let repos = backend.unstarred_repos(&projects);
+    if repos.is_empty() {
+        eprintln!("You have starred the GitHub repositories of all your projects.");
+        return Ok(());
+    }
+
+    if !all && !interactive {
+        for repo in &repos {
+            println!("https://github.com/{repo}");
+        }
+        eprintln!(
+            "\n{} unstarred repositories. Run `syld star --all` to star them, or \
+             `syld star --interactive` to choose.",
+            repos.len()
+        );
+        return Ok(());
+    }
+
+    let mut starred = 0;
+    for repo in &repos {
+        if interactive {
+            match confirm(&format!("Star {repo}? [y/N/q] "))? {
+                Some(true) => {}
+                Some(false) => continue,
+                None => break,
+            }
+        }
+        match backend.star(&storage, repo) {
+            Ok(()) => {
+                starred += 1;
+                eprintln!("Starred {repo}");
+            }
+            Err(e) => eprintln!("Warning: failed to star {repo}: {e}"),
+        }
+    }
+    eprintln!("Starred {starred} of {} repositories.", repos.len());
+    Ok(())
+}
+
+fn cmd_share(config: &Config, mastodon: bool) -> Result<()> {
+    let storage = Storage::open().context("Failed to open database")?;
+    let Some(scan) = storage
+        .latest_scan()
+        .context("Failed to read latest scan")?
+    else {
+        eprintln!("No scan data found. Run `syld scan` first.");
+        return Ok(());
+    };
+
+    let projects = scan_projects(&storage, &scan.packages);
+    let summary = spread_the_word::compose_summary(projects.len(), &projects);
+    println!("{summary}");
+    if !mastodon {
+        return Ok(());
+    }
+
+    if config.offline {
+        eprintln!("Posting to Mastodon needs network access, which offline mode disables.");
+        return Ok(());
+    }
+    let Some(client) = MastodonClient::from_config(config)? else {
+        eprintln!(
+            "Posting to Mastodon needs [mastodon] instance and token in the config \
+             (or MASTODON_TOKEN)."
+        );
+        return Ok(());
+    };
+    let url = client.post_status(&summary)?;
+    eprintln!("\nPosted: {url}");
+    Ok(())
+}
+
+/// The upstream projects of the scanned packages. Enriched projects are
+/// preferred, as their repository URL may have been resolved from a homepage
+/// or package registry page.
+fn scan_projects(
+    storage: &Storage,
+    packages: &[syld::discover::InstalledPackage],
+) -> Vec<UpstreamProject> {
+    scan_project_map(storage, packages).into_values().collect()
+}
+
+/// The projects of `packages` keyed by canonical URL, enriched where the
+/// cache has them.
+fn scan_project_map(
+    storage: &Storage,
+    packages: &[syld::discover::InstalledPackage],
+) -> EnrichmentMap {
+    syld::enrich::seed_projects(packages)
+        .into_iter()
+        .map(|(url, project)| match storage.get_enrichment(&url) {
+            Ok(Some(enriched)) => (url, enriched),
+            _ => (url, project),
+        })
+        .collect()
+}
+
+/// Ask a yes/no question on the terminal: `Some(true)` for yes, `Some(false)`
+/// for no (the default), and `None` to quit.
+fn confirm(prompt: &str) -> Result<Option<bool>> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+        return Ok(None);
+    }
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "q" | "quit" => None,
+        _ => Some(false),
+    })
+}
+
+fn cmd_project(command: &ProjectCommands) -> Result<()> {
+    match command {
+        ProjectCommands::Show { project } => cmd_project_show(project),
+    }
+}
+
+fn cmd_project_show(query: &str) -> Result<()> {
+    let storage = Storage::open().context("Failed to open database")?;
+
+    // A package name, or else a URL.
+    let packages = storage
+        .latest_scan()
+        .context("Failed to read latest scan")?
+        .map(|scan| scan.packages)
+        .unwrap_or_default();
+    let url = match packages.iter().find(|p| p.name == query) {
+        Some(pkg) => match &pkg.url {
+            Some(url) => url.clone(),
+            // Distro packages may have had their URL looked up on Repology.
+            None => storage
+                .url_resolution(&format!("repology:{query}"))?
+                .flatten()
+                .unwrap_or_default(),
+        },
+        None => query.to_string(),
+    };
+
+    match syld::enrich::cached_project(&storage, &url)? {
+        Some(project) => terminal::print_project(&project),
+        None => eprintln!("No enrichment data for {query}. Run `syld report --enrich` first."),
+    }
     Ok(())
 }
 
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Minimal client for posting to a Mastodon account.
+//!
+//! Used by `syld share --mastodon`. The instance and access token come from
+//! the `[mastodon]` config section; the token may instead be given in the
+//! `MASTODON_TOKEN` environment variable. The token needs the
+//! `write:statuses` scope.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::config::Config;
+
+/// Environment variable consulted when no token is configured.
+const TOKEN_ENV: &str = "MASTODON_TOKEN";
+
+/// Longest status Mastodon accepts by default.
+pub const MAX_STATUS_CHARS: usize = 500;
+
+pub struct MastodonClient {
+    http: Client,
+    instance: String,
+    token: String,
+}
+
+/// The part of a created status used here.
+#[derive(Debug, Deserialize)]
+struct Status {
+    url: Option<String>,
+}
+
+impl MastodonClient {
+    /// Create a client for the configured instance and token, or `None` when
+    /// either is missing.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let token = config
+            .mastodon
+            .token
+            .clone()
+            .or_else(|| std::env::var(TOKEN_ENV).ok())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        let (Some(instance), Some(token)) = (config.mastodon.instance.as_deref(), token) else {
+            return Ok(None);
+        };
+        let http = Client::builder()
+            .user_agent(concat!("syld/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        Ok(Some(Self {
+            http,
+            instance: instance_url(instance),
+            token,
+        }))
+    }
+
+    /// Publish a public status, returning its URL.
+    pub fn post_status(&self, text: &str) -> Result<String> {
+        let response = self
+            .http
+            .post(format!("{}/api/v1/statuses", self.instance))
+            .bearer_auth(&self.token)
+            .form(&[("status", text), ("visibility", "public")])
+            .send()
+            .with_context(|| format!("Failed to post to {}", self.instance))?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("{} refused the post: HTTP {status}", self.instance);
+        }
+        let posted: Status = response
+            .json()
+            .context("Failed to parse Mastodon API response")?;
+        Ok(posted.url.unwrap_or_else(|| self.instance.clone()))
+    }
+}
+
+/// The base URL of an instance given as `mastodon.social` or
+/// `https://mastodon.social/`.
+fn instance_url(instance: &str) -> String {
+    let instance = instance.trim().trim_end_matches('/');
+    if instance.starts_with("https://") || instance.starts_with("http://") {
+        instance.to_string()
+    } else {
+        format!("https://{instance}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instance_urls() {
+        assert_eq!(instance_url("mastodon.social"), "https://mastodon.social");
+        assert_eq!(
+            instance_url("https://fosstodon.org/"),
+            "https://fosstodon.org"
+        );
+    }
+
+    #[test]
+    fn no_client_without_instance() {
+        let config = Config::default();
+        assert!(MastodonClient::from_config(&config).unwrap().is_none());
+    }
+}
@@ -1,9 +1,16 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod url;
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub use url::canonicalize_url;
+
 /// An upstream open source project, potentially backing multiple installed packages.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpstreamProject {
     /// Canonical project name
     pub name: String,
@@ -41,14 +48,442 @@ pub struct UpstreamProject {
     /// Star/favorite count (e.g. GitHub stars)
     #[serde(default)]
     pub stars: Option<u64>,
+
+    /// Number of packages that depend on this project (populated by enrichment)
+    #[serde(default)]
+    pub dependents: Option<u64>,
+
+    /// Most recent published release (populated by enrichment)
+    #[serde(default)]
+    pub latest_release: Option<Release>,
+
+    /// Number of packages the latest release depends on, directly or
+    /// transitively (populated by enrichment)
+    #[serde(default)]
+    pub dependencies: Option<u64>,
+
+    /// OpenSSF Scorecard summary (populated by enrichment)
+    #[serde(default)]
+    pub scorecard: Option<Scorecard>,
+
+    /// Known vulnerabilities affecting the installed versions (populated by
+    /// enrichment)
+    #[serde(default)]
+    pub vulnerabilities: Option<VulnerabilitySummary>,
+
+    /// Funding figures published by funding platforms (populated by
+    /// enrichment)
+    #[serde(default)]
+    pub funding_status: Vec<FundingStatus>,
+
+    /// Organization that develops the project, e.g. "KDE" (populated by
+    /// enrichment)
+    #[serde(default)]
+    pub organization: Option<String>,
+
+    /// Larger projects this one is part of, e.g. "GNOME" (populated by
+    /// enrichment)
+    #[serde(default)]
+    pub part_of: Vec<String>,
+
+    /// Recent development activity of the repository (populated by
+    /// enrichment)
+    #[serde(default)]
+    pub activity: Option<Activity>,
+
+    /// Smallest number of contributors who made at least half of the
+    /// recent commits (populated by enrichment)
+    #[serde(default)]
+    pub bus_factor: Option<u32>,
+
+    /// Set when the repository is archived or the project declares itself
+    /// deprecated (populated by enrichment)
+    #[serde(default)]
+    pub deprecation: Option<Deprecation>,
+
+    /// Page of the project on a translation platform such as Weblate,
+    /// Crowdin, or Transifex (populated by enrichment)
+    #[serde(default)]
+    pub translation_url: Option<String>,
+
+    /// Criticality score from 0 to 1, higher for projects more of the
+    /// ecosystem depends on (populated by enrichment)
+    #[serde(default)]
+    pub criticality: Option<f64>,
+
+    /// Which enrichment backend supplied each field, and when, keyed by
+    /// field name. Fields taken from package metadata have no entry.
+    #[serde(default)]
+    pub provenance: BTreeMap<String, FieldSource>,
+}
+
+/// Where an enriched field came from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldSource {
+    /// Name of the enrichment backend, e.g. "github"
+    pub backend: String,
+
+    /// When the backend supplied the field
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// A published release of a project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Release {
+    /// Version number as published (e.g. "2.32.3")
+    pub version: String,
+
+    /// Publication time, as an RFC 3339 timestamp
+    #[serde(default)]
+    pub published_at: Option<String>,
+}
+
+/// A project that is no longer maintained.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Whether the repository is archived (read-only) on its forge
+    pub archived: bool,
+
+    /// Repository the project moved to or recommends instead
+    #[serde(default)]
+    pub replacement: Option<String>,
+}
+
+/// Development activity of a project's repository.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Activity {
+    /// Time of the latest commit pushed to the repository, as an RFC 3339
+    /// timestamp
+    #[serde(default)]
+    pub last_commit_at: Option<String>,
+
+    /// Publication time of the latest release, as an RFC 3339 timestamp
+    #[serde(default)]
+    pub last_release_at: Option<String>,
+
+    /// Average number of days between recent releases
+    #[serde(default)]
+    pub release_cadence_days: Option<f64>,
+}
+
+impl Activity {
+    /// Days without a commit or release after which a project counts as
+    /// dormant.
+    pub const DORMANT_AFTER_DAYS: i64 = 365;
+
+    /// Whether the project has seen neither a commit nor a release for
+    /// [`DORMANT_AFTER_DAYS`](Self::DORMANT_AFTER_DAYS) as of `now`, or
+    /// `None` if neither date is known.
+    pub fn is_dormant(&self, now: DateTime<Utc>) -> Option<bool> {
+        Some((now - self.latest()?).num_days() > Self::DORMANT_AFTER_DAYS)
+    }
+
+    /// Time of the latest commit or release, if either is known.
+    pub fn latest(&self) -> Option<DateTime<Utc>> {
+        [&self.last_commit_at, &self.last_release_at]
+            .into_iter()
+            .flatten()
+            .filter_map(|t| DateTime::parse_from_rfc3339(t).ok())
+            .max()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
+
+/// Summary of an OpenSSF Scorecard run on a project's repository.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Scorecard {
+    /// Overall score, from 0 to 10
+    pub score: f64,
+
+    /// Time of the run, as an RFC 3339 timestamp
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+/// Known vulnerabilities affecting the installed packages of a project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VulnerabilitySummary {
+    /// Number of distinct vulnerabilities
+    pub count: u64,
+
+    /// Highest severity among them, if any is rated
+    #[serde(default)]
+    pub max_severity: Option<Severity>,
+
+    /// Vulnerability identifiers (e.g. "GHSA-xxxx-xxxx-xxxx")
+    #[serde(default)]
+    pub ids: Vec<String>,
+}
+
+/// Vulnerability severity, as rated by the advisory database.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Low => write!(f, "low"),
+            Severity::Moderate => write!(f, "moderate"),
+            Severity::High => write!(f, "high"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
 }
 
 /// A way to financially support a project.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FundingChannel {
     /// Platform name (e.g., "GitHub Sponsors", "Open Collective", "Liberapay")
     pub platform: String,
 
     /// URL to the funding page
     pub url: String,
+
+    /// Details scraped from the funding page, where the platform has them
+    /// (populated by enrichment)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<FundingPage>,
+}
+
+/// Funding figures a platform publishes for a project's account there.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FundingStatus {
+    /// Platform the figures come from (as in [`FundingChannel::platform`])
+    pub platform: String,
+
+    /// Currency code of all amounts (e.g. "USD", "EUR")
+    pub currency: String,
+
+    /// Money held and not yet spent
+    #[serde(default)]
+    pub balance: Option<f64>,
+
+    /// Money received over a year
+    #[serde(default)]
+    pub yearly_income: Option<f64>,
+
+    /// Active funding goals
+    #[serde(default)]
+    pub goals: Vec<FundingGoal>,
+}
+
+/// A funding target set by a project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FundingGoal {
+    /// Goal title, as set by the project
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Target amount
+    pub amount: f64,
+
+    /// What the target amount is compared against
+    pub kind: GoalKind,
+}
+
+/// What a [`FundingGoal`] measures.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalKind {
+    /// A yearly income target
+    YearlyIncome,
+    /// A balance to raise
+    Balance,
+}
+
+impl FundingStatus {
+    /// How much is still missing to reach the most distant goal, or `None`
+    /// if no goal can be compared against a known figure.
+    pub fn funding_gap(&self) -> Option<f64> {
+        self.goals
+            .iter()
+            .filter_map(|goal| Some((goal.amount - self.current(goal)?).max(0.0)))
+            .reduce(f64::max)
+    }
+
+    /// The share of the most distant goal still missing, from 0 when every
+    /// goal is reached to 1 when nothing was raised, or `None` if no goal can
+    /// be compared against a known figure.
+    pub fn missing_share(&self) -> Option<f64> {
+        self.goals
+            .iter()
+            .filter(|goal| goal.amount > 0.0)
+            .filter_map(|goal| {
+                let missing = (goal.amount - self.current(goal)?).max(0.0);
+                Some((missing / goal.amount).min(1.0))
+            })
+            .reduce(f64::max)
+    }
+
+    /// The figure `goal` is compared against, if known.
+    fn current(&self, goal: &FundingGoal) -> Option<f64> {
+        match goal.kind {
+            GoalKind::YearlyIncome => self.yearly_income,
+            GoalKind::Balance => self.balance,
+        }
+    }
+}
+
+/// What a funding page says about where the money goes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FundingPage {
+    /// Page title, as set by the creator
+    pub title: Option<String>,
+
+    /// Current fundraising goal (e.g. "New build server: 40% of $500")
+    pub goal: Option<String>,
+
+    /// Sponsorship tiers on offer, monthly before one-time, cheapest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<FundingTier>,
+}
+
+/// A sponsorship tier (e.g. on GitHub Sponsors).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FundingTier {
+    /// Tier name, as set by the maintainer (e.g. "$5 a month")
+    pub name: String,
+
+    /// Price in whole US dollars
+    pub amount: u64,
+
+    /// Whether this is a one-time payment rather than a monthly one
+    pub one_time: bool,
+}
+
+impl FundingChannel {
+    /// Build a channel from a bare funding URL, inferring the platform from
+    /// its host. Unrecognized hosts are labelled `"Custom"`, matching the
+    /// `custom:` key of GitHub's `FUNDING.yml`.
+    pub fn from_url(url: &str) -> Self {
+        let host = url
+            .split("://")
+            .nth(1)
+            .unwrap_or(url)
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches("www.")
+            .to_lowercase();
+        let path = url.to_lowercase();
+
+        let platform = match host.as_str() {
+            "github.com" if path.contains("/sponsors/") => "GitHub Sponsors",
+            "opencollective.com" => "Open Collective",
+            "patreon.com" => "Patreon",
+            "ko-fi.com" => "Ko-fi",
+            "liberapay.com" => "Liberapay",
+            "tidelift.com" => "Tidelift",
+            "buymeacoffee.com" => "Buy Me a Coffee",
+            "polar.sh" => "Polar",
+            "thanks.dev" => "thanks.dev",
+            "paypal.me" | "paypal.com" => "PayPal",
+            _ => "Custom",
+        };
+
+        FundingChannel {
+            platform: platform.to_string(),
+            url: url.to_string(),
+            page: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_url_recognizes_platforms() {
+        let cases = [
+            (
+                "https://github.com/sponsors/sindresorhus",
+                "GitHub Sponsors",
+            ),
+            ("https://opencollective.com/webpack", "Open Collective"),
+            ("https://www.patreon.com/foo", "Patreon"),
+            ("https://ko-fi.com/bar", "Ko-fi"),
+            ("https://liberapay.com/baz", "Liberapay"),
+            ("https://tidelift.com/funding/github/npm/chalk", "Tidelift"),
+        ];
+        for (url, platform) in cases {
+            let channel = FundingChannel::from_url(url);
+            assert_eq!(channel.platform, platform, "{url}");
+            assert_eq!(channel.url, url);
+        }
+    }
+
+    #[test]
+    fn from_url_falls_back_to_custom() {
+        assert_eq!(
+            FundingChannel::from_url("https://example.org/donate").platform,
+            "Custom"
+        );
+        // A plain GitHub repo link is not a sponsors page.
+        assert_eq!(
+            FundingChannel::from_url("https://github.com/owner/repo").platform,
+            "Custom"
+        );
+    }
+
+    #[test]
+    fn missing_share_of_most_distant_goal() {
+        let goal = |amount, kind| FundingGoal {
+            title: None,
+            amount,
+            kind,
+        };
+        let mut status = FundingStatus {
+            platform: "Open Collective".to_string(),
+            currency: "USD".to_string(),
+            balance: Some(500.0),
+            yearly_income: Some(3000.0),
+            goals: vec![
+                goal(12000.0, GoalKind::YearlyIncome),
+                goal(1000.0, GoalKind::Balance),
+            ],
+        };
+        assert_eq!(status.missing_share(), Some(0.75));
+
+        status.yearly_income = Some(20000.0);
+        assert_eq!(status.missing_share(), Some(0.5));
+        status.balance = Some(1500.0);
+        assert_eq!(status.missing_share(), Some(0.0));
+
+        status.balance = None;
+        status.yearly_income = None;
+        assert_eq!(status.missing_share(), None);
+    }
+
+    #[test]
+    fn dormant_after_a_year_without_commits_or_releases() {
+        let now = DateTime::parse_from_rfc3339("2025-06-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let activity = Activity {
+            last_commit_at: Some("2023-01-10T12:00:00Z".to_string()),
+            last_release_at: Some("2024-09-30T08:00:00+02:00".to_string()),
+            release_cadence_days: None,
+        };
+        assert_eq!(activity.is_dormant(now), Some(false));
+
+        let stale = Activity {
+            last_release_at: None,
+            ..activity
+        };
+        assert_eq!(stale.is_dormant(now), Some(true));
+
+        let unknown = Activity {
+            last_commit_at: None,
+            last_release_at: Some("not a date".to_string()),
+            release_cadence_days: Some(30.0),
+        };
+        assert_eq!(unknown.is_dormant(now), None);
+    }
 }
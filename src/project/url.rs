@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Canonical project URLs.
+//!
+//! Packages point at their upstream project in many spellings: with or
+//! without a scheme, `www.`, `.git` suffix, query string, or trailing slash,
+//! as SSH remotes, as pages deep inside a repository, or on a mirror of the
+//! canonical repository. [`canonicalize_url`] maps them all to one key, which
+//! is what projects are grouped and their enrichment cached by.
+//!
+//! Canonicalization is purely textual. Redirects, which need the network,
+//! are followed beforehand by [`crate::enrich::resolve_redirects`].
+
+/// Hosts whose repositories live at exactly `owner/repo`, so that deeper
+/// paths (`/tree/main`, `/issues`, ...) are pages of the same project.
+const OWNER_REPO_HOSTS: &[&str] = &["github.com", "codeberg.org", "bitbucket.org"];
+
+/// Mirrors and former homes of repositories, as canonical URL prefixes, and
+/// the canonical location they map to.
+const MIRRORS: &[(&str, &str)] = &[
+    (
+        "kernel.googlesource.com/pub/scm/",
+        "git.kernel.org/pub/scm/",
+    ),
+    ("git.kernel.org/cgit/", "git.kernel.org/pub/scm/"),
+    (
+        "github.com/torvalds/linux",
+        "git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux",
+    ),
+    ("github.com/gnome/", "gitlab.gnome.org/gnome/"),
+    ("git.gnome.org/browse/", "gitlab.gnome.org/gnome/"),
+    ("cgit.freedesktop.org/", "gitlab.freedesktop.org/"),
+    ("anongit.freedesktop.org/git/", "gitlab.freedesktop.org/"),
+];
+
+/// Domains of Read the Docs sites, served as `<project>.<domain>`.
+const READTHEDOCS_DOMAINS: &[&str] = &[".readthedocs.io", ".readthedocs.org", ".rtfd.io"];
+
+/// Canonicalize a project URL for grouping and caching.
+///
+/// The result has no scheme, user, default port, leading `www.`, query
+/// string, fragment, trailing slash, or `.git` suffix, and is lowercase, so
+/// that `https://www.qemu.org/` and `http://qemu.org` are the same project:
+///
+/// - SSH remotes (`git@host:owner/repo.git`) become `host/owner/repo`.
+/// - Pages below a repository on GitHub, Codeberg, and Bitbucket, and
+///   below `/-/` on GitLab, become the repository.
+/// - Known mirrors map to the repository they mirror (see [`MIRRORS`]).
+/// - Read the Docs pages become the root of the project's documentation.
+pub fn canonicalize_url(url: &str) -> String {
+    let s = url.trim();
+    let s = s.strip_prefix("git+").unwrap_or(s);
+    let s = match s.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        // `git@host:path` has no scheme and a colon instead of a slash.
+        None => match s.split_once(':') {
+            Some((user_host, path)) if user_host.contains('@') && !user_host.contains('/') => {
+                format!("{user_host}/{}", path.trim_start_matches('/'))
+            }
+            _ => s.to_string(),
+        },
+    };
+    let s = s.to_lowercase();
+
+    let (authority, path) = s.split_once('/').unwrap_or((&s, ""));
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = host
+        .strip_suffix(":443")
+        .or_else(|| host.strip_suffix(":80"))
+        .unwrap_or(host);
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let path = path.split("/-/").next().unwrap_or_default();
+    let path = path.trim_matches('/');
+    let path = path
+        .strip_suffix(".git")
+        .unwrap_or(path)
+        .trim_end_matches('/');
+    let path = if OWNER_REPO_HOSTS.contains(&host) {
+        path.match_indices('/')
+            .nth(1)
+            .map_or(path, |(i, _)| &path[..i])
+    } else {
+        path
+    };
+
+    let canonical = if path.is_empty() {
+        host.to_string()
+    } else {
+        format!("{host}/{path}")
+    };
+    readthedocs_root(&canonical).unwrap_or_else(|| map_mirror(canonical))
+}
+
+/// Replace a known mirror prefix with the canonical location.
+fn map_mirror(canonical: String) -> String {
+    for (mirror, target) in MIRRORS {
+        if let Some(rest) = canonical.strip_prefix(mirror)
+            && (mirror.ends_with('/') || rest.is_empty() || rest.starts_with('/'))
+        {
+            return format!("{target}{rest}");
+        }
+    }
+    canonical
+}
+
+/// The documentation root `<project>.readthedocs.io` of a Read the Docs
+/// page, including project pages on readthedocs.org itself.
+fn readthedocs_root(canonical: &str) -> Option<String> {
+    let (host, path) = canonical.split_once('/').unwrap_or((canonical, ""));
+    let project = match READTHEDOCS_DOMAINS
+        .iter()
+        .find_map(|domain| host.strip_suffix(domain))
+    {
+        Some(project) => project,
+        None if host == "readthedocs.org" => path.strip_prefix("projects/")?.split('/').next()?,
+        None => return None,
+    };
+    (!project.is_empty() && !project.contains('.')).then(|| format!("{project}.readthedocs.io"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_scheme_www_and_trailing_slash() {
+        assert_eq!(canonicalize_url("https://qemu.org/"), "qemu.org");
+        assert_eq!(canonicalize_url("http://example.com"), "example.com");
+        assert_eq!(canonicalize_url("https://www.qemu.org/"), "qemu.org");
+        assert_eq!(canonicalize_url(""), "");
+    }
+
+    #[test]
+    fn lowercases_and_preserves_path() {
+        assert_eq!(
+            canonicalize_url("https://GitHub.com/Foo/Bar"),
+            "github.com/foo/bar"
+        );
+        assert_eq!(
+            canonicalize_url("https://example.org/projects/tool/"),
+            "example.org/projects/tool"
+        );
+    }
+
+    #[test]
+    fn strips_git_suffix_query_and_fragment() {
+        for url in [
+            "https://github.com/curl/curl.git",
+            "git+https://github.com/curl/curl.git#readme",
+            "https://github.com/curl/curl?tab=readme-ov-file",
+            "git@github.com:curl/curl.git",
+            "ssh://git@github.com/curl/curl",
+            "https://github.com:443/curl/curl/",
+        ] {
+            assert_eq!(canonicalize_url(url), "github.com/curl/curl", "{url}");
+        }
+    }
+
+    #[test]
+    fn collapses_pages_inside_repositories() {
+        assert_eq!(
+            canonicalize_url("https://github.com/rust-lang/rust/tree/master/src"),
+            "github.com/rust-lang/rust"
+        );
+        assert_eq!(
+            canonicalize_url("https://gitlab.com/group/sub/project/-/issues"),
+            "gitlab.com/group/sub/project"
+        );
+        // Organizations are kept as they are.
+        assert_eq!(canonicalize_url("https://github.com/kde"), "github.com/kde");
+    }
+
+    #[test]
+    fn maps_known_mirrors() {
+        assert_eq!(
+            canonicalize_url("https://github.com/torvalds/linux"),
+            "git.kernel.org/pub/scm/linux/kernel/git/torvalds/linux"
+        );
+        assert_eq!(
+            canonicalize_url("https://kernel.googlesource.com/pub/scm/git/git.git"),
+            "git.kernel.org/pub/scm/git/git"
+        );
+        assert_eq!(
+            canonicalize_url("https://github.com/GNOME/gimp"),
+            "gitlab.gnome.org/gnome/gimp"
+        );
+        assert_eq!(
+            canonicalize_url("https://cgit.freedesktop.org/xorg/xserver/"),
+            "gitlab.freedesktop.org/xorg/xserver"
+        );
+        // A prefix of another repository is not a mirror of it.
+        assert_eq!(
+            canonicalize_url("https://github.com/torvalds/linux-tools"),
+            "github.com/torvalds/linux-tools"
+        );
+    }
+
+    #[test]
+    fn collapses_readthedocs_pages() {
+        for url in [
+            "https://requests.readthedocs.io/en/latest/user/quickstart/",
+            "https://requests.readthedocs.org/",
+            "https://readthedocs.org/projects/requests/",
+        ] {
+            assert_eq!(canonicalize_url(url), "requests.readthedocs.io", "{url}");
+        }
+        assert_eq!(
+            canonicalize_url("https://readthedocs.org"),
+            "readthedocs.org"
+        );
+    }
+}
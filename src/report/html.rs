@@ -4,10 +4,13 @@ use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 
+use crate::contribute::{OpportunityState, ProjectOpportunity};
 use crate::discover::{InstalledPackage, PackageSource};
 use crate::enrich::EnrichmentMap;
 use crate::report::terminal::{group_by_project, sort_packages};
-use crate::report::{ContributionMap, lookup_contributions, lookup_enrichment};
+use crate::report::{
+    ContributionMap, count_install_reasons, lookup_enrichment, rank_contributions,
+};
 
 /// Escape HTML special characters.
 fn escape_html(s: &str) -> String {
@@ -33,6 +36,31 @@ fn format_package_html(pkg: &InstalledPackage, show_badge: bool) -> String {
     }
 }
 
+/// The document head of a report page, up to the opening `<body>` tag.
+fn page_head(title: &str) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_html(title)));
+    html.push_str("<style>\n");
+    html.push_str(
+        "body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1a1a1a; }\n",
+    );
+    html.push_str("h1, h2 { margin-top: 2rem; }\n");
+    html.push_str("table { border-collapse: collapse; width: 100%; margin: 1rem 0; }\n");
+    html.push_str(
+        "th, td { text-align: left; padding: 0.5rem 1rem; border-bottom: 1px solid #ddd; }\n",
+    );
+    html.push_str("th { background: #f5f5f5; }\n");
+    html.push_str("tr:hover { background: #fafafa; }\n");
+    html.push_str(".meta { color: #666; font-size: 0.9rem; }\n");
+    html.push_str(".badge { display: inline-block; font-size: 0.7rem; padding: 0.1rem 0.4rem; border-radius: 3px; background: #e8e8e8; color: #555; margin-left: 0.3rem; vertical-align: middle; }\n");
+    html.push_str("</style>\n");
+    html.push_str("</head>\n<body>\n");
+    html
+}
+
 /// Generate an HTML report and print it to stdout.
 pub fn print_html(
     packages: &[InstalledPackage],
@@ -56,25 +84,7 @@ pub fn print_html(
 
     let mut html = String::new();
 
-    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
-    html.push_str("<meta charset=\"utf-8\">\n");
-    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
-    html.push_str("<title>syld report</title>\n");
-    html.push_str("<style>\n");
-    html.push_str(
-        "body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1a1a1a; }\n",
-    );
-    html.push_str("h1, h2 { margin-top: 2rem; }\n");
-    html.push_str("table { border-collapse: collapse; width: 100%; margin: 1rem 0; }\n");
-    html.push_str(
-        "th, td { text-align: left; padding: 0.5rem 1rem; border-bottom: 1px solid #ddd; }\n",
-    );
-    html.push_str("th { background: #f5f5f5; }\n");
-    html.push_str("tr:hover { background: #fafafa; }\n");
-    html.push_str(".meta { color: #666; font-size: 0.9rem; }\n");
-    html.push_str(".badge { display: inline-block; font-size: 0.7rem; padding: 0.1rem 0.4rem; border-radius: 3px; background: #e8e8e8; color: #555; margin-left: 0.3rem; vertical-align: middle; }\n");
-    html.push_str("</style>\n");
-    html.push_str("</head>\n<body>\n");
+    html.push_str(&page_head("syld report"));
 
     let with_url_count = groups.iter().filter(|g| !g.url.is_empty()).count();
     let without_url_count = sorted.iter().filter(|p| p.url.is_none()).count();
@@ -96,19 +106,20 @@ pub fn print_html(
         "<p class=\"meta\">Packages without URL: {}</p>\n",
         without_url_count
     ));
+    if let Some((explicit, dependency)) = count_install_reasons(&sorted) {
+        html.push_str(&format!(
+            "<p class=\"meta\">Explicitly installed: {} ({} dependencies)</p>\n",
+            explicit, dependency
+        ));
+    }
 
-    if !contributions.is_empty() {
-        let projects_with_contribs = groups
-            .iter()
-            .filter(|g| {
-                !g.url.is_empty()
-                    && !lookup_contributions(&g.url, &g.project_urls, contributions).is_empty()
-            })
-            .count();
-        let total_opps: usize = contributions.values().map(|v| v.len()).sum();
+    let ranked_contributions = rank_contributions(&groups, contributions, enrichment, timestamp);
+    if !ranked_contributions.is_empty() {
+        let total_opps: usize = ranked_contributions.iter().map(|(_, o)| o.len()).sum();
         html.push_str(&format!(
             "<p class=\"meta\">Projects with contributions: {} ({} opportunities)</p>\n",
-            projects_with_contribs, total_opps
+            ranked_contributions.len(),
+            total_opps
         ));
     }
 
@@ -157,41 +168,22 @@ pub fn print_html(
         html.push_str("</table>\n");
     }
 
-    // Ways to Help section
-    if !contributions.is_empty() {
-        let mut has_any = false;
-
-        for group in &groups {
-            if group.url.is_empty() {
-                continue;
-            }
-            let opps = lookup_contributions(&group.url, &group.project_urls, contributions);
-            if opps.is_empty() {
-                continue;
-            }
-
-            if !has_any {
-                html.push_str("<h2>Ways to Help</h2>\n");
-                html.push_str(
-                    "<table>\n<tr><th>Project</th><th>Type</th><th>Opportunity</th></tr>\n",
-                );
-                has_any = true;
-            }
-
-            for opp in &opps {
+    // Ways to Help section, most important projects first
+    if !ranked_contributions.is_empty() {
+        html.push_str("<h2>Ways to Help</h2>\n");
+        html.push_str("<table>\n<tr><th>Project</th><th>Type</th><th>Opportunity</th></tr>\n");
+        for (url, opps) in &ranked_contributions {
+            for opp in opps {
                 html.push_str(&format!(
                     "<tr><td>{}</td><td>{}</td><td><a href=\"{}\">{}</a></td></tr>\n",
-                    escape_html(&group.url),
+                    escape_html(url),
                     escape_html(&opp.kind.to_string()),
                     escape_html(&opp.url),
                     escape_html(&opp.title),
                 ));
             }
         }
-
-        if has_any {
-            html.push_str("</table>\n");
-        }
+        html.push_str("</table>\n");
     }
 
     // Funding section
@@ -242,10 +234,45 @@ pub fn print_html(
     print!("{html}");
 }
 
+/// Print contribution opportunities as an HTML page.
+pub fn print_opportunities(opportunities: &[ProjectOpportunity]) {
+    let mut html = page_head("syld contributions");
+    html.push_str("<h1>Ways to Help</h1>\n");
+    if opportunities.is_empty() {
+        html.push_str("<p class=\"meta\">No contribution opportunities found.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Project</th><th>Type</th><th>Opportunity</th></tr>\n");
+        for found in opportunities {
+            let opp = &found.opportunity;
+            let mut kind = opp.kind.to_string();
+            if found.state == OpportunityState::Dismissed {
+                kind.push_str(" (dismissed)");
+            }
+            let description = opp
+                .description
+                .as_deref()
+                .map(|d| format!("<br><span class=\"meta\">{}</span>", escape_html(d)))
+                .unwrap_or_default();
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td><a href=\"{}\">{}</a>{}</td></tr>\n",
+                escape_html(&found.project),
+                escape_html(&kind),
+                escape_html(&opp.url),
+                escape_html(&opp.title),
+                description,
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+
+    print!("{html}");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::discover::PackageSource;
+    use crate::discover::{InstallReason, PackageSource};
 
     fn sample_packages() -> Vec<InstalledPackage> {
         vec![
@@ -256,6 +283,9 @@ mod tests {
                 url: Some("https://www.mozilla.org/firefox/".to_string()),
                 source: PackageSource::Pacman,
                 licenses: vec!["MPL-2.0".to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
             InstalledPackage {
                 name: "linux".to_string(),
@@ -264,6 +294,9 @@ mod tests {
                 url: Some("https://kernel.org".to_string()),
                 source: PackageSource::Pacman,
                 licenses: vec!["GPL-2.0".to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
         ]
     }
@@ -314,6 +347,9 @@ mod tests {
             url: None,
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         };
         assert_eq!(format_package_html(&pkg, false), "firefox");
     }
@@ -327,6 +363,9 @@ mod tests {
             url: None,
             source: PackageSource::Flatpak,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         };
         let html = format_package_html(&pkg, true);
         assert!(html.contains("firefox"));
@@ -343,6 +382,9 @@ mod tests {
             url: None,
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         };
         let html = format_package_html(&pkg, true);
         assert!(html.contains("&lt;script&gt;"));
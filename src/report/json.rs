@@ -4,12 +4,17 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
-use crate::contribute::ContributionOpportunity;
+use crate::budget::DonationPlan;
+use crate::contribute::{ContributionOpportunity, ProjectOpportunity};
 use crate::discover::InstalledPackage;
 use crate::enrich::EnrichmentMap;
-use crate::project::FundingChannel;
+use crate::project::{
+    Activity, Deprecation, FundingChannel, FundingStatus, Release, Scorecard, VulnerabilitySummary,
+};
 use crate::report::terminal::group_by_project;
-use crate::report::{ContributionMap, lookup_contributions, lookup_enrichment};
+use crate::report::{
+    ContributionMap, lookup_contributions, lookup_enrichment, select_contributions,
+};
 
 /// A grouped upstream project for the JSON report.
 #[derive(Serialize)]
@@ -27,6 +32,45 @@ pub struct JsonProject {
     /// Star/favorite count (e.g. GitHub stars).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stars: Option<u64>,
+    /// Number of packages that depend on this project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependents: Option<u64>,
+    /// Most recent published release.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_release: Option<Release>,
+    /// Number of packages the latest release depends on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<u64>,
+    /// OpenSSF Scorecard summary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scorecard: Option<Scorecard>,
+    /// Known vulnerabilities affecting the installed versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<VulnerabilitySummary>,
+    /// Funding figures published by funding platforms.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub funding_status: Vec<FundingStatus>,
+    /// Recent development activity of the repository.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<Activity>,
+    /// Set when the repository is archived or the project deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<Deprecation>,
+    /// Smallest number of contributors who made half of the recent commits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_factor: Option<u32>,
+    /// Organization that develops the project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    /// Larger projects this one is part of.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub part_of: Vec<String>,
+    /// Page of the project on a translation platform.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation_url: Option<String>,
+    /// Criticality score from 0 to 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub criticality: Option<f64>,
     /// Whether the project is open source.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_open_source: Option<bool>,
@@ -68,7 +112,7 @@ pub fn print_json(
                 g.packages.iter().map(|p| p.name.clone()).collect();
             package_names.sort();
             let project_contributions =
-                lookup_contributions(&g.url, &g.project_urls, contributions);
+                select_contributions(lookup_contributions(&g.url, &g.project_urls, contributions));
             let enriched = lookup_enrichment(&g.url, &g.project_urls, enrichment);
             JsonProject {
                 url: g.url.clone(),
@@ -76,6 +120,21 @@ pub fn print_json(
                 package_names,
                 funding: enriched.map(|e| e.funding.clone()).unwrap_or_default(),
                 stars: enriched.and_then(|e| e.stars),
+                dependents: enriched.and_then(|e| e.dependents),
+                latest_release: enriched.and_then(|e| e.latest_release.clone()),
+                dependencies: enriched.and_then(|e| e.dependencies),
+                scorecard: enriched.and_then(|e| e.scorecard.clone()),
+                vulnerabilities: enriched.and_then(|e| e.vulnerabilities.clone()),
+                funding_status: enriched
+                    .map(|e| e.funding_status.clone())
+                    .unwrap_or_default(),
+                activity: enriched.and_then(|e| e.activity.clone()),
+                deprecation: enriched.and_then(|e| e.deprecation.clone()),
+                bus_factor: enriched.and_then(|e| e.bus_factor),
+                organization: enriched.and_then(|e| e.organization.clone()),
+                part_of: enriched.map(|e| e.part_of.clone()).unwrap_or_default(),
+                translation_url: enriched.and_then(|e| e.translation_url.clone()),
+                criticality: enriched.and_then(|e| e.criticality),
                 is_open_source: enriched.and_then(|e| e.is_open_source),
                 contributions: project_contributions,
             }
@@ -105,10 +164,34 @@ pub fn print_json(
     Ok(())
 }
 
+/// The JSON output of `syld contribute`.
+#[derive(Debug, Serialize)]
+pub struct JsonContributions<'a> {
+    pub total_opportunities: usize,
+    pub opportunities: &'a [ProjectOpportunity],
+}
+
+/// Print contribution opportunities as JSON to stdout.
+pub fn print_opportunities(opportunities: &[ProjectOpportunity]) -> Result<()> {
+    let output = JsonContributions {
+        total_opportunities: opportunities.len(),
+        opportunities,
+    };
+    let json = serde_json::to_string_pretty(&output)?;
+    println!("{json}");
+    Ok(())
+}
+
+pub fn print_donation_plan(plan: &DonationPlan) -> Result<()> {
+    let json = serde_json::to_string_pretty(plan)?;
+    println!("{json}");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::discover::PackageSource;
+    use crate::discover::{InstallReason, PackageSource};
 
     fn sample_packages() -> Vec<InstalledPackage> {
         vec![
@@ -119,6 +202,9 @@ mod tests {
                 url: Some("https://www.mozilla.org/firefox/".to_string()),
                 source: PackageSource::Pacman,
                 licenses: vec!["MPL-2.0".to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
             InstalledPackage {
                 name: "linux".to_string(),
@@ -127,6 +213,9 @@ mod tests {
                 url: Some("https://kernel.org".to_string()),
                 source: PackageSource::Pacman,
                 licenses: vec!["GPL-2.0".to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
         ]
     }
@@ -152,6 +241,19 @@ mod tests {
                     stars: None,
                     is_open_source: None,
                     contributions: vec![],
+                    dependents: None,
+                    latest_release: None,
+                    dependencies: None,
+                    scorecard: None,
+                    vulnerabilities: None,
+                    funding_status: vec![],
+                    organization: None,
+                    part_of: vec![],
+                    activity: None,
+                    bus_factor: None,
+                    deprecation: None,
+                    translation_url: None,
+                    criticality: None,
                 },
                 JsonProject {
                     url: "mozilla.org/firefox".to_string(),
@@ -161,6 +263,19 @@ mod tests {
                     stars: None,
                     is_open_source: None,
                     contributions: vec![],
+                    dependents: None,
+                    latest_release: None,
+                    dependencies: None,
+                    scorecard: None,
+                    vulnerabilities: None,
+                    funding_status: vec![],
+                    organization: None,
+                    part_of: vec![],
+                    activity: None,
+                    bus_factor: None,
+                    deprecation: None,
+                    translation_url: None,
+                    criticality: None,
                 },
             ],
             packages: packages.clone(),
@@ -215,6 +330,9 @@ mod tests {
             url: None,
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         }];
         let timestamp = "2025-01-15T10:30:00Z".parse::<DateTime<Utc>>().unwrap();
 
@@ -268,6 +386,19 @@ mod tests {
                     stars: None,
                     is_open_source: None,
                     contributions: vec![],
+                    dependents: None,
+                    latest_release: None,
+                    dependencies: None,
+                    scorecard: None,
+                    vulnerabilities: None,
+                    funding_status: vec![],
+                    organization: None,
+                    part_of: vec![],
+                    activity: None,
+                    bus_factor: None,
+                    deprecation: None,
+                    translation_url: None,
+                    criticality: None,
                 },
                 JsonProject {
                     url: "mozilla.org/firefox".to_string(),
@@ -277,6 +408,19 @@ mod tests {
                     stars: None,
                     is_open_source: None,
                     contributions: vec![],
+                    dependents: None,
+                    latest_release: None,
+                    dependencies: None,
+                    scorecard: None,
+                    vulnerabilities: None,
+                    funding_status: vec![],
+                    organization: None,
+                    part_of: vec![],
+                    activity: None,
+                    bus_factor: None,
+                    deprecation: None,
+                    translation_url: None,
+                    criticality: None,
                 },
             ],
             packages,
@@ -322,6 +466,9 @@ mod tests {
             url: None,
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         }];
         let timestamp = "2025-01-15T10:30:00Z".parse::<DateTime<Utc>>().unwrap();
 
@@ -380,6 +527,19 @@ mod tests {
                             url: "https://github.com/torvalds/linux/issues/2".to_string(),
                         },
                     ],
+                    dependents: None,
+                    latest_release: None,
+                    dependencies: None,
+                    scorecard: None,
+                    vulnerabilities: None,
+                    funding_status: vec![],
+                    organization: None,
+                    part_of: vec![],
+                    activity: None,
+                    bus_factor: None,
+                    deprecation: None,
+                    translation_url: None,
+                    criticality: None,
                 },
                 JsonProject {
                     url: "mozilla.org/firefox".to_string(),
@@ -389,6 +549,19 @@ mod tests {
                     stars: None,
                     is_open_source: None,
                     contributions: vec![],
+                    dependents: None,
+                    latest_release: None,
+                    dependencies: None,
+                    scorecard: None,
+                    vulnerabilities: None,
+                    funding_status: vec![],
+                    organization: None,
+                    part_of: vec![],
+                    activity: None,
+                    bus_factor: None,
+                    deprecation: None,
+                    translation_url: None,
+                    criticality: None,
                 },
             ],
             packages: packages.clone(),
@@ -439,6 +612,19 @@ mod tests {
                     description: None,
                     url: "https://github.com/torvalds/linux/issues/1".to_string(),
                 }],
+                dependents: None,
+                latest_release: None,
+                dependencies: None,
+                scorecard: None,
+                vulnerabilities: None,
+                funding_status: vec![],
+                organization: None,
+                part_of: vec![],
+                activity: None,
+                bus_factor: None,
+                deprecation: None,
+                translation_url: None,
+                criticality: None,
             }],
             packages,
         };
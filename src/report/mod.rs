@@ -2,22 +2,46 @@
 
 //! Report generation in multiple output formats.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
 
 use crate::contribute::ContributionOpportunity;
+use crate::discover::{InstallReason, InstalledPackage};
 use crate::enrich::EnrichmentMap;
 use crate::project::UpstreamProject;
+use crate::report::terminal::ProjectGroup;
 
 pub mod html;
 pub mod json;
 pub mod terminal;
 
-/// Contribution opportunities keyed by normalized project URL.
+/// Contribution opportunities keyed by canonical project URL.
 ///
 /// Report functions accept this as an optional parameter so they can display
 /// a "Ways to Help" section alongside the existing package/project tables.
 pub type ContributionMap = HashMap<String, Vec<ContributionOpportunity>>;
 
+/// Most contribution opportunities shown per project, so that a project with
+/// many open issues does not flood the report.
+pub const MAX_OPPORTUNITIES_PER_PROJECT: usize = 5;
+
+/// Count explicitly installed packages and dependencies.
+///
+/// Returns `None` when no package manager in the scan records install
+/// reasons, so reports can omit the line entirely.
+pub fn count_install_reasons(packages: &[InstalledPackage]) -> Option<(usize, usize)> {
+    let count = |reason: InstallReason| {
+        packages
+            .iter()
+            .filter(|p| p.install_reason == reason)
+            .count()
+    };
+    let explicit = count(InstallReason::Explicit);
+    let dependency = count(InstallReason::Dependency);
+    (explicit + dependency > 0).then_some((explicit, dependency))
+}
+
 /// Look up contributions for a project group, checking both the group URL and
 /// any individual project URLs within an ancestor group.
 pub fn lookup_contributions(
@@ -40,6 +64,71 @@ pub fn lookup_contributions(
     result
 }
 
+/// Drop the opportunities that another backend already reported (same URL),
+/// keeping the first, and cap them at [`MAX_OPPORTUNITIES_PER_PROJECT`].
+pub fn select_contributions(
+    opportunities: Vec<ContributionOpportunity>,
+) -> Vec<ContributionOpportunity> {
+    let mut seen = HashSet::new();
+    opportunities
+        .into_iter()
+        .filter(|o| seen.insert(o.url.clone()))
+        .take(MAX_OPPORTUNITIES_PER_PROJECT)
+        .collect()
+}
+
+/// The selected contribution opportunities of each project group that has
+/// any, most important project first (see [`project_importance`]).
+pub fn rank_contributions<'g>(
+    groups: &'g [ProjectGroup],
+    contributions: &ContributionMap,
+    enrichment: &EnrichmentMap,
+    now: DateTime<Utc>,
+) -> Vec<(&'g str, Vec<ContributionOpportunity>)> {
+    let mut ranked: Vec<(f64, &str, Vec<ContributionOpportunity>)> = groups
+        .iter()
+        .filter(|g| !g.url.is_empty())
+        .filter_map(|g| {
+            let opportunities =
+                select_contributions(lookup_contributions(&g.url, &g.project_urls, contributions));
+            if opportunities.is_empty() {
+                return None;
+            }
+            let enriched = lookup_enrichment(&g.url, &g.project_urls, enrichment);
+            let importance = project_importance(g.packages.len(), enriched, now);
+            Some((importance, g.url.as_str(), opportunities))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(b.1)));
+    ranked
+        .into_iter()
+        .map(|(_, url, opportunities)| (url, opportunities))
+        .collect()
+}
+
+/// How much helping a project matters: more for projects the user installed
+/// more packages of, for less known projects (fewer stars), and for projects
+/// still active (recent commits or releases). Unknown stars and activity
+/// count as average.
+pub fn project_importance(
+    package_count: usize,
+    project: Option<&UpstreamProject>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let packages = (package_count as f64).ln_1p();
+    let obscurity = project
+        .and_then(|p| p.stars)
+        .map_or(0.5, |stars| 1.0 / (1.0 + (stars as f64).log10().max(0.0)));
+    let recency = project
+        .and_then(|p| p.activity.as_ref())
+        .and_then(|a| a.latest())
+        .map_or(0.5, |latest| {
+            let days = (now - latest).num_days().max(0) as f64;
+            1.0 - (days / 730.0).min(1.0)
+        });
+    packages + obscurity + recency
+}
+
 /// Look up enrichment data for a project group, checking both the group URL
 /// and any individual project URLs within an ancestor group.
 ///
@@ -120,6 +209,92 @@ mod tests {
         assert_eq!(result.len(), 2);
     }
 
+    #[test]
+    fn select_drops_duplicates_and_caps() {
+        let mut opportunities = vec![
+            make_opp(ContributionKind::GoodFirstIssue, "Fix bug"),
+            make_opp(ContributionKind::HelpWanted, "Fix bug"),
+        ];
+        for i in 0..10 {
+            let mut opp = make_opp(ContributionKind::HelpWanted, &format!("Issue {i}"));
+            opp.url = format!("https://example.com/issues/{i}");
+            opportunities.push(opp);
+        }
+
+        let selected = select_contributions(opportunities);
+        assert_eq!(selected.len(), MAX_OPPORTUNITIES_PER_PROJECT);
+        assert_eq!(selected[0].kind, ContributionKind::GoodFirstIssue);
+        assert_eq!(selected[1].title, "Issue 0");
+    }
+
+    #[test]
+    fn importance_favors_used_obscure_active_projects() {
+        let now = Utc::now();
+        let project = |stars: u64, days_since_commit: i64| UpstreamProject {
+            name: "repo".to_string(),
+            repo_url: Some("https://github.com/org/repo".to_string()),
+            stars: Some(stars),
+            activity: Some(crate::project::Activity {
+                last_commit_at: Some(
+                    (now - chrono::Duration::days(days_since_commit)).to_rfc3339(),
+                ),
+                last_release_at: None,
+                release_cadence_days: None,
+            }),
+            ..Default::default()
+        };
+
+        let popular = project(50_000, 10);
+        let obscure = project(12, 10);
+        let dormant = project(12, 1000);
+        assert!(
+            project_importance(1, Some(&obscure), now) > project_importance(1, Some(&popular), now)
+        );
+        assert!(
+            project_importance(1, Some(&obscure), now) > project_importance(1, Some(&dormant), now)
+        );
+        assert!(
+            project_importance(5, Some(&popular), now) > project_importance(1, Some(&popular), now)
+        );
+        assert!(project_importance(1, None, now) > 0.0);
+    }
+
+    #[test]
+    fn rank_puts_important_projects_first() {
+        let packages: Vec<InstalledPackage> = ["a1", "a2", "a3", "b"]
+            .iter()
+            .map(|name| InstalledPackage {
+                name: name.to_string(),
+                version: "1.0".to_string(),
+                description: None,
+                url: Some(if name.starts_with('a') {
+                    "https://a.example.com".to_string()
+                } else {
+                    "https://b.example.com".to_string()
+                }),
+                source: crate::discover::PackageSource::Pacman,
+                licenses: vec![],
+                funding: vec![],
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
+            })
+            .collect();
+        let groups = crate::report::terminal::group_by_project(&packages);
+        let mut map = ContributionMap::new();
+        map.insert(
+            "a.example.com".to_string(),
+            vec![make_opp(ContributionKind::Star, "Star a")],
+        );
+        map.insert(
+            "b.example.com".to_string(),
+            vec![make_opp(ContributionKind::Star, "Star b")],
+        );
+
+        let ranked = rank_contributions(&groups, &map, &EnrichmentMap::new(), Utc::now());
+        let urls: Vec<&str> = ranked.iter().map(|(url, _)| *url).collect();
+        assert_eq!(urls, ["a.example.com", "b.example.com"]);
+    }
+
     #[test]
     fn lookup_empty_map_returns_empty() {
         let map = ContributionMap::new();
@@ -138,4 +313,28 @@ mod tests {
         let result = lookup_contributions("github.com/foo", &[], &map);
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn count_install_reasons_when_known() {
+        let pkg = |reason: InstallReason| InstalledPackage {
+            name: "pkg".to_string(),
+            version: "1.0".to_string(),
+            description: None,
+            url: None,
+            source: crate::discover::PackageSource::Pacman,
+            licenses: Vec::new(),
+            funding: Vec::new(),
+            install_reason: reason,
+            maintainer: None,
+        };
+
+        let packages = vec![
+            pkg(InstallReason::Explicit),
+            pkg(InstallReason::Dependency),
+            pkg(InstallReason::Dependency),
+            pkg(InstallReason::Unknown),
+        ];
+        assert_eq!(count_install_reasons(&packages), Some((1, 2)));
+        assert_eq!(count_install_reasons(&packages[3..]), None);
+    }
 }
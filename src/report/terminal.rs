@@ -5,9 +5,17 @@ use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Utc};
 use comfy_table::{ContentArrangement, Table};
 
+use crate::budget::{self, DonationPlan};
+use crate::contribute::hacktoberfest::PlanWeek;
+use crate::contribute::{OpportunityState, ProjectOpportunity};
 use crate::discover::{InstalledPackage, PackageSource};
 use crate::enrich::EnrichmentMap;
-use crate::report::{ContributionMap, lookup_contributions, lookup_enrichment};
+use crate::project::{
+    Deprecation, FundingStatus, UpstreamProject, VulnerabilitySummary, canonicalize_url,
+};
+use crate::report::{
+    ContributionMap, count_install_reasons, lookup_enrichment, rank_contributions,
+};
 
 /// Sort packages alphabetically by name (case-insensitive), then by source.
 pub fn sort_packages(packages: &mut [InstalledPackage]) {
@@ -23,8 +31,8 @@ pub fn sort_packages(packages: &mut [InstalledPackage]) {
 /// URL ancestor.
 #[derive(Debug)]
 pub struct ProjectGroup<'a> {
-    /// Normalized URL used as the grouping key.  For ancestor groups this is the
-    /// common prefix; for single-project groups it is the exact normalized URL.
+    /// Canonical URL used as the grouping key.  For ancestor groups this is the
+    /// common prefix; for single-project groups it is the exact canonical URL.
     pub url: String,
     /// Individual project URLs within this ancestor group.
     /// Empty for single-project groups and for the no-URL bucket.
@@ -33,43 +41,30 @@ pub struct ProjectGroup<'a> {
     pub packages: Vec<&'a InstalledPackage>,
 }
 
-/// Normalize a URL for grouping purposes.
-///
-/// Strips trailing slashes, the scheme, and a leading "www." so that
-/// `https://www.qemu.org/` and `https://qemu.org` group together.
-pub fn normalize_url(url: &str) -> String {
-    let s = url.trim().trim_end_matches('/');
-    let s = s
-        .strip_prefix("https://")
-        .or_else(|| s.strip_prefix("http://"))
-        .unwrap_or(s);
-    let s = s.strip_prefix("www.").unwrap_or(s);
-    s.to_lowercase()
-}
-
 /// Compute the parent URL by stripping the last path segment.
 ///
-/// Returns `None` for bare domains (no `/` in the normalized URL) and for the
+/// Returns `None` for bare domains (no `/` in the canonical URL) and for the
 /// empty string (no-URL bucket).
-pub fn compute_ancestor(normalized_url: &str) -> Option<&str> {
-    if normalized_url.is_empty() {
+pub fn compute_ancestor(canonical_url: &str) -> Option<&str> {
+    if canonical_url.is_empty() {
         return None;
     }
-    normalized_url.rfind('/').map(|pos| &normalized_url[..pos])
+    canonical_url.rfind('/').map(|pos| &canonical_url[..pos])
 }
 
-/// Group packages by their normalized upstream URL, then merge groups that
-/// share a common URL ancestor when two or more sibling projects exist.
+/// Group packages by their canonical upstream URL (see [`canonicalize_url`]),
+/// then merge groups that share a common URL ancestor when two or more sibling
+/// projects exist.
 ///
 /// Packages without a URL are collected under a single empty-string key.
 /// The returned groups are sorted alphabetically by URL.
 pub fn group_by_project<'a>(packages: &'a [InstalledPackage]) -> Vec<ProjectGroup<'a>> {
-    // Step 1: exact grouping by normalized URL.
+    // Step 1: exact grouping by canonical URL.
     let mut exact_map: HashMap<String, Vec<&'a InstalledPackage>> = HashMap::new();
 
     for pkg in packages {
         let key = match &pkg.url {
-            Some(url) => normalize_url(url),
+            Some(url) => canonicalize_url(url),
             None => String::new(),
         };
         exact_map.entry(key).or_default().push(pkg);
@@ -139,6 +134,44 @@ pub fn paginate<T>(items: &[T], limit: usize) -> (&[T], usize) {
     }
 }
 
+/// Format a project's vulnerability summary as a suffix for its URL cell,
+/// e.g. ` [3 vulnerabilities, high]`.
+fn format_vulnerabilities(vulns: &VulnerabilitySummary) -> String {
+    let noun = if vulns.count == 1 {
+        "vulnerability"
+    } else {
+        "vulnerabilities"
+    };
+    match vulns.max_severity {
+        Some(severity) => format!(" [{} {noun}, {severity}]", vulns.count),
+        None => format!(" [{} {noun}]", vulns.count),
+    }
+}
+
+/// Format how far a platform's funding falls short of the project's goals
+/// as a suffix for its funding link, e.g. ` (3874 EUR short of goal)`.
+fn format_funding_gap(status: &FundingStatus) -> Option<String> {
+    let gap = status.funding_gap()?;
+    if gap <= 0.0 {
+        return Some(" (goal reached)".to_string());
+    }
+    Some(format!(" ({gap:.0} {} short of goal)", status.currency))
+}
+
+/// Format a project's deprecation as a suffix for its URL, e.g.
+/// ` (archived, see https://github.com/new/tool)`.
+fn format_deprecation(deprecation: &Deprecation) -> String {
+    let state = if deprecation.archived {
+        "archived"
+    } else {
+        "deprecated"
+    };
+    match &deprecation.replacement {
+        Some(replacement) => format!(" ({state}, see {replacement})"),
+        None => format!(" ({state})"),
+    }
+}
+
 /// Format a package name with an optional source tag.
 ///
 /// Tags are only shown when the report contains packages from multiple
@@ -208,24 +241,32 @@ pub fn print_summary(
     println!("Total packages:         {}", packages.len());
     println!("Upstream projects:      {}", with_url_count);
     println!("Packages without URL:   {}", without_url_count);
+    if let Some((explicit, dependency)) = count_install_reasons(packages) {
+        println!(
+            "Explicitly installed:   {} ({} dependencies)",
+            explicit, dependency
+        );
+    }
 
-    if !contributions.is_empty() {
-        let projects_with_contribs = groups
-            .iter()
-            .filter(|g| {
-                !g.url.is_empty()
-                    && !lookup_contributions(&g.url, &g.project_urls, contributions).is_empty()
-            })
-            .count();
-        let total_opps: usize = contributions.values().map(|v| v.len()).sum();
+    let ranked_contributions = rank_contributions(&groups, contributions, enrichment, timestamp);
+    if !ranked_contributions.is_empty() {
+        let total_opps: usize = ranked_contributions.iter().map(|(_, o)| o.len()).sum();
         println!(
             "Projects with contributions: {} ({} opportunities)",
-            projects_with_contribs, total_opps
+            ranked_contributions.len(),
+            total_opps
         );
     }
 
     if !enrichment.is_empty() {
         println!("Enriched projects:      {}", enrichment.len());
+        let vulnerable = enrichment
+            .values()
+            .filter(|p| p.vulnerabilities.is_some())
+            .count();
+        if vulnerable > 0 {
+            println!("Known vulnerabilities:  {vulnerable} projects need security attention");
+        }
     }
 
     println!();
@@ -237,7 +278,6 @@ pub fn print_summary(
     detail_table.set_header(vec!["Project URL", "Packages"]);
 
     for group in page {
-        let url_display;
         let base_url = if group.url.is_empty() {
             "(no project URL)".to_string()
         } else if !group.project_urls.is_empty() {
@@ -246,19 +286,33 @@ pub fn print_summary(
             group.url.clone()
         };
         let enriched = lookup_enrichment(&group.url, &group.project_urls, enrichment);
-        let url_cell = if let Some(stars) = enriched.and_then(|e| e.stars) {
-            url_display = format!("{base_url} (\u{2605} {stars})");
-            &url_display
+        let mut url_cell = if let Some(stars) = enriched.and_then(|e| e.stars) {
+            format!("{base_url} (\u{2605} {stars})")
         } else {
-            url_display = base_url;
-            &url_display
+            base_url
         };
+        if let Some(vulns) = enriched.and_then(|e| e.vulnerabilities.as_ref()) {
+            url_cell.push_str(&format_vulnerabilities(vulns));
+        }
+        if enriched
+            .and_then(|e| e.activity.as_ref())
+            .and_then(|a| a.is_dormant(timestamp))
+            == Some(true)
+        {
+            url_cell.push_str(" (dormant)");
+        }
+        if let Some(deprecation) = enriched.and_then(|e| e.deprecation.as_ref()) {
+            url_cell.push_str(&format_deprecation(deprecation));
+        }
+        if enriched.and_then(|e| e.bus_factor) == Some(1) {
+            url_cell.push_str(" (single maintainer)");
+        }
         let pkg_names: Vec<_> = group
             .packages
             .iter()
             .map(|p| format_package_terminal(p, has_multiple_sources))
             .collect();
-        detail_table.add_row(vec![url_cell, &pkg_names.join(", ")]);
+        detail_table.add_row(vec![&url_cell, &pkg_names.join(", ")]);
     }
 
     println!("{detail_table}");
@@ -270,45 +324,30 @@ pub fn print_summary(
         );
     }
 
-    // Ways to Help section
-    if !contributions.is_empty() {
-        let mut contribution_rows: Vec<(&str, Vec<String>)> = Vec::new();
-
-        for group in &groups {
-            if group.url.is_empty() {
-                continue;
-            }
-            let opps = lookup_contributions(&group.url, &group.project_urls, contributions);
-            if !opps.is_empty() {
-                let labels: Vec<String> = opps
-                    .iter()
-                    .map(|o| format!("{}: {}", o.kind, o.title))
-                    .collect();
-                contribution_rows.push((&group.url, labels));
-            }
-        }
-
-        if !contribution_rows.is_empty() {
-            println!();
-            println!("Ways to Help");
-            println!();
-
-            let mut help_table = Table::new();
-            help_table.set_content_arrangement(ContentArrangement::Dynamic);
-            help_table.set_header(vec!["Project", "Opportunities"]);
+    // Ways to Help section, most important projects first
+    if !ranked_contributions.is_empty() {
+        println!();
+        println!("Ways to Help");
+        println!();
 
-            for (url, labels) in &contribution_rows {
-                let joined = labels.join("\n");
-                help_table.add_row(vec![*url, &joined]);
-            }
+        let mut help_table = Table::new();
+        help_table.set_content_arrangement(ContentArrangement::Dynamic);
+        help_table.set_header(vec!["Project", "Opportunities"]);
 
-            println!("{help_table}");
+        for (url, opps) in &ranked_contributions {
+            let labels: Vec<String> = opps
+                .iter()
+                .map(|o| format!("{}: {}", o.kind, o.title))
+                .collect();
+            help_table.add_row(vec![*url, &labels.join("\n")]);
         }
+
+        println!("{help_table}");
     }
 
     // Funding section
     if !enrichment.is_empty() {
-        let mut funding_rows: Vec<(&str, Vec<String>)> = Vec::new();
+        let mut funding_rows: Vec<(String, Vec<String>)> = Vec::new();
 
         for group in &groups {
             if group.url.is_empty() {
@@ -320,9 +359,25 @@ pub fn print_summary(
                 let labels: Vec<String> = proj
                     .funding
                     .iter()
-                    .map(|f| format!("{}: {}", f.platform, f.url))
+                    .map(|f| {
+                        let gap = proj
+                            .funding_status
+                            .iter()
+                            .find(|s| s.platform == f.platform)
+                            .and_then(format_funding_gap)
+                            .unwrap_or_default();
+                        format!("{}: {}{gap}", f.platform, f.url)
+                    })
                     .collect();
-                funding_rows.push((&group.url, labels));
+                // Flag dead projects so their funding links are not mistaken
+                // for a recommendation.
+                let project = match &proj.deprecation {
+                    Some(deprecation) => {
+                        format!("{}{}", group.url, format_deprecation(deprecation))
+                    }
+                    None => group.url.clone(),
+                };
+                funding_rows.push((project, labels));
             }
         }
 
@@ -337,7 +392,7 @@ pub fn print_summary(
 
             for (url, labels) in &funding_rows {
                 let joined = labels.join("\n");
-                funding_table.add_row(vec![*url, &joined]);
+                funding_table.add_row(vec![url, &joined]);
             }
 
             println!("{funding_table}");
@@ -345,9 +400,159 @@ pub fn print_summary(
     }
 }
 
+/// Print contribution opportunities as a table, one row per opportunity.
+pub fn print_opportunities(opportunities: &[ProjectOpportunity]) {
+    if opportunities.is_empty() {
+        println!("No contribution opportunities found.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Project", "Kind", "Opportunity"]);
+    for found in opportunities {
+        let opp = &found.opportunity;
+        let mut cell = opp.title.clone();
+        if let Some(description) = &opp.description {
+            cell.push_str(&format!("\n{description}"));
+        }
+        cell.push_str(&format!("\n{}", opp.url));
+        let mut kind = opp.kind.to_string();
+        if found.state == OpportunityState::Dismissed {
+            kind.push_str("\n(dismissed)");
+        }
+        table.add_row(vec![found.project.clone(), kind, cell]);
+    }
+    println!("{table}");
+    println!("\n{} opportunities", opportunities.len());
+}
+
+/// Print a Hacktoberfest participation plan for October of `year`.
+pub fn print_hacktoberfest_plan(plan: &[PlanWeek], year: i32) {
+    if plan.is_empty() {
+        println!("\nNo Hacktoberfest issues found in the projects you use.");
+        return;
+    }
+
+    println!("\nYour Hacktoberfest {year} plan");
+    println!();
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Week", "Project", "Issue"]);
+    for week in plan {
+        let found = week.opportunity;
+        table.add_row(vec![
+            format!(
+                "{} – {}",
+                week.start.format("%b %-d"),
+                week.end.format("%b %-d")
+            ),
+            found.project.clone(),
+            format!("{}\n{}", found.opportunity.title, found.opportunity.url),
+        ]);
+    }
+    println!("{table}");
+}
+
+/// Print a donation plan, with the sponsorship tier closest to each
+/// allocation where the funding page lists tiers.
+pub fn print_donation_plan(plan: &DonationPlan) {
+    if plan.allocations.is_empty() {
+        println!("None of the projects you use has a known funding channel this strategy can use.");
+        println!("Run `syld report --enrich` to look them up.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Project", "Amount", "Every", "Via", "Why"]);
+    for allocation in &plan.allocations {
+        let every = match allocation.every_n_months {
+            1 => "month".to_string(),
+            n => format!("{n} months"),
+        };
+        let mut via = allocation.via.clone().unwrap_or_default();
+        let tier = allocation
+            .project
+            .funding
+            .iter()
+            .find(|channel| Some(&channel.url) == allocation.via.as_ref())
+            .and_then(|channel| {
+                budget::suggest_tier(channel, allocation.amount, allocation.every_n_months)
+            });
+        if let Some(tier) = tier {
+            via.push_str(&format!("\nTier: {}", tier.name));
+        }
+        table.add_row(vec![
+            allocation.project.name.clone(),
+            format!("{:.2} {}", allocation.amount, plan.currency),
+            every,
+            via,
+            allocation.reason.clone().unwrap_or_default(),
+        ]);
+    }
+    println!("{table}");
+    println!("\n{} projects", plan.allocations.len());
+}
+
+/// Print the known fields of a project, each with the enrichment backend
+/// that supplied it and when.
+pub fn print_project(project: &UpstreamProject) {
+    println!("{}", project.name);
+    println!();
+
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    table.set_header(vec!["Field", "Value", "Source", "Fetched"]);
+    for row in project_rows(project) {
+        table.add_row(row.to_vec());
+    }
+    println!("{table}");
+}
+
+/// Rows of [`print_project`]: field, value as JSON, source, and fetch date,
+/// for every non-empty field, sorted by field name. Funding figures get a
+/// row per platform.
+fn project_rows(project: &UpstreamProject) -> Vec<[String; 4]> {
+    let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(project) else {
+        return vec![];
+    };
+
+    let mut values: Vec<(String, serde_json::Value)> = Vec::new();
+    for (field, value) in fields {
+        match (field.as_str(), value) {
+            ("name" | "provenance", _) => {}
+            ("funding_status", serde_json::Value::Array(statuses)) => {
+                for status in statuses {
+                    let platform = status["platform"].as_str().unwrap_or_default();
+                    values.push((format!("funding_status.{platform}"), status));
+                }
+            }
+            (_, serde_json::Value::Null) => {}
+            (_, serde_json::Value::Array(items)) if items.is_empty() => {}
+            (_, value) => values.push((field, value)),
+        }
+    }
+
+    values
+        .into_iter()
+        .map(|(field, value)| {
+            let (source, fetched) = match project.provenance.get(&field) {
+                Some(source) => (
+                    source.backend.clone(),
+                    source.fetched_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                ),
+                None => ("package metadata".to_string(), String::new()),
+            };
+            [field, value.to_string(), source, fetched]
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::discover::InstallReason;
 
     fn make_pkg(name: &str, source: PackageSource) -> InstalledPackage {
         InstalledPackage {
@@ -357,6 +562,9 @@ mod tests {
             url: None,
             source,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         }
     }
 
@@ -368,6 +576,9 @@ mod tests {
             url: Some(url.to_string()),
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         }
     }
 
@@ -422,40 +633,6 @@ mod tests {
         assert_eq!(names, vec!["aaa", "bbb", "ccc"]);
     }
 
-    // --- normalize_url tests ---
-
-    #[test]
-    fn normalize_strips_trailing_slash() {
-        assert_eq!(normalize_url("https://qemu.org/"), "qemu.org");
-    }
-
-    #[test]
-    fn normalize_strips_scheme() {
-        assert_eq!(normalize_url("https://example.com"), "example.com");
-        assert_eq!(normalize_url("http://example.com"), "example.com");
-    }
-
-    #[test]
-    fn normalize_strips_www() {
-        assert_eq!(normalize_url("https://www.qemu.org/"), "qemu.org");
-    }
-
-    #[test]
-    fn normalize_lowercases() {
-        assert_eq!(
-            normalize_url("https://GitHub.com/Foo/Bar"),
-            "github.com/foo/bar"
-        );
-    }
-
-    #[test]
-    fn normalize_preserves_path() {
-        assert_eq!(
-            normalize_url("https://github.com/user/repo"),
-            "github.com/user/repo"
-        );
-    }
-
     // --- group_by_project tests ---
 
     #[test]
@@ -696,6 +873,76 @@ mod tests {
 
     // --- format_package_terminal tests ---
 
+    #[test]
+    fn format_vulnerability_suffix() {
+        use crate::project::Severity;
+
+        let single = VulnerabilitySummary {
+            count: 1,
+            max_severity: None,
+            ids: vec!["PYSEC-2023-74".to_string()],
+        };
+        assert_eq!(format_vulnerabilities(&single), " [1 vulnerability]");
+
+        let several = VulnerabilitySummary {
+            count: 3,
+            max_severity: Some(Severity::High),
+            ids: vec![],
+        };
+        assert_eq!(
+            format_vulnerabilities(&several),
+            " [3 vulnerabilities, high]"
+        );
+    }
+
+    #[test]
+    fn format_funding_gap_suffix() {
+        use crate::project::{FundingGoal, GoalKind};
+
+        let mut status = FundingStatus {
+            platform: "Liberapay".to_string(),
+            currency: "EUR".to_string(),
+            balance: None,
+            yearly_income: Some(1326.0),
+            goals: vec![],
+        };
+        assert_eq!(format_funding_gap(&status), None);
+
+        status.goals.push(FundingGoal {
+            title: None,
+            amount: 5200.0,
+            kind: GoalKind::YearlyIncome,
+        });
+        assert_eq!(
+            format_funding_gap(&status).as_deref(),
+            Some(" (3874 EUR short of goal)")
+        );
+
+        status.yearly_income = Some(6000.0);
+        assert_eq!(
+            format_funding_gap(&status).as_deref(),
+            Some(" (goal reached)")
+        );
+    }
+
+    #[test]
+    fn format_deprecation_suffix() {
+        let archived = Deprecation {
+            archived: true,
+            replacement: None,
+        };
+        assert_eq!(format_deprecation(&archived), " (archived)");
+
+        let deprecated = Deprecation {
+            archived: false,
+            replacement: Some("https://github.com/new/tool".to_string()),
+        };
+        assert_eq!(
+            format_deprecation(&deprecated),
+            " (deprecated, see https://github.com/new/tool)"
+        );
+    }
+
     #[test]
     fn format_package_without_source() {
         let pkg = make_pkg("firefox", PackageSource::Pacman);
@@ -710,4 +957,53 @@ mod tests {
             "org.gimp.GIMP [flatpak]"
         );
     }
+
+    #[test]
+    fn project_rows_show_sources() {
+        let fetched_at = "2026-03-01T12:00:00Z".parse().unwrap();
+        let project = UpstreamProject {
+            name: "curl".to_string(),
+            repo_url: Some("https://github.com/curl/curl".to_string()),
+            stars: Some(42),
+            funding_status: vec![FundingStatus {
+                platform: "open_collective".to_string(),
+                currency: "USD".to_string(),
+                balance: None,
+                yearly_income: None,
+                goals: vec![],
+            }],
+            provenance: [(
+                "stars".to_string(),
+                crate::project::FieldSource {
+                    backend: "github".to_string(),
+                    fetched_at,
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        let rows = project_rows(&project);
+        let fields: Vec<_> = rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(
+            fields,
+            ["funding_status.open_collective", "repo_url", "stars"]
+        );
+        assert_eq!(
+            rows[1][1..],
+            [
+                "\"https://github.com/curl/curl\"".to_string(),
+                "package metadata".to_string(),
+                String::new()
+            ]
+        );
+        assert_eq!(
+            rows[2][1..],
+            [
+                "42".to_string(),
+                "github".to_string(),
+                "2026-03-01 12:00 UTC".to_string()
+            ]
+        );
+    }
 }
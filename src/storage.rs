@@ -5,6 +5,7 @@
 //! Stores scan results, budget settings, and enrichment cache
 //! in ~/.local/share/syld/syld.db
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use anyhow::{Context, Result};
@@ -13,16 +14,86 @@ use rusqlite::{Connection, params};
 
 use crate::budget::DonationRecord;
 use crate::config::{BudgetConfig, Cadence, Config};
-use crate::discover::{InstalledPackage, PackageSource};
+use crate::contribute::OpportunityState;
+use crate::contribute::past_contributions::PastContribution;
+use crate::discover::{InstallReason, InstalledPackage, Maintainer, PackageSource};
 use crate::project::{FundingChannel, UpstreamProject};
 
 /// A saved scan with its metadata and packages.
 pub struct ScanRecord {
     pub id: i64,
     pub timestamp: DateTime<Utc>,
+    /// The scan an incremental scan was diffed against, or `None` for a full
+    /// snapshot.
+    pub base_scan_id: Option<i64>,
+    /// The full package list, reconstructed for incremental scans.
     pub packages: Vec<InstalledPackage>,
 }
 
+/// Packages added and removed between two scans.
+///
+/// A package whose metadata changed (e.g. a new version) shows up as removed
+/// in its old form and added in its new one.
+#[derive(Debug, Default)]
+pub struct ScanDiff {
+    pub added: Vec<InstalledPackage>,
+    pub removed: Vec<InstalledPackage>,
+}
+
+impl ScanDiff {
+    /// Compare a previous package list with the current one.
+    ///
+    /// Lists are compared as multisets, so duplicate entries are accounted
+    /// for individually.
+    pub fn between(previous: &[InstalledPackage], current: &[InstalledPackage]) -> Result<Self> {
+        let key = package_key;
+
+        let mut previous_counts: HashMap<String, usize> = HashMap::new();
+        for pkg in previous {
+            *previous_counts.entry(key(pkg)?).or_default() += 1;
+        }
+
+        let mut added = Vec::new();
+        let mut current_counts: HashMap<String, usize> = HashMap::new();
+        for pkg in current {
+            let k = key(pkg)?;
+            match previous_counts.get_mut(&k) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    *current_counts.entry(k).or_default() += 1;
+                }
+                _ => added.push(pkg.clone()),
+            }
+        }
+
+        let mut removed = Vec::new();
+        for pkg in previous {
+            let k = key(pkg)?;
+            match current_counts.get_mut(&k) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => removed.push(pkg.clone()),
+            }
+        }
+
+        Ok(Self { added, removed })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Change markers of package rows in incremental scans.
+const CHANGE_ADDED: &str = "added";
+const CHANGE_REMOVED: &str = "removed";
+
+/// Incremental scans saved on top of a full snapshot before the next scan is
+/// saved as a snapshot again, bounding the work of reconstructing one.
+const MAX_INCREMENTAL_SCANS: usize = 30;
+
+/// Days after which a cached enrichment result is discarded.
+pub const ENRICHMENT_MAX_AGE_DAYS: i64 = 30;
+
 /// SQLite-backed local storage for syld state.
 pub struct Storage {
     conn: Connection,
@@ -56,8 +127,9 @@ impl Storage {
             .execute_batch(
                 "
             CREATE TABLE IF NOT EXISTS scans (
-                id        INTEGER PRIMARY KEY AUTOINCREMENT,
-                timestamp TEXT    NOT NULL
+                id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp    TEXT    NOT NULL,
+                base_scan_id INTEGER REFERENCES scans(id)
             );
 
             CREATE TABLE IF NOT EXISTS packages (
@@ -68,7 +140,11 @@ impl Storage {
                 description TEXT,
                 url         TEXT,
                 source      TEXT    NOT NULL,
-                licenses    TEXT    NOT NULL DEFAULT '[]'
+                licenses    TEXT    NOT NULL DEFAULT '[]',
+                funding     TEXT    NOT NULL DEFAULT '[]',
+                install_reason TEXT,
+                maintainer  TEXT,
+                change      TEXT
             );
 
             CREATE INDEX IF NOT EXISTS idx_packages_scan_id ON packages(scan_id);
@@ -79,6 +155,12 @@ impl Storage {
                 cached_at   TEXT    NOT NULL
             );
 
+            CREATE TABLE IF NOT EXISTS url_resolutions (
+                key         TEXT PRIMARY KEY,
+                url         TEXT,
+                resolved_at TEXT NOT NULL
+            );
+
             CREATE TABLE IF NOT EXISTS budget (
                 id       INTEGER PRIMARY KEY CHECK (id = 1),
                 amount   REAL,
@@ -110,80 +192,250 @@ impl Storage {
                 via         TEXT,
                 notes       TEXT
             );
+
+            CREATE TABLE IF NOT EXISTS starred_repos (
+                repo       TEXT PRIMARY KEY,
+                starred_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS opportunity_states (
+                url        TEXT PRIMARY KEY,
+                state      TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS digested_opportunities (
+                url         TEXT PRIMARY KEY,
+                digested_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS contributions (
+                url             TEXT PRIMARY KEY,
+                repo            TEXT NOT NULL,
+                title           TEXT NOT NULL,
+                is_pull_request INTEGER NOT NULL,
+                recorded_at     TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS budget_pins (
+                url       TEXT PRIMARY KEY,
+                amount    REAL,
+                pinned_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS budget_exclusions (
+                url         TEXT PRIMARY KEY,
+                excluded_at TEXT NOT NULL
+            );
             ",
             )
             .context("Failed to run database migrations")?;
+
+        // Columns added after the initial schema. `CREATE TABLE IF NOT EXISTS`
+        // leaves existing tables untouched, so older databases need them
+        // added explicitly.
+        self.add_column_if_missing("packages", "funding", "TEXT NOT NULL DEFAULT '[]'")?;
+        self.add_column_if_missing("packages", "install_reason", "TEXT")?;
+        self.add_column_if_missing("packages", "change", "TEXT")?;
+        self.add_column_if_missing("packages", "maintainer", "TEXT")?;
+        self.add_column_if_missing("scans", "base_scan_id", "INTEGER REFERENCES scans(id)")?;
+
+        // Repology and redirect lookups were once cached as enrichment
+        // results.
+        self.conn.execute(
+            "DELETE FROM enrichment_cache
+             WHERE project_url LIKE 'repology:%' OR project_url LIKE 'redirect:%'",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Add a column to `table` unless it already exists.
+    fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let mut stmt = self.conn.prepare(&format!("PRAGMA table_info({table})"))?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .filter_map(|r| r.ok())
+            .any(|name| name == column);
+
+        if !exists {
+            self.conn
+                .execute_batch(&format!(
+                    "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+                ))
+                .with_context(|| format!("Failed to add column {table}.{column}"))?;
+        }
+
         Ok(())
     }
 
     /// Save a scan with the current timestamp, returning the scan ID.
+    ///
+    /// The full package list is stored as a snapshot.
     pub fn save_scan(&self, packages: &[InstalledPackage]) -> Result<i64> {
-        let now = Utc::now().to_rfc3339();
-
         let tx = self
             .conn
             .unchecked_transaction()
             .context("Failed to begin transaction")?;
 
-        tx.execute("INSERT INTO scans (timestamp) VALUES (?1)", params![now])
-            .context("Failed to insert scan")?;
+        let scan_id = insert_scan(&tx, None)?;
+        insert_packages(&tx, scan_id, packages, None)?;
 
-        let scan_id = tx.last_insert_rowid();
+        tx.commit().context("Failed to commit scan")?;
 
-        let mut stmt = tx.prepare_cached(
-            "INSERT INTO packages (scan_id, name, version, description, url, source, licenses)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        )?;
+        Ok(scan_id)
+    }
 
-        for pkg in packages {
-            let licenses_json =
-                serde_json::to_string(&pkg.licenses).context("Failed to serialize licenses")?;
-            stmt.execute(params![
-                scan_id,
-                pkg.name,
-                pkg.version,
-                pkg.description,
-                pkg.url,
-                pkg.source.to_string(),
-                licenses_json,
-            ])?;
+    /// Save a scan as the difference from the latest one, returning the scan
+    /// ID and the recorded changes.
+    ///
+    /// Only added and removed packages are stored, which keeps repeated scans
+    /// of a mostly unchanged system small. Falls back to a full snapshot when
+    /// there is no previous scan, and saves one again once the previous scan
+    /// builds on [`MAX_INCREMENTAL_SCANS`] incremental ones, or on more
+    /// changes than the snapshot would hold packages.
+    pub fn save_incremental_scan(&self, packages: &[InstalledPackage]) -> Result<(i64, ScanDiff)> {
+        let Some(previous) = self.latest_scan()? else {
+            let scan_id = self.save_scan(packages)?;
+            let diff = ScanDiff {
+                added: packages.to_vec(),
+                removed: Vec::new(),
+            };
+            return Ok((scan_id, diff));
+        };
+
+        let diff = ScanDiff::between(&previous.packages, packages)?;
+
+        let (incremental_scans, changes) = self.chain_size(previous.id)?;
+        if incremental_scans >= MAX_INCREMENTAL_SCANS
+            || changes + diff.added.len() + diff.removed.len() > packages.len()
+        {
+            let scan_id = self.save_scan(packages)?;
+            return Ok((scan_id, diff));
         }
 
-        drop(stmt);
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .context("Failed to begin transaction")?;
+
+        let scan_id = insert_scan(&tx, Some(previous.id))?;
+        insert_packages(&tx, scan_id, &diff.removed, Some(CHANGE_REMOVED))?;
+        insert_packages(&tx, scan_id, &diff.added, Some(CHANGE_ADDED))?;
+
         tx.commit().context("Failed to commit scan")?;
 
-        Ok(scan_id)
+        Ok((scan_id, diff))
     }
 
     /// Retrieve the latest scan with its packages.
     ///
-    /// Returns `None` if no scans exist. Otherwise returns a tuple of
-    /// `(scan_id, timestamp, packages)`.
+    /// Returns `None` if no scans exist.
     pub fn latest_scan(&self) -> Result<Option<ScanRecord>> {
-        let mut stmt = self
+        let row = self
             .conn
-            .prepare("SELECT id, timestamp FROM scans ORDER BY id DESC LIMIT 1")?;
+            .query_row("SELECT id FROM scans ORDER BY id DESC LIMIT 1", [], |row| {
+                row.get::<_, i64>(0)
+            });
 
-        let row = stmt.query_row([], |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
-        });
+        match row {
+            Ok(scan_id) => self.scan(scan_id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e).context("Failed to query latest scan"),
+        }
+    }
 
-        let (scan_id, ts_str) = match row {
-            Ok(r) => r,
-            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
-            Err(e) => return Err(e).context("Failed to query latest scan"),
-        };
+    /// Retrieve a scan by ID, reconstructing the full package list of
+    /// incremental scans from the snapshot they build on.
+    ///
+    /// Returns `None` if the scan does not exist.
+    pub fn scan(&self, scan_id: i64) -> Result<Option<ScanRecord>> {
+        // Walk back to the nearest full snapshot.
+        let mut chain = Vec::new();
+        let mut next = Some(scan_id);
+        while let Some(id) = next {
+            let row = self.conn.query_row(
+                "SELECT timestamp, base_scan_id FROM scans WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?)),
+            );
+            let (ts_str, base_scan_id) = match row {
+                Ok(r) => r,
+                Err(rusqlite::Error::QueryReturnedNoRows) if id == scan_id => return Ok(None),
+                Err(rusqlite::Error::QueryReturnedNoRows) => {
+                    anyhow::bail!("Scan {scan_id} builds on missing scan {id}")
+                }
+                Err(e) => return Err(e).context("Failed to query scan"),
+            };
+            chain.push((id, ts_str, base_scan_id));
+            next = base_scan_id;
+        }
+
+        // Packages in order, with removed ones left as holes, and the
+        // positions of the packages present by key, removals taking any
+        // identical package.
+        let mut slots: Vec<Option<InstalledPackage>> = Vec::new();
+        let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+        for (id, _, _) in chain.iter().rev() {
+            for (change, pkg) in self.scan_rows(*id)? {
+                let key = package_key(&pkg)?;
+                if change.as_deref() == Some(CHANGE_REMOVED) {
+                    if let Some(pos) = positions.get_mut(&key).and_then(Vec::pop) {
+                        slots[pos] = None;
+                    }
+                } else {
+                    positions.entry(key).or_default().push(slots.len());
+                    slots.push(Some(pkg));
+                }
+            }
+        }
+        let packages: Vec<InstalledPackage> = slots.into_iter().flatten().collect();
 
+        let (_, ts_str, base_scan_id) = &chain[0];
         let timestamp: DateTime<Utc> = ts_str
             .parse()
             .with_context(|| format!("Failed to parse timestamp: {ts_str}"))?;
 
+        Ok(Some(ScanRecord {
+            id: scan_id,
+            timestamp,
+            base_scan_id: *base_scan_id,
+            packages,
+        }))
+    }
+
+    /// The number of incremental scans the scan `scan_id` builds on,
+    /// including itself, and the number of changes they store.
+    fn chain_size(&self, scan_id: i64) -> Result<(usize, usize)> {
+        self.conn
+            .query_row(
+                "WITH RECURSIVE chain(id, base_scan_id) AS (
+                     SELECT id, base_scan_id FROM scans WHERE id = ?1
+                     UNION ALL
+                     SELECT scans.id, scans.base_scan_id
+                     FROM scans JOIN chain ON scans.id = chain.base_scan_id
+                 )
+                 SELECT
+                     (SELECT count(*) FROM chain WHERE base_scan_id IS NOT NULL),
+                     (SELECT count(*) FROM packages
+                      WHERE change IS NOT NULL AND scan_id IN (SELECT id FROM chain))",
+                params![scan_id],
+                |row| Ok((row.get::<_, usize>(0)?, row.get::<_, usize>(1)?)),
+            )
+            .context("Failed to query scan chain")
+    }
+
+    /// Read the package rows stored for a single scan, along with their
+    /// change marker (`None` in full snapshots).
+    fn scan_rows(&self, scan_id: i64) -> Result<Vec<(Option<String>, InstalledPackage)>> {
         let mut pkg_stmt = self.conn.prepare(
-            "SELECT name, version, description, url, source, licenses
-             FROM packages WHERE scan_id = ?1",
+            "SELECT name, version, description, url, source, licenses, funding, install_reason,
+                    maintainer, change
+             FROM packages WHERE scan_id = ?1 ORDER BY id",
         )?;
 
-        let packages = pkg_stmt
+        let rows = pkg_stmt
             .query_map(params![scan_id], |row| {
                 Ok((
                     row.get::<_, String>(0)?,
@@ -192,29 +444,56 @@ impl Storage {
                     row.get::<_, Option<String>>(3)?,
                     row.get::<_, String>(4)?,
                     row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                    row.get::<_, Option<String>>(8)?,
+                    row.get::<_, Option<String>>(9)?,
                 ))
             })?
             .map(|r| {
-                let (name, version, description, url, source_str, licenses_json) = r?;
+                let (
+                    name,
+                    version,
+                    description,
+                    url,
+                    source_str,
+                    licenses_json,
+                    funding_json,
+                    reason_str,
+                    maintainer_json,
+                    change,
+                ) = r?;
                 let source = parse_package_source(&source_str)?;
+                let install_reason = reason_str
+                    .as_deref()
+                    .map(parse_install_reason)
+                    .transpose()?
+                    .unwrap_or_default();
                 let licenses: Vec<String> = serde_json::from_str(&licenses_json)
                     .context("Failed to deserialize licenses")?;
-                Ok(InstalledPackage {
+                let funding: Vec<FundingChannel> =
+                    serde_json::from_str(&funding_json).context("Failed to deserialize funding")?;
+                let maintainer: Option<Maintainer> = maintainer_json
+                    .as_deref()
+                    .map(serde_json::from_str)
+                    .transpose()
+                    .context("Failed to deserialize maintainer")?;
+                let pkg = InstalledPackage {
                     name,
                     version,
                     description,
                     url,
                     source,
                     licenses,
-                })
+                    funding,
+                    install_reason,
+                    maintainer,
+                };
+                Ok((change, pkg))
             })
             .collect::<Result<Vec<_>>>()?;
 
-        Ok(Some(ScanRecord {
-            id: scan_id,
-            timestamp,
-            packages,
-        }))
+        Ok(rows)
     }
 
     /// Cache an enrichment result for a project URL.
@@ -232,8 +511,24 @@ impl Storage {
         Ok(())
     }
 
+    /// Replace a cached enrichment result after refreshing some of its
+    /// fields, keeping the time the entry was first cached, from which it
+    /// expires as a whole.
+    pub fn refresh_enrichment(&self, project_url: &str, project: &UpstreamProject) -> Result<()> {
+        let data =
+            serde_json::to_string(project).context("Failed to serialize upstream project")?;
+
+        self.conn.execute(
+            "UPDATE enrichment_cache SET data = ?2 WHERE project_url = ?1",
+            params![project_url, data],
+        )?;
+
+        Ok(())
+    }
+
     /// Get a cached enrichment result, returning `None` if missing or expired
-    /// (older than 7 days).
+    /// (older than [`ENRICHMENT_MAX_AGE_DAYS`]). Individual fields may expire
+    /// sooner (see [`crate::enrich::freshness`]).
     pub fn get_enrichment(&self, project_url: &str) -> Result<Option<UpstreamProject>> {
         let mut stmt = self
             .conn
@@ -253,7 +548,7 @@ impl Storage {
             .parse()
             .with_context(|| format!("Failed to parse cached_at: {cached_at_str}"))?;
 
-        if Utc::now() - cached_at > Duration::days(7) {
+        if Utc::now() - cached_at > Duration::days(ENRICHMENT_MAX_AGE_DAYS) {
             return Ok(None);
         }
 
@@ -263,6 +558,41 @@ impl Storage {
         Ok(Some(project))
     }
 
+    /// Cache the URL a lookup under `key` resolved to, `None` if it found
+    /// nothing.
+    pub fn save_url_resolution(&self, key: &str, url: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO url_resolutions (key, url, resolved_at)
+             VALUES (?1, ?2, ?3)",
+            params![key, url, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// The cached result of the lookup under `key`: `None` if missing or
+    /// older than [`ENRICHMENT_MAX_AGE_DAYS`], `Some(None)` if it found
+    /// nothing.
+    pub fn url_resolution(&self, key: &str) -> Result<Option<Option<String>>> {
+        let row = self.conn.query_row(
+            "SELECT url, resolved_at FROM url_resolutions WHERE key = ?1",
+            params![key],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?)),
+        );
+        let (url, resolved_at) = match row {
+            Ok(r) => r,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(e).context("Failed to query URL resolutions"),
+        };
+
+        let resolved_at: DateTime<Utc> = resolved_at
+            .parse()
+            .with_context(|| format!("Failed to parse resolved_at: {resolved_at}"))?;
+        if Utc::now() - resolved_at > Duration::days(ENRICHMENT_MAX_AGE_DAYS) {
+            return Ok(None);
+        }
+        Ok(Some(url))
+    }
+
     /// Save budget settings (upserts a single row).
     pub fn save_budget(&self, budget: &BudgetConfig) -> Result<()> {
         let cadence_str = match budget.cadence {
@@ -308,9 +638,65 @@ impl Storage {
             amount,
             currency,
             cadence,
+            ..Default::default()
         }))
     }
 
+    /// Always include the project at `url` in donation plans, with a fixed
+    /// `amount` per budget period if given.
+    pub fn pin_project(&self, url: &str, amount: Option<f64>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO budget_pins (url, amount, pinned_at)
+             VALUES (?1, ?2, ?3)",
+            params![url, amount, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Stop pinning the project at `url`. Returns whether it was pinned.
+    pub fn unpin_project(&self, url: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM budget_pins WHERE url = ?1", params![url])?;
+        Ok(removed > 0)
+    }
+
+    /// Pinned project URLs, with their fixed amount if any.
+    pub fn budget_pins(&self) -> Result<HashMap<String, Option<f64>>> {
+        let mut stmt = self.conn.prepare("SELECT url, amount FROM budget_pins")?;
+        let pins = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<HashMap<String, Option<f64>>>>()?;
+        Ok(pins)
+    }
+
+    /// Never allocate to the project at `url` in donation plans.
+    pub fn exclude_project(&self, url: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO budget_exclusions (url, excluded_at) VALUES (?1, ?2)",
+            params![url, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Allow the project at `url` in donation plans again. Returns whether it
+    /// was excluded.
+    pub fn include_project(&self, url: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM budget_exclusions WHERE url = ?1", params![url])?;
+        Ok(removed > 0)
+    }
+
+    /// Excluded project URLs.
+    pub fn budget_exclusions(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT url FROM budget_exclusions")?;
+        let urls = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<String>>>()?;
+        Ok(urls)
+    }
+
     // --- Project CRUD ---
 
     /// Save (upsert) an upstream project, keyed by its repo or homepage URL.
@@ -409,6 +795,7 @@ impl Storage {
                     documentation_url,
                     good_first_issues_url,
                     stars: stars.map(|s| s as u64),
+                    ..Default::default()
                 }))
             }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
@@ -471,6 +858,7 @@ impl Storage {
                     documentation_url,
                     good_first_issues_url,
                     stars: stars.map(|s| s as u64),
+                    ..Default::default()
                 })
             })
             .collect::<Result<Vec<_>>>()?;
@@ -546,6 +934,185 @@ impl Storage {
 
         Ok(rows)
     }
+
+    // --- Starred repositories ---
+
+    /// Record that `syld star` starred a GitHub repository (`owner/repo`).
+    pub fn record_starred(&self, repo: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO starred_repos (repo, starred_at) VALUES (?1, ?2)",
+            params![repo.to_lowercase(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// GitHub repositories (`owner/repo`, lowercase) starred by `syld star`.
+    pub fn starred_repos(&self) -> Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT repo FROM starred_repos")?;
+        let repos = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<HashSet<_>>>()?;
+        Ok(repos)
+    }
+
+    // --- Contribution opportunities ---
+
+    /// Record what the user did about the opportunity at `url`. Setting it
+    /// back to [`OpportunityState::New`] forgets it.
+    pub fn set_opportunity_state(&self, url: &str, state: OpportunityState) -> Result<()> {
+        if state == OpportunityState::New {
+            self.conn.execute(
+                "DELETE FROM opportunity_states WHERE url = ?1",
+                params![url],
+            )?;
+        } else {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO opportunity_states (url, state, updated_at)
+                 VALUES (?1, ?2, ?3)",
+                params![url, state.as_str(), Utc::now().to_rfc3339()],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Recorded opportunity states by URL.
+    pub fn opportunity_states(&self) -> Result<HashMap<String, OpportunityState>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT url, state FROM opportunity_states")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(url, state)| Some((url, OpportunityState::from_db(&state)?)))
+            .collect())
+    }
+
+    /// Record that a digest included the opportunities at `urls`.
+    pub fn record_digest(&self, urls: &[&str]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let now = Utc::now().to_rfc3339();
+        for url in urls {
+            tx.execute(
+                "INSERT OR REPLACE INTO digested_opportunities (url, digested_at)
+                 VALUES (?1, ?2)",
+                params![url, now],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// URLs of the opportunities included in earlier digests.
+    pub fn digested_urls(&self) -> Result<HashSet<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT url FROM digested_opportunities")?;
+        let urls = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<String>>>()?;
+        Ok(urls)
+    }
+
+    // --- Past contributions ---
+
+    /// Record an issue or pull request the user opened in a dependency.
+    pub fn record_contribution(&self, contribution: &PastContribution) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO contributions
+             (url, repo, title, is_pull_request, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                contribution.url,
+                contribution.repo,
+                contribution.title,
+                contribution.is_pull_request,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// GitHub repositories (`owner/repo`, lowercase) the user contributed to.
+    pub fn contributed_repos(&self) -> Result<HashSet<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT repo FROM contributions")?;
+        let repos = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<String>>>()?;
+        Ok(repos)
+    }
+}
+
+/// Insert a scan row with the current timestamp, returning its ID.
+fn insert_scan(conn: &Connection, base_scan_id: Option<i64>) -> Result<i64> {
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO scans (timestamp, base_scan_id) VALUES (?1, ?2)",
+        params![now, base_scan_id],
+    )
+    .context("Failed to insert scan")?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Insert package rows for a scan, tagged with an optional change marker.
+fn insert_packages(
+    conn: &Connection,
+    scan_id: i64,
+    packages: &[InstalledPackage],
+    change: Option<&str>,
+) -> Result<()> {
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO packages (scan_id, name, version, description, url, source, licenses, funding, install_reason, maintainer, change)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+    )?;
+
+    for pkg in packages {
+        let licenses_json =
+            serde_json::to_string(&pkg.licenses).context("Failed to serialize licenses")?;
+        let funding_json =
+            serde_json::to_string(&pkg.funding).context("Failed to serialize funding")?;
+        let maintainer_json = pkg
+            .maintainer
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .context("Failed to serialize maintainer")?;
+        stmt.execute(params![
+            scan_id,
+            pkg.name,
+            pkg.version,
+            pkg.description,
+            pkg.url,
+            pkg.source.to_string(),
+            licenses_json,
+            funding_json,
+            pkg.install_reason.to_string(),
+            maintainer_json,
+            change,
+        ])?;
+    }
+
+    Ok(())
+}
+
+/// Key identifying identical packages when comparing scans.
+fn package_key(pkg: &InstalledPackage) -> Result<String> {
+    serde_json::to_string(pkg).context("Failed to serialize package")
+}
+
+/// Parse a stored install reason back into the enum.
+fn parse_install_reason(s: &str) -> Result<InstallReason> {
+    match s {
+        "explicit" => Ok(InstallReason::Explicit),
+        "dependency" => Ok(InstallReason::Dependency),
+        "unknown" => Ok(InstallReason::Unknown),
+        other => anyhow::bail!("Unknown install reason: {other}"),
+    }
 }
 
 /// Parse a package source string back into the enum.
@@ -561,6 +1128,48 @@ fn parse_package_source(s: &str) -> Result<PackageSource> {
         "brew" => Ok(PackageSource::Brew),
         "docker" => Ok(PackageSource::Docker),
         "podman" => Ok(PackageSource::Podman),
+        "cargo" => Ok(PackageSource::Cargo),
+        "pipx" => Ok(PackageSource::Pipx),
+        "pip" => Ok(PackageSource::Pip),
+        "npm" => Ok(PackageSource::Npm),
+        "go" => Ok(PackageSource::Go),
+        "gem" => Ok(PackageSource::Gem),
+        "aur" => Ok(PackageSource::Aur),
+        "apk" => Ok(PackageSource::Apk),
+        "eopkg" => Ok(PackageSource::Eopkg),
+        "pkg" => Ok(PackageSource::FreeBsdPkg),
+        "winget" => Ok(PackageSource::Winget),
+        "scoop" => Ok(PackageSource::Scoop),
+        "choco" => Ok(PackageSource::Choco),
+        "msys2" => Ok(PackageSource::Msys2),
+        "termux" => Ok(PackageSource::Termux),
+        "rustup" => Ok(PackageSource::Rustup),
+        "uv" => Ok(PackageSource::Uv),
+        "python-lockfile" => Ok(PackageSource::PythonLockfile),
+        "deno" => Ok(PackageSource::Deno),
+        "bun" => Ok(PackageSource::Bun),
+        "pnpm" => Ok(PackageSource::Pnpm),
+        "yarn" => Ok(PackageSource::Yarn),
+        "dotnet" => Ok(PackageSource::Dotnet),
+        "composer" => Ok(PackageSource::Composer),
+        "opam" => Ok(PackageSource::Opam),
+        "ghc-pkg" => Ok(PackageSource::GhcPkg),
+        "vscode" => Ok(PackageSource::Vscode),
+        "browser-extension" => Ok(PackageSource::BrowserExtension),
+        "plasma" => Ok(PackageSource::Plasma),
+        "vim-plugin" => Ok(PackageSource::VimPlugin),
+        "shell-plugin" => Ok(PackageSource::ShellPlugin),
+        "gh-extension" => Ok(PackageSource::GhExtension),
+        "krew" => Ok(PackageSource::Krew),
+        "helm" => Ok(PackageSource::Helm),
+        "kubernetes" => Ok(PackageSource::Kubernetes),
+        "distrobox" => Ok(PackageSource::Distrobox),
+        "incus" => Ok(PackageSource::Incus),
+        "fwupd" => Ok(PackageSource::Fwupd),
+        "font" => Ok(PackageSource::Font),
+        "game" => Ok(PackageSource::Game),
+        "lockfile" => Ok(PackageSource::Lockfile),
+        "fdroid" => Ok(PackageSource::Fdroid),
         other => anyhow::bail!("Unknown package source: {other}"),
     }
 }
@@ -584,6 +1193,9 @@ mod tests {
                 url: Some("https://www.mozilla.org/firefox/".to_string()),
                 source: PackageSource::Pacman,
                 licenses: vec!["MPL-2.0".to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
             InstalledPackage {
                 name: "linux".to_string(),
@@ -592,6 +1204,9 @@ mod tests {
                 url: Some("https://kernel.org".to_string()),
                 source: PackageSource::Pacman,
                 licenses: vec!["GPL-2.0".to_string()],
+                funding: Vec::new(),
+                install_reason: InstallReason::Unknown,
+                maintainer: None,
             },
         ]
     }
@@ -613,7 +1228,14 @@ mod tests {
         assert_eq!(count("scans"), 0);
         assert_eq!(count("packages"), 0);
         assert_eq!(count("enrichment_cache"), 0);
+        assert_eq!(count("url_resolutions"), 0);
+        assert_eq!(count("starred_repos"), 0);
+        assert_eq!(count("opportunity_states"), 0);
+        assert_eq!(count("contributions"), 0);
+        assert_eq!(count("digested_opportunities"), 0);
         assert_eq!(count("budget"), 0);
+        assert_eq!(count("budget_pins"), 0);
+        assert_eq!(count("budget_exclusions"), 0);
         assert_eq!(count("projects"), 0);
         assert_eq!(count("donation_history"), 0);
     }
@@ -625,6 +1247,30 @@ mod tests {
         storage.migrate().expect("second migration should succeed");
     }
 
+    #[test]
+    fn migrate_adds_missing_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE packages (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                scan_id     INTEGER NOT NULL,
+                name        TEXT    NOT NULL,
+                version     TEXT    NOT NULL,
+                description TEXT,
+                url         TEXT,
+                source      TEXT    NOT NULL,
+                licenses    TEXT    NOT NULL DEFAULT '[]'
+            );",
+        )
+        .unwrap();
+        let storage = Storage { conn };
+        storage.migrate().expect("migration of old schema failed");
+
+        storage
+            .save_scan(&sample_packages())
+            .expect("save_scan on migrated db failed");
+    }
+
     // --- Scan tests ---
 
     #[test]
@@ -659,6 +1305,62 @@ mod tests {
         assert_eq!(scan.packages[1].description, None);
     }
 
+    #[test]
+    fn save_and_retrieve_package_funding() {
+        let storage = open_memory();
+        let mut packages = sample_packages();
+        packages[0].funding = vec![FundingChannel {
+            platform: "Open Collective".to_string(),
+            url: "https://opencollective.com/mozilla".to_string(),
+            page: None,
+        }];
+
+        storage.save_scan(&packages).expect("save_scan failed");
+        let scan = storage.latest_scan().unwrap().unwrap();
+
+        assert_eq!(scan.packages[0].funding, packages[0].funding);
+        assert!(scan.packages[1].funding.is_empty());
+    }
+
+    #[test]
+    fn save_and_retrieve_install_reason() {
+        let storage = open_memory();
+        let mut packages = sample_packages();
+        packages[0].install_reason = InstallReason::Explicit;
+        packages[1].install_reason = InstallReason::Dependency;
+
+        storage.save_scan(&packages).expect("save_scan failed");
+        let scan = storage.latest_scan().unwrap().unwrap();
+
+        assert_eq!(scan.packages[0].install_reason, InstallReason::Explicit);
+        assert_eq!(scan.packages[1].install_reason, InstallReason::Dependency);
+
+        // Rows saved before the column existed read back as unknown.
+        storage
+            .conn
+            .execute("UPDATE packages SET install_reason = NULL", [])
+            .unwrap();
+        let scan = storage.latest_scan().unwrap().unwrap();
+        assert_eq!(scan.packages[0].install_reason, InstallReason::Unknown);
+    }
+
+    #[test]
+    fn save_and_retrieve_maintainer() {
+        let storage = open_memory();
+        let mut packages = sample_packages();
+        packages[0].maintainer = Some(Maintainer::Account("jguer".to_string()));
+        packages[1].maintainer = Some(Maintainer::Orphaned);
+
+        storage.save_scan(&packages).expect("save_scan failed");
+        let scan = storage.latest_scan().unwrap().unwrap();
+
+        assert_eq!(
+            scan.packages[0].maintainer,
+            Some(Maintainer::Account("jguer".to_string()))
+        );
+        assert_eq!(scan.packages[1].maintainer, Some(Maintainer::Orphaned));
+    }
+
     #[test]
     fn latest_scan_returns_newest() {
         let storage = open_memory();
@@ -670,6 +1372,9 @@ mod tests {
             url: None,
             source: PackageSource::Apt,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         }];
         storage.save_scan(&pkgs1).expect("first save");
 
@@ -680,6 +1385,9 @@ mod tests {
             url: None,
             source: PackageSource::Dnf,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         }];
         let id2 = storage.save_scan(&pkgs2).expect("second save");
 
@@ -700,6 +1408,161 @@ mod tests {
         assert!(result.is_none());
     }
 
+    // --- Incremental scan tests ---
+
+    fn package_names(scan: &ScanRecord) -> Vec<String> {
+        let mut names: Vec<String> = scan
+            .packages
+            .iter()
+            .map(|p| format!("{}-{}", p.name, p.version))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn incremental_scan_stores_only_changes() {
+        let storage = open_memory();
+        let first = storage.save_scan(&sample_packages()).unwrap();
+
+        let mut packages = sample_packages();
+        packages[0].version = "129.0".to_string();
+        packages.push(InstalledPackage {
+            name: "git".to_string(),
+            version: "2.45.2".to_string(),
+            description: None,
+            url: Some("https://git-scm.com".to_string()),
+            source: PackageSource::Pacman,
+            licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Explicit,
+            maintainer: None,
+        });
+
+        let (second, diff) = storage.save_incremental_scan(&packages).unwrap();
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].version, "128.0");
+
+        let stored: i64 = storage
+            .conn
+            .query_row(
+                "SELECT count(*) FROM packages WHERE scan_id = ?1",
+                params![second],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(stored, 3);
+
+        let scan = storage.latest_scan().unwrap().unwrap();
+        assert_eq!(scan.id, second);
+        assert_eq!(scan.base_scan_id, Some(first));
+        assert_eq!(
+            package_names(&scan),
+            vec!["firefox-129.0", "git-2.45.2", "linux-6.9.7"]
+        );
+        assert_eq!(scan.packages[2].install_reason, InstallReason::Explicit);
+    }
+
+    #[test]
+    fn incremental_scans_chain_and_keep_history() {
+        let storage = open_memory();
+        let first = storage.save_scan(&sample_packages()).unwrap();
+
+        let (_, diff) = storage
+            .save_incremental_scan(&sample_packages()[..1])
+            .unwrap();
+        assert_eq!(diff.removed.len(), 1);
+
+        let (third, diff) = storage.save_incremental_scan(&sample_packages()).unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.removed.is_empty());
+
+        let latest = storage.scan(third).unwrap().unwrap();
+        assert_eq!(package_names(&latest), vec!["firefox-128.0", "linux-6.9.7"]);
+
+        let original = storage.scan(first).unwrap().unwrap();
+        assert_eq!(original.base_scan_id, None);
+        assert_eq!(original.packages.len(), 2);
+
+        assert!(storage.scan(42).unwrap().is_none());
+    }
+
+    #[test]
+    fn incremental_scan_without_previous_is_full() {
+        let storage = open_memory();
+        let (id, diff) = storage.save_incremental_scan(&sample_packages()).unwrap();
+        assert_eq!(diff.added.len(), 2);
+
+        let scan = storage.latest_scan().unwrap().unwrap();
+        assert_eq!(scan.id, id);
+        assert_eq!(scan.base_scan_id, None);
+        assert_eq!(scan.packages.len(), 2);
+    }
+
+    #[test]
+    fn unchanged_incremental_scan_is_empty() {
+        let storage = open_memory();
+        storage.save_scan(&sample_packages()).unwrap();
+        let (_, diff) = storage.save_incremental_scan(&sample_packages()).unwrap();
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn long_scan_chains_are_snapshotted() {
+        let storage = open_memory();
+        let packages: Vec<InstalledPackage> = (0..2 * MAX_INCREMENTAL_SCANS)
+            .map(|i| InstalledPackage {
+                name: format!("package-{i}"),
+                ..sample_packages()[0].clone()
+            })
+            .collect();
+        let first = storage.save_scan(&packages).unwrap();
+
+        // Drop and restore the last package: a change per scan.
+        let mut previous = first;
+        for i in 0..MAX_INCREMENTAL_SCANS {
+            let current = &packages[..packages.len() - (i + 1) % 2];
+            let (id, _) = storage.save_incremental_scan(current).unwrap();
+            let scan = storage.scan(id).unwrap().unwrap();
+            assert_eq!(scan.base_scan_id, Some(previous));
+            assert_eq!(scan.packages.len(), current.len());
+            previous = id;
+        }
+
+        let (id, diff) = storage.save_incremental_scan(&packages[1..]).unwrap();
+        assert_eq!(diff.removed.len(), 1);
+        let scan = storage.scan(id).unwrap().unwrap();
+        assert_eq!(scan.base_scan_id, None);
+        assert_eq!(scan.packages.len(), packages.len() - 1);
+    }
+
+    #[test]
+    fn scans_with_many_changes_are_snapshotted() {
+        let storage = open_memory();
+        storage.save_scan(&sample_packages()).unwrap();
+
+        let mut packages = sample_packages();
+        for pkg in &mut packages {
+            pkg.version.push_str("-1");
+        }
+        let (id, diff) = storage.save_incremental_scan(&packages).unwrap();
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(diff.removed.len(), 2);
+        assert_eq!(storage.scan(id).unwrap().unwrap().base_scan_id, None);
+    }
+
+    #[test]
+    fn diff_counts_duplicates() {
+        let packages = sample_packages();
+        let previous = vec![packages[0].clone(), packages[0].clone()];
+        let current = vec![packages[0].clone()];
+
+        let diff = ScanDiff::between(&previous, &current).unwrap();
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.removed.len(), 1);
+    }
+
     #[test]
     fn save_empty_scan() {
         let storage = open_memory();
@@ -727,13 +1590,10 @@ mod tests {
             funding: vec![FundingChannel {
                 platform: "Open Collective".to_string(),
                 url: "https://opencollective.com/firefox".to_string(),
+                page: None,
             }],
             bug_tracker: Some("https://bugzilla.mozilla.org".to_string()),
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         storage
@@ -770,16 +1630,7 @@ mod tests {
 
         let project1 = UpstreamProject {
             name: "Old".to_string(),
-            repo_url: None,
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
         storage
             .save_enrichment("https://example.org", &project1)
@@ -787,16 +1638,7 @@ mod tests {
 
         let project2 = UpstreamProject {
             name: "New".to_string(),
-            repo_url: None,
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
         storage
             .save_enrichment("https://example.org", &project2)
@@ -809,8 +1651,54 @@ mod tests {
         assert_eq!(loaded.name, "New");
     }
 
+    #[test]
+    fn refreshed_enrichment_keeps_its_age() {
+        let storage = open_memory();
+        let project = UpstreamProject {
+            name: "Refreshed".to_string(),
+            stars: Some(10),
+            ..Default::default()
+        };
+        let old = (Utc::now() - Duration::days(ENRICHMENT_MAX_AGE_DAYS + 1)).to_rfc3339();
+        storage
+            .conn
+            .execute(
+                "INSERT INTO enrichment_cache (project_url, data, cached_at) VALUES (?1, ?2, ?3)",
+                params!["example.org", "{}", old],
+            )
+            .unwrap();
+
+        storage.refresh_enrichment("example.org", &project).unwrap();
+        assert!(storage.get_enrichment("example.org").unwrap().is_none());
+
+        storage.save_enrichment("example.org", &project).unwrap();
+        let loaded = storage.get_enrichment("example.org").unwrap().unwrap();
+        assert_eq!(loaded.stars, Some(10));
+    }
+
     // --- Budget tests ---
 
+    #[test]
+    fn save_and_get_url_resolutions() {
+        let storage = open_memory();
+        assert_eq!(storage.url_resolution("repology:zstd").unwrap(), None);
+
+        storage
+            .save_url_resolution("repology:zstd", Some("https://github.com/facebook/zstd"))
+            .unwrap();
+        storage
+            .save_url_resolution("repology:unknown", None)
+            .unwrap();
+        assert_eq!(
+            storage.url_resolution("repology:zstd").unwrap(),
+            Some(Some("https://github.com/facebook/zstd".to_string()))
+        );
+        assert_eq!(
+            storage.url_resolution("repology:unknown").unwrap(),
+            Some(None)
+        );
+    }
+
     #[test]
     fn save_and_get_budget() {
         let storage = open_memory();
@@ -818,6 +1706,7 @@ mod tests {
             amount: Some(25.0),
             currency: "EUR".to_string(),
             cadence: Cadence::Yearly,
+            ..Default::default()
         };
 
         storage.save_budget(&budget).expect("save budget failed");
@@ -847,6 +1736,7 @@ mod tests {
             amount: Some(10.0),
             currency: "USD".to_string(),
             cadence: Cadence::Monthly,
+            ..Default::default()
         };
         storage.save_budget(&budget1).unwrap();
 
@@ -854,6 +1744,7 @@ mod tests {
             amount: Some(50.0),
             currency: "GBP".to_string(),
             cadence: Cadence::Yearly,
+            ..Default::default()
         };
         storage.save_budget(&budget2).unwrap();
 
@@ -870,6 +1761,7 @@ mod tests {
             amount: None,
             currency: "USD".to_string(),
             cadence: Cadence::Monthly,
+            ..Default::default()
         };
         storage.save_budget(&budget).unwrap();
 
@@ -877,6 +1769,30 @@ mod tests {
         assert!(loaded.amount.is_none());
     }
 
+    #[test]
+    fn pin_and_exclude_projects() {
+        let storage = open_memory();
+        storage.pin_project("curl.se", Some(5.0)).unwrap();
+        storage.pin_project("vim.org", None).unwrap();
+        storage.pin_project("curl.se", Some(8.0)).unwrap();
+        let pins = storage.budget_pins().unwrap();
+        assert_eq!(pins.len(), 2);
+        assert_eq!(pins["curl.se"], Some(8.0));
+        assert_eq!(pins["vim.org"], None);
+        assert!(storage.unpin_project("vim.org").unwrap());
+        assert!(!storage.unpin_project("vim.org").unwrap());
+
+        storage.exclude_project("google.com/chrome").unwrap();
+        assert!(
+            storage
+                .budget_exclusions()
+                .unwrap()
+                .contains("google.com/chrome")
+        );
+        assert!(storage.include_project("google.com/chrome").unwrap());
+        assert!(storage.budget_exclusions().unwrap().is_empty());
+    }
+
     // --- Package source round-trip ---
 
     #[test]
@@ -892,11 +1808,53 @@ mod tests {
             PackageSource::Brew,
             PackageSource::Docker,
             PackageSource::Podman,
+            PackageSource::Cargo,
+            PackageSource::Pipx,
+            PackageSource::Pip,
+            PackageSource::Npm,
+            PackageSource::Go,
+            PackageSource::Gem,
+            PackageSource::Aur,
+            PackageSource::Apk,
+            PackageSource::Eopkg,
+            PackageSource::FreeBsdPkg,
+            PackageSource::Winget,
+            PackageSource::Scoop,
+            PackageSource::Choco,
+            PackageSource::Msys2,
+            PackageSource::Termux,
+            PackageSource::Rustup,
+            PackageSource::Uv,
+            PackageSource::PythonLockfile,
+            PackageSource::Deno,
+            PackageSource::Bun,
+            PackageSource::Pnpm,
+            PackageSource::Yarn,
+            PackageSource::Dotnet,
+            PackageSource::Composer,
+            PackageSource::Opam,
+            PackageSource::GhcPkg,
+            PackageSource::Vscode,
+            PackageSource::BrowserExtension,
+            PackageSource::Plasma,
+            PackageSource::VimPlugin,
+            PackageSource::ShellPlugin,
+            PackageSource::GhExtension,
+            PackageSource::Krew,
+            PackageSource::Helm,
+            PackageSource::Kubernetes,
+            PackageSource::Distrobox,
+            PackageSource::Incus,
+            PackageSource::Fwupd,
+            PackageSource::Font,
+            PackageSource::Game,
+            PackageSource::Lockfile,
+            PackageSource::Fdroid,
         ];
 
         for source in sources {
             let s = source.to_string();
-            let parsed = parse_package_source(&s).expect(&format!("Failed to parse {s}"));
+            let parsed = parse_package_source(&s).unwrap_or_else(|_| panic!("Failed to parse {s}"));
             assert_eq!(parsed, source);
         }
     }
@@ -936,6 +1894,7 @@ mod tests {
             funding: vec![FundingChannel {
                 platform: "Open Collective".to_string(),
                 url: "https://opencollective.com/firefox".to_string(),
+                page: None,
             }],
             bug_tracker: Some("https://bugzilla.mozilla.org".to_string()),
             contributing_url: Some(
@@ -945,6 +1904,7 @@ mod tests {
             documentation_url: Some("https://firefox-source-docs.mozilla.org".to_string()),
             good_first_issues_url: Some("https://codetribute.mozilla.org".to_string()),
             stars: Some(1234),
+            ..Default::default()
         }
     }
 
@@ -989,16 +1949,8 @@ mod tests {
         let storage = open_memory();
         let project = UpstreamProject {
             name: "HomepageOnly".to_string(),
-            repo_url: None,
             homepage: Some("https://example.org".to_string()),
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         storage.save_project(&project).unwrap();
@@ -1011,16 +1963,7 @@ mod tests {
         let storage = open_memory();
         let project = UpstreamProject {
             name: "NoUrl".to_string(),
-            repo_url: None,
-            homepage: None,
-            licenses: vec![],
-            funding: vec![],
-            bug_tracker: None,
-            contributing_url: None,
-            is_open_source: None,
-            documentation_url: None,
-            good_first_issues_url: None,
-            stars: None,
+            ..Default::default()
         };
 
         assert!(storage.save_project(&project).is_err());
@@ -1073,6 +2016,118 @@ mod tests {
         assert_eq!(all.len(), 1);
     }
 
+    // --- Starred repository tests ---
+
+    #[test]
+    fn record_and_list_starred_repos() {
+        let storage = open_memory();
+        assert!(storage.starred_repos().unwrap().is_empty());
+
+        storage.record_starred("curl/curl").unwrap();
+        storage.record_starred("BurntSushi/ripgrep").unwrap();
+        storage.record_starred("curl/curl").unwrap();
+
+        let starred = storage.starred_repos().unwrap();
+        assert_eq!(starred.len(), 2);
+        assert!(starred.contains("burntsushi/ripgrep"));
+    }
+
+    // --- Opportunity state tests ---
+
+    #[test]
+    fn set_and_reset_opportunity_states() {
+        let storage = open_memory();
+        let issue = "https://github.com/curl/curl/issues/1";
+        let translation = "https://hosted.weblate.org/projects/gimp/";
+        storage
+            .set_opportunity_state(issue, OpportunityState::Dismissed)
+            .unwrap();
+        storage
+            .set_opportunity_state(issue, OpportunityState::Done)
+            .unwrap();
+        storage
+            .set_opportunity_state(translation, OpportunityState::Dismissed)
+            .unwrap();
+
+        let states = storage.opportunity_states().unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[issue], OpportunityState::Done);
+        assert_eq!(states[translation], OpportunityState::Dismissed);
+
+        storage
+            .set_opportunity_state(translation, OpportunityState::New)
+            .unwrap();
+        assert!(
+            !storage
+                .opportunity_states()
+                .unwrap()
+                .contains_key(translation)
+        );
+    }
+
+    #[test]
+    fn record_and_list_digested_urls() {
+        let storage = open_memory();
+        assert!(storage.digested_urls().unwrap().is_empty());
+
+        storage
+            .record_digest(&["https://github.com/curl/curl/issues/1"])
+            .unwrap();
+        storage
+            .record_digest(&[
+                "https://github.com/curl/curl/issues/1",
+                "https://hosted.weblate.org/projects/gimp/",
+            ])
+            .unwrap();
+
+        let urls = storage.digested_urls().unwrap();
+        assert_eq!(urls.len(), 2);
+        assert!(urls.contains("https://hosted.weblate.org/projects/gimp/"));
+    }
+
+    // --- Past contribution tests ---
+
+    #[test]
+    fn record_and_list_contributed_repos() {
+        let storage = open_memory();
+        assert!(storage.contributed_repos().unwrap().is_empty());
+
+        let contribution = |url: &str, repo: &str| PastContribution {
+            repo: repo.to_string(),
+            title: "Fix typo".to_string(),
+            url: url.to_string(),
+            is_pull_request: true,
+        };
+        storage
+            .record_contribution(&contribution(
+                "https://github.com/curl/curl/pull/1",
+                "curl/curl",
+            ))
+            .unwrap();
+        storage
+            .record_contribution(&contribution(
+                "https://github.com/curl/curl/pull/2",
+                "curl/curl",
+            ))
+            .unwrap();
+        storage
+            .record_contribution(&contribution(
+                "https://github.com/curl/curl/pull/1",
+                "curl/curl",
+            ))
+            .unwrap();
+        storage
+            .record_contribution(&contribution(
+                "https://github.com/burntsushi/ripgrep/issues/3",
+                "burntsushi/ripgrep",
+            ))
+            .unwrap();
+
+        let repos = storage.contributed_repos().unwrap();
+        assert_eq!(repos.len(), 2);
+        assert!(repos.contains("curl/curl"));
+    }
+
     // --- Donation history tests ---
 
     #[test]
@@ -7,7 +7,7 @@ use assert_cmd::cargo::cargo_bin_cmd;
 use predicates::prelude::*;
 
 fn syld(config_home: &std::path::Path) -> Command {
-    let mut cmd: Command = cargo_bin_cmd!("syld").into();
+    let mut cmd: Command = cargo_bin_cmd!("syld");
     cmd.env("XDG_CONFIG_HOME", config_home);
     cmd
 }
@@ -67,6 +67,47 @@ fn config_show_reflects_custom_config() {
         .stdout(predicate::str::contains("cadence = \"yearly\""));
 }
 
+#[test]
+fn config_show_redacts_github_token() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config_dir = tmp.path().join("syld");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        "[github]\ntoken = \"ghp_s3cr3t\"\n",
+    )
+    .unwrap();
+
+    syld(tmp.path())
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("token = \"<redacted>\""))
+        .stdout(predicate::str::contains("ghp_s3cr3t").not())
+        .stderr(predicate::str::contains("ghp_s3cr3t").not());
+}
+
+#[test]
+fn config_show_redacts_mastodon_token() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config_dir = tmp.path().join("syld");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        "[mastodon]\ninstance = \"mastodon.social\"\ntoken = \"m4st0d0n-s3cr3t\"\n",
+    )
+    .unwrap();
+
+    syld(tmp.path())
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("instance = \"mastodon.social\""))
+        .stdout(predicate::str::contains("token = \"<redacted>\""))
+        .stdout(predicate::str::contains("m4st0d0n-s3cr3t").not())
+        .stderr(predicate::str::contains("m4st0d0n-s3cr3t").not());
+}
+
 #[test]
 fn config_show_output_is_valid_toml() {
     let tmp = tempfile::tempdir().unwrap();
@@ -6,11 +6,12 @@ use assert_cmd::Command;
 use assert_cmd::cargo::cargo_bin_cmd;
 use predicates::prelude::*;
 
-use syld::discover::{InstalledPackage, PackageSource};
+use syld::discover::{InstallReason, InstalledPackage, PackageSource};
+use syld::project::FundingChannel;
 use syld::storage::Storage;
 
 fn syld_with_db(config_home: &Path, data_home: &Path) -> Command {
-    let mut cmd: Command = cargo_bin_cmd!("syld").into();
+    let mut cmd: Command = cargo_bin_cmd!("syld");
     cmd.env("XDG_CONFIG_HOME", config_home);
     cmd.env("XDG_DATA_HOME", data_home);
     cmd
@@ -29,6 +30,9 @@ fn seed_multi_source_scan(data_home: &Path) {
         url: None,
         source: PackageSource::Flatpak,
         licenses: vec![],
+        funding: Vec::new(),
+        install_reason: InstallReason::Unknown,
+        maintainer: None,
     });
     seed_scan_packages(data_home, &packages);
 }
@@ -49,6 +53,9 @@ fn ancestor_group_packages() -> Vec<InstalledPackage> {
             url: Some("https://0pointer.de/lennart/projects/libdaemon".to_string()),
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         },
         InstalledPackage {
             name: "nss-mdns".to_string(),
@@ -57,6 +64,9 @@ fn ancestor_group_packages() -> Vec<InstalledPackage> {
             url: Some("https://0pointer.de/lennart/projects/nss-mdns".to_string()),
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         },
         InstalledPackage {
             name: "linux".to_string(),
@@ -65,6 +75,9 @@ fn ancestor_group_packages() -> Vec<InstalledPackage> {
             url: Some("https://kernel.org".to_string()),
             source: PackageSource::Pacman,
             licenses: vec!["GPL-2.0".to_string()],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         },
     ]
 }
@@ -78,6 +91,9 @@ fn single_source_packages() -> Vec<InstalledPackage> {
             url: Some("https://www.mozilla.org/firefox/".to_string()),
             source: PackageSource::Pacman,
             licenses: vec!["MPL-2.0".to_string()],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         },
         InstalledPackage {
             name: "linux".to_string(),
@@ -86,6 +102,9 @@ fn single_source_packages() -> Vec<InstalledPackage> {
             url: Some("https://kernel.org".to_string()),
             source: PackageSource::Pacman,
             licenses: vec!["GPL-2.0".to_string()],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         },
         InstalledPackage {
             name: "orphan".to_string(),
@@ -94,6 +113,9 @@ fn single_source_packages() -> Vec<InstalledPackage> {
             url: None,
             source: PackageSource::Pacman,
             licenses: vec![],
+            funding: Vec::new(),
+            install_reason: InstallReason::Unknown,
+            maintainer: None,
         },
     ]
 }
@@ -376,3 +398,397 @@ fn report_json_ancestor_validates_against_schema() {
     jsonschema::validate(&schema, &instance)
         .expect("JSON report with ancestor groups should validate against the schema");
 }
+
+#[test]
+fn project_show_lists_field_sources() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    let project: syld::project::UpstreamProject = serde_json::from_value(serde_json::json!({
+        "name": "firefox",
+        "repo_url": "https://www.mozilla.org/firefox/",
+        "homepage": null,
+        "licenses": ["MPL-2.0"],
+        "funding": [],
+        "bug_tracker": "https://bugzilla.mozilla.org",
+        "contributing_url": null,
+        "provenance": {
+            "bug_tracker": {"backend": "wikidata", "fetched_at": "2026-03-01T12:00:00Z"}
+        }
+    }))
+    .unwrap();
+    let storage = Storage::open_path(&data.path().join("syld/syld.db")).unwrap();
+    storage
+        .save_enrichment("mozilla.org/firefox", &project)
+        .unwrap();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["project", "show", "firefox"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bug_tracker"))
+        .stdout(predicate::str::contains("wikidata"))
+        .stdout(predicate::str::contains("package metadata"));
+}
+
+#[test]
+fn project_show_without_enrichment_shows_message() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["project", "show", "linux"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No enrichment data for linux"));
+}
+
+#[test]
+fn report_enrich_offline_uses_offline_backends_only() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["--offline", "report", "--enrich"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Offline"))
+        .stderr(predicate::str::contains("github").not())
+        .stdout(predicate::str::contains("firefox"));
+}
+
+#[test]
+fn star_without_token_explains_why() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .env_remove("GITHUB_TOKEN")
+        .args(["star", "--all"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("needs a GitHub token"));
+}
+
+#[test]
+fn star_options_conflict() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["star", "--all", "--interactive"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn share_prints_summary() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .arg("share")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "I depend on 2 open source projects.",
+        ));
+}
+
+#[test]
+fn share_on_mastodon_without_account_explains_why() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .env_remove("MASTODON_TOKEN")
+        .args(["share", "--mastodon"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("needs [mastodon] instance"));
+}
+
+#[test]
+fn contribute_offline_outputs_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    let output = syld_with_db(tmp.path(), data.path())
+        .args(["contribute", "--offline", "--format", "json"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Offline"))
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["total_opportunities"], 0);
+    assert!(json["opportunities"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn contribute_rejects_unknown_kind() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["contribute", "--kind", "fame"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("good-first-issue"));
+}
+
+#[test]
+fn contribute_without_scan_shows_message() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["contribute", "--offline"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No scan data found"));
+}
+
+#[test]
+fn contribute_done_is_counted_in_report() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .args([
+            "contribute",
+            "done",
+            "https://github.com/curl/curl/issues/1",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Marked as done: https://github.com/curl/curl/issues/1",
+        ));
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["report", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "You have completed 1 contribution(s)",
+        ));
+}
+
+#[test]
+fn digest_lists_only_new_opportunities() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("syld")).unwrap();
+    std::fs::write(
+        tmp.path().join("syld/config.toml"),
+        "offline = true\n\n[digest]\ndesktop = false\n",
+    )
+    .unwrap();
+    let mut packages = single_source_packages();
+    packages[0].funding = vec![FundingChannel {
+        platform: "Open Collective".to_string(),
+        url: "https://opencollective.com/firefox".to_string(),
+        page: None,
+    }];
+    seed_scan_packages(data.path(), &packages);
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["digest", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("syld: 1 new way to contribute"));
+
+    syld_with_db(tmp.path(), data.path())
+        .arg("digest")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tell others about firefox"));
+
+    // Already delivered: nothing to say.
+    syld_with_db(tmp.path(), data.path())
+        .arg("digest")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn hacktoberfest_needs_network() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    seed_scan(data.path());
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["contribute", "--hacktoberfest", "--offline"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Hacktoberfest issues needs network access",
+        ));
+}
+
+#[test]
+fn budget_set_then_show() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "show"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No budget set"));
+
+    syld_with_db(tmp.path(), data.path())
+        .args([
+            "budget",
+            "set",
+            "120",
+            "--cadence",
+            "yearly",
+            "--currency",
+            "eur",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Budget set to 120.00 EUR per year.",
+        ));
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Budget: 120.00 EUR per year"))
+        .stdout(predicate::str::contains("Current period ends on"));
+}
+
+#[test]
+fn budget_set_rejects_invalid_amounts() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "set", "--", "-5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be a positive number"));
+}
+
+#[test]
+fn budget_plan_splits_between_funded_projects() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let mut packages = single_source_packages();
+    packages[0].funding = vec![FundingChannel::from_url(
+        "https://opencollective.com/firefox",
+    )];
+    seed_scan_packages(data.path(), &packages);
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "plan"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No budget set"));
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "set", "10", "--currency", "EUR"])
+        .assert()
+        .success();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("10.00 EUR"))
+        .stdout(predicate::str::contains(
+            "https://opencollective.com/firefox",
+        ));
+
+    let output = syld_with_db(tmp.path(), data.path())
+        .args(["budget", "plan", "--format", "json"])
+        .output()
+        .unwrap();
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan["currency"], "EUR");
+    assert_eq!(plan["allocations"].as_array().unwrap().len(), 1);
+    assert_eq!(plan["allocations"][0]["amount"], 10.0);
+}
+
+#[test]
+fn budget_plan_weighted_by_installed_packages() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let mut packages = single_source_packages();
+    packages[0].funding = vec![FundingChannel::from_url(
+        "https://opencollective.com/firefox",
+    )];
+    seed_scan_packages(data.path(), &packages);
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "set", "10"])
+        .assert()
+        .success();
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "plan", "--strategy", "weighted"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("10.00 USD"))
+        .stdout(predicate::str::contains("installed package"));
+}
+
+#[test]
+fn budget_plan_respects_pins_and_exclusions() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data = tempfile::tempdir().unwrap();
+    let mut packages = single_source_packages();
+    packages[0].funding = vec![FundingChannel::from_url(
+        "https://opencollective.com/firefox",
+    )];
+    seed_scan_packages(data.path(), &packages);
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "set", "20"])
+        .assert()
+        .success();
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "pin", "https://kernel.org/", "--amount", "8"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Pinned kernel.org at 8.00 USD per month",
+        ));
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "plan", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"amount\": 8.0"))
+        .stdout(predicate::str::contains("\"amount\": 12.0"));
+
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "exclude", "https://www.mozilla.org/firefox/"])
+        .assert()
+        .success();
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pinned: kernel.org (8.00 USD)"))
+        .stdout(predicate::str::contains("Excluded: mozilla.org/firefox"));
+    syld_with_db(tmp.path(), data.path())
+        .args(["budget", "plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("firefox").not());
+}